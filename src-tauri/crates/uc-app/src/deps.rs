@@ -14,8 +14,9 @@ use std::sync::Arc;
 use tokio::sync::mpsc;
 use uc_core::ids::RepresentationId;
 use uc_core::ports::clipboard::{
-    ClipboardChangeOriginPort, ClipboardRepresentationNormalizerPort, RepresentationCachePort,
-    SpoolQueuePort, SystemClipboardPort, ThumbnailGeneratorPort, ThumbnailRepositoryPort,
+    ClipboardChangeOriginPort, ClipboardRepresentationNormalizerPort, ClipboardSearchIndexPort,
+    RemotePayloadRepositoryPort, RepresentationCachePort, SpoolQueuePort, SystemClipboardPort,
+    ThumbnailGeneratorPort, ThumbnailRepositoryPort,
 };
 use uc_core::ports::*;
 
@@ -37,10 +38,14 @@ pub struct AppDeps {
     pub representation_normalizer: Arc<dyn ClipboardRepresentationNormalizerPort>,
     pub selection_repo: Arc<dyn ClipboardSelectionRepositoryPort>,
     pub representation_policy: Arc<dyn SelectRepresentationPolicyPort>,
+    pub sync_filter_policy: Arc<dyn SyncFilterPolicyPort>,
     pub representation_cache: Arc<dyn RepresentationCachePort>,
     pub spool_queue: Arc<dyn SpoolQueuePort>,
     pub clipboard_change_origin: Arc<dyn ClipboardChangeOriginPort>,
+    pub search_index: Arc<dyn ClipboardSearchIndexPort>,
     pub worker_tx: mpsc::Sender<RepresentationId>,
+    pub payload_resolver: Arc<dyn ClipboardPayloadResolverPort>,
+    pub remote_payload_repo: Arc<dyn RemotePayloadRepositoryPort>,
 
     // Security dependencies / 安全依赖
     pub encryption: Arc<dyn EncryptionPort>,
@@ -49,6 +54,7 @@ pub struct AppDeps {
     pub key_scope: Arc<dyn uc_core::ports::security::key_scope::KeyScopePort>,
     pub secure_storage: Arc<dyn SecureStoragePort>,
     pub key_material: Arc<dyn KeyMaterialPort>,
+    pub payload_reencryption: Arc<dyn PayloadReencryptionPort>,
     pub watcher_control: Arc<dyn WatcherControlPort>,
 
     // Device dependencies / 设备依赖
@@ -58,9 +64,13 @@ pub struct AppDeps {
     // Pairing dependencies / 配对依赖
     pub paired_device_repo: Arc<dyn PairedDeviceRepositoryPort>,
 
+    // Space dependencies / 空间依赖
+    pub active_space: Arc<dyn uc_core::ports::space::ActiveSpacePort>,
+
     // Network dependencies / 网络依赖
     pub network: Arc<dyn NetworkPort>,
     pub network_control: Arc<dyn NetworkControlPort>,
+    pub outbound_sync_queue: Arc<dyn OutboundSyncQueuePort>,
 
     // Setup dependencies / 设置流程依赖
     pub setup_status: Arc<dyn SetupStatusPort>,
@@ -0,0 +1,585 @@
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+use uc_core::clipboard::{is_sensitive_format, ContentKind, RetentionCandidate};
+use uc_core::ids::EntryId;
+use uc_core::ports::{
+    ClipboardEntryRepositoryPort, ClipboardEventWriterPort, ClipboardSelectionRepositoryPort,
+    ClockPort, SettingsPort,
+};
+use uc_core::settings::model::{
+    RetentionDeleteMode, RetentionPolicy, RetentionRule, RuleEvaluation,
+};
+
+use super::DeleteClipboardEntry;
+
+/// How long soft-deleted entries are kept before they are purged for good.
+const SOFT_DELETE_GRACE_MS: i64 = 7 * 24 * 60 * 60 * 1000;
+
+/// Result of one retention pass.
+/// 一次保留策略执行的结果。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetentionReport {
+    pub removed_entry_ids: Vec<EntryId>,
+    pub freed_bytes: i64,
+    pub delete_mode: RetentionDeleteMode,
+    /// Soft-deleted entries past the grace period that were removed for good.
+    pub purged_count: usize,
+}
+
+impl RetentionReport {
+    fn empty(delete_mode: RetentionDeleteMode) -> Self {
+        Self {
+            removed_entry_ids: Vec::new(),
+            freed_bytes: 0,
+            delete_mode,
+            purged_count: 0,
+        }
+    }
+
+    pub fn removed_count(&self) -> usize {
+        self.removed_entry_ids.len()
+    }
+}
+
+/// Use case for enforcing the configured retention policy.
+/// 按设置中的保留策略清理过期剪贴板条目的用例。
+pub struct ApplyRetentionPolicy {
+    entry_repo: Arc<dyn ClipboardEntryRepositoryPort>,
+    selection_repo: Arc<dyn ClipboardSelectionRepositoryPort>,
+    event_writer: Arc<dyn ClipboardEventWriterPort>,
+    settings: Arc<dyn SettingsPort>,
+    clock: Arc<dyn ClockPort>,
+}
+
+impl ApplyRetentionPolicy {
+    pub fn new(
+        entry_repo: Arc<dyn ClipboardEntryRepositoryPort>,
+        selection_repo: Arc<dyn ClipboardSelectionRepositoryPort>,
+        event_writer: Arc<dyn ClipboardEventWriterPort>,
+        settings: Arc<dyn SettingsPort>,
+        clock: Arc<dyn ClockPort>,
+    ) -> Self {
+        Self {
+            entry_repo,
+            selection_repo,
+            event_writer,
+            settings,
+            clock,
+        }
+    }
+
+    /// Evaluate the retention rules and delete every matching entry.
+    ///
+    /// Hard deletion reuses [`DeleteClipboardEntry`]; a failure on one entry is
+    /// logged and does not stop the pass.
+    ///
+    /// Every pass first purges entries soft-deleted more than
+    /// `SOFT_DELETE_GRACE_MS` ago, even while the policy is disabled.
+    /// Their blobs are reclaimed by the next blob garbage collection.
+    #[tracing::instrument(name = "usecase.apply_retention_policy.execute", skip(self))]
+    pub async fn execute(&self) -> Result<RetentionReport> {
+        let policy = self.settings.load().await?.retention_policy;
+        let mut report = RetentionReport::empty(policy.delete_mode);
+        let now_ms = self.clock.now_ms();
+        report.purged_count = self.purge_soft_deleted(now_ms).await?;
+        if !policy.enabled || policy.rules.is_empty() {
+            return Ok(report);
+        }
+
+        let candidates = self.entry_repo.list_retention_candidates().await?;
+        let expired = select_expired(&policy, candidates, now_ms);
+        if expired.is_empty() {
+            return Ok(report);
+        }
+
+        match policy.delete_mode {
+            RetentionDeleteMode::Soft => {
+                let ids: Vec<EntryId> = expired.iter().map(|c| c.entry.entry_id.clone()).collect();
+                self.entry_repo.soft_delete_entries(&ids, now_ms).await?;
+                report.freed_bytes = expired.iter().map(|c| c.entry.total_size).sum();
+                report.removed_entry_ids = ids;
+            }
+            RetentionDeleteMode::Hard => {
+                let delete = DeleteClipboardEntry::from_ports(
+                    self.entry_repo.clone(),
+                    self.selection_repo.clone(),
+                    self.event_writer.clone(),
+                );
+                for candidate in expired {
+                    let entry_id = &candidate.entry.entry_id;
+                    match delete.execute(entry_id).await {
+                        Ok(()) => {
+                            report.freed_bytes += candidate.entry.total_size;
+                            report.removed_entry_ids.push(entry_id.clone());
+                        }
+                        Err(err) => {
+                            warn!(error = %err, entry_id = %entry_id, "Failed to delete expired entry");
+                        }
+                    }
+                }
+            }
+        }
+
+        info!(
+            removed = report.removed_count(),
+            freed_bytes = report.freed_bytes,
+            mode = ?report.delete_mode,
+            purged = report.purged_count,
+            "Retention policy applied"
+        );
+        Ok(report)
+    }
+
+    /// Hard-delete entries whose soft deletion is older than the grace period.
+    async fn purge_soft_deleted(&self, now_ms: i64) -> Result<usize> {
+        let entries = self
+            .entry_repo
+            .list_soft_deleted_entries(now_ms - SOFT_DELETE_GRACE_MS)
+            .await?;
+        if entries.is_empty() {
+            return Ok(0);
+        }
+
+        let delete = DeleteClipboardEntry::from_ports(
+            self.entry_repo.clone(),
+            self.selection_repo.clone(),
+            self.event_writer.clone(),
+        );
+        let mut purged = 0;
+        for entry in &entries {
+            match delete.delete_loaded(entry).await {
+                Ok(()) => purged += 1,
+                Err(err) => {
+                    warn!(error = %err, entry_id = %entry.entry_id, "Failed to purge soft-deleted entry");
+                }
+            }
+        }
+        info!(purged, "Purged soft-deleted entries past the grace period");
+        Ok(purged)
+    }
+}
+
+/// Select the candidates matched by the policy.
+///
+/// Count and size limits keep the most recently active entries, so candidates
+/// are ranked by `active_time_ms` (newest first). Pinned entries are left out
+/// of the ranking entirely when `skip_pinned` is set.
+fn select_expired(
+    policy: &RetentionPolicy,
+    mut candidates: Vec<RetentionCandidate>,
+    now_ms: i64,
+) -> Vec<RetentionCandidate> {
+    if policy.skip_pinned {
+        candidates.retain(|c| !c.entry.pinned);
+    }
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.entry.active_time_ms));
+
+    let mut cumulative_bytes: u64 = 0;
+    let mut expired = Vec::new();
+    for (rank, candidate) in candidates.into_iter().enumerate() {
+        cumulative_bytes += candidate.entry.total_size.max(0) as u64;
+        let age_ms = now_ms.saturating_sub(candidate.entry.active_time_ms).max(0) as u128;
+        let older_than = |max_age: &Duration| age_ms > max_age.as_millis();
+
+        let mut matches = policy.rules.iter().map(|rule| match rule {
+            RetentionRule::ByAge { max_age } => older_than(max_age),
+            RetentionRule::ByCount { max_items } => rank >= *max_items,
+            RetentionRule::ByContentType {
+                content_type,
+                max_age,
+            } => {
+                older_than(max_age)
+                    && candidate.representations.iter().any(|rep| {
+                        content_type.includes(ContentKind::classify(
+                            rep.mime_type.as_ref(),
+                            rep.format_id.as_ref(),
+                        ))
+                    })
+            }
+            RetentionRule::ByTotalSize { max_bytes } => cumulative_bytes > *max_bytes,
+            RetentionRule::Sensitive { max_age } => {
+                older_than(max_age)
+                    && candidate
+                        .representations
+                        .iter()
+                        .any(|rep| is_sensitive_format(rep.format_id.as_ref()))
+            }
+        });
+
+        let is_expired = match policy.evaluation {
+            RuleEvaluation::AnyMatch => matches.any(|m| m),
+            RuleEvaluation::AllMatch => matches.all(|m| m),
+        };
+        if is_expired {
+            expired.push(candidate);
+        }
+    }
+    expired
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+    use uc_core::clipboard::{
+        ClipboardEntry, ClipboardSelectionDecision, MimeType, RetentionRepresentation,
+    };
+    use uc_core::ids::{EventId, FormatId};
+    use uc_core::settings::model::{ContentTypes, Settings};
+
+    const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+    const NOW_MS: i64 = 100 * DAY_MS;
+
+    fn candidate(id: &str, age_days: i64, size: i64, pinned: bool) -> RetentionCandidate {
+        let mut entry = ClipboardEntry::new(
+            EntryId::from(id),
+            EventId::from(format!("event-{id}")),
+            NOW_MS - age_days * DAY_MS,
+            None,
+            size,
+        );
+        entry.pinned = pinned;
+        RetentionCandidate {
+            entry,
+            representations: vec![RetentionRepresentation {
+                format_id: FormatId::from("text"),
+                mime_type: Some(MimeType::text_plain()),
+            }],
+        }
+    }
+
+    fn policy(rules: Vec<RetentionRule>, evaluation: RuleEvaluation) -> RetentionPolicy {
+        RetentionPolicy {
+            enabled: true,
+            rules,
+            skip_pinned: true,
+            evaluation,
+            delete_mode: RetentionDeleteMode::Soft,
+        }
+    }
+
+    fn ids(expired: &[RetentionCandidate]) -> Vec<&str> {
+        expired.iter().map(|c| c.entry.entry_id.as_ref()).collect()
+    }
+
+    #[test]
+    fn any_match_combines_age_and_count_and_skips_pinned() {
+        let policy = policy(
+            vec![
+                RetentionRule::ByAge {
+                    max_age: Duration::from_secs(30 * 24 * 60 * 60),
+                },
+                RetentionRule::ByCount { max_items: 2 },
+            ],
+            RuleEvaluation::AnyMatch,
+        );
+        let candidates = vec![
+            candidate("new", 1, 10, false),
+            candidate("pinned-old", 60, 10, true),
+            candidate("mid", 2, 10, false),
+            candidate("third", 3, 10, false),
+            candidate("old", 40, 10, false),
+        ];
+
+        let expired = select_expired(&policy, candidates, NOW_MS);
+
+        assert_eq!(ids(&expired), vec!["third", "old"]);
+    }
+
+    #[test]
+    fn all_match_requires_every_rule() {
+        let policy = policy(
+            vec![
+                RetentionRule::ByAge {
+                    max_age: Duration::from_secs(24 * 60 * 60),
+                },
+                RetentionRule::ByTotalSize { max_bytes: 15 },
+            ],
+            RuleEvaluation::AllMatch,
+        );
+        let candidates = vec![
+            candidate("a", 0, 10, false),
+            candidate("b", 2, 10, false),
+            candidate("c", 0, 10, false),
+        ];
+
+        let expired = select_expired(&policy, candidates, NOW_MS);
+
+        // "c" is newer than "b" so the size limit is crossed at "b"; "c" is too young.
+        assert_eq!(ids(&expired), vec!["b"]);
+    }
+
+    #[test]
+    fn content_type_and_sensitive_rules_look_at_representations() {
+        let policy = policy(
+            vec![
+                RetentionRule::ByContentType {
+                    content_type: ContentTypes {
+                        image: true,
+                        ..ContentTypes::default()
+                    },
+                    max_age: Duration::from_secs(60),
+                },
+                RetentionRule::Sensitive {
+                    max_age: Duration::from_secs(60),
+                },
+            ],
+            RuleEvaluation::AnyMatch,
+        );
+        let mut image = candidate("image", 1, 10, false);
+        image.representations = vec![RetentionRepresentation {
+            format_id: FormatId::from("public.png"),
+            mime_type: Some(MimeType("image/png".to_string())),
+        }];
+        let mut secret = candidate("secret", 1, 10, false);
+        secret.representations.push(RetentionRepresentation {
+            format_id: FormatId::from("org.nspasteboard.ConcealedType"),
+            mime_type: None,
+        });
+        let text = candidate("text", 1, 10, false);
+
+        let expired = select_expired(&policy, vec![image, secret, text], NOW_MS);
+
+        assert_eq!(expired.len(), 2);
+        assert!(ids(&expired).contains(&"image"));
+        assert!(ids(&expired).contains(&"secret"));
+    }
+
+    struct MockEntryRepo {
+        candidates: Vec<RetentionCandidate>,
+        soft_deleted: Mutex<Vec<EntryId>>,
+        /// Previously soft-deleted entries with their `deleted_at_ms`
+        trashed: Vec<(ClipboardEntry, i64)>,
+        hard_deleted: Mutex<Vec<EntryId>>,
+    }
+
+    impl MockEntryRepo {
+        fn new(candidates: Vec<RetentionCandidate>) -> Self {
+            Self {
+                candidates,
+                soft_deleted: Mutex::new(Vec::new()),
+                trashed: Vec::new(),
+                hard_deleted: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ClipboardEntryRepositoryPort for MockEntryRepo {
+        async fn save_entry_and_selection(
+            &self,
+            _entry: &ClipboardEntry,
+            _selection: &ClipboardSelectionDecision,
+        ) -> Result<()> {
+            unimplemented!("Not used in tests")
+        }
+
+        async fn get_entry(&self, _entry_id: &EntryId) -> Result<Option<ClipboardEntry>> {
+            unimplemented!("Not used in tests")
+        }
+
+        async fn list_entries(&self, _limit: usize, _offset: usize) -> Result<Vec<ClipboardEntry>> {
+            unimplemented!("Not used in tests")
+        }
+
+        async fn delete_entry(&self, entry_id: &EntryId) -> Result<()> {
+            self.hard_deleted.lock().unwrap().push(entry_id.clone());
+            Ok(())
+        }
+
+        async fn list_retention_candidates(&self) -> Result<Vec<RetentionCandidate>> {
+            Ok(self.candidates.clone())
+        }
+
+        async fn soft_delete_entries(
+            &self,
+            entry_ids: &[EntryId],
+            _deleted_at_ms: i64,
+        ) -> Result<usize> {
+            self.soft_deleted
+                .lock()
+                .unwrap()
+                .extend_from_slice(entry_ids);
+            Ok(entry_ids.len())
+        }
+
+        async fn list_soft_deleted_entries(
+            &self,
+            deleted_before_ms: i64,
+        ) -> Result<Vec<ClipboardEntry>> {
+            Ok(self
+                .trashed
+                .iter()
+                .filter(|(_, deleted_at_ms)| *deleted_at_ms < deleted_before_ms)
+                .map(|(entry, _)| entry.clone())
+                .collect())
+        }
+
+        async fn get_entry_source_device(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+        ) -> anyhow::Result<Option<uc_core::ids::DeviceId>> {
+            Ok(None)
+        }
+
+        async fn set_entry_pinned(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+            _pinned: bool,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn list_pinned_entries(
+            &self,
+            _limit: usize,
+            _offset: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn list_catch_up_candidates(
+            &self,
+            _since_ms: i64,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::CatchUpCandidate>> {
+            Ok(Vec::new())
+        }
+    }
+
+    struct NoopSelectionRepo;
+
+    #[async_trait]
+    impl ClipboardSelectionRepositoryPort for NoopSelectionRepo {
+        async fn get_selection(
+            &self,
+            _entry_id: &EntryId,
+        ) -> Result<Option<ClipboardSelectionDecision>> {
+            unimplemented!("Not used in tests")
+        }
+
+        async fn delete_selection(&self, _entry_id: &EntryId) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    struct NoopEventWriter;
+
+    #[async_trait]
+    impl ClipboardEventWriterPort for NoopEventWriter {
+        async fn insert_event(
+            &self,
+            _event: &uc_core::clipboard::ClipboardEvent,
+            _representations: &Vec<uc_core::clipboard::PersistedClipboardRepresentation>,
+        ) -> Result<()> {
+            unimplemented!("Not used in tests")
+        }
+
+        async fn delete_event_and_representations(&self, _event_id: &EventId) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    struct MockSettings(Settings);
+
+    #[async_trait]
+    impl SettingsPort for MockSettings {
+        async fn load(&self) -> Result<Settings> {
+            Ok(self.0.clone())
+        }
+
+        async fn save(&self, _settings: &Settings) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    struct FixedClock;
+
+    impl ClockPort for FixedClock {
+        fn now_ms(&self) -> i64 {
+            NOW_MS
+        }
+    }
+
+    #[tokio::test]
+    async fn execute_soft_deletes_and_reports_expired_entries() {
+        let entry_repo = Arc::new(MockEntryRepo::new(vec![
+            candidate("fresh", 1, 10, false),
+            candidate("stale", 45, 32, false),
+        ]));
+        let mut settings = Settings::default();
+        settings.retention_policy.delete_mode = RetentionDeleteMode::Soft;
+
+        let usecase = ApplyRetentionPolicy::new(
+            entry_repo.clone(),
+            Arc::new(NoopSelectionRepo),
+            Arc::new(NoopEventWriter),
+            Arc::new(MockSettings(settings)),
+            Arc::new(FixedClock),
+        );
+
+        let report = usecase.execute().await.expect("apply retention");
+
+        assert_eq!(report.removed_count(), 1);
+        assert_eq!(report.freed_bytes, 32);
+        assert_eq!(
+            *entry_repo.soft_deleted.lock().unwrap(),
+            vec![EntryId::from("stale")]
+        );
+    }
+
+    #[tokio::test]
+    async fn execute_is_noop_when_policy_disabled() {
+        let entry_repo = Arc::new(MockEntryRepo::new(vec![candidate("stale", 45, 32, false)]));
+        let mut settings = Settings::default();
+        settings.retention_policy.enabled = false;
+
+        let usecase = ApplyRetentionPolicy::new(
+            entry_repo.clone(),
+            Arc::new(NoopSelectionRepo),
+            Arc::new(NoopEventWriter),
+            Arc::new(MockSettings(settings)),
+            Arc::new(FixedClock),
+        );
+
+        let report = usecase.execute().await.expect("apply retention");
+
+        assert_eq!(report.removed_count(), 0);
+        assert!(entry_repo.soft_deleted.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn execute_purges_soft_deleted_entries_past_grace_period() {
+        let mut entry_repo = MockEntryRepo::new(Vec::new());
+        entry_repo.trashed = vec![
+            (candidate("recent", 50, 10, false).entry, NOW_MS - DAY_MS),
+            (
+                candidate("expired", 50, 10, false).entry,
+                NOW_MS - 8 * DAY_MS,
+            ),
+        ];
+        let entry_repo = Arc::new(entry_repo);
+        let mut settings = Settings::default();
+        settings.retention_policy.enabled = false;
+
+        let usecase = ApplyRetentionPolicy::new(
+            entry_repo.clone(),
+            Arc::new(NoopSelectionRepo),
+            Arc::new(NoopEventWriter),
+            Arc::new(MockSettings(settings)),
+            Arc::new(FixedClock),
+        );
+
+        let report = usecase.execute().await.expect("apply retention");
+
+        assert_eq!(report.purged_count, 1);
+        assert_eq!(report.removed_count(), 0);
+        assert_eq!(
+            *entry_repo.hard_deleted.lock().unwrap(),
+            vec![EntryId::from("expired")]
+        );
+    }
+}
@@ -14,6 +14,8 @@ use uc_core::{
     security::{model::EncryptionError, state::EncryptionState},
 };
 
+use super::keyslot_sync::unwrap_older_keys;
+
 #[derive(Debug, thiserror::Error)]
 pub enum AutoUnlockError {
     #[error("encryption state check failed: {0}")]
@@ -130,6 +132,7 @@ impl AutoUnlockEncryptionSession {
             // 4. Get wrapped master key
             let wrapped_master_key = keyslot
                 .wrapped_master_key
+                .clone()
                 .ok_or(AutoUnlockError::MissingWrappedMasterKey)?;
 
             // 5. Load KEK from keyring
@@ -146,9 +149,14 @@ impl AutoUnlockEncryptionSession {
                 .await
                 .map_err(AutoUnlockError::UnwrapFailed)?;
 
-            // 7. Set master key in session
+            // 7. Unwrap the keys of older epochs that still seal stored data
+            let older_keys = unwrap_older_keys(self.encryption.as_ref(), &keyslot, &kek)
+                .await
+                .map_err(AutoUnlockError::UnwrapFailed)?;
+
+            // 8. Set master key (and key epoch) in session
             self.encryption_session
-                .set_master_key(master_key)
+                .set_epoch_keys(keyslot.epoch, master_key, older_keys)
                 .await
                 .map_err(AutoUnlockError::SessionSetFailed)?;
 
@@ -325,6 +333,7 @@ mod tests {
                 nonce: vec![0u8; 24],
                 ciphertext: vec![0u8; 32],
                 aad_fingerprint: None,
+                key_epoch: 0,
             })
         }
 
@@ -352,6 +361,7 @@ mod tests {
                 nonce: vec![0u8; 24],
                 ciphertext: vec![],
                 aad_fingerprint: None,
+                key_epoch: 0,
             })
         }
 
@@ -422,6 +432,38 @@ mod tests {
                 .store(false, std::sync::atomic::Ordering::SeqCst);
             Ok(())
         }
+
+        async fn get_current_key(
+            &self,
+        ) -> Result<
+            (
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            ),
+            uc_core::security::model::EncryptionError,
+        > {
+            Ok((0, self.get_master_key().await?))
+        }
+
+        async fn get_master_key_for_epoch(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+        ) -> Result<uc_core::security::model::MasterKey, uc_core::security::model::EncryptionError>
+        {
+            self.get_master_key().await
+        }
+
+        async fn set_epoch_keys(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+            master_key: uc_core::security::model::MasterKey,
+            _older: Vec<(
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            )>,
+        ) -> Result<(), uc_core::security::model::EncryptionError> {
+            self.set_master_key(master_key).await
+        }
     }
 
     /// Creates a valid test keyslot with wrapped master key
@@ -431,6 +473,10 @@ mod tests {
             scope,
             kdf: uc_core::security::model::KdfParams::for_initialization(),
             salt: vec![0u8; 16],
+            epoch: 0,
+            revision: 0,
+            retired_master_key: None,
+            retained_master_keys: Vec::new(),
             wrapped_master_key: Some(WrappedMasterKey {
                 blob: EncryptedBlob {
                     version: EncryptionFormatVersion::V1,
@@ -438,6 +484,7 @@ mod tests {
                     nonce: vec![0u8; 24],
                     ciphertext: vec![0u8; 32],
                     aad_fingerprint: None,
+                    key_epoch: 0,
                 },
             }),
         }
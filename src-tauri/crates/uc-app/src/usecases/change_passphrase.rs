@@ -1 +1,432 @@
+use std::sync::Arc;
+use tracing::{debug, info, info_span, warn, Instrument};
 
+use uc_core::{
+    ports::{
+        security::{
+            encryption_state::EncryptionStatePort,
+            key_scope::{KeyScopePort, ScopeError},
+        },
+        DeviceIdentityPort, EncryptionPort, KeyMaterialPort, NetworkPort,
+        PairedDeviceRepositoryPort,
+    },
+    security::{
+        model::{EncryptionAlgo, EncryptionError, KeySlot, Passphrase, WrappedMasterKey},
+        state::{EncryptionState, EncryptionStateError},
+    },
+};
+
+use super::keyslot_sync::{replace_key_material, rewrap_older_keys, KeySlotPublisher};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ChangePassphraseError {
+    #[error("encryption is not initialized")]
+    NotInitialized,
+
+    #[error("wrong passphrase")]
+    WrongPassphrase,
+
+    #[error("new passphrase must differ from the current one")]
+    PassphraseUnchanged,
+
+    #[error("keyslot is missing the wrapped master key")]
+    MissingWrappedMasterKey,
+
+    #[error("failed to rewrap master key")]
+    EncryptionFailed(#[from] EncryptionError),
+
+    #[error("failed to update keyring, keyslot was restored")]
+    KeyringUpdateFailed(#[source] EncryptionError),
+
+    #[error("failed to load encryption state")]
+    StateLoadFailed(#[from] EncryptionStateError),
+
+    #[error("failed to resolve key scope")]
+    ScopeFailed(#[from] ScopeError),
+}
+
+/// Use case for changing the encryption passphrase.
+/// 修改加密口令的用例。
+///
+/// # Behavior / 行为
+/// - 1. Verify the old passphrase by unwrapping the `MasterKey` (校验旧口令)
+/// - 2. Derive a new KEK with fresh salt and `KdfParams` (派生新 KEK)
+/// - 3. Rewrap the `MasterKey` and replace the `KeySlot` (重新封装并替换密钥槽)
+/// - 4. Replace the KEK in secure storage; restore the old keyslot on failure (同步自动解锁密钥)
+/// - 5. Push the new keyslot to connected trusted peers, best effort (同步到受信任设备)
+///
+/// The `MasterKey` itself does not change, so existing clipboard data stays readable.
+///
+/// `MasterKey` 本身不变，已有剪贴板数据仍可读取。
+pub struct ChangePassphrase {
+    encryption: Arc<dyn EncryptionPort>,
+    key_material: Arc<dyn KeyMaterialPort>,
+    key_scope: Arc<dyn KeyScopePort>,
+    encryption_state_repo: Arc<dyn EncryptionStatePort>,
+    publisher: KeySlotPublisher,
+}
+
+impl ChangePassphrase {
+    pub fn new(
+        encryption: Arc<dyn EncryptionPort>,
+        key_material: Arc<dyn KeyMaterialPort>,
+        key_scope: Arc<dyn KeyScopePort>,
+        encryption_state_repo: Arc<dyn EncryptionStatePort>,
+        paired_device_repo: Arc<dyn PairedDeviceRepositoryPort>,
+        network: Arc<dyn NetworkPort>,
+        device_identity: Arc<dyn DeviceIdentityPort>,
+    ) -> Self {
+        Self {
+            publisher: KeySlotPublisher::new(
+                encryption.clone(),
+                paired_device_repo,
+                network,
+                device_identity,
+            ),
+            encryption,
+            key_material,
+            key_scope,
+            encryption_state_repo,
+        }
+    }
+
+    /// Change the passphrase and return how many peers the new keyslot was sent to.
+    pub async fn execute(
+        &self,
+        old_passphrase: Passphrase,
+        new_passphrase: Passphrase,
+    ) -> Result<usize, ChangePassphraseError> {
+        let span = info_span!("usecase.change_passphrase.execute");
+
+        async {
+            if old_passphrase.as_bytes() == new_passphrase.as_bytes() {
+                return Err(ChangePassphraseError::PassphraseUnchanged);
+            }
+
+            let state = self.encryption_state_repo.load_state().await?;
+            if state != EncryptionState::Initialized {
+                return Err(ChangePassphraseError::NotInitialized);
+            }
+
+            let scope = self.key_scope.current_scope().await?;
+            let old_keyslot = self.key_material.load_keyslot(&scope).await?;
+            let wrapped = old_keyslot
+                .wrapped_master_key
+                .as_ref()
+                .ok_or(ChangePassphraseError::MissingWrappedMasterKey)?;
+
+            // 1. verify old passphrase
+            debug!("Verifying old passphrase");
+            let old_kek = self
+                .encryption
+                .derive_kek(&old_passphrase, &old_keyslot.salt, &old_keyslot.kdf)
+                .await?;
+            let master_key = self
+                .encryption
+                .unwrap_master_key(&old_kek, &wrapped.blob)
+                .await
+                .map_err(|err| match err {
+                    EncryptionError::WrongPassphrase => ChangePassphraseError::WrongPassphrase,
+                    other => ChangePassphraseError::EncryptionFailed(other),
+                })?;
+
+            // 2. derive new KEK with fresh salt and params
+            debug!("Deriving new KEK");
+            let keyslot_draft = KeySlot::draft_v1(scope.clone())?;
+            let new_kek = self
+                .encryption
+                .derive_kek(&new_passphrase, &keyslot_draft.salt, &keyslot_draft.kdf)
+                .await?;
+
+            // 3. rewrap MasterKey and the keys of older epochs
+            let blob = self
+                .encryption
+                .wrap_master_key(&new_kek, &master_key, EncryptionAlgo::XChaCha20Poly1305)
+                .await?;
+            let (retired_master_key, retained_master_keys) =
+                rewrap_older_keys(self.encryption.as_ref(), &old_keyslot, &old_kek, &new_kek)
+                    .await?;
+            let new_keyslot = KeySlot {
+                epoch: old_keyslot.epoch,
+                revision: old_keyslot.revision.saturating_add(1),
+                retired_master_key,
+                retained_master_keys,
+                ..keyslot_draft.finalize(WrappedMasterKey { blob })
+            };
+
+            // 4. replace keyslot and keyring KEK
+            replace_key_material(
+                self.key_material.as_ref(),
+                &scope,
+                &old_keyslot,
+                &new_keyslot,
+                &new_kek,
+            )
+            .await
+            .map_err(ChangePassphraseError::KeyringUpdateFailed)?;
+            info!("Passphrase changed");
+
+            // 5. propagate to peers
+            let propagated = match self
+                .publisher
+                .publish(old_keyslot.epoch, &master_key, &new_keyslot)
+                .await
+            {
+                Ok(count) => count,
+                Err(err) => {
+                    warn!(error = %err, "Failed to propagate keyslot update to peers");
+                    0
+                }
+            };
+
+            Ok(propagated)
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::usecases::keyslot_sync::test_support::{
+        build_apply_and_confirm, seed_keyslot, trusted, InMemoryKeyMaterial, InitializedState,
+        TestDeviceIdentity, TestKeyScope,
+    };
+    use crate::usecases::keyslot_sync::ConfirmKeySlotUpdateError;
+    use uc_core::network::ProtocolMessage;
+    use uc_core::ports::EncryptionSessionPort;
+    use uc_core::security::{
+        aad,
+        model::{EncryptedBlob, MasterKey},
+    };
+    use uc_core::testing::{InMemoryPairedDeviceRepository, TestNetwork};
+    use uc_infra::security::{EncryptionRepository, InMemoryEncryptionSession};
+
+    /// Unwrap the stored keyslot with `passphrase`.
+    async fn unlock(
+        key_material: &InMemoryKeyMaterial,
+        passphrase: &str,
+    ) -> Result<MasterKey, EncryptionError> {
+        let encryption = EncryptionRepository;
+        let keyslot = key_material.keyslot.lock().unwrap().clone().unwrap();
+        let kek = encryption
+            .derive_kek(
+                &Passphrase(passphrase.to_string()),
+                &keyslot.salt,
+                &keyslot.kdf,
+            )
+            .await?;
+        encryption
+            .unwrap_master_key(&kek, &keyslot.wrapped_master_key.unwrap().blob)
+            .await
+    }
+
+    fn build_use_case(
+        key_material: Arc<InMemoryKeyMaterial>,
+        network: Arc<TestNetwork>,
+    ) -> ChangePassphrase {
+        ChangePassphrase::new(
+            Arc::new(EncryptionRepository),
+            key_material,
+            Arc::new(TestKeyScope("local")),
+            Arc::new(InitializedState),
+            Arc::new(InMemoryPairedDeviceRepository::with_devices(vec![trusted(
+                "peer-1",
+            )])),
+            network,
+            Arc::new(TestDeviceIdentity),
+        )
+    }
+
+    #[tokio::test]
+    async fn change_passphrase_rewraps_keyslot_and_updates_kek() {
+        let master_key = MasterKey::generate().unwrap();
+        let key_material = Arc::new(InMemoryKeyMaterial::default());
+        seed_keyslot(&key_material, "local", "old-pass", &master_key).await;
+        let old_salt = key_material.keyslot.lock().unwrap().clone().unwrap().salt;
+        let uc = build_use_case(key_material.clone(), Arc::new(TestNetwork::new()));
+
+        let propagated = uc
+            .execute(
+                Passphrase("old-pass".to_string()),
+                Passphrase("new-pass".to_string()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(propagated, 0);
+        assert_eq!(unlock(&key_material, "new-pass").await.unwrap(), master_key);
+        assert!(matches!(
+            unlock(&key_material, "old-pass").await,
+            Err(EncryptionError::WrongPassphrase)
+        ));
+        let keyslot = key_material.keyslot.lock().unwrap().clone().unwrap();
+        assert_ne!(keyslot.salt, old_salt, "salt should be regenerated");
+
+        let stored_kek = key_material.kek.lock().unwrap().clone().unwrap();
+        let from_keyring = EncryptionRepository
+            .unwrap_master_key(&stored_kek, &keyslot.wrapped_master_key.unwrap().blob)
+            .await
+            .unwrap();
+        assert_eq!(
+            from_keyring, master_key,
+            "keyring KEK must match new keyslot"
+        );
+    }
+
+    #[tokio::test]
+    async fn change_passphrase_rejects_wrong_old_passphrase() {
+        let master_key = MasterKey::generate().unwrap();
+        let key_material = Arc::new(InMemoryKeyMaterial::default());
+        seed_keyslot(&key_material, "local", "old-pass", &master_key).await;
+        let before = key_material.keyslot.lock().unwrap().clone();
+        let uc = build_use_case(key_material.clone(), Arc::new(TestNetwork::new()));
+
+        let result = uc
+            .execute(
+                Passphrase("not-it".to_string()),
+                Passphrase("new-pass".to_string()),
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ChangePassphraseError::WrongPassphrase)
+        ));
+        assert_eq!(*key_material.keyslot.lock().unwrap(), before);
+    }
+
+    #[tokio::test]
+    async fn change_passphrase_restores_keyslot_when_keyring_write_fails() {
+        let master_key = MasterKey::generate().unwrap();
+        let key_material = Arc::new(InMemoryKeyMaterial {
+            fail_store_kek: true,
+            ..Default::default()
+        });
+        seed_keyslot(&key_material, "local", "old-pass", &master_key).await;
+        let uc = build_use_case(key_material.clone(), Arc::new(TestNetwork::new()));
+
+        let result = uc
+            .execute(
+                Passphrase("old-pass".to_string()),
+                Passphrase("new-pass".to_string()),
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ChangePassphraseError::KeyringUpdateFailed(_))
+        ));
+        assert_eq!(unlock(&key_material, "old-pass").await.unwrap(), master_key);
+    }
+
+    #[tokio::test]
+    async fn keyslot_update_does_not_carry_the_kek() {
+        let master_key = MasterKey::generate().unwrap();
+        let local = Arc::new(InMemoryKeyMaterial::default());
+        seed_keyslot(&local, "local", "old-pass", &master_key).await;
+        let network = Arc::new(TestNetwork::new().with_connected(["peer-1"]));
+        build_use_case(local, network.clone())
+            .execute(
+                Passphrase("old-pass".to_string()),
+                Passphrase("new-pass".to_string()),
+            )
+            .await
+            .unwrap();
+
+        let (_, payload) = network.sent_clipboard().pop().unwrap();
+        let message = match ProtocolMessage::from_bytes(&payload).unwrap() {
+            ProtocolMessage::KeySlotUpdate(message) => message,
+            other => panic!("unexpected message: {other:?}"),
+        };
+        let encrypted: EncryptedBlob = serde_json::from_slice(&message.encrypted_payload).unwrap();
+        let plaintext = EncryptionRepository
+            .decrypt_blob(
+                &master_key,
+                &encrypted,
+                &aad::for_keyslot_update(&message.id),
+            )
+            .await
+            .unwrap();
+        let fields: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_slice(&plaintext).unwrap();
+        assert_eq!(fields.keys().collect::<Vec<_>>(), vec!["keyslot"]);
+    }
+
+    #[tokio::test]
+    async fn keyslot_update_waits_for_passphrase_confirmation() {
+        let master_key = MasterKey::generate().unwrap();
+        let local = Arc::new(InMemoryKeyMaterial::default());
+        seed_keyslot(&local, "local", "old-pass", &master_key).await;
+        let network = Arc::new(TestNetwork::new().with_connected(["peer-1"]));
+        let uc = build_use_case(local, network.clone());
+
+        let propagated = uc
+            .execute(
+                Passphrase("old-pass".to_string()),
+                Passphrase("new-pass".to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(propagated, 1);
+
+        let (peer_id, payload) = network.sent_clipboard().pop().unwrap();
+        assert_eq!(peer_id, "peer-1");
+        let message = match ProtocolMessage::from_bytes(&payload).unwrap() {
+            ProtocolMessage::KeySlotUpdate(message) => message,
+            other => panic!("unexpected message: {other:?}"),
+        };
+
+        // Receiving side: a peer in its own scope holding the same master key.
+        let remote = Arc::new(InMemoryKeyMaterial::default());
+        seed_keyslot(&remote, "remote", "old-pass", &master_key).await;
+        let remote_kek = remote.kek.lock().unwrap().clone();
+        let session = Arc::new(InMemoryEncryptionSession::new());
+        session.set_master_key(master_key.clone()).await.unwrap();
+        let (apply, confirm) =
+            build_apply_and_confirm(session, remote.clone(), "device-local-peer");
+
+        assert!(
+            apply.execute("stranger", message.clone()).await.is_err(),
+            "updates from untrusted peers must be rejected"
+        );
+        let staged = apply
+            .execute("device-local-peer", message.clone())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!((staged.epoch, staged.revision), (0, 1));
+
+        // Staging alone changes nothing.
+        assert_eq!(unlock(&remote, "old-pass").await.unwrap(), master_key);
+        assert_eq!(*remote.kek.lock().unwrap(), remote_kek);
+
+        assert!(matches!(
+            confirm.execute(Passphrase("not-it".to_string())).await,
+            Err(ConfirmKeySlotUpdateError::WrongPassphrase)
+        ));
+        confirm
+            .execute(Passphrase("new-pass".to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(unlock(&remote, "new-pass").await.unwrap(), master_key);
+        let keyslot = remote.keyslot.lock().unwrap().clone().unwrap();
+        assert_eq!(keyslot.scope.profile_id, "remote");
+        assert_eq!(keyslot.revision, 1);
+
+        assert!(
+            apply
+                .execute("device-local-peer", message)
+                .await
+                .unwrap()
+                .is_none(),
+            "replayed updates must be ignored"
+        );
+        assert!(matches!(
+            confirm.execute(Passphrase("new-pass".to_string())).await,
+            Err(ConfirmKeySlotUpdateError::NothingPending)
+        ));
+    }
+}
@@ -0,0 +1,245 @@
+use anyhow::Result;
+use std::sync::Arc;
+use tracing::{info, warn};
+use uc_core::ids::EntryId;
+use uc_core::ports::{
+    ClipboardEntryRepositoryPort, ClipboardEventWriterPort, ClipboardSelectionRepositoryPort,
+};
+
+use crate::usecases::DeleteClipboardEntry;
+
+const CLEAR_PAGE_SIZE: usize = 200;
+
+/// Use case for clearing clipboard history while keeping pinned entries.
+/// 清空剪贴板历史（保留置顶条目）的用例。
+pub struct ClearClipboardHistory {
+    entry_repo: Arc<dyn ClipboardEntryRepositoryPort>,
+    delete_entry: DeleteClipboardEntry,
+}
+
+impl ClearClipboardHistory {
+    pub fn from_ports(
+        entry_repo: Arc<dyn ClipboardEntryRepositoryPort>,
+        selection_repo: Arc<dyn ClipboardSelectionRepositoryPort>,
+        event_writer: Arc<dyn ClipboardEventWriterPort>,
+    ) -> Self {
+        Self {
+            delete_entry: DeleteClipboardEntry::from_ports(
+                entry_repo.clone(),
+                selection_repo,
+                event_writer,
+            ),
+            entry_repo,
+        }
+    }
+
+    /// Delete every unpinned entry and return the ids that were removed.
+    ///
+    /// Entries that fail to delete are logged and kept; they are skipped on
+    /// the following pages so the pass always terminates.
+    #[tracing::instrument(name = "usecase.clear_clipboard_history.execute", skip(self))]
+    pub async fn execute(&self) -> Result<Vec<EntryId>> {
+        let mut removed = Vec::new();
+        // Entries that stay in place (pinned or failed) shift the next page.
+        let mut kept = 0;
+
+        loop {
+            let entries = self.entry_repo.list_entries(CLEAR_PAGE_SIZE, kept).await?;
+            let page_len = entries.len();
+
+            for entry in entries {
+                if entry.pinned {
+                    kept += 1;
+                    continue;
+                }
+                match self.delete_entry.execute(&entry.entry_id).await {
+                    Ok(()) => removed.push(entry.entry_id),
+                    Err(err) => {
+                        warn!(entry_id = %entry.entry_id, error = %err, "Failed to clear clipboard entry");
+                        kept += 1;
+                    }
+                }
+            }
+
+            if page_len < CLEAR_PAGE_SIZE {
+                break;
+            }
+        }
+
+        info!(removed = removed.len(), kept, "Cleared clipboard history");
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+    use uc_core::clipboard::{ClipboardEntry, ClipboardSelectionDecision};
+    use uc_core::ids::EventId;
+
+    struct InMemoryEntryRepo {
+        entries: Mutex<Vec<ClipboardEntry>>,
+    }
+
+    #[async_trait]
+    impl ClipboardEntryRepositoryPort for InMemoryEntryRepo {
+        async fn save_entry_and_selection(
+            &self,
+            _entry: &ClipboardEntry,
+            _selection: &ClipboardSelectionDecision,
+        ) -> Result<()> {
+            unimplemented!("Not used in tests")
+        }
+
+        async fn get_entry(&self, entry_id: &EntryId) -> Result<Option<ClipboardEntry>> {
+            Ok(self
+                .entries
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|e| &e.entry_id == entry_id)
+                .cloned())
+        }
+
+        async fn list_entries(&self, limit: usize, offset: usize) -> Result<Vec<ClipboardEntry>> {
+            Ok(self
+                .entries
+                .lock()
+                .unwrap()
+                .iter()
+                .skip(offset)
+                .take(limit)
+                .cloned()
+                .collect())
+        }
+
+        async fn delete_entry(&self, entry_id: &EntryId) -> Result<()> {
+            self.entries
+                .lock()
+                .unwrap()
+                .retain(|e| &e.entry_id != entry_id);
+            Ok(())
+        }
+
+        async fn list_retention_candidates(
+            &self,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::RetentionCandidate>> {
+            Ok(Vec::new())
+        }
+
+        async fn soft_delete_entries(
+            &self,
+            _entry_ids: &[uc_core::ids::EntryId],
+            _deleted_at_ms: i64,
+        ) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_soft_deleted_entries(
+            &self,
+            _deleted_before_ms: i64,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_entry_source_device(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+        ) -> anyhow::Result<Option<uc_core::ids::DeviceId>> {
+            Ok(None)
+        }
+
+        async fn set_entry_pinned(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+            _pinned: bool,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn list_pinned_entries(
+            &self,
+            _limit: usize,
+            _offset: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn list_catch_up_candidates(
+            &self,
+            _since_ms: i64,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::CatchUpCandidate>> {
+            Ok(Vec::new())
+        }
+    }
+
+    struct NoopSelectionRepo;
+
+    #[async_trait]
+    impl ClipboardSelectionRepositoryPort for NoopSelectionRepo {
+        async fn get_selection(
+            &self,
+            _entry_id: &EntryId,
+        ) -> Result<Option<ClipboardSelectionDecision>> {
+            Ok(None)
+        }
+
+        async fn delete_selection(&self, _entry_id: &EntryId) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    struct NoopEventWriter;
+
+    #[async_trait]
+    impl ClipboardEventWriterPort for NoopEventWriter {
+        async fn insert_event(
+            &self,
+            _event: &uc_core::clipboard::ClipboardEvent,
+            _representations: &Vec<uc_core::clipboard::PersistedClipboardRepresentation>,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn delete_event_and_representations(&self, _event_id: &EventId) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn clears_unpinned_entries_across_pages() {
+        let entries: Vec<ClipboardEntry> = (0..(CLEAR_PAGE_SIZE * 2 + 5))
+            .map(|i| {
+                let mut entry = ClipboardEntry::new(
+                    EntryId::from(format!("entry-{i}")),
+                    EventId::from(format!("event-{i}")),
+                    i as i64,
+                    None,
+                    1,
+                );
+                entry.pinned = i % 50 == 0;
+                entry
+            })
+            .collect();
+        let pinned_count = entries.iter().filter(|e| e.pinned).count();
+        let total = entries.len();
+        let repo = Arc::new(InMemoryEntryRepo {
+            entries: Mutex::new(entries),
+        });
+
+        let uc = ClearClipboardHistory::from_ports(
+            repo.clone(),
+            Arc::new(NoopSelectionRepo),
+            Arc::new(NoopEventWriter),
+        );
+        let removed = uc.execute().await.expect("clear");
+
+        assert_eq!(removed.len(), total - pinned_count);
+        let remaining = repo.entries.lock().unwrap();
+        assert_eq!(remaining.len(), pinned_count);
+        assert!(remaining.iter().all(|e| e.pinned));
+    }
+}
@@ -0,0 +1,816 @@
+use anyhow::{anyhow, Result};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{debug, info, info_span, warn, Instrument};
+
+use uc_core::clipboard::{CatchUpCandidate, ObservedClipboardRepresentation};
+use uc_core::network::{CatchUpRequest, PairingState, ProtocolFeature, ProtocolMessage};
+use uc_core::ports::{
+    BlobStorePort, ClipboardEntryRepositoryPort, ClipboardPayloadResolverPort,
+    ClipboardRepresentationRepositoryPort, ClipboardSelectionRepositoryPort, ClockPort,
+    DeviceIdentityPort, NetworkPort, PairedDeviceRepositoryPort, ResolvedClipboardPayload,
+    SettingsPort,
+};
+use uc_core::{PeerId, SystemClipboardSnapshot};
+
+use super::sync_outbound::SyncOutboundClipboardUseCase;
+
+/// Upper bound on history entries summarised or resent per catch-up.
+///
+/// 单次补同步最多比对和补发的条目数。
+const MAX_CATCH_UP_ENTRIES: usize = 200;
+
+/// Reconcile clipboard history with a trusted peer that was offline.
+///
+/// 与离线后重新上线的受信任设备对齐剪贴板历史。
+///
+/// # Behavior / 行为
+/// - [`Self::request`]: when a peer becomes ready, send it the snapshot hashes
+///   of every entry kept within `sync.catch_up_window_secs` (发送窗口内历史摘要);
+///   peers that have not negotiated [`ProtocolFeature::CatchUp`] are skipped
+/// - [`Self::respond`]: on a peer's request, resend the entries this device
+///   captured itself that are missing from the peer's summary (补发对端缺失的本机条目)
+///
+/// Only entries whose source device is this device are resent, so history
+/// never echoes back to the device it came from. Resent entries pass through
+/// the current sync filters and arrive flagged as catch-up, so the receiver
+/// only adds them to history.
+///
+/// 只补发本机捕获的条目，不会回传给来源设备；补发内容同样经过当前同步过滤，接收方只写入历史。
+pub struct CatchUpClipboardHistory {
+    entry_repo: Arc<dyn ClipboardEntryRepositoryPort>,
+    selection_repo: Arc<dyn ClipboardSelectionRepositoryPort>,
+    representation_repo: Arc<dyn ClipboardRepresentationRepositoryPort>,
+    blob_store: Arc<dyn BlobStorePort>,
+    payload_resolver: Arc<dyn ClipboardPayloadResolverPort>,
+    paired_device_repo: Arc<dyn PairedDeviceRepositoryPort>,
+    network: Arc<dyn NetworkPort>,
+    device_identity: Arc<dyn DeviceIdentityPort>,
+    settings: Arc<dyn SettingsPort>,
+    clock: Arc<dyn ClockPort>,
+    outbound: SyncOutboundClipboardUseCase,
+}
+
+impl CatchUpClipboardHistory {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        entry_repo: Arc<dyn ClipboardEntryRepositoryPort>,
+        selection_repo: Arc<dyn ClipboardSelectionRepositoryPort>,
+        representation_repo: Arc<dyn ClipboardRepresentationRepositoryPort>,
+        blob_store: Arc<dyn BlobStorePort>,
+        payload_resolver: Arc<dyn ClipboardPayloadResolverPort>,
+        paired_device_repo: Arc<dyn PairedDeviceRepositoryPort>,
+        network: Arc<dyn NetworkPort>,
+        device_identity: Arc<dyn DeviceIdentityPort>,
+        settings: Arc<dyn SettingsPort>,
+        clock: Arc<dyn ClockPort>,
+        outbound: SyncOutboundClipboardUseCase,
+    ) -> Self {
+        Self {
+            entry_repo,
+            selection_repo,
+            representation_repo,
+            blob_store,
+            payload_resolver,
+            paired_device_repo,
+            network,
+            device_identity,
+            settings,
+            clock,
+            outbound,
+        }
+    }
+
+    /// Ask `peer_id` for the history this device missed.
+    ///
+    /// 向对端发送本机近期历史摘要，请求补发缺失条目。
+    ///
+    /// # Returns / 返回值
+    /// - `false` when the peer is not trusted, has not negotiated catch-up, or
+    ///   catch-up is switched off
+    /// - 对端不受信任、未协商补同步特性或补同步已关闭时返回 `false`
+    pub async fn request(&self, peer_id: &str) -> Result<bool> {
+        let span = info_span!("usecase.catch_up_clipboard_history.request", peer_id = %peer_id);
+        async move {
+            if !self.is_trusted(peer_id).await? {
+                return Ok(false);
+            }
+            if !self.supports_catch_up(peer_id).await? {
+                debug!("Peer has not negotiated clipboard catch-up, skipping request");
+                return Ok(false);
+            }
+            let Some(since_ms) = self.window_start().await? else {
+                debug!("Clipboard catch-up disabled, skipping request");
+                return Ok(false);
+            };
+
+            let known_hashes: Vec<String> = self
+                .entry_repo
+                .list_catch_up_candidates(since_ms, MAX_CATCH_UP_ENTRIES)
+                .await?
+                .into_iter()
+                .map(|candidate| candidate.snapshot_hash)
+                .collect();
+            let known_count = known_hashes.len();
+            let payload = ProtocolMessage::CatchUp(CatchUpRequest {
+                origin_device_id: self.device_identity.current_device_id().to_string(),
+                since_ms,
+                known_hashes,
+            })
+            .to_bytes()?;
+            self.network.send_clipboard(peer_id, payload).await?;
+
+            info!(known = known_count, "Clipboard catch-up requested");
+            Ok(true)
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Resend the entries `peer_id` reported missing.
+    ///
+    /// 根据对端摘要补发其缺失的本机条目（按捕获顺序）。
+    ///
+    /// # Returns / 返回值
+    /// - Number of entries resent
+    /// - 补发的条目数量
+    pub async fn respond(&self, peer_id: &str, request: CatchUpRequest) -> Result<usize> {
+        let span = info_span!(
+            "usecase.catch_up_clipboard_history.respond",
+            peer_id = %peer_id,
+            known = request.known_hashes.len(),
+        );
+        async move {
+            if !self.is_trusted(peer_id).await? {
+                return Err(anyhow!("catch-up request from untrusted peer"));
+            }
+            let Some(window_start) = self.window_start().await? else {
+                debug!("Clipboard catch-up disabled, ignoring request");
+                return Ok(0);
+            };
+
+            let since_ms = request.since_ms.max(window_start);
+            let local_device = self.device_identity.current_device_id();
+            let known: HashSet<String> = request.known_hashes.into_iter().collect();
+            let mut candidates: Vec<CatchUpCandidate> = self
+                .entry_repo
+                .list_catch_up_candidates(since_ms, MAX_CATCH_UP_ENTRIES)
+                .await?
+                .into_iter()
+                .filter(|candidate| {
+                    candidate.source_device == local_device
+                        && candidate.entry.sync_skip_reason.is_none()
+                })
+                .collect();
+            candidates.reverse();
+
+            let mut sent = 0;
+            for candidate in candidates {
+                let entry_id = candidate.entry.entry_id.clone();
+                let snapshot = match self.build_snapshot(&candidate).await {
+                    Ok(snapshot) => snapshot,
+                    Err(err) => {
+                        warn!(entry_id = %entry_id, error = %err, "Failed to load entry for catch-up");
+                        continue;
+                    }
+                };
+                if self
+                    .outbound
+                    .send_catch_up(peer_id, snapshot, &known)
+                    .await?
+                {
+                    sent += 1;
+                }
+            }
+
+            info!(sent, "Clipboard catch-up answered");
+            Ok(sent)
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Rebuild the selected representations of a history entry.
+    ///
+    /// 重建历史条目中被选择策略引用的全部表示形式。
+    async fn build_snapshot(
+        &self,
+        candidate: &CatchUpCandidate,
+    ) -> Result<SystemClipboardSnapshot> {
+        let entry = &candidate.entry;
+        let selection = self
+            .selection_repo
+            .get_selection(&entry.entry_id)
+            .await?
+            .ok_or_else(|| anyhow!("selection not found"))?
+            .selection;
+
+        let mut rep_ids = vec![
+            selection.paste_rep_id,
+            selection.primary_rep_id,
+            selection.preview_rep_id,
+        ];
+        rep_ids.extend(selection.secondary_rep_ids);
+        let mut seen = HashSet::new();
+        rep_ids.retain(|rep_id| seen.insert(rep_id.clone()));
+
+        let mut representations = Vec::with_capacity(rep_ids.len());
+        for rep_id in &rep_ids {
+            let Some(rep) = self
+                .representation_repo
+                .get_representation(&entry.event_id, rep_id)
+                .await?
+            else {
+                continue;
+            };
+            let bytes = if let Some(inline_data) = &rep.inline_data {
+                inline_data.clone()
+            } else if let Some(blob_id) = &rep.blob_id {
+                self.blob_store.get(blob_id).await?
+            } else {
+                match self.payload_resolver.resolve(&rep).await? {
+                    ResolvedClipboardPayload::Inline { bytes, .. } => bytes,
+                    ResolvedClipboardPayload::BlobRef { blob_id, .. } => {
+                        self.blob_store.get(&blob_id).await?
+                    }
+                }
+            };
+            representations.push(ObservedClipboardRepresentation {
+                id: rep.id,
+                format_id: rep.format_id,
+                mime: rep.mime_type,
+                bytes,
+            });
+        }
+
+        Ok(SystemClipboardSnapshot {
+            ts_ms: entry.created_at_ms,
+            representations,
+        })
+    }
+
+    /// Start of the catch-up window, or `None` when catch-up is off.
+    async fn window_start(&self) -> Result<Option<i64>> {
+        let window_secs = self.settings.load().await?.sync.catch_up_window_secs;
+        if window_secs == 0 {
+            return Ok(None);
+        }
+        let window_ms = i64::try_from(window_secs.saturating_mul(1000)).unwrap_or(i64::MAX);
+        Ok(Some(self.clock.now_ms().saturating_sub(window_ms)))
+    }
+
+    async fn is_trusted(&self, peer_id: &str) -> Result<bool> {
+        Ok(self
+            .paired_device_repo
+            .get_by_peer_id(&PeerId::from(peer_id))
+            .await?
+            .is_some_and(|device| device.pairing_state == PairingState::Trusted))
+    }
+
+    /// Peers without stored capabilities never answered a hello and are
+    /// treated as lacking every optional feature.
+    ///
+    /// 未保存能力信息的对端视为不支持任何可选特性。
+    async fn supports_catch_up(&self, peer_id: &str) -> Result<bool> {
+        Ok(self
+            .paired_device_repo
+            .get_capabilities(&PeerId::from(peer_id))
+            .await?
+            .is_some_and(|capabilities| capabilities.supports(ProtocolFeature::CatchUp)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use uc_core::clipboard::{
+        ClipboardEntry, ClipboardSelection, ClipboardSelectionDecision, PayloadAvailability,
+        PersistedClipboardRepresentation, SelectRepresentationPolicyV1, SelectionPolicyVersion,
+        SyncFilterPolicyV1,
+    };
+    use uc_core::ids::{EntryId, EventId, FormatId, RepresentationId};
+    use uc_core::network::{PairedDevice, PeerCapabilities};
+    use uc_core::ports::clipboard::ProcessingUpdateOutcome;
+    use uc_core::ports::{OutboundSyncQueuePort, QueuedClipboardMessage};
+    use uc_core::security::model::MasterKey;
+    use uc_core::settings::model::Settings;
+    use uc_core::testing::{InMemoryPairedDeviceRepository, TestNetwork};
+    use uc_core::{BlobId, DeviceId, MimeType};
+    use uc_infra::security::{EncryptionRepository, InMemoryEncryptionSession};
+
+    const NOW_MS: i64 = 10_000_000;
+
+    struct TestEntryRepo {
+        candidates: Vec<CatchUpCandidate>,
+    }
+
+    #[async_trait]
+    impl ClipboardEntryRepositoryPort for TestEntryRepo {
+        async fn save_entry_and_selection(
+            &self,
+            _entry: &ClipboardEntry,
+            _selection: &ClipboardSelectionDecision,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_entry(&self, _entry_id: &EntryId) -> Result<Option<ClipboardEntry>> {
+            Ok(None)
+        }
+
+        async fn list_entries(&self, _limit: usize, _offset: usize) -> Result<Vec<ClipboardEntry>> {
+            Ok(vec![])
+        }
+
+        async fn delete_entry(&self, _entry_id: &EntryId) -> Result<()> {
+            Ok(())
+        }
+
+        async fn list_catch_up_candidates(
+            &self,
+            since_ms: i64,
+            limit: usize,
+        ) -> Result<Vec<CatchUpCandidate>> {
+            Ok(self
+                .candidates
+                .iter()
+                .filter(|candidate| candidate.entry.created_at_ms >= since_ms)
+                .take(limit)
+                .cloned()
+                .collect())
+        }
+
+        async fn list_retention_candidates(
+            &self,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::RetentionCandidate>> {
+            Ok(Vec::new())
+        }
+
+        async fn soft_delete_entries(
+            &self,
+            _entry_ids: &[uc_core::ids::EntryId],
+            _deleted_at_ms: i64,
+        ) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_soft_deleted_entries(
+            &self,
+            _deleted_before_ms: i64,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_entry_source_device(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+        ) -> anyhow::Result<Option<uc_core::ids::DeviceId>> {
+            Ok(None)
+        }
+
+        async fn set_entry_pinned(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+            _pinned: bool,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn list_pinned_entries(
+            &self,
+            _limit: usize,
+            _offset: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Every entry selects the single representation named after it.
+    struct TestSelectionRepo;
+
+    #[async_trait]
+    impl ClipboardSelectionRepositoryPort for TestSelectionRepo {
+        async fn get_selection(
+            &self,
+            entry_id: &EntryId,
+        ) -> Result<Option<ClipboardSelectionDecision>> {
+            let rep_id = RepresentationId::from(format!("rep-{entry_id}"));
+            Ok(Some(ClipboardSelectionDecision::new(
+                entry_id.clone(),
+                ClipboardSelection {
+                    primary_rep_id: rep_id.clone(),
+                    secondary_rep_ids: vec![],
+                    preview_rep_id: rep_id.clone(),
+                    paste_rep_id: rep_id,
+                    policy_version: SelectionPolicyVersion::V1,
+                },
+            )))
+        }
+
+        async fn delete_selection(&self, _entry_id: &EntryId) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    struct TestRepresentationRepo {
+        reps: HashMap<RepresentationId, PersistedClipboardRepresentation>,
+    }
+
+    #[async_trait]
+    impl ClipboardRepresentationRepositoryPort for TestRepresentationRepo {
+        async fn get_representation(
+            &self,
+            _event_id: &EventId,
+            representation_id: &RepresentationId,
+        ) -> Result<Option<PersistedClipboardRepresentation>> {
+            Ok(self.reps.get(representation_id).cloned())
+        }
+
+        async fn get_representation_by_id(
+            &self,
+            _representation_id: &RepresentationId,
+        ) -> Result<Option<PersistedClipboardRepresentation>> {
+            Ok(None)
+        }
+
+        async fn get_representation_by_blob_id(
+            &self,
+            _blob_id: &BlobId,
+        ) -> Result<Option<PersistedClipboardRepresentation>> {
+            Ok(None)
+        }
+
+        async fn update_blob_id(
+            &self,
+            _representation_id: &RepresentationId,
+            _blob_id: &BlobId,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn update_blob_id_if_none(
+            &self,
+            _representation_id: &RepresentationId,
+            _blob_id: &BlobId,
+        ) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn update_processing_result(
+            &self,
+            _rep_id: &RepresentationId,
+            _expected_states: &[PayloadAvailability],
+            _blob_id: Option<&BlobId>,
+            _new_state: PayloadAvailability,
+            _last_error: Option<&str>,
+        ) -> Result<ProcessingUpdateOutcome> {
+            Ok(ProcessingUpdateOutcome::NotFound)
+        }
+
+        async fn count_inline_representations(&self) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_inline_representation_ids(
+            &self,
+            _after: Option<&uc_core::ids::RepresentationId>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<(uc_core::ids::EventId, uc_core::ids::RepresentationId)>> {
+            Ok(Vec::new())
+        }
+
+        async fn update_inline_data(
+            &self,
+            _representation_id: &uc_core::ids::RepresentationId,
+            _inline_data: &[u8],
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    struct TestBlobStore;
+
+    #[async_trait]
+    impl BlobStorePort for TestBlobStore {
+        async fn put(&self, _blob_id: &BlobId, _data: &[u8]) -> Result<std::path::PathBuf> {
+            Ok(std::path::PathBuf::from("/tmp/unused"))
+        }
+
+        async fn get(&self, _blob_id: &BlobId) -> Result<Vec<u8>> {
+            Err(anyhow!("unexpected blob fetch"))
+        }
+
+        async fn put_stream(
+            &self,
+            blob_id: &uc_core::BlobId,
+            reader: &mut (dyn tokio::io::AsyncRead + Send + Unpin),
+        ) -> anyhow::Result<std::path::PathBuf> {
+            let mut data = Vec::new();
+            tokio::io::AsyncReadExt::read_to_end(reader, &mut data).await?;
+            self.put(blob_id, &data).await
+        }
+
+        async fn open_read(
+            &self,
+            blob_id: &uc_core::BlobId,
+        ) -> anyhow::Result<uc_core::ports::BlobReader> {
+            Ok(Box::new(std::io::Cursor::new(self.get(blob_id).await?)))
+        }
+
+        async fn delete(&self, _blob_id: &uc_core::BlobId) -> anyhow::Result<u64> {
+            Ok(0)
+        }
+
+        async fn list_objects(&self) -> anyhow::Result<Vec<uc_core::ports::StoredBlobObject>> {
+            Ok(Vec::new())
+        }
+    }
+
+    struct TestPayloadResolver;
+
+    #[async_trait]
+    impl ClipboardPayloadResolverPort for TestPayloadResolver {
+        async fn resolve(
+            &self,
+            _representation: &PersistedClipboardRepresentation,
+        ) -> Result<ResolvedClipboardPayload> {
+            Err(anyhow!("unexpected payload resolve"))
+        }
+    }
+
+    struct TestDeviceIdentity;
+
+    impl DeviceIdentityPort for TestDeviceIdentity {
+        fn current_device_id(&self) -> DeviceId {
+            DeviceId::new("device-local")
+        }
+    }
+
+    struct TestSettings;
+
+    #[async_trait]
+    impl SettingsPort for TestSettings {
+        async fn load(&self) -> Result<Settings> {
+            let mut settings = Settings::default();
+            settings.sync.catch_up_window_secs = 60 * 60;
+            Ok(settings)
+        }
+
+        async fn save(&self, _settings: &Settings) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    struct TestQueue;
+
+    #[async_trait]
+    impl OutboundSyncQueuePort for TestQueue {
+        async fn enqueue(&self, _message: QueuedClipboardMessage) -> Result<()> {
+            Ok(())
+        }
+
+        async fn list_pending(&self) -> Result<Vec<QueuedClipboardMessage>> {
+            Ok(vec![])
+        }
+
+        async fn remove(&self, _message_ids: &[String]) -> Result<usize> {
+            Ok(0)
+        }
+
+        async fn retain_latest(&self, _keep: usize) -> Result<usize> {
+            Ok(0)
+        }
+    }
+
+    struct TestClock;
+
+    impl ClockPort for TestClock {
+        fn now_ms(&self) -> i64 {
+            NOW_MS
+        }
+    }
+
+    /// Text entry `name` captured on `source_device`, with its inline representation.
+    fn text_entry(
+        name: &str,
+        source_device: &str,
+        created_at_ms: i64,
+    ) -> (CatchUpCandidate, PersistedClipboardRepresentation) {
+        let rep_id = RepresentationId::from(format!("rep-{name}"));
+        let bytes = name.as_bytes().to_vec();
+        let rep = PersistedClipboardRepresentation::new(
+            rep_id,
+            FormatId::from("text"),
+            Some(MimeType::text_plain()),
+            bytes.len() as i64,
+            Some(bytes),
+            None,
+        );
+        let candidate = CatchUpCandidate {
+            entry: ClipboardEntry::new(
+                EntryId::from(name),
+                EventId::from(format!("event-{name}")),
+                created_at_ms,
+                None,
+                0,
+            ),
+            source_device: DeviceId::new(source_device),
+            snapshot_hash: format!("hash-{name}"),
+        };
+        (candidate, rep)
+    }
+
+    fn text_snapshot_hash(text: &str, created_at_ms: i64) -> String {
+        SystemClipboardSnapshot {
+            ts_ms: created_at_ms,
+            representations: vec![ObservedClipboardRepresentation {
+                id: RepresentationId::from(format!("rep-{text}")),
+                format_id: FormatId::from("text"),
+                mime: Some(MimeType::text_plain()),
+                bytes: text.as_bytes().to_vec(),
+            }],
+        }
+        .snapshot_hash()
+        .to_string()
+    }
+
+    async fn build_use_case(
+        entries: Vec<(CatchUpCandidate, PersistedClipboardRepresentation)>,
+        network: Arc<TestNetwork>,
+    ) -> CatchUpClipboardHistory {
+        let session = Arc::new(InMemoryEncryptionSession::new());
+        session
+            .set_master_key(MasterKey::generate().unwrap())
+            .await
+            .unwrap();
+        let network: Arc<dyn NetworkPort> = network;
+        let paired_device_repo: Arc<dyn PairedDeviceRepositoryPort> = Arc::new(
+            InMemoryPairedDeviceRepository::with_devices(vec![
+                device("peer-trusted", PairingState::Trusted),
+                device("peer-pending", PairingState::Pending),
+                device("peer-legacy", PairingState::Trusted),
+            ])
+            .with_capabilities(capabilities("peer-trusted", vec![ProtocolFeature::CatchUp]))
+            .with_capabilities(capabilities("peer-pending", vec![ProtocolFeature::CatchUp]))
+            .with_capabilities(capabilities(
+                "peer-legacy",
+                vec![ProtocolFeature::LazyFetch],
+            )),
+        );
+        let device_identity: Arc<dyn DeviceIdentityPort> = Arc::new(TestDeviceIdentity);
+        let settings: Arc<dyn SettingsPort> = Arc::new(TestSettings);
+        let clock: Arc<dyn ClockPort> = Arc::new(TestClock);
+
+        let outbound = SyncOutboundClipboardUseCase::new(
+            Arc::new(SelectRepresentationPolicyV1::new()),
+            Arc::new(SyncFilterPolicyV1::new()),
+            Arc::new(EncryptionRepository),
+            session,
+            paired_device_repo.clone(),
+            network.clone(),
+            device_identity.clone(),
+            settings.clone(),
+            Arc::new(TestQueue),
+            clock.clone(),
+        );
+        let (candidates, reps): (Vec<_>, Vec<_>) = entries.into_iter().unzip();
+
+        CatchUpClipboardHistory::new(
+            Arc::new(TestEntryRepo { candidates }),
+            Arc::new(TestSelectionRepo),
+            Arc::new(TestRepresentationRepo {
+                reps: reps.into_iter().map(|rep| (rep.id.clone(), rep)).collect(),
+            }),
+            Arc::new(TestBlobStore),
+            Arc::new(TestPayloadResolver),
+            paired_device_repo,
+            network,
+            device_identity,
+            settings,
+            clock,
+            outbound,
+        )
+    }
+
+    fn device(peer_id: &str, pairing_state: PairingState) -> PairedDevice {
+        PairedDevice {
+            peer_id: PeerId::from(peer_id),
+            pairing_state,
+            identity_fingerprint: "fp".to_string(),
+            paired_at: Utc::now(),
+            last_seen_at: None,
+            device_name: peer_id.to_string(),
+            device_id: "device-peer".to_string(),
+        }
+    }
+
+    fn capabilities(peer_id: &str, features: Vec<ProtocolFeature>) -> PeerCapabilities {
+        PeerCapabilities {
+            peer_id: peer_id.to_string(),
+            app_version: "1.0.0".to_string(),
+            protocol_version: "1.0.0".to_string(),
+            features,
+            negotiated_at: Utc::now(),
+        }
+    }
+
+    fn sent_messages(network: &TestNetwork) -> Vec<ProtocolMessage> {
+        network
+            .sent_clipboard()
+            .iter()
+            .map(|(_, payload)| ProtocolMessage::from_bytes(payload).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn request_summarises_history_within_window() {
+        let network = Arc::new(TestNetwork::new());
+        let uc = build_use_case(
+            vec![
+                text_entry("recent", "device-remote", NOW_MS - 1_000),
+                text_entry("stale", "device-local", NOW_MS - 2 * 60 * 60 * 1000),
+            ],
+            network.clone(),
+        )
+        .await;
+
+        assert!(uc.request("peer-trusted").await.unwrap());
+        assert!(!uc.request("peer-pending").await.unwrap());
+
+        let messages = sent_messages(&network);
+        assert_eq!(messages.len(), 1);
+        let ProtocolMessage::CatchUp(request) = &messages[0] else {
+            panic!("unexpected protocol message: {:?}", messages[0]);
+        };
+        assert_eq!(request.origin_device_id, "device-local");
+        assert_eq!(request.since_ms, NOW_MS - 60 * 60 * 1000);
+        assert_eq!(request.known_hashes, vec!["hash-recent".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn request_skips_peers_without_catch_up() {
+        let network = Arc::new(TestNetwork::new());
+        let uc = build_use_case(
+            vec![text_entry("recent", "device-remote", NOW_MS - 1_000)],
+            network.clone(),
+        )
+        .await;
+
+        assert!(!uc.request("peer-legacy").await.unwrap());
+        assert!(!uc.request("peer-unknown").await.unwrap());
+        assert!(network.sent_clipboard().is_empty());
+    }
+
+    #[tokio::test]
+    async fn respond_resends_only_local_entries_the_peer_lacks() {
+        let network = Arc::new(TestNetwork::new());
+        let uc = build_use_case(
+            vec![
+                text_entry("missing", "device-local", NOW_MS - 1_000),
+                text_entry("known", "device-local", NOW_MS - 2_000),
+                text_entry("foreign", "device-remote", NOW_MS - 3_000),
+            ],
+            network.clone(),
+        )
+        .await;
+        let request = CatchUpRequest {
+            origin_device_id: "device-remote".to_string(),
+            since_ms: 0,
+            known_hashes: vec![text_snapshot_hash("known", NOW_MS - 2_000)],
+        };
+
+        let resent = uc.respond("peer-trusted", request).await.unwrap();
+
+        assert_eq!(resent, 1);
+        let messages = sent_messages(&network);
+        let ProtocolMessage::Clipboard(message) = &messages[0] else {
+            panic!("unexpected protocol message: {:?}", messages[0]);
+        };
+        assert!(message.catch_up);
+        assert_eq!(
+            message.content_hash,
+            text_snapshot_hash("missing", NOW_MS - 1_000)
+        );
+    }
+
+    #[tokio::test]
+    async fn respond_rejects_untrusted_peers() {
+        let network = Arc::new(TestNetwork::new());
+        let uc = build_use_case(
+            vec![text_entry("missing", "device-local", NOW_MS - 1_000)],
+            network.clone(),
+        )
+        .await;
+        let request = CatchUpRequest {
+            origin_device_id: "device-remote".to_string(),
+            since_ms: 0,
+            known_hashes: vec![],
+        };
+
+        assert!(uc.respond("peer-pending", request).await.is_err());
+        assert!(network.sent_clipboard().is_empty());
+    }
+}
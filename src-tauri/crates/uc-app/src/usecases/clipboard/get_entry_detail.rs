@@ -5,8 +5,9 @@ use uc_core::{
     clipboard::MimeType,
     ids::EntryId,
     ports::{
-        BlobStorePort, ClipboardEntryRepositoryPort, ClipboardRepresentationRepositoryPort,
-        ClipboardSelectionRepositoryPort,
+        BlobStorePort, ClipboardEntryRepositoryPort, ClipboardPayloadResolverPort,
+        ClipboardRepresentationRepositoryPort, ClipboardSelectionRepositoryPort,
+        ResolvedClipboardPayload,
     },
 };
 
@@ -17,6 +18,7 @@ pub struct GetEntryDetailUseCase {
     selection_repo: Arc<dyn ClipboardSelectionRepositoryPort>,
     representation_repo: Arc<dyn ClipboardRepresentationRepositoryPort>,
     blob_store: Arc<dyn BlobStorePort>,
+    payload_resolver: Arc<dyn ClipboardPayloadResolverPort>,
 }
 
 /// Detail result from GetEntryDetailUseCase
@@ -29,6 +31,7 @@ pub struct EntryDetailResult {
     pub created_at_ms: i64,
     pub active_time_ms: i64,
     pub mime_type: Option<String>,
+    pub pinned: bool,
 }
 
 impl GetEntryDetailUseCase {
@@ -37,12 +40,14 @@ impl GetEntryDetailUseCase {
         selection_repo: Arc<dyn ClipboardSelectionRepositoryPort>,
         representation_repo: Arc<dyn ClipboardRepresentationRepositoryPort>,
         blob_store: Arc<dyn BlobStorePort>,
+        payload_resolver: Arc<dyn ClipboardPayloadResolverPort>,
     ) -> Self {
         Self {
             entry_repo,
             selection_repo,
             representation_repo,
             blob_store,
+            payload_resolver,
         }
     }
 
@@ -75,14 +80,17 @@ impl GetEntryDetailUseCase {
         let full_content = if let Some(blob_id) = &preview_rep.blob_id {
             let blob_content = self.blob_store.get(blob_id).await?;
             String::from_utf8_lossy(&blob_content).to_string()
+        } else if let Some(inline_data) = &preview_rep.inline_data {
+            String::from_utf8_lossy(inline_data).to_string()
         } else {
-            String::from_utf8_lossy(
-                preview_rep
-                    .inline_data
-                    .as_ref()
-                    .ok_or(anyhow::anyhow!("No inline data"))?,
-            )
-            .to_string()
+            // Staged or remote payloads: let the resolver find the bytes.
+            let bytes = match self.payload_resolver.resolve(&preview_rep).await? {
+                ResolvedClipboardPayload::Inline { bytes, .. } => bytes,
+                ResolvedClipboardPayload::BlobRef { blob_id, .. } => {
+                    self.blob_store.get(&blob_id).await?
+                }
+            };
+            String::from_utf8_lossy(&bytes).to_string()
         };
 
         Ok(EntryDetailResult {
@@ -92,6 +100,7 @@ impl GetEntryDetailUseCase {
             created_at_ms: entry.created_at_ms,
             active_time_ms: entry.active_time_ms,
             mime_type: mime_type_str.map(String::from),
+            pinned: entry.pinned,
         })
     }
 
@@ -122,6 +122,58 @@ mod tests {
         async fn delete_entry(&self, _entry_id: &EntryId) -> Result<()> {
             Ok(())
         }
+
+        async fn list_retention_candidates(
+            &self,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::RetentionCandidate>> {
+            Ok(Vec::new())
+        }
+
+        async fn soft_delete_entries(
+            &self,
+            _entry_ids: &[uc_core::ids::EntryId],
+            _deleted_at_ms: i64,
+        ) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_soft_deleted_entries(
+            &self,
+            _deleted_before_ms: i64,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_entry_source_device(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+        ) -> anyhow::Result<Option<uc_core::ids::DeviceId>> {
+            Ok(None)
+        }
+
+        async fn set_entry_pinned(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+            _pinned: bool,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn list_pinned_entries(
+            &self,
+            _limit: usize,
+            _offset: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn list_catch_up_candidates(
+            &self,
+            _since_ms: i64,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::CatchUpCandidate>> {
+            Ok(Vec::new())
+        }
     }
 
     #[async_trait]
@@ -188,6 +240,26 @@ mod tests {
         ) -> Result<uc_core::ports::clipboard::ProcessingUpdateOutcome> {
             Ok(uc_core::ports::clipboard::ProcessingUpdateOutcome::NotFound)
         }
+
+        async fn count_inline_representations(&self) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_inline_representation_ids(
+            &self,
+            _after: Option<&uc_core::ids::RepresentationId>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<(uc_core::ids::EventId, uc_core::ids::RepresentationId)>> {
+            Ok(Vec::new())
+        }
+
+        async fn update_inline_data(
+            &self,
+            _representation_id: &uc_core::ids::RepresentationId,
+            _inline_data: &[u8],
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
     }
 
     #[tokio::test]
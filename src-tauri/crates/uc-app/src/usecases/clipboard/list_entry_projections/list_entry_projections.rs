@@ -19,11 +19,23 @@ pub struct EntryProjectionDto {
     pub captured_at: i64,
     pub content_type: String,
     pub thumbnail_url: Option<String>,
-    // TODO: is_encrypted, is_favorited to be implemented later
+    // TODO: is_encrypted to be implemented later
     pub is_encrypted: bool,
     pub is_favorited: bool,
     pub updated_at: i64,
     pub active_time: i64,
+    /// Why the entry was kept local (`SyncSkipReason::as_str`), if it was
+    pub sync_skip_reason: Option<String>,
+}
+
+/// Which entries to project
+/// 投影条目的过滤方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EntryProjectionFilter {
+    #[default]
+    All,
+    /// Only pinned (favorited) entries
+    Pinned,
 }
 
 /// Error type for list projections use case
@@ -73,6 +85,17 @@ impl ListClipboardEntryProjections {
         &self,
         limit: usize,
         offset: usize,
+    ) -> Result<Vec<EntryProjectionDto>, ListProjectionsError> {
+        self.execute_with_filter(limit, offset, EntryProjectionFilter::All)
+            .await
+    }
+
+    /// Execute the use case, restricted by `filter`
+    pub async fn execute_with_filter(
+        &self,
+        limit: usize,
+        offset: usize,
+        filter: EntryProjectionFilter,
     ) -> Result<Vec<EntryProjectionDto>, ListProjectionsError> {
         // Validate limit
         if limit == 0 {
@@ -90,11 +113,13 @@ impl ListClipboardEntryProjections {
         }
 
         // Query entries from repository
-        let entries = self
-            .entry_repo
-            .list_entries(limit, offset)
-            .await
-            .map_err(|e| ListProjectionsError::RepositoryError(e.to_string()))?;
+        let entries = match filter {
+            EntryProjectionFilter::All => self.entry_repo.list_entries(limit, offset).await,
+            EntryProjectionFilter::Pinned => {
+                self.entry_repo.list_pinned_entries(limit, offset).await
+            }
+        }
+        .map_err(|e| ListProjectionsError::RepositoryError(e.to_string()))?;
 
         let mut projections = Vec::with_capacity(entries.len());
 
@@ -103,6 +128,7 @@ impl ListClipboardEntryProjections {
             let event_id_str = entry.event_id.inner().clone();
             let captured_at = entry.created_at_ms;
             let active_time = entry.active_time_ms;
+            let is_favorited = entry.pinned;
 
             // Get selection for this entry
             let selection = self
@@ -189,9 +215,12 @@ impl ListClipboardEntryProjections {
                 content_type,
                 thumbnail_url,
                 is_encrypted: false, // TODO: implement later
-                is_favorited: false, // TODO: implement later
+                is_favorited,
                 updated_at: captured_at,
                 active_time,
+                sync_skip_reason: entry
+                    .sync_skip_reason
+                    .map(|reason| reason.as_str().to_string()),
             });
         }
 
@@ -253,9 +282,68 @@ mod tests {
                 .collect())
         }
 
+        async fn list_pinned_entries(
+            &self,
+            limit: usize,
+            offset: usize,
+        ) -> Result<Vec<ClipboardEntry>> {
+            Ok(self
+                .entries
+                .iter()
+                .filter(|entry| entry.pinned)
+                .skip(offset)
+                .take(limit)
+                .cloned()
+                .collect())
+        }
+
         async fn delete_entry(&self, _entry_id: &EntryId) -> Result<()> {
             unimplemented!()
         }
+
+        async fn list_retention_candidates(
+            &self,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::RetentionCandidate>> {
+            Ok(Vec::new())
+        }
+
+        async fn soft_delete_entries(
+            &self,
+            _entry_ids: &[uc_core::ids::EntryId],
+            _deleted_at_ms: i64,
+        ) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_soft_deleted_entries(
+            &self,
+            _deleted_before_ms: i64,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_entry_source_device(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+        ) -> anyhow::Result<Option<uc_core::ids::DeviceId>> {
+            Ok(None)
+        }
+
+        async fn set_entry_pinned(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+            _pinned: bool,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn list_catch_up_candidates(
+            &self,
+            _since_ms: i64,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::CatchUpCandidate>> {
+            Ok(Vec::new())
+        }
     }
 
     #[async_trait::async_trait]
@@ -325,6 +413,26 @@ mod tests {
         ) -> Result<uc_core::ports::clipboard::ProcessingUpdateOutcome> {
             unimplemented!()
         }
+
+        async fn count_inline_representations(&self) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_inline_representation_ids(
+            &self,
+            _after: Option<&uc_core::ids::RepresentationId>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<(uc_core::ids::EventId, uc_core::ids::RepresentationId)>> {
+            Ok(Vec::new())
+        }
+
+        async fn update_inline_data(
+            &self,
+            _representation_id: &uc_core::ids::RepresentationId,
+            _inline_data: &[u8],
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
     }
 
     #[async_trait::async_trait]
@@ -487,4 +595,64 @@ mod tests {
             Some(format!("uc://thumbnail/{}", rep_id.inner()))
         );
     }
+
+    #[tokio::test]
+    async fn test_pinned_filter_returns_only_favorited_entries() {
+        let mut entries = Vec::new();
+        let mut selections = HashMap::new();
+        let mut representations = HashMap::new();
+        for (id, pinned) in [("entry-a", true), ("entry-b", false)] {
+            let entry_id = EntryId::from(id);
+            let event_id = EventId::from(format!("event-{id}"));
+            let rep_id = RepresentationId::from(format!("rep-{id}"));
+            let mut entry = ClipboardEntry::new(entry_id.clone(), event_id.clone(), 1, None, 5);
+            entry.pinned = pinned;
+            entries.push(entry);
+            selections.insert(
+                entry_id.inner().clone(),
+                ClipboardSelectionDecision::new(
+                    entry_id,
+                    ClipboardSelection {
+                        primary_rep_id: rep_id.clone(),
+                        secondary_rep_ids: vec![],
+                        preview_rep_id: rep_id.clone(),
+                        paste_rep_id: rep_id.clone(),
+                        policy_version: SelectionPolicyVersion::V1,
+                    },
+                ),
+            );
+            representations.insert(
+                (event_id.inner().clone(), rep_id.inner().clone()),
+                PersistedClipboardRepresentation::new(
+                    rep_id,
+                    FormatId::from("public.utf8-plain-text"),
+                    Some(MimeType::text_plain()),
+                    5,
+                    Some(b"hello".to_vec()),
+                    None,
+                ),
+            );
+        }
+
+        let use_case = ListClipboardEntryProjections::new(
+            Arc::new(MockEntryRepository { entries }),
+            Arc::new(MockSelectionRepository { selections }),
+            Arc::new(MockRepresentationRepository { representations }),
+            Arc::new(MockThumbnailRepository {
+                thumbnails: HashMap::new(),
+            }),
+        );
+
+        let all = use_case.execute(50, 0).await.expect("all projections");
+        assert_eq!(all.len(), 2);
+        assert!(all[0].is_favorited);
+        assert!(!all[1].is_favorited);
+
+        let pinned = use_case
+            .execute_with_filter(50, 0, EntryProjectionFilter::Pinned)
+            .await
+            .expect("pinned projections");
+        assert_eq!(pinned.len(), 1);
+        assert_eq!(pinned[0].id, "entry-a");
+    }
 }
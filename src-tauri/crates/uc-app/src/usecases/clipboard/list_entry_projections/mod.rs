@@ -4,5 +4,5 @@
 mod list_entry_projections;
 
 pub use list_entry_projections::{
-    EntryProjectionDto, ListClipboardEntryProjections, ListProjectionsError,
+    EntryProjectionDto, EntryProjectionFilter, ListClipboardEntryProjections, ListProjectionsError,
 };
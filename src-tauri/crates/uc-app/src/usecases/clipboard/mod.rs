@@ -1,11 +1,21 @@
+pub mod catch_up;
 pub mod get_entry_detail;
 pub mod get_entry_resource;
 pub mod list_entry_projections;
+pub mod pin_clipboard_entry;
 pub mod resolve_blob_resource;
 pub mod resolve_thumbnail_resource;
 pub mod restore_clipboard_selection;
+pub mod search_clipboard_entries;
+pub mod sync_inbound;
+pub mod sync_outbound;
 pub mod touch_clipboard_entry;
 
+pub use catch_up::CatchUpClipboardHistory;
 pub use list_entry_projections::{
-    EntryProjectionDto, ListClipboardEntryProjections, ListProjectionsError,
+    EntryProjectionDto, EntryProjectionFilter, ListClipboardEntryProjections, ListProjectionsError,
 };
+pub use pin_clipboard_entry::PinClipboardEntryUseCase;
+pub use search_clipboard_entries::{SearchClipboardEntries, SearchClipboardError};
+pub use sync_inbound::{InboundClipboardOutcome, SyncInboundClipboardUseCase};
+pub use sync_outbound::SyncOutboundClipboardUseCase;
@@ -0,0 +1,165 @@
+use anyhow::{bail, Result};
+use std::sync::Arc;
+
+use uc_core::clipboard::ClipboardContentAction;
+use uc_core::ids::EntryId;
+use uc_core::ports::ClipboardEntryRepositoryPort;
+
+/// Pin or unpin a clipboard entry.
+///
+/// 置顶或取消置顶剪贴板条目。置顶条目不会被保留策略或清空操作删除。
+pub struct PinClipboardEntryUseCase {
+    entry_repo: Arc<dyn ClipboardEntryRepositoryPort>,
+}
+
+impl PinClipboardEntryUseCase {
+    pub fn new(entry_repo: Arc<dyn ClipboardEntryRepositoryPort>) -> Self {
+        Self { entry_repo }
+    }
+
+    /// Apply a `Pin` or `Unpin` action; any other action is rejected.
+    ///
+    /// Returns `false` if the entry does not exist.
+    pub async fn execute(
+        &self,
+        entry_id: &EntryId,
+        action: ClipboardContentAction,
+    ) -> Result<bool> {
+        let pinned = match action {
+            ClipboardContentAction::Pin => true,
+            ClipboardContentAction::Unpin => false,
+            other => bail!("unsupported pin action: {:?}", other),
+        };
+
+        self.entry_repo.set_entry_pinned(entry_id, pinned).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PinClipboardEntryUseCase;
+    use async_trait::async_trait;
+    use std::sync::{Arc, Mutex};
+    use uc_core::clipboard::{ClipboardContentAction, ClipboardEntry, ClipboardSelectionDecision};
+    use uc_core::ids::EntryId;
+    use uc_core::ports::ClipboardEntryRepositoryPort;
+
+    struct MockEntryRepository {
+        pinned: Arc<Mutex<Option<bool>>>,
+    }
+
+    #[async_trait]
+    impl ClipboardEntryRepositoryPort for MockEntryRepository {
+        async fn save_entry_and_selection(
+            &self,
+            _entry: &ClipboardEntry,
+            _selection: &ClipboardSelectionDecision,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn get_entry(&self, _entry_id: &EntryId) -> anyhow::Result<Option<ClipboardEntry>> {
+            Ok(None)
+        }
+
+        async fn list_entries(
+            &self,
+            _limit: usize,
+            _offset: usize,
+        ) -> anyhow::Result<Vec<ClipboardEntry>> {
+            Ok(vec![])
+        }
+
+        async fn set_entry_pinned(
+            &self,
+            _entry_id: &EntryId,
+            pinned: bool,
+        ) -> anyhow::Result<bool> {
+            *self.pinned.lock().unwrap() = Some(pinned);
+            Ok(true)
+        }
+
+        async fn delete_entry(&self, _entry_id: &EntryId) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn list_retention_candidates(
+            &self,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::RetentionCandidate>> {
+            Ok(Vec::new())
+        }
+
+        async fn soft_delete_entries(
+            &self,
+            _entry_ids: &[uc_core::ids::EntryId],
+            _deleted_at_ms: i64,
+        ) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_soft_deleted_entries(
+            &self,
+            _deleted_before_ms: i64,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_entry_source_device(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+        ) -> anyhow::Result<Option<uc_core::ids::DeviceId>> {
+            Ok(None)
+        }
+
+        async fn list_pinned_entries(
+            &self,
+            _limit: usize,
+            _offset: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn list_catch_up_candidates(
+            &self,
+            _since_ms: i64,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::CatchUpCandidate>> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn pin_and_unpin_update_repository_flag() {
+        let pinned = Arc::new(Mutex::new(None));
+        let uc = PinClipboardEntryUseCase::new(Arc::new(MockEntryRepository {
+            pinned: pinned.clone(),
+        }));
+        let entry_id = EntryId::from("entry-1");
+
+        assert!(uc
+            .execute(&entry_id, ClipboardContentAction::Pin)
+            .await
+            .unwrap());
+        assert_eq!(*pinned.lock().unwrap(), Some(true));
+
+        uc.execute(&entry_id, ClipboardContentAction::Unpin)
+            .await
+            .unwrap();
+        assert_eq!(*pinned.lock().unwrap(), Some(false));
+    }
+
+    #[tokio::test]
+    async fn rejects_non_pin_actions() {
+        let pinned = Arc::new(Mutex::new(None));
+        let uc = PinClipboardEntryUseCase::new(Arc::new(MockEntryRepository {
+            pinned: pinned.clone(),
+        }));
+
+        let result = uc
+            .execute(&EntryId::from("entry-1"), ClipboardContentAction::Delete)
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(*pinned.lock().unwrap(), None);
+    }
+}
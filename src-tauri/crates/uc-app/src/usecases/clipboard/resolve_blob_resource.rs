@@ -3,7 +3,7 @@ use std::sync::Arc;
 
 use uc_core::{
     clipboard::MimeType,
-    ports::{BlobStorePort, ClipboardRepresentationRepositoryPort},
+    ports::{BlobReader, BlobStorePort, ClipboardRepresentationRepositoryPort},
     BlobId,
 };
 
@@ -14,13 +14,15 @@ pub struct ResolveBlobResourceUseCase {
     blob_store: Arc<dyn BlobStorePort>,
 }
 
-/// Blob resource payload and metadata.
-/// Blob 资源内容与元信息。
-#[derive(Debug, Clone)]
+/// Blob resource metadata and a reader over its plaintext.
+/// Blob 资源元信息及其明文读取器。
 pub struct BlobResourceResult {
     pub blob_id: BlobId,
     pub mime_type: Option<String>,
-    pub bytes: Vec<u8>,
+    /// Plaintext length recorded for the representation.
+    pub size_bytes: u64,
+    /// Decrypts frame by frame as it is read; nothing is buffered up front.
+    pub reader: BlobReader,
 }
 
 impl ResolveBlobResourceUseCase {
@@ -53,12 +55,13 @@ impl ResolveBlobResourceUseCase {
             .map(MimeType::as_str)
             .map(String::from);
 
-        let bytes = self.blob_store.get(blob_id).await?;
+        let reader = self.blob_store.open_read(blob_id).await?;
 
         Ok(BlobResourceResult {
             blob_id: blob_id.clone(),
             mime_type,
-            bytes,
+            size_bytes: representation.size_bytes.max(0) as u64,
+            reader,
         })
     }
 }
@@ -131,6 +134,26 @@ mod tests {
         ) -> Result<ProcessingUpdateOutcome> {
             Ok(ProcessingUpdateOutcome::NotFound)
         }
+
+        async fn count_inline_representations(&self) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_inline_representation_ids(
+            &self,
+            _after: Option<&uc_core::ids::RepresentationId>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<(uc_core::ids::EventId, uc_core::ids::RepresentationId)>> {
+            Ok(Vec::new())
+        }
+
+        async fn update_inline_data(
+            &self,
+            _representation_id: &uc_core::ids::RepresentationId,
+            _inline_data: &[u8],
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
     }
 
     #[async_trait]
@@ -146,10 +169,35 @@ mod tests {
                 Err(anyhow::anyhow!("Blob not found"))
             }
         }
+
+        async fn put_stream(
+            &self,
+            blob_id: &uc_core::BlobId,
+            reader: &mut (dyn tokio::io::AsyncRead + Send + Unpin),
+        ) -> anyhow::Result<std::path::PathBuf> {
+            let mut data = Vec::new();
+            tokio::io::AsyncReadExt::read_to_end(reader, &mut data).await?;
+            self.put(blob_id, &data).await
+        }
+
+        async fn open_read(
+            &self,
+            blob_id: &uc_core::BlobId,
+        ) -> anyhow::Result<uc_core::ports::BlobReader> {
+            Ok(Box::new(std::io::Cursor::new(self.get(blob_id).await?)))
+        }
+
+        async fn delete(&self, _blob_id: &uc_core::BlobId) -> anyhow::Result<u64> {
+            Ok(0)
+        }
+
+        async fn list_objects(&self) -> anyhow::Result<Vec<uc_core::ports::StoredBlobObject>> {
+            Ok(Vec::new())
+        }
     }
 
     #[tokio::test]
-    async fn test_resolve_blob_resource_returns_bytes() {
+    async fn test_resolve_blob_resource_streams_bytes() {
         let blob_id = BlobId::from("blob-1");
         let rep_id = RepresentationId::from("rep-1");
         let representation = PersistedClipboardRepresentation::new(
@@ -171,10 +219,15 @@ mod tests {
             }),
         );
 
-        let result = uc.execute(&blob_id).await.unwrap();
+        let mut result = uc.execute(&blob_id).await.unwrap();
 
         assert_eq!(result.blob_id, blob_id);
         assert_eq!(result.mime_type, Some("image/png".to_string()));
-        assert_eq!(result.bytes, vec![1, 2, 3]);
+        assert_eq!(result.size_bytes, 128);
+        let mut bytes = Vec::new();
+        tokio::io::AsyncReadExt::read_to_end(&mut result.reader, &mut bytes)
+            .await
+            .unwrap();
+        assert_eq!(bytes, vec![1, 2, 3]);
     }
 }
@@ -99,6 +99,10 @@ mod tests {
         async fn insert_thumbnail(&self, _metadata: &ThumbnailMetadata) -> Result<()> {
             Ok(())
         }
+
+        async fn delete_orphaned_thumbnails(&self) -> anyhow::Result<usize> {
+            Ok(0)
+        }
     }
 
     #[async_trait]
@@ -114,6 +118,31 @@ mod tests {
                 Err(anyhow::anyhow!("Blob not found"))
             }
         }
+
+        async fn put_stream(
+            &self,
+            blob_id: &uc_core::BlobId,
+            reader: &mut (dyn tokio::io::AsyncRead + Send + Unpin),
+        ) -> anyhow::Result<std::path::PathBuf> {
+            let mut data = Vec::new();
+            tokio::io::AsyncReadExt::read_to_end(reader, &mut data).await?;
+            self.put(blob_id, &data).await
+        }
+
+        async fn open_read(
+            &self,
+            blob_id: &uc_core::BlobId,
+        ) -> anyhow::Result<uc_core::ports::BlobReader> {
+            Ok(Box::new(std::io::Cursor::new(self.get(blob_id).await?)))
+        }
+
+        async fn delete(&self, _blob_id: &uc_core::BlobId) -> anyhow::Result<u64> {
+            Ok(0)
+        }
+
+        async fn list_objects(&self) -> anyhow::Result<Vec<uc_core::ports::StoredBlobObject>> {
+            Ok(Vec::new())
+        }
     }
 
     #[tokio::test]
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::io::AsyncReadExt;
 
 use uc_core::{
     clipboard::{
@@ -9,10 +10,10 @@ use uc_core::{
     ids::{EntryId, EventId, RepresentationId},
     ports::{
         BlobStorePort, ClipboardChangeOriginPort, ClipboardEntryRepositoryPort,
-        ClipboardRepresentationRepositoryPort, ClipboardSelectionRepositoryPort,
-        SystemClipboardPort,
+        ClipboardPayloadResolverPort, ClipboardRepresentationRepositoryPort,
+        ClipboardSelectionRepositoryPort, ResolvedClipboardPayload, SystemClipboardPort,
     },
-    ClipboardChangeOrigin,
+    BlobId, ClipboardChangeOrigin,
 };
 
 /// Reconstructs a system clipboard state from a historical clipboard entry,
@@ -23,6 +24,7 @@ pub struct RestoreClipboardSelectionUseCase {
     selection_repo: Arc<dyn ClipboardSelectionRepositoryPort>,
     representation_repo: Arc<dyn ClipboardRepresentationRepositoryPort>,
     blob_store: Arc<dyn BlobStorePort>,
+    payload_resolver: Arc<dyn ClipboardPayloadResolverPort>,
     clipboard_change_origin: Arc<dyn ClipboardChangeOriginPort>,
 }
 
@@ -34,7 +36,7 @@ impl RestoreClipboardSelectionUseCase {
     /// ```no_run
     /// use std::sync::Arc;
     /// use uc_app::usecases::clipboard::restore_clipboard_selection::RestoreClipboardSelectionUseCase;
-    /// use uc_core::ports::{BlobStorePort, ClipboardChangeOriginPort, ClipboardEntryRepositoryPort, ClipboardRepresentationRepositoryPort, ClipboardSelectionRepositoryPort, SystemClipboardPort};
+    /// use uc_core::ports::{BlobStorePort, ClipboardChangeOriginPort, ClipboardEntryRepositoryPort, ClipboardPayloadResolverPort, ClipboardRepresentationRepositoryPort, ClipboardSelectionRepositoryPort, SystemClipboardPort};
     /// // All parameters must implement their respective ports
     /// // let use_case = RestoreClipboardSelectionUseCase::new(
     /// //     Arc::new(clipboard_repo),
@@ -42,6 +44,7 @@ impl RestoreClipboardSelectionUseCase {
     /// //     Arc::new(selection_repo),
     /// //     Arc::new(representation_repo),
     /// //     Arc::new(blob_store),
+    /// //     Arc::new(payload_resolver),
     /// //     Arc::new(clipboard_change_origin),
     /// // );
     /// ```
@@ -51,6 +54,7 @@ impl RestoreClipboardSelectionUseCase {
         selection_repo: Arc<dyn ClipboardSelectionRepositoryPort>,
         representation_repo: Arc<dyn ClipboardRepresentationRepositoryPort>,
         blob_store: Arc<dyn BlobStorePort>,
+        payload_resolver: Arc<dyn ClipboardPayloadResolverPort>,
         clipboard_change_origin: Arc<dyn ClipboardChangeOriginPort>,
     ) -> Self {
         Self {
@@ -59,6 +63,7 @@ impl RestoreClipboardSelectionUseCase {
             selection_repo,
             representation_repo,
             blob_store,
+            payload_resolver,
             clipboard_change_origin,
         }
     }
@@ -114,12 +119,15 @@ impl RestoreClipboardSelectionUseCase {
         let bytes = if let Some(inline_data) = &restore_rep.inline_data {
             inline_data.clone()
         } else if let Some(blob_id) = &restore_rep.blob_id {
-            self.blob_store.get(blob_id).await?
+            self.read_blob(blob_id, restore_rep.size_bytes).await?
         } else {
-            return Err(anyhow::anyhow!(
-                "Representation has no data: {}",
-                restore_rep.id
-            ));
+            // Staged or remote payloads: let the resolver find the bytes.
+            match self.payload_resolver.resolve(restore_rep).await? {
+                ResolvedClipboardPayload::Inline { bytes, .. } => bytes,
+                ResolvedClipboardPayload::BlobRef { blob_id, .. } => {
+                    self.read_blob(&blob_id, restore_rep.size_bytes).await?
+                }
+            }
         };
 
         let representations = vec![ObservedClipboardRepresentation {
@@ -136,6 +144,18 @@ impl RestoreClipboardSelectionUseCase {
         })
     }
 
+    /// Read a blob through the streaming reader into a buffer of the expected size.
+    ///
+    /// The system clipboard takes the whole payload, so the plaintext is held
+    /// once; the store decrypts it frame by frame instead of alongside the
+    /// ciphertext.
+    async fn read_blob(&self, blob_id: &BlobId, size_bytes: i64) -> Result<Vec<u8>> {
+        let mut reader = self.blob_store.open_read(blob_id).await?;
+        let mut bytes = Vec::with_capacity(size_bytes.max(0) as usize);
+        reader.read_to_end(&mut bytes).await?;
+        Ok(bytes)
+    }
+
     fn select_restore_representation<'a>(
         candidates: &'a [PersistedClipboardRepresentation],
         paste_rep_id: &RepresentationId,
@@ -225,6 +245,8 @@ mod tests {
 
     struct MockBlobStore;
 
+    /// Resolves every representation to `b"fetched"`.
+    struct MockPayloadResolver;
     struct MockSystemClipboard;
 
     struct MockClipboardChangeOrigin {
@@ -254,6 +276,58 @@ mod tests {
         async fn delete_entry(&self, _entry_id: &EntryId) -> Result<()> {
             Ok(())
         }
+
+        async fn list_retention_candidates(
+            &self,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::RetentionCandidate>> {
+            Ok(Vec::new())
+        }
+
+        async fn soft_delete_entries(
+            &self,
+            _entry_ids: &[uc_core::ids::EntryId],
+            _deleted_at_ms: i64,
+        ) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_soft_deleted_entries(
+            &self,
+            _deleted_before_ms: i64,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_entry_source_device(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+        ) -> anyhow::Result<Option<uc_core::ids::DeviceId>> {
+            Ok(None)
+        }
+
+        async fn set_entry_pinned(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+            _pinned: bool,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn list_pinned_entries(
+            &self,
+            _limit: usize,
+            _offset: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn list_catch_up_candidates(
+            &self,
+            _since_ms: i64,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::CatchUpCandidate>> {
+            Ok(Vec::new())
+        }
     }
 
     #[async_trait]
@@ -320,6 +394,26 @@ mod tests {
         ) -> Result<ProcessingUpdateOutcome> {
             Ok(ProcessingUpdateOutcome::NotFound)
         }
+
+        async fn count_inline_representations(&self) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_inline_representation_ids(
+            &self,
+            _after: Option<&uc_core::ids::RepresentationId>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<(uc_core::ids::EventId, uc_core::ids::RepresentationId)>> {
+            Ok(Vec::new())
+        }
+
+        async fn update_inline_data(
+            &self,
+            _representation_id: &uc_core::ids::RepresentationId,
+            _inline_data: &[u8],
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
     }
 
     #[async_trait]
@@ -335,6 +429,31 @@ mod tests {
         async fn get(&self, _blob_id: &uc_core::BlobId) -> Result<Vec<u8>> {
             Err(anyhow::anyhow!("unexpected blob fetch"))
         }
+
+        async fn put_stream(
+            &self,
+            blob_id: &uc_core::BlobId,
+            reader: &mut (dyn tokio::io::AsyncRead + Send + Unpin),
+        ) -> anyhow::Result<std::path::PathBuf> {
+            let mut data = Vec::new();
+            tokio::io::AsyncReadExt::read_to_end(reader, &mut data).await?;
+            self.put(blob_id, &data).await
+        }
+
+        async fn open_read(
+            &self,
+            blob_id: &uc_core::BlobId,
+        ) -> anyhow::Result<uc_core::ports::BlobReader> {
+            Ok(Box::new(std::io::Cursor::new(self.get(blob_id).await?)))
+        }
+
+        async fn delete(&self, _blob_id: &uc_core::BlobId) -> anyhow::Result<u64> {
+            Ok(0)
+        }
+
+        async fn list_objects(&self) -> anyhow::Result<Vec<uc_core::ports::StoredBlobObject>> {
+            Ok(Vec::new())
+        }
     }
 
     impl SystemClipboardPort for MockSystemClipboard {
@@ -401,6 +520,63 @@ mod tests {
         }
     }
 
+    #[async_trait]
+    impl ClipboardPayloadResolverPort for MockPayloadResolver {
+        async fn resolve(
+            &self,
+            representation: &PersistedClipboardRepresentation,
+        ) -> Result<ResolvedClipboardPayload> {
+            Ok(ResolvedClipboardPayload::Inline {
+                mime: representation
+                    .mime_type
+                    .as_ref()
+                    .map(|mime| mime.to_string())
+                    .unwrap_or_default(),
+                bytes: b"fetched".to_vec(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn build_snapshot_resolves_representation_without_local_bytes() {
+        let entry_id = EntryId::from("entry-remote");
+        let event_id = EventId::from("event-remote");
+        let rep_id = RepresentationId::from("rep-remote");
+
+        let selection = ClipboardSelection {
+            primary_rep_id: rep_id.clone(),
+            secondary_rep_ids: vec![],
+            preview_rep_id: rep_id.clone(),
+            paste_rep_id: rep_id.clone(),
+            policy_version: SelectionPolicyVersion::V1,
+        };
+        let entry = ClipboardEntry::new(entry_id.clone(), event_id.clone(), 1, None, 0);
+        let remote_representation = PersistedClipboardRepresentation::new_remote(
+            rep_id.clone(),
+            FormatId::from("public.utf8-plain-text"),
+            Some(MimeType::text_plain()),
+            7,
+        );
+
+        let uc = RestoreClipboardSelectionUseCase::new(
+            Arc::new(MockEntryRepository { entry: Some(entry) }),
+            Arc::new(MockSystemClipboard),
+            Arc::new(MockSelectionRepository {
+                selection: Some(ClipboardSelectionDecision::new(entry_id.clone(), selection)),
+            }),
+            Arc::new(MockRepresentationRepository {
+                reps: HashMap::from([(rep_id.clone(), remote_representation)]),
+            }),
+            Arc::new(MockBlobStore),
+            Arc::new(MockPayloadResolver),
+            Arc::new(NoopClipboardChangeOrigin),
+        );
+
+        let snapshot = uc.build_snapshot(&entry_id).await.unwrap();
+
+        assert_eq!(snapshot.representations[0].bytes, b"fetched".to_vec());
+    }
+
     #[tokio::test]
     async fn build_snapshot_returns_only_paste_representation() {
         let entry_id = EntryId::from("entry-1");
@@ -449,6 +625,7 @@ mod tests {
                 ]),
             }),
             Arc::new(MockBlobStore),
+            Arc::new(MockPayloadResolver),
             Arc::new(NoopClipboardChangeOrigin),
         );
 
@@ -506,6 +683,7 @@ mod tests {
                 ]),
             }),
             Arc::new(MockBlobStore),
+            Arc::new(MockPayloadResolver),
             Arc::new(NoopClipboardChangeOrigin),
         );
 
@@ -528,6 +706,7 @@ mod tests {
                 reps: HashMap::new(),
             }),
             Arc::new(MockBlobStore),
+            Arc::new(MockPayloadResolver),
             Arc::new(MockClipboardChangeOrigin {
                 calls: calls.clone(),
             }),
@@ -0,0 +1,620 @@
+//! Use case for full-text search over clipboard history
+//! 剪贴板历史全文检索用例
+
+use std::sync::Arc;
+use tokio::io::AsyncReadExt;
+use tracing::{info, warn};
+use uc_core::clipboard::{
+    pick_search_text, search_text, ClipboardEntry, ClipboardSearchDocument, ClipboardSearchHit,
+    ClipboardSearchQuery, ContentKind, PersistedClipboardRepresentation, SEARCH_TEXT_MAX_BYTES,
+};
+use uc_core::ports::clipboard::ClipboardSearchIndexPort;
+use uc_core::ports::{
+    BlobStorePort, ClipboardEntryRepositoryPort, ClipboardRepresentationRepositoryPort,
+    ClipboardSelectionRepositoryPort,
+};
+
+const REBUILD_PAGE_SIZE: usize = 200;
+
+/// Error type for search use case
+#[derive(Debug, thiserror::Error)]
+pub enum SearchClipboardError {
+    #[error("Invalid limit: {0}")]
+    InvalidLimit(String),
+
+    #[error("Search index error: {0}")]
+    IndexError(String),
+
+    #[error("Repository error: {0}")]
+    RepositoryError(String),
+}
+
+/// Use case for searching clipboard entries.
+///
+/// The index is opened lazily: the first search after startup (or after the
+/// index was closed) rebuilds it from the repositories. Callers must only run
+/// this while the encryption session is unlocked, since the rebuild reads
+/// decrypted representation data. The index must be closed once the session
+/// is locked; the wiring does this whenever the encryption session is cleared,
+/// and [`close_index`](Self::close_index) covers callers that find it locked.
+///
+/// 索引按需打开：启动后（或索引关闭后）首次检索时从仓储重建。
+/// 调用方需确保加密会话已解锁，会话锁定后需关闭索引以丢弃明文。
+pub struct SearchClipboardEntries {
+    entry_repo: Arc<dyn ClipboardEntryRepositoryPort>,
+    selection_repo: Arc<dyn ClipboardSelectionRepositoryPort>,
+    representation_repo: Arc<dyn ClipboardRepresentationRepositoryPort>,
+    blob_store: Arc<dyn BlobStorePort>,
+    search_index: Arc<dyn ClipboardSearchIndexPort>,
+    max_limit: usize,
+}
+
+impl SearchClipboardEntries {
+    pub fn new(
+        entry_repo: Arc<dyn ClipboardEntryRepositoryPort>,
+        selection_repo: Arc<dyn ClipboardSelectionRepositoryPort>,
+        representation_repo: Arc<dyn ClipboardRepresentationRepositoryPort>,
+        blob_store: Arc<dyn BlobStorePort>,
+        search_index: Arc<dyn ClipboardSearchIndexPort>,
+    ) -> Self {
+        Self {
+            entry_repo,
+            selection_repo,
+            representation_repo,
+            blob_store,
+            search_index,
+            max_limit: 200,
+        }
+    }
+
+    /// Run a search, rebuilding the index first if it is not open.
+    ///
+    /// Hits whose entry has since been deleted or soft-deleted are dropped
+    /// from the result and evicted from the index.
+    pub async fn execute(
+        &self,
+        query: &ClipboardSearchQuery,
+    ) -> Result<Vec<ClipboardSearchHit>, SearchClipboardError> {
+        if query.limit == 0 || query.limit > self.max_limit {
+            return Err(SearchClipboardError::InvalidLimit(format!(
+                "Must be between 1 and {}, got {}",
+                self.max_limit, query.limit
+            )));
+        }
+
+        if !self.search_index.is_open().await {
+            self.rebuild().await?;
+        }
+
+        let hits = self
+            .search_index
+            .search(query)
+            .await
+            .map_err(|e| SearchClipboardError::IndexError(e.to_string()))?;
+
+        let mut live_hits = Vec::with_capacity(hits.len());
+        for hit in hits {
+            let exists = self
+                .entry_repo
+                .get_entry(&hit.entry_id)
+                .await
+                .map_err(|e| SearchClipboardError::RepositoryError(e.to_string()))?
+                .is_some();
+            if exists {
+                live_hits.push(hit);
+            } else if let Err(err) = self.search_index.remove_entry(&hit.entry_id).await {
+                warn!(entry_id = %hit.entry_id, error = %err, "Failed to evict stale search entry");
+            }
+        }
+        Ok(live_hits)
+    }
+
+    /// Drop all indexed plaintext; the next search rebuilds it.
+    /// 丢弃索引中的明文，解锁后的下一次检索会重建索引。
+    pub async fn close_index(&self) {
+        if self.search_index.is_open().await {
+            if let Err(err) = self.search_index.close().await {
+                warn!(error = %err, "Failed to close clipboard search index");
+            }
+        }
+    }
+
+    /// Open an empty index and populate it from all live entries.
+    /// 打开空索引并从所有未删除的条目重建。
+    pub async fn rebuild(&self) -> Result<usize, SearchClipboardError> {
+        self.search_index
+            .open()
+            .await
+            .map_err(|e| SearchClipboardError::IndexError(e.to_string()))?;
+
+        let mut indexed = 0;
+        let mut offset = 0;
+        loop {
+            let entries = self
+                .entry_repo
+                .list_entries(REBUILD_PAGE_SIZE, offset)
+                .await
+                .map_err(|e| SearchClipboardError::RepositoryError(e.to_string()))?;
+            let page_len = entries.len();
+
+            for entry in entries {
+                match self.build_document(&entry).await {
+                    Ok(Some(document)) => {
+                        self.search_index
+                            .index_document(&document)
+                            .await
+                            .map_err(|e| SearchClipboardError::IndexError(e.to_string()))?;
+                        indexed += 1;
+                    }
+                    Ok(None) => {}
+                    Err(err) => {
+                        warn!(entry_id = %entry.entry_id, error = %err, "Skipping entry during search index rebuild");
+                    }
+                }
+            }
+
+            if page_len < REBUILD_PAGE_SIZE {
+                break;
+            }
+            offset += page_len;
+        }
+
+        info!(indexed, "Rebuilt clipboard search index");
+        Ok(indexed)
+    }
+
+    /// Build the document from the entry's preview and paste representations,
+    /// picking the text the same way capture does.
+    async fn build_document(
+        &self,
+        entry: &ClipboardEntry,
+    ) -> anyhow::Result<Option<ClipboardSearchDocument>> {
+        let Some(selection) = self.selection_repo.get_selection(&entry.entry_id).await? else {
+            return Ok(None);
+        };
+
+        let mut candidates = Vec::new();
+        for rep_id in [
+            &selection.selection.preview_rep_id,
+            &selection.selection.paste_rep_id,
+        ] {
+            let Some(rep) = self
+                .representation_repo
+                .get_representation(&entry.event_id, rep_id)
+                .await?
+            else {
+                continue;
+            };
+            let kind = ContentKind::classify(rep.mime_type.as_ref(), rep.format_id.as_ref());
+            if let Some(text) = self.read_search_text(kind, &rep).await? {
+                candidates.push((kind, text));
+            }
+        }
+
+        let Some((text, content_kind)) = pick_search_text(candidates) else {
+            return Ok(None);
+        };
+        let source_device = self
+            .entry_repo
+            .get_entry_source_device(&entry.entry_id)
+            .await?
+            .map(|device| device.as_str().to_string())
+            .unwrap_or_default();
+
+        Ok(Some(ClipboardSearchDocument {
+            entry_id: entry.entry_id.clone(),
+            text,
+            content_kind,
+            source_device,
+            created_at_ms: entry.created_at_ms,
+        }))
+    }
+
+    /// Searchable text of a stored representation.
+    ///
+    /// Large text keeps only a preview inline, so the text is read from the
+    /// blob when there is one; only the first [`SEARCH_TEXT_MAX_BYTES`] are read.
+    ///
+    /// 大文本仅内联预览，存在 blob 时从 blob 读取前缀。
+    async fn read_search_text(
+        &self,
+        kind: ContentKind,
+        rep: &PersistedClipboardRepresentation,
+    ) -> anyhow::Result<Option<String>> {
+        if matches!(kind, ContentKind::Image | ContentKind::Unknown) {
+            return Ok(None);
+        }
+        let Some(blob_id) = rep.blob_id.as_ref() else {
+            return Ok(rep
+                .inline_data
+                .as_deref()
+                .and_then(|data| search_text(kind, data)));
+        };
+        let mut prefix = Vec::new();
+        self.blob_store
+            .open_read(blob_id)
+            .await?
+            .take(SEARCH_TEXT_MAX_BYTES as u64)
+            .read_to_end(&mut prefix)
+            .await?;
+        Ok(search_text(kind, &prefix))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+    use uc_core::clipboard::{
+        ClipboardSelection, ClipboardSelectionDecision, MimeType, PersistedClipboardRepresentation,
+        SelectionPolicyVersion,
+    };
+    use uc_core::ids::{EntryId, EventId, FormatId, RepresentationId};
+    use uc_core::ports::clipboard::ProcessingUpdateOutcome;
+    use uc_core::testing::InMemoryBlobStore;
+    use uc_core::{BlobId, DeviceId, PayloadAvailability};
+    use uc_infra::clipboard::InMemoryClipboardSearchIndex;
+
+    struct Fixture {
+        entries: Vec<ClipboardEntry>,
+        selections: HashMap<EntryId, ClipboardSelectionDecision>,
+        reps: HashMap<RepresentationId, PersistedClipboardRepresentation>,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            Self {
+                entries: Vec::new(),
+                selections: HashMap::new(),
+                reps: HashMap::new(),
+            }
+        }
+
+        fn add(&mut self, id: &str, mime: &str, data: &[u8]) {
+            self.add_rep(id, mime, data, None);
+        }
+
+        fn add_rep(&mut self, id: &str, mime: &str, inline: &[u8], blob_id: Option<BlobId>) {
+            let entry_id = EntryId::from(id);
+            let rep_id = RepresentationId::from(format!("rep-{id}"));
+            self.entries.push(ClipboardEntry::new(
+                entry_id.clone(),
+                EventId::from(format!("event-{id}")),
+                1_000,
+                None,
+                inline.len() as i64,
+            ));
+            self.selections.insert(
+                entry_id.clone(),
+                ClipboardSelectionDecision::new(
+                    entry_id,
+                    ClipboardSelection {
+                        primary_rep_id: rep_id.clone(),
+                        secondary_rep_ids: vec![],
+                        preview_rep_id: rep_id.clone(),
+                        paste_rep_id: rep_id.clone(),
+                        policy_version: SelectionPolicyVersion::V1,
+                    },
+                ),
+            );
+            self.reps.insert(
+                rep_id.clone(),
+                PersistedClipboardRepresentation::new(
+                    rep_id,
+                    FormatId::from("format"),
+                    Some(MimeType(mime.to_string())),
+                    inline.len() as i64,
+                    Some(inline.to_vec()),
+                    blob_id,
+                ),
+            );
+        }
+    }
+
+    struct FixtureEntryRepo(Arc<std::sync::Mutex<Fixture>>);
+
+    #[async_trait]
+    impl ClipboardEntryRepositoryPort for FixtureEntryRepo {
+        async fn save_entry_and_selection(
+            &self,
+            _entry: &ClipboardEntry,
+            _selection: &ClipboardSelectionDecision,
+        ) -> Result<()> {
+            unimplemented!("Not used in tests")
+        }
+
+        async fn get_entry(&self, entry_id: &EntryId) -> Result<Option<ClipboardEntry>> {
+            let fixture = self.0.lock().unwrap();
+            Ok(fixture
+                .entries
+                .iter()
+                .find(|e| &e.entry_id == entry_id)
+                .cloned())
+        }
+
+        async fn list_entries(&self, limit: usize, offset: usize) -> Result<Vec<ClipboardEntry>> {
+            let fixture = self.0.lock().unwrap();
+            Ok(fixture
+                .entries
+                .iter()
+                .skip(offset)
+                .take(limit)
+                .cloned()
+                .collect())
+        }
+
+        async fn delete_entry(&self, _entry_id: &EntryId) -> Result<()> {
+            unimplemented!("Not used in tests")
+        }
+
+        async fn get_entry_source_device(&self, _entry_id: &EntryId) -> Result<Option<DeviceId>> {
+            Ok(Some(DeviceId::new("device-a")))
+        }
+
+        async fn list_retention_candidates(
+            &self,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::RetentionCandidate>> {
+            Ok(Vec::new())
+        }
+
+        async fn soft_delete_entries(
+            &self,
+            _entry_ids: &[uc_core::ids::EntryId],
+            _deleted_at_ms: i64,
+        ) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_soft_deleted_entries(
+            &self,
+            _deleted_before_ms: i64,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn set_entry_pinned(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+            _pinned: bool,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn list_pinned_entries(
+            &self,
+            _limit: usize,
+            _offset: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn list_catch_up_candidates(
+            &self,
+            _since_ms: i64,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::CatchUpCandidate>> {
+            Ok(Vec::new())
+        }
+    }
+
+    struct FixtureSelectionRepo(Arc<std::sync::Mutex<Fixture>>);
+
+    #[async_trait]
+    impl ClipboardSelectionRepositoryPort for FixtureSelectionRepo {
+        async fn get_selection(
+            &self,
+            entry_id: &EntryId,
+        ) -> Result<Option<ClipboardSelectionDecision>> {
+            Ok(self.0.lock().unwrap().selections.get(entry_id).cloned())
+        }
+
+        async fn delete_selection(&self, _entry_id: &EntryId) -> Result<()> {
+            unimplemented!("Not used in tests")
+        }
+    }
+
+    struct FixtureRepresentationRepo(Arc<std::sync::Mutex<Fixture>>);
+
+    #[async_trait]
+    impl ClipboardRepresentationRepositoryPort for FixtureRepresentationRepo {
+        async fn get_representation(
+            &self,
+            _event_id: &EventId,
+            representation_id: &RepresentationId,
+        ) -> Result<Option<PersistedClipboardRepresentation>> {
+            Ok(self.0.lock().unwrap().reps.get(representation_id).cloned())
+        }
+
+        async fn get_representation_by_id(
+            &self,
+            _representation_id: &RepresentationId,
+        ) -> Result<Option<PersistedClipboardRepresentation>> {
+            unimplemented!("Not used in tests")
+        }
+
+        async fn get_representation_by_blob_id(
+            &self,
+            _blob_id: &BlobId,
+        ) -> Result<Option<PersistedClipboardRepresentation>> {
+            unimplemented!("Not used in tests")
+        }
+
+        async fn update_blob_id(
+            &self,
+            _representation_id: &RepresentationId,
+            _blob_id: &BlobId,
+        ) -> Result<()> {
+            unimplemented!("Not used in tests")
+        }
+
+        async fn update_blob_id_if_none(
+            &self,
+            _representation_id: &RepresentationId,
+            _blob_id: &BlobId,
+        ) -> Result<bool> {
+            unimplemented!("Not used in tests")
+        }
+
+        async fn update_processing_result(
+            &self,
+            _rep_id: &RepresentationId,
+            _expected_states: &[PayloadAvailability],
+            _blob_id: Option<&BlobId>,
+            _new_state: PayloadAvailability,
+            _last_error: Option<&str>,
+        ) -> Result<ProcessingUpdateOutcome> {
+            unimplemented!("Not used in tests")
+        }
+
+        async fn count_inline_representations(&self) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_inline_representation_ids(
+            &self,
+            _after: Option<&uc_core::ids::RepresentationId>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<(uc_core::ids::EventId, uc_core::ids::RepresentationId)>> {
+            Ok(Vec::new())
+        }
+
+        async fn update_inline_data(
+            &self,
+            _representation_id: &uc_core::ids::RepresentationId,
+            _inline_data: &[u8],
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn usecase(fixture: Arc<std::sync::Mutex<Fixture>>) -> SearchClipboardEntries {
+        usecase_with(
+            fixture,
+            Arc::new(InMemoryBlobStore::new()),
+            Arc::new(InMemoryClipboardSearchIndex::new()),
+        )
+    }
+
+    fn usecase_with(
+        fixture: Arc<std::sync::Mutex<Fixture>>,
+        blob_store: Arc<InMemoryBlobStore>,
+        search_index: Arc<InMemoryClipboardSearchIndex>,
+    ) -> SearchClipboardEntries {
+        SearchClipboardEntries::new(
+            Arc::new(FixtureEntryRepo(fixture.clone())),
+            Arc::new(FixtureSelectionRepo(fixture.clone())),
+            Arc::new(FixtureRepresentationRepo(fixture)),
+            blob_store,
+            search_index,
+        )
+    }
+
+    fn query(text: &str) -> ClipboardSearchQuery {
+        ClipboardSearchQuery {
+            text: text.to_string(),
+            limit: 20,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn first_search_rebuilds_index_from_text_entries() {
+        let fixture = Arc::new(std::sync::Mutex::new(Fixture::new()));
+        {
+            let mut f = fixture.lock().unwrap();
+            f.add("text", "text/plain", b"meeting notes for friday");
+            f.add("image", "image/png", b"meeting");
+        }
+        let uc = usecase(fixture);
+
+        let hits = uc.execute(&query("meeting")).await.expect("search");
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].entry_id, EntryId::from("text"));
+        assert_eq!(hits[0].content_kind, ContentKind::Text);
+        assert_eq!(hits[0].source_device, "device-a");
+    }
+
+    #[tokio::test]
+    async fn deleted_entries_are_dropped_from_results() {
+        let fixture = Arc::new(std::sync::Mutex::new(Fixture::new()));
+        {
+            let mut f = fixture.lock().unwrap();
+            f.add("keep", "text/plain", b"alpha report");
+            f.add("gone", "text/plain", b"alpha draft");
+        }
+        let uc = usecase(fixture.clone());
+        uc.rebuild().await.expect("rebuild");
+
+        fixture
+            .lock()
+            .unwrap()
+            .entries
+            .retain(|e| e.entry_id != EntryId::from("gone"));
+
+        let hits = uc.execute(&query("alpha")).await.expect("search");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].entry_id, EntryId::from("keep"));
+    }
+
+    #[tokio::test]
+    async fn rebuild_reads_large_text_from_blob_instead_of_preview() {
+        let fixture = Arc::new(std::sync::Mutex::new(Fixture::new()));
+        let blob_id = BlobId::from("blob-large");
+        fixture.lock().unwrap().add_rep(
+            "large",
+            "text/plain",
+            b"quarterly report preview",
+            Some(blob_id.clone()),
+        );
+        let blob_store = Arc::new(
+            InMemoryBlobStore::new()
+                .with_blob(&blob_id, b"quarterly report preview ... appendix budget"),
+        );
+        let uc = usecase_with(
+            fixture,
+            blob_store,
+            Arc::new(InMemoryClipboardSearchIndex::new()),
+        );
+
+        let hits = uc.execute(&query("appendix")).await.expect("search");
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].entry_id, EntryId::from("large"));
+    }
+
+    #[tokio::test]
+    async fn close_index_drops_text_until_next_search() {
+        let fixture = Arc::new(std::sync::Mutex::new(Fixture::new()));
+        fixture
+            .lock()
+            .unwrap()
+            .add("text", "text/plain", b"secret notes");
+        let search_index = Arc::new(InMemoryClipboardSearchIndex::new());
+        let uc = usecase_with(
+            fixture,
+            Arc::new(InMemoryBlobStore::new()),
+            search_index.clone(),
+        );
+        uc.execute(&query("secret")).await.expect("search");
+
+        uc.close_index().await;
+        assert!(!search_index.is_open().await);
+
+        let hits = uc.execute(&query("secret")).await.expect("search");
+        assert_eq!(hits.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_limit() {
+        let uc = usecase(Arc::new(std::sync::Mutex::new(Fixture::new())));
+        let mut q = query("x");
+        q.limit = 0;
+        assert!(matches!(
+            uc.execute(&q).await,
+            Err(SearchClipboardError::InvalidLimit(_))
+        ));
+    }
+}
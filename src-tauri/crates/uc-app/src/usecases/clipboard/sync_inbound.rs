@@ -0,0 +1,981 @@
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, info_span, warn, Instrument};
+
+use uc_core::clipboard::{decode_sealed_payload, RemotePayloadRef};
+use uc_core::ids::{DeviceId, EventId, RepresentationId};
+use uc_core::network::{
+    pinned_device, ClipboardApplyMode, ClipboardMessage, ClipboardPayload, DeviceSyncPolicy,
+};
+use uc_core::ports::clipboard::RemotePayloadRepositoryPort;
+use uc_core::ports::{
+    ClipboardChangeOriginPort, DeviceIdentityPort, EncryptionPort, EncryptionSessionPort,
+    NetworkPort, PairedDeviceRepositoryPort, SettingsPort, SystemClipboardPort,
+};
+use uc_core::security::aad;
+use uc_core::security::model::EncryptedBlob;
+use uc_core::{ClipboardChangeOrigin, PeerId, SystemClipboardSnapshot};
+
+use crate::usecases::internal::capture_clipboard::CaptureClipboardUseCase;
+
+/// How long the `LocalRestore` origin marker stays valid after writing to the system clipboard.
+const APPLIED_ORIGIN_TTL: Duration = Duration::from_secs(2);
+
+/// Result of applying an inbound clipboard message.
+///
+/// 处理入站剪贴板消息的结果。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InboundClipboardOutcome {
+    /// Message originated from this device and was dropped.
+    /// 消息来自本设备，已忽略。
+    Ignored,
+    /// The sync policy set for the sending device refuses this content.
+    /// 为发送设备设置的同步策略拒收该内容，已丢弃。
+    Refused,
+    /// `origin_device_id` does not belong to the peer the message came from.
+    /// 消息声明的来源设备与发送方不符，已丢弃。
+    OriginMismatch,
+    /// Snapshot was persisted into history and written to the system clipboard.
+    /// 快照已写入历史记录并写入系统剪贴板。
+    AppliedToClipboard(EventId),
+    /// Snapshot was persisted directly into history.
+    /// 快照已直接写入历史记录。
+    Persisted(EventId),
+}
+
+/// Decrypt a clipboard message received from a peer and apply it locally.
+///
+/// 解密从对端收到的剪贴板消息并在本地应用。
+///
+/// # Behavior / 行为
+/// - 1. Drop messages that originated from this device (防止回环)
+/// - 2. Drop messages from a device whose sync policy is send-only (同步方向)
+/// - 3. Decrypt the payload with the space `MasterKey` (端到端解密)
+/// - 4. Drop content types the device's policy does not accept (内容类型)
+/// - 5. Persist into history with the sender as source device (记录来源设备)
+/// - 6. Also write to the system clipboard when `sync.apply_remote_to_clipboard`
+///   is on and the device's policy is not history-only
+///
+/// - 6. 开启 `sync.apply_remote_to_clipboard` 且设备策略不是“仅历史”时同时写入系统剪贴板
+///
+/// Large representations may arrive as manifests only. They are pulled right
+/// away when writing to the system clipboard, and otherwise persisted as
+/// `Remote` and pulled on first use.
+///
+/// 大型表示可能只携带清单：写入系统剪贴板时立即拉取，否则以 `Remote` 状态入库，首次使用时再拉取。
+///
+/// The entry is recorded with `ClipboardChangeOrigin::RemotePush` and the
+/// system clipboard write is marked `LocalRestore`, so the watcher neither
+/// stores it twice nor syncs it back out. Messages resent for offline
+/// catch-up are only added to history.
+///
+/// 条目以 `RemotePush` 记录，写入系统剪贴板时标记为 `LocalRestore`，监听器不会重复入库或再次同步；
+/// 离线补同步的消息只写入历史。
+pub struct SyncInboundClipboardUseCase {
+    encryption: Arc<dyn EncryptionPort>,
+    encryption_session: Arc<dyn EncryptionSessionPort>,
+    device_identity: Arc<dyn DeviceIdentityPort>,
+    settings: Arc<dyn SettingsPort>,
+    system_clipboard: Arc<dyn SystemClipboardPort>,
+    clipboard_change_origin: Arc<dyn ClipboardChangeOriginPort>,
+    network: Arc<dyn NetworkPort>,
+    remote_payloads: Arc<dyn RemotePayloadRepositoryPort>,
+    paired_device_repo: Arc<dyn PairedDeviceRepositoryPort>,
+    capture: CaptureClipboardUseCase,
+}
+
+impl SyncInboundClipboardUseCase {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        encryption: Arc<dyn EncryptionPort>,
+        encryption_session: Arc<dyn EncryptionSessionPort>,
+        device_identity: Arc<dyn DeviceIdentityPort>,
+        settings: Arc<dyn SettingsPort>,
+        system_clipboard: Arc<dyn SystemClipboardPort>,
+        clipboard_change_origin: Arc<dyn ClipboardChangeOriginPort>,
+        network: Arc<dyn NetworkPort>,
+        remote_payloads: Arc<dyn RemotePayloadRepositoryPort>,
+        paired_device_repo: Arc<dyn PairedDeviceRepositoryPort>,
+        capture: CaptureClipboardUseCase,
+    ) -> Self {
+        Self {
+            encryption,
+            encryption_session,
+            device_identity,
+            settings,
+            system_clipboard,
+            clipboard_change_origin,
+            network,
+            remote_payloads,
+            paired_device_repo,
+            capture,
+        }
+    }
+
+    pub async fn execute(&self, message: ClipboardMessage) -> Result<InboundClipboardOutcome> {
+        let span = info_span!(
+            "usecase.sync_inbound_clipboard.execute",
+            message_id = %message.id,
+            origin_device_id = %message.origin_device_id,
+            catch_up = message.catch_up,
+        );
+        async move {
+            if message.origin_device_id == self.device_identity.current_device_id().as_str() {
+                info!("Ignoring clipboard message from local device");
+                return Ok(InboundClipboardOutcome::Ignored);
+            }
+
+            let Some(policy) = self.verified_sender_policy(&message).await? else {
+                warn!(
+                    sender_peer_id = ?message.sender_peer_id,
+                    "Dropping clipboard message whose origin device does not match the sender"
+                );
+                return Ok(InboundClipboardOutcome::OriginMismatch);
+            };
+            if !policy.allows_receiving() {
+                info!("Refusing clipboard message from send-only device");
+                return Ok(InboundClipboardOutcome::Refused);
+            }
+
+            let ClipboardPayload {
+                snapshot,
+                mut remote_payloads,
+            } = self.decrypt_payload(&message).await?;
+            let Some(mut snapshot) = policy.retain_allowed(snapshot) else {
+                info!("Refusing clipboard content types not accepted from device");
+                return Ok(InboundClipboardOutcome::Refused);
+            };
+            remote_payloads.retain(|remote| {
+                snapshot
+                    .representations
+                    .iter()
+                    .any(|rep| rep.id == remote.representation_id)
+            });
+            let source_device = DeviceId::new(message.origin_device_id.clone());
+            let settings = self.settings.load().await?;
+
+            if settings.sync.apply_remote_to_clipboard
+                && policy.apply_mode == ClipboardApplyMode::AutoApply
+                && !message.catch_up
+            {
+                // The system clipboard needs real bytes, so lazy payloads are pulled now.
+                self.fetch_remote_payloads(&mut snapshot, remote_payloads)
+                    .await?;
+                let event_id = self
+                    .capture
+                    .execute_remote_push(snapshot.clone(), source_device, &[])
+                    .await?;
+                self.clipboard_change_origin
+                    .set_next_origin(ClipboardChangeOrigin::LocalRestore, APPLIED_ORIGIN_TTL)
+                    .await;
+
+                if let Err(err) = self.system_clipboard.write_snapshot(snapshot) {
+                    self.clipboard_change_origin
+                        .consume_origin_or_default(ClipboardChangeOrigin::LocalCapture)
+                        .await;
+                    return Err(err);
+                }
+
+                info!(event_id = %event_id, "Applied remote clipboard to system clipboard");
+                return Ok(InboundClipboardOutcome::AppliedToClipboard(event_id));
+            }
+
+            for remote in &remote_payloads {
+                self.remote_payloads.insert_remote_payload(remote).await?;
+            }
+            let event_id = self
+                .capture
+                .execute_remote_push(snapshot, source_device, &remote_payloads)
+                .await?;
+            info!(event_id = %event_id, "Persisted remote clipboard");
+            Ok(InboundClipboardOutcome::Persisted(event_id))
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Sync policy of the paired device the message arrived from, or `None`
+    /// when `origin_device_id` does not belong to that device.
+    ///
+    /// Legacy pairings have no device id on record. For those the origin is
+    /// looked up among the other paired devices, and a claim to a device that
+    /// was paired from a different peer is refused.
+    ///
+    /// 来源设备 ID 必须与发送方配对时登记的设备 ID 一致；
+    /// 早期配对记录未登记设备 ID 时，改为核对声明的设备所登记的 peer ID 是否为发送方。
+    async fn verified_sender_policy(
+        &self,
+        message: &ClipboardMessage,
+    ) -> Result<Option<DeviceSyncPolicy>> {
+        let Some(peer_id) = message.sender_peer_id.as_deref() else {
+            return Ok(None);
+        };
+        let peer_id = PeerId::from(peer_id);
+        let Some(device) = self.paired_device_repo.get_by_peer_id(&peer_id).await? else {
+            return Ok(None);
+        };
+        if device.device_id.is_empty() {
+            let devices = self.paired_device_repo.list_all().await?;
+            let claimed = pinned_device(&devices, &peer_id, &message.origin_device_id);
+            if claimed.is_some_and(|claimed| claimed.peer_id != peer_id) {
+                return Ok(None);
+            }
+        } else if device.device_id != message.origin_device_id {
+            return Ok(None);
+        }
+        Ok(Some(
+            self.paired_device_repo.get_sync_policy(&peer_id).await?,
+        ))
+    }
+
+    /// Pull lazily synced representations into the snapshot.
+    ///
+    /// 从持有数据的设备拉取延迟同步的表示，解密后填回快照。
+    async fn fetch_remote_payloads(
+        &self,
+        snapshot: &mut SystemClipboardSnapshot,
+        remote_payloads: Vec<RemotePayloadRef>,
+    ) -> Result<()> {
+        for remote in remote_payloads {
+            let sealed = self.network.fetch_blob_from_any(remote.manifest).await?;
+            let bytes = self
+                .open_remote_payload(&remote.representation_id, &sealed)
+                .await?;
+            if let Some(rep) = snapshot
+                .representations
+                .iter_mut()
+                .find(|rep| rep.id == remote.representation_id)
+            {
+                rep.bytes = bytes;
+            }
+        }
+        Ok(())
+    }
+
+    async fn open_remote_payload(
+        &self,
+        rep_id: &RepresentationId,
+        sealed: &[u8],
+    ) -> Result<Vec<u8>> {
+        let sealed = decode_sealed_payload(sealed)?;
+        let master_key = self
+            .encryption_session
+            .get_master_key_for_epoch(sealed.key_epoch)
+            .await?;
+        Ok(self
+            .encryption
+            .decrypt_blob(&master_key, &sealed, &aad::for_remote_payload(rep_id))
+            .await?)
+    }
+
+    async fn decrypt_payload(&self, message: &ClipboardMessage) -> Result<ClipboardPayload> {
+        let encrypted: EncryptedBlob = serde_json::from_slice(&message.encrypted_content)
+            .map_err(|e| anyhow::anyhow!("failed to parse encrypted clipboard payload: {e}"))?;
+        let master_key = self
+            .encryption_session
+            .get_master_key_for_epoch(encrypted.key_epoch)
+            .await?;
+        let plaintext = self
+            .encryption
+            .decrypt_blob(
+                &master_key,
+                &encrypted,
+                &aad::for_clipboard_message(&message.id),
+            )
+            .await?;
+
+        serde_json::from_slice(&plaintext)
+            .map_err(|e| anyhow::anyhow!("failed to decode clipboard snapshot: {e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use uc_core::clipboard::{
+        encode_sealed_payload, ObservedClipboardRepresentation, PersistedClipboardRepresentation,
+        SelectRepresentationPolicyV1, SyncFilterPolicyV1,
+    };
+    use uc_core::ids::{EntryId, FormatId, RepresentationId};
+    use uc_core::network::{BlobManifest, PairedDevice, PairingState, SyncDirection};
+    use uc_core::ports::clipboard::{
+        ClipboardRepresentationNormalizerPort, RepresentationCachePort, SpoolQueuePort,
+        SpoolRequest,
+    };
+    use uc_core::ports::{ClipboardEntryRepositoryPort, ClipboardEventWriterPort};
+    use uc_core::security::model::{EncryptionAlgo, MasterKey};
+    use uc_core::settings::model::{ContentTypes, Settings};
+    use uc_core::testing::{InMemoryPairedDeviceRepository, TestNetwork};
+    use uc_core::{
+        ClipboardEntry, ClipboardEvent, ClipboardSelectionDecision, DeviceId, MimeType,
+        PayloadAvailability,
+    };
+    use uc_infra::clipboard::InMemoryClipboardSearchIndex;
+    use uc_infra::security::{EncryptionRepository, InMemoryEncryptionSession};
+
+    struct TestDeviceIdentity;
+
+    impl DeviceIdentityPort for TestDeviceIdentity {
+        fn current_device_id(&self) -> DeviceId {
+            DeviceId::new("device-local")
+        }
+    }
+
+    struct TestSettings {
+        settings: Settings,
+    }
+
+    #[async_trait]
+    impl SettingsPort for TestSettings {
+        async fn load(&self) -> Result<Settings> {
+            Ok(self.settings.clone())
+        }
+
+        async fn save(&self, _settings: &Settings) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    struct TestSystemClipboard {
+        writes: Arc<Mutex<Vec<SystemClipboardSnapshot>>>,
+    }
+
+    impl SystemClipboardPort for TestSystemClipboard {
+        fn read_snapshot(&self) -> Result<SystemClipboardSnapshot> {
+            Err(anyhow::anyhow!("read not supported"))
+        }
+
+        fn write_snapshot(&self, snapshot: SystemClipboardSnapshot) -> Result<()> {
+            self.writes.lock().unwrap().push(snapshot);
+            Ok(())
+        }
+    }
+
+    struct TestClipboardChangeOrigin {
+        origins: Arc<Mutex<Vec<ClipboardChangeOrigin>>>,
+    }
+
+    #[async_trait]
+    impl ClipboardChangeOriginPort for TestClipboardChangeOrigin {
+        async fn set_next_origin(&self, origin: ClipboardChangeOrigin, _ttl: Duration) {
+            self.origins.lock().unwrap().push(origin);
+        }
+
+        async fn consume_origin_or_default(
+            &self,
+            default_origin: ClipboardChangeOrigin,
+        ) -> ClipboardChangeOrigin {
+            default_origin
+        }
+    }
+
+    struct TestEntryRepository {
+        save_calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl ClipboardEntryRepositoryPort for TestEntryRepository {
+        async fn save_entry_and_selection(
+            &self,
+            _entry: &ClipboardEntry,
+            _selection: &ClipboardSelectionDecision,
+        ) -> Result<()> {
+            self.save_calls.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn get_entry(&self, _entry_id: &EntryId) -> Result<Option<ClipboardEntry>> {
+            Ok(None)
+        }
+
+        async fn list_entries(&self, _limit: usize, _offset: usize) -> Result<Vec<ClipboardEntry>> {
+            Ok(vec![])
+        }
+
+        async fn delete_entry(&self, _entry_id: &EntryId) -> Result<()> {
+            Ok(())
+        }
+
+        async fn list_retention_candidates(
+            &self,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::RetentionCandidate>> {
+            Ok(Vec::new())
+        }
+
+        async fn soft_delete_entries(
+            &self,
+            _entry_ids: &[uc_core::ids::EntryId],
+            _deleted_at_ms: i64,
+        ) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_soft_deleted_entries(
+            &self,
+            _deleted_before_ms: i64,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_entry_source_device(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+        ) -> anyhow::Result<Option<uc_core::ids::DeviceId>> {
+            Ok(None)
+        }
+
+        async fn set_entry_pinned(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+            _pinned: bool,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn list_pinned_entries(
+            &self,
+            _limit: usize,
+            _offset: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn list_catch_up_candidates(
+            &self,
+            _since_ms: i64,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::CatchUpCandidate>> {
+            Ok(Vec::new())
+        }
+    }
+
+    struct TestEventWriter {
+        events: Arc<Mutex<Vec<ClipboardEvent>>>,
+        representations: Arc<Mutex<Vec<PersistedClipboardRepresentation>>>,
+    }
+
+    #[async_trait]
+    impl ClipboardEventWriterPort for TestEventWriter {
+        async fn insert_event(
+            &self,
+            event: &ClipboardEvent,
+            representations: &Vec<PersistedClipboardRepresentation>,
+        ) -> Result<()> {
+            self.events.lock().unwrap().push(event.clone());
+            self.representations
+                .lock()
+                .unwrap()
+                .extend(representations.iter().cloned());
+            Ok(())
+        }
+
+        async fn delete_event_and_representations(&self, _event_id: &EventId) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    struct TestNormalizer;
+
+    #[async_trait]
+    impl ClipboardRepresentationNormalizerPort for TestNormalizer {
+        async fn normalize(
+            &self,
+            observed: &ObservedClipboardRepresentation,
+        ) -> Result<PersistedClipboardRepresentation> {
+            Ok(PersistedClipboardRepresentation::new(
+                observed.id.clone(),
+                observed.format_id.clone(),
+                observed.mime.clone(),
+                observed.size_bytes(),
+                Some(observed.bytes.clone()),
+                None,
+            ))
+        }
+    }
+
+    struct TestRepresentationCache;
+
+    #[async_trait]
+    impl RepresentationCachePort for TestRepresentationCache {
+        async fn put(&self, _rep_id: &RepresentationId, _bytes: Vec<u8>) {}
+
+        async fn get(&self, _rep_id: &RepresentationId) -> Option<Vec<u8>> {
+            None
+        }
+
+        async fn mark_completed(&self, _rep_id: &RepresentationId) {}
+
+        async fn mark_spooling(&self, _rep_id: &RepresentationId) {}
+
+        async fn remove(&self, _rep_id: &RepresentationId) {}
+    }
+
+    struct TestSpoolQueue;
+
+    #[async_trait]
+    impl SpoolQueuePort for TestSpoolQueue {
+        async fn enqueue(&self, _request: SpoolRequest) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    struct TestRemotePayloads {
+        inserted: Arc<Mutex<Vec<RemotePayloadRef>>>,
+    }
+
+    #[async_trait]
+    impl RemotePayloadRepositoryPort for TestRemotePayloads {
+        async fn insert_remote_payload(&self, remote: &RemotePayloadRef) -> Result<()> {
+            self.inserted.lock().unwrap().push(remote.clone());
+            Ok(())
+        }
+
+        async fn get_by_representation_id(
+            &self,
+            _representation_id: &RepresentationId,
+        ) -> Result<Option<RemotePayloadRef>> {
+            Ok(None)
+        }
+
+        async fn delete_remote_payload(&self, _representation_id: &RepresentationId) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    const LARGE_BYTES: &[u8] = b"large payload";
+
+    struct Harness {
+        use_case: SyncInboundClipboardUseCase,
+        master_key: MasterKey,
+        /// `LARGE_BYTES` as the sending peer offers it
+        sealed_large: Vec<u8>,
+        writes: Arc<Mutex<Vec<SystemClipboardSnapshot>>>,
+        origins: Arc<Mutex<Vec<ClipboardChangeOrigin>>>,
+        save_calls: Arc<AtomicUsize>,
+        events: Arc<Mutex<Vec<ClipboardEvent>>>,
+        representations: Arc<Mutex<Vec<PersistedClipboardRepresentation>>>,
+        remote_inserted: Arc<Mutex<Vec<RemotePayloadRef>>>,
+    }
+
+    async fn build_harness(apply_remote_to_clipboard: bool) -> Harness {
+        build_harness_with_policy(apply_remote_to_clipboard, DeviceSyncPolicy::default()).await
+    }
+
+    async fn build_harness_with_policy(
+        apply_remote_to_clipboard: bool,
+        policy: DeviceSyncPolicy,
+    ) -> Harness {
+        build_harness_sealed_for(apply_remote_to_clipboard, policy, "rep-text").await
+    }
+
+    /// Harness whose peer serves `LARGE_BYTES` sealed for `sealed_rep_id`.
+    async fn build_harness_sealed_for(
+        apply_remote_to_clipboard: bool,
+        policy: DeviceSyncPolicy,
+        sealed_rep_id: &str,
+    ) -> Harness {
+        build_harness_with_devices(
+            apply_remote_to_clipboard,
+            policy,
+            sealed_rep_id,
+            vec![paired_device("peer-remote", "device-remote")],
+        )
+        .await
+    }
+
+    fn paired_device(peer_id: &str, device_id: &str) -> PairedDevice {
+        PairedDevice {
+            peer_id: PeerId::from(peer_id),
+            pairing_state: PairingState::Trusted,
+            identity_fingerprint: "fp".to_string(),
+            paired_at: Utc::now(),
+            last_seen_at: None,
+            device_name: "Remote".to_string(),
+            device_id: device_id.to_string(),
+        }
+    }
+
+    async fn build_harness_with_devices(
+        apply_remote_to_clipboard: bool,
+        policy: DeviceSyncPolicy,
+        sealed_rep_id: &str,
+        devices: Vec<PairedDevice>,
+    ) -> Harness {
+        let master_key = MasterKey::generate().unwrap();
+        let session = Arc::new(InMemoryEncryptionSession::new());
+        session.set_master_key(master_key.clone()).await.unwrap();
+        let sealed_large = seal_remote_payload(&master_key, sealed_rep_id).await;
+
+        let mut settings = Settings::default();
+        settings.sync.apply_remote_to_clipboard = apply_remote_to_clipboard;
+
+        let writes = Arc::new(Mutex::new(Vec::new()));
+        let origins = Arc::new(Mutex::new(Vec::new()));
+        let save_calls = Arc::new(AtomicUsize::new(0));
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let representations = Arc::new(Mutex::new(Vec::new()));
+        let remote_inserted = Arc::new(Mutex::new(Vec::new()));
+
+        let device_identity: Arc<dyn DeviceIdentityPort> = Arc::new(TestDeviceIdentity);
+        let capture = CaptureClipboardUseCase::new(
+            Arc::new(TestEntryRepository {
+                save_calls: save_calls.clone(),
+            }),
+            Arc::new(TestEventWriter {
+                events: events.clone(),
+                representations: representations.clone(),
+            }),
+            Arc::new(SelectRepresentationPolicyV1::new()),
+            Arc::new(TestNormalizer),
+            device_identity.clone(),
+            Arc::new(TestRepresentationCache),
+            Arc::new(TestSpoolQueue),
+            Arc::new(InMemoryClipboardSearchIndex::new()),
+            Arc::new(SyncFilterPolicyV1::new()),
+            Arc::new(TestSettings {
+                settings: settings.clone(),
+            }),
+        );
+
+        let use_case = SyncInboundClipboardUseCase::new(
+            Arc::new(EncryptionRepository),
+            session,
+            device_identity,
+            Arc::new(TestSettings { settings }),
+            Arc::new(TestSystemClipboard {
+                writes: writes.clone(),
+            }),
+            Arc::new(TestClipboardChangeOrigin {
+                origins: origins.clone(),
+            }),
+            Arc::new(TestNetwork::new().serving(&sealed_large)),
+            Arc::new(TestRemotePayloads {
+                inserted: remote_inserted.clone(),
+            }),
+            Arc::new(
+                InMemoryPairedDeviceRepository::with_devices(devices)
+                    .with_sync_policy("peer-remote", policy),
+            ),
+            capture,
+        );
+
+        Harness {
+            use_case,
+            master_key,
+            sealed_large,
+            writes,
+            origins,
+            save_calls,
+            events,
+            representations,
+            remote_inserted,
+        }
+    }
+
+    fn text_snapshot(bytes: &[u8]) -> SystemClipboardSnapshot {
+        SystemClipboardSnapshot {
+            ts_ms: 42,
+            representations: vec![ObservedClipboardRepresentation {
+                id: RepresentationId::from("rep-text"),
+                format_id: FormatId::from("text"),
+                mime: Some(MimeType::text_plain()),
+                bytes: bytes.to_vec(),
+            }],
+        }
+    }
+
+    async fn encrypted_message(master_key: &MasterKey, origin_device_id: &str) -> ClipboardMessage {
+        let payload = ClipboardPayload {
+            snapshot: text_snapshot(b"hello"),
+            remote_payloads: vec![],
+        };
+        encrypted_payload(master_key, origin_device_id, payload).await
+    }
+
+    async fn seal_remote_payload(master_key: &MasterKey, rep_id: &str) -> Vec<u8> {
+        let sealed = EncryptionRepository
+            .encrypt_blob(
+                master_key,
+                LARGE_BYTES,
+                &aad::for_remote_payload(&RepresentationId::from(rep_id)),
+                EncryptionAlgo::XChaCha20Poly1305,
+            )
+            .await
+            .unwrap();
+        encode_sealed_payload(sealed).unwrap()
+    }
+
+    /// A message whose only representation is synced metadata-first.
+    async fn lazy_message(harness: &Harness) -> ClipboardMessage {
+        let payload = ClipboardPayload {
+            snapshot: text_snapshot(b""),
+            remote_payloads: vec![RemotePayloadRef {
+                representation_id: RepresentationId::from("rep-text"),
+                content_hash: text_snapshot(LARGE_BYTES).representations[0]
+                    .content_hash()
+                    .0,
+                size_bytes: LARGE_BYTES.len() as u64,
+                manifest: BlobManifest::from_bytes(&harness.sealed_large, 4),
+            }],
+        };
+        encrypted_payload(&harness.master_key, "device-remote", payload).await
+    }
+
+    async fn encrypted_payload(
+        master_key: &MasterKey,
+        origin_device_id: &str,
+        payload: ClipboardPayload,
+    ) -> ClipboardMessage {
+        let content_hash = payload
+            .snapshot
+            .snapshot_hash_with_remote(&payload.remote_payloads);
+        let message_id = "message-1".to_string();
+        let encrypted = EncryptionRepository
+            .encrypt_blob(
+                master_key,
+                &serde_json::to_vec(&payload).unwrap(),
+                &aad::for_clipboard_message(&message_id),
+                EncryptionAlgo::XChaCha20Poly1305,
+            )
+            .await
+            .unwrap();
+
+        ClipboardMessage {
+            id: message_id,
+            content_hash: content_hash.to_string(),
+            encrypted_content: serde_json::to_vec(&encrypted).unwrap(),
+            timestamp: Utc::now(),
+            origin_device_id: origin_device_id.to_string(),
+            origin_device_name: "Remote".to_string(),
+            payload_manifest: None,
+            catch_up: false,
+            sender_peer_id: Some("peer-remote".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn execute_persists_and_writes_system_clipboard_as_local_restore() {
+        let harness = build_harness(true).await;
+        let message = encrypted_message(&harness.master_key, "device-remote").await;
+
+        let outcome = harness.use_case.execute(message).await.unwrap();
+
+        assert!(matches!(
+            outcome,
+            InboundClipboardOutcome::AppliedToClipboard(_)
+        ));
+        let writes = harness.writes.lock().unwrap();
+        assert_eq!(writes.len(), 1);
+        assert_eq!(writes[0].representations[0].bytes, b"hello".to_vec());
+        assert_eq!(
+            *harness.origins.lock().unwrap(),
+            vec![ClipboardChangeOrigin::LocalRestore]
+        );
+        assert_eq!(harness.save_calls.load(Ordering::SeqCst), 1);
+        let events = harness.events.lock().unwrap();
+        assert_eq!(events[0].source_device, DeviceId::new("device-remote"));
+    }
+
+    #[tokio::test]
+    async fn execute_only_persists_catch_up_messages() {
+        let harness = build_harness(true).await;
+        let mut message = encrypted_message(&harness.master_key, "device-remote").await;
+        message.catch_up = true;
+
+        let outcome = harness.use_case.execute(message).await.unwrap();
+
+        assert!(matches!(outcome, InboundClipboardOutcome::Persisted(_)));
+        assert!(harness.writes.lock().unwrap().is_empty());
+        assert!(harness.origins.lock().unwrap().is_empty());
+        assert_eq!(harness.save_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn execute_persists_directly_when_clipboard_write_disabled() {
+        let harness = build_harness(false).await;
+        let message = encrypted_message(&harness.master_key, "device-remote").await;
+
+        let outcome = harness.use_case.execute(message).await.unwrap();
+
+        assert!(matches!(outcome, InboundClipboardOutcome::Persisted(_)));
+        assert!(harness.writes.lock().unwrap().is_empty());
+        assert_eq!(harness.save_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn execute_persists_lazy_payload_as_remote() {
+        let harness = build_harness(false).await;
+        let message = lazy_message(&harness).await;
+        let content_hash = message.content_hash.clone();
+
+        let outcome = harness.use_case.execute(message).await.unwrap();
+
+        assert!(matches!(outcome, InboundClipboardOutcome::Persisted(_)));
+        assert_eq!(
+            harness.events.lock().unwrap()[0].snapshot_hash.to_string(),
+            content_hash
+        );
+        let representations = harness.representations.lock().unwrap();
+        assert_eq!(representations.len(), 1);
+        assert_eq!(
+            representations[0].payload_state(),
+            PayloadAvailability::Remote
+        );
+        assert_eq!(representations[0].size_bytes, LARGE_BYTES.len() as i64);
+        assert_eq!(harness.remote_inserted.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn execute_fetches_lazy_payload_before_writing_system_clipboard() {
+        let harness = build_harness(true).await;
+        let message = lazy_message(&harness).await;
+
+        harness.use_case.execute(message).await.unwrap();
+
+        let writes = harness.writes.lock().unwrap();
+        assert_eq!(writes[0].representations[0].bytes, LARGE_BYTES.to_vec());
+        assert!(harness.remote_inserted.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn execute_rejects_lazy_payload_sealed_for_another_representation() {
+        // Sealed under the space key but bound to a different representation.
+        let harness =
+            build_harness_sealed_for(true, DeviceSyncPolicy::default(), "rep-other").await;
+        let message = lazy_message(&harness).await;
+
+        assert!(harness.use_case.execute(message).await.is_err());
+        assert!(harness.writes.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn execute_ignores_messages_from_local_device() {
+        let harness = build_harness(true).await;
+        let message = encrypted_message(&harness.master_key, "device-local").await;
+
+        let outcome = harness.use_case.execute(message).await.unwrap();
+
+        assert_eq!(outcome, InboundClipboardOutcome::Ignored);
+        assert!(harness.writes.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn execute_drops_message_claiming_another_devices_origin() {
+        let harness = build_harness(true).await;
+        let message = encrypted_message(&harness.master_key, "device-third").await;
+
+        let outcome = harness.use_case.execute(message).await.unwrap();
+
+        assert_eq!(outcome, InboundClipboardOutcome::OriginMismatch);
+        assert!(harness.writes.lock().unwrap().is_empty());
+        assert_eq!(harness.save_calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn execute_checks_legacy_pairing_origin_against_paired_peer() {
+        let legacy_harness = || {
+            build_harness_with_devices(
+                true,
+                DeviceSyncPolicy::default(),
+                "rep-text",
+                vec![
+                    paired_device("peer-remote", ""),
+                    paired_device("peer-third", "device-third"),
+                ],
+            )
+        };
+
+        let harness = legacy_harness().await;
+        let message = encrypted_message(&harness.master_key, "device-third").await;
+        assert_eq!(
+            harness.use_case.execute(message).await.unwrap(),
+            InboundClipboardOutcome::OriginMismatch
+        );
+        assert_eq!(harness.save_calls.load(Ordering::SeqCst), 0);
+
+        let harness = legacy_harness().await;
+        let message = encrypted_message(&harness.master_key, "device-remote").await;
+        assert!(matches!(
+            harness.use_case.execute(message).await.unwrap(),
+            InboundClipboardOutcome::AppliedToClipboard(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn execute_drops_message_from_unpaired_sender() {
+        let harness = build_harness(true).await;
+        let mut message = encrypted_message(&harness.master_key, "device-remote").await;
+        message.sender_peer_id = Some("peer-stranger".to_string());
+
+        let outcome = harness.use_case.execute(message).await.unwrap();
+
+        assert_eq!(outcome, InboundClipboardOutcome::OriginMismatch);
+        assert!(harness.writes.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn execute_rejects_message_encrypted_with_other_key() {
+        let harness = build_harness(true).await;
+        let other_key = MasterKey::generate().unwrap();
+        let message = encrypted_message(&other_key, "device-remote").await;
+
+        let result = harness.use_case.execute(message).await;
+
+        assert!(result.is_err());
+        assert!(harness.writes.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn execute_refuses_messages_from_send_only_device() {
+        let harness = build_harness_with_policy(
+            true,
+            DeviceSyncPolicy {
+                direction: SyncDirection::SendOnly,
+                ..DeviceSyncPolicy::default()
+            },
+        )
+        .await;
+        let message = encrypted_message(&harness.master_key, "device-remote").await;
+
+        let outcome = harness.use_case.execute(message).await.unwrap();
+
+        assert_eq!(outcome, InboundClipboardOutcome::Refused);
+        assert!(harness.writes.lock().unwrap().is_empty());
+        assert_eq!(harness.save_calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn execute_honours_content_types_and_history_only_mode() {
+        let no_text = DeviceSyncPolicy {
+            content_types: ContentTypes {
+                text: false,
+                ..ContentTypes::all()
+            },
+            ..DeviceSyncPolicy::default()
+        };
+        let harness = build_harness_with_policy(true, no_text).await;
+        let message = encrypted_message(&harness.master_key, "device-remote").await;
+        assert_eq!(
+            harness.use_case.execute(message).await.unwrap(),
+            InboundClipboardOutcome::Refused
+        );
+
+        let history_only = DeviceSyncPolicy {
+            apply_mode: ClipboardApplyMode::HistoryOnly,
+            ..DeviceSyncPolicy::default()
+        };
+        let harness = build_harness_with_policy(true, history_only).await;
+        let message = encrypted_message(&harness.master_key, "device-remote").await;
+
+        let outcome = harness.use_case.execute(message).await.unwrap();
+
+        assert!(matches!(outcome, InboundClipboardOutcome::Persisted(_)));
+        assert!(harness.writes.lock().unwrap().is_empty());
+        assert_eq!(harness.save_calls.load(Ordering::SeqCst), 1);
+    }
+}
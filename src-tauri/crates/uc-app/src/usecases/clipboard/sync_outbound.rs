@@ -0,0 +1,1416 @@
+use anyhow::Result;
+use chrono::Utc;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{debug, info, info_span, warn, Instrument};
+
+use uc_core::clipboard::{encode_sealed_payload, RemotePayloadRef};
+use uc_core::ids::RepresentationId;
+use uc_core::network::{
+    BlobManifest, ClipboardMessage, ClipboardPayload, DeviceSyncPolicy, PairingState,
+    ProtocolFeature, ProtocolMessage, LAZY_PAYLOAD_MIN_BYTES,
+};
+use uc_core::ports::{
+    ClockPort, DeviceIdentityPort, EncryptionPort, EncryptionSessionPort, NetworkPort,
+    OutboundSyncQueuePort, PairedDeviceRepositoryPort, QueuedClipboardMessage,
+    SelectRepresentationPolicyPort, SettingsPort, SyncFilterPolicyPort,
+};
+use uc_core::security::aad;
+use uc_core::security::model::{EncryptedBlob, EncryptionAlgo, KeyEpoch, MasterKey};
+use uc_core::settings::model::{IntervalBatchMode, Settings, SyncFrequency};
+use uc_core::{PeerId, SystemClipboardSnapshot};
+
+/// Upper bound on queued messages in `IntervalBatchMode::All`.
+///
+/// 间隔同步“全部发送”模式下队列的最大长度，超出时丢弃最旧的消息。
+const MAX_QUEUED_MESSAGES: usize = 100;
+
+/// Connected trusted peers sharing one encrypted frame.
+struct PeerGroup {
+    policy: DeviceSyncPolicy,
+    /// Whether every peer negotiated `ProtocolFeature::LazyFetch`
+    lazy_fetch: bool,
+    peers: Vec<String>,
+}
+
+impl PeerGroup {
+    /// Peers large representations may be offered to instead of sent inline.
+    fn lazy_peers(&self) -> &[String] {
+        if self.lazy_fetch {
+            &self.peers
+        } else {
+            &[]
+        }
+    }
+}
+
+/// Encrypt a locally captured snapshot and send it to all trusted peers.
+///
+/// 加密本地捕获的快照并发送给所有受信任的对端设备。
+///
+/// # Behavior / 行为
+/// - 1. Drop representations the sync filter policy does not send (同步过滤)
+/// - 2. Resolve connected peers in `PairingState::Trusted` whose sync policy
+///   allows sending (目标设备)
+/// - 3. Drop content types each device's policy does not accept (按设备裁剪)
+/// - 4. Keep only the representations chosen by the selection policy (选择表示形式)
+/// - 5. Encrypt the reduced snapshot with the space `MasterKey` (端到端加密)
+/// - 6. Send a `ProtocolMessage::Clipboard` frame to each peer (逐个发送)
+///
+/// Peers with the same content type allowances share one encrypted frame.
+/// Large representations are synced metadata-first only to peers that
+/// negotiated `ProtocolFeature::LazyFetch`; the rest receive them inline.
+///
+/// 内容类型设置相同的设备共用同一条加密消息；大型表示仅对支持延迟拉取的设备只发送清单，
+/// 其余设备仍内联发送。
+///
+/// With `SyncFrequency::Interval` steps 2 to 6 are deferred: the filtered
+/// snapshot is sealed with the space key and put on the outbound queue, and
+/// [`Self::flush_pending`] trims, encrypts and sends it once the configured
+/// interval has elapsed.
+///
+/// 间隔同步模式下第 2 至 6 步延后：过滤后的快照加密后进入出站队列，由 `flush_pending`
+/// 按周期裁剪、加密并发送。
+///
+/// # Returns / 返回值
+/// - Number of peers the message was queued for (0 when deferred)
+/// - 成功排队发送的对端数量（延后发送时为 0）
+pub struct SyncOutboundClipboardUseCase {
+    representation_policy: Arc<dyn SelectRepresentationPolicyPort>,
+    sync_filter: Arc<dyn SyncFilterPolicyPort>,
+    encryption: Arc<dyn EncryptionPort>,
+    encryption_session: Arc<dyn EncryptionSessionPort>,
+    paired_device_repo: Arc<dyn PairedDeviceRepositoryPort>,
+    network: Arc<dyn NetworkPort>,
+    device_identity: Arc<dyn DeviceIdentityPort>,
+    settings: Arc<dyn SettingsPort>,
+    outbound_queue: Arc<dyn OutboundSyncQueuePort>,
+    clock: Arc<dyn ClockPort>,
+}
+
+impl SyncOutboundClipboardUseCase {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        representation_policy: Arc<dyn SelectRepresentationPolicyPort>,
+        sync_filter: Arc<dyn SyncFilterPolicyPort>,
+        encryption: Arc<dyn EncryptionPort>,
+        encryption_session: Arc<dyn EncryptionSessionPort>,
+        paired_device_repo: Arc<dyn PairedDeviceRepositoryPort>,
+        network: Arc<dyn NetworkPort>,
+        device_identity: Arc<dyn DeviceIdentityPort>,
+        settings: Arc<dyn SettingsPort>,
+        outbound_queue: Arc<dyn OutboundSyncQueuePort>,
+        clock: Arc<dyn ClockPort>,
+    ) -> Self {
+        Self {
+            representation_policy,
+            sync_filter,
+            encryption,
+            encryption_session,
+            paired_device_repo,
+            network,
+            device_identity,
+            settings,
+            outbound_queue,
+            clock,
+        }
+    }
+
+    pub async fn execute(&self, snapshot: SystemClipboardSnapshot) -> Result<usize> {
+        let span = info_span!(
+            "usecase.sync_outbound_clipboard.execute",
+            representations = snapshot.representations.len(),
+        );
+        async move {
+            let settings = self.settings.load().await?;
+            let decision = self.sync_filter.evaluate(&snapshot, &settings.sync);
+            if let Some(reason) = decision.sync_skip_reason() {
+                debug!(
+                    reason = reason.as_str(),
+                    "Snapshot filtered by sync settings, skipping outbound clipboard sync"
+                );
+                return Ok(0);
+            }
+            let snapshot = decision.retain_sendable(snapshot);
+            let device_name = Self::device_name(&settings);
+
+            if settings.sync.sync_frequency == SyncFrequency::Interval {
+                self.enqueue(snapshot, settings.sync.interval_batch_mode)
+                    .await?;
+                return Ok(0);
+            }
+
+            let target_peers = self.resolve_target_peers().await?;
+            if target_peers.is_empty() {
+                debug!("No connected trusted peers, skipping outbound clipboard sync");
+                return Ok(0);
+            }
+
+            let mut sent = 0;
+            for group in Self::group_peers(target_peers) {
+                let Some(allowed) = group.policy.retain_allowed(snapshot.clone()) else {
+                    debug!(
+                        peer_count = group.peers.len(),
+                        "Content types not accepted by device sync policy, skipping peers"
+                    );
+                    continue;
+                };
+                let (message_id, payload) = self
+                    .build_payload(allowed, device_name.clone(), group.lazy_peers())
+                    .await?;
+                let group_sent = self.send_to_peers(&group.peers, &payload).await;
+                info!(
+                    message_id = %message_id,
+                    peer_count = group_sent,
+                    "Outbound clipboard sync queued"
+                );
+                sent += group_sent;
+            }
+            Ok(sent)
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Send queued messages once the sync interval has elapsed.
+    ///
+    /// 当最早排队的消息等待满一个同步周期后，将队列中的消息发送给受信任设备。
+    ///
+    /// The interval is measured from the oldest queued message, so a restart
+    /// does not reset it. Switching back to realtime flushes leftovers
+    /// immediately; switching auto sync off discards them.
+    ///
+    /// Each queued snapshot is trimmed to every device's content types and
+    /// encrypted with the key current at flush time. A message leaves the queue
+    /// once at least one peer has accepted it; messages no peer accepted stay
+    /// queued for the next flush.
+    ///
+    /// 每条快照按设备的内容类型裁剪，并使用发送时的当前密钥加密；至少一个设备接收后才出队。
+    ///
+    /// # Returns / 返回值
+    /// - Number of messages delivered
+    /// - 已送达的消息数量
+    pub async fn flush_pending(&self) -> Result<usize> {
+        let span = info_span!("usecase.sync_outbound_clipboard.flush_pending");
+        async move {
+            let pending = self.outbound_queue.list_pending().await?;
+            let Some(oldest) = pending.first() else {
+                return Ok(0);
+            };
+
+            let settings = self.settings.load().await?;
+            if !settings.sync.auto_sync {
+                let message_ids: Vec<String> = pending
+                    .iter()
+                    .map(|message| message.message_id.clone())
+                    .collect();
+                let discarded = self.outbound_queue.remove(&message_ids).await?;
+                debug!(
+                    discarded,
+                    "Auto sync disabled, discarding queued clipboard sync"
+                );
+                return Ok(0);
+            }
+
+            if settings.sync.sync_frequency == SyncFrequency::Interval {
+                let interval_ms =
+                    i64::try_from(settings.sync.sync_interval_secs.saturating_mul(1000))
+                        .unwrap_or(i64::MAX);
+                if self.clock.now_ms() < oldest.enqueued_at_ms.saturating_add(interval_ms) {
+                    return Ok(0);
+                }
+            }
+
+            if !self.encryption_session.is_ready().await {
+                debug!(
+                    pending = pending.len(),
+                    "Encryption session locked, keeping clipboard sync queued"
+                );
+                return Ok(0);
+            }
+
+            let target_peers = self.resolve_target_peers().await?;
+            if target_peers.is_empty() {
+                debug!(
+                    pending = pending.len(),
+                    "No connected trusted peers, keeping clipboard sync queued"
+                );
+                return Ok(0);
+            }
+
+            let peer_count = target_peers.len();
+            let groups = Self::group_peers(target_peers);
+            let device_name = Self::device_name(&settings);
+            let mut delivered = Vec::new();
+            let mut unreadable = Vec::new();
+            for message in &pending {
+                let snapshot = match self.open_queued(message).await {
+                    Ok(snapshot) => snapshot,
+                    Err(err) => {
+                        warn!(
+                            message_id = %message.message_id,
+                            error = %err,
+                            "Dropping queued clipboard sync that can no longer be opened"
+                        );
+                        unreadable.push(message.message_id.clone());
+                        continue;
+                    }
+                };
+
+                let mut sent = 0;
+                for group in &groups {
+                    let Some(allowed) = group.policy.retain_allowed(snapshot.clone()) else {
+                        continue;
+                    };
+                    let selected = self.select_representations(allowed)?;
+                    let payload = self
+                        .encode_message(
+                            message.message_id.clone(),
+                            selected,
+                            device_name.clone(),
+                            false,
+                            group.lazy_peers(),
+                        )
+                        .await?;
+                    sent += self.send_to_peers(&group.peers, &payload).await;
+                }
+                if sent > 0 {
+                    delivered.push(message.message_id.clone());
+                }
+            }
+            self.outbound_queue.remove(&unreadable).await?;
+            self.outbound_queue.remove(&delivered).await?;
+
+            info!(
+                delivered = delivered.len(),
+                kept = pending.len() - delivered.len() - unreadable.len(),
+                peer_count,
+                "Queued clipboard sync flushed"
+            );
+            Ok(delivered.len())
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Resend a history snapshot to one peer for offline catch-up.
+    ///
+    /// 为离线补同步向单个设备补发历史快照：按当前同步设置和该设备的同步策略过滤，
+    /// 对端已有相同快照（命中 `known_hashes`）时跳过。
+    ///
+    /// # Returns / 返回值
+    /// - `true` if the snapshot was sent
+    /// - 已发送时返回 `true`
+    pub async fn send_catch_up(
+        &self,
+        peer_id: &str,
+        snapshot: SystemClipboardSnapshot,
+        known_hashes: &HashSet<String>,
+    ) -> Result<bool> {
+        let settings = self.settings.load().await?;
+        let decision = self.sync_filter.evaluate(&snapshot, &settings.sync);
+        if let Some(reason) = decision.sync_skip_reason() {
+            debug!(
+                reason = reason.as_str(),
+                "History entry filtered by sync settings, skipping catch-up"
+            );
+            return Ok(false);
+        }
+        let policy = self
+            .paired_device_repo
+            .get_sync_policy(&PeerId::from(peer_id))
+            .await?;
+        if !policy.allows_sending() {
+            return Ok(false);
+        }
+        let Some(allowed) = policy.retain_allowed(decision.retain_sendable(snapshot)) else {
+            return Ok(false);
+        };
+        let selected = self.select_representations(allowed)?;
+        if known_hashes.contains(&selected.snapshot_hash().to_string()) {
+            return Ok(false);
+        }
+
+        let lazy_peers = if self.supports(peer_id, ProtocolFeature::LazyFetch).await? {
+            vec![peer_id.to_string()]
+        } else {
+            Vec::new()
+        };
+        let (message_id, payload) = self
+            .encode_payload(selected, Self::device_name(&settings), true, &lazy_peers)
+            .await?;
+        self.network.send_clipboard(peer_id, payload).await?;
+        debug!(message_id = %message_id, peer_id = %peer_id, "Catch-up clipboard sent");
+        Ok(true)
+    }
+
+    fn device_name(settings: &Settings) -> String {
+        settings
+            .general
+            .device_name
+            .clone()
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    }
+
+    /// Reduce the snapshot, encrypt it and encode it as a `ProtocolMessage::Clipboard` frame.
+    ///
+    /// 裁剪并加密快照，编码为剪贴板协议消息。
+    async fn build_payload(
+        &self,
+        snapshot: SystemClipboardSnapshot,
+        device_name: String,
+        lazy_peers: &[String],
+    ) -> Result<(String, Vec<u8>)> {
+        let selected = self.select_representations(snapshot)?;
+        self.encode_payload(selected, device_name, false, lazy_peers)
+            .await
+    }
+
+    async fn encode_payload(
+        &self,
+        selected: SystemClipboardSnapshot,
+        device_name: String,
+        catch_up: bool,
+        lazy_peers: &[String],
+    ) -> Result<(String, Vec<u8>)> {
+        let message_id = uuid::Uuid::new_v4().to_string();
+        let payload = self
+            .encode_message(
+                message_id.clone(),
+                selected,
+                device_name,
+                catch_up,
+                lazy_peers,
+            )
+            .await?;
+        Ok((message_id, payload))
+    }
+
+    /// Encrypt the snapshot and encode it as a `ProtocolMessage::Clipboard` frame with the given id.
+    ///
+    /// 使用给定的消息 ID 加密快照并编码为剪贴板协议消息。
+    /// Large representations are offered to `lazy_peers` only; with none
+    /// they stay inline.
+    async fn encode_message(
+        &self,
+        message_id: String,
+        selected: SystemClipboardSnapshot,
+        device_name: String,
+        catch_up: bool,
+        lazy_peers: &[String],
+    ) -> Result<Vec<u8>> {
+        let content_hash = selected.snapshot_hash().to_string();
+        let (key_epoch, master_key) = self.encryption_session.get_current_key().await?;
+        let payload = self
+            .offer_lazy_payloads(selected, lazy_peers, key_epoch, &master_key)
+            .await;
+        let plaintext = serde_json::to_vec(&payload)?;
+        let mut encrypted = self
+            .encryption
+            .encrypt_blob(
+                &master_key,
+                &plaintext,
+                &aad::for_clipboard_message(&message_id),
+                EncryptionAlgo::XChaCha20Poly1305,
+            )
+            .await?;
+        encrypted.key_epoch = key_epoch;
+
+        let message = ClipboardMessage {
+            id: message_id,
+            content_hash,
+            encrypted_content: serde_json::to_vec(&encrypted)?,
+            timestamp: Utc::now(),
+            origin_device_id: self.device_identity.current_device_id().to_string(),
+            origin_device_name: device_name,
+            payload_manifest: None,
+            catch_up,
+            sender_peer_id: None,
+        };
+        Ok(ProtocolMessage::Clipboard(message).to_bytes()?)
+    }
+
+    /// Offer large representations to `peers` and send only their manifests.
+    ///
+    /// 大于 [`LAZY_PAYLOAD_MIN_BYTES`] 的表示形式使用空间密钥加密后提供给目标设备，
+    /// 消息中只同步清单，对端首次使用时再拉取；无法提供时仍随消息内联发送。
+    async fn offer_lazy_payloads(
+        &self,
+        mut snapshot: SystemClipboardSnapshot,
+        peers: &[String],
+        key_epoch: KeyEpoch,
+        master_key: &MasterKey,
+    ) -> ClipboardPayload {
+        let mut remote_payloads = Vec::new();
+        for rep in &mut snapshot.representations {
+            if peers.is_empty() || rep.bytes.len() < LAZY_PAYLOAD_MIN_BYTES {
+                continue;
+            }
+            match self
+                .offer_sealed(&rep.id, &rep.bytes, peers, key_epoch, master_key)
+                .await
+            {
+                Ok(manifest) => {
+                    remote_payloads.push(RemotePayloadRef {
+                        representation_id: rep.id.clone(),
+                        content_hash: rep.content_hash().0,
+                        size_bytes: rep.bytes.len() as u64,
+                        manifest,
+                    });
+                    rep.bytes = Vec::new();
+                }
+                Err(err) => {
+                    debug!(representation_id = %rep.id, "keeping large payload inline: {err}");
+                }
+            }
+        }
+        ClipboardPayload {
+            snapshot,
+            remote_payloads,
+        }
+    }
+
+    /// Seal one representation under [`aad::for_remote_payload`] and offer it.
+    async fn offer_sealed(
+        &self,
+        rep_id: &RepresentationId,
+        bytes: &[u8],
+        peers: &[String],
+        key_epoch: KeyEpoch,
+        master_key: &MasterKey,
+    ) -> Result<BlobManifest> {
+        let mut sealed = self
+            .encryption
+            .encrypt_blob(
+                master_key,
+                bytes,
+                &aad::for_remote_payload(rep_id),
+                EncryptionAlgo::XChaCha20Poly1305,
+            )
+            .await?;
+        sealed.key_epoch = key_epoch;
+        self.network
+            .offer_blob(encode_sealed_payload(sealed)?, peers)
+            .await
+    }
+
+    /// Seal the filtered snapshot and queue it for the next interval flush.
+    ///
+    /// 使用空间密钥加密过滤后的快照并加入出站队列，再按合并方式裁剪队列。
+    async fn enqueue(
+        &self,
+        snapshot: SystemClipboardSnapshot,
+        batch_mode: IntervalBatchMode,
+    ) -> Result<()> {
+        let message_id = uuid::Uuid::new_v4().to_string();
+        let (key_epoch, master_key) = self.encryption_session.get_current_key().await?;
+        let mut sealed = self
+            .encryption
+            .encrypt_blob(
+                &master_key,
+                &serde_json::to_vec(&snapshot)?,
+                &aad::for_outbound_queue(&message_id),
+                EncryptionAlgo::XChaCha20Poly1305,
+            )
+            .await?;
+        sealed.key_epoch = key_epoch;
+
+        self.outbound_queue
+            .enqueue(QueuedClipboardMessage {
+                message_id: message_id.clone(),
+                payload: serde_json::to_vec(&sealed)?,
+                enqueued_at_ms: self.clock.now_ms(),
+            })
+            .await?;
+
+        let dropped = match batch_mode {
+            IntervalBatchMode::LatestOnly => {
+                let superseded: Vec<String> = self
+                    .outbound_queue
+                    .list_pending()
+                    .await?
+                    .into_iter()
+                    .map(|message| message.message_id)
+                    .filter(|id| *id != message_id)
+                    .collect();
+                self.outbound_queue.remove(&superseded).await?
+            }
+            IntervalBatchMode::All => {
+                self.outbound_queue
+                    .retain_latest(MAX_QUEUED_MESSAGES)
+                    .await?
+            }
+        };
+
+        debug!(
+            message_id = %message_id,
+            dropped,
+            "Outbound clipboard sync deferred to next interval"
+        );
+        Ok(())
+    }
+
+    /// Decrypt a queued snapshot with the key of the epoch it was sealed under.
+    ///
+    /// 使用入队时的密钥纪元解密排队的快照。
+    async fn open_queued(
+        &self,
+        message: &QueuedClipboardMessage,
+    ) -> Result<SystemClipboardSnapshot> {
+        let sealed: EncryptedBlob = serde_json::from_slice(&message.payload)?;
+        let master_key = self
+            .encryption_session
+            .get_master_key_for_epoch(sealed.key_epoch)
+            .await?;
+        let plaintext = self
+            .encryption
+            .decrypt_blob(
+                &master_key,
+                &sealed,
+                &aad::for_outbound_queue(&message.message_id),
+            )
+            .await?;
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+
+    async fn send_to_peers(&self, target_peers: &[String], payload: &[u8]) -> usize {
+        let mut sent = 0;
+        for peer_id in target_peers {
+            match self.network.send_clipboard(peer_id, payload.to_vec()).await {
+                Ok(()) => sent += 1,
+                Err(err) => {
+                    warn!(peer_id = %peer_id, error = %err, "Failed to queue clipboard sync");
+                }
+            }
+        }
+        sent
+    }
+
+    /// Trusted peers that are currently connected and accept content from this
+    /// device, with their policy and whether they support lazy fetch.
+    ///
+    /// 当前已连接、且同步策略允许发送的受信任设备及其策略与延迟拉取能力。
+    async fn resolve_target_peers(&self) -> Result<Vec<(String, DeviceSyncPolicy, bool)>> {
+        let connected: HashSet<String> = self
+            .network
+            .get_connected_peers()
+            .await?
+            .into_iter()
+            .map(|peer| peer.peer_id)
+            .collect();
+
+        let mut targets = Vec::new();
+        for device in self.paired_device_repo.list_all().await? {
+            if device.pairing_state != PairingState::Trusted
+                || !connected.contains(device.peer_id.as_str())
+            {
+                continue;
+            }
+            let policy = self
+                .paired_device_repo
+                .get_sync_policy(&device.peer_id)
+                .await?;
+            if policy.allows_sending() {
+                let peer_id = device.peer_id.as_str().to_string();
+                let lazy_fetch = self.supports(&peer_id, ProtocolFeature::LazyFetch).await?;
+                targets.push((peer_id, policy, lazy_fetch));
+            }
+        }
+        Ok(targets)
+    }
+
+    /// Whether the capabilities last negotiated with `peer_id` include
+    /// `feature`; devices that never said hello support none.
+    async fn supports(&self, peer_id: &str, feature: ProtocolFeature) -> Result<bool> {
+        Ok(self
+            .paired_device_repo
+            .get_capabilities(&PeerId::from(peer_id))
+            .await?
+            .is_some_and(|capabilities| capabilities.supports(feature)))
+    }
+
+    /// Group peers whose policies accept the same content types and that
+    /// agree on lazy fetch.
+    fn group_peers(targets: Vec<(String, DeviceSyncPolicy, bool)>) -> Vec<PeerGroup> {
+        let mut groups: Vec<PeerGroup> = Vec::new();
+        for (peer_id, policy, lazy_fetch) in targets {
+            match groups.iter_mut().find(|group| {
+                group.policy.content_types == policy.content_types && group.lazy_fetch == lazy_fetch
+            }) {
+                Some(group) => group.peers.push(peer_id),
+                None => groups.push(PeerGroup {
+                    policy,
+                    lazy_fetch,
+                    peers: vec![peer_id],
+                }),
+            }
+        }
+        groups
+    }
+
+    /// Reduce the snapshot to the representations referenced by the selection policy.
+    ///
+    /// 仅保留选择策略引用的表示形式。
+    fn select_representations(
+        &self,
+        snapshot: SystemClipboardSnapshot,
+    ) -> Result<SystemClipboardSnapshot> {
+        let selection = self.representation_policy.select(&snapshot)?;
+
+        let mut selected_ids: HashSet<RepresentationId> = HashSet::new();
+        selected_ids.insert(selection.paste_rep_id);
+        selected_ids.insert(selection.primary_rep_id);
+        selected_ids.insert(selection.preview_rep_id);
+        selected_ids.extend(selection.secondary_rep_ids);
+
+        Ok(SystemClipboardSnapshot {
+            ts_ms: snapshot.ts_ms,
+            representations: snapshot
+                .representations
+                .into_iter()
+                .filter(|rep| selected_ids.contains(&rep.id))
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SyncOutboundClipboardUseCase;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use std::collections::{HashMap, HashSet};
+    use std::sync::atomic::{AtomicI64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use uc_core::clipboard::{
+        decode_sealed_payload, ObservedClipboardRepresentation, SelectRepresentationPolicyV1,
+        SyncFilterPolicyV1,
+    };
+    use uc_core::ids::{FormatId, RepresentationId};
+    use uc_core::network::{
+        ClipboardPayload, DeviceSyncPolicy, PairedDevice, PairingState, PeerCapabilities,
+        ProtocolFeature, ProtocolMessage, SyncDirection, LAZY_PAYLOAD_MIN_BYTES,
+    };
+    use uc_core::ports::{
+        ClockPort, DeviceIdentityPort, EncryptionPort, EncryptionSessionPort,
+        OutboundSyncQueuePort, QueuedClipboardMessage, SettingsPort,
+    };
+    use uc_core::security::aad;
+    use uc_core::security::model::{EncryptedBlob, MasterKey};
+    use uc_core::settings::model::{ContentTypes, IntervalBatchMode, Settings, SyncFrequency};
+    use uc_core::testing::{InMemoryPairedDeviceRepository, TestNetwork};
+    use uc_core::{DeviceId, MimeType, PeerId, SystemClipboardSnapshot};
+    use uc_infra::security::{EncryptionRepository, InMemoryEncryptionSession};
+
+    struct TestDeviceIdentity;
+
+    impl DeviceIdentityPort for TestDeviceIdentity {
+        fn current_device_id(&self) -> DeviceId {
+            DeviceId::new("device-local")
+        }
+    }
+
+    struct TestSettings {
+        settings: Settings,
+    }
+
+    #[async_trait]
+    impl SettingsPort for TestSettings {
+        async fn load(&self) -> anyhow::Result<Settings> {
+            Ok(self.settings.clone())
+        }
+
+        async fn save(&self, _settings: &Settings) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct TestQueue {
+        messages: Mutex<Vec<QueuedClipboardMessage>>,
+    }
+
+    #[async_trait]
+    impl OutboundSyncQueuePort for TestQueue {
+        async fn enqueue(&self, message: QueuedClipboardMessage) -> anyhow::Result<()> {
+            self.messages.lock().unwrap().push(message);
+            Ok(())
+        }
+
+        async fn list_pending(&self) -> anyhow::Result<Vec<QueuedClipboardMessage>> {
+            Ok(self.messages.lock().unwrap().clone())
+        }
+
+        async fn remove(&self, message_ids: &[String]) -> anyhow::Result<usize> {
+            let mut messages = self.messages.lock().unwrap();
+            let before = messages.len();
+            messages.retain(|message| !message_ids.contains(&message.message_id));
+            Ok(before - messages.len())
+        }
+
+        async fn retain_latest(&self, keep: usize) -> anyhow::Result<usize> {
+            let mut messages = self.messages.lock().unwrap();
+            let dropped = messages.len().saturating_sub(keep);
+            messages.drain(..dropped);
+            Ok(dropped)
+        }
+    }
+
+    #[derive(Default)]
+    struct TestClock {
+        now_ms: AtomicI64,
+    }
+
+    impl TestClock {
+        fn set(&self, now_ms: i64) {
+            self.now_ms.store(now_ms, Ordering::SeqCst);
+        }
+    }
+
+    impl ClockPort for TestClock {
+        fn now_ms(&self) -> i64 {
+            self.now_ms.load(Ordering::SeqCst)
+        }
+    }
+
+    fn paired(peer_id: &str, pairing_state: PairingState) -> PairedDevice {
+        PairedDevice {
+            peer_id: PeerId::from(peer_id),
+            pairing_state,
+            identity_fingerprint: "fp".to_string(),
+            paired_at: Utc::now(),
+            last_seen_at: None,
+            device_name: peer_id.to_string(),
+            device_id: "device-peer".to_string(),
+        }
+    }
+
+    fn text_snapshot() -> SystemClipboardSnapshot {
+        SystemClipboardSnapshot {
+            ts_ms: 42,
+            representations: vec![ObservedClipboardRepresentation {
+                id: RepresentationId::from("rep-text"),
+                format_id: FormatId::from("text"),
+                mime: Some(MimeType::text_plain()),
+                bytes: b"hello".to_vec(),
+            }],
+        }
+    }
+
+    async fn build_use_case(
+        settings: Settings,
+        devices: Vec<PairedDevice>,
+        network: Arc<TestNetwork>,
+        master_key: MasterKey,
+    ) -> SyncOutboundClipboardUseCase {
+        build_use_case_with_queue(
+            settings,
+            devices,
+            network,
+            master_key,
+            Arc::new(TestQueue::default()),
+            Arc::new(TestClock::default()),
+        )
+        .await
+    }
+
+    async fn build_use_case_with_queue(
+        settings: Settings,
+        devices: Vec<PairedDevice>,
+        network: Arc<TestNetwork>,
+        master_key: MasterKey,
+        queue: Arc<TestQueue>,
+        clock: Arc<TestClock>,
+    ) -> SyncOutboundClipboardUseCase {
+        build_use_case_with_policies(settings, devices, vec![], network, master_key, queue, clock)
+            .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn build_use_case_with_policies(
+        settings: Settings,
+        devices: Vec<PairedDevice>,
+        policies: Vec<(&str, DeviceSyncPolicy)>,
+        network: Arc<TestNetwork>,
+        master_key: MasterKey,
+        queue: Arc<TestQueue>,
+        clock: Arc<TestClock>,
+    ) -> SyncOutboundClipboardUseCase {
+        let repo = policies.into_iter().fold(
+            InMemoryPairedDeviceRepository::with_devices(devices),
+            |repo, (peer_id, policy)| repo.with_sync_policy(peer_id, policy),
+        );
+        build_use_case_with_repo(settings, repo, network, master_key, queue, clock).await
+    }
+
+    async fn build_use_case_with_repo(
+        settings: Settings,
+        repo: InMemoryPairedDeviceRepository,
+        network: Arc<TestNetwork>,
+        master_key: MasterKey,
+        queue: Arc<TestQueue>,
+        clock: Arc<TestClock>,
+    ) -> SyncOutboundClipboardUseCase {
+        let session = Arc::new(InMemoryEncryptionSession::new());
+        session.set_master_key(master_key).await.unwrap();
+
+        SyncOutboundClipboardUseCase::new(
+            Arc::new(SelectRepresentationPolicyV1::new()),
+            Arc::new(SyncFilterPolicyV1::new()),
+            Arc::new(EncryptionRepository),
+            session,
+            Arc::new(repo),
+            network,
+            Arc::new(TestDeviceIdentity),
+            Arc::new(TestSettings { settings }),
+            queue,
+            clock,
+        )
+    }
+
+    fn capabilities(peer_id: &str, features: Vec<ProtocolFeature>) -> PeerCapabilities {
+        PeerCapabilities {
+            peer_id: peer_id.to_string(),
+            app_version: "0.2.0".to_string(),
+            protocol_version: "1.0.0".to_string(),
+            features,
+            negotiated_at: Utc::now(),
+        }
+    }
+
+    fn text_and_image_snapshot() -> SystemClipboardSnapshot {
+        let mut snapshot = text_snapshot();
+        snapshot
+            .representations
+            .push(ObservedClipboardRepresentation {
+                id: RepresentationId::from("rep-image"),
+                format_id: FormatId::from("public.png"),
+                mime: Some(MimeType("image/png".to_string())),
+                bytes: vec![0x89, b'P', b'N', b'G'],
+            });
+        snapshot
+    }
+
+    fn no_images_policy() -> DeviceSyncPolicy {
+        DeviceSyncPolicy {
+            content_types: ContentTypes {
+                image: false,
+                ..ContentTypes::all()
+            },
+            ..DeviceSyncPolicy::default()
+        }
+    }
+
+    async fn decrypt_snapshot(master_key: &MasterKey, payload: &[u8]) -> SystemClipboardSnapshot {
+        let message = match ProtocolMessage::from_bytes(payload).unwrap() {
+            ProtocolMessage::Clipboard(message) => message,
+            other => panic!("unexpected protocol message: {other:?}"),
+        };
+        let encrypted: EncryptedBlob = serde_json::from_slice(&message.encrypted_content).unwrap();
+        let plaintext = EncryptionRepository
+            .decrypt_blob(
+                master_key,
+                &encrypted,
+                &aad::for_clipboard_message(&message.id),
+            )
+            .await
+            .unwrap();
+        serde_json::from_slice(&plaintext).unwrap()
+    }
+
+    fn interval_settings(batch_mode: IntervalBatchMode) -> Settings {
+        let mut settings = Settings::default();
+        settings.sync.sync_frequency = SyncFrequency::Interval;
+        settings.sync.sync_interval_secs = 30;
+        settings.sync.interval_batch_mode = batch_mode;
+        settings
+    }
+
+    #[tokio::test]
+    async fn execute_sends_encrypted_message_to_connected_trusted_peers_only() {
+        let network = Arc::new(TestNetwork::new().with_connected(["peer-trusted", "peer-pending"]));
+        let master_key = MasterKey::generate().unwrap();
+        let uc = build_use_case(
+            Settings::default(),
+            vec![
+                paired("peer-trusted", PairingState::Trusted),
+                paired("peer-offline", PairingState::Trusted),
+                paired("peer-pending", PairingState::Pending),
+            ],
+            network.clone(),
+            master_key.clone(),
+        )
+        .await;
+
+        let peer_count = uc.execute(text_snapshot()).await.unwrap();
+
+        assert_eq!(peer_count, 1);
+        let (peer_id, payload) = {
+            let sent = network.sent_clipboard();
+            assert_eq!(sent.len(), 1);
+            sent[0].clone()
+        };
+        assert_eq!(peer_id, "peer-trusted");
+
+        let message = match ProtocolMessage::from_bytes(&payload).unwrap() {
+            ProtocolMessage::Clipboard(message) => message,
+            other => panic!("unexpected protocol message: {other:?}"),
+        };
+        assert_eq!(message.origin_device_id, "device-local");
+
+        let encrypted: EncryptedBlob = serde_json::from_slice(&message.encrypted_content).unwrap();
+        let plaintext = EncryptionRepository
+            .decrypt_blob(
+                &master_key,
+                &encrypted,
+                &aad::for_clipboard_message(&message.id),
+            )
+            .await
+            .unwrap();
+        let snapshot: SystemClipboardSnapshot = serde_json::from_slice(&plaintext).unwrap();
+        assert_eq!(snapshot.representations.len(), 1);
+        assert_eq!(snapshot.representations[0].bytes, b"hello".to_vec());
+    }
+
+    #[tokio::test]
+    async fn execute_sends_large_representations_as_manifests() {
+        let network = Arc::new(TestNetwork::new().with_connected(["peer-trusted"]));
+        let master_key = MasterKey::generate().unwrap();
+        let uc = build_use_case_with_repo(
+            Settings::default(),
+            InMemoryPairedDeviceRepository::with_devices(vec![paired(
+                "peer-trusted",
+                PairingState::Trusted,
+            )])
+            .with_capabilities(capabilities(
+                "peer-trusted",
+                vec![ProtocolFeature::LazyFetch],
+            )),
+            network.clone(),
+            master_key.clone(),
+            Arc::new(TestQueue::default()),
+            Arc::new(TestClock::default()),
+        )
+        .await;
+        let mut snapshot = text_snapshot();
+        let large = vec![b'a'; LAZY_PAYLOAD_MIN_BYTES];
+        snapshot.representations[0].bytes = large.clone();
+        let expected_hash = snapshot.snapshot_hash().to_string();
+        let expected_rep_hash = snapshot.representations[0].content_hash().0;
+
+        assert_eq!(uc.execute(snapshot).await.unwrap(), 1);
+
+        let payload = network.sent_clipboard()[0].1.clone();
+        let message = match ProtocolMessage::from_bytes(&payload).unwrap() {
+            ProtocolMessage::Clipboard(message) => message,
+            other => panic!("unexpected protocol message: {other:?}"),
+        };
+        assert_eq!(message.content_hash, expected_hash);
+        let encrypted: EncryptedBlob = serde_json::from_slice(&message.encrypted_content).unwrap();
+        let plaintext = EncryptionRepository
+            .decrypt_blob(
+                &master_key,
+                &encrypted,
+                &aad::for_clipboard_message(&message.id),
+            )
+            .await
+            .unwrap();
+        let payload: ClipboardPayload = serde_json::from_slice(&plaintext).unwrap();
+        assert!(payload.snapshot.representations[0].bytes.is_empty());
+        assert_eq!(payload.remote_payloads.len(), 1);
+        assert_eq!(
+            payload.remote_payloads[0].representation_id,
+            RepresentationId::from("rep-text")
+        );
+        let remote = &payload.remote_payloads[0];
+        assert_eq!(remote.size_bytes, large.len() as u64);
+        assert_eq!(remote.content_hash, expected_rep_hash);
+
+        // Only the sealed bytes are offered, and only to the receiving peer.
+        let offered = network.offered_blobs();
+        assert_eq!(offered.len(), 1);
+        let (sealed_bytes, offered_to) = &offered[0];
+        assert_eq!(offered_to, &vec!["peer-trusted".to_string()]);
+        assert!(remote.manifest.verify_blob(sealed_bytes));
+        assert!(!sealed_bytes
+            .windows(64)
+            .any(|window| window == &large[..64]));
+        let sealed = decode_sealed_payload(sealed_bytes).unwrap();
+        let opened = EncryptionRepository
+            .decrypt_blob(
+                &master_key,
+                &sealed,
+                &aad::for_remote_payload(&remote.representation_id),
+            )
+            .await
+            .unwrap();
+        assert_eq!(opened, large);
+    }
+
+    #[tokio::test]
+    async fn execute_inlines_large_representations_for_peers_without_lazy_fetch() {
+        let network =
+            Arc::new(TestNetwork::new().with_connected(["peer-lazy", "peer-old", "peer-silent"]));
+        let master_key = MasterKey::generate().unwrap();
+        let uc = build_use_case_with_repo(
+            Settings::default(),
+            InMemoryPairedDeviceRepository::with_devices(vec![
+                paired("peer-lazy", PairingState::Trusted),
+                paired("peer-old", PairingState::Trusted),
+                paired("peer-silent", PairingState::Trusted),
+            ])
+            .with_capabilities(capabilities("peer-lazy", vec![ProtocolFeature::LazyFetch]))
+            .with_capabilities(capabilities(
+                "peer-old",
+                vec![ProtocolFeature::ChunkedTransfer],
+            )),
+            network.clone(),
+            master_key.clone(),
+            Arc::new(TestQueue::default()),
+            Arc::new(TestClock::default()),
+        )
+        .await;
+        let mut snapshot = text_snapshot();
+        let large = vec![b'a'; LAZY_PAYLOAD_MIN_BYTES];
+        snapshot.representations[0].bytes = large.clone();
+
+        assert_eq!(uc.execute(snapshot).await.unwrap(), 3);
+
+        let offered = network.offered_blobs();
+        assert_eq!(offered.len(), 1);
+        assert_eq!(offered[0].1, vec!["peer-lazy".to_string()]);
+        for (peer_id, payload) in network.sent_clipboard() {
+            let message = match ProtocolMessage::from_bytes(&payload).unwrap() {
+                ProtocolMessage::Clipboard(message) => message,
+                other => panic!("unexpected protocol message: {other:?}"),
+            };
+            let encrypted: EncryptedBlob =
+                serde_json::from_slice(&message.encrypted_content).unwrap();
+            let plaintext = EncryptionRepository
+                .decrypt_blob(
+                    &master_key,
+                    &encrypted,
+                    &aad::for_clipboard_message(&message.id),
+                )
+                .await
+                .unwrap();
+            let payload: ClipboardPayload = serde_json::from_slice(&plaintext).unwrap();
+            let inline = payload.remote_payloads.is_empty();
+            assert_eq!(inline, peer_id != "peer-lazy", "peer {peer_id}");
+            if inline {
+                assert_eq!(payload.snapshot.representations[0].bytes, large);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn send_catch_up_skips_snapshots_the_peer_already_has() {
+        let network = Arc::new(TestNetwork::new().with_connected(["peer-trusted"]));
+        let uc = build_use_case(
+            Settings::default(),
+            vec![paired("peer-trusted", PairingState::Trusted)],
+            network.clone(),
+            MasterKey::generate().unwrap(),
+        )
+        .await;
+        let known = HashSet::from([text_snapshot().snapshot_hash().to_string()]);
+
+        assert!(!uc
+            .send_catch_up("peer-trusted", text_snapshot(), &known)
+            .await
+            .unwrap());
+        assert!(network.sent_clipboard().is_empty());
+
+        assert!(uc
+            .send_catch_up("peer-trusted", text_snapshot(), &HashSet::new())
+            .await
+            .unwrap());
+        let payload = network.sent_clipboard()[0].1.clone();
+        let message = match ProtocolMessage::from_bytes(&payload).unwrap() {
+            ProtocolMessage::Clipboard(message) => message,
+            other => panic!("unexpected protocol message: {other:?}"),
+        };
+        assert!(message.catch_up);
+    }
+
+    #[tokio::test]
+    async fn execute_skips_when_auto_sync_disabled() {
+        let network = Arc::new(TestNetwork::new().with_connected(["peer-trusted"]));
+        let mut settings = Settings::default();
+        settings.sync.auto_sync = false;
+        let uc = build_use_case(
+            settings,
+            vec![paired("peer-trusted", PairingState::Trusted)],
+            network.clone(),
+            MasterKey::generate().unwrap(),
+        )
+        .await;
+
+        let peer_count = uc.execute(text_snapshot()).await.unwrap();
+
+        assert_eq!(peer_count, 0);
+        assert!(network.sent_clipboard().is_empty());
+    }
+
+    #[tokio::test]
+    async fn execute_skips_disabled_content_types() {
+        let network = Arc::new(TestNetwork::new().with_connected(["peer-trusted"]));
+        let mut settings = Settings::default();
+        settings.sync.content_types = ContentTypes {
+            image: true,
+            ..ContentTypes::default()
+        };
+        let uc = build_use_case(
+            settings,
+            vec![paired("peer-trusted", PairingState::Trusted)],
+            network.clone(),
+            MasterKey::generate().unwrap(),
+        )
+        .await;
+
+        let peer_count = uc.execute(text_snapshot()).await.unwrap();
+
+        assert_eq!(peer_count, 0);
+        assert!(network.sent_clipboard().is_empty());
+    }
+
+    #[tokio::test]
+    async fn interval_mode_defers_until_interval_elapses() {
+        let network = Arc::new(TestNetwork::new().with_connected(["peer-trusted"]));
+        let queue = Arc::new(TestQueue::default());
+        let clock = Arc::new(TestClock::default());
+        clock.set(1_000);
+        let uc = build_use_case_with_queue(
+            interval_settings(IntervalBatchMode::LatestOnly),
+            vec![paired("peer-trusted", PairingState::Trusted)],
+            network.clone(),
+            MasterKey::generate().unwrap(),
+            queue.clone(),
+            clock.clone(),
+        )
+        .await;
+
+        assert_eq!(uc.execute(text_snapshot()).await.unwrap(), 0);
+        assert!(network.sent_clipboard().is_empty());
+        assert_eq!(queue.messages.lock().unwrap().len(), 1);
+
+        clock.set(30_999);
+        assert_eq!(uc.flush_pending().await.unwrap(), 0);
+        assert!(network.sent_clipboard().is_empty());
+
+        clock.set(31_000);
+        assert_eq!(uc.flush_pending().await.unwrap(), 1);
+        assert_eq!(network.sent_clipboard().len(), 1);
+        assert!(queue.messages.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn interval_batch_mode_controls_coalescing() {
+        for (batch_mode, expected) in [
+            (IntervalBatchMode::LatestOnly, 1),
+            (IntervalBatchMode::All, 3),
+        ] {
+            let network = Arc::new(TestNetwork::new().with_connected(["peer-trusted"]));
+            let queue = Arc::new(TestQueue::default());
+            let clock = Arc::new(TestClock::default());
+            let uc = build_use_case_with_queue(
+                interval_settings(batch_mode),
+                vec![paired("peer-trusted", PairingState::Trusted)],
+                network.clone(),
+                MasterKey::generate().unwrap(),
+                queue.clone(),
+                clock.clone(),
+            )
+            .await;
+
+            for ts in [0, 1, 2] {
+                clock.set(ts);
+                uc.execute(text_snapshot()).await.unwrap();
+            }
+            let last_id = queue
+                .messages
+                .lock()
+                .unwrap()
+                .last()
+                .unwrap()
+                .message_id
+                .clone();
+            assert_eq!(queue.messages.lock().unwrap().len(), expected);
+
+            clock.set(60_000);
+            assert_eq!(uc.flush_pending().await.unwrap(), expected);
+            let sent = network.sent_clipboard();
+            assert_eq!(sent.len(), expected);
+            match ProtocolMessage::from_bytes(&sent[expected - 1].1).unwrap() {
+                ProtocolMessage::Clipboard(message) => assert_eq!(message.id, last_id),
+                other => panic!("unexpected protocol message: {other:?}"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn flush_keeps_messages_queued_without_connected_peers() {
+        let network = Arc::new(TestNetwork::new());
+        let queue = Arc::new(TestQueue::default());
+        let clock = Arc::new(TestClock::default());
+        let uc = build_use_case_with_queue(
+            interval_settings(IntervalBatchMode::LatestOnly),
+            vec![paired("peer-trusted", PairingState::Trusted)],
+            network.clone(),
+            MasterKey::generate().unwrap(),
+            queue.clone(),
+            clock.clone(),
+        )
+        .await;
+
+        uc.execute(text_snapshot()).await.unwrap();
+        clock.set(60_000);
+
+        assert_eq!(uc.flush_pending().await.unwrap(), 0);
+        assert_eq!(queue.messages.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn execute_applies_device_sync_policies() {
+        let network = Arc::new(TestNetwork::new().with_connected([
+            "peer-all",
+            "peer-no-images",
+            "peer-receive-only",
+        ]));
+        let master_key = MasterKey::generate().unwrap();
+        let uc = build_use_case_with_policies(
+            Settings::default(),
+            vec![
+                paired("peer-all", PairingState::Trusted),
+                paired("peer-no-images", PairingState::Trusted),
+                paired("peer-receive-only", PairingState::Trusted),
+            ],
+            vec![
+                ("peer-no-images", no_images_policy()),
+                (
+                    "peer-receive-only",
+                    DeviceSyncPolicy {
+                        direction: SyncDirection::ReceiveOnly,
+                        ..DeviceSyncPolicy::default()
+                    },
+                ),
+            ],
+            network.clone(),
+            master_key.clone(),
+            Arc::new(TestQueue::default()),
+            Arc::new(TestClock::default()),
+        )
+        .await;
+
+        assert_eq!(uc.execute(text_and_image_snapshot()).await.unwrap(), 2);
+
+        let sent: HashMap<String, Vec<u8>> = network.sent_clipboard().iter().cloned().collect();
+        assert_eq!(sent.len(), 2);
+        let full = decrypt_snapshot(&master_key, &sent["peer-all"]).await;
+        assert_eq!(full.representations.len(), 2);
+        let trimmed = decrypt_snapshot(&master_key, &sent["peer-no-images"]).await;
+        assert_eq!(trimmed.representations.len(), 1);
+        assert_eq!(
+            trimmed.representations[0].id,
+            RepresentationId::from("rep-text")
+        );
+    }
+
+    #[tokio::test]
+    async fn flush_trims_queued_messages_per_device() {
+        let network = Arc::new(TestNetwork::new().with_connected(["peer-all", "peer-no-images"]));
+        let clock = Arc::new(TestClock::default());
+        let master_key = MasterKey::generate().unwrap();
+        let uc = build_use_case_with_policies(
+            interval_settings(IntervalBatchMode::All),
+            vec![
+                paired("peer-all", PairingState::Trusted),
+                paired("peer-no-images", PairingState::Trusted),
+            ],
+            vec![("peer-no-images", no_images_policy())],
+            network.clone(),
+            master_key.clone(),
+            Arc::new(TestQueue::default()),
+            clock.clone(),
+        )
+        .await;
+
+        uc.execute(text_and_image_snapshot()).await.unwrap();
+        clock.set(60_000);
+        assert_eq!(uc.flush_pending().await.unwrap(), 1);
+
+        let sent: HashMap<String, Vec<u8>> = network.sent_clipboard().iter().cloned().collect();
+        assert_eq!(sent.len(), 2);
+        let full = decrypt_snapshot(&master_key, &sent["peer-all"]).await;
+        assert_eq!(full.representations.len(), 2);
+        let trimmed = decrypt_snapshot(&master_key, &sent["peer-no-images"]).await;
+        assert_eq!(trimmed.representations.len(), 1);
+        assert_eq!(
+            trimmed.representations[0].id,
+            RepresentationId::from("rep-text")
+        );
+    }
+
+    #[tokio::test]
+    async fn flush_keeps_messages_no_peer_accepted() {
+        let network = Arc::new(
+            TestNetwork::new()
+                .with_connected(["peer-trusted"])
+                .with_offline_peer("peer-trusted"),
+        );
+        let queue = Arc::new(TestQueue::default());
+        let clock = Arc::new(TestClock::default());
+        let uc = build_use_case_with_queue(
+            interval_settings(IntervalBatchMode::LatestOnly),
+            vec![paired("peer-trusted", PairingState::Trusted)],
+            network.clone(),
+            MasterKey::generate().unwrap(),
+            queue.clone(),
+            clock.clone(),
+        )
+        .await;
+
+        uc.execute(text_snapshot()).await.unwrap();
+        clock.set(60_000);
+
+        assert_eq!(uc.flush_pending().await.unwrap(), 0);
+        assert_eq!(queue.messages.lock().unwrap().len(), 1);
+
+        network.set_online("peer-trusted", true);
+        assert_eq!(uc.flush_pending().await.unwrap(), 1);
+        assert_eq!(network.sent_clipboard().len(), 1);
+        assert!(queue.messages.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn queued_messages_hold_no_plaintext() {
+        let queue = Arc::new(TestQueue::default());
+        let master_key = MasterKey::generate().unwrap();
+        let uc = build_use_case_with_queue(
+            interval_settings(IntervalBatchMode::LatestOnly),
+            vec![],
+            Arc::new(TestNetwork::new()),
+            master_key.clone(),
+            queue.clone(),
+            Arc::new(TestClock::default()),
+        )
+        .await;
+
+        uc.execute(text_snapshot()).await.unwrap();
+
+        let queued = queue.messages.lock().unwrap()[0].clone();
+        let sealed: EncryptedBlob = serde_json::from_slice(&queued.payload).unwrap();
+        let plaintext = EncryptionRepository
+            .decrypt_blob(
+                &master_key,
+                &sealed,
+                &aad::for_outbound_queue(&queued.message_id),
+            )
+            .await
+            .unwrap();
+        let snapshot: SystemClipboardSnapshot = serde_json::from_slice(&plaintext).unwrap();
+        assert_eq!(snapshot.representations[0].bytes, b"hello".to_vec());
+    }
+}
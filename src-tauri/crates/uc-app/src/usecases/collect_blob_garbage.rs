@@ -0,0 +1,421 @@
+use anyhow::Result;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{info, warn};
+use uc_core::ports::{BlobRepositoryPort, BlobStorePort, ClockPort, ThumbnailRepositoryPort};
+use uc_core::BlobId;
+
+/// Blobs and files younger than this are never collected, so a blob that is
+/// being written (file first, then `blob` row, then the representation or
+/// thumbnail reference) is not mistaken for garbage.
+const GRACE_PERIOD_MS: i64 = 60 * 60 * 1000;
+
+const PAGE_SIZE: usize = 200;
+
+/// Result of one garbage collection pass.
+/// 一次 blob 垃圾回收的结果。
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct BlobGcReport {
+    /// Unreferenced `blob` rows removed together with their files.
+    pub deleted_blobs: usize,
+    /// Files in blob storage that had no `blob` row.
+    pub orphan_files: usize,
+    /// Thumbnail rows whose representation no longer exists.
+    pub orphan_thumbnails: usize,
+    /// Bytes freed in blob storage.
+    pub reclaimed_bytes: u64,
+}
+
+/// Mark-and-sweep garbage collection for blob storage.
+/// blob 存储的标记-清除垃圾回收用例。
+///
+/// Blobs are content-addressed and shared, so deleting an entry never deletes
+/// its blobs directly. Instead this pass:
+/// 1. drops thumbnail rows whose representation is gone,
+/// 2. deletes `blob` rows referenced by neither a representation nor a
+///    thumbnail, then their files,
+/// 3. deletes files in the blob directory that have no `blob` row.
+pub struct CollectBlobGarbage {
+    blob_repo: Arc<dyn BlobRepositoryPort>,
+    blob_store: Arc<dyn BlobStorePort>,
+    thumbnail_repo: Arc<dyn ThumbnailRepositoryPort>,
+    clock: Arc<dyn ClockPort>,
+}
+
+impl CollectBlobGarbage {
+    pub fn new(
+        blob_repo: Arc<dyn BlobRepositoryPort>,
+        blob_store: Arc<dyn BlobStorePort>,
+        thumbnail_repo: Arc<dyn ThumbnailRepositoryPort>,
+        clock: Arc<dyn ClockPort>,
+    ) -> Self {
+        Self {
+            blob_repo,
+            blob_store,
+            thumbnail_repo,
+            clock,
+        }
+    }
+
+    /// Run one pass. Failures on individual blobs are logged and skipped.
+    #[tracing::instrument(name = "usecase.collect_blob_garbage.execute", skip(self))]
+    pub async fn execute(&self) -> Result<BlobGcReport> {
+        let mut report = BlobGcReport::default();
+        let cutoff_ms = self.clock.now_ms() - GRACE_PERIOD_MS;
+
+        report.orphan_thumbnails = self.thumbnail_repo.delete_orphaned_thumbnails().await?;
+
+        // Sweep unreferenced blob rows. The delete re-checks references and
+        // age atomically, so a row that was reused after listing is kept. The
+        // row goes first: if deleting the file then fails, it is picked up as
+        // an orphan file on a later pass.
+        loop {
+            let page = self
+                .blob_repo
+                .list_unreferenced_blobs(cutoff_ms, PAGE_SIZE)
+                .await?;
+            if page.is_empty() {
+                break;
+            }
+
+            let mut progressed = false;
+            for blob in page {
+                match self
+                    .blob_repo
+                    .delete_unreferenced_blob(&blob.blob_id, cutoff_ms)
+                    .await
+                {
+                    Ok(true) => progressed = true,
+                    Ok(false) => continue,
+                    Err(err) => {
+                        warn!(error = %err, blob_id = %blob.blob_id, "Failed to delete blob record");
+                        continue;
+                    }
+                }
+                report.deleted_blobs += 1;
+                match self.blob_store.delete(&blob.blob_id).await {
+                    Ok(bytes) => report.reclaimed_bytes += bytes,
+                    Err(err) => {
+                        warn!(error = %err, blob_id = %blob.blob_id, "Failed to delete blob file");
+                    }
+                }
+            }
+            if !progressed {
+                break;
+            }
+        }
+
+        // Reconcile the blob directory against the remaining rows.
+        let objects = self.blob_store.list_objects().await?;
+        if !objects.is_empty() {
+            let known = self.known_blob_ids().await?;
+            for object in objects {
+                if known.contains(&object.blob_id) || object.modified_at_ms >= cutoff_ms {
+                    continue;
+                }
+                match self.blob_store.delete(&object.blob_id).await {
+                    Ok(bytes) => {
+                        report.orphan_files += 1;
+                        report.reclaimed_bytes += bytes;
+                    }
+                    Err(err) => {
+                        warn!(error = %err, blob_id = %object.blob_id, "Failed to delete orphan blob file");
+                    }
+                }
+            }
+        }
+
+        info!(
+            deleted_blobs = report.deleted_blobs,
+            orphan_files = report.orphan_files,
+            orphan_thumbnails = report.orphan_thumbnails,
+            reclaimed_bytes = report.reclaimed_bytes,
+            "Blob garbage collection finished"
+        );
+        Ok(report)
+    }
+
+    async fn known_blob_ids(&self) -> Result<HashSet<BlobId>> {
+        let mut known = HashSet::new();
+        let mut after: Option<BlobId> = None;
+        loop {
+            let page = self
+                .blob_repo
+                .list_blob_ids(after.as_ref(), PAGE_SIZE)
+                .await?;
+            let Some(last) = page.last().cloned() else {
+                break;
+            };
+            let full_page = page.len() == PAGE_SIZE;
+            known.extend(page);
+            if !full_page {
+                break;
+            }
+            after = Some(last);
+        }
+        Ok(known)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use std::sync::Mutex;
+    use uc_core::blob::BlobStorageLocator;
+    use uc_core::clipboard::ThumbnailMetadata;
+    use uc_core::ids::RepresentationId;
+    use uc_core::ports::StoredBlobObject;
+    use uc_core::{Blob, ContentHash};
+
+    const NOW_MS: i64 = 10 * GRACE_PERIOD_MS;
+    const OLD_MS: i64 = NOW_MS - 2 * GRACE_PERIOD_MS;
+
+    struct FixedClock;
+
+    impl ClockPort for FixedClock {
+        fn now_ms(&self) -> i64 {
+            NOW_MS
+        }
+    }
+
+    /// Blob rows plus the set of ids still referenced by representations/thumbnails.
+    #[derive(Default)]
+    struct MemBlobRepo {
+        rows: Mutex<HashMap<BlobId, i64>>,
+        referenced: Mutex<HashSet<BlobId>>,
+    }
+
+    #[async_trait]
+    impl BlobRepositoryPort for MemBlobRepo {
+        async fn insert_blob(&self, blob: &Blob) -> Result<()> {
+            self.rows
+                .lock()
+                .unwrap()
+                .insert(blob.blob_id.clone(), blob.created_at_ms);
+            Ok(())
+        }
+
+        async fn find_by_hash(&self, _content_hash: &ContentHash) -> Result<Option<Blob>> {
+            Ok(None)
+        }
+
+        async fn list_blob_ids(&self, after: Option<&BlobId>, limit: usize) -> Result<Vec<BlobId>> {
+            let mut ids: Vec<BlobId> = self.rows.lock().unwrap().keys().cloned().collect();
+            ids.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+            Ok(ids
+                .into_iter()
+                .filter(|id| after.is_none_or(|after| id.as_str() > after.as_str()))
+                .take(limit)
+                .collect())
+        }
+
+        async fn list_unreferenced_blobs(
+            &self,
+            created_before_ms: i64,
+            limit: usize,
+        ) -> Result<Vec<Blob>> {
+            let referenced = self.referenced.lock().unwrap();
+            Ok(self
+                .rows
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(id, created)| **created < created_before_ms && !referenced.contains(*id))
+                .take(limit)
+                .map(|(id, created)| blob(id.as_str(), *created))
+                .collect())
+        }
+
+        async fn count_blobs(&self) -> Result<usize> {
+            Ok(self.rows.lock().unwrap().len())
+        }
+
+        async fn touch_blob(&self, blob_id: &BlobId, now_ms: i64) -> Result<bool> {
+            Ok(self
+                .rows
+                .lock()
+                .unwrap()
+                .get_mut(blob_id)
+                .map(|created| *created = now_ms)
+                .is_some())
+        }
+
+        async fn delete_unreferenced_blob(
+            &self,
+            blob_id: &BlobId,
+            created_before_ms: i64,
+        ) -> Result<bool> {
+            if self.referenced.lock().unwrap().contains(blob_id) {
+                return Ok(false);
+            }
+            let mut rows = self.rows.lock().unwrap();
+            match rows.get(blob_id) {
+                Some(created) if *created < created_before_ms => {
+                    rows.remove(blob_id);
+                    Ok(true)
+                }
+                _ => Ok(false),
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct MemBlobStore {
+        files: Mutex<HashMap<BlobId, (u64, i64)>>,
+    }
+
+    impl MemBlobStore {
+        fn add(&self, id: &str, size: u64, modified_at_ms: i64) {
+            self.files
+                .lock()
+                .unwrap()
+                .insert(BlobId::from(id), (size, modified_at_ms));
+        }
+    }
+
+    #[async_trait]
+    impl BlobStorePort for MemBlobStore {
+        async fn put(&self, _blob_id: &BlobId, _data: &[u8]) -> Result<PathBuf> {
+            unimplemented!()
+        }
+
+        async fn get(&self, _blob_id: &BlobId) -> Result<Vec<u8>> {
+            unimplemented!()
+        }
+
+        async fn delete(&self, blob_id: &BlobId) -> Result<u64> {
+            Ok(self
+                .files
+                .lock()
+                .unwrap()
+                .remove(blob_id)
+                .map(|(size, _)| size)
+                .unwrap_or(0))
+        }
+
+        async fn list_objects(&self) -> Result<Vec<StoredBlobObject>> {
+            Ok(self
+                .files
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(id, (size, modified_at_ms))| StoredBlobObject {
+                    blob_id: id.clone(),
+                    size_bytes: *size,
+                    modified_at_ms: *modified_at_ms,
+                })
+                .collect())
+        }
+
+        async fn put_stream(
+            &self,
+            blob_id: &uc_core::BlobId,
+            reader: &mut (dyn tokio::io::AsyncRead + Send + Unpin),
+        ) -> anyhow::Result<std::path::PathBuf> {
+            let mut data = Vec::new();
+            tokio::io::AsyncReadExt::read_to_end(reader, &mut data).await?;
+            self.put(blob_id, &data).await
+        }
+
+        async fn open_read(
+            &self,
+            blob_id: &uc_core::BlobId,
+        ) -> anyhow::Result<uc_core::ports::BlobReader> {
+            Ok(Box::new(std::io::Cursor::new(self.get(blob_id).await?)))
+        }
+    }
+
+    struct OrphanThumbnails(usize);
+
+    #[async_trait]
+    impl ThumbnailRepositoryPort for OrphanThumbnails {
+        async fn get_by_representation_id(
+            &self,
+            _representation_id: &RepresentationId,
+        ) -> Result<Option<ThumbnailMetadata>> {
+            Ok(None)
+        }
+
+        async fn insert_thumbnail(&self, _metadata: &ThumbnailMetadata) -> Result<()> {
+            Ok(())
+        }
+
+        async fn delete_orphaned_thumbnails(&self) -> Result<usize> {
+            Ok(self.0)
+        }
+    }
+
+    fn blob(id: &str, created_at_ms: i64) -> Blob {
+        Blob::new(
+            BlobId::from(id),
+            BlobStorageLocator::new_local_fs(PathBuf::from(id)),
+            1,
+            ContentHash::from(format!("hash-{id}").as_str()),
+            created_at_ms,
+        )
+    }
+
+    #[tokio::test]
+    async fn sweeps_unreferenced_blobs_and_orphan_files() {
+        let repo = Arc::new(MemBlobRepo::default());
+        let store = Arc::new(MemBlobStore::default());
+
+        // Referenced blob: kept.
+        repo.insert_blob(&blob("live", OLD_MS)).await.unwrap();
+        repo.referenced.lock().unwrap().insert(BlobId::from("live"));
+        store.add("live", 100, OLD_MS);
+        // Unreferenced blob: row and file removed.
+        repo.insert_blob(&blob("dead", OLD_MS)).await.unwrap();
+        store.add("dead", 200, OLD_MS);
+        // Unreferenced but inside the grace period: kept.
+        repo.insert_blob(&blob("fresh", NOW_MS)).await.unwrap();
+        store.add("fresh", 300, NOW_MS);
+        // File without a row: removed once old enough.
+        store.add("orphan", 400, OLD_MS);
+        store.add("orphan-new", 500, NOW_MS);
+
+        let usecase = CollectBlobGarbage::new(
+            repo.clone(),
+            store.clone(),
+            Arc::new(OrphanThumbnails(2)),
+            Arc::new(FixedClock),
+        );
+        let report = usecase.execute().await.unwrap();
+
+        assert_eq!(
+            report,
+            BlobGcReport {
+                deleted_blobs: 1,
+                orphan_files: 1,
+                orphan_thumbnails: 2,
+                reclaimed_bytes: 600,
+            }
+        );
+        let mut rows: Vec<String> = repo
+            .rows
+            .lock()
+            .unwrap()
+            .keys()
+            .map(|id| id.to_string())
+            .collect();
+        rows.sort();
+        assert_eq!(rows, vec!["fresh", "live"]);
+        let mut files: Vec<String> = store
+            .files
+            .lock()
+            .unwrap()
+            .keys()
+            .map(|id| id.to_string())
+            .collect();
+        files.sort();
+        assert_eq!(files, vec!["fresh", "live", "orphan-new"]);
+
+        // A second pass has nothing left to do.
+        let report = usecase.execute().await.unwrap();
+        assert_eq!(report.reclaimed_bytes, 0);
+        assert_eq!(report.deleted_blobs, 0);
+    }
+}
@@ -5,6 +5,7 @@ use uc_core::ids::EntryId;
 use uc_core::ports::{
     ClipboardEntryRepositoryPort, ClipboardEventWriterPort, ClipboardSelectionRepositoryPort,
 };
+use uc_core::ClipboardEntry;
 
 /// Use case for deleting clipboard entries with all associated data.
 /// 删除剪贴板条目及其所有关联数据的用例。
@@ -98,6 +99,13 @@ impl DeleteClipboardEntry {
             entry_id = %entry_id
         ))
         .await?;
+        self.delete_loaded(&entry).await
+    }
+
+    /// Run steps 2-4 of [`Self::execute`] for an entry the caller already
+    /// loaded, such as a soft-deleted entry being purged.
+    pub(crate) async fn delete_loaded(&self, entry: &ClipboardEntry) -> Result<()> {
+        let entry_id = &entry.entry_id;
         let event_id = entry.event_id.clone();
 
         // 2. Delete selection (references entry)
@@ -262,6 +270,58 @@ mod tests {
         async fn list_entries(&self, _limit: usize, _offset: usize) -> Result<Vec<ClipboardEntry>> {
             unimplemented!("Not used in tests")
         }
+
+        async fn list_retention_candidates(
+            &self,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::RetentionCandidate>> {
+            Ok(Vec::new())
+        }
+
+        async fn soft_delete_entries(
+            &self,
+            _entry_ids: &[uc_core::ids::EntryId],
+            _deleted_at_ms: i64,
+        ) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_soft_deleted_entries(
+            &self,
+            _deleted_before_ms: i64,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_entry_source_device(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+        ) -> anyhow::Result<Option<uc_core::ids::DeviceId>> {
+            Ok(None)
+        }
+
+        async fn set_entry_pinned(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+            _pinned: bool,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn list_pinned_entries(
+            &self,
+            _limit: usize,
+            _offset: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn list_catch_up_candidates(
+            &self,
+            _since_ms: i64,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::CatchUpCandidate>> {
+            Ok(Vec::new())
+        }
     }
 
     // Mock selection repository
@@ -122,64 +122,85 @@ impl InitializeEncryption {
                 return Err(InitializeEncryptionError::AlreadyInitialized);
             }
 
-            debug!("Getting current scope");
-            let scope = self.key_scope.current_scope().await?;
-            debug!(scope = %scope.to_identifier(), "Got scope");
-
-            debug!("Creating keyslot draft");
-            let keyslot_draft = KeySlot::draft_v1(scope.clone())?;
-            debug!("Keyslot draft created");
-
-            // 2. derive KEK
-            debug!("Deriving KEK");
-            let kek = self
-                .encryption
-                .derive_kek(&passphrase, &keyslot_draft.salt, &keyslot_draft.kdf)
-                .await?;
-            debug!("KEK derived successfully");
-
-            // 3. generate MasterKey
-            debug!("Generating master key");
-            let master_key = MasterKey::generate()?;
-            debug!("Master key generated");
-
-            // 4. wrap MasterKey
-            debug!("Wrapping master key");
-            let blob = self
-                .encryption
-                .wrap_master_key(&kek, &master_key, EncryptionAlgo::XChaCha20Poly1305)
-                .await?;
-            debug!("Master key wrapped successfully");
-
-            let keyslot = keyslot_draft.finalize(WrappedMasterKey { blob });
-            debug!("Keyslot finalized");
-
-            // 5. persist wrapped key, store keyslot
-            debug!("Storing keyslot");
-            self.key_material.store_keyslot(&keyslot).await?;
-            debug!("Keyslot stored successfully");
-
-            // 6. store KEK material into keyring
-            debug!("Storing KEK in keyring");
-            self.key_material.store_kek(&scope, &kek).await?;
-            debug!("KEK stored successfully");
-
-            // 7. persist initialized state
-            debug!("Persisting initialized state");
-            self.encryption_state_repo.persist_initialized().await?;
-            debug!("Encryption state persisted");
-
-            // 8. set master key in session for immediate use
-            debug!("Setting master key in session");
-            self.encryption_session.set_master_key(master_key).await?;
-            debug!("Master key set in session successfully");
-
-            info!("Encryption initialized successfully");
-            Ok(())
+            self.create_keyslot(passphrase).await
         }
         .instrument(span)
         .await
     }
+
+    /// Create the keyslot and KEK of a newly created space.
+    /// 为新建空间创建密钥槽与 KEK。
+    ///
+    /// Unlike [`Self::execute`] this does not refuse when another space has
+    /// already initialized encryption; the key scope must already point at
+    /// the new space.
+    pub async fn execute_for_new_space(
+        &self,
+        passphrase: Passphrase,
+    ) -> Result<(), InitializeEncryptionError> {
+        let span = info_span!("usecase.initialize_encryption.execute_for_new_space");
+        self.create_keyslot(passphrase).instrument(span).await
+    }
+
+    async fn create_keyslot(
+        &self,
+        passphrase: Passphrase,
+    ) -> Result<(), InitializeEncryptionError> {
+        debug!("Getting current scope");
+        let scope = self.key_scope.current_scope().await?;
+        debug!(scope = %scope.to_identifier(), "Got scope");
+
+        debug!("Creating keyslot draft");
+        let keyslot_draft = KeySlot::draft_v1(scope.clone())?;
+        debug!("Keyslot draft created");
+
+        // 2. derive KEK
+        debug!("Deriving KEK");
+        let kek = self
+            .encryption
+            .derive_kek(&passphrase, &keyslot_draft.salt, &keyslot_draft.kdf)
+            .await?;
+        debug!("KEK derived successfully");
+
+        // 3. generate MasterKey
+        debug!("Generating master key");
+        let master_key = MasterKey::generate()?;
+        debug!("Master key generated");
+
+        // 4. wrap MasterKey
+        debug!("Wrapping master key");
+        let blob = self
+            .encryption
+            .wrap_master_key(&kek, &master_key, EncryptionAlgo::XChaCha20Poly1305)
+            .await?;
+        debug!("Master key wrapped successfully");
+
+        let keyslot = keyslot_draft.finalize(WrappedMasterKey { blob });
+        debug!("Keyslot finalized");
+
+        // 5. persist wrapped key, store keyslot
+        debug!("Storing keyslot");
+        self.key_material.store_keyslot(&keyslot).await?;
+        debug!("Keyslot stored successfully");
+
+        // 6. store KEK material into keyring
+        debug!("Storing KEK in keyring");
+        self.key_material.store_kek(&scope, &kek).await?;
+        debug!("KEK stored successfully");
+
+        // 7. persist initialized state
+        debug!("Persisting initialized state");
+        self.encryption_state_repo.persist_initialized().await?;
+        debug!("Encryption state persisted");
+
+        // 8. set master key in session for immediate use
+        debug!("Setting master key in session");
+        self.encryption_session.set_master_key(master_key).await?;
+        debug!("Master key set in session successfully");
+
+        info!("Encryption initialized successfully");
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -331,6 +352,7 @@ mod tests {
                 nonce: vec![0u8; 24],
                 ciphertext: vec![0u8; 32],
                 aad_fingerprint: None,
+                key_epoch: 0,
             })
         }
 
@@ -355,6 +377,7 @@ mod tests {
                 nonce: vec![0u8; 24],
                 ciphertext: vec![],
                 aad_fingerprint: None,
+                key_epoch: 0,
             })
         }
 
@@ -415,6 +438,38 @@ mod tests {
                 .store(false, std::sync::atomic::Ordering::SeqCst);
             Ok(())
         }
+
+        async fn get_current_key(
+            &self,
+        ) -> Result<
+            (
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            ),
+            uc_core::security::model::EncryptionError,
+        > {
+            Ok((0, self.get_master_key().await?))
+        }
+
+        async fn get_master_key_for_epoch(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+        ) -> Result<uc_core::security::model::MasterKey, uc_core::security::model::EncryptionError>
+        {
+            self.get_master_key().await
+        }
+
+        async fn set_epoch_keys(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+            master_key: uc_core::security::model::MasterKey,
+            _older: Vec<(
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            )>,
+        ) -> Result<(), uc_core::security::model::EncryptionError> {
+            self.set_master_key(master_key).await
+        }
     }
 
     #[tokio::test]
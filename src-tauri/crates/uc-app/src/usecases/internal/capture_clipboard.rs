@@ -5,15 +5,22 @@ use anyhow::Result;
 use futures::future::try_join_all;
 use tracing::{debug, info, info_span, warn, Instrument};
 
-use uc_core::ids::{EntryId, EventId};
-use uc_core::ports::clipboard::{RepresentationCachePort, SpoolQueuePort, SpoolRequest};
+use uc_core::clipboard::{
+    pick_search_text, search_text, ObservedClipboardRepresentation,
+    PersistedClipboardRepresentation, RemotePayloadRef,
+};
+use uc_core::ids::{DeviceId, EntryId, EventId};
+use uc_core::ports::clipboard::{
+    ClipboardSearchIndexPort, RepresentationCachePort, SpoolQueuePort, SpoolRequest,
+};
 use uc_core::ports::{
     ClipboardEntryRepositoryPort, ClipboardEventWriterPort, ClipboardRepresentationNormalizerPort,
-    DeviceIdentityPort, SelectRepresentationPolicyPort,
+    DeviceIdentityPort, SelectRepresentationPolicyPort, SettingsPort, SyncFilterPolicyPort,
 };
 use uc_core::{
-    ClipboardChangeOrigin, ClipboardEntry, ClipboardEvent, ClipboardSelectionDecision,
-    PayloadAvailability, SystemClipboardSnapshot,
+    ClipboardChangeOrigin, ClipboardEntry, ClipboardEvent, ClipboardSearchDocument,
+    ClipboardSelectionDecision, ContentKind, PayloadAvailability, SyncSkipReason,
+    SystemClipboardSnapshot,
 };
 
 /// Capture clipboard content and create persistent entries.
@@ -22,12 +29,14 @@ use uc_core::{
 ///
 /// # Behavior / 行为
 /// - 1. Use the provided snapshot from the platform layer (事实)
+/// - 1a. Record why the sync filter policy keeps the entry local (同步过滤)
 /// - 2. Generate ClipboardEvent with timestamp (时间点)
 /// - 3. Normalize snapshot representations (类型转换)
 /// - 4. Apply representation selection policy (策略决策)
 /// - 5. Create ClipboardEntry for user consumption (用户可见结果)
 ///
 /// - 1. 使用平台层提供的快照（事实）
+/// - 1a. 记录同步过滤策略不同步该条目的原因（同步过滤）
 /// - 2. 生成带时间戳的剪贴板事件（时间点）
 /// - 3. 规范化快照表示形式（类型转换）
 /// - 4. 应用表示形式选择策略（策略决策）
@@ -40,6 +49,39 @@ use uc_core::{
 ///
 /// 此用例使用 **trait 对象** (`Arc<dyn Port>`) 而不是泛型类型参数。
 /// 这是 uc-app 层用例的推荐模式。
+/// Why a capture did not produce an entry.
+///
+/// 捕获未生成条目的原因。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotRecordedReason {
+    /// The change was written by this app restoring an entry.
+    /// 由本应用恢复条目引起的变化。
+    LocalRestore,
+}
+
+/// Result of a capture attempt.
+///
+/// 一次捕获的结果。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CaptureOutcome {
+    /// An entry was stored for this event.
+    /// 已为该事件保存条目。
+    Recorded(EventId),
+    /// Nothing was stored.
+    /// 未保存任何内容。
+    NotRecorded(NotRecordedReason),
+}
+
+impl CaptureOutcome {
+    /// The recorded event, if any.
+    pub fn event_id(&self) -> Option<&EventId> {
+        match self {
+            CaptureOutcome::Recorded(event_id) => Some(event_id),
+            CaptureOutcome::NotRecorded(_) => None,
+        }
+    }
+}
+
 pub struct CaptureClipboardUseCase {
     entry_repo: Arc<dyn ClipboardEntryRepositoryPort>,
     event_writer: Arc<dyn ClipboardEventWriterPort>,
@@ -48,6 +90,9 @@ pub struct CaptureClipboardUseCase {
     device_identity: Arc<dyn DeviceIdentityPort>,
     representation_cache: Arc<dyn RepresentationCachePort>,
     spool_queue: Arc<dyn SpoolQueuePort>,
+    search_index: Arc<dyn ClipboardSearchIndexPort>,
+    sync_filter: Arc<dyn SyncFilterPolicyPort>,
+    settings: Arc<dyn SettingsPort>,
 }
 
 impl CaptureClipboardUseCase {
@@ -63,6 +108,9 @@ impl CaptureClipboardUseCase {
     /// - `device_identity`: Current device identification
     /// - `representation_cache`: Cache for representation metadata
     /// - `spool_queue`: Queue for disk spool requests
+    /// - `search_index`: Full-text index fed with extracted text
+    /// - `sync_filter`: Store/sync decision per representation
+    /// - `settings`: Source of the sync settings the filter evaluates
     ///
     /// - `entry_repo`: 剪贴板条目持久化
     /// - `event_writer`: 事件和表示形式存储
@@ -71,6 +119,10 @@ impl CaptureClipboardUseCase {
     /// - `device_identity`: 当前设备标识
     /// - `representation_cache`: 表示形式元数据缓存
     /// - `spool_queue`: 磁盘假脱机请求队列
+    /// - `search_index`: 写入提取文本的全文索引
+    /// - `sync_filter`: 逐个表示形式的存储/同步决策
+    /// - `settings`: 同步过滤所需的设置来源
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         entry_repo: Arc<dyn ClipboardEntryRepositoryPort>,
         event_writer: Arc<dyn ClipboardEventWriterPort>,
@@ -79,6 +131,9 @@ impl CaptureClipboardUseCase {
         device_identity: Arc<dyn DeviceIdentityPort>,
         representation_cache: Arc<dyn RepresentationCachePort>,
         spool_queue: Arc<dyn SpoolQueuePort>,
+        search_index: Arc<dyn ClipboardSearchIndexPort>,
+        sync_filter: Arc<dyn SyncFilterPolicyPort>,
+        settings: Arc<dyn SettingsPort>,
     ) -> Self {
         Self {
             entry_repo,
@@ -88,6 +143,9 @@ impl CaptureClipboardUseCase {
             device_identity,
             representation_cache,
             spool_queue,
+            search_index,
+            sync_filter,
+            settings,
         }
     }
 
@@ -111,15 +169,16 @@ impl CaptureClipboardUseCase {
     ///               来自平台层的预捕获剪贴板快照
     ///
     /// # Returns / 返回值
-    /// - `EventId` of the created capture event
-    /// - 创建的捕获事件的 `EventId`
+    /// - [`CaptureOutcome::Recorded`] with the `EventId` of the created event,
+    ///   or [`CaptureOutcome::NotRecorded`] when nothing was stored
+    /// - 创建的捕获事件的 `EventId`，未保存时返回不记录的原因
     ///
     /// # When to Use / 使用时机
     /// - Called from clipboard change callback (snapshot already read)
     /// - 从剪贴板变化回调调用时（快照已读取）
     /// - Avoids redundant system clipboard reads
     /// - 避免重复读取系统剪贴板
-    pub async fn execute(&self, snapshot: SystemClipboardSnapshot) -> Result<EventId> {
+    pub async fn execute(&self, snapshot: SystemClipboardSnapshot) -> Result<CaptureOutcome> {
         self.execute_with_origin(snapshot, ClipboardChangeOrigin::LocalCapture)
             .await
     }
@@ -128,7 +187,50 @@ impl CaptureClipboardUseCase {
         &self,
         snapshot: SystemClipboardSnapshot,
         origin: ClipboardChangeOrigin,
+    ) -> Result<CaptureOutcome> {
+        self.capture(snapshot, origin, None, &[]).await
+    }
+
+    /// Capture a snapshot received from `source_device`.
+    ///
+    /// The event is recorded with `ClipboardChangeOrigin::RemotePush` and the
+    /// sender as its source device, so offline catch-up never sends it back.
+    /// Representations named in `remote_payloads` carry no bytes and are
+    /// persisted as [`PayloadAvailability::Remote`], to be pulled on first use;
+    /// they are hashed by the plaintext hash the sender recorded, so the
+    /// snapshot hash matches the sender's.
+    ///
+    /// 捕获从 `source_device` 收到的快照：事件记录发送方为来源设备。延迟同步的表示以 `Remote`
+    /// 状态持久化，首次使用时再拉取，并按发送方记录的明文哈希计算快照哈希以与发送方一致。
+    pub async fn execute_remote_push(
+        &self,
+        snapshot: SystemClipboardSnapshot,
+        source_device: DeviceId,
+        remote_payloads: &[RemotePayloadRef],
     ) -> Result<EventId> {
+        let outcome = self
+            .capture(
+                snapshot,
+                ClipboardChangeOrigin::RemotePush,
+                Some(source_device),
+                remote_payloads,
+            )
+            .await?;
+        match outcome {
+            CaptureOutcome::Recorded(event_id) => Ok(event_id),
+            CaptureOutcome::NotRecorded(reason) => Err(anyhow::anyhow!(
+                "remote clipboard content was not recorded: {reason:?}"
+            )),
+        }
+    }
+
+    async fn capture(
+        &self,
+        snapshot: SystemClipboardSnapshot,
+        origin: ClipboardChangeOrigin,
+        source_device: Option<DeviceId>,
+        remote_payloads: &[RemotePayloadRef],
+    ) -> Result<CaptureOutcome> {
         let span = info_span!(
             "usecase.capture_clipboard.execute",
             source = "callback",
@@ -138,14 +240,21 @@ impl CaptureClipboardUseCase {
         async move {
             if origin == ClipboardChangeOrigin::LocalRestore {
                 info!(origin = ?origin, "Skipping clipboard capture");
-                return Ok(EventId::new());
+                return Ok(CaptureOutcome::NotRecorded(NotRecordedReason::LocalRestore));
             }
             info!("Starting clipboard capture with provided snapshot");
 
+            let sync_skip_reason = match origin {
+                ClipboardChangeOrigin::LocalCapture => self.evaluate_local_capture(&snapshot).await,
+                _ => None,
+            };
+
             let event_id = EventId::new();
             let captured_at_ms = snapshot.ts_ms;
-            let source_device = self.device_identity.current_device_id();
-            let snapshot_hash = snapshot.snapshot_hash();
+            let source_device =
+                source_device.unwrap_or_else(|| self.device_identity.current_device_id());
+            let search_source_device = source_device.as_str().to_string();
+            let snapshot_hash = snapshot.snapshot_hash_with_remote(remote_payloads);
 
             // 1. 生成 event + snapshot representations
             let new_event = ClipboardEvent::new(
@@ -161,7 +270,24 @@ impl CaptureClipboardUseCase {
                 .iter()
                 .map(|rep| self.representation_normalizer.normalize(rep))
                 .collect();
-            let normalized_reps = try_join_all(normalized_futures).await?;
+            let normalized_reps: Vec<_> = try_join_all(normalized_futures)
+                .await?
+                .into_iter()
+                .map(|rep| {
+                    match remote_payloads
+                        .iter()
+                        .find(|remote| remote.representation_id == rep.id)
+                    {
+                        Some(remote) => PersistedClipboardRepresentation::new_remote(
+                            rep.id,
+                            rep.format_id,
+                            rep.mime_type,
+                            remote.size_bytes as i64,
+                        ),
+                        None => rep,
+                    }
+                })
+                .collect();
             self.event_writer
                 .insert_event(&new_event, &normalized_reps)
                 .await?;
@@ -206,26 +332,119 @@ impl CaptureClipboardUseCase {
                 .duration_since(SystemTime::UNIX_EPOCH)
                 .map_err(|e| anyhow::anyhow!("Failed to get system time: {}", e))?
                 .as_millis() as i64;
-            let total_size = snapshot.total_size_bytes();
+            let total_size = snapshot.total_size_bytes()
+                + remote_payloads
+                    .iter()
+                    .map(|remote| remote.size_bytes as i64)
+                    .sum::<i64>();
 
-            let new_entry = ClipboardEntry::new(
+            let mut new_entry = ClipboardEntry::new(
                 entry_id.clone(),
                 event_id.clone(),
                 created_at_ms,
                 Self::generate_title(&snapshot),
                 total_size,
             );
+            new_entry.sync_skip_reason = sync_skip_reason;
             self.entry_repo
                 .save_entry_and_selection(&new_entry, &new_selection)
                 .await?;
 
+            // 6. Feed the full-text index (best effort, no-op while the index is closed)
+            let selected = &new_selection.selection;
+            let search_reps = [&selected.preview_rep_id, &selected.paste_rep_id]
+                .into_iter()
+                .filter_map(|rep_id| {
+                    snapshot
+                        .representations
+                        .iter()
+                        .find(|rep| &rep.id == rep_id)
+                });
+            if let Some((text, content_kind)) = Self::extract_search_text(search_reps) {
+                let document = ClipboardSearchDocument {
+                    entry_id: entry_id.clone(),
+                    text,
+                    content_kind,
+                    source_device: search_source_device,
+                    created_at_ms,
+                };
+                if let Err(err) = self.search_index.index_document(&document).await {
+                    warn!(entry_id = %entry_id, error = %err, "Failed to index clipboard entry");
+                }
+            }
+
             info!(event_id = %event_id, "Clipboard capture completed");
-            Ok(event_id)
+            Ok(CaptureOutcome::Recorded(event_id))
         }
         .instrument(span)
         .await
     }
 
+    /// Apply the sync filter policy to a locally captured snapshot.
+    /// 对本地捕获的快照应用同步过滤策略。
+    ///
+    /// Returns why the entry will not be synced. Every representation is kept
+    /// locally; the filter only decides what leaves the device. If settings
+    /// cannot be loaded nothing is filtered.
+    ///
+    /// Captures always land in the active space: it is the only one whose
+    /// master key is unlocked, so routing rules naming another space are
+    /// rejected when settings are saved.
+    async fn evaluate_local_capture(
+        &self,
+        snapshot: &SystemClipboardSnapshot,
+    ) -> Option<SyncSkipReason> {
+        let settings = match self.settings.load().await {
+            Ok(settings) => settings,
+            Err(err) => {
+                warn!(error = %err, "Failed to load settings, capturing without sync filter");
+                return None;
+            }
+        };
+
+        let kinds: Vec<ContentKind> = snapshot
+            .representations
+            .iter()
+            .map(|rep| ContentKind::classify(rep.mime.as_ref(), rep.format_id.as_ref()))
+            .collect();
+        let text = Self::extract_search_text(&snapshot.representations).map(|(text, _)| text);
+        let target_space = settings.spaces.route_capture(&kinds, text.as_deref());
+        if target_space != settings.spaces.active_space_id {
+            // A rule saved before a space switch; dropping the capture would lose it
+            warn!(
+                target_space = %target_space,
+                active_space = %settings.spaces.active_space_id,
+                "Routing rule names an inactive space, recording capture in the active space"
+            );
+        }
+
+        let skip_reason = self
+            .sync_filter
+            .evaluate(snapshot, &settings.sync)
+            .sync_skip_reason();
+        if let Some(reason) = skip_reason {
+            debug!(
+                reason = reason.as_str(),
+                "Captured entry will not be synced"
+            );
+        }
+        skip_reason
+    }
+
+    /// Pick the searchable text among `reps`.
+    /// 从给定表示中选出用于全文检索的文本。
+    ///
+    /// The index is fed from the preview and paste representations, the same
+    /// ones a rebuild reads back from storage.
+    fn extract_search_text<'a>(
+        reps: impl IntoIterator<Item = &'a ObservedClipboardRepresentation>,
+    ) -> Option<(String, ContentKind)> {
+        pick_search_text(reps.into_iter().filter_map(|rep| {
+            let kind = ContentKind::classify(rep.mime.as_ref(), rep.format_id.as_ref());
+            search_text(kind, &rep.bytes).map(|text| (kind, text))
+        }))
+    }
+
     /// Generate a title from the clipboard snapshot for display.
     /// 从剪贴板快照生成用于显示的标题。
     ///
@@ -288,14 +507,20 @@ mod tests {
     use async_trait::async_trait;
     use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Arc;
-    use uc_core::clipboard::PolicyError;
-    use uc_core::ids::EntryId;
+    use uc_core::clipboard::{
+        ObservedClipboardRepresentation, PolicyError, SelectRepresentationPolicyV1,
+        SyncFilterPolicyV1,
+    };
+    use uc_core::ids::{EntryId, FormatId, RepresentationId};
     use uc_core::ports::clipboard::{RepresentationCachePort, SpoolQueuePort, SpoolRequest};
     use uc_core::ports::{
         ClipboardEntryRepositoryPort, ClipboardEventWriterPort,
         ClipboardRepresentationNormalizerPort, DeviceIdentityPort, SelectRepresentationPolicyPort,
     };
-    use uc_core::{ClipboardChangeOrigin, ClipboardSelectionDecision, DeviceId};
+    use uc_core::settings::model::Settings;
+    use uc_core::{ClipboardChangeOrigin, ClipboardSelectionDecision, DeviceId, MimeType};
+    use uc_infra::clipboard::{ClipboardRepresentationNormalizer, InMemoryClipboardSearchIndex};
+    use uc_infra::config::ClipboardStorageConfig;
 
     struct MockEntryRepository {
         save_calls: Arc<AtomicUsize>,
@@ -349,6 +574,58 @@ mod tests {
         async fn delete_entry(&self, _entry_id: &EntryId) -> Result<()> {
             Ok(())
         }
+
+        async fn list_retention_candidates(
+            &self,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::RetentionCandidate>> {
+            Ok(Vec::new())
+        }
+
+        async fn soft_delete_entries(
+            &self,
+            _entry_ids: &[uc_core::ids::EntryId],
+            _deleted_at_ms: i64,
+        ) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_soft_deleted_entries(
+            &self,
+            _deleted_before_ms: i64,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_entry_source_device(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+        ) -> anyhow::Result<Option<uc_core::ids::DeviceId>> {
+            Ok(None)
+        }
+
+        async fn set_entry_pinned(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+            _pinned: bool,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn list_pinned_entries(
+            &self,
+            _limit: usize,
+            _offset: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn list_catch_up_candidates(
+            &self,
+            _since_ms: i64,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::CatchUpCandidate>> {
+            Ok(Vec::new())
+        }
     }
 
     #[async_trait]
@@ -422,52 +699,176 @@ mod tests {
         }
     }
 
-    #[tokio::test]
-    async fn capture_skips_local_restore() {
-        let save_calls = Arc::new(AtomicUsize::new(0));
-        let insert_calls = Arc::new(AtomicUsize::new(0));
-        let select_calls = Arc::new(AtomicUsize::new(0));
-        let normalize_calls = Arc::new(AtomicUsize::new(0));
-        let cache_put_calls = Arc::new(AtomicUsize::new(0));
-        let enqueue_calls = Arc::new(AtomicUsize::new(0));
+    struct MockSettings {
+        settings: Settings,
+    }
 
-        let use_case = CaptureClipboardUseCase::new(
+    #[async_trait]
+    impl SettingsPort for MockSettings {
+        async fn load(&self) -> Result<Settings> {
+            Ok(self.settings.clone())
+        }
+
+        async fn save(&self, _settings: &Settings) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct CallCounters {
+        save: Arc<AtomicUsize>,
+        insert: Arc<AtomicUsize>,
+        select: Arc<AtomicUsize>,
+        normalize: Arc<AtomicUsize>,
+        cache_put: Arc<AtomicUsize>,
+        enqueue: Arc<AtomicUsize>,
+    }
+
+    impl CallCounters {
+        fn assert_untouched(&self) {
+            assert_eq!(self.save.load(Ordering::SeqCst), 0);
+            assert_eq!(self.insert.load(Ordering::SeqCst), 0);
+            assert_eq!(self.select.load(Ordering::SeqCst), 0);
+            assert_eq!(self.normalize.load(Ordering::SeqCst), 0);
+            assert_eq!(self.cache_put.load(Ordering::SeqCst), 0);
+            assert_eq!(self.enqueue.load(Ordering::SeqCst), 0);
+        }
+    }
+
+    fn build_use_case(counters: &CallCounters, settings: Settings) -> CaptureClipboardUseCase {
+        CaptureClipboardUseCase::new(
             Arc::new(MockEntryRepository {
-                save_calls: save_calls.clone(),
+                save_calls: counters.save.clone(),
             }),
             Arc::new(MockEventWriter {
-                insert_calls: insert_calls.clone(),
+                insert_calls: counters.insert.clone(),
             }),
             Arc::new(MockRepresentationPolicy {
-                select_calls: select_calls.clone(),
+                select_calls: counters.select.clone(),
             }),
             Arc::new(MockNormalizer {
-                normalize_calls: normalize_calls.clone(),
+                normalize_calls: counters.normalize.clone(),
             }),
             Arc::new(MockDeviceIdentity),
             Arc::new(MockRepresentationCache {
-                put_calls: cache_put_calls.clone(),
+                put_calls: counters.cache_put.clone(),
             }),
             Arc::new(MockSpoolQueue {
-                enqueue_calls: enqueue_calls.clone(),
+                enqueue_calls: counters.enqueue.clone(),
             }),
-        );
+            Arc::new(InMemoryClipboardSearchIndex::new()),
+            Arc::new(SyncFilterPolicyV1::new()),
+            Arc::new(MockSettings { settings }),
+        )
+    }
+
+    #[tokio::test]
+    async fn capture_skips_local_restore() {
+        let counters = CallCounters::default();
+        let use_case = build_use_case(&counters, Settings::default());
 
         let snapshot = SystemClipboardSnapshot {
             ts_ms: 0,
             representations: vec![],
         };
 
-        let _ = use_case
+        let outcome = use_case
             .execute_with_origin(snapshot, ClipboardChangeOrigin::LocalRestore)
             .await
             .expect("expected ok result");
 
-        assert_eq!(save_calls.load(Ordering::SeqCst), 0);
-        assert_eq!(insert_calls.load(Ordering::SeqCst), 0);
-        assert_eq!(select_calls.load(Ordering::SeqCst), 0);
-        assert_eq!(normalize_calls.load(Ordering::SeqCst), 0);
-        assert_eq!(cache_put_calls.load(Ordering::SeqCst), 0);
-        assert_eq!(enqueue_calls.load(Ordering::SeqCst), 0);
+        assert_eq!(
+            outcome,
+            CaptureOutcome::NotRecorded(NotRecordedReason::LocalRestore)
+        );
+        counters.assert_untouched();
+    }
+
+    #[tokio::test]
+    async fn capture_keeps_oversized_snapshot_in_local_history() {
+        let counters = CallCounters::default();
+        let mut settings = Settings::default();
+        settings.sync.max_file_size_mb = 1;
+        let use_case = CaptureClipboardUseCase::new(
+            Arc::new(MockEntryRepository {
+                save_calls: counters.save.clone(),
+            }),
+            Arc::new(MockEventWriter {
+                insert_calls: counters.insert.clone(),
+            }),
+            Arc::new(SelectRepresentationPolicyV1::new()),
+            Arc::new(ClipboardRepresentationNormalizer::new(Arc::new(
+                ClipboardStorageConfig::defaults(),
+            ))),
+            Arc::new(MockDeviceIdentity),
+            Arc::new(MockRepresentationCache {
+                put_calls: counters.cache_put.clone(),
+            }),
+            Arc::new(MockSpoolQueue {
+                enqueue_calls: counters.enqueue.clone(),
+            }),
+            Arc::new(InMemoryClipboardSearchIndex::new()),
+            Arc::new(SyncFilterPolicyV1::new()),
+            Arc::new(MockSettings { settings }),
+        );
+
+        let snapshot = SystemClipboardSnapshot {
+            ts_ms: 0,
+            representations: vec![ObservedClipboardRepresentation {
+                id: RepresentationId::new(),
+                format_id: FormatId::from("public.png"),
+                mime: Some(MimeType("image/png".to_string())),
+                bytes: vec![0; 2 * 1024 * 1024],
+            }],
+        };
+
+        let outcome = use_case
+            .execute_with_origin(snapshot, ClipboardChangeOrigin::LocalCapture)
+            .await
+            .expect("expected ok result");
+
+        assert!(matches!(outcome, CaptureOutcome::Recorded(_)));
+        assert_eq!(counters.insert.load(Ordering::SeqCst), 1);
+        assert_eq!(counters.save.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn capture_records_snapshot_matching_rule_for_inactive_space() {
+        let counters = CallCounters::default();
+        let mut settings = Settings::default();
+        settings.spaces.spaces.push(uc_core::space::SpaceProfile {
+            id: uc_core::ids::SpaceId::from("work"),
+            name: "Work".to_string(),
+            created_at_ms: 0,
+        });
+        settings
+            .spaces
+            .routing_rules
+            .push(uc_core::space::SpaceRoutingRule {
+                space_id: uc_core::ids::SpaceId::from("work"),
+                content_kinds: vec![ContentKind::Image],
+                text_contains: None,
+            });
+        // A rule left over from before a space switch must not drop captures.
+        let use_case = build_use_case(&counters, settings);
+
+        let snapshot = SystemClipboardSnapshot {
+            ts_ms: 0,
+            representations: vec![ObservedClipboardRepresentation {
+                id: RepresentationId::new(),
+                format_id: FormatId::from("public.png"),
+                mime: Some(MimeType("image/png".to_string())),
+                bytes: vec![0; 16],
+            }],
+        };
+
+        let outcome = use_case
+            .execute_with_origin(snapshot, ClipboardChangeOrigin::LocalCapture)
+            .await
+            .expect("expected ok result");
+
+        assert!(matches!(outcome, CaptureOutcome::Recorded(_)));
+        assert_eq!(counters.insert.load(Ordering::SeqCst), 1);
+        assert_eq!(counters.save.load(Ordering::SeqCst), 1);
     }
 }
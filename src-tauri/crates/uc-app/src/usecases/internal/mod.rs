@@ -1,5 +1,5 @@
 pub mod capture_clipboard;
 pub mod resolve_clipboard_selection;
 
-pub use capture_clipboard::CaptureClipboardUseCase;
+pub use capture_clipboard::{CaptureClipboardUseCase, CaptureOutcome, NotRecordedReason};
 pub use resolve_clipboard_selection::ResolveClipboardSelectionPayloadUseCase;
@@ -0,0 +1,124 @@
+use anyhow::anyhow;
+use std::sync::Arc;
+use tracing::{debug, info, info_span, Instrument};
+
+use uc_core::{
+    network::{KeySlotUpdateMessage, PairingState},
+    ports::{
+        security::key_scope::KeyScopePort, EncryptionPort, EncryptionSessionPort, KeyMaterialPort,
+        PairedDeviceRepositoryPort,
+    },
+    security::{aad, model::EncryptedBlob},
+    PeerId,
+};
+
+use super::pending_updates::check_follows;
+use super::publisher::KeySlotUpdatePayload;
+use super::{PendingKeySlotUpdate, PendingKeySlotUpdates};
+
+/// Stage a keyslot pushed by a trusted peer after a passphrase change or key rotation.
+/// 暂存受信任设备修改口令或轮换密钥后推送的密钥槽。
+///
+/// The update must decrypt with a key held by this session and be newer than the
+/// local keyslot (a later key epoch, or a later revision of the same epoch); stale
+/// updates are ignored.
+/// Nothing is applied here: the user confirms it with the passphrase through
+/// [`ConfirmKeySlotUpdate`](super::ConfirmKeySlotUpdate).
+///
+/// 更新必须可用本会话持有的密钥解密，且比本地密钥槽更新；此处不会应用，需用户通过
+/// [`ConfirmKeySlotUpdate`](super::ConfirmKeySlotUpdate) 输入口令确认。
+pub struct ApplyKeySlotUpdate {
+    encryption: Arc<dyn EncryptionPort>,
+    encryption_session: Arc<dyn EncryptionSessionPort>,
+    key_material: Arc<dyn KeyMaterialPort>,
+    key_scope: Arc<dyn KeyScopePort>,
+    paired_device_repo: Arc<dyn PairedDeviceRepositoryPort>,
+    pending: Arc<PendingKeySlotUpdates>,
+}
+
+impl ApplyKeySlotUpdate {
+    pub fn new(
+        encryption: Arc<dyn EncryptionPort>,
+        encryption_session: Arc<dyn EncryptionSessionPort>,
+        key_material: Arc<dyn KeyMaterialPort>,
+        key_scope: Arc<dyn KeyScopePort>,
+        paired_device_repo: Arc<dyn PairedDeviceRepositoryPort>,
+        pending: Arc<PendingKeySlotUpdates>,
+    ) -> Self {
+        Self {
+            encryption,
+            encryption_session,
+            key_material,
+            key_scope,
+            paired_device_repo,
+            pending,
+        }
+    }
+
+    /// Returns the staged update, or `None` if the update is not newer than the local
+    /// keyslot or an equal or newer one is already waiting.
+    pub async fn execute(
+        &self,
+        peer_id: &str,
+        message: KeySlotUpdateMessage,
+    ) -> anyhow::Result<Option<PendingKeySlotUpdate>> {
+        let span = info_span!(
+            "usecase.apply_keyslot_update.execute",
+            peer_id = %peer_id,
+            message_id = %message.id,
+        );
+        async move {
+            let trusted = self
+                .paired_device_repo
+                .get_by_peer_id(&PeerId::from(peer_id))
+                .await?
+                .is_some_and(|device| device.pairing_state == PairingState::Trusted);
+            if !trusted {
+                return Err(anyhow!("keyslot update from untrusted peer"));
+            }
+
+            let encrypted: EncryptedBlob = serde_json::from_slice(&message.encrypted_payload)
+                .map_err(|e| anyhow!("failed to parse encrypted keyslot payload: {e}"))?;
+            let scope = self.key_scope.current_scope().await?;
+            let local = self.key_material.load_keyslot(&scope).await?;
+            // Peers send one copy per key epoch they hold; only the copy sealed
+            // with our current epoch is meant for us.
+            if encrypted.key_epoch != local.epoch {
+                debug!(
+                    seal_epoch = encrypted.key_epoch,
+                    local_epoch = local.epoch,
+                    "Skipping keyslot update sealed for another key epoch"
+                );
+                return Ok(None);
+            }
+            let seal_key = self
+                .encryption_session
+                .get_master_key_for_epoch(encrypted.key_epoch)
+                .await?;
+            let plaintext = self
+                .encryption
+                .decrypt_blob(&seal_key, &encrypted, &aad::for_keyslot_update(&message.id))
+                .await?;
+            let payload: KeySlotUpdatePayload = serde_json::from_slice(&plaintext)
+                .map_err(|e| anyhow!("failed to parse keyslot payload: {e}"))?;
+            if payload.keyslot.wrapped_master_key.is_none() {
+                return Err(anyhow!("keyslot update is missing the wrapped master key"));
+            }
+
+            if !payload.keyslot.is_newer_than(&local) {
+                debug!("Keyslot update is not newer than the local keyslot, ignoring");
+                return Ok(None);
+            }
+            check_follows(&local, &payload.keyslot)?;
+
+            if !self.pending.stage(peer_id, payload.keyslot) {
+                debug!("A newer keyslot update is already waiting for confirmation");
+                return Ok(None);
+            }
+            info!("Keyslot update staged, waiting for user confirmation");
+            Ok(self.pending.current())
+        }
+        .instrument(span)
+        .await
+    }
+}
@@ -0,0 +1,214 @@
+use std::sync::Arc;
+use tracing::{info, info_span, Instrument};
+
+use uc_core::{
+    ports::{
+        security::key_scope::{KeyScopePort, ScopeError},
+        EncryptionPort, EncryptionSessionPort, KeyMaterialPort,
+    },
+    security::model::{EncryptionError, KeyEpoch, KeySlot, Passphrase, RetainedMasterKey},
+};
+
+use super::pending_updates::check_follows;
+use super::{replace_key_material, rewrap_older_keys, unwrap_older_keys, PendingKeySlotUpdates};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfirmKeySlotUpdateError {
+    #[error("no keyslot update is waiting for confirmation")]
+    NothingPending,
+
+    #[error("wrong passphrase")]
+    WrongPassphrase,
+
+    #[error("keyslot update was rejected: {0}")]
+    Rejected(String),
+
+    #[error("failed to apply keyslot update")]
+    EncryptionFailed(#[from] EncryptionError),
+
+    #[error("failed to resolve key scope")]
+    ScopeFailed(#[from] ScopeError),
+}
+
+/// Apply the staged keyslot update once the user enters the passphrase it was wrapped with.
+/// 用户输入密钥槽对应的口令后，应用暂存的密钥槽更新。
+///
+/// The passphrase derives the KEK from the update's own salt and KDF parameters.
+/// The unwrapped key must be the current `MasterKey` or, for a rotation to the next
+/// key epoch, its retired key must be. A wrong passphrase keeps the update staged.
+///
+/// 口令结合更新自带的盐与 KDF 参数派生 KEK；解出的密钥必须为当前 `MasterKey`（轮换时为退役密钥）。口令错误时更新保持暂存。
+pub struct ConfirmKeySlotUpdate {
+    encryption: Arc<dyn EncryptionPort>,
+    encryption_session: Arc<dyn EncryptionSessionPort>,
+    key_material: Arc<dyn KeyMaterialPort>,
+    key_scope: Arc<dyn KeyScopePort>,
+    pending: Arc<PendingKeySlotUpdates>,
+}
+
+impl ConfirmKeySlotUpdate {
+    pub fn new(
+        encryption: Arc<dyn EncryptionPort>,
+        encryption_session: Arc<dyn EncryptionSessionPort>,
+        key_material: Arc<dyn KeyMaterialPort>,
+        key_scope: Arc<dyn KeyScopePort>,
+        pending: Arc<PendingKeySlotUpdates>,
+    ) -> Self {
+        Self {
+            encryption,
+            encryption_session,
+            key_material,
+            key_scope,
+            pending,
+        }
+    }
+
+    /// Apply the staged update and return the resulting key epoch.
+    pub async fn execute(
+        &self,
+        passphrase: Passphrase,
+    ) -> Result<KeyEpoch, ConfirmKeySlotUpdateError> {
+        let span = info_span!("usecase.confirm_keyslot_update.execute");
+        async move {
+            let (peer_id, update) = self
+                .pending
+                .take()
+                .ok_or(ConfirmKeySlotUpdateError::NothingPending)?;
+            match self.apply(&update, &passphrase).await {
+                Err(ConfirmKeySlotUpdateError::WrongPassphrase) => {
+                    self.pending.stage(&peer_id, update);
+                    Err(ConfirmKeySlotUpdateError::WrongPassphrase)
+                }
+                other => other,
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    async fn apply(
+        &self,
+        update: &KeySlot,
+        passphrase: &Passphrase,
+    ) -> Result<KeyEpoch, ConfirmKeySlotUpdateError> {
+        let scope = self.key_scope.current_scope().await?;
+        let old_keyslot = self.key_material.load_keyslot(&scope).await?;
+        check_follows(&old_keyslot, update)
+            .map_err(|e| ConfirmKeySlotUpdateError::Rejected(e.to_string()))?;
+
+        let wrapped = update
+            .wrapped_master_key
+            .as_ref()
+            .ok_or(EncryptionError::CorruptedKeySlot)?;
+        let kek = self
+            .encryption
+            .derive_kek(passphrase, &update.salt, &update.kdf)
+            .await?;
+        let unwrapped = self
+            .encryption
+            .unwrap_master_key(&kek, &wrapped.blob)
+            .await
+            .map_err(|err| match err {
+                EncryptionError::WrongPassphrase => ConfirmKeySlotUpdateError::WrongPassphrase,
+                other => ConfirmKeySlotUpdateError::EncryptionFailed(other),
+            })?;
+        let (local_epoch, current_key) = self.encryption_session.get_current_key().await?;
+
+        let rotated = update.epoch != local_epoch;
+        let new_keyslot = if !rotated {
+            // Passphrase change: same MasterKey, keep local re-encryption progress.
+            if unwrapped != current_key {
+                return Err(ConfirmKeySlotUpdateError::Rejected(
+                    "keyslot update wraps a different master key".to_string(),
+                ));
+            }
+            let (retired_master_key, retained_master_keys) =
+                if old_keyslot.older_master_keys().is_empty() {
+                    (None, Vec::new())
+                } else {
+                    let old_kek = self.key_material.load_kek(&scope).await?;
+                    rewrap_older_keys(self.encryption.as_ref(), &old_keyslot, &old_kek, &kek)
+                        .await?
+                };
+            KeySlot {
+                scope: scope.clone(),
+                retired_master_key,
+                retained_master_keys,
+                ..update.clone()
+            }
+        } else {
+            // Key rotation: the retired key must be the one we hold now.
+            if old_keyslot.is_rotating() {
+                return Err(ConfirmKeySlotUpdateError::Rejected(
+                    "local history re-encryption is still running".to_string(),
+                ));
+            }
+            let retired = update.retired_master_key.as_ref().ok_or_else(|| {
+                ConfirmKeySlotUpdateError::Rejected(
+                    "key rotation update is missing the retired key".to_string(),
+                )
+            })?;
+            let retired = self
+                .encryption
+                .unwrap_master_key(&kek, &retired.blob)
+                .await?;
+            if retired != current_key {
+                return Err(ConfirmKeySlotUpdateError::Rejected(
+                    "key rotation does not follow the current master key".to_string(),
+                ));
+            }
+            let mut keyslot = KeySlot {
+                scope: scope.clone(),
+                ..update.clone()
+            };
+            // Keep retained keys for local data the sender never held.
+            let held: Vec<KeyEpoch> = keyslot
+                .older_master_keys()
+                .iter()
+                .map(|(epoch, _)| *epoch)
+                .collect();
+            let missing: Vec<RetainedMasterKey> = old_keyslot
+                .retained_master_keys
+                .iter()
+                .filter(|retained| !held.contains(&retained.epoch))
+                .cloned()
+                .collect();
+            if !missing.is_empty() {
+                let old_kek = self.key_material.load_kek(&scope).await?;
+                let local_only = KeySlot {
+                    retired_master_key: None,
+                    retained_master_keys: missing,
+                    ..old_keyslot.clone()
+                };
+                let (_, rewrapped) =
+                    rewrap_older_keys(self.encryption.as_ref(), &local_only, &old_kek, &kek)
+                        .await?;
+                keyslot.retained_master_keys.extend(rewrapped);
+            }
+            keyslot
+        };
+
+        replace_key_material(
+            self.key_material.as_ref(),
+            &scope,
+            &old_keyslot,
+            &new_keyslot,
+            &kek,
+        )
+        .await?;
+
+        if rotated {
+            let older = unwrap_older_keys(self.encryption.as_ref(), &new_keyslot, &kek).await?;
+            self.encryption_session
+                .set_epoch_keys(update.epoch, unwrapped, older)
+                .await?;
+            info!(
+                epoch = update.epoch,
+                "Applied master key rotation from peer"
+            );
+        } else {
+            info!("Applied keyslot update from peer");
+        }
+        Ok(update.epoch)
+    }
+}
@@ -0,0 +1,81 @@
+use tracing::error;
+
+use uc_core::{
+    ports::{EncryptionPort, KeyMaterialPort},
+    security::model::{
+        EncryptionAlgo, EncryptionError, Kek, KeyEpoch, KeyScope, KeySlot, MasterKey,
+        RetainedMasterKey, WrappedMasterKey,
+    },
+};
+
+/// Rewrap the retired and retained keys of `keyslot` from `old_kek` to `new_kek`.
+pub(crate) async fn rewrap_older_keys(
+    encryption: &dyn EncryptionPort,
+    keyslot: &KeySlot,
+    old_kek: &Kek,
+    new_kek: &Kek,
+) -> Result<(Option<WrappedMasterKey>, Vec<RetainedMasterKey>), EncryptionError> {
+    let retired_master_key = match &keyslot.retired_master_key {
+        Some(wrapped) => Some(rewrap_key(encryption, wrapped, old_kek, new_kek).await?),
+        None => None,
+    };
+    let mut retained_master_keys = Vec::with_capacity(keyslot.retained_master_keys.len());
+    for retained in &keyslot.retained_master_keys {
+        retained_master_keys.push(RetainedMasterKey {
+            epoch: retained.epoch,
+            wrapped: rewrap_key(encryption, &retained.wrapped, old_kek, new_kek).await?,
+        });
+    }
+    Ok((retired_master_key, retained_master_keys))
+}
+
+async fn rewrap_key(
+    encryption: &dyn EncryptionPort,
+    wrapped: &WrappedMasterKey,
+    old_kek: &Kek,
+    new_kek: &Kek,
+) -> Result<WrappedMasterKey, EncryptionError> {
+    let key = encryption.unwrap_master_key(old_kek, &wrapped.blob).await?;
+    Ok(WrappedMasterKey {
+        blob: encryption
+            .wrap_master_key(new_kek, &key, EncryptionAlgo::XChaCha20Poly1305)
+            .await?,
+    })
+}
+
+/// Unwrap the keys of every older epoch held by `keyslot`, for the session keyring.
+pub(crate) async fn unwrap_older_keys(
+    encryption: &dyn EncryptionPort,
+    keyslot: &KeySlot,
+    kek: &Kek,
+) -> Result<Vec<(KeyEpoch, MasterKey)>, EncryptionError> {
+    let mut keys = Vec::new();
+    for (epoch, wrapped) in keyslot.older_master_keys() {
+        keys.push((
+            epoch,
+            encryption.unwrap_master_key(kek, &wrapped.blob).await?,
+        ));
+    }
+    Ok(keys)
+}
+
+/// Store the new keyslot, then the new KEK; restore `old_keyslot` if the KEK write fails.
+pub(crate) async fn replace_key_material(
+    key_material: &dyn KeyMaterialPort,
+    scope: &KeyScope,
+    old_keyslot: &KeySlot,
+    new_keyslot: &KeySlot,
+    new_kek: &Kek,
+) -> Result<(), EncryptionError> {
+    key_material.store_keyslot(new_keyslot).await?;
+
+    if let Err(err) = key_material.store_kek(scope, new_kek).await {
+        error!(error = %err, "store_kek failed, restoring previous keyslot");
+        if let Err(restore_err) = key_material.store_keyslot(old_keyslot).await {
+            error!(error = %restore_err, "failed to restore previous keyslot");
+        }
+        return Err(err);
+    }
+
+    Ok(())
+}
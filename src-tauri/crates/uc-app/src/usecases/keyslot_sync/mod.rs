@@ -0,0 +1,23 @@
+//! Keyslot propagation between trusted devices
+//! 受信任设备间的密钥槽同步
+//!
+//! A passphrase change or key rotation on one device is pushed to its trusted
+//! peers, staged there, and applied once the user confirms the passphrase.
+//!
+//! 一台设备修改口令或轮换密钥后推送给受信任设备，对端暂存，用户输入口令确认后应用。
+pub mod apply_keyslot_update;
+pub mod confirm_keyslot_update;
+mod key_material;
+pub mod pending_updates;
+mod publisher;
+pub mod resend_keyslot;
+#[cfg(test)]
+pub(crate) mod test_support;
+
+pub use apply_keyslot_update::ApplyKeySlotUpdate;
+pub use confirm_keyslot_update::{ConfirmKeySlotUpdate, ConfirmKeySlotUpdateError};
+pub use pending_updates::{PendingKeySlotUpdate, PendingKeySlotUpdates};
+pub use resend_keyslot::ResendKeySlot;
+
+pub(crate) use key_material::{replace_key_material, rewrap_older_keys, unwrap_older_keys};
+pub(crate) use publisher::KeySlotPublisher;
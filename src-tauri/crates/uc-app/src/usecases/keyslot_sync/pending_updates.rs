@@ -0,0 +1,89 @@
+use anyhow::anyhow;
+use serde::Serialize;
+use std::sync::Mutex;
+
+use uc_core::security::model::{KeyEpoch, KeySlot};
+
+/// Keyslot update received from a peer, waiting for the user to confirm it.
+/// 从受信任设备收到、等待用户确认的密钥槽更新。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PendingKeySlotUpdate {
+    pub peer_id: String,
+    pub epoch: KeyEpoch,
+    pub revision: u64,
+}
+
+/// Holds the newest staged keyslot update until the user confirms or dismisses it.
+///
+/// Shared between [`ApplyKeySlotUpdate`](super::ApplyKeySlotUpdate) and
+/// [`ConfirmKeySlotUpdate`](super::ConfirmKeySlotUpdate).
+#[derive(Default)]
+pub struct PendingKeySlotUpdates {
+    pending: Mutex<Option<(String, KeySlot)>>,
+}
+
+impl PendingKeySlotUpdates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The staged update, if any.
+    pub fn current(&self) -> Option<PendingKeySlotUpdate> {
+        self.lock()
+            .as_ref()
+            .map(|(peer_id, keyslot)| PendingKeySlotUpdate {
+                peer_id: peer_id.clone(),
+                epoch: keyslot.epoch,
+                revision: keyslot.revision,
+            })
+    }
+
+    /// Drop the staged update without applying it.
+    pub fn dismiss(&self) {
+        self.lock().take();
+    }
+
+    /// Stage `keyslot` unless an equal or newer update is already waiting.
+    pub(super) fn stage(&self, peer_id: &str, keyslot: KeySlot) -> bool {
+        let mut pending = self.lock();
+        if pending
+            .as_ref()
+            .is_some_and(|(_, staged)| !keyslot.is_newer_than(staged))
+        {
+            return false;
+        }
+        *pending = Some((peer_id.to_string(), keyslot));
+        true
+    }
+
+    pub(super) fn take(&self) -> Option<(String, KeySlot)> {
+        self.lock().take()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<(String, KeySlot)>> {
+        self.pending
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Accept `update` only if it is newer than `local` and at most one key epoch ahead.
+pub(super) fn check_follows(local: &KeySlot, update: &KeySlot) -> anyhow::Result<()> {
+    if !update.is_newer_than(local) {
+        return Err(anyhow!(
+            "keyslot update (epoch {}, revision {}) is not newer than the local keyslot (epoch {}, revision {})",
+            update.epoch,
+            update.revision,
+            local.epoch,
+            local.revision
+        ));
+    }
+    if update.epoch != local.epoch && local.epoch.checked_add(1) != Some(update.epoch) {
+        return Err(anyhow!(
+            "keyslot update for key epoch {} does not follow local epoch {}",
+            update.epoch,
+            local.epoch
+        ));
+    }
+    Ok(())
+}
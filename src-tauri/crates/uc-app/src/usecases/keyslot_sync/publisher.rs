@@ -0,0 +1,151 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+use uc_core::{
+    network::{KeySlotUpdateMessage, PairingState, ProtocolMessage},
+    ports::{DeviceIdentityPort, EncryptionPort, NetworkPort, PairedDeviceRepositoryPort},
+    security::{
+        aad,
+        model::{EncryptionAlgo, KeyEpoch, KeySlot, MasterKey},
+    },
+    PeerId,
+};
+
+/// Keyslot material sealed inside a [`KeySlotUpdateMessage`].
+///
+/// Only the rewrapped keyslot travels; the KEK never leaves the device.
+/// Receivers unlock it with the passphrase the user enters on that device.
+#[derive(Serialize, Deserialize)]
+pub(super) struct KeySlotUpdatePayload {
+    pub(super) keyslot: KeySlot,
+}
+
+/// Pushes a rewrapped keyslot to connected trusted peers.
+///
+/// Shared by passphrase changes and key rotation.
+pub(crate) struct KeySlotPublisher {
+    encryption: Arc<dyn EncryptionPort>,
+    paired_device_repo: Arc<dyn PairedDeviceRepositoryPort>,
+    network: Arc<dyn NetworkPort>,
+    device_identity: Arc<dyn DeviceIdentityPort>,
+}
+
+impl KeySlotPublisher {
+    pub(crate) fn new(
+        encryption: Arc<dyn EncryptionPort>,
+        paired_device_repo: Arc<dyn PairedDeviceRepositoryPort>,
+        network: Arc<dyn NetworkPort>,
+        device_identity: Arc<dyn DeviceIdentityPort>,
+    ) -> Self {
+        Self {
+            encryption,
+            paired_device_repo,
+            network,
+            device_identity,
+        }
+    }
+
+    /// Seal the update with `seal_key` (tagged `seal_epoch`) and send it;
+    /// returns how many peers it was queued for.
+    pub(crate) async fn publish(
+        &self,
+        seal_epoch: KeyEpoch,
+        seal_key: &MasterKey,
+        keyslot: &KeySlot,
+    ) -> anyhow::Result<usize> {
+        let target_peers = self.resolve_target_peers().await?;
+        if target_peers.is_empty() {
+            debug!("No connected trusted peers, skipping keyslot propagation");
+            return Ok(0);
+        }
+        self.send(&target_peers, seal_epoch, seal_key, keyslot)
+            .await
+    }
+
+    /// Send the update to a single peer if it is trusted; returns whether it was sent.
+    pub(crate) async fn publish_to(
+        &self,
+        peer_id: &str,
+        seal_epoch: KeyEpoch,
+        seal_key: &MasterKey,
+        keyslot: &KeySlot,
+    ) -> anyhow::Result<bool> {
+        let trusted = self
+            .paired_device_repo
+            .get_by_peer_id(&PeerId::from(peer_id))
+            .await?
+            .is_some_and(|device| device.pairing_state == PairingState::Trusted);
+        if !trusted {
+            return Ok(false);
+        }
+        let sent = self
+            .send(&[peer_id.to_string()], seal_epoch, seal_key, keyslot)
+            .await?;
+        Ok(sent > 0)
+    }
+
+    async fn send(
+        &self,
+        target_peers: &[String],
+        seal_epoch: KeyEpoch,
+        seal_key: &MasterKey,
+        keyslot: &KeySlot,
+    ) -> anyhow::Result<usize> {
+        let message_id = uuid::Uuid::new_v4().to_string();
+        let plaintext = serde_json::to_vec(&KeySlotUpdatePayload {
+            keyslot: keyslot.clone(),
+        })?;
+        let mut encrypted = self
+            .encryption
+            .encrypt_blob(
+                seal_key,
+                &plaintext,
+                &aad::for_keyslot_update(&message_id),
+                EncryptionAlgo::XChaCha20Poly1305,
+            )
+            .await?;
+        encrypted.key_epoch = seal_epoch;
+        let payload = ProtocolMessage::KeySlotUpdate(KeySlotUpdateMessage {
+            id: message_id,
+            origin_device_id: self.device_identity.current_device_id().to_string(),
+            encrypted_payload: serde_json::to_vec(&encrypted)?,
+            timestamp: Utc::now(),
+        })
+        .to_bytes()?;
+
+        let mut sent = 0;
+        for peer_id in target_peers {
+            match self.network.send_clipboard(peer_id, payload.clone()).await {
+                Ok(()) => sent += 1,
+                Err(err) => {
+                    warn!(peer_id = %peer_id, error = %err, "Failed to send keyslot update");
+                }
+            }
+        }
+        info!(peer_count = sent, "Keyslot update sent to peers");
+        Ok(sent)
+    }
+
+    async fn resolve_target_peers(&self) -> anyhow::Result<Vec<String>> {
+        let connected: HashSet<String> = self
+            .network
+            .get_connected_peers()
+            .await?
+            .into_iter()
+            .map(|peer| peer.peer_id)
+            .collect();
+
+        Ok(self
+            .paired_device_repo
+            .list_all()
+            .await?
+            .into_iter()
+            .filter(|device| device.pairing_state == PairingState::Trusted)
+            .map(|device| device.peer_id.as_str().to_string())
+            .filter(|peer_id| connected.contains(peer_id))
+            .collect())
+    }
+}
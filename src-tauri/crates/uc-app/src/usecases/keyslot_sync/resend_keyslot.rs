@@ -0,0 +1,93 @@
+use std::sync::Arc;
+use tracing::{debug, info_span, Instrument};
+
+use uc_core::ports::{
+    security::key_scope::KeyScopePort, DeviceIdentityPort, EncryptionPort, EncryptionSessionPort,
+    KeyMaterialPort, NetworkPort, PairedDeviceRepositoryPort,
+};
+
+use super::KeySlotPublisher;
+
+/// Push the local keyslot to a trusted peer that just became reachable.
+/// 向刚可达的受信任设备推送本地密钥槽。
+///
+/// Peers that were offline during a passphrase change or key rotation get the
+/// update here. One copy is sealed with each key epoch this device still holds,
+/// so a peer that missed a rotation can open the copy sealed with its epoch;
+/// receivers ignore copies that are not newer than their own keyslot. A peer that
+/// is behind by more than the retained epochs has to re-pair.
+///
+/// 离线期间错过口令修改或密钥轮换的设备在此收到更新。每个仍持有的密钥纪元各封装一份，
+/// 落后于所有保留纪元的设备需要重新配对。
+pub struct ResendKeySlot {
+    encryption_session: Arc<dyn EncryptionSessionPort>,
+    key_material: Arc<dyn KeyMaterialPort>,
+    key_scope: Arc<dyn KeyScopePort>,
+    publisher: KeySlotPublisher,
+}
+
+impl ResendKeySlot {
+    pub fn new(
+        encryption: Arc<dyn EncryptionPort>,
+        encryption_session: Arc<dyn EncryptionSessionPort>,
+        key_material: Arc<dyn KeyMaterialPort>,
+        key_scope: Arc<dyn KeyScopePort>,
+        paired_device_repo: Arc<dyn PairedDeviceRepositoryPort>,
+        network: Arc<dyn NetworkPort>,
+        device_identity: Arc<dyn DeviceIdentityPort>,
+    ) -> Self {
+        Self {
+            publisher: KeySlotPublisher::new(
+                encryption,
+                paired_device_repo,
+                network,
+                device_identity,
+            ),
+            encryption_session,
+            key_material,
+            key_scope,
+        }
+    }
+
+    /// Returns how many copies were sent to `peer_id`.
+    pub async fn execute(&self, peer_id: &str) -> anyhow::Result<usize> {
+        let span = info_span!("usecase.resend_keyslot.execute", peer_id = %peer_id);
+        async move {
+            if !self.encryption_session.is_ready().await {
+                return Ok(0);
+            }
+            let scope = self.key_scope.current_scope().await?;
+            let keyslot = self.key_material.load_keyslot(&scope).await?;
+            if keyslot.epoch == 0 && keyslot.revision == 0 {
+                // Never rotated nor changed: every peer already has an equivalent keyslot.
+                return Ok(0);
+            }
+
+            let (epoch, current_key) = self.encryption_session.get_current_key().await?;
+            let mut seals = vec![(epoch, current_key)];
+            for (older_epoch, _) in keyslot.older_master_keys() {
+                let key = self
+                    .encryption_session
+                    .get_master_key_for_epoch(older_epoch)
+                    .await?;
+                seals.push((older_epoch, key));
+            }
+
+            let mut sent = 0;
+            for (seal_epoch, seal_key) in &seals {
+                if !self
+                    .publisher
+                    .publish_to(peer_id, *seal_epoch, seal_key, &keyslot)
+                    .await?
+                {
+                    break;
+                }
+                sent += 1;
+            }
+            debug!(copies = sent, "Keyslot resent to peer");
+            Ok(sent)
+        }
+        .instrument(span)
+        .await
+    }
+}
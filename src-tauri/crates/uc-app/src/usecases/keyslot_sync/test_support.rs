@@ -0,0 +1,171 @@
+//! Test doubles shared by the keyslot use cases and their callers.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::{Arc, Mutex};
+
+use uc_core::{
+    network::{PairedDevice, PairingState},
+    ports::{
+        security::{
+            encryption_state::EncryptionStatePort,
+            key_scope::{KeyScopePort, ScopeError},
+        },
+        DeviceIdentityPort, EncryptionPort, KeyMaterialPort,
+    },
+    security::{
+        model::{
+            EncryptionAlgo, EncryptionError, Kek, KeyScope, KeySlot, MasterKey, Passphrase,
+            WrappedMasterKey,
+        },
+        state::{EncryptionState, EncryptionStateError},
+    },
+    testing::InMemoryPairedDeviceRepository,
+    DeviceId, PeerId,
+};
+use uc_infra::security::{EncryptionRepository, InMemoryEncryptionSession};
+
+use super::{ApplyKeySlotUpdate, ConfirmKeySlotUpdate, PendingKeySlotUpdates};
+
+#[derive(Default)]
+pub(crate) struct InMemoryKeyMaterial {
+    pub(crate) keyslot: Mutex<Option<KeySlot>>,
+    pub(crate) kek: Mutex<Option<Kek>>,
+    pub(crate) fail_store_kek: bool,
+}
+
+#[async_trait]
+impl KeyMaterialPort for InMemoryKeyMaterial {
+    async fn load_keyslot(&self, _scope: &KeyScope) -> Result<KeySlot, EncryptionError> {
+        self.keyslot
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or(EncryptionError::KeyNotFound)
+    }
+
+    async fn store_keyslot(&self, keyslot: &KeySlot) -> Result<(), EncryptionError> {
+        *self.keyslot.lock().unwrap() = Some(keyslot.clone());
+        Ok(())
+    }
+
+    async fn delete_keyslot(&self, _scope: &KeyScope) -> Result<(), EncryptionError> {
+        *self.keyslot.lock().unwrap() = None;
+        Ok(())
+    }
+
+    async fn load_kek(&self, _scope: &KeyScope) -> Result<Kek, EncryptionError> {
+        self.kek
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or(EncryptionError::KeyNotFound)
+    }
+
+    async fn store_kek(&self, _scope: &KeyScope, kek: &Kek) -> Result<(), EncryptionError> {
+        if self.fail_store_kek {
+            return Err(EncryptionError::KeyringError("unavailable".to_string()));
+        }
+        *self.kek.lock().unwrap() = Some(kek.clone());
+        Ok(())
+    }
+
+    async fn delete_kek(&self, _scope: &KeyScope) -> Result<(), EncryptionError> {
+        *self.kek.lock().unwrap() = None;
+        Ok(())
+    }
+}
+
+pub(crate) struct TestKeyScope(pub(crate) &'static str);
+
+#[async_trait]
+impl KeyScopePort for TestKeyScope {
+    async fn current_scope(&self) -> Result<KeyScope, ScopeError> {
+        Ok(KeyScope {
+            profile_id: self.0.to_string(),
+        })
+    }
+}
+
+pub(crate) struct InitializedState;
+
+#[async_trait]
+impl EncryptionStatePort for InitializedState {
+    async fn load_state(&self) -> Result<EncryptionState, EncryptionStateError> {
+        Ok(EncryptionState::Initialized)
+    }
+
+    async fn persist_initialized(&self) -> Result<(), EncryptionStateError> {
+        Ok(())
+    }
+}
+
+pub(crate) struct TestDeviceIdentity;
+
+impl DeviceIdentityPort for TestDeviceIdentity {
+    fn current_device_id(&self) -> DeviceId {
+        DeviceId::new("device-local")
+    }
+}
+
+pub(crate) fn trusted(peer_id: &str) -> PairedDevice {
+    PairedDevice {
+        peer_id: PeerId::from(peer_id),
+        pairing_state: PairingState::Trusted,
+        identity_fingerprint: "fp".to_string(),
+        paired_at: Utc::now(),
+        last_seen_at: None,
+        device_name: peer_id.to_string(),
+        device_id: "device-peer".to_string(),
+    }
+}
+
+/// Seed key material with a keyslot wrapping `master_key` under `passphrase`.
+pub(crate) async fn seed_keyslot(
+    key_material: &InMemoryKeyMaterial,
+    profile_id: &str,
+    passphrase: &str,
+    master_key: &MasterKey,
+) {
+    let encryption = EncryptionRepository;
+    let draft = KeySlot::draft_v1(KeyScope {
+        profile_id: profile_id.to_string(),
+    })
+    .unwrap();
+    let kek = encryption
+        .derive_kek(&Passphrase(passphrase.to_string()), &draft.salt, &draft.kdf)
+        .await
+        .unwrap();
+    let blob = encryption
+        .wrap_master_key(&kek, master_key, EncryptionAlgo::XChaCha20Poly1305)
+        .await
+        .unwrap();
+    *key_material.keyslot.lock().unwrap() = Some(draft.finalize(WrappedMasterKey { blob }));
+    *key_material.kek.lock().unwrap() = Some(kek);
+}
+
+pub(crate) fn build_apply_and_confirm(
+    session: Arc<InMemoryEncryptionSession>,
+    key_material: Arc<InMemoryKeyMaterial>,
+    trusted_peer: &str,
+) -> (ApplyKeySlotUpdate, ConfirmKeySlotUpdate) {
+    let pending = Arc::new(PendingKeySlotUpdates::new());
+    let apply = ApplyKeySlotUpdate::new(
+        Arc::new(EncryptionRepository),
+        session.clone(),
+        key_material.clone(),
+        Arc::new(TestKeyScope("remote")),
+        Arc::new(InMemoryPairedDeviceRepository::with_devices(vec![trusted(
+            trusted_peer,
+        )])),
+        pending.clone(),
+    );
+    let confirm = ConfirmKeySlotUpdate::new(
+        Arc::new(EncryptionRepository),
+        session,
+        key_material,
+        Arc::new(TestKeyScope("remote")),
+        pending,
+    );
+    (apply, confirm)
+}
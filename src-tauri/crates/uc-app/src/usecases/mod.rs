@@ -14,18 +14,24 @@
 //         ↓
 // MaterializeClipboardSelectionUseCase → 粘贴 / 恢复 / 同步
 pub mod app_lifecycle;
+pub mod apply_retention_policy;
 pub mod auto_unlock_encryption_session;
 pub mod change_passphrase;
+pub mod clear_clipboard_history;
 pub mod clipboard;
+pub mod collect_blob_garbage;
 pub mod delete_clipboard_entry;
 pub mod get_settings;
 pub mod initialize_encryption;
 pub mod internal;
+pub mod keyslot_sync;
 pub mod list_clipboard_entries;
 pub mod pairing;
-pub mod space_access;
+pub mod rotate_master_key;
 pub mod settings;
 pub mod setup;
+pub mod space_access;
+pub mod spaces;
 pub mod start_clipboard_watcher;
 pub mod start_network;
 pub mod start_network_after_unlock;
@@ -35,20 +41,39 @@ pub use app_lifecycle::{
     AppLifecycleCoordinator, AppLifecycleCoordinatorDeps, DeviceAnnouncer, LifecycleEvent,
     LifecycleEventEmitter, LifecycleState, LifecycleStatusPort, SessionReadyEmitter,
 };
+pub use apply_retention_policy::{ApplyRetentionPolicy, RetentionReport};
 pub use auto_unlock_encryption_session::AutoUnlockEncryptionSession;
+pub use change_passphrase::{ChangePassphrase, ChangePassphraseError};
+pub use clear_clipboard_history::ClearClipboardHistory;
 pub use clipboard::list_entry_projections::{
-    EntryProjectionDto, ListClipboardEntryProjections, ListProjectionsError,
+    EntryProjectionDto, EntryProjectionFilter, ListClipboardEntryProjections, ListProjectionsError,
 };
+pub use collect_blob_garbage::{BlobGcReport, CollectBlobGarbage};
 pub use delete_clipboard_entry::DeleteClipboardEntry;
 pub use get_settings::GetSettings;
 pub use initialize_encryption::InitializeEncryption;
+pub use keyslot_sync::{
+    ApplyKeySlotUpdate, ConfirmKeySlotUpdate, ConfirmKeySlotUpdateError, PendingKeySlotUpdate,
+    PendingKeySlotUpdates, ResendKeySlot,
+};
 pub use list_clipboard_entries::ListClipboardEntries;
 pub use pairing::{
-    AnnounceDeviceName, GetLocalDeviceInfo, GetLocalPeerId, ListConnectedPeers,
-    ListDiscoveredPeers, ListPairedDevices, LocalDeviceInfo, PairingConfig, PairingOrchestrator,
-    ResolveConnectionPolicy, SetPairingState, UnpairDevice,
+    AddManualPeer, AnnounceDeviceName, ApplyPeerRevocation, CreatePairingInvitation,
+    GetDeviceSyncPolicy, GetLocalDeviceInfo, GetLocalNetworkInfo, GetLocalPeerId,
+    ListConnectedPeers, ListDiscoveredPeers, ListPairedDevices, ListPeerCapabilities,
+    ListPeerLiveness, LocalDeviceInfo, PairWithInvitation, PairingConfig, PairingOrchestrator,
+    RecordPeerAddress, RecordPeerCapabilities, RecordPeerHeartbeat, RedialKnownPeers,
+    ResolveConnectionPolicy, SetDeviceSyncPolicy, SetPairingState, UnpairDevice,
+    VerifyPeerIdentity,
+};
+pub use rotate_master_key::{
+    KeyRotationStatus, ReencryptHistory, ReencryptionProgress, RotateMasterKey,
+    RotateMasterKeyError,
 };
 pub use setup::{MarkSetupComplete, SetupError, SetupOrchestrator};
+pub use spaces::{
+    CreateSpace, ListSpaces, SpaceError, SpaceSummary, SwitchSpace, SwitchSpaceOutcome,
+};
 pub use start_clipboard_watcher::StartClipboardWatcher;
 pub use start_network::StartNetwork;
 pub use start_network_after_unlock::StartNetworkAfterUnlock;
@@ -0,0 +1,129 @@
+use anyhow::Result;
+use std::sync::Arc;
+use tracing::{info, info_span, warn, Instrument};
+use uc_core::network::normalize_peer_address;
+use uc_core::ports::{NetworkPort, SettingsPort};
+
+/// Use case for adding a peer by address when mDNS discovery is unavailable.
+///
+/// 手动添加对端地址：规范化并经传输层校验后写入 `network.bootstrap_peers` 并立即拨号。
+/// A failed dial is only logged; the address stays in the bootstrap list and
+/// is retried by [`super::RedialKnownPeers`].
+pub struct AddManualPeer {
+    network: Arc<dyn NetworkPort>,
+    settings: Arc<dyn SettingsPort>,
+}
+
+impl AddManualPeer {
+    pub fn new(network: Arc<dyn NetworkPort>, settings: Arc<dyn SettingsPort>) -> Self {
+        Self { network, settings }
+    }
+
+    /// Returns the normalized multiaddr that was stored.
+    pub async fn execute(&self, input: String) -> Result<String> {
+        let span = info_span!("usecase.add_manual_peer.execute");
+
+        async {
+            let address = normalize_peer_address(&input)?;
+            let address = self.network.parse_peer_address(&address)?;
+
+            let mut settings = self.settings.load().await?;
+            if !settings.network.bootstrap_peers.contains(&address) {
+                settings.network.bootstrap_peers.push(address.clone());
+                self.settings.save(&settings).await?;
+                info!(address = %address, "manual peer address added");
+            }
+
+            if let Err(err) = self.network.dial_address(address.clone()).await {
+                warn!(address = %address, error = %err, "initial dial of manual peer failed");
+            }
+
+            Ok(address)
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+    use uc_core::settings::model::Settings;
+    use uc_core::testing::TestNetwork;
+
+    #[derive(Default)]
+    struct TestSettings {
+        settings: Mutex<Settings>,
+    }
+
+    #[async_trait]
+    impl SettingsPort for TestSettings {
+        async fn load(&self) -> Result<Settings> {
+            Ok(self.settings.lock().unwrap().clone())
+        }
+
+        async fn save(&self, settings: &Settings) -> Result<()> {
+            *self.settings.lock().unwrap() = settings.clone();
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn add_stores_normalized_address_once_and_dials() {
+        let network = Arc::new(TestNetwork::new().failing("dial_address", "network not started"));
+        let settings = Arc::new(TestSettings::default());
+        let uc = AddManualPeer::new(network.clone(), settings.clone());
+
+        let first = uc.execute("10.0.0.5:4001".to_string()).await.unwrap();
+        let second = uc
+            .execute("/ip4/10.0.0.5/tcp/4001".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(first, "/ip4/10.0.0.5/tcp/4001");
+        assert_eq!(first, second);
+        assert_eq!(
+            settings.load().await.unwrap().network.bootstrap_peers,
+            vec![first.clone()]
+        );
+        assert_eq!(network.dialed_addresses().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn add_rejects_invalid_input() {
+        let network = Arc::new(TestNetwork::new().failing("dial_address", "network not started"));
+        let settings = Arc::new(TestSettings::default());
+        let uc = AddManualPeer::new(network.clone(), settings.clone());
+
+        assert!(uc.execute("not an address".to_string()).await.is_err());
+        assert!(network.dialed_addresses().is_empty());
+        assert!(settings
+            .load()
+            .await
+            .unwrap()
+            .network
+            .bootstrap_peers
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn add_rejects_address_the_transport_cannot_dial() {
+        let network = Arc::new(
+            TestNetwork::new().failing("parse_peer_address", "not a dialable peer address"),
+        );
+        let settings = Arc::new(TestSettings::default());
+        let uc = AddManualPeer::new(network.clone(), settings.clone());
+
+        assert!(uc.execute("/foo/bar".to_string()).await.is_err());
+        assert!(network.dialed_addresses().is_empty());
+        assert!(settings
+            .load()
+            .await
+            .unwrap()
+            .network
+            .bootstrap_peers
+            .is_empty());
+    }
+}
@@ -27,116 +27,25 @@ impl AnnounceDeviceName {
 #[cfg(test)]
 mod tests {
     use super::AnnounceDeviceName;
-    use async_trait::async_trait;
-    use std::sync::{Arc, Mutex};
-    use tokio::sync::mpsc;
-    use uc_core::network::PairingMessage;
-    use uc_core::network::{ClipboardMessage, ConnectedPeer, DiscoveredPeer, NetworkEvent};
-    use uc_core::ports::NetworkPort;
-
-    struct TestNetwork {
-        called: Arc<Mutex<Vec<String>>>,
-        result: anyhow::Result<()>,
-    }
-
-    #[async_trait]
-    impl NetworkPort for TestNetwork {
-        async fn send_clipboard(
-            &self,
-            _peer_id: &str,
-            _encrypted_data: Vec<u8>,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn broadcast_clipboard(&self, _encrypted_data: Vec<u8>) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn subscribe_clipboard(&self) -> anyhow::Result<mpsc::Receiver<ClipboardMessage>> {
-            let (_tx, rx) = mpsc::channel(1);
-            Ok(rx)
-        }
-
-        async fn get_discovered_peers(&self) -> anyhow::Result<Vec<DiscoveredPeer>> {
-            Ok(vec![])
-        }
-
-        async fn get_connected_peers(&self) -> anyhow::Result<Vec<ConnectedPeer>> {
-            Ok(vec![])
-        }
-
-        fn local_peer_id(&self) -> String {
-            "local-peer".to_string()
-        }
-
-        async fn announce_device_name(&self, device_name: String) -> anyhow::Result<()> {
-            let mut called = self.called.lock().expect("called lock");
-            called.push(device_name);
-            match &self.result {
-                Ok(()) => Ok(()),
-                Err(error) => Err(anyhow::anyhow!(error.to_string())),
-            }
-        }
-
-        async fn open_pairing_session(
-            &self,
-            _peer_id: String,
-            _session_id: String,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn send_pairing_on_session(
-            &self,
-            _session_id: String,
-            _message: PairingMessage,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn close_pairing_session(
-            &self,
-            _session_id: String,
-            _reason: Option<String>,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn unpair_device(&self, _peer_id: String) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn subscribe_events(&self) -> anyhow::Result<mpsc::Receiver<NetworkEvent>> {
-            let (_tx, rx) = mpsc::channel(1);
-            Ok(rx)
-        }
-    }
+    use std::sync::Arc;
+    use uc_core::testing::TestNetwork;
 
     #[tokio::test]
     async fn announce_device_name_invokes_network_port() {
-        let called = Arc::new(Mutex::new(Vec::new()));
-        let network = Arc::new(TestNetwork {
-            called: called.clone(),
-            result: Ok(()),
-        });
-        let uc = AnnounceDeviceName::new(network);
+        let network = Arc::new(TestNetwork::new());
+        let uc = AnnounceDeviceName::new(network.clone());
 
         uc.execute("Desk".to_string())
             .await
             .expect("announce device name");
 
-        let called = called.lock().expect("called lock");
-        assert_eq!(called.as_slice(), ["Desk".to_string()]);
+        assert_eq!(network.announced_names().as_slice(), ["Desk".to_string()]);
     }
 
     #[tokio::test]
     async fn announce_device_name_propagates_error() {
-        let called = Arc::new(Mutex::new(Vec::new()));
-        let network = Arc::new(TestNetwork {
-            called: called.clone(),
-            result: Err(anyhow::anyhow!("announce failed")),
-        });
+        let network =
+            Arc::new(TestNetwork::new().failing("announce_device_name", "announce failed"));
         let uc = AnnounceDeviceName::new(network);
 
         let result = uc.execute("Desk".to_string()).await;
@@ -0,0 +1,51 @@
+use anyhow::{anyhow, Result};
+use std::sync::Arc;
+use tracing::{info, info_span, Instrument};
+use uc_core::network::PairingInvitation;
+use uc_core::ports::NetworkPort;
+
+use super::PairingOrchestrator;
+
+/// Use case for generating an out-of-band pairing invitation.
+///
+/// 生成一次性配对邀请（二维码/URI），携带本机 PeerID、监听地址、身份指纹与随机密钥。
+/// The invitation expires after [`uc_core::network::PAIRING_INVITATION_TTL_SECS`]
+/// and replaces any previously issued one.
+pub struct CreatePairingInvitation {
+    network: Arc<dyn NetworkPort>,
+    orchestrator: Arc<PairingOrchestrator>,
+}
+
+impl CreatePairingInvitation {
+    pub fn new(network: Arc<dyn NetworkPort>, orchestrator: Arc<PairingOrchestrator>) -> Self {
+        Self {
+            network,
+            orchestrator,
+        }
+    }
+
+    pub async fn execute(&self) -> Result<PairingInvitation> {
+        let span = info_span!("usecase.create_pairing_invitation.execute");
+
+        async {
+            let addresses = self
+                .network
+                .get_local_network_info()
+                .await?
+                .listen_addresses;
+            if addresses.is_empty() {
+                return Err(anyhow!("network is not listening on any address yet"));
+            }
+
+            let invitation = self.orchestrator.create_invitation(addresses).await?;
+            info!(
+                addresses = invitation.addresses.len(),
+                expires_at = %invitation.expires_at,
+                "pairing invitation created"
+            );
+            Ok(invitation)
+        }
+        .instrument(span)
+        .await
+    }
+}
@@ -0,0 +1,97 @@
+use anyhow::Result;
+use std::sync::Arc;
+use uc_core::network::DeviceSyncPolicy;
+use uc_core::ports::PairedDeviceRepositoryPort;
+use uc_core::PeerId;
+
+/// Read the sync policy of a paired device.
+///
+/// 读取已配对设备的同步策略；未设置时返回默认策略。
+pub struct GetDeviceSyncPolicy {
+    repo: Arc<dyn PairedDeviceRepositoryPort>,
+}
+
+impl GetDeviceSyncPolicy {
+    pub fn new(repo: Arc<dyn PairedDeviceRepositoryPort>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(&self, peer_id: &PeerId) -> Result<DeviceSyncPolicy> {
+        self.repo
+            .get_sync_policy(peer_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to load device sync policy: {}", e))
+    }
+}
+
+/// Store the sync policy of a paired device.
+///
+/// 保存已配对设备的同步策略，发送扇出与接收路径在下一条消息时生效。
+pub struct SetDeviceSyncPolicy {
+    repo: Arc<dyn PairedDeviceRepositoryPort>,
+}
+
+impl SetDeviceSyncPolicy {
+    pub fn new(repo: Arc<dyn PairedDeviceRepositoryPort>) -> Self {
+        Self { repo }
+    }
+
+    /// Fails for devices that are not paired in the active space.
+    pub async fn execute(&self, peer_id: &PeerId, policy: DeviceSyncPolicy) -> Result<()> {
+        let device = self
+            .repo
+            .get_by_peer_id(peer_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to load paired device: {}", e))?;
+        if device.is_none() {
+            return Err(anyhow::anyhow!("Device {} is not paired", peer_id));
+        }
+
+        self.repo
+            .set_sync_policy(peer_id, &policy)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to save device sync policy: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uc_core::network::{PairedDevice, PairingState, SyncDirection};
+    use uc_core::testing::InMemoryPairedDeviceRepository;
+
+    #[tokio::test]
+    async fn policy_is_stored_for_paired_devices_only() {
+        let repo = Arc::new(InMemoryPairedDeviceRepository::with_devices(vec![
+            PairedDevice {
+                peer_id: PeerId::from("peer-1"),
+                pairing_state: PairingState::Trusted,
+                identity_fingerprint: "fp".to_string(),
+                paired_at: chrono::Utc::now(),
+                last_seen_at: None,
+                device_name: "Peer".to_string(),
+                device_id: "device-peer".to_string(),
+            },
+        ]));
+        let set = SetDeviceSyncPolicy::new(repo.clone());
+        let get = GetDeviceSyncPolicy::new(repo);
+        let policy = DeviceSyncPolicy {
+            direction: SyncDirection::ReceiveOnly,
+            ..DeviceSyncPolicy::default()
+        };
+
+        assert!(set
+            .execute(&PeerId::from("peer-unknown"), policy.clone())
+            .await
+            .is_err());
+        assert_eq!(
+            get.execute(&PeerId::from("peer-1")).await.unwrap(),
+            DeviceSyncPolicy::default()
+        );
+
+        set.execute(&PeerId::from("peer-1"), policy.clone())
+            .await
+            .unwrap();
+        assert_eq!(get.execute(&PeerId::from("peer-1")).await.unwrap(), policy);
+    }
+}
@@ -51,11 +51,8 @@ impl GetLocalDeviceInfo {
 mod tests {
     use super::*;
     use async_trait::async_trait;
-    use tokio::sync::mpsc;
-    use uc_core::network::{
-        ClipboardMessage, ConnectedPeer, DiscoveredPeer, NetworkEvent, PairingMessage,
-    };
     use uc_core::settings::model::Settings;
+    use uc_core::testing::TestNetwork;
 
     enum SettingsOutcome {
         Ok(Settings),
@@ -80,88 +77,13 @@ mod tests {
         }
     }
 
-    struct TestNetwork {
-        peer_id: String,
-    }
-
-    #[async_trait]
-    impl NetworkPort for TestNetwork {
-        async fn send_clipboard(
-            &self,
-            _peer_id: &str,
-            _encrypted_data: Vec<u8>,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn broadcast_clipboard(&self, _encrypted_data: Vec<u8>) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn subscribe_clipboard(&self) -> anyhow::Result<mpsc::Receiver<ClipboardMessage>> {
-            let (_tx, rx) = mpsc::channel(1);
-            Ok(rx)
-        }
-
-        async fn get_discovered_peers(&self) -> anyhow::Result<Vec<DiscoveredPeer>> {
-            Ok(Vec::new())
-        }
-
-        async fn get_connected_peers(&self) -> anyhow::Result<Vec<ConnectedPeer>> {
-            Ok(Vec::new())
-        }
-
-        fn local_peer_id(&self) -> String {
-            self.peer_id.clone()
-        }
-
-        async fn announce_device_name(&self, _device_name: String) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn open_pairing_session(
-            &self,
-            _peer_id: String,
-            _session_id: String,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn send_pairing_on_session(
-            &self,
-            _session_id: String,
-            _message: PairingMessage,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn close_pairing_session(
-            &self,
-            _session_id: String,
-            _reason: Option<String>,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn unpair_device(&self, _peer_id: String) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn subscribe_events(&self) -> anyhow::Result<mpsc::Receiver<NetworkEvent>> {
-            let (_tx, rx) = mpsc::channel(1);
-            Ok(rx)
-        }
-    }
-
     #[tokio::test]
     async fn uses_device_name_from_settings() {
         let mut settings = Settings::default();
         settings.general.device_name = Some("Desk".to_string());
 
         let usecase = GetLocalDeviceInfo::new(
-            Arc::new(TestNetwork {
-                peer_id: "peer-1".to_string(),
-            }),
+            Arc::new(TestNetwork::new().with_local_peer_id("peer-1")),
             Arc::new(TestSettings {
                 outcome: SettingsOutcome::Ok(settings),
             }),
@@ -178,9 +100,7 @@ mod tests {
         settings.general.device_name = Some("  Desk  ".to_string());
 
         let usecase = GetLocalDeviceInfo::new(
-            Arc::new(TestNetwork {
-                peer_id: "peer-2".to_string(),
-            }),
+            Arc::new(TestNetwork::new().with_local_peer_id("peer-2")),
             Arc::new(TestSettings {
                 outcome: SettingsOutcome::Ok(settings),
             }),
@@ -196,9 +116,7 @@ mod tests {
         settings.general.device_name = Some("   ".to_string());
 
         let usecase = GetLocalDeviceInfo::new(
-            Arc::new(TestNetwork {
-                peer_id: "peer-3".to_string(),
-            }),
+            Arc::new(TestNetwork::new().with_local_peer_id("peer-3")),
             Arc::new(TestSettings {
                 outcome: SettingsOutcome::Ok(settings),
             }),
@@ -211,9 +129,7 @@ mod tests {
     #[tokio::test]
     async fn uses_default_name_when_settings_fail_to_load() {
         let usecase = GetLocalDeviceInfo::new(
-            Arc::new(TestNetwork {
-                peer_id: "peer-4".to_string(),
-            }),
+            Arc::new(TestNetwork::new().with_local_peer_id("peer-4")),
             Arc::new(TestSettings {
                 outcome: SettingsOutcome::Err("load failed".to_string()),
             }),
@@ -0,0 +1,21 @@
+use anyhow::Result;
+use std::sync::Arc;
+
+use uc_core::network::LocalNetworkInfo;
+use uc_core::ports::NetworkPort;
+
+/// Local interfaces and listen addresses, shown so an address can be entered
+/// on another device when mDNS is blocked.
+pub struct GetLocalNetworkInfo {
+    network: Arc<dyn NetworkPort>,
+}
+
+impl GetLocalNetworkInfo {
+    pub fn new(network: Arc<dyn NetworkPort>) -> Self {
+        Self { network }
+    }
+
+    pub async fn execute(&self) -> Result<LocalNetworkInfo> {
+        self.network.get_local_network_info().await
+    }
+}
@@ -24,91 +24,8 @@ impl ListDiscoveredPeers {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use async_trait::async_trait;
     use chrono::Utc;
-    use tokio::sync::mpsc;
-    use uc_core::network::{ClipboardMessage, ConnectedPeer, NetworkEvent, PairingMessage};
-
-    enum DiscoveredOutcome {
-        Ok(Vec<DiscoveredPeer>),
-        Err(String),
-    }
-
-    struct TestNetwork {
-        outcome: DiscoveredOutcome,
-    }
-
-    #[async_trait]
-    impl NetworkPort for TestNetwork {
-        async fn send_clipboard(
-            &self,
-            _peer_id: &str,
-            _encrypted_data: Vec<u8>,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn broadcast_clipboard(&self, _encrypted_data: Vec<u8>) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn subscribe_clipboard(&self) -> anyhow::Result<mpsc::Receiver<ClipboardMessage>> {
-            let (_tx, rx) = mpsc::channel(1);
-            Ok(rx)
-        }
-
-        async fn get_discovered_peers(&self) -> anyhow::Result<Vec<DiscoveredPeer>> {
-            match &self.outcome {
-                DiscoveredOutcome::Ok(peers) => Ok(peers.clone()),
-                DiscoveredOutcome::Err(message) => Err(anyhow::anyhow!(message.clone())),
-            }
-        }
-
-        async fn get_connected_peers(&self) -> anyhow::Result<Vec<ConnectedPeer>> {
-            Ok(Vec::new())
-        }
-
-        fn local_peer_id(&self) -> String {
-            "peer-local".to_string()
-        }
-
-        async fn announce_device_name(&self, _device_name: String) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn open_pairing_session(
-            &self,
-            _peer_id: String,
-            _session_id: String,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn send_pairing_on_session(
-            &self,
-            _session_id: String,
-            _message: PairingMessage,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn close_pairing_session(
-            &self,
-            _session_id: String,
-            _reason: Option<String>,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn unpair_device(&self, _peer_id: String) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn subscribe_events(&self) -> anyhow::Result<mpsc::Receiver<NetworkEvent>> {
-            let (_tx, rx) = mpsc::channel(1);
-            Ok(rx)
-        }
-    }
+    use uc_core::testing::TestNetwork;
 
     #[tokio::test]
     async fn returns_discovered_peers_on_success() {
@@ -122,9 +39,8 @@ mod tests {
             is_paired: false,
         }];
 
-        let usecase = ListDiscoveredPeers::new(Arc::new(TestNetwork {
-            outcome: DiscoveredOutcome::Ok(peers.clone()),
-        }));
+        let usecase =
+            ListDiscoveredPeers::new(Arc::new(TestNetwork::new().with_discovered(peers.clone())));
 
         let result = usecase.execute().await.expect("list discovered peers");
         assert_eq!(result.len(), 1);
@@ -134,9 +50,9 @@ mod tests {
 
     #[tokio::test]
     async fn wraps_errors_with_context() {
-        let usecase = ListDiscoveredPeers::new(Arc::new(TestNetwork {
-            outcome: DiscoveredOutcome::Err("boom".to_string()),
-        }));
+        let usecase = ListDiscoveredPeers::new(Arc::new(
+            TestNetwork::new().failing("get_discovered_peers", "boom"),
+        ));
 
         let err = usecase.execute().await.expect_err("expected error");
         let message = err.to_string();
@@ -0,0 +1,23 @@
+use anyhow::Result;
+use std::sync::Arc;
+
+use uc_core::network::PeerCapabilities;
+use uc_core::ports::NetworkPort;
+
+/// Use case for reading the protocol version and features negotiated with each peer.
+pub struct ListPeerCapabilities {
+    network: Arc<dyn NetworkPort>,
+}
+
+impl ListPeerCapabilities {
+    pub fn new(network: Arc<dyn NetworkPort>) -> Self {
+        Self { network }
+    }
+
+    pub async fn execute(&self) -> Result<Vec<PeerCapabilities>> {
+        self.network
+            .get_peer_capabilities()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to list peer capabilities: {}", e))
+    }
+}
@@ -0,0 +1,23 @@
+use anyhow::Result;
+use std::sync::Arc;
+
+use uc_core::network::PeerLiveness;
+use uc_core::ports::NetworkPort;
+
+/// Use case for reading the latest heartbeat and RTT of each peer.
+pub struct ListPeerLiveness {
+    network: Arc<dyn NetworkPort>,
+}
+
+impl ListPeerLiveness {
+    pub fn new(network: Arc<dyn NetworkPort>) -> Self {
+        Self { network }
+    }
+
+    pub async fn execute(&self) -> Result<Vec<PeerLiveness>> {
+        self.network
+            .get_peer_liveness()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to list peer liveness: {}", e))
+    }
+}
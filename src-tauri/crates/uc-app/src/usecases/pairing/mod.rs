@@ -1,28 +1,52 @@
+pub mod add_manual_peer;
 pub mod announce_device_name;
+pub mod create_pairing_invitation;
+pub mod device_sync_policy;
 pub mod events;
 pub mod facade;
 pub mod get_local_device_info;
+pub mod get_local_network_info;
 pub mod get_local_peer_id;
 pub mod list_connected_peers;
 pub mod list_discovered_peers;
 pub mod list_paired_devices;
+pub mod list_peer_capabilities;
+pub mod list_peer_liveness;
 pub mod orchestrator;
+pub mod pair_with_invitation;
+pub mod record_peer_address;
+pub mod record_peer_capabilities;
+pub mod record_peer_heartbeat;
+pub mod redial_known_peers;
 pub mod resolve_connection_policy;
 pub mod set_pairing_state;
 pub(crate) mod staged_paired_device_store;
 #[cfg(test)]
 mod transport_error_test;
 pub mod unpair_device;
+pub mod verify_peer_identity;
 
+pub use add_manual_peer::AddManualPeer;
 pub use announce_device_name::AnnounceDeviceName;
+pub use create_pairing_invitation::CreatePairingInvitation;
+pub use device_sync_policy::{GetDeviceSyncPolicy, SetDeviceSyncPolicy};
 pub use events::{PairingDomainEvent, PairingEventPort};
 pub use facade::PairingFacade;
 pub use get_local_device_info::{GetLocalDeviceInfo, LocalDeviceInfo};
+pub use get_local_network_info::GetLocalNetworkInfo;
 pub use get_local_peer_id::GetLocalPeerId;
 pub use list_connected_peers::ListConnectedPeers;
 pub use list_discovered_peers::ListDiscoveredPeers;
 pub use list_paired_devices::ListPairedDevices;
+pub use list_peer_capabilities::ListPeerCapabilities;
+pub use list_peer_liveness::ListPeerLiveness;
 pub use orchestrator::{PairingConfig, PairingOrchestrator};
+pub use pair_with_invitation::PairWithInvitation;
+pub use record_peer_address::RecordPeerAddress;
+pub use record_peer_capabilities::RecordPeerCapabilities;
+pub use record_peer_heartbeat::RecordPeerHeartbeat;
+pub use redial_known_peers::RedialKnownPeers;
 pub use resolve_connection_policy::ResolveConnectionPolicy;
 pub use set_pairing_state::SetPairingState;
-pub use unpair_device::UnpairDevice;
+pub use unpair_device::{ApplyPeerRevocation, UnpairDevice};
+pub use verify_peer_identity::VerifyPeerIdentity;
@@ -30,7 +30,11 @@ use tokio::sync::{mpsc, Mutex, RwLock};
 use tracing::{info_span, Instrument};
 
 use uc_core::{
+    crypto::IdentityFingerprint,
     network::{
+        capabilities::{BUSINESS_PROTOCOL_VERSION, SUPPORTED_PROTOCOL_VERSIONS},
+        paired_device::{PairedDevice, PairingState as DeviceTrustState},
+        pairing_invitation::{InvitationSecret, PairingInvitation, PAIRING_INVITATION_TTL_SECS},
         pairing_state_machine::{
             FailureReason, PairingAction, PairingEvent, PairingPolicy, PairingRole, PairingState,
             PairingStateMachine, SessionId, TimeoutKind,
@@ -74,13 +78,25 @@ impl PairingConfig {
             .as_secs()
             .min(i64::MAX as u64) as i64;
         let session = pairing.session_timeout.as_secs().min(i64::MAX as u64) as i64;
+        // The version feeds the short code, so both sides must agree on a supported one.
+        let protocol_version =
+            if SUPPORTED_PROTOCOL_VERSIONS.contains(&pairing.protocol_version.as_str()) {
+                pairing.protocol_version.clone()
+            } else {
+                tracing::warn!(
+                    configured = %pairing.protocol_version,
+                    fallback = BUSINESS_PROTOCOL_VERSION,
+                    "Unsupported pairing protocol version, using default"
+                );
+                BUSINESS_PROTOCOL_VERSION.to_string()
+            };
 
         Self {
             step_timeout_secs: step.max(1),
             user_verification_timeout_secs: verify.max(1),
             session_timeout_secs: session.max(1),
             max_retries: pairing.max_retries.max(1),
-            protocol_version: pairing.protocol_version.clone(),
+            protocol_version,
         }
     }
 }
@@ -104,6 +120,8 @@ pub struct PairingOrchestrator {
     action_tx: mpsc::Sender<PairingAction>,
     /// 配对事件订阅者
     event_senders: Arc<Mutex<Vec<mpsc::Sender<PairingDomainEvent>>>>,
+    /// 当前有效的配对邀请 (Responder,带外配对)
+    invitation: Arc<Mutex<Option<PairingInvitation>>>,
 }
 
 /// 配对会话上下文
@@ -160,16 +178,63 @@ impl PairingOrchestrator {
             },
             action_tx,
             event_senders: Arc::new(Mutex::new(Vec::new())),
+            invitation: Arc::new(Mutex::new(None)),
         };
 
         (orchestrator, action_rx)
     }
 
+    /// 创建配对邀请 (Responder)
+    ///
+    /// The invitation replaces any previous one and is consumed by the first
+    /// request that proves its secret.
+    pub async fn create_invitation(&self, addresses: Vec<String>) -> Result<PairingInvitation> {
+        let fingerprint =
+            IdentityFingerprint::from_public_key(&self.local_identity.identity_pubkey)?;
+        let invitation = PairingInvitation {
+            peer_id: self.local_identity.peer_id.clone(),
+            addresses,
+            fingerprint,
+            secret: InvitationSecret::generate(),
+            expires_at: Utc::now() + Duration::seconds(PAIRING_INVITATION_TTL_SECS),
+        };
+        *self.invitation.lock().await = Some(invitation.clone());
+        Ok(invitation)
+    }
+
     /// 发起配对 (Initiator)
     pub async fn initiate_pairing(&self, peer_id: String) -> Result<SessionId> {
+        self.start_initiator_session(peer_id, None).await
+    }
+
+    /// 按扫描/粘贴的邀请发起配对 (Initiator)
+    ///
+    /// 对端身份由邀请中的指纹与一次性密钥认证,无需比对短码。
+    pub async fn initiate_pairing_with_invitation(
+        &self,
+        invitation: &PairingInvitation,
+    ) -> Result<SessionId> {
+        if invitation.is_expired(Utc::now()) {
+            return Err(anyhow::anyhow!("Pairing invitation has expired"));
+        }
+        if invitation.peer_id == self.local_identity.peer_id {
+            return Err(anyhow::anyhow!(
+                "Cannot pair with this device's own invitation"
+            ));
+        }
+        self.start_initiator_session(invitation.peer_id.clone(), Some(invitation))
+            .await
+    }
+
+    async fn start_initiator_session(
+        &self,
+        peer_id: String,
+        invitation: Option<&PairingInvitation>,
+    ) -> Result<SessionId> {
         let span = info_span!(
             "pairing.initiate",
-            peer_id = %peer_id
+            peer_id = %peer_id,
+            invited = invitation.is_some()
         );
         async {
             let policy = self.build_policy();
@@ -179,6 +244,9 @@ impl PairingOrchestrator {
                 self.local_identity.identity_pubkey.clone(),
                 policy,
             );
+            if let Some(invitation) = invitation {
+                state_machine = state_machine.with_invitation(invitation);
+            }
             let (state, actions) = state_machine.handle_event(
                 PairingEvent::StartPairing {
                     role: PairingRole::Initiator,
@@ -248,6 +316,12 @@ impl PairingOrchestrator {
             )
             .await;
 
+            let invitation_secret = if request.invitation_proof.is_some() {
+                self.active_invitation_secret().await
+            } else {
+                None
+            };
+
             let policy = self.build_policy();
             let mut state_machine = PairingStateMachine::new_with_local_identity_and_policy(
                 self.local_identity.device_name.clone(),
@@ -255,6 +329,9 @@ impl PairingOrchestrator {
                 self.local_identity.identity_pubkey.clone(),
                 policy,
             );
+            if let Some(secret) = invitation_secret {
+                state_machine = state_machine.with_invitation_secret(secret);
+            }
             let (_state, actions) = state_machine.handle_event(
                 PairingEvent::RecvRequest {
                     session_id: session_id.clone(),
@@ -263,6 +340,11 @@ impl PairingOrchestrator {
                 },
                 Utc::now(),
             );
+            if state_machine.invitation_verified() {
+                // 邀请仅可使用一次
+                self.invitation.lock().await.take();
+                tracing::info!(session_id = %session_id, "Pairing invitation redeemed");
+            }
 
             let context = PairingSessionContext {
                 state_machine,
@@ -867,6 +949,20 @@ impl PairingOrchestrator {
                         );
                         let peer_id = device.peer_id.to_string();
                         staged_paired_device_store::stage(&session_id, device.clone());
+                        if let Err(err) = Self::quarantine_replaced_identities(
+                            &device_repo,
+                            &action_tx,
+                            &session_id,
+                            &device,
+                        )
+                        .await
+                        {
+                            tracing::warn!(
+                                session_id = %session_id,
+                                error = %err,
+                                "Failed to check earlier pairings of device"
+                            );
+                        }
 
                         let persist_result = device_repo.upsert(device).await;
 
@@ -967,6 +1063,12 @@ impl PairingOrchestrator {
                             handle.abort();
                         }
                     }
+                    PairingAction::IdentityChanged { .. } => {
+                        action_tx
+                            .send(action)
+                            .await
+                            .context("Failed to queue identity changed action")?;
+                    }
                     PairingAction::LogTransition { .. } => {
                         // 日志已记录,无需额外操作
                     }
@@ -1009,6 +1111,18 @@ impl PairingOrchestrator {
             .and_then(|ctx| ctx.state_machine.role())
     }
 
+    /// Secret of the current invitation; an expired one is dropped.
+    async fn active_invitation_secret(&self) -> Option<InvitationSecret> {
+        let mut invitation = self.invitation.lock().await;
+        if invitation
+            .as_ref()
+            .is_some_and(|active| active.is_expired(Utc::now()))
+        {
+            *invitation = None;
+        }
+        invitation.as_ref().map(|active| active.secret.clone())
+    }
+
     fn build_policy(&self) -> PairingPolicy {
         PairingPolicy {
             step_timeout_secs: self.config.step_timeout_secs,
@@ -1115,6 +1229,54 @@ impl PairingOrchestrator {
         .await
     }
 
+    /// Quarantine earlier pairings of the same device id made under another
+    /// identity key, and ask the UI to tell the user the device's key changed.
+    ///
+    /// 同一设备 ID 以新的身份密钥重新配对时，隔离旧记录并提醒用户。
+    async fn quarantine_replaced_identities(
+        device_repo: &Arc<dyn PairedDeviceRepositoryPort + Send + Sync + 'static>,
+        action_tx: &mpsc::Sender<PairingAction>,
+        session_id: &str,
+        device: &PairedDevice,
+    ) -> Result<()> {
+        let devices = device_repo
+            .list_all()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to load paired devices: {}", e))?;
+        let replaced = devices.into_iter().filter(|previous| {
+            previous.device_id == device.device_id
+                && previous.peer_id != device.peer_id
+                && previous.identity_fingerprint != device.identity_fingerprint
+                && matches!(
+                    previous.pairing_state,
+                    DeviceTrustState::Trusted | DeviceTrustState::Pending
+                )
+        });
+        for previous in replaced {
+            tracing::warn!(
+                session_id = %session_id,
+                device_id = %device.device_id,
+                previous_peer_id = %previous.peer_id,
+                peer_id = %device.peer_id,
+                "Device re-paired with a new identity key, quarantining earlier pairing"
+            );
+            device_repo
+                .set_state(&previous.peer_id, DeviceTrustState::Quarantined)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to quarantine peer: {}", e))?;
+            action_tx
+                .send(PairingAction::IdentityChanged {
+                    session_id: session_id.to_string(),
+                    peer_id: previous.peer_id.to_string(),
+                    expected_fingerprint: previous.identity_fingerprint,
+                    presented_fingerprint: device.identity_fingerprint.clone(),
+                })
+                .await
+                .context("Failed to queue identity changed action")?;
+        }
+        Ok(())
+    }
+
     async fn emit_event(&self, event: PairingDomainEvent) {
         Self::emit_event_to_senders(self.event_senders.clone(), event).await;
     }
@@ -1177,13 +1339,12 @@ mod tests {
     use uc_core::network::paired_device::{PairedDevice, PairingState};
     use uc_core::network::pairing_state_machine::FailureReason;
     use uc_core::network::protocol::{PairingRequest, PairingResponse};
-    use uc_core::network::PairingMessage;
+    use uc_core::network::{DeviceSyncPolicy, PairingMessage, PeerCapabilities};
     use uc_core::security::model::{
         EncryptedBlob, EncryptionAlgo, EncryptionFormatVersion, KdfAlgorithm, KdfParams,
         KdfParamsV1, KeyScope, KeySlotFile, KeySlotVersion,
     };
-
-    struct MockDeviceRepository;
+    use uc_core::testing::InMemoryPairedDeviceRepository;
 
     #[derive(Default)]
     struct CountingDeviceRepository {
@@ -1199,7 +1360,7 @@ mod tests {
     }
 
     #[async_trait::async_trait]
-    impl PairedDeviceRepositoryPort for MockDeviceRepository {
+    impl PairedDeviceRepositoryPort for CountingDeviceRepository {
         async fn get_by_peer_id(
             &self,
             _peer_id: &uc_core::ids::PeerId,
@@ -1219,6 +1380,7 @@ mod tests {
             &self,
             _device: PairedDevice,
         ) -> Result<(), uc_core::ports::errors::PairedDeviceRepositoryError> {
+            self.upsert_calls.fetch_add(1, Ordering::SeqCst);
             Ok(())
         }
 
@@ -1244,52 +1406,72 @@ mod tests {
         ) -> Result<(), uc_core::ports::errors::PairedDeviceRepositoryError> {
             Ok(())
         }
-    }
 
-    #[async_trait::async_trait]
-    impl PairedDeviceRepositoryPort for CountingDeviceRepository {
-        async fn get_by_peer_id(
+        async fn record_address(
             &self,
             _peer_id: &uc_core::ids::PeerId,
-        ) -> Result<Option<PairedDevice>, uc_core::ports::errors::PairedDeviceRepositoryError>
-        {
-            Ok(None)
+            _address: &str,
+            _confirmed_at: chrono::DateTime<chrono::Utc>,
+        ) -> Result<(), uc_core::ports::errors::PairedDeviceRepositoryError> {
+            Ok(())
         }
 
-        async fn list_all(
+        async fn list_addresses(
             &self,
-        ) -> Result<Vec<PairedDevice>, uc_core::ports::errors::PairedDeviceRepositoryError>
-        {
-            Ok(vec![])
+            _peer_id: &uc_core::ids::PeerId,
+        ) -> Result<Vec<String>, uc_core::ports::errors::PairedDeviceRepositoryError> {
+            Ok(Vec::new())
         }
 
-        async fn upsert(
+        async fn mark_revocation_pending(
             &self,
-            _device: PairedDevice,
+            _peer_id: &uc_core::ids::PeerId,
+            _revoked_at: chrono::DateTime<chrono::Utc>,
         ) -> Result<(), uc_core::ports::errors::PairedDeviceRepositoryError> {
-            self.upsert_calls.fetch_add(1, Ordering::SeqCst);
             Ok(())
         }
 
-        async fn set_state(
+        async fn is_revocation_pending(
+            &self,
+            _peer_id: &uc_core::ids::PeerId,
+        ) -> Result<bool, uc_core::ports::errors::PairedDeviceRepositoryError> {
+            Ok(false)
+        }
+
+        async fn clear_revocation_pending(
             &self,
             _peer_id: &uc_core::ids::PeerId,
-            _state: PairingState,
         ) -> Result<(), uc_core::ports::errors::PairedDeviceRepositoryError> {
             Ok(())
         }
 
-        async fn update_last_seen(
+        async fn get_sync_policy(
             &self,
             _peer_id: &uc_core::ids::PeerId,
-            _last_seen_at: chrono::DateTime<chrono::Utc>,
+        ) -> Result<DeviceSyncPolicy, uc_core::ports::errors::PairedDeviceRepositoryError> {
+            Ok(DeviceSyncPolicy::default())
+        }
+
+        async fn set_sync_policy(
+            &self,
+            _peer_id: &uc_core::ids::PeerId,
+            _policy: &DeviceSyncPolicy,
         ) -> Result<(), uc_core::ports::errors::PairedDeviceRepositoryError> {
             Ok(())
         }
 
-        async fn delete(
+        async fn get_capabilities(
             &self,
             _peer_id: &uc_core::ids::PeerId,
+        ) -> Result<Option<PeerCapabilities>, uc_core::ports::errors::PairedDeviceRepositoryError>
+        {
+            Ok(None)
+        }
+
+        async fn set_capabilities(
+            &self,
+            _peer_id: &uc_core::ids::PeerId,
+            _capabilities: &PeerCapabilities,
         ) -> Result<(), uc_core::ports::errors::PairedDeviceRepositoryError> {
             Ok(())
         }
@@ -1345,6 +1527,75 @@ mod tests {
         ) -> Result<(), uc_core::ports::errors::PairedDeviceRepositoryError> {
             Ok(())
         }
+
+        async fn record_address(
+            &self,
+            _peer_id: &uc_core::ids::PeerId,
+            _address: &str,
+            _confirmed_at: chrono::DateTime<chrono::Utc>,
+        ) -> Result<(), uc_core::ports::errors::PairedDeviceRepositoryError> {
+            Ok(())
+        }
+
+        async fn list_addresses(
+            &self,
+            _peer_id: &uc_core::ids::PeerId,
+        ) -> Result<Vec<String>, uc_core::ports::errors::PairedDeviceRepositoryError> {
+            Ok(Vec::new())
+        }
+
+        async fn mark_revocation_pending(
+            &self,
+            _peer_id: &uc_core::ids::PeerId,
+            _revoked_at: chrono::DateTime<chrono::Utc>,
+        ) -> Result<(), uc_core::ports::errors::PairedDeviceRepositoryError> {
+            Ok(())
+        }
+
+        async fn is_revocation_pending(
+            &self,
+            _peer_id: &uc_core::ids::PeerId,
+        ) -> Result<bool, uc_core::ports::errors::PairedDeviceRepositoryError> {
+            Ok(false)
+        }
+
+        async fn clear_revocation_pending(
+            &self,
+            _peer_id: &uc_core::ids::PeerId,
+        ) -> Result<(), uc_core::ports::errors::PairedDeviceRepositoryError> {
+            Ok(())
+        }
+
+        async fn get_sync_policy(
+            &self,
+            _peer_id: &uc_core::ids::PeerId,
+        ) -> Result<DeviceSyncPolicy, uc_core::ports::errors::PairedDeviceRepositoryError> {
+            Ok(DeviceSyncPolicy::default())
+        }
+
+        async fn set_sync_policy(
+            &self,
+            _peer_id: &uc_core::ids::PeerId,
+            _policy: &DeviceSyncPolicy,
+        ) -> Result<(), uc_core::ports::errors::PairedDeviceRepositoryError> {
+            Ok(())
+        }
+
+        async fn get_capabilities(
+            &self,
+            _peer_id: &uc_core::ids::PeerId,
+        ) -> Result<Option<PeerCapabilities>, uc_core::ports::errors::PairedDeviceRepositoryError>
+        {
+            Ok(None)
+        }
+
+        async fn set_capabilities(
+            &self,
+            _peer_id: &uc_core::ids::PeerId,
+            _capabilities: &PeerCapabilities,
+        ) -> Result<(), uc_core::ports::errors::PairedDeviceRepositoryError> {
+            Ok(())
+        }
     }
 
     #[tokio::test]
@@ -1373,6 +1624,7 @@ mod tests {
                         paired_at: Utc::now(),
                         last_seen_at: None,
                         device_name: "Remote Device".to_string(),
+                        device_id: "device-peer".to_string(),
                     },
                 },
             )
@@ -1385,10 +1637,69 @@ mod tests {
         assert!(staged.is_some());
     }
 
+    #[tokio::test]
+    async fn repairing_with_new_key_quarantines_old_pairing_and_alerts() {
+        staged_paired_device_store::clear();
+        let paired = |peer_id: &str, fingerprint: &str| PairedDevice {
+            peer_id: uc_core::ids::PeerId::from(peer_id),
+            pairing_state: PairingState::Trusted,
+            identity_fingerprint: fingerprint.to_string(),
+            paired_at: Utc::now(),
+            last_seen_at: None,
+            device_name: "Remote Device".to_string(),
+            device_id: "device-peer".to_string(),
+        };
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::with_devices(vec![paired(
+            "peer-old", "fp-old",
+        )]));
+        let (orchestrator, mut action_rx) = PairingOrchestrator::new(
+            PairingConfig::default(),
+            device_repo.clone(),
+            "LocalDevice".to_string(),
+            "device-123".to_string(),
+            "peer-local".to_string(),
+            vec![0u8; 32],
+        );
+
+        orchestrator
+            .execute_action(
+                "session-repair",
+                "peer-new",
+                PairingAction::PersistPairedDevice {
+                    session_id: "session-repair".to_string(),
+                    device: PairedDevice {
+                        pairing_state: PairingState::Pending,
+                        ..paired("peer-new", "fp-new")
+                    },
+                },
+            )
+            .await
+            .expect("persist re-paired device");
+
+        assert_eq!(
+            device_repo.device("peer-old").unwrap().pairing_state,
+            PairingState::Quarantined
+        );
+        assert!(device_repo.device("peer-new").is_some());
+        match action_rx.recv().await.expect("identity changed action") {
+            PairingAction::IdentityChanged {
+                peer_id,
+                expected_fingerprint,
+                presented_fingerprint,
+                ..
+            } => {
+                assert_eq!(peer_id, "peer-old");
+                assert_eq!(expected_fingerprint, "fp-old");
+                assert_eq!(presented_fingerprint, "fp-new");
+            }
+            other => panic!("expected IdentityChanged, got {other:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn test_orchestrator_creation() {
         let config = PairingConfig::default();
-        let device_repo = Arc::new(MockDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (_orchestrator, _action_rx) = PairingOrchestrator::new(
             config,
             device_repo,
@@ -1402,7 +1713,7 @@ mod tests {
     #[tokio::test]
     async fn test_initiate_pairing() {
         let config = PairingConfig::default();
-        let device_repo = Arc::new(MockDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             config,
             device_repo,
@@ -1422,7 +1733,7 @@ mod tests {
     #[tokio::test]
     async fn initiate_pairing_emits_request_action() {
         let config = PairingConfig::default();
-        let device_repo = Arc::new(MockDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, mut action_rx) = PairingOrchestrator::new(
             config,
             device_repo,
@@ -1460,7 +1771,7 @@ mod tests {
             max_retries: 1,
             protocol_version: "1.0.0".to_string(),
         };
-        let device_repo = Arc::new(MockDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             config,
             device_repo,
@@ -1493,7 +1804,7 @@ mod tests {
             max_retries: 1,
             protocol_version: "1.0.0".to_string(),
         };
-        let device_repo = Arc::new(MockDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             config,
             device_repo,
@@ -1534,7 +1845,7 @@ mod tests {
         settings.pairing.user_verification_timeout = std::time::Duration::from_secs(90);
         settings.pairing.session_timeout = std::time::Duration::from_secs(400);
         settings.pairing.max_retries = 5;
-        settings.pairing.protocol_version = "2.0.0".to_string();
+        settings.pairing.protocol_version = "1.0.0".to_string();
 
         let config = PairingConfig::from_settings(&settings);
 
@@ -1542,13 +1853,23 @@ mod tests {
         assert_eq!(config.user_verification_timeout_secs, 90);
         assert_eq!(config.session_timeout_secs, 400);
         assert_eq!(config.max_retries, 5);
-        assert_eq!(config.protocol_version, "2.0.0");
+        assert_eq!(config.protocol_version, "1.0.0");
+    }
+
+    #[test]
+    fn test_pairing_config_rejects_unsupported_protocol_version() {
+        let mut settings = Settings::default();
+        settings.pairing.protocol_version = "9.9.9".to_string();
+
+        let config = PairingConfig::from_settings(&settings);
+
+        assert_eq!(config.protocol_version, "1.0.0");
     }
 
     #[tokio::test]
     async fn test_handle_response_emits_confirm_action() {
         let config = PairingConfig::default();
-        let device_repo = Arc::new(MockDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, mut action_rx) = PairingOrchestrator::new(
             config,
             device_repo,
@@ -1565,6 +1886,7 @@ mod tests {
             peer_id: "peer-local".to_string(),
             identity_pubkey: vec![1; 32],
             nonce: vec![2; 16],
+            invitation_proof: None,
         };
 
         orchestrator
@@ -1646,6 +1968,7 @@ mod tests {
             peer_id: "peer-local".to_string(),
             identity_pubkey: vec![1; 32],
             nonce: vec![2; 16],
+            invitation_proof: None,
         };
 
         orchestrator
@@ -1724,7 +2047,7 @@ mod tests {
     #[tokio::test]
     async fn test_show_verification_is_forwarded_to_action_channel() {
         let config = PairingConfig::default();
-        let device_repo = Arc::new(MockDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, mut action_rx) = PairingOrchestrator::new(
             config,
             device_repo,
@@ -1760,7 +2083,7 @@ mod tests {
     #[tokio::test]
     async fn test_start_timer_records_handle() {
         let config = PairingConfig::default();
-        let device_repo = Arc::new(MockDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             config,
             device_repo,
@@ -1796,7 +2119,7 @@ mod tests {
     #[tokio::test]
     async fn test_cancel_timer_removes_handle() {
         let config = PairingConfig::default();
-        let device_repo = Arc::new(MockDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             config,
             device_repo,
@@ -1850,7 +2173,7 @@ mod tests {
     #[tokio::test]
     async fn test_handle_incoming_request_validates_target_peer_id() {
         let config = PairingConfig::default();
-        let device_repo = Arc::new(MockDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             config,
             device_repo,
@@ -1867,6 +2190,7 @@ mod tests {
             peer_id: "wrong-peer-id".to_string(),
             identity_pubkey: vec![2; 32],
             nonce: vec![3; 16],
+            invitation_proof: None,
         };
 
         let result = orchestrator
@@ -1880,6 +2204,71 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn invited_request_skips_approval_and_consumes_invitation() {
+        let (responder, mut responder_rx) = PairingOrchestrator::new(
+            PairingConfig::default(),
+            Arc::new(InMemoryPairedDeviceRepository::new()),
+            "Responder".to_string(),
+            "device-responder".to_string(),
+            "peer-responder".to_string(),
+            vec![2; 32],
+        );
+        let (initiator, mut initiator_rx) = PairingOrchestrator::new(
+            PairingConfig::default(),
+            Arc::new(InMemoryPairedDeviceRepository::new()),
+            "Initiator".to_string(),
+            "device-initiator".to_string(),
+            "peer-initiator".to_string(),
+            vec![1; 32],
+        );
+
+        let invitation = responder
+            .create_invitation(vec!["/ip4/127.0.0.1/tcp/4001".to_string()])
+            .await
+            .expect("create invitation");
+        initiator
+            .initiate_pairing_with_invitation(&invitation)
+            .await
+            .expect("initiate with invitation");
+
+        let request = loop {
+            let action = timeout(Duration::from_secs(1), initiator_rx.recv())
+                .await
+                .expect("action timeout")
+                .expect("action missing");
+            if let PairingAction::Send {
+                message: PairingMessage::Request(request),
+                ..
+            } = action
+            {
+                break request;
+            }
+        };
+        assert!(request.invitation_proof.is_some());
+
+        responder
+            .handle_incoming_request("peer-initiator".to_string(), request)
+            .await
+            .expect("handle invited request");
+
+        let challenge = loop {
+            let action = timeout(Duration::from_secs(1), responder_rx.recv())
+                .await
+                .expect("action timeout")
+                .expect("action missing");
+            if let PairingAction::Send {
+                message: PairingMessage::Challenge(challenge),
+                ..
+            } = action
+            {
+                break challenge;
+            }
+        };
+        assert!(challenge.invitation_proof.is_some());
+        assert!(responder.invitation.lock().await.is_none());
+    }
+
     fn sample_keyslot_file() -> KeySlotFile {
         KeySlotFile {
             version: KeySlotVersion::V1,
@@ -1895,12 +2284,17 @@ mod tests {
                 },
             },
             salt: vec![1, 2, 3],
+            epoch: 0,
+            revision: 0,
+            retired_master_key: None,
+            retained_master_keys: Vec::new(),
             wrapped_master_key: EncryptedBlob {
                 version: EncryptionFormatVersion::V1,
                 aead: EncryptionAlgo::XChaCha20Poly1305,
                 nonce: vec![9, 8, 7],
                 ciphertext: vec![6, 5, 4],
                 aad_fingerprint: None,
+                key_epoch: 0,
             },
             created_at: None,
             updated_at: None,
@@ -1910,7 +2304,7 @@ mod tests {
     #[tokio::test]
     async fn pairing_orchestrator_emits_keyslot_received_event() {
         let config = PairingConfig::default();
-        let device_repo = Arc::new(MockDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             config,
             device_repo,
@@ -1948,7 +2342,7 @@ mod tests {
     #[tokio::test]
     async fn pairing_orchestrator_emits_pairing_result_events() {
         let config = PairingConfig::default();
-        let device_repo = Arc::new(MockDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             config,
             device_repo,
@@ -2020,7 +2414,7 @@ mod tests {
     #[tokio::test]
     async fn pairing_orchestrator_emits_verification_required_event() {
         let config = PairingConfig::default();
-        let device_repo = Arc::new(MockDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, mut action_rx) = PairingOrchestrator::new(
             config,
             device_repo,
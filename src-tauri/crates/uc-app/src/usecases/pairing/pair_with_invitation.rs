@@ -0,0 +1,84 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, info_span, warn, Instrument};
+use uc_core::network::{PairingInvitation, SessionId};
+use uc_core::ports::NetworkPort;
+
+use super::PairingOrchestrator;
+
+/// How long to wait for the dialed peer to become connected.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const CONNECT_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Use case for pairing with a device from its scanned or pasted invitation URI.
+///
+/// 解析邀请 → 按邀请中的地址拨号 → 等待连接建立 → 以邀请密钥发起配对。
+/// 对端身份由邀请指纹认证，双方都无需比对短码。
+pub struct PairWithInvitation {
+    network: Arc<dyn NetworkPort>,
+    orchestrator: Arc<PairingOrchestrator>,
+}
+
+impl PairWithInvitation {
+    pub fn new(network: Arc<dyn NetworkPort>, orchestrator: Arc<PairingOrchestrator>) -> Self {
+        Self {
+            network,
+            orchestrator,
+        }
+    }
+
+    pub async fn execute(&self, uri: &str) -> Result<SessionId> {
+        let span = info_span!("usecase.pair_with_invitation.execute");
+
+        async {
+            let invitation = PairingInvitation::parse_uri(uri, Utc::now())?;
+            if invitation.peer_id == self.network.local_peer_id() {
+                return Err(anyhow!("cannot pair with this device's own invitation"));
+            }
+
+            if !invitation.addresses.is_empty() {
+                if let Err(err) = self
+                    .network
+                    .dial_peer(invitation.peer_id.clone(), invitation.addresses.clone())
+                    .await
+                {
+                    warn!(
+                        peer_id = %invitation.peer_id,
+                        error = %err,
+                        "dialing invitation addresses failed"
+                    );
+                }
+            }
+            self.wait_for_connection(&invitation.peer_id).await?;
+
+            let session_id = self
+                .orchestrator
+                .initiate_pairing_with_invitation(&invitation)
+                .await?;
+            info!(
+                peer_id = %invitation.peer_id,
+                session_id = %session_id,
+                "invited pairing started"
+            );
+            Ok(session_id)
+        }
+        .instrument(span)
+        .await
+    }
+
+    async fn wait_for_connection(&self, peer_id: &str) -> Result<()> {
+        let deadline = tokio::time::Instant::now() + CONNECT_TIMEOUT;
+        loop {
+            let connected = self.network.get_connected_peers().await?;
+            if connected.iter().any(|peer| peer.peer_id == peer_id) {
+                return Ok(());
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(anyhow!("could not connect to peer {}", peer_id));
+            }
+            tokio::time::sleep(CONNECT_POLL_INTERVAL).await;
+        }
+    }
+}
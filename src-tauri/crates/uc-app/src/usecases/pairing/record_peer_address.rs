@@ -0,0 +1,38 @@
+use anyhow::Result;
+use chrono::Utc;
+use std::sync::Arc;
+use uc_core::network::PairingState;
+use uc_core::ports::PairedDeviceRepositoryPort;
+use uc_core::PeerId;
+
+/// Use case for remembering an address a paired device was reached on.
+///
+/// 仅记录 `Trusted` 设备的地址，未配对对端的地址不落盘。
+pub struct RecordPeerAddress {
+    repo: Arc<dyn PairedDeviceRepositoryPort>,
+}
+
+impl RecordPeerAddress {
+    pub fn new(repo: Arc<dyn PairedDeviceRepositoryPort>) -> Self {
+        Self { repo }
+    }
+
+    /// Returns whether the address was recorded.
+    pub async fn execute(&self, peer_id: String, address: String) -> Result<bool> {
+        let peer = PeerId::from(peer_id.as_str());
+        let device = self
+            .repo
+            .get_by_peer_id(&peer)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to load paired device: {}", e))?;
+        if !matches!(device, Some(device) if device.pairing_state == PairingState::Trusted) {
+            return Ok(false);
+        }
+
+        self.repo
+            .record_address(&peer, &address, Utc::now())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to record peer address: {}", e))?;
+        Ok(true)
+    }
+}
@@ -0,0 +1,114 @@
+use anyhow::Result;
+use std::sync::Arc;
+use uc_core::network::{PeerCapabilities, ProtocolFeature};
+use uc_core::ports::PairedDeviceRepositoryPort;
+use uc_core::PeerId;
+
+/// Use case for persisting the capabilities negotiated with a paired device.
+///
+/// 保存握手协商出的设备能力，使下次连接时在握手完成前即可按能力同步；
+/// 未配对的对端不记录。
+pub struct RecordPeerCapabilities {
+    repo: Arc<dyn PairedDeviceRepositoryPort>,
+}
+
+impl RecordPeerCapabilities {
+    pub fn new(repo: Arc<dyn PairedDeviceRepositoryPort>) -> Self {
+        Self { repo }
+    }
+
+    /// Returns whether the device supports clipboard catch-up now but did
+    /// not before, so the caller can request the catch-up that was skipped
+    /// when the connection came up.
+    pub async fn execute(&self, capabilities: &PeerCapabilities) -> Result<bool> {
+        let peer = PeerId::from(capabilities.peer_id.as_str());
+        let paired = self
+            .repo
+            .get_by_peer_id(&peer)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to load paired device: {}", e))?;
+        if paired.is_none() {
+            return Ok(false);
+        }
+
+        let had_catch_up = self
+            .repo
+            .get_capabilities(&peer)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to load peer capabilities: {}", e))?
+            .is_some_and(|previous| previous.supports(ProtocolFeature::CatchUp));
+        self.repo
+            .set_capabilities(&peer, capabilities)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to store peer capabilities: {}", e))?;
+        Ok(!had_catch_up && capabilities.supports(ProtocolFeature::CatchUp))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uc_core::network::{PairedDevice, PairingState};
+    use uc_core::testing::InMemoryPairedDeviceRepository;
+
+    fn capabilities(peer_id: &str, features: Vec<ProtocolFeature>) -> PeerCapabilities {
+        PeerCapabilities {
+            peer_id: peer_id.to_string(),
+            app_version: "0.2.0".to_string(),
+            protocol_version: "1.0.0".to_string(),
+            features,
+            negotiated_at: Utc::now(),
+        }
+    }
+
+    fn repo_with_device() -> Arc<InMemoryPairedDeviceRepository> {
+        Arc::new(InMemoryPairedDeviceRepository::with_devices(vec![
+            PairedDevice {
+                peer_id: PeerId::from("peer-1"),
+                pairing_state: PairingState::Trusted,
+                identity_fingerprint: "fp".to_string(),
+                paired_at: Utc::now(),
+                last_seen_at: None,
+                device_name: "Desk".to_string(),
+                device_id: "device-peer".to_string(),
+            },
+        ]))
+    }
+
+    #[tokio::test]
+    async fn reports_catch_up_only_when_it_first_becomes_available() {
+        let repo = repo_with_device();
+        let uc = RecordPeerCapabilities::new(repo.clone());
+        let peer = PeerId::from("peer-1");
+
+        assert!(!uc
+            .execute(&capabilities("peer-1", vec![ProtocolFeature::LazyFetch]))
+            .await
+            .unwrap());
+        let upgraded = capabilities(
+            "peer-1",
+            vec![ProtocolFeature::LazyFetch, ProtocolFeature::CatchUp],
+        );
+        assert!(uc.execute(&upgraded).await.unwrap());
+        assert!(!uc.execute(&upgraded).await.unwrap());
+        assert_eq!(repo.get_capabilities(&peer).await.unwrap(), Some(upgraded));
+    }
+
+    #[tokio::test]
+    async fn capabilities_of_unpaired_peer_are_ignored() {
+        let repo = Arc::new(InMemoryPairedDeviceRepository::new());
+        let uc = RecordPeerCapabilities::new(repo.clone());
+
+        assert!(!uc
+            .execute(&capabilities("stranger", vec![ProtocolFeature::CatchUp]))
+            .await
+            .unwrap());
+        assert_eq!(
+            repo.get_capabilities(&PeerId::from("stranger"))
+                .await
+                .unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+use uc_core::ports::PairedDeviceRepositoryPort;
+use uc_core::PeerId;
+
+/// Minimum gap between two `last_seen_at` writes for the same device.
+const LAST_SEEN_WRITE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Use case for persisting `last_seen_at` from peer heartbeats.
+///
+/// 心跳每 15 秒一次，写库按 [`LAST_SEEN_WRITE_INTERVAL`] 节流；
+/// 精确的最近心跳时间由网络层在内存中维护。
+pub struct RecordPeerHeartbeat {
+    repo: Arc<dyn PairedDeviceRepositoryPort>,
+}
+
+impl RecordPeerHeartbeat {
+    pub fn new(repo: Arc<dyn PairedDeviceRepositoryPort>) -> Self {
+        Self { repo }
+    }
+
+    /// Returns whether `last_seen_at` was written.
+    pub async fn execute(&self, peer_id: &str, seen_at: DateTime<Utc>) -> Result<bool> {
+        let peer = PeerId::from(peer_id);
+        let device = self
+            .repo
+            .get_by_peer_id(&peer)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to load paired device: {}", e))?;
+        let Some(device) = device else {
+            return Ok(false);
+        };
+        let fresh = device.last_seen_at.is_some_and(|last_seen| {
+            (seen_at - last_seen)
+                .to_std()
+                .map_or(true, |age| age < LAST_SEEN_WRITE_INTERVAL)
+        });
+        if fresh {
+            return Ok(false);
+        }
+
+        self.repo
+            .update_last_seen(&peer, seen_at)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to update last seen: {}", e))?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uc_core::network::{PairedDevice, PairingState};
+    use uc_core::testing::InMemoryPairedDeviceRepository;
+
+    fn repo_with_device() -> Arc<InMemoryPairedDeviceRepository> {
+        Arc::new(InMemoryPairedDeviceRepository::with_devices(vec![
+            PairedDevice {
+                peer_id: PeerId::from("peer-1"),
+                pairing_state: PairingState::Trusted,
+                identity_fingerprint: "fp".to_string(),
+                paired_at: Utc::now(),
+                last_seen_at: None,
+                device_name: "Desk".to_string(),
+                device_id: "device-peer".to_string(),
+            },
+        ]))
+    }
+
+    #[tokio::test]
+    async fn heartbeats_are_written_at_most_once_per_interval() {
+        let repo = repo_with_device();
+        let uc = RecordPeerHeartbeat::new(repo.clone());
+        let start = Utc::now();
+
+        assert!(uc.execute("peer-1", start).await.unwrap());
+        assert!(!uc
+            .execute("peer-1", start + chrono::Duration::seconds(15))
+            .await
+            .unwrap());
+        assert!(uc
+            .execute("peer-1", start + chrono::Duration::seconds(75))
+            .await
+            .unwrap());
+        assert_eq!(
+            repo.device("peer-1").unwrap().last_seen_at,
+            Some(start + chrono::Duration::seconds(75))
+        );
+    }
+
+    #[tokio::test]
+    async fn heartbeat_from_unknown_peer_is_ignored() {
+        let repo = Arc::new(InMemoryPairedDeviceRepository::new());
+        let uc = RecordPeerHeartbeat::new(repo);
+
+        assert!(!uc.execute("stranger", Utc::now()).await.unwrap());
+    }
+}
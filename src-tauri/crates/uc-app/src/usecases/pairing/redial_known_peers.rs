@@ -0,0 +1,159 @@
+use anyhow::Result;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{debug, info_span, warn, Instrument};
+use uc_core::network::PairingState;
+use uc_core::ports::{NetworkPort, PairedDeviceRepositoryPort, SettingsPort};
+
+/// Use case for reconnecting without mDNS.
+///
+/// 周期性重连：拨号 `network.bootstrap_peers` 中的地址，并对未连接的
+/// `Trusted` 设备按其已记录地址重新拨号。Dials are fire-and-forget; the
+/// network adapter skips peers that are already connected.
+pub struct RedialKnownPeers {
+    network: Arc<dyn NetworkPort>,
+    repo: Arc<dyn PairedDeviceRepositoryPort>,
+    settings: Arc<dyn SettingsPort>,
+}
+
+impl RedialKnownPeers {
+    pub fn new(
+        network: Arc<dyn NetworkPort>,
+        repo: Arc<dyn PairedDeviceRepositoryPort>,
+        settings: Arc<dyn SettingsPort>,
+    ) -> Self {
+        Self {
+            network,
+            repo,
+            settings,
+        }
+    }
+
+    /// Returns the number of dial requests issued.
+    pub async fn execute(&self) -> Result<usize> {
+        let span = info_span!("usecase.redial_known_peers.execute");
+
+        async {
+            let mut dialed = 0;
+
+            let settings = self.settings.load().await?;
+            for address in settings.network.bootstrap_peers {
+                match self.network.dial_address(address.clone()).await {
+                    Ok(()) => dialed += 1,
+                    Err(err) => debug!(address = %address, error = %err, "bootstrap dial failed"),
+                }
+            }
+
+            let connected: HashSet<String> = self
+                .network
+                .get_connected_peers()
+                .await?
+                .into_iter()
+                .map(|peer| peer.peer_id)
+                .collect();
+            let devices = self
+                .repo
+                .list_all()
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to list paired devices: {}", e))?;
+
+            for device in devices {
+                if device.pairing_state != PairingState::Trusted
+                    || connected.contains(device.peer_id.as_str())
+                {
+                    continue;
+                }
+                let addresses = match self.repo.list_addresses(&device.peer_id).await {
+                    Ok(addresses) if !addresses.is_empty() => addresses,
+                    Ok(_) => continue,
+                    Err(err) => {
+                        warn!(peer_id = %device.peer_id, error = %err, "failed to load peer addresses");
+                        continue;
+                    }
+                };
+                match self
+                    .network
+                    .dial_peer(device.peer_id.as_str().to_string(), addresses)
+                    .await
+                {
+                    Ok(()) => dialed += 1,
+                    Err(err) => debug!(peer_id = %device.peer_id, error = %err, "redial failed"),
+                }
+            }
+
+            Ok(dialed)
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use uc_core::network::PairedDevice;
+    use uc_core::settings::model::Settings;
+    use uc_core::testing::{InMemoryPairedDeviceRepository, TestNetwork};
+    use uc_core::PeerId;
+
+    struct TestSettings {
+        settings: Settings,
+    }
+
+    #[async_trait]
+    impl SettingsPort for TestSettings {
+        async fn load(&self) -> Result<Settings> {
+            Ok(self.settings.clone())
+        }
+
+        async fn save(&self, _settings: &Settings) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn device(peer_id: &str, state: PairingState) -> PairedDevice {
+        PairedDevice {
+            peer_id: PeerId::from(peer_id),
+            pairing_state: state,
+            identity_fingerprint: "fp".to_string(),
+            paired_at: Utc::now(),
+            last_seen_at: None,
+            device_name: peer_id.to_string(),
+            device_id: "device-peer".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn redials_bootstrap_list_and_disconnected_trusted_peers() {
+        let network = Arc::new(TestNetwork::new().with_connected(["connected"]));
+        let address = |peer: &str| vec![format!("/ip4/10.0.0.1/tcp/4001/{peer}")];
+        let repo = Arc::new(InMemoryPairedDeviceRepository::with_devices(vec![
+            device("offline", PairingState::Trusted),
+            device("connected", PairingState::Trusted),
+            device("pending", PairingState::Pending),
+            device("no-address", PairingState::Trusted),
+        ]));
+        for peer in ["offline", "connected", "pending"] {
+            repo.record_address(&PeerId::from(peer), &address(peer)[0], Utc::now())
+                .await
+                .unwrap();
+        }
+        let mut settings = Settings::default();
+        settings.network.bootstrap_peers = vec!["/dns/desk.local/tcp/4001".to_string()];
+        let uc = RedialKnownPeers::new(network.clone(), repo, Arc::new(TestSettings { settings }));
+
+        let dialed = uc.execute().await.unwrap();
+
+        assert_eq!(dialed, 2);
+        assert_eq!(
+            network.dialed_addresses().as_slice(),
+            ["/dns/desk.local/tcp/4001".to_string()]
+        );
+        assert_eq!(
+            network.dialed_peers().as_slice(),
+            [("offline".to_string(), address("offline"))]
+        );
+    }
+}
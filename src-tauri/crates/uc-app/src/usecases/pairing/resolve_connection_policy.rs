@@ -1,10 +1,12 @@
 use std::sync::Arc;
-use uc_core::network::{ConnectionPolicy, PairingState, ResolvedConnectionPolicy};
+use uc_core::network::{ConnectionPolicy, IdentityCheck, PairingState, ResolvedConnectionPolicy};
 use uc_core::ports::{
     ConnectionPolicyResolverError, ConnectionPolicyResolverPort, PairedDeviceRepositoryPort,
 };
 use uc_core::PeerId;
 
+use super::VerifyPeerIdentity;
+
 pub struct ResolveConnectionPolicy {
     repo: Arc<dyn PairedDeviceRepositoryPort>,
 }
@@ -47,13 +49,27 @@ impl ConnectionPolicyResolverPort for ResolveConnectionPolicy {
             .await
             .map_err(|err| ConnectionPolicyResolverError::Repository(err.to_string()))
     }
+
+    async fn verify_peer_identity(
+        &self,
+        peer_id: &PeerId,
+        device_id: &str,
+        identity_pubkey: &[u8],
+    ) -> Result<IdentityCheck, ConnectionPolicyResolverError> {
+        VerifyPeerIdentity::new(self.repo.clone())
+            .execute(peer_id, device_id, identity_pubkey)
+            .await
+            .map_err(|err| ConnectionPolicyResolverError::Repository(err.to_string()))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::sync::Arc;
-    use uc_core::network::{PairedDevice, PairingState, ProtocolKind};
+    use uc_core::network::{
+        DeviceSyncPolicy, PairedDevice, PairingState, PeerCapabilities, ProtocolKind,
+    };
     use uc_core::ports::{PairedDeviceRepositoryError, PairedDeviceRepositoryPort};
 
     struct MockRepo {
@@ -96,6 +112,7 @@ mod tests {
                 paired_at: chrono::Utc::now(),
                 last_seen_at: None,
                 device_name: "Mock Device".to_string(),
+                device_id: "device-peer".to_string(),
             }))
         }
 
@@ -126,6 +143,74 @@ mod tests {
         async fn delete(&self, _peer_id: &PeerId) -> Result<(), PairedDeviceRepositoryError> {
             Ok(())
         }
+
+        async fn record_address(
+            &self,
+            _peer_id: &PeerId,
+            _address: &str,
+            _confirmed_at: chrono::DateTime<chrono::Utc>,
+        ) -> Result<(), PairedDeviceRepositoryError> {
+            Ok(())
+        }
+
+        async fn list_addresses(
+            &self,
+            _peer_id: &PeerId,
+        ) -> Result<Vec<String>, PairedDeviceRepositoryError> {
+            Ok(Vec::new())
+        }
+
+        async fn mark_revocation_pending(
+            &self,
+            _peer_id: &PeerId,
+            _revoked_at: chrono::DateTime<chrono::Utc>,
+        ) -> Result<(), PairedDeviceRepositoryError> {
+            Ok(())
+        }
+
+        async fn is_revocation_pending(
+            &self,
+            _peer_id: &PeerId,
+        ) -> Result<bool, PairedDeviceRepositoryError> {
+            Ok(false)
+        }
+
+        async fn clear_revocation_pending(
+            &self,
+            _peer_id: &PeerId,
+        ) -> Result<(), PairedDeviceRepositoryError> {
+            Ok(())
+        }
+
+        async fn get_sync_policy(
+            &self,
+            _peer_id: &PeerId,
+        ) -> Result<DeviceSyncPolicy, PairedDeviceRepositoryError> {
+            Ok(DeviceSyncPolicy::default())
+        }
+
+        async fn set_sync_policy(
+            &self,
+            _peer_id: &PeerId,
+            _policy: &DeviceSyncPolicy,
+        ) -> Result<(), PairedDeviceRepositoryError> {
+            Ok(())
+        }
+
+        async fn get_capabilities(
+            &self,
+            _peer_id: &PeerId,
+        ) -> Result<Option<PeerCapabilities>, PairedDeviceRepositoryError> {
+            Ok(None)
+        }
+
+        async fn set_capabilities(
+            &self,
+            _peer_id: &PeerId,
+            _capabilities: &PeerCapabilities,
+        ) -> Result<(), PairedDeviceRepositoryError> {
+            Ok(())
+        }
     }
 
     #[tokio::test]
@@ -1,63 +1,17 @@
 #[cfg(test)]
 mod tests {
     use crate::usecases::pairing::orchestrator::{PairingConfig, PairingOrchestrator};
-    use chrono::Utc;
     use std::sync::Arc;
     use std::time::Duration;
     use tokio::time::timeout;
-    use uc_core::network::paired_device::{PairedDevice, PairingState};
     use uc_core::network::pairing_state_machine::PairingAction;
     use uc_core::network::protocol::PairingChallenge;
-    use uc_core::ports::errors::PairedDeviceRepositoryError;
-    use uc_core::ports::PairedDeviceRepositoryPort;
-
-    struct MockDeviceRepository;
-
-    #[async_trait::async_trait]
-    impl PairedDeviceRepositoryPort for MockDeviceRepository {
-        async fn get_by_peer_id(
-            &self,
-            _peer_id: &uc_core::ids::PeerId,
-        ) -> Result<Option<PairedDevice>, PairedDeviceRepositoryError> {
-            Ok(None)
-        }
-
-        async fn list_all(&self) -> Result<Vec<PairedDevice>, PairedDeviceRepositoryError> {
-            Ok(vec![])
-        }
-
-        async fn upsert(&self, _device: PairedDevice) -> Result<(), PairedDeviceRepositoryError> {
-            Ok(())
-        }
-
-        async fn set_state(
-            &self,
-            _peer_id: &uc_core::ids::PeerId,
-            _state: PairingState,
-        ) -> Result<(), PairedDeviceRepositoryError> {
-            Ok(())
-        }
-
-        async fn update_last_seen(
-            &self,
-            _peer_id: &uc_core::ids::PeerId,
-            _last_seen_at: chrono::DateTime<Utc>,
-        ) -> Result<(), PairedDeviceRepositoryError> {
-            Ok(())
-        }
-
-        async fn delete(
-            &self,
-            _peer_id: &uc_core::ids::PeerId,
-        ) -> Result<(), PairedDeviceRepositoryError> {
-            Ok(())
-        }
-    }
+    use uc_core::testing::InMemoryPairedDeviceRepository;
 
     #[tokio::test]
     async fn transport_error_aborts_waiting_confirm() {
         let config = PairingConfig::default();
-        let device_repo = Arc::new(MockDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, mut action_rx) = PairingOrchestrator::new(
             config,
             device_repo,
@@ -87,6 +41,7 @@ mod tests {
             device_id: "device-999".to_string(),
             identity_pubkey: vec![1; 32],
             nonce: vec![2; 16],
+            invitation_proof: None,
         };
         orchestrator
             .handle_challenge(&session_id, "peer-remote", challenge)
@@ -1,9 +1,19 @@
 use anyhow::Result;
+use chrono::Utc;
 use std::sync::Arc;
+use tracing::{info, info_span, warn, Instrument};
 
+use uc_core::network::{PairingMessage, PairingRevokeAck, PairingState};
 use uc_core::ports::{NetworkPort, PairedDeviceRepositoryPort};
 use uc_core::PeerId;
 
+/// Use case for unpairing a device and telling the other side.
+///
+/// 取消配对：删除本地配对记录，并通知对端将本机标记为 `Revoked`。
+/// The notice is kept as pending until the peer acknowledges it, so a peer
+/// that is offline now, or whose acknowledgement got lost, receives it again
+/// on reconnect via [`UnpairDevice::deliver_pending`]. Pairing with the peer
+/// again drops the pending notice.
 pub struct UnpairDevice {
     network: Arc<dyn NetworkPort>,
     repo: Arc<dyn PairedDeviceRepositoryPort>,
@@ -15,15 +25,264 @@ impl UnpairDevice {
     }
 
     pub async fn execute(&self, peer_id: String) -> Result<()> {
-        let peer = PeerId::from(peer_id.as_str());
-        self.network
-            .unpair_device(peer_id.clone())
+        let span = info_span!("usecase.unpair_device.execute", peer_id = %peer_id);
+
+        async {
+            let peer = PeerId::from(peer_id.as_str());
+            self.repo
+                .delete(&peer)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to delete paired device: {}", e))?;
+            self.repo
+                .mark_revocation_pending(&peer, Utc::now())
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to record pending revocation: {}", e))?;
+            info!("device unpaired locally");
+
+            self.deliver_pending(&peer_id).await?;
+            Ok(())
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Send a pending revocation notice to `peer_id`, if there is one.
+    ///
+    /// Returns whether a notice was sent. The notice stays pending until
+    /// [`Self::acknowledge`] is called; delivery failures are not errors.
+    pub async fn deliver_pending(&self, peer_id: &str) -> Result<bool> {
+        let peer = PeerId::from(peer_id);
+        let pending = self
+            .repo
+            .is_revocation_pending(&peer)
             .await
-            .map_err(|e| anyhow::anyhow!("Failed to unpair device: {}", e))?;
+            .map_err(|e| anyhow::anyhow!("Failed to load pending revocation: {}", e))?;
+        if !pending {
+            return Ok(false);
+        }
+
+        if let Err(err) = self.network.unpair_device(peer_id.to_string()).await {
+            info!(peer_id = %peer_id, error = %err, "revocation notice not delivered, retrying on reconnect");
+            return Ok(false);
+        }
+        info!(peer_id = %peer_id, "revocation notice sent, awaiting acknowledgement");
+        Ok(true)
+    }
+
+    /// Forget the pending revocation for `peer_id` once it acknowledged it.
+    ///
+    /// 对端确认收到撤销通知后删除待送达记录。
+    pub async fn acknowledge(&self, peer_id: &str) -> Result<bool> {
+        let peer = PeerId::from(peer_id);
+        let pending = self
+            .repo
+            .is_revocation_pending(&peer)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to load pending revocation: {}", e))?;
+        if !pending {
+            return Ok(false);
+        }
         self.repo
-            .delete(&peer)
+            .clear_revocation_pending(&peer)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to clear pending revocation: {}", e))?;
+        info!(peer_id = %peer_id, "revocation notice acknowledged");
+        Ok(true)
+    }
+}
+
+/// Use case for a revocation notice received from a peer.
+///
+/// 处理对端的撤销通知：将该设备标记为 `Revoked`，业务协议随之被拒绝。
+/// Returns whether the stored state changed; unknown peers are ignored.
+/// Every notice is acknowledged, including repeats, so the sender stops
+/// resending it.
+pub struct ApplyPeerRevocation {
+    network: Arc<dyn NetworkPort>,
+    repo: Arc<dyn PairedDeviceRepositoryPort>,
+}
+
+impl ApplyPeerRevocation {
+    pub fn new(network: Arc<dyn NetworkPort>, repo: Arc<dyn PairedDeviceRepositoryPort>) -> Self {
+        Self { network, repo }
+    }
+
+    pub async fn execute(&self, peer_id: &str) -> Result<bool> {
+        let span = info_span!("usecase.apply_peer_revocation.execute", peer_id = %peer_id);
+
+        async {
+            let peer = PeerId::from(peer_id);
+            let device = self
+                .repo
+                .get_by_peer_id(&peer)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to load paired device: {}", e))?;
+            self.acknowledge(peer_id).await;
+            match device {
+                Some(device) if device.pairing_state != PairingState::Revoked => {
+                    self.repo
+                        .set_state(&peer, PairingState::Revoked)
+                        .await
+                        .map_err(|e| anyhow::anyhow!("Failed to revoke paired device: {}", e))?;
+                    info!("peer revoked pairing");
+                    Ok(true)
+                }
+                Some(_) => Ok(false),
+                None => {
+                    warn!("revocation notice from unpaired peer ignored");
+                    Ok(false)
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Confirm receipt of the notice; a lost acknowledgement only means the
+    /// sender resends the notice on the next connection.
+    async fn acknowledge(&self, peer_id: &str) {
+        let session_id = format!("revoke-ack-{}", uuid::Uuid::new_v4());
+        let ack = PairingMessage::RevokeAck(PairingRevokeAck {
+            session_id: session_id.clone(),
+        });
+        let result = async {
+            self.network
+                .open_pairing_session(peer_id.to_string(), session_id.clone())
+                .await?;
+            let sent = self
+                .network
+                .send_pairing_on_session(session_id.clone(), ack)
+                .await;
+            self.network
+                .close_pairing_session(session_id, Some("revoke-ack".to_string()))
+                .await?;
+            sent
+        }
+        .await;
+        if let Err(err) = result {
+            warn!(peer_id = %peer_id, error = %err, "Failed to acknowledge revocation notice");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uc_core::network::PairedDevice;
+    use uc_core::testing::{InMemoryPairedDeviceRepository, TestNetwork};
+
+    fn repo_with_device(peer_id: &str, state: PairingState) -> InMemoryPairedDeviceRepository {
+        InMemoryPairedDeviceRepository::with_devices(vec![PairedDevice {
+            peer_id: PeerId::from(peer_id),
+            pairing_state: state,
+            identity_fingerprint: "fp".to_string(),
+            paired_at: Utc::now(),
+            last_seen_at: None,
+            device_name: peer_id.to_string(),
+            device_id: "device-peer".to_string(),
+        }])
+    }
+
+    fn state(repo: &InMemoryPairedDeviceRepository, peer_id: &str) -> Option<PairingState> {
+        repo.device(peer_id).map(|device| device.pairing_state)
+    }
+
+    async fn pending(repo: &InMemoryPairedDeviceRepository, peer_id: &str) -> bool {
+        repo.is_revocation_pending(&PeerId::from(peer_id))
             .await
-            .map_err(|e| anyhow::anyhow!("Failed to delete paired device: {}", e))?;
-        Ok(())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn unpair_online_peer_notifies_it_immediately() {
+        let network = Arc::new(TestNetwork::new());
+        let repo = Arc::new(repo_with_device("peer-1", PairingState::Trusted));
+        let uc = UnpairDevice::new(network.clone(), repo.clone());
+
+        uc.execute("peer-1".to_string()).await.unwrap();
+
+        assert_eq!(state(&repo, "peer-1"), None);
+        assert_eq!(network.unpaired_peers().as_slice(), ["peer-1"]);
+        assert!(pending(&repo, "peer-1").await);
+
+        assert!(uc.acknowledge("peer-1").await.unwrap());
+        assert!(!pending(&repo, "peer-1").await);
+        assert!(!uc.acknowledge("peer-1").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn unpair_offline_peer_keeps_notice_pending_until_reconnect() {
+        let repo = Arc::new(repo_with_device("peer-1", PairingState::Trusted));
+        let network = Arc::new(TestNetwork::new().with_offline_peer("peer-1"));
+        let offline = UnpairDevice::new(network.clone(), repo.clone());
+
+        offline.execute("peer-1".to_string()).await.unwrap();
+        assert_eq!(state(&repo, "peer-1"), None);
+        assert!(pending(&repo, "peer-1").await);
+
+        network.set_online("peer-1", true);
+        assert!(offline.deliver_pending("peer-1").await.unwrap());
+        // Without an acknowledgement the notice is sent again.
+        assert!(offline.deliver_pending("peer-1").await.unwrap());
+        offline.acknowledge("peer-1").await.unwrap();
+        assert!(!offline.deliver_pending("peer-1").await.unwrap());
+        assert_eq!(network.unpaired_peers().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn repairing_after_unpair_drops_pending_revocation() {
+        let repo = Arc::new(repo_with_device("peer-1", PairingState::Trusted));
+        let network = Arc::new(TestNetwork::new().with_offline_peer("peer-1"));
+        let uc = UnpairDevice::new(network.clone(), repo.clone());
+
+        uc.execute("peer-1".to_string()).await.unwrap();
+        assert!(pending(&repo, "peer-1").await);
+
+        repo.upsert(PairedDevice {
+            peer_id: PeerId::from("peer-1"),
+            pairing_state: PairingState::Trusted,
+            identity_fingerprint: "fp".to_string(),
+            paired_at: Utc::now(),
+            last_seen_at: None,
+            device_name: "peer-1".to_string(),
+            device_id: "device-peer".to_string(),
+        })
+        .await
+        .unwrap();
+
+        network.set_online("peer-1", true);
+        assert!(!uc.deliver_pending("peer-1").await.unwrap());
+        assert!(network.unpaired_peers().is_empty());
+        assert_eq!(state(&repo, "peer-1"), Some(PairingState::Trusted));
+    }
+
+    #[tokio::test]
+    async fn unpair_unknown_device_fails_without_notice() {
+        let network = Arc::new(TestNetwork::new());
+        let repo = Arc::new(InMemoryPairedDeviceRepository::new());
+        let uc = UnpairDevice::new(network.clone(), repo.clone());
+
+        assert!(uc.execute("peer-1".to_string()).await.is_err());
+        assert!(network.unpaired_peers().is_empty());
+        assert!(!pending(&repo, "peer-1").await);
+    }
+
+    #[tokio::test]
+    async fn peer_revocation_marks_trusted_device_revoked_once() {
+        let repo = Arc::new(repo_with_device("peer-1", PairingState::Trusted));
+        let network = Arc::new(TestNetwork::new());
+        let uc = ApplyPeerRevocation::new(network.clone(), repo.clone());
+
+        assert!(uc.execute("peer-1").await.unwrap());
+        assert!(!uc.execute("peer-1").await.unwrap());
+        assert!(!uc.execute("stranger").await.unwrap());
+        assert_eq!(state(&repo, "peer-1"), Some(PairingState::Revoked));
+
+        let acks = network
+            .pairing_messages()
+            .into_iter()
+            .filter(|(_, message)| matches!(message, PairingMessage::RevokeAck(_)))
+            .count();
+        assert_eq!(acks, 3);
     }
 }
@@ -0,0 +1,132 @@
+use anyhow::Result;
+use std::sync::Arc;
+use tracing::warn;
+use uc_core::crypto::IdentityFingerprint;
+use uc_core::network::{pinned_device, IdentityCheck, PairingState};
+use uc_core::ports::PairedDeviceRepositoryPort;
+use uc_core::PeerId;
+
+/// Use case for trust-on-first-use verification of a connected peer.
+///
+/// The pin is found by the device id the peer claims, so a known device
+/// showing up under a new peer id is compared with the key it was paired with.
+///
+/// 按对端声明的设备 ID 查找配对时记录的指纹并比对；不一致时把出示该身份的
+/// 已配对设备置为 `Quarantined`，业务协议随即被 [`super::ResolveConnectionPolicy`] 拒绝。
+pub struct VerifyPeerIdentity {
+    repo: Arc<dyn PairedDeviceRepositoryPort>,
+}
+
+impl VerifyPeerIdentity {
+    pub fn new(repo: Arc<dyn PairedDeviceRepositoryPort>) -> Self {
+        Self { repo }
+    }
+
+    /// Returns `Mismatch` only for the check that quarantined the peer;
+    /// later checks report `Quarantined`. A presenting peer that was never
+    /// paired has nothing to quarantine and keeps getting `Mismatch`.
+    pub async fn execute(
+        &self,
+        peer_id: &PeerId,
+        device_id: &str,
+        identity_pubkey: &[u8],
+    ) -> Result<IdentityCheck> {
+        let presented = IdentityFingerprint::from_public_key(identity_pubkey)?;
+        let devices = self
+            .repo
+            .list_all()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to load paired devices: {}", e))?;
+
+        let pinned = pinned_device(&devices, peer_id, device_id);
+        let check = IdentityCheck::evaluate(pinned, &presented);
+        if let IdentityCheck::Mismatch {
+            expected_fingerprint,
+            presented_fingerprint,
+        } = &check
+        {
+            warn!(
+                peer_id = %peer_id,
+                device_id = %device_id,
+                expected_fingerprint = %expected_fingerprint,
+                presented_fingerprint = %presented_fingerprint,
+                "peer identity changed, quarantining device"
+            );
+            if devices.iter().any(|device| &device.peer_id == peer_id) {
+                self.repo
+                    .set_state(peer_id, PairingState::Quarantined)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to quarantine peer: {}", e))?;
+            }
+        }
+        Ok(check)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uc_core::network::PairedDevice;
+    use uc_core::testing::InMemoryPairedDeviceRepository;
+
+    fn trusted(pubkey: &[u8]) -> InMemoryPairedDeviceRepository {
+        InMemoryPairedDeviceRepository::with_devices(vec![PairedDevice {
+            peer_id: PeerId::from("peer-1"),
+            pairing_state: PairingState::Trusted,
+            identity_fingerprint: IdentityFingerprint::from_public_key(pubkey)
+                .unwrap()
+                .to_string(),
+            paired_at: chrono::Utc::now(),
+            last_seen_at: None,
+            device_name: "Peer".to_string(),
+            device_id: "device-peer".to_string(),
+        }])
+    }
+
+    fn state(repo: &InMemoryPairedDeviceRepository) -> PairingState {
+        repo.device("peer-1").unwrap().pairing_state
+    }
+
+    #[tokio::test]
+    async fn matching_key_keeps_peer_trusted() {
+        let repo = Arc::new(trusted(&[1; 32]));
+        let uc = VerifyPeerIdentity::new(repo.clone());
+
+        let check = uc
+            .execute(&PeerId::from("peer-1"), "device-peer", &[1; 32])
+            .await
+            .unwrap();
+
+        assert_eq!(check, IdentityCheck::Verified);
+        assert_eq!(state(&repo), PairingState::Trusted);
+    }
+
+    #[tokio::test]
+    async fn changed_key_quarantines_peer_once() {
+        let repo = Arc::new(trusted(&[1; 32]));
+        let uc = VerifyPeerIdentity::new(repo.clone());
+        let peer_id = PeerId::from("peer-1");
+
+        let first = uc.execute(&peer_id, "device-peer", &[2; 32]).await.unwrap();
+        let second = uc.execute(&peer_id, "device-peer", &[2; 32]).await.unwrap();
+
+        assert!(matches!(first, IdentityCheck::Mismatch { .. }));
+        assert_eq!(second, IdentityCheck::Quarantined);
+        assert_eq!(state(&repo), PairingState::Quarantined);
+    }
+
+    #[tokio::test]
+    async fn known_device_from_new_peer_is_a_mismatch() {
+        let repo = Arc::new(trusted(&[1; 32]));
+        let uc = VerifyPeerIdentity::new(repo.clone());
+
+        let check = uc
+            .execute(&PeerId::from("peer-2"), "device-peer", &[2; 32])
+            .await
+            .unwrap();
+
+        assert!(matches!(check, IdentityCheck::Mismatch { .. }));
+        assert_eq!(state(&repo), PairingState::Trusted);
+        assert!(repo.device("peer-2").is_none());
+    }
+}
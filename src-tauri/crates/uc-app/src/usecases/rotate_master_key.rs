@@ -0,0 +1,854 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tracing::{debug, info, info_span, warn, Instrument};
+
+use uc_core::{
+    ports::{
+        security::{
+            encryption_state::EncryptionStatePort,
+            key_scope::{KeyScopePort, ScopeError},
+        },
+        DeviceIdentityPort, EncryptionPort, EncryptionSessionPort, KeyMaterialPort, NetworkPort,
+        PairedDeviceRepositoryPort, PayloadReencryptionPort,
+    },
+    security::{
+        model::{
+            EncryptionAlgo, EncryptionError, KeyEpoch, KeySlot, MasterKey, RetainedMasterKey,
+            WrappedMasterKey,
+        },
+        state::{EncryptionState, EncryptionStateError},
+    },
+};
+
+use super::keyslot_sync::KeySlotPublisher;
+
+/// Number of payloads listed per page by [`ReencryptHistory`].
+const REENCRYPT_PAGE_SIZE: usize = 100;
+
+/// Passes a payload may fail before [`ReencryptHistory`] gives up on it.
+const MAX_REENCRYPT_ATTEMPTS: u32 = 3;
+
+#[derive(Debug, thiserror::Error)]
+pub enum RotateMasterKeyError {
+    #[error("encryption is not initialized")]
+    NotInitialized,
+
+    #[error("encryption session is locked")]
+    SessionLocked,
+
+    #[error("a key rotation is already in progress")]
+    RotationInProgress,
+
+    #[error("keyslot is missing the wrapped master key")]
+    MissingWrappedMasterKey,
+
+    #[error("keyring KEK does not match the current keyslot")]
+    KekMismatch,
+
+    #[error("failed to rotate master key")]
+    EncryptionFailed(#[from] EncryptionError),
+
+    #[error("failed to load encryption state")]
+    StateLoadFailed(#[from] EncryptionStateError),
+
+    #[error("failed to resolve key scope")]
+    ScopeFailed(#[from] ScopeError),
+}
+
+/// Use case for rotating the `MasterKey`.
+/// 轮换 `MasterKey` 的用例。
+///
+/// # Behavior / 行为
+/// - 1. Generate a new `MasterKey` for the next key epoch (生成下一纪元的主密钥)
+/// - 2. Wrap it with the keyring KEK and keep the old wrapped key as retired (保留退役密钥)
+/// - 3. Install both keys in the session so old data stays readable (会话同时持有新旧密钥)
+/// - 4. Push the new keyslot to connected trusted peers, best effort (同步到受信任设备)
+///
+/// Existing history is re-encrypted afterwards by [`ReencryptHistory`]; the
+/// retired key is dropped once that job finishes.
+///
+/// 历史数据随后由 [`ReencryptHistory`] 重新加密，完成后丢弃退役密钥。
+pub struct RotateMasterKey {
+    encryption: Arc<dyn EncryptionPort>,
+    encryption_session: Arc<dyn EncryptionSessionPort>,
+    key_material: Arc<dyn KeyMaterialPort>,
+    key_scope: Arc<dyn KeyScopePort>,
+    encryption_state_repo: Arc<dyn EncryptionStatePort>,
+    publisher: KeySlotPublisher,
+}
+
+impl RotateMasterKey {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        encryption: Arc<dyn EncryptionPort>,
+        encryption_session: Arc<dyn EncryptionSessionPort>,
+        key_material: Arc<dyn KeyMaterialPort>,
+        key_scope: Arc<dyn KeyScopePort>,
+        encryption_state_repo: Arc<dyn EncryptionStatePort>,
+        paired_device_repo: Arc<dyn PairedDeviceRepositoryPort>,
+        network: Arc<dyn NetworkPort>,
+        device_identity: Arc<dyn DeviceIdentityPort>,
+    ) -> Self {
+        Self {
+            publisher: KeySlotPublisher::new(
+                encryption.clone(),
+                paired_device_repo,
+                network,
+                device_identity,
+            ),
+            encryption,
+            encryption_session,
+            key_material,
+            key_scope,
+            encryption_state_repo,
+        }
+    }
+
+    /// Rotate the master key and return the new key epoch.
+    pub async fn execute(&self) -> Result<KeyEpoch, RotateMasterKeyError> {
+        let span = info_span!("usecase.rotate_master_key.execute");
+
+        async {
+            let state = self.encryption_state_repo.load_state().await?;
+            if state != EncryptionState::Initialized {
+                return Err(RotateMasterKeyError::NotInitialized);
+            }
+            if !self.encryption_session.is_ready().await {
+                return Err(RotateMasterKeyError::SessionLocked);
+            }
+
+            let scope = self.key_scope.current_scope().await?;
+            let keyslot = self.key_material.load_keyslot(&scope).await?;
+            if keyslot.is_rotating() {
+                return Err(RotateMasterKeyError::RotationInProgress);
+            }
+            let wrapped = keyslot
+                .wrapped_master_key
+                .clone()
+                .ok_or(RotateMasterKeyError::MissingWrappedMasterKey)?;
+
+            // 1. the keyring KEK must still open the current keyslot
+            let (_, current_key) = self.encryption_session.get_current_key().await?;
+            let kek = self.key_material.load_kek(&scope).await?;
+            let unwrapped = self
+                .encryption
+                .unwrap_master_key(&kek, &wrapped.blob)
+                .await
+                .map_err(|_| RotateMasterKeyError::KekMismatch)?;
+            if unwrapped != current_key {
+                return Err(RotateMasterKeyError::KekMismatch);
+            }
+
+            // 2. generate and wrap the next MasterKey
+            let old_epoch = keyslot.epoch;
+            let mut older_keys = vec![(old_epoch, current_key.clone())];
+            for retained in &keyslot.retained_master_keys {
+                older_keys.push((
+                    retained.epoch,
+                    self.encryption_session
+                        .get_master_key_for_epoch(retained.epoch)
+                        .await?,
+                ));
+            }
+            let new_epoch = old_epoch + 1;
+            let new_key = MasterKey::generate()?;
+            let blob = self
+                .encryption
+                .wrap_master_key(&kek, &new_key, EncryptionAlgo::XChaCha20Poly1305)
+                .await?;
+            let new_keyslot = KeySlot {
+                epoch: new_epoch,
+                retired_master_key: Some(wrapped),
+                wrapped_master_key: Some(WrappedMasterKey { blob }),
+                ..keyslot
+            };
+
+            // 3. persist, then switch the session to the new epoch
+            self.key_material.store_keyslot(&new_keyslot).await?;
+            self.encryption_session
+                .set_epoch_keys(new_epoch, new_key, older_keys)
+                .await?;
+            info!(epoch = new_epoch, "Master key rotated");
+
+            // 4. propagate to peers, sealed with the key they still hold
+            if let Err(err) = self
+                .publisher
+                .publish(old_epoch, &current_key, &new_keyslot)
+                .await
+            {
+                warn!(error = %err, "Failed to propagate key rotation to peers");
+            }
+
+            Ok(new_epoch)
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+/// Progress of a history re-encryption pass.
+/// 历史数据重新加密进度。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReencryptionProgress {
+    pub epoch: KeyEpoch,
+    pub processed: usize,
+    pub total: usize,
+    /// Payloads that failed this pass and will be retried.
+    pub failed: usize,
+    /// Payloads given up after [`MAX_REENCRYPT_ATTEMPTS`] failed passes; they
+    /// stay readable through the retained key of their epoch.
+    pub abandoned: usize,
+}
+
+impl ReencryptionProgress {
+    pub fn is_complete(&self) -> bool {
+        self.failed == 0 && self.processed >= self.total
+    }
+}
+
+/// Key epoch of the local keyslot and whether a rotation is still being applied.
+/// 本地密钥槽的纪元以及轮换是否仍在进行。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct KeyRotationStatus {
+    pub epoch: KeyEpoch,
+    pub in_progress: bool,
+}
+
+/// Re-encrypt stored history after a key rotation.
+/// 密钥轮换后重新加密历史数据。
+///
+/// Walks every inline payload and blob, re-sealing those still tagged with an
+/// older epoch. A payload that fails [`MAX_REENCRYPT_ATTEMPTS`] passes is given
+/// up so the rotation can finish. When a pass finishes with nothing left to
+/// retry, the retired key is dropped from the keyslot and the session, or moved
+/// to the retained keys if some payloads were given up. The job is resumable:
+/// payloads already at the current epoch are skipped, and the retired key stays
+/// in the keyslot until the job has completed. Attempt counts live in memory
+/// and restart with the process.
+///
+/// 多次失败的数据会被放弃以便轮换完成，其密钥保留在密钥环中以保证仍可读取。
+/// 可恢复：已是当前纪元的数据会被跳过，退役密钥在任务完成前一直保存在密钥槽中。
+pub struct ReencryptHistory {
+    reencryption: Arc<dyn PayloadReencryptionPort>,
+    encryption_session: Arc<dyn EncryptionSessionPort>,
+    key_material: Arc<dyn KeyMaterialPort>,
+    key_scope: Arc<dyn KeyScopePort>,
+    /// Failed passes per payload for the epoch being applied.
+    attempts: Mutex<(KeyEpoch, HashMap<String, u32>)>,
+}
+
+impl ReencryptHistory {
+    pub fn new(
+        reencryption: Arc<dyn PayloadReencryptionPort>,
+        encryption_session: Arc<dyn EncryptionSessionPort>,
+        key_material: Arc<dyn KeyMaterialPort>,
+        key_scope: Arc<dyn KeyScopePort>,
+    ) -> Self {
+        Self {
+            reencryption,
+            encryption_session,
+            key_material,
+            key_scope,
+            attempts: Mutex::new((0, HashMap::new())),
+        }
+    }
+
+    fn attempts(&self) -> std::sync::MutexGuard<'_, (KeyEpoch, HashMap<String, u32>)> {
+        self.attempts
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn gave_up(&self, item: &str) -> bool {
+        self.attempts()
+            .1
+            .get(item)
+            .is_some_and(|count| *count >= MAX_REENCRYPT_ATTEMPTS)
+    }
+
+    /// Record a failed attempt; returns `true` once the payload is given up.
+    fn record_failure(&self, item: String) -> bool {
+        let mut attempts = self.attempts();
+        let count = attempts.1.entry(item).or_insert(0);
+        *count += 1;
+        *count >= MAX_REENCRYPT_ATTEMPTS
+    }
+
+    /// Current key epoch and whether re-encryption is still pending.
+    pub async fn status(&self) -> anyhow::Result<KeyRotationStatus> {
+        let scope = self.key_scope.current_scope().await?;
+        let keyslot = self.key_material.load_keyslot(&scope).await?;
+        Ok(KeyRotationStatus {
+            epoch: keyslot.epoch,
+            in_progress: keyslot.is_rotating(),
+        })
+    }
+
+    /// Run one re-encryption pass.
+    ///
+    /// Returns `None` when no rotation is pending. `on_progress` is called after
+    /// every page and once more when the pass ends.
+    pub async fn execute(
+        &self,
+        on_progress: &(dyn Fn(ReencryptionProgress) + Send + Sync),
+    ) -> anyhow::Result<Option<ReencryptionProgress>> {
+        let span = info_span!("usecase.reencrypt_history.execute");
+
+        async {
+            let scope = self.key_scope.current_scope().await?;
+            let keyslot = self.key_material.load_keyslot(&scope).await?;
+            if !keyslot.is_rotating() {
+                return Ok(None);
+            }
+
+            let (epoch, current_key) = self.encryption_session.get_current_key().await?;
+            if epoch != keyslot.epoch {
+                anyhow::bail!(
+                    "session key epoch {epoch} does not match keyslot epoch {}",
+                    keyslot.epoch
+                );
+            }
+
+            {
+                let mut attempts = self.attempts();
+                if attempts.0 != epoch {
+                    *attempts = (epoch, HashMap::new());
+                }
+            }
+
+            let mut progress = ReencryptionProgress {
+                epoch,
+                processed: 0,
+                total: self.reencryption.count_inline_payloads().await?
+                    + self.reencryption.count_blob_payloads().await?,
+                failed: 0,
+                abandoned: 0,
+            };
+            info!(epoch, total = progress.total, "Re-encrypting history");
+            on_progress(progress);
+
+            // 1. inline representation data
+            let mut cursor = None;
+            loop {
+                let page = self
+                    .reencryption
+                    .list_inline_payloads(cursor.as_ref(), REENCRYPT_PAGE_SIZE)
+                    .await?;
+                let Some((_, last)) = page.last() else {
+                    break;
+                };
+                cursor = Some(last.clone());
+                for (event_id, representation_id) in &page {
+                    let item = format!("inline:{representation_id}");
+                    progress.processed += 1;
+                    if self.gave_up(&item) {
+                        progress.abandoned += 1;
+                        continue;
+                    }
+                    if let Err(err) = self
+                        .reencryption
+                        .reencrypt_inline(event_id, representation_id)
+                        .await
+                    {
+                        warn!(
+                            representation_id = %representation_id,
+                            error = %err,
+                            "Failed to re-encrypt inline payload"
+                        );
+                        if self.record_failure(item) {
+                            progress.abandoned += 1;
+                        } else {
+                            progress.failed += 1;
+                        }
+                    }
+                }
+                on_progress(progress);
+            }
+
+            // 2. blobs
+            let mut cursor = None;
+            loop {
+                let page = self
+                    .reencryption
+                    .list_blob_payloads(cursor.as_ref(), REENCRYPT_PAGE_SIZE)
+                    .await?;
+                let Some(last) = page.last() else {
+                    break;
+                };
+                cursor = Some(last.clone());
+                for blob_id in &page {
+                    let item = format!("blob:{blob_id}");
+                    progress.processed += 1;
+                    if self.gave_up(&item) {
+                        progress.abandoned += 1;
+                        continue;
+                    }
+                    if let Err(err) = self.reencryption.reencrypt_blob(blob_id).await {
+                        warn!(blob_id = %blob_id, error = %err, "Failed to re-encrypt blob");
+                        if self.record_failure(item) {
+                            progress.abandoned += 1;
+                        } else {
+                            progress.failed += 1;
+                        }
+                    }
+                }
+                on_progress(progress);
+            }
+
+            // Items added while the pass ran are already current; report them as done.
+            progress.total = progress.total.max(progress.processed);
+
+            if progress.failed > 0 {
+                warn!(
+                    failed = progress.failed,
+                    "History re-encryption incomplete, retired key kept"
+                );
+                on_progress(progress);
+                return Ok(Some(progress));
+            }
+
+            // 3. drop the retired key, or retain it for payloads that were given up
+            let keyslot = self.key_material.load_keyslot(&scope).await?;
+            if keyslot.epoch == epoch {
+                let mut retained_master_keys = Vec::new();
+                if progress.abandoned > 0 {
+                    retained_master_keys = keyslot.retained_master_keys.clone();
+                    if let (Some(wrapped), Some(retired_epoch)) =
+                        (keyslot.retired_master_key.clone(), epoch.checked_sub(1))
+                    {
+                        retained_master_keys.push(RetainedMasterKey {
+                            epoch: retired_epoch,
+                            wrapped,
+                        });
+                    }
+                }
+                let finished = KeySlot {
+                    retired_master_key: None,
+                    retained_master_keys,
+                    ..keyslot
+                };
+                let mut older_keys = Vec::new();
+                for (older_epoch, _) in finished.older_master_keys() {
+                    older_keys.push((
+                        older_epoch,
+                        self.encryption_session
+                            .get_master_key_for_epoch(older_epoch)
+                            .await?,
+                    ));
+                }
+                self.key_material.store_keyslot(&finished).await?;
+                self.encryption_session
+                    .set_epoch_keys(epoch, current_key, older_keys)
+                    .await?;
+            } else {
+                debug!("Keyslot epoch changed during re-encryption, leaving it untouched");
+            }
+            if progress.abandoned > 0 {
+                warn!(
+                    epoch,
+                    abandoned = progress.abandoned,
+                    "History re-encryption finished, older keys retained for payloads given up"
+                );
+            } else {
+                info!(
+                    epoch,
+                    processed = progress.processed,
+                    "History re-encryption completed"
+                );
+            }
+            on_progress(progress);
+
+            Ok(Some(progress))
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::usecases::keyslot_sync::test_support::{
+        build_apply_and_confirm, seed_keyslot, trusted, InMemoryKeyMaterial, InitializedState,
+        TestDeviceIdentity, TestKeyScope,
+    };
+    use crate::usecases::ResendKeySlot;
+    use async_trait::async_trait;
+    use std::collections::BTreeMap;
+    use std::sync::Mutex;
+    use uc_core::ids::{EventId, RepresentationId};
+    use uc_core::network::ProtocolMessage;
+    use uc_core::security::model::Passphrase;
+    use uc_core::testing::{InMemoryPairedDeviceRepository, TestNetwork};
+    use uc_core::BlobId;
+    use uc_infra::security::{EncryptionRepository, InMemoryEncryptionSession};
+
+    /// Tracks the key epoch of each payload; ids prefixed `bad` always fail.
+    struct FakeReencryption {
+        session: Arc<InMemoryEncryptionSession>,
+        inline: Mutex<BTreeMap<String, KeyEpoch>>,
+        blobs: Mutex<BTreeMap<String, KeyEpoch>>,
+    }
+
+    impl FakeReencryption {
+        fn new(session: Arc<InMemoryEncryptionSession>, inline: &[&str], blobs: &[&str]) -> Self {
+            Self {
+                session,
+                inline: Mutex::new(inline.iter().map(|id| (id.to_string(), 0)).collect()),
+                blobs: Mutex::new(blobs.iter().map(|id| (id.to_string(), 0)).collect()),
+            }
+        }
+
+        fn page(
+            items: &BTreeMap<String, KeyEpoch>,
+            after: Option<&str>,
+            limit: usize,
+        ) -> Vec<String> {
+            items
+                .keys()
+                .filter(|id| after.is_none_or(|after| id.as_str() > after))
+                .take(limit)
+                .cloned()
+                .collect()
+        }
+
+        async fn bump(
+            &self,
+            items: &Mutex<BTreeMap<String, KeyEpoch>>,
+            id: &str,
+        ) -> anyhow::Result<bool> {
+            if id.starts_with("bad") {
+                anyhow::bail!("corrupted payload");
+            }
+            let (epoch, _) = self.session.get_current_key().await?;
+            let previous = items.lock().unwrap().insert(id.to_string(), epoch);
+            Ok(previous != Some(epoch))
+        }
+    }
+
+    #[async_trait]
+    impl PayloadReencryptionPort for FakeReencryption {
+        async fn count_inline_payloads(&self) -> anyhow::Result<usize> {
+            Ok(self.inline.lock().unwrap().len())
+        }
+
+        async fn list_inline_payloads(
+            &self,
+            after: Option<&RepresentationId>,
+            limit: usize,
+        ) -> anyhow::Result<Vec<(EventId, RepresentationId)>> {
+            let inline = self.inline.lock().unwrap();
+            Ok(Self::page(&inline, after.map(|id| id.as_ref()), limit)
+                .into_iter()
+                .map(|id| (EventId::from("event"), RepresentationId::from(id)))
+                .collect())
+        }
+
+        async fn count_blob_payloads(&self) -> anyhow::Result<usize> {
+            Ok(self.blobs.lock().unwrap().len())
+        }
+
+        async fn list_blob_payloads(
+            &self,
+            after: Option<&BlobId>,
+            limit: usize,
+        ) -> anyhow::Result<Vec<BlobId>> {
+            let blobs = self.blobs.lock().unwrap();
+            Ok(Self::page(&blobs, after.map(|id| id.as_ref()), limit)
+                .into_iter()
+                .map(BlobId::from)
+                .collect())
+        }
+
+        async fn reencrypt_inline(
+            &self,
+            _event_id: &EventId,
+            representation_id: &RepresentationId,
+        ) -> anyhow::Result<bool> {
+            self.bump(&self.inline, representation_id.as_ref()).await
+        }
+
+        async fn reencrypt_blob(&self, blob_id: &BlobId) -> anyhow::Result<bool> {
+            self.bump(&self.blobs, blob_id.as_ref()).await
+        }
+    }
+
+    fn build_rotate(
+        session: Arc<InMemoryEncryptionSession>,
+        key_material: Arc<InMemoryKeyMaterial>,
+        network: Arc<TestNetwork>,
+    ) -> RotateMasterKey {
+        RotateMasterKey::new(
+            Arc::new(EncryptionRepository),
+            session,
+            key_material,
+            Arc::new(TestKeyScope("local")),
+            Arc::new(InitializedState),
+            Arc::new(InMemoryPairedDeviceRepository::with_devices(vec![trusted(
+                "peer-1",
+            )])),
+            network,
+            Arc::new(TestDeviceIdentity),
+        )
+    }
+
+    #[tokio::test]
+    async fn rotate_then_reencrypt_drops_retired_key() {
+        let old_key = MasterKey::generate().unwrap();
+        let key_material = Arc::new(InMemoryKeyMaterial::default());
+        seed_keyslot(&key_material, "local", "pass", &old_key).await;
+        let session = Arc::new(InMemoryEncryptionSession::new());
+        session.set_master_key(old_key.clone()).await.unwrap();
+
+        let rotate = build_rotate(
+            session.clone(),
+            key_material.clone(),
+            Arc::new(TestNetwork::new()),
+        );
+        assert_eq!(rotate.execute().await.unwrap(), 1);
+        assert!(matches!(
+            rotate.execute().await,
+            Err(RotateMasterKeyError::RotationInProgress)
+        ));
+
+        let (epoch, new_key) = session.get_current_key().await.unwrap();
+        assert_eq!(epoch, 1);
+        assert_ne!(new_key, old_key);
+        assert_eq!(session.get_master_key_for_epoch(0).await.unwrap(), old_key);
+        let keyslot = key_material.keyslot.lock().unwrap().clone().unwrap();
+        assert!(keyslot.is_rotating());
+
+        let reencryption = Arc::new(FakeReencryption::new(
+            session.clone(),
+            &["rep-1", "rep-2"],
+            &["blob-1"],
+        ));
+        let job = ReencryptHistory::new(
+            reencryption.clone(),
+            session.clone(),
+            key_material.clone(),
+            Arc::new(TestKeyScope("local")),
+        );
+        let reports = Mutex::new(Vec::new());
+        let progress = job
+            .execute(&|progress| reports.lock().unwrap().push(progress))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(progress.is_complete());
+        assert_eq!(progress.processed, 3);
+        assert_eq!(reports.lock().unwrap().last(), Some(&progress));
+        assert!(reencryption
+            .inline
+            .lock()
+            .unwrap()
+            .values()
+            .all(|e| *e == 1));
+        assert!(reencryption.blobs.lock().unwrap().values().all(|e| *e == 1));
+        assert!(!job.status().await.unwrap().in_progress);
+        assert_eq!(session.get_master_key_for_epoch(0).await.unwrap(), new_key);
+        assert!(job.execute(&|_| {}).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn reencrypt_keeps_retired_key_when_items_fail() {
+        let old_key = MasterKey::generate().unwrap();
+        let key_material = Arc::new(InMemoryKeyMaterial::default());
+        seed_keyslot(&key_material, "local", "pass", &old_key).await;
+        let session = Arc::new(InMemoryEncryptionSession::new());
+        session.set_master_key(old_key.clone()).await.unwrap();
+        build_rotate(
+            session.clone(),
+            key_material.clone(),
+            Arc::new(TestNetwork::new()),
+        )
+        .execute()
+        .await
+        .unwrap();
+
+        let job = ReencryptHistory::new(
+            Arc::new(FakeReencryption::new(
+                session.clone(),
+                &["bad-1", "rep-1"],
+                &[],
+            )),
+            session.clone(),
+            key_material.clone(),
+            Arc::new(TestKeyScope("local")),
+        );
+        let progress = job.execute(&|_| {}).await.unwrap().unwrap();
+
+        assert_eq!(progress.failed, 1);
+        assert!(!progress.is_complete());
+        assert!(job.status().await.unwrap().in_progress);
+        assert_eq!(session.get_master_key_for_epoch(0).await.unwrap(), old_key);
+    }
+
+    #[tokio::test]
+    async fn rotation_propagates_to_trusted_peer() {
+        let old_key = MasterKey::generate().unwrap();
+        let local = Arc::new(InMemoryKeyMaterial::default());
+        seed_keyslot(&local, "local", "pass", &old_key).await;
+        let session = Arc::new(InMemoryEncryptionSession::new());
+        session.set_master_key(old_key.clone()).await.unwrap();
+        let network = Arc::new(TestNetwork::new().with_connected(["peer-1"]));
+        build_rotate(session.clone(), local, network.clone())
+            .execute()
+            .await
+            .unwrap();
+        let (_, new_key) = session.get_current_key().await.unwrap();
+
+        let (_, payload) = network.sent_clipboard().pop().unwrap();
+        let message = match ProtocolMessage::from_bytes(&payload).unwrap() {
+            ProtocolMessage::KeySlotUpdate(message) => message,
+            other => panic!("unexpected message: {other:?}"),
+        };
+
+        // Receiving side still holds only the old key.
+        let remote = Arc::new(InMemoryKeyMaterial::default());
+        seed_keyslot(&remote, "remote", "pass", &old_key).await;
+        let remote_session = Arc::new(InMemoryEncryptionSession::new());
+        remote_session
+            .set_master_key(old_key.clone())
+            .await
+            .unwrap();
+        let (apply, confirm) =
+            build_apply_and_confirm(remote_session.clone(), remote.clone(), "device-local-peer");
+        apply
+            .execute("device-local-peer", message.clone())
+            .await
+            .unwrap();
+        assert_eq!(remote_session.get_current_key().await.unwrap().0, 0);
+        confirm
+            .execute(Passphrase("pass".to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            remote_session.get_current_key().await.unwrap(),
+            (1, new_key)
+        );
+        assert_eq!(
+            remote_session.get_master_key_for_epoch(0).await.unwrap(),
+            old_key
+        );
+        let keyslot = remote.keyslot.lock().unwrap().clone().unwrap();
+        assert_eq!(keyslot.epoch, 1);
+        assert!(keyslot.is_rotating());
+        assert_eq!(keyslot.scope.profile_id, "remote");
+
+        // Replaying the update is ignored and changes nothing.
+        assert!(apply
+            .execute("device-local-peer", message)
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(remote_session.get_current_key().await.unwrap().0, 1);
+        assert_eq!(
+            remote_session.get_master_key_for_epoch(0).await.unwrap(),
+            old_key
+        );
+    }
+
+    #[tokio::test]
+    async fn reencrypt_gives_up_on_failing_items_and_retains_their_key() {
+        let old_key = MasterKey::generate().unwrap();
+        let key_material = Arc::new(InMemoryKeyMaterial::default());
+        seed_keyslot(&key_material, "local", "pass", &old_key).await;
+        let session = Arc::new(InMemoryEncryptionSession::new());
+        session.set_master_key(old_key.clone()).await.unwrap();
+        let rotate = build_rotate(
+            session.clone(),
+            key_material.clone(),
+            Arc::new(TestNetwork::new()),
+        );
+        rotate.execute().await.unwrap();
+
+        let job = ReencryptHistory::new(
+            Arc::new(FakeReencryption::new(
+                session.clone(),
+                &["bad-1", "rep-1"],
+                &[],
+            )),
+            session.clone(),
+            key_material.clone(),
+            Arc::new(TestKeyScope("local")),
+        );
+        for _ in 1..MAX_REENCRYPT_ATTEMPTS {
+            let progress = job.execute(&|_| {}).await.unwrap().unwrap();
+            assert_eq!((progress.failed, progress.abandoned), (1, 0));
+        }
+        let progress = job.execute(&|_| {}).await.unwrap().unwrap();
+
+        assert_eq!((progress.failed, progress.abandoned), (0, 1));
+        assert!(progress.is_complete());
+        assert!(!job.status().await.unwrap().in_progress);
+        let keyslot = key_material.keyslot.lock().unwrap().clone().unwrap();
+        assert_eq!(keyslot.retained_master_keys.len(), 1);
+        assert_eq!(keyslot.retained_master_keys[0].epoch, 0);
+        assert_eq!(session.get_master_key_for_epoch(0).await.unwrap(), old_key);
+
+        // The next rotation is allowed and keeps the retained epoch readable.
+        assert_eq!(rotate.execute().await.unwrap(), 2);
+        assert_eq!(session.get_master_key_for_epoch(0).await.unwrap(), old_key);
+    }
+
+    #[tokio::test]
+    async fn rotation_reaches_peer_that_was_offline() {
+        let old_key = MasterKey::generate().unwrap();
+        let local = Arc::new(InMemoryKeyMaterial::default());
+        seed_keyslot(&local, "local", "pass", &old_key).await;
+        let session = Arc::new(InMemoryEncryptionSession::new());
+        session.set_master_key(old_key.clone()).await.unwrap();
+        let network = Arc::new(TestNetwork::new());
+        build_rotate(session.clone(), local.clone(), network.clone())
+            .execute()
+            .await
+            .unwrap();
+        assert!(network.sent_clipboard().is_empty());
+
+        // The peer comes back online.
+        let resend = ResendKeySlot::new(
+            Arc::new(EncryptionRepository),
+            session.clone(),
+            local,
+            Arc::new(TestKeyScope("local")),
+            Arc::new(InMemoryPairedDeviceRepository::with_devices(vec![trusted(
+                "peer-1",
+            )])),
+            network.clone(),
+            Arc::new(TestDeviceIdentity),
+        );
+        assert_eq!(resend.execute("peer-1").await.unwrap(), 2);
+        assert_eq!(resend.execute("stranger").await.unwrap(), 0);
+
+        let remote = Arc::new(InMemoryKeyMaterial::default());
+        seed_keyslot(&remote, "remote", "pass", &old_key).await;
+        let remote_session = Arc::new(InMemoryEncryptionSession::new());
+        remote_session
+            .set_master_key(old_key.clone())
+            .await
+            .unwrap();
+        let (apply, confirm) =
+            build_apply_and_confirm(remote_session.clone(), remote, "device-local-peer");
+        let mut staged = Vec::new();
+        for (_, payload) in network.sent_clipboard() {
+            let message = match ProtocolMessage::from_bytes(&payload).unwrap() {
+                ProtocolMessage::KeySlotUpdate(message) => message,
+                other => panic!("unexpected message: {other:?}"),
+            };
+            staged.extend(apply.execute("device-local-peer", message).await.unwrap());
+        }
+        assert_eq!(staged.len(), 1, "only the copy sealed with epoch 0 applies");
+
+        confirm
+            .execute(Passphrase("pass".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(
+            remote_session.get_current_key().await.unwrap(),
+            session.get_current_key().await.unwrap()
+        );
+    }
+}
@@ -781,7 +781,7 @@ mod tests {
     use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::{Arc, Mutex as StdMutex};
     use tokio::time::{sleep, Duration, Instant};
-    use uc_core::network::{DiscoveredPeer, PairedDevice, PairingState};
+    use uc_core::network::DiscoveredPeer;
     use uc_core::ports::network_control::NetworkControlPort;
     use uc_core::ports::security::encryption::EncryptionPort;
     use uc_core::ports::security::encryption_session::EncryptionSessionPort;
@@ -790,10 +790,7 @@ mod tests {
     use uc_core::ports::security::key_scope::{KeyScopePort, ScopeError};
     use uc_core::ports::space::{CryptoPort, PersistencePort, ProofPort, SpaceAccessTransportPort};
     use uc_core::ports::watcher_control::{WatcherControlError, WatcherControlPort};
-    use uc_core::ports::{
-        DiscoveryPort, NetworkPort, PairedDeviceRepositoryError, PairedDeviceRepositoryPort,
-        SetupEventPort, TimerPort,
-    };
+    use uc_core::ports::{DiscoveryPort, NetworkPort, SetupEventPort, TimerPort};
     use uc_core::security::model::{
         EncryptedBlob, EncryptionAlgo, EncryptionError, EncryptionFormatVersion, KdfAlgorithm,
         KdfParams, KdfParamsV1, Kek, KeyScope, KeySlot, KeySlotFile, KeySlotVersion, MasterKey,
@@ -802,7 +799,7 @@ mod tests {
     use uc_core::security::space_access::SpaceAccessProofArtifact;
     use uc_core::security::state::{EncryptionState, EncryptionStateError};
     use uc_core::setup::{SetupError as SetupDomainError, SetupStatus};
-    use uc_core::PeerId;
+    use uc_core::testing::{InMemoryPairedDeviceRepository, TestNetwork};
 
     use crate::usecases::{
         AppLifecycleCoordinatorDeps, LifecycleEvent, LifecycleEventEmitter, LifecycleState,
@@ -978,6 +975,38 @@ mod tests {
         async fn clear(&self) -> Result<(), EncryptionError> {
             Ok(())
         }
+
+        async fn get_current_key(
+            &self,
+        ) -> Result<
+            (
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            ),
+            uc_core::security::model::EncryptionError,
+        > {
+            Ok((0, self.get_master_key().await?))
+        }
+
+        async fn get_master_key_for_epoch(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+        ) -> Result<uc_core::security::model::MasterKey, uc_core::security::model::EncryptionError>
+        {
+            self.get_master_key().await
+        }
+
+        async fn set_epoch_keys(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+            master_key: uc_core::security::model::MasterKey,
+            _older: Vec<(
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            )>,
+        ) -> Result<(), uc_core::security::model::EncryptionError> {
+            self.set_master_key(master_key).await
+        }
     }
 
     struct SucceedEncryption;
@@ -1005,6 +1034,7 @@ mod tests {
                 nonce: vec![0u8; 24],
                 ciphertext: vec![1u8; 32],
                 aad_fingerprint: None,
+                key_epoch: 0,
             })
         }
 
@@ -1029,6 +1059,7 @@ mod tests {
                 nonce: vec![0u8; 24],
                 ciphertext: vec![1u8; 32],
                 aad_fingerprint: None,
+                key_epoch: 0,
             })
         }
 
@@ -1114,6 +1145,38 @@ mod tests {
         async fn clear(&self) -> Result<(), EncryptionError> {
             Ok(())
         }
+
+        async fn get_current_key(
+            &self,
+        ) -> Result<
+            (
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            ),
+            uc_core::security::model::EncryptionError,
+        > {
+            Ok((0, self.get_master_key().await?))
+        }
+
+        async fn get_master_key_for_epoch(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+        ) -> Result<uc_core::security::model::MasterKey, uc_core::security::model::EncryptionError>
+        {
+            self.get_master_key().await
+        }
+
+        async fn set_epoch_keys(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+            master_key: uc_core::security::model::MasterKey,
+            _older: Vec<(
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            )>,
+        ) -> Result<(), uc_core::security::model::EncryptionError> {
+            self.set_master_key(master_key).await
+        }
     }
 
     // -- Lifecycle mocks -------------------------------------------------------
@@ -1171,46 +1234,6 @@ mod tests {
         }
     }
 
-    struct NoopPairedDeviceRepository;
-
-    #[async_trait]
-    impl PairedDeviceRepositoryPort for NoopPairedDeviceRepository {
-        async fn get_by_peer_id(
-            &self,
-            _peer_id: &PeerId,
-        ) -> Result<Option<PairedDevice>, PairedDeviceRepositoryError> {
-            Ok(None)
-        }
-
-        async fn list_all(&self) -> Result<Vec<PairedDevice>, PairedDeviceRepositoryError> {
-            Ok(Vec::new())
-        }
-
-        async fn upsert(&self, _device: PairedDevice) -> Result<(), PairedDeviceRepositoryError> {
-            Ok(())
-        }
-
-        async fn set_state(
-            &self,
-            _peer_id: &PeerId,
-            _state: PairingState,
-        ) -> Result<(), PairedDeviceRepositoryError> {
-            Ok(())
-        }
-
-        async fn update_last_seen(
-            &self,
-            _peer_id: &PeerId,
-            _last_seen_at: chrono::DateTime<chrono::Utc>,
-        ) -> Result<(), PairedDeviceRepositoryError> {
-            Ok(())
-        }
-
-        async fn delete(&self, _peer_id: &PeerId) -> Result<(), PairedDeviceRepositoryError> {
-            Ok(())
-        }
-    }
-
     struct NoopDiscoveryPort;
 
     #[async_trait]
@@ -1286,86 +1309,6 @@ mod tests {
         }
     }
 
-    struct NoopSpaceAccessNetworkPort;
-
-    #[async_trait]
-    impl NetworkPort for NoopSpaceAccessNetworkPort {
-        async fn send_clipboard(
-            &self,
-            _peer_id: &str,
-            _encrypted_data: Vec<u8>,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn broadcast_clipboard(&self, _encrypted_data: Vec<u8>) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn subscribe_clipboard(
-            &self,
-        ) -> anyhow::Result<tokio::sync::mpsc::Receiver<uc_core::network::ClipboardMessage>>
-        {
-            let (_tx, rx) = tokio::sync::mpsc::channel(1);
-            Ok(rx)
-        }
-
-        async fn get_discovered_peers(
-            &self,
-        ) -> anyhow::Result<Vec<uc_core::network::DiscoveredPeer>> {
-            Ok(vec![])
-        }
-
-        async fn get_connected_peers(
-            &self,
-        ) -> anyhow::Result<Vec<uc_core::network::ConnectedPeer>> {
-            Ok(vec![])
-        }
-
-        fn local_peer_id(&self) -> String {
-            "local".to_string()
-        }
-
-        async fn announce_device_name(&self, _device_name: String) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn open_pairing_session(
-            &self,
-            _peer_id: String,
-            _session_id: String,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn send_pairing_on_session(
-            &self,
-            _session_id: String,
-            _message: uc_core::network::PairingMessage,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn close_pairing_session(
-            &self,
-            _session_id: String,
-            _reason: Option<String>,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn unpair_device(&self, _peer_id: String) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn subscribe_events(
-            &self,
-        ) -> anyhow::Result<tokio::sync::mpsc::Receiver<uc_core::network::NetworkEvent>> {
-            let (_tx, rx) = tokio::sync::mpsc::channel(1);
-            Ok(rx)
-        }
-    }
-
     struct NoopSpaceAccessTransportPort;
 
     #[async_trait]
@@ -1435,6 +1378,10 @@ mod tests {
         async fn stop(&mut self, _session_id: &uc_core::SessionId) -> anyhow::Result<()> {
             Ok(())
         }
+
+        async fn sleep(&mut self, _duration: std::time::Duration) -> anyhow::Result<()> {
+            Ok(())
+        }
     }
 
     struct NoopSpaceAccessPersistencePort;
@@ -1491,7 +1438,7 @@ mod tests {
     }
 
     fn build_pairing_orchestrator() -> Arc<PairingOrchestrator> {
-        let repo = Arc::new(NoopPairedDeviceRepository);
+        let repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _rx) = PairingOrchestrator::new(
             PairingConfig::default(),
             repo,
@@ -1509,7 +1456,7 @@ mod tests {
             tokio::sync::mpsc::Receiver<uc_core::network::pairing_state_machine::PairingAction>,
         >,
     ) {
-        let repo = Arc::new(NoopPairedDeviceRepository);
+        let repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, rx) = PairingOrchestrator::new(
             PairingConfig::default(),
             repo,
@@ -1542,7 +1489,7 @@ mod tests {
     }
 
     fn build_network_port() -> Arc<dyn NetworkPort> {
-        Arc::new(NoopSpaceAccessNetworkPort)
+        Arc::new(TestNetwork::new())
     }
 
     fn build_transport_port() -> Arc<Mutex<dyn SpaceAccessTransportPort>> {
@@ -1621,12 +1568,17 @@ mod tests {
                 },
             },
             salt: vec![1, 2, 3, 4],
+            epoch: 0,
+            revision: 0,
+            retired_master_key: None,
+            retained_master_keys: Vec::new(),
             wrapped_master_key: EncryptedBlob {
                 version: EncryptionFormatVersion::V1,
                 aead: EncryptionAlgo::XChaCha20Poly1305,
                 nonce: vec![9; 24],
                 ciphertext: vec![7; 32],
                 aad_fingerprint: None,
+                key_epoch: 0,
             },
             created_at: None,
             updated_at: None,
@@ -1794,6 +1746,7 @@ mod tests {
                     device_id: "remote-device-id".to_string(),
                     identity_pubkey: vec![9; 32],
                     nonce: vec![2; 32],
+                    invitation_proof: None,
                 },
             )
             .await
@@ -1879,6 +1832,7 @@ mod tests {
                     device_id: "remote-device-id".to_string(),
                     identity_pubkey: vec![9; 32],
                     nonce: vec![2; 32],
+                    invitation_proof: None,
                 },
             )
             .await
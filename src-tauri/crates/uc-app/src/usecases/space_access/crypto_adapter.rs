@@ -250,9 +250,10 @@ impl CryptoPort for SpaceAccessCryptoAdapter {
             };
             debug!("Master key unwrapped");
 
+            // A joining device has no history sealed under a retired key.
             if let Err(e) = self
                 .encryption_session
-                .set_master_key(master_key.clone())
+                .set_epoch_keys(keyslot.epoch, master_key.clone(), Vec::new())
                 .await
             {
                 error!(error = %e, "set_master_key failed");
@@ -342,6 +343,7 @@ mod tests {
                 nonce: vec![0u8; 24],
                 ciphertext: vec![1u8; 32],
                 aad_fingerprint: None,
+                key_epoch: 0,
             })
         }
 
@@ -558,6 +560,38 @@ mod tests {
             guard.clear_called = true;
             Ok(())
         }
+
+        async fn get_current_key(
+            &self,
+        ) -> Result<
+            (
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            ),
+            uc_core::security::model::EncryptionError,
+        > {
+            Ok((0, self.get_master_key().await?))
+        }
+
+        async fn get_master_key_for_epoch(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+        ) -> Result<uc_core::security::model::MasterKey, uc_core::security::model::EncryptionError>
+        {
+            self.get_master_key().await
+        }
+
+        async fn set_epoch_keys(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+            master_key: uc_core::security::model::MasterKey,
+            _older: Vec<(
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            )>,
+        ) -> Result<(), uc_core::security::model::EncryptionError> {
+            self.set_master_key(master_key).await
+        }
     }
 
     #[tokio::test]
@@ -603,6 +637,7 @@ mod tests {
                 nonce: vec![0u8; 24],
                 ciphertext: vec![1u8; 32],
                 aad_fingerprint: None,
+                key_epoch: 0,
             },
         });
         let keyslot_blob = serde_json::to_vec(&keyslot).expect("serialize keyslot");
@@ -672,6 +707,7 @@ mod tests {
                 nonce: vec![0u8; 24],
                 ciphertext: vec![2u8; 32],
                 aad_fingerprint: None,
+                key_epoch: 0,
             },
         });
         let keyslot_blob = serde_json::to_vec(&keyslot).expect("serialize keyslot");
@@ -95,6 +95,7 @@ mod tests {
     };
     use uc_core::security::space_access::SpaceAccessProofArtifact;
     use uc_core::security::{MasterKey, SecretString};
+    use uc_core::testing::TestNetwork;
 
     struct MockCryptoPort {
         exported: Arc<AtomicBool>,
@@ -134,6 +135,7 @@ mod tests {
                     nonce: vec![0u8; 24],
                     ciphertext: vec![1u8; 32],
                     aad_fingerprint: None,
+                    key_epoch: 0,
                 },
             }))
         }
@@ -147,86 +149,6 @@ mod tests {
         }
     }
 
-    struct MockNetworkPort;
-
-    #[async_trait]
-    impl NetworkPort for MockNetworkPort {
-        async fn send_clipboard(
-            &self,
-            _peer_id: &str,
-            _encrypted_data: Vec<u8>,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn broadcast_clipboard(&self, _encrypted_data: Vec<u8>) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn subscribe_clipboard(
-            &self,
-        ) -> anyhow::Result<tokio::sync::mpsc::Receiver<uc_core::network::ClipboardMessage>>
-        {
-            let (_tx, rx) = tokio::sync::mpsc::channel(1);
-            Ok(rx)
-        }
-
-        async fn get_discovered_peers(
-            &self,
-        ) -> anyhow::Result<Vec<uc_core::network::DiscoveredPeer>> {
-            Ok(vec![])
-        }
-
-        async fn get_connected_peers(
-            &self,
-        ) -> anyhow::Result<Vec<uc_core::network::ConnectedPeer>> {
-            Ok(vec![])
-        }
-
-        fn local_peer_id(&self) -> String {
-            "local".to_string()
-        }
-
-        async fn announce_device_name(&self, _device_name: String) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn open_pairing_session(
-            &self,
-            _peer_id: String,
-            _session_id: String,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn send_pairing_on_session(
-            &self,
-            _session_id: String,
-            _message: uc_core::network::PairingMessage,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn close_pairing_session(
-            &self,
-            _session_id: String,
-            _reason: Option<String>,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn unpair_device(&self, _peer_id: String) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn subscribe_events(
-            &self,
-        ) -> anyhow::Result<tokio::sync::mpsc::Receiver<uc_core::network::NetworkEvent>> {
-            let (_tx, rx) = tokio::sync::mpsc::channel(1);
-            Ok(rx)
-        }
-    }
-
     struct MockTimerPort;
 
     #[async_trait]
@@ -242,6 +164,10 @@ mod tests {
         async fn stop(&mut self, _session_id: &CoreSessionId) -> anyhow::Result<()> {
             Ok(())
         }
+
+        async fn sleep(&mut self, _duration: std::time::Duration) -> anyhow::Result<()> {
+            Ok(())
+        }
     }
 
     struct MockStorePort;
@@ -267,7 +193,7 @@ mod tests {
         let crypto_factory = Arc::new(MockCryptoFactory {
             exported: exported.clone(),
         });
-        let network = Arc::new(MockNetworkPort);
+        let network = Arc::new(TestNetwork::new());
         let transport = Arc::new(Mutex::new(MockTransportPort));
         let proof = Arc::new(MockProofPort);
         let timer = Arc::new(Mutex::new(MockTimerPort));
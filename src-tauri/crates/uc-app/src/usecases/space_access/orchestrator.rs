@@ -419,15 +419,11 @@ mod tests {
     use super::*;
     use async_trait::async_trait;
     use chrono::{Duration, Utc};
-    use tokio::sync::mpsc;
     use tokio::time::{timeout, Duration as TokioDuration};
     use uc_core::ids::{SessionId as CoreSessionId, SpaceId};
     use uc_core::network::SessionId as NetSessionId;
-    use uc_core::network::{
-        ClipboardMessage, ConnectedPeer, DiscoveredPeer, NetworkEvent, PairingMessage,
-    };
     use uc_core::ports::space::{CryptoPort, PersistencePort, ProofPort, SpaceAccessTransportPort};
-    use uc_core::ports::{NetworkPort, TimerPort};
+    use uc_core::ports::TimerPort;
     use uc_core::security::model::{
         EncryptedBlob, EncryptionAlgo, EncryptionFormatVersion, KdfParams, KeyScope, KeySlot,
         KeySlotVersion, MasterKey, WrappedMasterKey,
@@ -436,6 +432,7 @@ mod tests {
     use uc_core::security::space_access::event::SpaceAccessEvent;
     use uc_core::security::space_access::state::{DenyReason, SpaceAccessState};
     use uc_core::security::SecretString;
+    use uc_core::testing::TestNetwork;
 
     use crate::usecases::space_access::SpaceAccessEventPort;
     use crate::usecases::space_access::SpaceAccessJoinerOffer;
@@ -491,7 +488,7 @@ mod tests {
     #[derive(Default)]
     struct AccessTestHarness {
         crypto: MockCrypto,
-        net: NoopNetwork,
+        net: TestNetwork,
         transport: MockTransport,
         proof: MockProof,
         timer: MockTimer,
@@ -917,6 +914,10 @@ mod tests {
             },
             kdf: KdfParams::for_initialization(),
             salt: vec![0u8; 16],
+            epoch: 0,
+            revision: 0,
+            retired_master_key: None,
+            retained_master_keys: Vec::new(),
             wrapped_master_key: Some(WrappedMasterKey {
                 blob: EncryptedBlob {
                     version: EncryptionFormatVersion::V1,
@@ -924,6 +925,7 @@ mod tests {
                     nonce: vec![0u8; 24],
                     ciphertext: vec![0u8; 32],
                     aad_fingerprint: None,
+                    key_epoch: 0,
                 },
             }),
         }
@@ -1014,6 +1016,10 @@ mod tests {
             self.stop_calls.push(session_id.to_string());
             Ok(())
         }
+
+        async fn sleep(&mut self, _duration: std::time::Duration) -> anyhow::Result<()> {
+            Ok(())
+        }
     }
 
     #[derive(Default)]
@@ -1039,76 +1045,4 @@ mod tests {
             Ok(())
         }
     }
-
-    #[derive(Default)]
-    struct NoopNetwork;
-
-    #[async_trait]
-    impl NetworkPort for NoopNetwork {
-        async fn send_clipboard(
-            &self,
-            _peer_id: &str,
-            _encrypted_data: Vec<u8>,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn broadcast_clipboard(&self, _encrypted_data: Vec<u8>) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn subscribe_clipboard(&self) -> anyhow::Result<mpsc::Receiver<ClipboardMessage>> {
-            let (_tx, rx) = mpsc::channel(1);
-            Ok(rx)
-        }
-
-        async fn get_discovered_peers(&self) -> anyhow::Result<Vec<DiscoveredPeer>> {
-            Ok(Vec::new())
-        }
-
-        async fn get_connected_peers(&self) -> anyhow::Result<Vec<ConnectedPeer>> {
-            Ok(Vec::new())
-        }
-
-        fn local_peer_id(&self) -> String {
-            "local-peer".into()
-        }
-
-        async fn announce_device_name(&self, _device_name: String) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn open_pairing_session(
-            &self,
-            _peer_id: String,
-            _session_id: String,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn send_pairing_on_session(
-            &self,
-            _session_id: String,
-            _message: PairingMessage,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn close_pairing_session(
-            &self,
-            _session_id: String,
-            _reason: Option<String>,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn unpair_device(&self, _peer_id: String) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn subscribe_events(&self) -> anyhow::Result<mpsc::Receiver<NetworkEvent>> {
-            let (_tx, rx) = mpsc::channel(1);
-            Ok(rx)
-        }
-    }
 }
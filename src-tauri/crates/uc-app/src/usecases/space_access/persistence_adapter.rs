@@ -54,14 +54,16 @@ impl PersistencePort for SpaceAccessPersistenceAdapter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
     use std::sync::Arc;
 
     use chrono::Utc;
-    use tokio::sync::Mutex;
     use uc_core::network::PairedDevice;
-    use uc_core::ports::errors::PairedDeviceRepositoryError;
     use uc_core::security::state::{EncryptionState, EncryptionStateError};
+    use uc_core::testing::InMemoryPairedDeviceRepository;
+
+    fn state_of(repo: &InMemoryPairedDeviceRepository, peer_id: &str) -> Option<PairingState> {
+        repo.device(peer_id).map(|device| device.pairing_state)
+    }
 
     struct MockEncryptionState;
 
@@ -76,71 +78,11 @@ mod tests {
         }
     }
 
-    #[derive(Default)]
-    struct MockPairedDeviceRepo {
-        devices: Mutex<HashMap<String, PairedDevice>>,
-    }
-
-    impl MockPairedDeviceRepo {
-        async fn state_of(&self, peer_id: &str) -> Option<PairingState> {
-            self.devices
-                .lock()
-                .await
-                .get(peer_id)
-                .map(|device| device.pairing_state.clone())
-        }
-    }
-
-    #[async_trait]
-    impl PairedDeviceRepositoryPort for MockPairedDeviceRepo {
-        async fn get_by_peer_id(
-            &self,
-            peer_id: &PeerId,
-        ) -> Result<Option<PairedDevice>, PairedDeviceRepositoryError> {
-            Ok(self.devices.lock().await.get(peer_id.as_str()).cloned())
-        }
-
-        async fn list_all(&self) -> Result<Vec<PairedDevice>, PairedDeviceRepositoryError> {
-            Ok(self.devices.lock().await.values().cloned().collect())
-        }
-
-        async fn upsert(&self, device: PairedDevice) -> Result<(), PairedDeviceRepositoryError> {
-            self.devices
-                .lock()
-                .await
-                .insert(device.peer_id.to_string(), device);
-            Ok(())
-        }
-
-        async fn set_state(
-            &self,
-            peer_id: &PeerId,
-            state: PairingState,
-        ) -> Result<(), PairedDeviceRepositoryError> {
-            if let Some(existing) = self.devices.lock().await.get_mut(peer_id.as_str()) {
-                existing.pairing_state = state;
-            }
-            Ok(())
-        }
-
-        async fn update_last_seen(
-            &self,
-            _peer_id: &PeerId,
-            _last_seen_at: chrono::DateTime<chrono::Utc>,
-        ) -> Result<(), PairedDeviceRepositoryError> {
-            Ok(())
-        }
-
-        async fn delete(&self, _peer_id: &PeerId) -> Result<(), PairedDeviceRepositoryError> {
-            Ok(())
-        }
-    }
-
     #[tokio::test]
     async fn pairing_deferred_persistence_promotes_to_trusted_on_proof_verified() {
         staged_paired_device_store::clear();
         let peer_id = PeerId::from("peer-1");
-        let repo = Arc::new(MockPairedDeviceRepo::default());
+        let repo = Arc::new(InMemoryPairedDeviceRepository::new());
 
         repo.upsert(PairedDevice {
             peer_id: peer_id.clone(),
@@ -149,6 +91,7 @@ mod tests {
             paired_at: Utc::now(),
             last_seen_at: None,
             device_name: "Peer Device".to_string(),
+            device_id: "device-peer".to_string(),
         })
         .await
         .expect("seed pending paired device");
@@ -157,7 +100,7 @@ mod tests {
             SpaceAccessPersistenceAdapter::new(Arc::new(MockEncryptionState), repo.clone());
 
         assert_eq!(
-            repo.state_of(peer_id.as_str()).await,
+            state_of(&repo, peer_id.as_str()),
             Some(PairingState::Pending)
         );
 
@@ -167,7 +110,7 @@ mod tests {
             .expect("persist sponsor access");
 
         assert_eq!(
-            repo.state_of(peer_id.as_str()).await,
+            state_of(&repo, peer_id.as_str()),
             Some(PairingState::Trusted)
         );
     }
@@ -185,10 +128,11 @@ mod tests {
                 paired_at: Utc::now(),
                 last_seen_at: None,
                 device_name: "Staged Device".to_string(),
+                device_id: "device-peer".to_string(),
             },
         );
 
-        let repo = Arc::new(MockPairedDeviceRepo::default());
+        let repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let mut adapter =
             SpaceAccessPersistenceAdapter::new(Arc::new(MockEncryptionState), repo.clone());
 
@@ -198,7 +142,7 @@ mod tests {
             .expect("persist sponsor access");
 
         assert_eq!(
-            repo.state_of(peer_id.as_str()).await,
+            state_of(&repo, peer_id.as_str()),
             Some(PairingState::Trusted)
         );
     }
@@ -203,6 +203,38 @@ mod tests {
             *self.master_key.lock().await = None;
             Ok(())
         }
+
+        async fn get_current_key(
+            &self,
+        ) -> Result<
+            (
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            ),
+            uc_core::security::model::EncryptionError,
+        > {
+            Ok((0, self.get_master_key().await?))
+        }
+
+        async fn get_master_key_for_epoch(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+        ) -> Result<uc_core::security::model::MasterKey, uc_core::security::model::EncryptionError>
+        {
+            self.get_master_key().await
+        }
+
+        async fn set_epoch_keys(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+            master_key: uc_core::security::model::MasterKey,
+            _older: Vec<(
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            )>,
+        ) -> Result<(), uc_core::security::model::EncryptionError> {
+            self.set_master_key(master_key).await
+        }
     }
 
     #[tokio::test]
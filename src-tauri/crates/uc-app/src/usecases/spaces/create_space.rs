@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use tracing::{info, info_span, warn, Instrument};
+
+use uc_core::ids::SpaceId;
+use uc_core::ports::clipboard::ClipboardSearchIndexPort;
+use uc_core::ports::space::ActiveSpacePort;
+use uc_core::ports::{ClockPort, EncryptionSessionPort, SettingsPort};
+use uc_core::security::model::Passphrase;
+use uc_core::space::SpaceProfile;
+
+use super::SpaceError;
+use crate::usecases::InitializeEncryption;
+
+/// Create a space with its own passphrase and make it the active one.
+///
+/// 新建空间：生成 `SpaceId`，在该空间的密钥范围内创建 keyslot 与 KEK，
+/// 然后将其设为活动空间。The new space starts with no paired devices and an
+/// empty history partition.
+pub struct CreateSpace {
+    settings: Arc<dyn SettingsPort>,
+    active_space: Arc<dyn ActiveSpacePort>,
+    encryption_session: Arc<dyn EncryptionSessionPort>,
+    search_index: Arc<dyn ClipboardSearchIndexPort>,
+    initialize_encryption: Arc<InitializeEncryption>,
+    clock: Arc<dyn ClockPort>,
+}
+
+impl CreateSpace {
+    pub fn new(
+        settings: Arc<dyn SettingsPort>,
+        active_space: Arc<dyn ActiveSpacePort>,
+        encryption_session: Arc<dyn EncryptionSessionPort>,
+        search_index: Arc<dyn ClipboardSearchIndexPort>,
+        initialize_encryption: Arc<InitializeEncryption>,
+        clock: Arc<dyn ClockPort>,
+    ) -> Self {
+        Self {
+            settings,
+            active_space,
+            encryption_session,
+            search_index,
+            initialize_encryption,
+            clock,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        name: String,
+        passphrase: Passphrase,
+    ) -> Result<SpaceProfile, SpaceError> {
+        let span = info_span!("usecase.create_space.execute");
+
+        async {
+            let name = name.trim().to_string();
+            if name.is_empty() {
+                return Err(SpaceError::InvalidName);
+            }
+            let mut settings = self
+                .settings
+                .load()
+                .await
+                .map_err(|e| SpaceError::Settings(e.to_string()))?;
+
+            let profile = SpaceProfile {
+                id: SpaceId::new(),
+                name,
+                created_at_ms: self.clock.now_ms(),
+            };
+            let previous = self.active_space.active_space();
+
+            // Key material is created in the scope of the active space
+            self.active_space.set_active_space(profile.id.clone());
+            if let Err(err) = self
+                .initialize_encryption
+                .execute_for_new_space(passphrase)
+                .await
+            {
+                self.active_space.set_active_space(previous);
+                return Err(err.into());
+            }
+
+            settings.spaces.spaces.push(profile.clone());
+            settings.spaces.active_space_id = profile.id.clone();
+            if let Err(err) = self.settings.save(&settings).await {
+                // The session already holds the new space's key; lock it
+                // rather than let it seal data of the previous space.
+                self.active_space.set_active_space(previous);
+                if let Err(clear_err) = self.encryption_session.clear().await {
+                    warn!(error = %clear_err, "Failed to clear encryption session");
+                }
+                return Err(SpaceError::Settings(err.to_string()));
+            }
+
+            if let Err(err) = self.search_index.close().await {
+                warn!(error = %err, "Failed to close search index after creating space");
+            }
+
+            info!(space_id = %profile.id, previous = %previous, "Created and switched to new space");
+            Ok(profile)
+        }
+        .instrument(span)
+        .await
+    }
+}
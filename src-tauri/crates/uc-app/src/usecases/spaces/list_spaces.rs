@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+use tracing::{info_span, Instrument};
+
+use uc_core::ids::SpaceId;
+use uc_core::ports::SettingsPort;
+
+use super::SpaceError;
+
+/// A space as shown in the space selector
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SpaceSummary {
+    pub id: SpaceId,
+    pub name: String,
+    pub created_at_ms: i64,
+    pub is_active: bool,
+}
+
+/// 列出本机已知的空间，并标记活动空间。
+pub struct ListSpaces {
+    settings: Arc<dyn SettingsPort>,
+}
+
+impl ListSpaces {
+    pub fn new(settings: Arc<dyn SettingsPort>) -> Self {
+        Self { settings }
+    }
+
+    pub async fn execute(&self) -> Result<Vec<SpaceSummary>, SpaceError> {
+        async {
+            let settings = self
+                .settings
+                .load()
+                .await
+                .map_err(|e| SpaceError::Settings(e.to_string()))?;
+            let active = &settings.spaces.active_space_id;
+            Ok(settings
+                .spaces
+                .spaces
+                .iter()
+                .map(|space| SpaceSummary {
+                    id: space.id.clone(),
+                    name: space.name.clone(),
+                    created_at_ms: space.created_at_ms,
+                    is_active: &space.id == active,
+                })
+                .collect())
+        }
+        .instrument(info_span!("usecase.list_spaces.execute"))
+        .await
+    }
+}
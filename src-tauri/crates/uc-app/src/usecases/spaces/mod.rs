@@ -0,0 +1,31 @@
+//! Space management use cases
+//! 空间管理用例
+//!
+//! Switching the active space moves the key scope, the paired device set and
+//! the history partition together, so these use cases also reset the
+//! encryption session and the search index.
+
+pub mod create_space;
+pub mod list_spaces;
+pub mod switch_space;
+
+pub use create_space::CreateSpace;
+pub use list_spaces::{ListSpaces, SpaceSummary};
+pub use switch_space::{SwitchSpace, SwitchSpaceOutcome};
+
+use crate::usecases::initialize_encryption::InitializeEncryptionError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SpaceError {
+    #[error("space name must not be empty")]
+    InvalidName,
+
+    #[error("unknown space: {0}")]
+    UnknownSpace(String),
+
+    #[error("failed to access space settings: {0}")]
+    Settings(String),
+
+    #[error("failed to initialize space encryption: {0}")]
+    Encryption(#[from] InitializeEncryptionError),
+}
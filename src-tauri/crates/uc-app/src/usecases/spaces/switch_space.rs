@@ -0,0 +1,263 @@
+use std::sync::Arc;
+
+use tracing::{info, info_span, warn, Instrument};
+
+use uc_core::ids::SpaceId;
+use uc_core::ports::clipboard::ClipboardSearchIndexPort;
+use uc_core::ports::space::ActiveSpacePort;
+use uc_core::ports::{EncryptionSessionPort, SettingsPort};
+
+use super::SpaceError;
+use crate::usecases::AutoUnlockEncryptionSession;
+
+/// Result of a space switch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwitchSpaceOutcome {
+    /// Whether the new space's master key is loaded; otherwise the session
+    /// stays locked until the user unlocks it.
+    pub unlocked: bool,
+}
+
+/// Make another known space the active one.
+///
+/// 切换活动空间：更新密钥范围、持久化设置、清空当前主密钥会话并关闭搜索索引
+/// （下次搜索时从新空间的历史重建）。Auto-unlock runs afterwards when enabled
+/// in settings and attached via [`SwitchSpace::with_auto_unlock`].
+pub struct SwitchSpace {
+    settings: Arc<dyn SettingsPort>,
+    active_space: Arc<dyn ActiveSpacePort>,
+    encryption_session: Arc<dyn EncryptionSessionPort>,
+    search_index: Arc<dyn ClipboardSearchIndexPort>,
+    auto_unlock: Option<Arc<AutoUnlockEncryptionSession>>,
+}
+
+impl SwitchSpace {
+    pub fn new(
+        settings: Arc<dyn SettingsPort>,
+        active_space: Arc<dyn ActiveSpacePort>,
+        encryption_session: Arc<dyn EncryptionSessionPort>,
+        search_index: Arc<dyn ClipboardSearchIndexPort>,
+    ) -> Self {
+        Self {
+            settings,
+            active_space,
+            encryption_session,
+            search_index,
+            auto_unlock: None,
+        }
+    }
+
+    pub fn with_auto_unlock(mut self, auto_unlock: Arc<AutoUnlockEncryptionSession>) -> Self {
+        self.auto_unlock = Some(auto_unlock);
+        self
+    }
+
+    pub async fn execute(&self, space_id: SpaceId) -> Result<SwitchSpaceOutcome, SpaceError> {
+        let span = info_span!("usecase.switch_space.execute", space_id = %space_id);
+
+        async {
+            let mut settings = self
+                .settings
+                .load()
+                .await
+                .map_err(|e| SpaceError::Settings(e.to_string()))?;
+            if settings.spaces.find(&space_id).is_none() {
+                return Err(SpaceError::UnknownSpace(space_id.to_string()));
+            }
+
+            let previous = self.active_space.active_space();
+            if previous == space_id {
+                return Ok(SwitchSpaceOutcome {
+                    unlocked: self.encryption_session.is_ready().await,
+                });
+            }
+
+            settings.spaces.active_space_id = space_id.clone();
+            if let Err(err) = self.settings.save(&settings).await {
+                return Err(SpaceError::Settings(err.to_string()));
+            }
+            self.active_space.set_active_space(space_id.clone());
+
+            // The old master key must not seal anything in the new space
+            if let Err(err) = self.encryption_session.clear().await {
+                warn!(error = %err, "Failed to clear encryption session on space switch");
+            }
+            if let Err(err) = self.search_index.close().await {
+                warn!(error = %err, "Failed to close search index on space switch");
+            }
+
+            let unlocked = match &self.auto_unlock {
+                Some(auto_unlock) if settings.security.auto_unlock_enabled => {
+                    match auto_unlock.execute().await {
+                        Ok(unlocked) => unlocked,
+                        Err(err) => {
+                            warn!(error = %err, "Auto unlock after space switch failed");
+                            false
+                        }
+                    }
+                }
+                _ => false,
+            };
+
+            info!(previous = %previous, unlocked, "Switched active space");
+            Ok(SwitchSpaceOutcome { unlocked })
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+    use uc_core::security::model::{EncryptionError, MasterKey};
+    use uc_core::settings::model::Settings;
+    use uc_core::space::SpaceProfile;
+    use uc_infra::clipboard::InMemoryClipboardSearchIndex;
+    use uc_infra::space::InMemoryActiveSpace;
+
+    struct MockSettings {
+        settings: Mutex<Settings>,
+    }
+
+    #[async_trait]
+    impl SettingsPort for MockSettings {
+        async fn load(&self) -> Result<Settings> {
+            Ok(self.settings.lock().unwrap().clone())
+        }
+
+        async fn save(&self, settings: &Settings) -> Result<()> {
+            *self.settings.lock().unwrap() = settings.clone();
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockSession {
+        key: Mutex<Option<MasterKey>>,
+    }
+
+    #[async_trait]
+    impl EncryptionSessionPort for MockSession {
+        async fn is_ready(&self) -> bool {
+            self.key.lock().unwrap().is_some()
+        }
+
+        async fn get_master_key(&self) -> Result<MasterKey, EncryptionError> {
+            self.key
+                .lock()
+                .unwrap()
+                .clone()
+                .ok_or(EncryptionError::Locked)
+        }
+
+        async fn set_master_key(&self, master_key: MasterKey) -> Result<(), EncryptionError> {
+            *self.key.lock().unwrap() = Some(master_key);
+            Ok(())
+        }
+
+        async fn clear(&self) -> Result<(), EncryptionError> {
+            *self.key.lock().unwrap() = None;
+            Ok(())
+        }
+
+        async fn get_current_key(
+            &self,
+        ) -> Result<
+            (
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            ),
+            uc_core::security::model::EncryptionError,
+        > {
+            Ok((0, self.get_master_key().await?))
+        }
+
+        async fn get_master_key_for_epoch(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+        ) -> Result<uc_core::security::model::MasterKey, uc_core::security::model::EncryptionError>
+        {
+            self.get_master_key().await
+        }
+
+        async fn set_epoch_keys(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+            master_key: uc_core::security::model::MasterKey,
+            _older: Vec<(
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            )>,
+        ) -> Result<(), uc_core::security::model::EncryptionError> {
+            self.set_master_key(master_key).await
+        }
+    }
+
+    fn settings_with_work_space() -> Settings {
+        let mut settings = Settings::default();
+        settings.spaces.spaces.push(SpaceProfile {
+            id: SpaceId::from("work"),
+            name: "Work".to_string(),
+            created_at_ms: 1,
+        });
+        settings
+    }
+
+    #[tokio::test]
+    async fn switch_moves_active_space_and_locks_session() {
+        let settings = Arc::new(MockSettings {
+            settings: Mutex::new(settings_with_work_space()),
+        });
+        let active_space = Arc::new(InMemoryActiveSpace::default());
+        let session = Arc::new(MockSession::default());
+        session
+            .set_master_key(MasterKey::generate().unwrap())
+            .await
+            .unwrap();
+        let search_index = Arc::new(InMemoryClipboardSearchIndex::new());
+        search_index.open().await.unwrap();
+        let usecase = SwitchSpace::new(
+            settings.clone(),
+            active_space.clone(),
+            session.clone(),
+            search_index.clone(),
+        );
+
+        let outcome = usecase.execute(SpaceId::from("work")).await.unwrap();
+
+        assert!(!outcome.unlocked);
+        assert_eq!(active_space.active_space(), SpaceId::from("work"));
+        assert_eq!(
+            settings.settings.lock().unwrap().spaces.active_space_id,
+            SpaceId::from("work")
+        );
+        assert!(!session.is_ready().await);
+        assert!(!search_index.is_open().await);
+    }
+
+    #[tokio::test]
+    async fn switch_rejects_unknown_space() {
+        let settings = Arc::new(MockSettings {
+            settings: Mutex::new(Settings::default()),
+        });
+        let active_space = Arc::new(InMemoryActiveSpace::default());
+        let usecase = SwitchSpace::new(
+            settings,
+            active_space.clone(),
+            Arc::new(MockSession::default()),
+            Arc::new(InMemoryClipboardSearchIndex::new()),
+        );
+
+        let err = usecase
+            .execute(SpaceId::from("missing"))
+            .await
+            .expect_err("unknown space");
+
+        assert!(matches!(err, SpaceError::UnknownSpace(_)));
+        assert!(active_space.active_space().is_default());
+    }
+}
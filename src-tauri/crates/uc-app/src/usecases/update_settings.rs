@@ -5,7 +5,8 @@ use anyhow::Result;
 use tracing::{info, info_span, Instrument};
 use uc_core::ports::SettingsPort;
 use uc_core::settings::model::{
-    ContentTypes, GeneralSettings, RetentionPolicy, SecuritySettings, Settings, SyncSettings,
+    ContentTypes, GeneralSettings, NetworkSettings, RetentionPolicy, SecuritySettings, Settings,
+    SyncSettings,
 };
 
 /// Use case for updating application settings.
@@ -13,6 +14,9 @@ use uc_core::settings::model::{
 /// ## Behavior / 行为
 /// - Loads current settings for comparison
 /// - Validates settings (basic validation)
+/// - Keeps the stored space list and active space; those change only through
+///   the space use cases
+/// - Rejects new routing rules naming a space other than the active one
 /// - Logs changed fields with old/new values
 /// - Persists settings through the settings port
 ///
@@ -37,13 +41,18 @@ impl UpdateSettings {
     /// # Returns / 返回值
     /// - `Ok(())` if settings are saved successfully
     /// - `Err(e)` if validation or save fails
-    pub async fn execute(&self, settings: Settings) -> Result<()> {
+    pub async fn execute(&self, mut settings: Settings) -> Result<()> {
         let span = info_span!("usecase.update_settings.execute");
 
         async {
             // Load current settings for diffing
             let old_settings = self.settings.load().await?;
 
+            // Spaces are created and switched by their own use cases, which also
+            // move the key scope; a stale settings form must not undo that.
+            settings.spaces.active_space_id = old_settings.spaces.active_space_id.clone();
+            settings.spaces.spaces = old_settings.spaces.spaces.clone();
+
             // Calculate and log changes
             let changes = SettingsDiff::diff(&old_settings, &settings);
             if !changes.is_empty() {
@@ -55,6 +64,18 @@ impl UpdateSettings {
                 info!("Updating application settings (no changes detected)");
             }
 
+            // Captures can only be sealed for the active space; a rule naming another
+            // space would route them nowhere.
+            if let Some(rule) = settings
+                .spaces
+                .new_rule_for_inactive_space(&old_settings.spaces)
+            {
+                return Err(anyhow::anyhow!(
+                    "Routing rule names inactive space {}; captures are recorded in the active space only",
+                    rule.space_id
+                ));
+            }
+
             // Basic validation: ensure schema version is current
             let current_version = uc_core::settings::model::CURRENT_SCHEMA_VERSION;
             if settings.schema_version != current_version {
@@ -85,6 +106,9 @@ struct SettingsDiff {
     sync: Option<SyncSettingsDiff>,
     retention_policy: Option<RetentionPolicyDiff>,
     security: Option<SecuritySettingsDiff>,
+    network: Option<NetworkSettingsDiff>,
+    /// Routing rule counts only; rule text may be sensitive.
+    space_routing_rules: Option<(usize, usize)>,
 }
 
 impl SettingsDiff {
@@ -98,6 +122,13 @@ impl SettingsDiff {
                 &new.retention_policy,
             ),
             security: SecuritySettingsDiff::diff(&old.security, &new.security),
+            network: NetworkSettingsDiff::diff(&old.network, &new.network),
+            space_routing_rules: (old.spaces.routing_rules != new.spaces.routing_rules).then_some(
+                (
+                    old.spaces.routing_rules.len(),
+                    new.spaces.routing_rules.len(),
+                ),
+            ),
         }
     }
 
@@ -107,6 +138,8 @@ impl SettingsDiff {
             && self.sync.is_none()
             && self.retention_policy.is_none()
             && self.security.is_none()
+            && self.network.is_none()
+            && self.space_routing_rules.is_none()
     }
 
     /// Convert to a structured log string
@@ -125,6 +158,15 @@ impl SettingsDiff {
         if let Some(ref diff) = self.security {
             parts.push(diff.to_log_string("security"));
         }
+        if let Some(ref diff) = self.network {
+            parts.push(diff.to_log_string("network"));
+        }
+        if let Some((old, new)) = self.space_routing_rules {
+            parts.push(format!(
+                "spaces.routing_rules: {} entries → {} entries",
+                old, new
+            ));
+        }
 
         if parts.is_empty() {
             "(no changes)".to_string()
@@ -215,6 +257,9 @@ impl GeneralSettingsDiff {
 struct SyncSettingsDiff {
     auto_sync: Option<(bool, bool)>,
     sync_frequency: Option<(String, String)>,
+    sync_interval_secs: Option<(u64, u64)>,
+    interval_batch_mode: Option<(String, String)>,
+    catch_up_window_secs: Option<(u64, u64)>,
     max_file_size_mb: Option<(u32, u32)>,
     content_types: Option<(ContentTypes, ContentTypes)>,
 }
@@ -226,6 +271,14 @@ impl SyncSettingsDiff {
             format!("{:?}", old.sync_frequency),
             format!("{:?}", new.sync_frequency),
         ));
+        let sync_interval_secs = (old.sync_interval_secs != new.sync_interval_secs)
+            .then_some((old.sync_interval_secs, new.sync_interval_secs));
+        let interval_batch_mode = (old.interval_batch_mode != new.interval_batch_mode).then_some((
+            format!("{:?}", old.interval_batch_mode),
+            format!("{:?}", new.interval_batch_mode),
+        ));
+        let catch_up_window_secs = (old.catch_up_window_secs != new.catch_up_window_secs)
+            .then_some((old.catch_up_window_secs, new.catch_up_window_secs));
         let max_file_size_mb = (old.max_file_size_mb != new.max_file_size_mb)
             .then_some((old.max_file_size_mb, new.max_file_size_mb));
         let content_types_changed = old.content_types.text != new.content_types.text
@@ -239,6 +292,9 @@ impl SyncSettingsDiff {
 
         if auto_sync.is_none()
             && sync_frequency.is_none()
+            && sync_interval_secs.is_none()
+            && interval_batch_mode.is_none()
+            && catch_up_window_secs.is_none()
             && max_file_size_mb.is_none()
             && content_types.is_none()
         {
@@ -247,6 +303,9 @@ impl SyncSettingsDiff {
             Some(Self {
                 auto_sync,
                 sync_frequency,
+                sync_interval_secs,
+                interval_batch_mode,
+                catch_up_window_secs,
                 max_file_size_mb,
                 content_types,
             })
@@ -262,6 +321,18 @@ impl SyncSettingsDiff {
         if let Some((old, new)) = &self.sync_frequency {
             parts.push(format!("{}.sync_frequency: {} → {}", prefix, old, new));
         }
+        if let Some((old, new)) = &self.sync_interval_secs {
+            parts.push(format!("{}.sync_interval_secs: {} → {}", prefix, old, new));
+        }
+        if let Some((old, new)) = &self.interval_batch_mode {
+            parts.push(format!("{}.interval_batch_mode: {} → {}", prefix, old, new));
+        }
+        if let Some((old, new)) = &self.catch_up_window_secs {
+            parts.push(format!(
+                "{}.catch_up_window_secs: {} → {}",
+                prefix, old, new
+            ));
+        }
         if let Some((old, new)) = &self.max_file_size_mb {
             parts.push(format!("{}.max_file_size_mb: {} → {}", prefix, old, new));
         }
@@ -356,6 +427,106 @@ impl SecuritySettingsDiff {
     }
 }
 
+/// Network settings diff
+struct NetworkSettingsDiff {
+    listen_port: Option<(u16, u16)>,
+    bootstrap_peers: Option<(usize, usize)>,
+    tcp_only: Option<(bool, bool)>,
+    allowed_interfaces: Option<(Vec<String>, Vec<String>)>,
+    denied_interfaces: Option<(Vec<String>, Vec<String>)>,
+    relay_enabled: Option<(bool, bool)>,
+    /// Only whether it changed; the relay host is not logged.
+    relay_address_changed: bool,
+    relay_namespace_changed: bool,
+}
+
+impl NetworkSettingsDiff {
+    fn diff(old: &NetworkSettings, new: &NetworkSettings) -> Option<Self> {
+        let listen_port =
+            (old.listen_port != new.listen_port).then_some((old.listen_port, new.listen_port));
+        // Only counts are logged; peer addresses identify hosts on the user's network.
+        let bootstrap_peers = (old.bootstrap_peers != new.bootstrap_peers)
+            .then_some((old.bootstrap_peers.len(), new.bootstrap_peers.len()));
+        let tcp_only = (old.tcp_only != new.tcp_only).then_some((old.tcp_only, new.tcp_only));
+        let allowed_interfaces = (old.allowed_interfaces != new.allowed_interfaces).then(|| {
+            (
+                old.allowed_interfaces.clone(),
+                new.allowed_interfaces.clone(),
+            )
+        });
+        let denied_interfaces = (old.denied_interfaces != new.denied_interfaces)
+            .then(|| (old.denied_interfaces.clone(), new.denied_interfaces.clone()));
+
+        let relay_enabled = (old.relay.enabled != new.relay.enabled)
+            .then_some((old.relay.enabled, new.relay.enabled));
+        let relay_address_changed = old.relay.address != new.relay.address;
+        let relay_namespace_changed = old.relay.namespace != new.relay.namespace;
+
+        if listen_port.is_none()
+            && bootstrap_peers.is_none()
+            && tcp_only.is_none()
+            && allowed_interfaces.is_none()
+            && denied_interfaces.is_none()
+            && relay_enabled.is_none()
+            && !relay_address_changed
+            && !relay_namespace_changed
+        {
+            None
+        } else {
+            Some(Self {
+                listen_port,
+                bootstrap_peers,
+                tcp_only,
+                allowed_interfaces,
+                denied_interfaces,
+                relay_enabled,
+                relay_address_changed,
+                relay_namespace_changed,
+            })
+        }
+    }
+
+    fn to_log_string(&self, prefix: &str) -> String {
+        let mut parts = Vec::new();
+
+        if let Some((old, new)) = &self.listen_port {
+            parts.push(format!("{}.listen_port: {} → {}", prefix, old, new));
+        }
+        if let Some((old, new)) = &self.bootstrap_peers {
+            parts.push(format!(
+                "{}.bootstrap_peers: {} entries → {} entries",
+                prefix, old, new
+            ));
+        }
+        if let Some((old, new)) = &self.tcp_only {
+            parts.push(format!("{}.tcp_only: {} → {}", prefix, old, new));
+        }
+        if let Some((old, new)) = &self.allowed_interfaces {
+            parts.push(format!(
+                "{}.allowed_interfaces: {:?} → {:?}",
+                prefix, old, new
+            ));
+        }
+        if let Some((old, new)) = &self.denied_interfaces {
+            parts.push(format!(
+                "{}.denied_interfaces: {:?} → {:?}",
+                prefix, old, new
+            ));
+        }
+        if let Some((old, new)) = &self.relay_enabled {
+            parts.push(format!("{}.relay.enabled: {} → {}", prefix, old, new));
+        }
+        if self.relay_address_changed {
+            parts.push(format!("{}.relay.address: changed", prefix));
+        }
+        if self.relay_namespace_changed {
+            parts.push(format!("{}.relay.namespace: changed", prefix));
+        }
+
+        parts.join(", ")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -420,6 +591,59 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_update_settings_keeps_stored_spaces() {
+        let mut initial = Settings::default();
+        initial.spaces.spaces.push(uc_core::space::SpaceProfile {
+            id: uc_core::ids::SpaceId::from("work"),
+            name: "Work".to_string(),
+            created_at_ms: 1,
+        });
+        initial.spaces.active_space_id = uc_core::ids::SpaceId::from("work");
+        let repo = Arc::new(MockSettingsPort::new(initial.clone()));
+
+        let usecase = UpdateSettings::new(repo.clone());
+        usecase.execute(Settings::default()).await.unwrap();
+
+        assert_eq!(repo.stored.lock().unwrap().spaces, initial.spaces);
+    }
+
+    #[tokio::test]
+    async fn test_update_settings_rejects_rule_for_inactive_space() {
+        let mut initial = Settings::default();
+        initial.spaces.spaces.push(uc_core::space::SpaceProfile {
+            id: uc_core::ids::SpaceId::from("work"),
+            name: "Work".to_string(),
+            created_at_ms: 1,
+        });
+        let repo = Arc::new(MockSettingsPort::new(initial.clone()));
+        let usecase = UpdateSettings::new(repo.clone());
+
+        let mut updated = initial.clone();
+        updated
+            .spaces
+            .routing_rules
+            .push(uc_core::space::SpaceRoutingRule {
+                space_id: uc_core::ids::SpaceId::from("work"),
+                content_kinds: vec![],
+                text_contains: Some("jira".to_string()),
+            });
+        assert!(usecase.execute(updated).await.is_err());
+        assert!(repo.stored.lock().unwrap().spaces.routing_rules.is_empty());
+
+        let mut updated = initial;
+        updated
+            .spaces
+            .routing_rules
+            .push(uc_core::space::SpaceRoutingRule {
+                space_id: uc_core::ids::SpaceId::default_space(),
+                content_kinds: vec![],
+                text_contains: Some("jira".to_string()),
+            });
+        usecase.execute(updated).await.unwrap();
+        assert_eq!(repo.stored.lock().unwrap().spaces.routing_rules.len(), 1);
+    }
+
     #[test]
     fn test_settings_diff_empty_when_no_changes() {
         let settings = Settings::default();
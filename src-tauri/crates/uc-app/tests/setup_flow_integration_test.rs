@@ -15,20 +15,19 @@ use uc_app::usecases::{
 };
 use uc_core::network::pairing_state_machine::PairingAction;
 use uc_core::network::protocol::{PairingChallenge, PairingMessage};
-use uc_core::network::{DiscoveredPeer, PairedDevice, PairingState};
+use uc_core::network::DiscoveredPeer;
 use uc_core::ports::network_control::NetworkControlPort;
 use uc_core::ports::security::key_scope::{KeyScopePort, ScopeError};
 use uc_core::ports::security::secure_storage::{SecureStorageError, SecureStoragePort};
 use uc_core::ports::space::{CryptoPort, PersistencePort, SpaceAccessTransportPort};
 use uc_core::ports::watcher_control::{WatcherControlError, WatcherControlPort};
 use uc_core::ports::{
-    DiscoveryPort, EncryptionSessionPort, PairedDeviceRepositoryError, PairedDeviceRepositoryPort,
-    SetupEventPort, SetupStatusPort, TimerPort,
+    DiscoveryPort, EncryptionSessionPort, SetupEventPort, SetupStatusPort, TimerPort,
 };
 use uc_core::security::model::KeyScope;
 use uc_core::security::space_access::event::SpaceAccessEvent;
 use uc_core::setup::SetupState;
-use uc_core::PeerId;
+use uc_core::testing::{InMemoryPairedDeviceRepository, TestNetwork};
 use uc_infra::fs::key_slot_store::JsonKeySlotStore;
 use uc_infra::security::{
     DefaultKeyMaterialService, EncryptionRepository, FileEncryptionStateRepository,
@@ -150,46 +149,6 @@ impl LifecycleEventEmitter for MockLifecycleEventEmitter {
     }
 }
 
-struct NoopPairedDeviceRepository;
-
-#[async_trait]
-impl PairedDeviceRepositoryPort for NoopPairedDeviceRepository {
-    async fn get_by_peer_id(
-        &self,
-        _peer_id: &PeerId,
-    ) -> Result<Option<PairedDevice>, PairedDeviceRepositoryError> {
-        Ok(None)
-    }
-
-    async fn list_all(&self) -> Result<Vec<PairedDevice>, PairedDeviceRepositoryError> {
-        Ok(Vec::new())
-    }
-
-    async fn upsert(&self, _device: PairedDevice) -> Result<(), PairedDeviceRepositoryError> {
-        Ok(())
-    }
-
-    async fn set_state(
-        &self,
-        _peer_id: &PeerId,
-        _state: PairingState,
-    ) -> Result<(), PairedDeviceRepositoryError> {
-        Ok(())
-    }
-
-    async fn update_last_seen(
-        &self,
-        _peer_id: &PeerId,
-        _last_seen_at: chrono::DateTime<chrono::Utc>,
-    ) -> Result<(), PairedDeviceRepositoryError> {
-        Ok(())
-    }
-
-    async fn delete(&self, _peer_id: &PeerId) -> Result<(), PairedDeviceRepositoryError> {
-        Ok(())
-    }
-}
-
 struct NoopDiscoveryPort;
 
 #[async_trait]
@@ -244,77 +203,6 @@ impl SpaceAccessTransportPort for NoopSpaceAccessTransport {
     }
 }
 
-struct NoopSpaceAccessNetworkPort;
-
-#[async_trait]
-impl uc_core::ports::NetworkPort for NoopSpaceAccessNetworkPort {
-    async fn send_clipboard(&self, _peer_id: &str, _encrypted_data: Vec<u8>) -> anyhow::Result<()> {
-        Ok(())
-    }
-
-    async fn broadcast_clipboard(&self, _encrypted_data: Vec<u8>) -> anyhow::Result<()> {
-        Ok(())
-    }
-
-    async fn subscribe_clipboard(
-        &self,
-    ) -> anyhow::Result<tokio::sync::mpsc::Receiver<uc_core::network::ClipboardMessage>> {
-        let (_tx, rx) = tokio::sync::mpsc::channel(1);
-        Ok(rx)
-    }
-
-    async fn get_discovered_peers(&self) -> anyhow::Result<Vec<uc_core::network::DiscoveredPeer>> {
-        Ok(vec![])
-    }
-
-    async fn get_connected_peers(&self) -> anyhow::Result<Vec<uc_core::network::ConnectedPeer>> {
-        Ok(vec![])
-    }
-
-    fn local_peer_id(&self) -> String {
-        "local".to_string()
-    }
-
-    async fn announce_device_name(&self, _device_name: String) -> anyhow::Result<()> {
-        Ok(())
-    }
-
-    async fn open_pairing_session(
-        &self,
-        _peer_id: String,
-        _session_id: String,
-    ) -> anyhow::Result<()> {
-        Ok(())
-    }
-
-    async fn send_pairing_on_session(
-        &self,
-        _session_id: String,
-        _message: uc_core::network::PairingMessage,
-    ) -> anyhow::Result<()> {
-        Ok(())
-    }
-
-    async fn close_pairing_session(
-        &self,
-        _session_id: String,
-        _reason: Option<String>,
-    ) -> anyhow::Result<()> {
-        Ok(())
-    }
-
-    async fn unpair_device(&self, _peer_id: String) -> anyhow::Result<()> {
-        Ok(())
-    }
-
-    async fn subscribe_events(
-        &self,
-    ) -> anyhow::Result<tokio::sync::mpsc::Receiver<uc_core::network::NetworkEvent>> {
-        let (_tx, rx) = tokio::sync::mpsc::channel(1);
-        Ok(rx)
-    }
-}
-
 struct DeterministicSpaceAccessCrypto;
 
 #[async_trait]
@@ -334,6 +222,10 @@ impl CryptoPort for DeterministicSpaceAccessCrypto {
             },
             kdf: uc_core::security::model::KdfParams::for_initialization(),
             salt: vec![2; 16],
+            epoch: 0,
+            revision: 0,
+            retired_master_key: None,
+            retained_master_keys: Vec::new(),
             wrapped_master_key: None,
         })
     }
@@ -387,7 +279,7 @@ async fn drive_space_access_to_waiting_decision(
         guard.sponsor_peer_id = Some("peer-join".to_string());
     }
 
-    let network_port = NoopSpaceAccessNetworkPort;
+    let network_port = TestNetwork::new();
     let crypto = DeterministicSpaceAccessCrypto;
     let mut transport = NoopSpaceAccessTransport;
     let proof = HmacProofAdapter::new();
@@ -473,7 +365,7 @@ fn build_ordered_mock_lifecycle(
 }
 
 fn build_pairing_orchestrator() -> Arc<PairingOrchestrator> {
-    let repo = Arc::new(NoopPairedDeviceRepository);
+    let repo = Arc::new(InMemoryPairedDeviceRepository::new());
     let (orchestrator, _rx) = PairingOrchestrator::new(
         PairingConfig::default(),
         repo,
@@ -489,7 +381,7 @@ fn build_pairing_orchestrator_with_actions() -> (
     Arc<PairingOrchestrator>,
     tokio::sync::Mutex<mpsc::Receiver<PairingAction>>,
 ) {
-    let repo = Arc::new(NoopPairedDeviceRepository);
+    let repo = Arc::new(InMemoryPairedDeviceRepository::new());
     let (orchestrator, rx) = PairingOrchestrator::new(
         PairingConfig::default(),
         repo,
@@ -553,7 +445,7 @@ async fn create_space_flow_marks_setup_complete_and_persists_state() {
     let persistence_port: Arc<tokio::sync::Mutex<dyn uc_core::ports::space::PersistencePort>> =
         Arc::new(tokio::sync::Mutex::new(SpaceAccessPersistenceAdapter::new(
             encryption_state,
-            Arc::new(NoopPairedDeviceRepository),
+            Arc::new(InMemoryPairedDeviceRepository::new()),
         )));
     let orchestrator = SetupOrchestrator::new(
         initialize_encryption,
@@ -566,7 +458,7 @@ async fn create_space_flow_marks_setup_complete_and_persists_state() {
         build_discovery_port(),
         Arc::new(MockNetworkControl),
         crypto_factory,
-        Arc::new(NoopSpaceAccessNetworkPort),
+        Arc::new(TestNetwork::new()),
         transport_port,
         proof_port,
         timer_port,
@@ -624,6 +516,7 @@ async fn drive_to_join_passphrase_state(
                 device_id: "remote-device-id".to_string(),
                 identity_pubkey: vec![7; 32],
                 nonce: vec![1; 32],
+                invitation_proof: None,
             },
         )
         .await
@@ -693,7 +586,7 @@ async fn ensure_discovery_starts_network_before_listing_peers() {
     let persistence_port: Arc<tokio::sync::Mutex<dyn uc_core::ports::space::PersistencePort>> =
         Arc::new(tokio::sync::Mutex::new(SpaceAccessPersistenceAdapter::new(
             encryption_state,
-            Arc::new(NoopPairedDeviceRepository),
+            Arc::new(InMemoryPairedDeviceRepository::new()),
         )));
     let calls = Arc::new(Mutex::new(Vec::new()));
 
@@ -712,7 +605,7 @@ async fn ensure_discovery_starts_network_before_listing_peers() {
             calls: calls.clone(),
         }),
         crypto_factory,
-        Arc::new(NoopSpaceAccessNetworkPort),
+        Arc::new(TestNetwork::new()),
         transport_port,
         proof_port,
         timer_port,
@@ -768,7 +661,7 @@ async fn join_space_access_invokes_space_access_orchestrator() {
     let persistence_port: Arc<tokio::sync::Mutex<dyn uc_core::ports::space::PersistencePort>> =
         Arc::new(tokio::sync::Mutex::new(SpaceAccessPersistenceAdapter::new(
             encryption_state,
-            Arc::new(NoopPairedDeviceRepository),
+            Arc::new(InMemoryPairedDeviceRepository::new()),
         )));
     let (pairing_orchestrator, action_rx) = build_pairing_orchestrator_with_actions();
     let space_access_orchestrator = build_space_access_orchestrator();
@@ -783,7 +676,7 @@ async fn join_space_access_invokes_space_access_orchestrator() {
         build_discovery_port(),
         Arc::new(MockNetworkControl),
         crypto_factory,
-        Arc::new(NoopSpaceAccessNetworkPort),
+        Arc::new(TestNetwork::new()),
         transport_port,
         proof_port,
         timer_port,
@@ -847,7 +740,7 @@ async fn join_space_access_propagates_space_access_error() {
     let persistence_port: Arc<tokio::sync::Mutex<dyn uc_core::ports::space::PersistencePort>> =
         Arc::new(tokio::sync::Mutex::new(SpaceAccessPersistenceAdapter::new(
             encryption_state,
-            Arc::new(NoopPairedDeviceRepository),
+            Arc::new(InMemoryPairedDeviceRepository::new()),
         )));
     let (pairing_orchestrator, action_rx) = build_pairing_orchestrator_with_actions();
     let space_access_orchestrator = build_space_access_orchestrator();
@@ -862,7 +755,7 @@ async fn join_space_access_propagates_space_access_error() {
         build_discovery_port(),
         Arc::new(MockNetworkControl),
         crypto_factory.clone(),
-        Arc::new(NoopSpaceAccessNetworkPort),
+        Arc::new(TestNetwork::new()),
         transport_port.clone(),
         proof_port,
         timer_port.clone(),
@@ -876,7 +769,7 @@ async fn join_space_access_propagates_space_access_error() {
     let crypto = crypto_factory.build(uc_core::security::SecretString::new(
         "seed-pass".to_string(),
     ));
-    let network_port = NoopSpaceAccessNetworkPort;
+    let network_port = TestNetwork::new();
     let proof_adapter = HmacProofAdapter::new();
     let mut transport = transport_port.lock().await;
     let mut timer = timer_port.lock().await;
@@ -940,7 +833,7 @@ async fn join_space_flow_converges_to_granted_on_access_granted_result() {
     )
     .await;
 
-    let network_port = NoopSpaceAccessNetworkPort;
+    let network_port = TestNetwork::new();
     let crypto = DeterministicSpaceAccessCrypto;
     let mut transport = NoopSpaceAccessTransport;
     let proof = HmacProofAdapter::new();
@@ -986,7 +879,7 @@ async fn join_space_flow_converges_to_denied_on_access_denied_result() {
     )
     .await;
 
-    let network_port = NoopSpaceAccessNetworkPort;
+    let network_port = TestNetwork::new();
     let crypto = DeterministicSpaceAccessCrypto;
     let mut transport = NoopSpaceAccessTransport;
     let proof = HmacProofAdapter::new();
@@ -1036,7 +929,7 @@ async fn join_space_flow_times_out_when_result_does_not_arrive() {
     )
     .await;
 
-    let network_port = NoopSpaceAccessNetworkPort;
+    let network_port = TestNetwork::new();
     let crypto = DeterministicSpaceAccessCrypto;
     let mut transport = NoopSpaceAccessTransport;
     let proof = HmacProofAdapter::new();
@@ -14,12 +14,12 @@ use tokio::time::{sleep, timeout};
 
 use tracing_subscriber::EnvFilter;
 use uc_app::usecases::internal::capture_clipboard::CaptureClipboardUseCase;
-use uc_core::clipboard::SelectRepresentationPolicyV1;
 use uc_core::clipboard::{
     ClipboardEntry, ClipboardEvent, ClipboardSelectionDecision, ObservedClipboardRepresentation,
     PayloadAvailability, PersistedClipboardRepresentation, SystemClipboardSnapshot,
     ThumbnailMetadata,
 };
+use uc_core::clipboard::{SelectRepresentationPolicyV1, SyncFilterPolicyV1};
 use uc_core::ids::{EntryId, EventId, FormatId, RepresentationId};
 use uc_core::ports::clipboard::{
     GeneratedThumbnail, ProcessingUpdateOutcome, RepresentationCachePort, SpoolQueuePort,
@@ -27,15 +27,17 @@ use uc_core::ports::clipboard::{
 };
 use uc_core::ports::BlobWriterPort;
 use uc_core::ports::ClockPort;
+use uc_core::ports::SettingsPort;
 use uc_core::ports::{
     ClipboardEntryRepositoryPort, ClipboardEventWriterPort, ClipboardRepresentationNormalizerPort,
     ClipboardRepresentationRepositoryPort, DeviceIdentityPort, SelectRepresentationPolicyPort,
 };
+use uc_core::settings::model::Settings;
 use uc_core::DeviceId;
 use uc_core::{Blob, BlobId, ContentHash, MimeType};
 use uc_infra::clipboard::{
-    BackgroundBlobWorker, ClipboardRepresentationNormalizer, MpscSpoolQueue, RepresentationCache,
-    SpoolManager,
+    BackgroundBlobWorker, ClipboardRepresentationNormalizer, InMemoryClipboardSearchIndex,
+    MpscSpoolQueue, RepresentationCache, SpoolManager,
 };
 use uc_infra::config::ClipboardStorageConfig;
 use uc_infra::security::Blake3Hasher;
@@ -100,6 +102,19 @@ impl DeviceIdentityPort for InMemoryDeviceIdentity {
     }
 }
 
+struct DefaultSettings;
+
+#[async_trait::async_trait]
+impl SettingsPort for DefaultSettings {
+    async fn load(&self) -> Result<Settings> {
+        Ok(Settings::default())
+    }
+
+    async fn save(&self, _settings: &Settings) -> Result<()> {
+        Ok(())
+    }
+}
+
 struct InMemoryThumbnailRepo {
     thumbnails: Mutex<HashMap<RepresentationId, ThumbnailMetadata>>,
 }
@@ -145,6 +160,10 @@ impl ThumbnailRepositoryPort for InMemoryThumbnailRepo {
         );
         Ok(())
     }
+
+    async fn delete_orphaned_thumbnails(&self) -> anyhow::Result<usize> {
+        Ok(0)
+    }
 }
 
 struct NoopThumbnailGenerator;
@@ -208,6 +227,58 @@ impl ClipboardEntryRepositoryPort for InMemoryEntryRepo {
         self.selections.lock().unwrap().remove(entry_id);
         Ok(())
     }
+
+    async fn list_retention_candidates(
+        &self,
+    ) -> anyhow::Result<Vec<uc_core::clipboard::RetentionCandidate>> {
+        Ok(Vec::new())
+    }
+
+    async fn soft_delete_entries(
+        &self,
+        _entry_ids: &[uc_core::ids::EntryId],
+        _deleted_at_ms: i64,
+    ) -> anyhow::Result<usize> {
+        Ok(0)
+    }
+
+    async fn list_soft_deleted_entries(
+        &self,
+        _deleted_before_ms: i64,
+    ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+        Ok(Vec::new())
+    }
+
+    async fn get_entry_source_device(
+        &self,
+        _entry_id: &uc_core::ids::EntryId,
+    ) -> anyhow::Result<Option<uc_core::ids::DeviceId>> {
+        Ok(None)
+    }
+
+    async fn set_entry_pinned(
+        &self,
+        _entry_id: &uc_core::ids::EntryId,
+        _pinned: bool,
+    ) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+
+    async fn list_pinned_entries(
+        &self,
+        _limit: usize,
+        _offset: usize,
+    ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+        Ok(Vec::new())
+    }
+
+    async fn list_catch_up_candidates(
+        &self,
+        _since_ms: i64,
+        _limit: usize,
+    ) -> anyhow::Result<Vec<uc_core::clipboard::CatchUpCandidate>> {
+        Ok(Vec::new())
+    }
 }
 
 #[derive(Default)]
@@ -303,6 +374,26 @@ impl ClipboardRepresentationRepositoryPort for InMemoryRepresentationRepo {
 
         Ok(ProcessingUpdateOutcome::Updated(rep.clone()))
     }
+
+    async fn count_inline_representations(&self) -> anyhow::Result<usize> {
+        Ok(0)
+    }
+
+    async fn list_inline_representation_ids(
+        &self,
+        _after: Option<&uc_core::ids::RepresentationId>,
+        _limit: usize,
+    ) -> anyhow::Result<Vec<(uc_core::ids::EventId, uc_core::ids::RepresentationId)>> {
+        Ok(Vec::new())
+    }
+
+    async fn update_inline_data(
+        &self,
+        _representation_id: &uc_core::ids::RepresentationId,
+        _inline_data: &[u8],
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
 }
 
 struct InMemoryEventWriter {
@@ -420,6 +511,9 @@ async fn test_capture_returns_error_when_spool_queue_closed() -> Result<()> {
         Arc::new(InMemoryDeviceIdentity),
         rep_cache_port,
         spool_queue,
+        Arc::new(InMemoryClipboardSearchIndex::new()),
+        Arc::new(SyncFilterPolicyV1::new()),
+        Arc::new(DefaultSettings),
     );
     let result = timeout(Duration::from_millis(200), usecase.execute(snapshot)).await?;
     assert!(result.is_err(), "expected enqueue failure");
@@ -466,6 +560,9 @@ async fn test_capture_logs_on_spool_queue_closed() -> Result<()> {
         Arc::new(InMemoryDeviceIdentity),
         rep_cache_port,
         spool_queue,
+        Arc::new(InMemoryClipboardSearchIndex::new()),
+        Arc::new(SyncFilterPolicyV1::new()),
+        Arc::new(DefaultSettings),
     );
     let result = usecase.execute(snapshot).await;
     assert!(result.is_err(), "expected enqueue failure");
@@ -596,6 +693,9 @@ async fn test_worker_materializes_after_spool_eviction_with_cache_hit() -> Resul
         Arc::new(InMemoryDeviceIdentity),
         rep_cache_port,
         spool_queue.clone(),
+        Arc::new(InMemoryClipboardSearchIndex::new()),
+        Arc::new(SyncFilterPolicyV1::new()),
+        Arc::new(DefaultSettings),
     );
 
     let rep_id_a = RepresentationId::new();
@@ -729,6 +829,9 @@ async fn test_worker_materializes_blob_from_cache() -> Result<()> {
         Arc::new(InMemoryDeviceIdentity),
         rep_cache_port,
         spool_queue,
+        Arc::new(InMemoryClipboardSearchIndex::new()),
+        Arc::new(SyncFilterPolicyV1::new()),
+        Arc::new(DefaultSettings),
     );
 
     usecase.execute(snapshot).await?;
@@ -11,12 +11,12 @@ use tokio::sync::mpsc;
 use tokio::time::sleep;
 
 use uc_app::usecases::internal::capture_clipboard::CaptureClipboardUseCase;
-use uc_core::clipboard::SelectRepresentationPolicyV1;
 use uc_core::clipboard::{
     ClipboardEntry, ClipboardEvent, ClipboardSelectionDecision, ObservedClipboardRepresentation,
     PayloadAvailability, PersistedClipboardRepresentation, SystemClipboardSnapshot,
     ThumbnailMetadata,
 };
+use uc_core::clipboard::{SelectRepresentationPolicyV1, SyncFilterPolicyV1};
 use uc_core::ids::{EntryId, EventId, FormatId, RepresentationId};
 use uc_core::ports::clipboard::{
     GeneratedThumbnail, ProcessingUpdateOutcome, RepresentationCachePort, SpoolQueuePort,
@@ -24,15 +24,17 @@ use uc_core::ports::clipboard::{
 };
 use uc_core::ports::BlobWriterPort;
 use uc_core::ports::ClockPort;
+use uc_core::ports::SettingsPort;
 use uc_core::ports::{
     ClipboardEntryRepositoryPort, ClipboardEventWriterPort, ClipboardRepresentationNormalizerPort,
     ClipboardRepresentationRepositoryPort, DeviceIdentityPort, SelectRepresentationPolicyPort,
 };
+use uc_core::settings::model::Settings;
 use uc_core::DeviceId;
 use uc_core::{Blob, BlobId, ContentHash, MimeType};
 use uc_infra::clipboard::{
-    BackgroundBlobWorker, ClipboardRepresentationNormalizer, MpscSpoolQueue, RepresentationCache,
-    SpoolManager,
+    BackgroundBlobWorker, ClipboardRepresentationNormalizer, InMemoryClipboardSearchIndex,
+    MpscSpoolQueue, RepresentationCache, SpoolManager,
 };
 use uc_infra::config::ClipboardStorageConfig;
 use uc_infra::security::Blake3Hasher;
@@ -56,6 +58,19 @@ impl DeviceIdentityPort for InMemoryDeviceIdentity {
     }
 }
 
+struct DefaultSettings;
+
+#[async_trait::async_trait]
+impl SettingsPort for DefaultSettings {
+    async fn load(&self) -> Result<Settings> {
+        Ok(Settings::default())
+    }
+
+    async fn save(&self, _settings: &Settings) -> Result<()> {
+        Ok(())
+    }
+}
+
 struct InMemoryThumbnailRepo {
     thumbnails: Mutex<HashMap<RepresentationId, ThumbnailMetadata>>,
 }
@@ -101,6 +116,10 @@ impl ThumbnailRepositoryPort for InMemoryThumbnailRepo {
         );
         Ok(())
     }
+
+    async fn delete_orphaned_thumbnails(&self) -> anyhow::Result<usize> {
+        Ok(0)
+    }
 }
 
 struct NoopThumbnailGenerator;
@@ -164,6 +183,58 @@ impl ClipboardEntryRepositoryPort for InMemoryEntryRepo {
         self.selections.lock().unwrap().remove(entry_id);
         Ok(())
     }
+
+    async fn list_retention_candidates(
+        &self,
+    ) -> anyhow::Result<Vec<uc_core::clipboard::RetentionCandidate>> {
+        Ok(Vec::new())
+    }
+
+    async fn soft_delete_entries(
+        &self,
+        _entry_ids: &[uc_core::ids::EntryId],
+        _deleted_at_ms: i64,
+    ) -> anyhow::Result<usize> {
+        Ok(0)
+    }
+
+    async fn list_soft_deleted_entries(
+        &self,
+        _deleted_before_ms: i64,
+    ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+        Ok(Vec::new())
+    }
+
+    async fn get_entry_source_device(
+        &self,
+        _entry_id: &uc_core::ids::EntryId,
+    ) -> anyhow::Result<Option<uc_core::ids::DeviceId>> {
+        Ok(None)
+    }
+
+    async fn set_entry_pinned(
+        &self,
+        _entry_id: &uc_core::ids::EntryId,
+        _pinned: bool,
+    ) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+
+    async fn list_pinned_entries(
+        &self,
+        _limit: usize,
+        _offset: usize,
+    ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+        Ok(Vec::new())
+    }
+
+    async fn list_catch_up_candidates(
+        &self,
+        _since_ms: i64,
+        _limit: usize,
+    ) -> anyhow::Result<Vec<uc_core::clipboard::CatchUpCandidate>> {
+        Ok(Vec::new())
+    }
 }
 
 #[derive(Default)]
@@ -259,6 +330,26 @@ impl ClipboardRepresentationRepositoryPort for InMemoryRepresentationRepo {
 
         Ok(ProcessingUpdateOutcome::Updated(rep.clone()))
     }
+
+    async fn count_inline_representations(&self) -> anyhow::Result<usize> {
+        Ok(0)
+    }
+
+    async fn list_inline_representation_ids(
+        &self,
+        _after: Option<&uc_core::ids::RepresentationId>,
+        _limit: usize,
+    ) -> anyhow::Result<Vec<(uc_core::ids::EventId, uc_core::ids::RepresentationId)>> {
+        Ok(Vec::new())
+    }
+
+    async fn update_inline_data(
+        &self,
+        _representation_id: &uc_core::ids::RepresentationId,
+        _inline_data: &[u8],
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
 }
 
 struct InMemoryEventWriter {
@@ -411,6 +502,9 @@ async fn stress_test_100_large_images() -> Result<()> {
         Arc::new(InMemoryDeviceIdentity),
         rep_cache_port,
         spool_queue,
+        Arc::new(InMemoryClipboardSearchIndex::new()),
+        Arc::new(SyncFilterPolicyV1::new()),
+        Arc::new(DefaultSettings),
     );
 
     let mut rep_ids = Vec::new();
@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::clipboard::MimeType;
+
+/// 剪贴板内容的粗粒度类别
+///
+/// Coarse-grained content category, used by retention and sync filters to
+/// match a representation against the user-facing `ContentTypes` switches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentKind {
+    Text,
+    RichText,
+    Image,
+    Link,
+    File,
+    CodeSnippet,
+    Unknown,
+}
+
+/// 被识别为代码片段的 mime 子串（保守列表）
+const CODE_MIME_MARKERS: &[&str] = &[
+    "json",
+    "javascript",
+    "typescript",
+    "x-python",
+    "x-rust",
+    "x-csrc",
+    "x-c++src",
+    "x-java",
+    "x-sh",
+    "x-shellscript",
+];
+
+/// 平台用于标记敏感/不应进入历史的剪贴板格式
+///
+/// - macOS: nspasteboard.org 约定
+/// - Windows: Clipboard History 排除标记
+/// - KDE: 密码管理器提示
+const SENSITIVE_FORMAT_MARKERS: &[&str] = &[
+    "org.nspasteboard.ConcealedType",
+    "org.nspasteboard.TransientType",
+    "ExcludeClipboardContentFromMonitorProcessing",
+    "CanIncludeInClipboardHistory",
+    "x-kde-passwordManagerHint",
+];
+
+impl ContentKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContentKind::Text => "text",
+            ContentKind::RichText => "rich_text",
+            ContentKind::Image => "image",
+            ContentKind::Link => "link",
+            ContentKind::File => "file",
+            ContentKind::CodeSnippet => "code_snippet",
+            ContentKind::Unknown => "unknown",
+        }
+    }
+
+    /// Classify a single representation by mime type, falling back to format id.
+    ///
+    /// 规则与 `SelectRepresentationPolicyV1` 保持一致，额外识别代码片段。
+    pub fn classify(mime: Option<&MimeType>, format_id: &str) -> Self {
+        if format_id.eq_ignore_ascii_case("files") || format_id.contains("uri-list") {
+            return ContentKind::File;
+        }
+
+        let mime = match mime {
+            Some(m) => m.as_str().to_ascii_lowercase(),
+            None => return ContentKind::Unknown,
+        };
+
+        if mime.starts_with("text/uri-list") {
+            return ContentKind::File;
+        }
+        if mime.starts_with("image/") {
+            return ContentKind::Image;
+        }
+        if mime == "text/html" || mime == "text/rtf" {
+            return ContentKind::RichText;
+        }
+        if CODE_MIME_MARKERS.iter().any(|marker| mime.contains(marker)) {
+            return ContentKind::CodeSnippet;
+        }
+        if mime.starts_with("text/") {
+            return ContentKind::Text;
+        }
+        if mime.contains("uri") || mime.contains("url") {
+            return ContentKind::Link;
+        }
+
+        ContentKind::Unknown
+    }
+}
+
+impl FromStr for ContentKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(ContentKind::Text),
+            "rich_text" => Ok(ContentKind::RichText),
+            "image" => Ok(ContentKind::Image),
+            "link" => Ok(ContentKind::Link),
+            "file" => Ok(ContentKind::File),
+            "code_snippet" => Ok(ContentKind::CodeSnippet),
+            "unknown" => Ok(ContentKind::Unknown),
+            other => Err(anyhow::anyhow!("unknown content kind: {other}")),
+        }
+    }
+}
+
+/// Whether the format id is a platform marker for sensitive content
+/// (password managers, concealed or transient pasteboard types).
+pub fn is_sensitive_format(format_id: &str) -> bool {
+    SENSITIVE_FORMAT_MARKERS
+        .iter()
+        .any(|marker| format_id.eq_ignore_ascii_case(marker))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mime(s: &str) -> MimeType {
+        MimeType(s.to_string())
+    }
+
+    #[test]
+    fn classify_matches_common_mime_types() {
+        assert_eq!(
+            ContentKind::classify(Some(&mime("text/plain")), "public.utf8-plain-text"),
+            ContentKind::Text
+        );
+        assert_eq!(
+            ContentKind::classify(Some(&mime("text/html")), "public.html"),
+            ContentKind::RichText
+        );
+        assert_eq!(
+            ContentKind::classify(Some(&mime("image/png")), "public.png"),
+            ContentKind::Image
+        );
+        assert_eq!(
+            ContentKind::classify(Some(&mime("text/uri-list")), "public.file-url"),
+            ContentKind::File
+        );
+        assert_eq!(
+            ContentKind::classify(Some(&mime("application/json")), "json"),
+            ContentKind::CodeSnippet
+        );
+        assert_eq!(
+            ContentKind::classify(Some(&mime("application/x-url")), "url"),
+            ContentKind::Link
+        );
+        assert_eq!(ContentKind::classify(None, "files"), ContentKind::File);
+        assert_eq!(ContentKind::classify(None, "custom"), ContentKind::Unknown);
+    }
+
+    #[test]
+    fn content_kind_string_round_trip() {
+        for kind in [
+            ContentKind::Text,
+            ContentKind::RichText,
+            ContentKind::Image,
+            ContentKind::Link,
+            ContentKind::File,
+            ContentKind::CodeSnippet,
+            ContentKind::Unknown,
+        ] {
+            assert_eq!(kind.as_str().parse::<ContentKind>().unwrap(), kind);
+        }
+        assert!("video".parse::<ContentKind>().is_err());
+    }
+
+    #[test]
+    fn sensitive_format_markers_are_detected() {
+        assert!(is_sensitive_format("org.nspasteboard.ConcealedType"));
+        assert!(is_sensitive_format("x-kde-passwordManagerHint"));
+        assert!(!is_sensitive_format("public.utf8-plain-text"));
+    }
+}
@@ -1,4 +1,5 @@
-use crate::ids::{EntryId, EventId};
+use crate::clipboard::{MimeType, SyncSkipReason};
+use crate::ids::{DeviceId, EntryId, EventId, FormatId};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ClipboardEntry {
@@ -8,6 +9,12 @@ pub struct ClipboardEntry {
     pub active_time_ms: i64,
     pub title: Option<String>,
     pub total_size: i64,
+    /// Pinned entries are kept by retention and clear-all flows.
+    /// 置顶（收藏）条目不会被保留策略和清空操作删除。
+    pub pinned: bool,
+    /// Why the entry was not sent to peers when it was captured; `None` if it was eligible.
+    /// 捕获时未同步到其他设备的原因；可同步时为 `None`。
+    pub sync_skip_reason: Option<SyncSkipReason>,
 }
 
 impl ClipboardEntry {
@@ -25,6 +32,8 @@ impl ClipboardEntry {
             active_time_ms: created_at_ms,
             title,
             total_size,
+            pinned: false,
+            sync_skip_reason: None,
         }
     }
 
@@ -43,6 +52,36 @@ impl ClipboardEntry {
             active_time_ms,
             title,
             total_size,
+            pinned: false,
+            sync_skip_reason: None,
         }
     }
 }
+
+/// Representation metadata carried by a retention candidate.
+/// 保留策略评估所需的表示元数据。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetentionRepresentation {
+    pub format_id: FormatId,
+    pub mime_type: Option<MimeType>,
+}
+
+/// A live (not soft-deleted) entry evaluated by the retention policy.
+/// 参与保留策略评估的条目（不含已软删除条目）。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetentionCandidate {
+    pub entry: ClipboardEntry,
+    pub representations: Vec<RetentionRepresentation>,
+}
+
+/// A live entry summarised for offline catch-up with a peer.
+/// 离线补同步时用于比对的条目摘要。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatchUpCandidate {
+    pub entry: ClipboardEntry,
+    /// Device that produced the capture event.
+    /// 产生该捕获事件的设备。
+    pub source_device: DeviceId,
+    /// `SnapshotHash` of the capture event, in its string form.
+    pub snapshot_hash: String,
+}
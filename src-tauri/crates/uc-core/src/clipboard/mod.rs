@@ -1,5 +1,6 @@
 //! Clipboard domain models.
 mod change;
+mod content_kind;
 mod decision;
 mod entry;
 mod event;
@@ -8,19 +9,29 @@ mod mime;
 mod origin;
 mod payload_availability;
 mod policy;
+mod remote_payload;
+mod search;
 mod selection;
 mod snapshot;
+mod sync_filter;
 mod system;
 mod thumbnail;
 mod timestamp;
 
 pub use change::*;
+pub use content_kind::{is_sensitive_format, ContentKind};
 pub use entry::*;
 pub use event::*;
 pub use policy::ClipboardSelection;
 pub use policy::*;
+pub use search::{
+    pick_search_text, search_text, ClipboardSearchDocument, ClipboardSearchHit,
+    ClipboardSearchQuery, ClipboardSearchTerm, SnippetSegment, SEARCH_TEXT_MAX_BYTES,
+    SEARCH_TEXT_MAX_CHARS,
+};
 pub use selection::*;
 pub use snapshot::*;
+pub use sync_filter::{RepresentationFilterDecision, SnapshotFilterDecision, SyncSkipReason};
 pub use system::{
     ObservedClipboardRepresentation, RepresentationHash, SnapshotHash, SystemClipboardSnapshot,
 };
@@ -30,5 +41,6 @@ pub use hash::{ContentHash, HashAlgorithm};
 pub use mime::MimeType;
 pub use origin::ClipboardOrigin;
 pub use payload_availability::PayloadAvailability;
+pub use remote_payload::{decode_sealed_payload, encode_sealed_payload, RemotePayloadRef};
 pub use thumbnail::ThumbnailMetadata;
 pub use timestamp::TimestampMs;
@@ -22,6 +22,9 @@ pub enum PayloadAvailability {
 
     /// Data permanently lost - inline_data=None, blob_id=None
     Lost,
+
+    /// Synced without its bytes; pulled from a peer on first use - inline_data=None, blob_id=None
+    Remote,
 }
 
 impl PayloadAvailability {
@@ -44,6 +47,7 @@ impl PayloadAvailability {
             PayloadAvailability::Processing => "Processing",
             PayloadAvailability::Failed { .. } => "Failed",
             PayloadAvailability::Lost => "Lost",
+            PayloadAvailability::Remote => "Remote",
         }
     }
 
@@ -74,6 +78,14 @@ mod tests {
         assert_eq!(state.requires_blob_id(), true);
     }
 
+    #[test]
+    fn test_remote_state_requires_neither() {
+        let state = PayloadAvailability::Remote;
+        assert_eq!(state.requires_inline_data(), false);
+        assert_eq!(state.requires_blob_id(), false);
+        assert_eq!(state.is_cache_or_spool_expected(), false);
+    }
+
     #[test]
     fn test_staged_state_requires_neither() {
         let state = PayloadAvailability::Staged;
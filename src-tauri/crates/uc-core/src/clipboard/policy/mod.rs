@@ -1,7 +1,9 @@
 mod error;
 mod model;
+mod sync_filter_v1;
 mod v1;
 
 pub use error::PolicyError;
 pub use model::{ClipboardSelection, SelectionPolicyVersion, SelectionTarget};
+pub use sync_filter_v1::SyncFilterPolicyV1;
 pub use v1::SelectRepresentationPolicyV1;
@@ -0,0 +1,240 @@
+use crate::{
+    clipboard::{
+        is_sensitive_format, ContentKind, RepresentationFilterDecision, SnapshotFilterDecision,
+        SyncSkipReason, SystemClipboardSnapshot,
+    },
+    ports::SyncFilterPolicyPort,
+    settings::model::SyncSettings,
+};
+
+const BYTES_PER_MB: i64 = 1024 * 1024;
+
+/// v1 同步过滤策略
+///
+/// 规则（按顺序判定，命中即停）：
+/// - 超过 `max_file_size_mb` 的 representation 不发送（0 表示不限制）
+/// - `auto_sync` 关闭时全部不发送
+/// - 快照带有敏感标记时全部不发送
+/// - 类型开关关闭的 representation 不发送；`Unknown` 跟随快照中已识别的类型，
+///   只要有一个已识别类型被发送，它就作为伴随格式一起发送
+///
+/// The kinds come from [`ContentKind::classify`], which follows the same rules
+/// as `SelectRepresentationPolicyV1`. `content_types` is an allow-list: with
+/// every switch off nothing is synced. The filter only decides what leaves the
+/// device; local history always keeps every representation.
+#[derive(Debug, Default)]
+pub struct SyncFilterPolicyV1;
+
+impl SyncFilterPolicyV1 {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn exceeds_max_size(size_bytes: i64, max_file_size_mb: u32) -> bool {
+        max_file_size_mb > 0 && size_bytes > i64::from(max_file_size_mb) * BYTES_PER_MB
+    }
+}
+
+impl SyncFilterPolicyPort for SyncFilterPolicyV1 {
+    fn evaluate(
+        &self,
+        snapshot: &SystemClipboardSnapshot,
+        settings: &SyncSettings,
+    ) -> SnapshotFilterDecision {
+        let sensitive = snapshot
+            .representations
+            .iter()
+            .any(|rep| is_sensitive_format(rep.format_id.as_ref()));
+
+        let mut representations: Vec<RepresentationFilterDecision> = snapshot
+            .representations
+            .iter()
+            .map(|rep| {
+                let content_kind = ContentKind::classify(rep.mime.as_ref(), rep.format_id.as_ref());
+                let skip_reason =
+                    if Self::exceeds_max_size(rep.size_bytes(), settings.max_file_size_mb) {
+                        Some(SyncSkipReason::ExceedsMaxFileSize)
+                    } else if !settings.auto_sync {
+                        Some(SyncSkipReason::AutoSyncDisabled)
+                    } else if sensitive {
+                        Some(SyncSkipReason::Sensitive)
+                    } else if content_kind != ContentKind::Unknown
+                        && !settings.content_types.includes(content_kind)
+                    {
+                        Some(SyncSkipReason::ContentTypeDisabled)
+                    } else {
+                        None
+                    };
+                RepresentationFilterDecision {
+                    representation_id: rep.id.clone(),
+                    content_kind,
+                    send_to_peers: skip_reason.is_none(),
+                    skip_reason,
+                }
+            })
+            .collect();
+
+        // Unknown representations only travel alongside a recognised one.
+        let known_sent = representations
+            .iter()
+            .any(|rep| rep.send_to_peers && rep.content_kind != ContentKind::Unknown);
+        if !known_sent {
+            for rep in representations
+                .iter_mut()
+                .filter(|rep| rep.send_to_peers && rep.content_kind == ContentKind::Unknown)
+            {
+                rep.send_to_peers = false;
+                rep.skip_reason = Some(SyncSkipReason::ContentTypeDisabled);
+            }
+        }
+
+        SnapshotFilterDecision { representations }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clipboard::{MimeType, ObservedClipboardRepresentation};
+    use crate::ids::{FormatId, RepresentationId};
+    use crate::settings::model::ContentTypes;
+
+    fn rep(
+        id: &str,
+        format_id: &str,
+        mime: Option<&str>,
+        size: usize,
+    ) -> ObservedClipboardRepresentation {
+        ObservedClipboardRepresentation {
+            id: RepresentationId::from(id),
+            format_id: FormatId::from(format_id),
+            mime: mime.map(|m| MimeType(m.to_string())),
+            bytes: vec![0; size],
+        }
+    }
+
+    fn snapshot(representations: Vec<ObservedClipboardRepresentation>) -> SystemClipboardSnapshot {
+        SystemClipboardSnapshot {
+            ts_ms: 0,
+            representations,
+        }
+    }
+
+    fn text_only() -> SyncSettings {
+        SyncSettings {
+            content_types: ContentTypes {
+                text: true,
+                ..ContentTypes::default()
+            },
+            ..SyncSettings::default()
+        }
+    }
+
+    #[test]
+    fn disabled_content_type_is_not_sent() {
+        let decision = SyncFilterPolicyV1::new().evaluate(
+            &snapshot(vec![rep("img", "public.png", Some("image/png"), 16)]),
+            &text_only(),
+        );
+
+        assert!(!decision.should_send());
+        assert_eq!(
+            decision.sync_skip_reason(),
+            Some(SyncSkipReason::ContentTypeDisabled)
+        );
+    }
+
+    #[test]
+    fn unknown_representations_follow_recognised_ones() {
+        let policy = SyncFilterPolicyV1::new();
+
+        let with_text = policy.evaluate(
+            &snapshot(vec![
+                rep("text", "text", Some("text/plain"), 4),
+                rep("raw", "com.app.private", None, 4),
+            ]),
+            &text_only(),
+        );
+        assert!(with_text
+            .representations
+            .iter()
+            .all(|rep| rep.send_to_peers));
+
+        let alone = policy.evaluate(
+            &snapshot(vec![rep("raw", "com.app.private", None, 4)]),
+            &text_only(),
+        );
+        assert!(!alone.should_send());
+    }
+
+    #[test]
+    fn oversized_representations_are_not_sent() {
+        let settings = SyncSettings {
+            max_file_size_mb: 1,
+            ..SyncSettings::default()
+        };
+        let decision = SyncFilterPolicyV1::new().evaluate(
+            &snapshot(vec![
+                rep("small", "text", Some("text/plain"), 4),
+                rep("large", "public.png", Some("image/png"), 2 * 1024 * 1024),
+            ]),
+            &settings,
+        );
+
+        let large = &decision.representations[1];
+        assert!(!large.send_to_peers);
+        assert_eq!(large.skip_reason, Some(SyncSkipReason::ExceedsMaxFileSize));
+        assert!(decision.representations[0].send_to_peers);
+        assert_eq!(decision.sync_skip_reason(), None);
+    }
+
+    #[test]
+    fn auto_sync_off_and_sensitive_markers_block_sending() {
+        let policy = SyncFilterPolicyV1::new();
+        let text = snapshot(vec![rep("text", "text", Some("text/plain"), 4)]);
+
+        let off = policy.evaluate(
+            &text,
+            &SyncSettings {
+                auto_sync: false,
+                ..SyncSettings::default()
+            },
+        );
+        assert_eq!(
+            off.sync_skip_reason(),
+            Some(SyncSkipReason::AutoSyncDisabled)
+        );
+
+        let sensitive = policy.evaluate(
+            &snapshot(vec![
+                rep("text", "text", Some("text/plain"), 4),
+                rep("marker", "org.nspasteboard.ConcealedType", None, 1),
+            ]),
+            &SyncSettings::default(),
+        );
+        assert_eq!(
+            sensitive.sync_skip_reason(),
+            Some(SyncSkipReason::Sensitive)
+        );
+    }
+
+    #[test]
+    fn empty_content_type_mask_syncs_nothing() {
+        let settings = SyncSettings {
+            content_types: ContentTypes::default(),
+            ..SyncSettings::default()
+        };
+        let decision = SyncFilterPolicyV1::new().evaluate(
+            &snapshot(vec![
+                rep("text", "text", Some("text/plain"), 4),
+                rep("raw", "com.app.private", None, 4),
+            ]),
+            &settings,
+        );
+        assert!(!decision.should_send());
+        assert_eq!(
+            decision.sync_skip_reason(),
+            Some(SyncSkipReason::ContentTypeDisabled)
+        );
+    }
+}
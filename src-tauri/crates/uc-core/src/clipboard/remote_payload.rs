@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+
+use crate::clipboard::ContentHash;
+use crate::ids::RepresentationId;
+use crate::network::BlobManifest;
+use crate::security::model::{EncryptedBlob, EncryptionError};
+
+/// A representation synced without its bytes.
+///
+/// 延迟同步的表示形式：只保存清单，首次粘贴或打开时从提供方设备拉取。
+///
+/// Peers serve the representation sealed with the space key under
+/// [`crate::security::aad::for_remote_payload`], so `manifest` describes the
+/// sealed bytes while `content_hash` and `size_bytes` describe the plaintext.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemotePayloadRef {
+    pub representation_id: RepresentationId,
+    /// blake3 of the plaintext representation bytes
+    pub content_hash: ContentHash,
+    pub size_bytes: u64,
+    pub manifest: BlobManifest,
+}
+
+/// Encode a sealed remote payload for transfer.
+///
+/// Layout: `header_len u32 BE | EncryptedBlob JSON without ciphertext | ciphertext`.
+/// The ciphertext stays raw because JSON would spell out every byte.
+///
+/// 延迟同步数据的传输格式：密文保持原始字节，避免 JSON 编码导致体积膨胀。
+pub fn encode_sealed_payload(mut sealed: EncryptedBlob) -> Result<Vec<u8>, EncryptionError> {
+    let ciphertext = std::mem::take(&mut sealed.ciphertext);
+    let header = serde_json::to_vec(&sealed).map_err(|_| EncryptionError::CorruptedBlob)?;
+    let header_len = u32::try_from(header.len()).map_err(|_| EncryptionError::CorruptedBlob)?;
+    let mut bytes = Vec::with_capacity(4 + header.len() + ciphertext.len());
+    bytes.extend_from_slice(&header_len.to_be_bytes());
+    bytes.extend_from_slice(&header);
+    bytes.extend_from_slice(&ciphertext);
+    Ok(bytes)
+}
+
+/// Decode bytes produced by [`encode_sealed_payload`].
+pub fn decode_sealed_payload(bytes: &[u8]) -> Result<EncryptedBlob, EncryptionError> {
+    let (len, rest) = bytes
+        .split_first_chunk::<4>()
+        .ok_or(EncryptionError::CorruptedBlob)?;
+    let header_len = u32::from_be_bytes(*len) as usize;
+    if rest.len() < header_len {
+        return Err(EncryptionError::CorruptedBlob);
+    }
+    let (header, ciphertext) = rest.split_at(header_len);
+    let mut sealed: EncryptedBlob =
+        serde_json::from_slice(header).map_err(|_| EncryptionError::CorruptedBlob)?;
+    sealed.ciphertext = ciphertext.to_vec();
+    Ok(sealed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::model::{EncryptionAlgo, EncryptionFormatVersion};
+
+    #[test]
+    fn sealed_payload_round_trips_with_raw_ciphertext() {
+        let sealed = EncryptedBlob {
+            version: EncryptionFormatVersion::V1,
+            aead: EncryptionAlgo::XChaCha20Poly1305,
+            nonce: vec![1; 24],
+            ciphertext: vec![0xab; 1000],
+            aad_fingerprint: None,
+            key_epoch: 3,
+        };
+
+        let bytes = encode_sealed_payload(sealed.clone()).unwrap();
+
+        assert!(bytes.len() < 1000 + 200);
+        assert_eq!(decode_sealed_payload(&bytes).unwrap(), sealed);
+        assert!(decode_sealed_payload(&bytes[..10]).is_err());
+    }
+}
@@ -0,0 +1,234 @@
+use crate::clipboard::ContentKind;
+use crate::ids::EntryId;
+
+/// 写入全文索引的文档
+///
+/// One document per clipboard entry; `text` is the plain text picked by
+/// [`pick_search_text`] and is never persisted outside the search index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardSearchDocument {
+    pub entry_id: EntryId,
+    pub text: String,
+    pub content_kind: ContentKind,
+    pub source_device: String,
+    pub created_at_ms: i64,
+}
+
+/// A single search term parsed from the user query.
+/// 从用户查询中解析出的单个检索词。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardSearchTerm {
+    pub text: String,
+    /// Whether the term came from a `"quoted phrase"`.
+    pub phrase: bool,
+}
+
+/// 全文检索查询条件
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClipboardSearchQuery {
+    /// Raw query text; `"..."` marks a phrase, other words are matched independently.
+    pub text: String,
+    /// Restrict results to these content kinds (empty = any).
+    pub content_kinds: Vec<ContentKind>,
+    /// Inclusive lower bound on entry creation time.
+    pub from_ms: Option<i64>,
+    /// Inclusive upper bound on entry creation time.
+    pub to_ms: Option<i64>,
+    /// Restrict results to these source devices (empty = any).
+    pub source_devices: Vec<String>,
+    pub limit: usize,
+    pub offset: usize,
+}
+
+impl ClipboardSearchQuery {
+    /// Split the raw query into terms.
+    ///
+    /// Text inside double quotes becomes a single phrase term; everything else
+    /// is split on whitespace. An unterminated quote runs to the end of input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uc_core::clipboard::ClipboardSearchQuery;
+    ///
+    /// let query = ClipboardSearchQuery {
+    ///     text: r#"invoice "march 2026""#.to_string(),
+    ///     ..Default::default()
+    /// };
+    /// let terms = query.terms();
+    /// assert_eq!(terms.len(), 2);
+    /// assert!(!terms[0].phrase);
+    /// assert_eq!(terms[1].text, "march 2026");
+    /// assert!(terms[1].phrase);
+    /// ```
+    pub fn terms(&self) -> Vec<ClipboardSearchTerm> {
+        let mut terms = Vec::new();
+        for (index, segment) in self.text.split('"').enumerate() {
+            if index % 2 == 1 {
+                let phrase = segment.trim();
+                if !phrase.is_empty() {
+                    terms.push(ClipboardSearchTerm {
+                        text: phrase.to_string(),
+                        phrase: true,
+                    });
+                }
+            } else {
+                terms.extend(segment.split_whitespace().map(|word| ClipboardSearchTerm {
+                    text: word.to_string(),
+                    phrase: false,
+                }));
+            }
+        }
+        terms
+    }
+}
+
+/// Maximum number of characters of an entry kept in the search index.
+/// 每个条目写入全文索引的最大字符数。
+pub const SEARCH_TEXT_MAX_CHARS: usize = 32 * 1024;
+
+/// Byte prefix that always covers [`SEARCH_TEXT_MAX_CHARS`] characters of UTF-8.
+pub const SEARCH_TEXT_MAX_BYTES: usize = SEARCH_TEXT_MAX_CHARS * 4;
+
+/// Searchable text of a representation, from its bytes or a prefix of them.
+///
+/// Returns `None` for images and unknown content, invalid UTF-8 or blank
+/// text. A UTF-8 sequence cut off at the end of `bytes` is dropped, so a
+/// [`SEARCH_TEXT_MAX_BYTES`] prefix yields the same text as the full bytes.
+///
+/// # Examples
+///
+/// ```
+/// use uc_core::clipboard::{search_text, ContentKind};
+///
+/// assert_eq!(search_text(ContentKind::Text, b"  hi  "), Some("hi".to_string()));
+/// assert_eq!(search_text(ContentKind::Image, b"hi"), None);
+/// // "é" cut in half
+/// assert_eq!(search_text(ContentKind::Text, b"caf\xc3"), Some("caf".to_string()));
+/// ```
+pub fn search_text(kind: ContentKind, bytes: &[u8]) -> Option<String> {
+    if matches!(kind, ContentKind::Image | ContentKind::Unknown) {
+        return None;
+    }
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(err) if err.error_len().is_none() => {
+            std::str::from_utf8(&bytes[..err.valid_up_to()]).ok()?
+        }
+        Err(_) => return None,
+    };
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    Some(text.chars().take(SEARCH_TEXT_MAX_CHARS).collect())
+}
+
+/// Pick the document text among `(kind, text)` candidates.
+///
+/// Plain representations win over rich text so markup does not end up in
+/// the index. Capture and index rebuild both go through this, so an entry
+/// is indexed the same way whichever path produced its document.
+///
+/// 捕获与重建索引共用此规则：纯文本优先于富文本。
+pub fn pick_search_text(
+    candidates: impl IntoIterator<Item = (ContentKind, String)>,
+) -> Option<(String, ContentKind)> {
+    let mut rich_text = None;
+    for (kind, text) in candidates {
+        if kind == ContentKind::RichText {
+            rich_text.get_or_insert((text, kind));
+        } else {
+            return Some((text, kind));
+        }
+    }
+    rich_text
+}
+
+/// A piece of a highlighted snippet.
+/// 高亮摘要片段。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnippetSegment {
+    pub text: String,
+    pub highlighted: bool,
+}
+
+/// 检索结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClipboardSearchHit {
+    pub entry_id: EntryId,
+    /// Excerpt around the match, split into plain and highlighted segments.
+    pub snippet: Vec<SnippetSegment>,
+    pub content_kind: ContentKind,
+    pub source_device: String,
+    pub created_at_ms: i64,
+    /// Relevance score, lower is better (FTS5 bm25 convention).
+    pub rank: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(text: &str) -> Vec<(String, bool)> {
+        ClipboardSearchQuery {
+            text: text.to_string(),
+            ..Default::default()
+        }
+        .terms()
+        .into_iter()
+        .map(|t| (t.text, t.phrase))
+        .collect()
+    }
+
+    #[test]
+    fn terms_split_words_and_phrases() {
+        assert_eq!(
+            terms(r#"  foo "bar baz"  qux "#),
+            vec![
+                ("foo".to_string(), false),
+                ("bar baz".to_string(), true),
+                ("qux".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn terms_handle_empty_and_unterminated_quotes() {
+        assert!(terms("   ").is_empty());
+        assert!(terms(r#""""#).is_empty());
+        assert_eq!(
+            terms(r#"say "hello world"#),
+            vec![
+                ("say".to_string(), false),
+                ("hello world".to_string(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn pick_search_text_prefers_plain_over_rich_text() {
+        let picked = pick_search_text([
+            (ContentKind::RichText, "<b>hi</b>".to_string()),
+            (ContentKind::Text, "hi".to_string()),
+        ]);
+        assert_eq!(picked, Some(("hi".to_string(), ContentKind::Text)));
+
+        let picked = pick_search_text([(ContentKind::RichText, "<b>hi</b>".to_string())]);
+        assert_eq!(
+            picked,
+            Some(("<b>hi</b>".to_string(), ContentKind::RichText))
+        );
+    }
+
+    #[test]
+    fn search_text_of_prefix_matches_full_text() {
+        let full = "é".repeat(SEARCH_TEXT_MAX_CHARS + 10);
+        let prefix = &full.as_bytes()[..SEARCH_TEXT_MAX_BYTES.min(full.len()) - 1];
+
+        assert_eq!(
+            search_text(ContentKind::Text, prefix),
+            search_text(ContentKind::Text, full.as_bytes())
+        );
+    }
+}
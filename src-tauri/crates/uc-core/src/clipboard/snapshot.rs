@@ -130,6 +130,25 @@ impl PersistedClipboardRepresentation {
         }
     }
 
+    /// Create a representation whose bytes stay on peers until first use.
+    pub fn new_remote(
+        id: RepresentationId,
+        format_id: FormatId,
+        mime_type: Option<MimeType>,
+        size_bytes: i64,
+    ) -> Self {
+        Self {
+            id,
+            format_id,
+            mime_type,
+            size_bytes,
+            inline_data: None,
+            blob_id: None,
+            payload_state: PayloadAvailability::Remote,
+            last_error: None,
+        }
+    }
+
     pub fn payload_state(&self) -> PayloadAvailability {
         self.payload_state.clone()
     }
@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use crate::clipboard::{ContentKind, SystemClipboardSnapshot};
+use crate::ids::RepresentationId;
+
+/// 内容未同步到其他设备的原因
+///
+/// Why a snapshot (or one of its representations) was not sent to peers.
+/// Persisted on the entry so the UI can explain the decision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncSkipReason {
+    /// `sync.auto_sync` is switched off.
+    AutoSyncDisabled,
+    /// The content kind is switched off in `sync.content_types`.
+    ContentTypeDisabled,
+    /// The representation is larger than `sync.max_file_size_mb`.
+    ExceedsMaxFileSize,
+    /// The snapshot carries a platform marker for sensitive content.
+    Sensitive,
+}
+
+impl SyncSkipReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncSkipReason::AutoSyncDisabled => "auto_sync_disabled",
+            SyncSkipReason::ContentTypeDisabled => "content_type_disabled",
+            SyncSkipReason::ExceedsMaxFileSize => "exceeds_max_file_size",
+            SyncSkipReason::Sensitive => "sensitive",
+        }
+    }
+}
+
+impl FromStr for SyncSkipReason {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto_sync_disabled" => Ok(SyncSkipReason::AutoSyncDisabled),
+            "content_type_disabled" => Ok(SyncSkipReason::ContentTypeDisabled),
+            "exceeds_max_file_size" => Ok(SyncSkipReason::ExceedsMaxFileSize),
+            "sensitive" => Ok(SyncSkipReason::Sensitive),
+            other => Err(anyhow::anyhow!("unknown sync skip reason: {other}")),
+        }
+    }
+}
+
+/// 单个 representation 的同步决策
+///
+/// Local history always keeps every representation; this only decides what
+/// is sent to peers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepresentationFilterDecision {
+    pub representation_id: RepresentationId,
+    pub content_kind: ContentKind,
+    /// Send the representation to trusted peers.
+    pub send_to_peers: bool,
+    /// Set whenever `send_to_peers` is `false`.
+    pub skip_reason: Option<SyncSkipReason>,
+}
+
+/// 整个快照的同步决策
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotFilterDecision {
+    pub representations: Vec<RepresentationFilterDecision>,
+}
+
+impl SnapshotFilterDecision {
+    /// Whether at least one representation is sent to peers.
+    pub fn should_send(&self) -> bool {
+        self.representations.iter().any(|rep| rep.send_to_peers)
+    }
+
+    /// Why nothing is sent, or `None` when something is.
+    ///
+    /// 整个快照未同步的原因；只要有任何 representation 会被发送则为 `None`。
+    pub fn sync_skip_reason(&self) -> Option<SyncSkipReason> {
+        if self.should_send() {
+            return None;
+        }
+        self.representations.iter().find_map(|rep| rep.skip_reason)
+    }
+
+    /// Keep only the representations sent to peers.
+    pub fn retain_sendable(&self, snapshot: SystemClipboardSnapshot) -> SystemClipboardSnapshot {
+        let kept: HashSet<&RepresentationId> = self
+            .representations
+            .iter()
+            .filter(|rep| rep.send_to_peers)
+            .map(|rep| &rep.representation_id)
+            .collect();
+        SystemClipboardSnapshot {
+            ts_ms: snapshot.ts_ms,
+            representations: snapshot
+                .representations
+                .into_iter()
+                .filter(|rep| kept.contains(&rep.id))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sync_skip_reason_string_round_trip() {
+        for reason in [
+            SyncSkipReason::AutoSyncDisabled,
+            SyncSkipReason::ContentTypeDisabled,
+            SyncSkipReason::ExceedsMaxFileSize,
+            SyncSkipReason::Sensitive,
+        ] {
+            assert_eq!(reason.as_str().parse::<SyncSkipReason>().unwrap(), reason);
+        }
+        assert!("offline".parse::<SyncSkipReason>().is_err());
+    }
+
+    #[test]
+    fn snapshot_skip_reason_is_none_when_anything_is_sent() {
+        let decision = |send_to_peers, skip_reason| RepresentationFilterDecision {
+            representation_id: RepresentationId::new(),
+            content_kind: ContentKind::Text,
+            send_to_peers,
+            skip_reason,
+        };
+
+        let skipped = SnapshotFilterDecision {
+            representations: vec![decision(false, Some(SyncSkipReason::ContentTypeDisabled))],
+        };
+        assert_eq!(
+            skipped.sync_skip_reason(),
+            Some(SyncSkipReason::ContentTypeDisabled)
+        );
+
+        let partial = SnapshotFilterDecision {
+            representations: vec![
+                decision(false, Some(SyncSkipReason::ExceedsMaxFileSize)),
+                decision(true, None),
+            ],
+        };
+        assert_eq!(partial.sync_skip_reason(), None);
+    }
+}
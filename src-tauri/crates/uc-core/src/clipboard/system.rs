@@ -1,4 +1,5 @@
 use crate::{
+    clipboard::RemotePayloadRef,
     ids::{FormatId, RepresentationId},
     ContentHash, MimeType,
 };
@@ -70,11 +71,23 @@ impl SystemClipboardSnapshot {
     }
 
     pub fn snapshot_hash(&self) -> SnapshotHash {
+        self.snapshot_hash_with_remote(&[])
+    }
+
+    /// Snapshot hash where representations listed in `remote_payloads` are
+    /// hashed by their recorded content hash instead of their (empty) bytes.
+    ///
+    /// 延迟同步的表示不携带数据，使用其记录的明文内容哈希计算，结果与发送方一致。
+    pub fn snapshot_hash_with_remote(&self, remote_payloads: &[RemotePayloadRef]) -> SnapshotHash {
         let mut rep_hashes: Vec<[u8; 32]> = self
             .representations
             .iter()
             .map(|r| {
-                let content_hash = r.content_hash();
+                let content_hash = remote_payloads
+                    .iter()
+                    .find(|remote| remote.representation_id == r.id)
+                    .map(|remote| remote.content_hash.clone())
+                    .unwrap_or_else(|| r.content_hash().0);
                 let hash_bytes = content_hash.as_ref();
                 hash_bytes
                     .try_into()
@@ -2,7 +2,25 @@ use serde::{Deserialize, Serialize};
 
 use super::id_macro::impl_id;
 
+/// Id of the space every installation starts with
+///
+/// Matches the key scope used before multiple spaces existed, so existing
+/// key material keeps resolving.
+pub const DEFAULT_SPACE_ID: &str = "default";
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct SpaceId(String);
 
 impl_id!(SpaceId);
+
+impl SpaceId {
+    /// The space every installation starts with.
+    /// 每个安装默认拥有的空间。
+    pub fn default_space() -> Self {
+        Self::from(DEFAULT_SPACE_ID)
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.0 == DEFAULT_SPACE_ID
+    }
+}
@@ -17,6 +17,9 @@ pub mod ports;
 pub mod security;
 pub mod settings;
 pub mod setup;
+pub mod space;
+#[cfg(any(test, feature = "test-support"))]
+pub mod testing;
 
 // Re-export commonly used types at the crate root
 pub use blob::Blob;
@@ -0,0 +1,167 @@
+//! Protocol version negotiation and capability exchange.
+//! 协议版本协商与能力交换
+//!
+//! Peers exchange a [`HelloMessage`] when a connection becomes usable. Each
+//! side picks the newest business protocol version both support and the
+//! features both advertise; a peer with no common version is refused on the
+//! business protocol with `ProtocolDenyReason::NotSupported`.
+//!
+//! Peers that never answer a hello (builds older than negotiation) are
+//! treated as speaking [`BUSINESS_PROTOCOL_VERSION`] with no optional features.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::protocol::HelloMessage;
+
+/// Business protocol version spoken by this build
+pub const BUSINESS_PROTOCOL_VERSION: &str = "1.0.0";
+
+/// Business protocol versions this build accepts, newest first
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &[BUSINESS_PROTOCOL_VERSION];
+
+/// Optional features a peer may advertise
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtocolFeature {
+    /// Large payloads are pulled in chunks over the transfer protocol
+    ChunkedTransfer,
+    /// Payload compression before encryption
+    Compression,
+    /// Metadata-first sync; payloads are fetched on demand
+    LazyFetch,
+    /// Offline history catch-up on reconnect
+    CatchUp,
+}
+
+impl ProtocolFeature {
+    pub const ALL: [ProtocolFeature; 4] = [
+        ProtocolFeature::ChunkedTransfer,
+        ProtocolFeature::Compression,
+        ProtocolFeature::LazyFetch,
+        ProtocolFeature::CatchUp,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProtocolFeature::ChunkedTransfer => "chunked_transfer",
+            ProtocolFeature::Compression => "compression",
+            ProtocolFeature::LazyFetch => "lazy_fetch",
+            ProtocolFeature::CatchUp => "catch_up",
+        }
+    }
+
+    /// Parse a wire name; unknown names come from newer peers and are ignored.
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|feature| feature.as_str() == name)
+    }
+}
+
+/// Features implemented by this build
+pub const LOCAL_FEATURES: &[ProtocolFeature] = &[
+    ProtocolFeature::ChunkedTransfer,
+    ProtocolFeature::LazyFetch,
+    ProtocolFeature::CatchUp,
+];
+
+/// Outcome of a completed negotiation with one peer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerCapabilities {
+    pub peer_id: String,
+    pub app_version: String,
+    /// Newest business protocol version both sides support
+    pub protocol_version: String,
+    /// Features both sides advertise
+    pub features: Vec<ProtocolFeature>,
+    pub negotiated_at: DateTime<Utc>,
+}
+
+impl PeerCapabilities {
+    pub fn supports(&self, feature: ProtocolFeature) -> bool {
+        self.features.contains(&feature)
+    }
+
+    /// Negotiate with a peer's hello against what `local` offers.
+    ///
+    /// 选取双方都支持的最新协议版本与共同特性；没有共同版本时返回 `None`。
+    pub fn negotiate(
+        peer_id: String,
+        local: &HelloMessage,
+        remote: &HelloMessage,
+        now: DateTime<Utc>,
+    ) -> Option<Self> {
+        let protocol_version = local
+            .protocol_versions
+            .iter()
+            .find(|version| remote.protocol_versions.contains(version))?
+            .clone();
+        let features = local
+            .features
+            .iter()
+            .filter(|feature| remote.features.contains(feature))
+            .filter_map(|feature| ProtocolFeature::parse(feature))
+            .collect();
+        Some(Self {
+            peer_id,
+            app_version: remote.app_version.clone(),
+            protocol_version,
+            features,
+            negotiated_at: now,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hello(versions: &[&str], features: &[&str]) -> HelloMessage {
+        HelloMessage {
+            device_id: "peer".to_string(),
+            app_version: "0.2.0".to_string(),
+            protocol_versions: versions.iter().map(|v| v.to_string()).collect(),
+            features: features.iter().map(|f| f.to_string()).collect(),
+            reply: false,
+        }
+    }
+
+    #[test]
+    fn negotiate_picks_newest_common_version_and_shared_features() {
+        let local = hello(&["2.0.0", "1.0.0"], &["chunked_transfer", "lazy_fetch"]);
+        let remote = hello(&["1.0.0"], &["lazy_fetch", "compression", "telepathy"]);
+
+        let negotiated =
+            PeerCapabilities::negotiate("peer-1".to_string(), &local, &remote, Utc::now())
+                .expect("common version");
+
+        assert_eq!(negotiated.protocol_version, "1.0.0");
+        assert_eq!(negotiated.app_version, "0.2.0");
+        assert_eq!(negotiated.features, vec![ProtocolFeature::LazyFetch]);
+        assert!(!negotiated.supports(ProtocolFeature::ChunkedTransfer));
+    }
+
+    #[test]
+    fn negotiate_fails_without_common_version() {
+        let local = hello(&["1.0.0"], &["chunked_transfer"]);
+        let remote = hello(&["2.0.0"], &["chunked_transfer"]);
+
+        assert!(
+            PeerCapabilities::negotiate("peer-1".to_string(), &local, &remote, Utc::now())
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn feature_names_round_trip() {
+        for feature in ProtocolFeature::ALL {
+            assert_eq!(ProtocolFeature::parse(feature.as_str()), Some(feature));
+            assert_eq!(
+                serde_json::to_string(&feature).unwrap(),
+                format!("\"{}\"", feature.as_str())
+            );
+        }
+        assert_eq!(ProtocolFeature::parse("telepathy"), None);
+    }
+}
@@ -30,10 +30,12 @@ impl ConnectionPolicy {
                 pairing: true,
                 business: true,
             },
-            PairingState::Pending | PairingState::Revoked => AllowedProtocols {
-                pairing: true,
-                business: false,
-            },
+            PairingState::Pending | PairingState::Revoked | PairingState::Quarantined => {
+                AllowedProtocols {
+                    pairing: true,
+                    business: false,
+                }
+            }
         }
     }
 }
@@ -62,6 +64,13 @@ mod tests {
         assert!(allowed.allows(ProtocolKind::Business));
     }
 
+    #[test]
+    fn quarantined_allows_pairing_only() {
+        let allowed = ConnectionPolicy::allowed_protocols(PairingState::Quarantined);
+        assert!(allowed.allows(ProtocolKind::Pairing));
+        assert!(!allowed.allows(ProtocolKind::Business));
+    }
+
     #[test]
     fn revoked_allows_pairing_only() {
         let allowed = ConnectionPolicy::allowed_protocols(PairingState::Revoked);
@@ -0,0 +1,231 @@
+//! Per-device clipboard sync policy
+//!
+//! 每台已配对设备的同步策略：同步方向、允许交换的内容类型，以及收到的内容是否写入系统剪贴板。
+//! 连接策略只决定是否开放业务协议；本策略在发送扇出和接收路径上进一步裁剪内容。
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::clipboard::{ContentKind, SystemClipboardSnapshot};
+use crate::settings::model::ContentTypes;
+
+/// Which way clipboard content flows between this device and a paired device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncDirection {
+    #[default]
+    Bidirectional,
+    /// Only send local content to the device
+    SendOnly,
+    /// Only accept content from the device
+    ReceiveOnly,
+}
+
+impl SyncDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncDirection::Bidirectional => "bidirectional",
+            SyncDirection::SendOnly => "send_only",
+            SyncDirection::ReceiveOnly => "receive_only",
+        }
+    }
+}
+
+impl FromStr for SyncDirection {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bidirectional" => Ok(SyncDirection::Bidirectional),
+            "send_only" => Ok(SyncDirection::SendOnly),
+            "receive_only" => Ok(SyncDirection::ReceiveOnly),
+            other => Err(anyhow::anyhow!("unknown sync direction: {other}")),
+        }
+    }
+}
+
+/// What happens to content received from a paired device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ClipboardApplyMode {
+    /// Follow `sync.apply_remote_to_clipboard`
+    #[default]
+    AutoApply,
+    /// Only add it to history, never write the system clipboard
+    HistoryOnly,
+}
+
+impl ClipboardApplyMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ClipboardApplyMode::AutoApply => "auto_apply",
+            ClipboardApplyMode::HistoryOnly => "history_only",
+        }
+    }
+}
+
+impl FromStr for ClipboardApplyMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto_apply" => Ok(ClipboardApplyMode::AutoApply),
+            "history_only" => Ok(ClipboardApplyMode::HistoryOnly),
+            other => Err(anyhow::anyhow!("unknown clipboard apply mode: {other}")),
+        }
+    }
+}
+
+/// Sync policy of one paired device
+///
+/// `content_types` applies in both directions. Devices without a stored
+/// policy use the default: bidirectional, every content type, auto-apply.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceSyncPolicy {
+    #[serde(default)]
+    pub direction: SyncDirection,
+    #[serde(default = "ContentTypes::all")]
+    pub content_types: ContentTypes,
+    #[serde(default)]
+    pub apply_mode: ClipboardApplyMode,
+}
+
+impl Default for DeviceSyncPolicy {
+    fn default() -> Self {
+        Self {
+            direction: SyncDirection::Bidirectional,
+            content_types: ContentTypes::all(),
+            apply_mode: ClipboardApplyMode::AutoApply,
+        }
+    }
+}
+
+impl DeviceSyncPolicy {
+    /// Whether local content may be sent to the device.
+    pub fn allows_sending(&self) -> bool {
+        self.direction != SyncDirection::ReceiveOnly
+    }
+
+    /// Whether content from the device may be accepted.
+    pub fn allows_receiving(&self) -> bool {
+        self.direction != SyncDirection::SendOnly
+    }
+
+    /// Keep only the representations this device may exchange.
+    ///
+    /// 按内容类型裁剪快照；`Unknown` 表示仅在保留了已识别类型时随之保留。
+    /// Returns `None` when no recognised representation is left.
+    pub fn retain_allowed(
+        &self,
+        snapshot: SystemClipboardSnapshot,
+    ) -> Option<SystemClipboardSnapshot> {
+        let mut kept_recognised = false;
+        let mut kept = Vec::with_capacity(snapshot.representations.len());
+        for rep in snapshot.representations {
+            match ContentKind::classify(rep.mime.as_ref(), rep.format_id.as_ref()) {
+                ContentKind::Unknown => kept.push(rep),
+                kind if self.content_types.includes(kind) => {
+                    kept_recognised = true;
+                    kept.push(rep);
+                }
+                _ => {}
+            }
+        }
+
+        kept_recognised.then_some(SystemClipboardSnapshot {
+            ts_ms: snapshot.ts_ms,
+            representations: kept,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clipboard::{MimeType, ObservedClipboardRepresentation};
+    use crate::ids::{FormatId, RepresentationId};
+
+    fn rep(id: &str, format_id: &str, mime: Option<&str>) -> ObservedClipboardRepresentation {
+        ObservedClipboardRepresentation {
+            id: RepresentationId::from(id),
+            format_id: FormatId::from(format_id),
+            mime: mime.map(|m| MimeType(m.to_string())),
+            bytes: vec![1],
+        }
+    }
+
+    fn snapshot() -> SystemClipboardSnapshot {
+        SystemClipboardSnapshot {
+            ts_ms: 1,
+            representations: vec![
+                rep("text", "public.utf8-plain-text", Some("text/plain")),
+                rep("image", "public.png", Some("image/png")),
+                rep("custom", "com.example.private", None),
+            ],
+        }
+    }
+
+    fn no_images() -> DeviceSyncPolicy {
+        DeviceSyncPolicy {
+            content_types: ContentTypes {
+                image: false,
+                ..ContentTypes::all()
+            },
+            ..DeviceSyncPolicy::default()
+        }
+    }
+
+    #[test]
+    fn default_policy_is_bidirectional_and_keeps_everything() {
+        let policy = DeviceSyncPolicy::default();
+
+        assert!(policy.allows_sending());
+        assert!(policy.allows_receiving());
+        assert_eq!(
+            policy
+                .retain_allowed(snapshot())
+                .unwrap()
+                .representations
+                .len(),
+            3
+        );
+        assert!(!DeviceSyncPolicy {
+            direction: SyncDirection::ReceiveOnly,
+            ..DeviceSyncPolicy::default()
+        }
+        .allows_sending());
+    }
+
+    #[test]
+    fn disallowed_kinds_are_dropped_and_unknown_follows_recognised() {
+        let policy = no_images();
+
+        let kept = policy.retain_allowed(snapshot()).unwrap();
+        let ids: Vec<&str> = kept
+            .representations
+            .iter()
+            .map(|rep| rep.id.as_ref())
+            .collect();
+        assert_eq!(ids, vec!["text", "custom"]);
+
+        let image_only = SystemClipboardSnapshot {
+            ts_ms: 1,
+            representations: vec![
+                rep("image", "public.png", Some("image/png")),
+                rep("custom", "com.example.private", None),
+            ],
+        };
+        assert!(policy.retain_allowed(image_only).is_none());
+    }
+
+    #[test]
+    fn policy_deserializes_missing_fields_to_defaults() {
+        let policy: DeviceSyncPolicy =
+            serde_json::from_str(r#"{"direction":"send_only"}"#).unwrap();
+
+        assert_eq!(policy.direction, SyncDirection::SendOnly);
+        assert_eq!(policy.content_types, ContentTypes::all());
+        assert_eq!(policy.apply_mode, ClipboardApplyMode::AutoApply);
+    }
+}
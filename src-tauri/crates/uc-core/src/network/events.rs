@@ -1,4 +1,8 @@
-use super::protocol::{ClipboardMessage, PairingMessage, PairingRequest, PairingResponse};
+use super::capabilities::PeerCapabilities;
+use super::protocol::{
+    CatchUpRequest, ClipboardMessage, KeySlotUpdateMessage, PairingMessage, PairingRequest,
+    PairingResponse,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -25,6 +29,40 @@ pub enum ProtocolDenyReason {
     NotSupported,
 }
 
+/// Transport carrying a peer connection
+///
+/// 连接所使用的传输层。同时存在多条连接时优先 QUIC，其次 TCP，
+/// 经中继转发的连接最后考虑。
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum TransportKind {
+    Tcp,
+    Quic,
+    /// Circuit through a relay node (no direct connection yet)
+    Relay,
+}
+
+impl TransportKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransportKind::Tcp => "tcp",
+            TransportKind::Quic => "quic",
+            TransportKind::Relay => "relay",
+        }
+    }
+}
+
+/// Progress of a chunked blob transfer
+///
+/// `Inbound`: we are pulling from `peer_id`; `Outbound`: `peer_id` pulls from us.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TransferProgress {
+    pub peer_id: String,
+    pub content_hash: String,
+    pub direction: ProtocolDirection,
+    pub transferred_bytes: u64,
+    pub total_bytes: u64,
+}
+
 /// A peer discovered via mDNS
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiscoveredPeer {
@@ -46,6 +84,24 @@ pub struct ConnectedPeer {
     pub connected_at: DateTime<Utc>,
 }
 
+/// An address of a local network interface
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NetworkInterfaceInfo {
+    pub name: String,
+    pub address: String,
+    /// Whether the address passes the interface allow/deny rules
+    pub eligible: bool,
+}
+
+/// Local interfaces and the addresses currently listened on
+///
+/// 本机网卡及当前监听地址，便于在其他设备上手动填写
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LocalNetworkInfo {
+    pub listen_addresses: Vec<String>,
+    pub interfaces: Vec<NetworkInterfaceInfo>,
+}
+
 /// Core network events (domain layer)
 /// Infrastructure-specific events should extend this
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,16 +118,41 @@ pub enum NetworkEvent {
     // Connection events
     PeerConnected(ConnectedPeer),
     PeerDisconnected(String), // peer_id
+    /// An outbound dial to `address` reached `peer_id`
+    ///
+    /// 主动拨号成功，可记录为该对端的已知地址
+    PeerAddressConfirmed {
+        peer_id: String,
+        address: String,
+    },
 
     // Readiness events (protocol-agnostic)
     /// A peer is now ready to receive broadcast messages
     PeerReady {
         peer_id: String,
+        transport: TransportKind,
+    },
+    /// The preferred connection of a ready peer moved to another transport
+    ///
+    /// 已就绪对端的首选连接切换了传输层（例如 QUIC 连接建立或断开）
+    PeerTransportChanged {
+        peer_id: String,
+        transport: TransportKind,
     },
     /// A peer is no longer ready to receive broadcast messages
     PeerNotReady {
         peer_id: String,
     },
+    /// A heartbeat arrived from a peer
+    ///
+    /// `rtt_ms` is set when the heartbeat answers one of our probes.
+    PeerHeartbeat {
+        peer_id: String,
+        seen_at: DateTime<Utc>,
+        rtt_ms: Option<u64>,
+    },
+    /// Version and capability handshake with a peer completed
+    PeerCapabilitiesNegotiated(PeerCapabilities),
 
     // Pairing events
     PairingMessageReceived {
@@ -107,6 +188,30 @@ pub enum NetworkEvent {
         peer_id: String,
         error: String,
     },
+    /// Pairing with `peer_id` was revoked
+    ///
+    /// `Outbound`: our revocation notice reached the peer.
+    /// `Inbound`: the peer unpaired us and asks to be marked `Revoked`.
+    PairingRevoked {
+        peer_id: String,
+        direction: ProtocolDirection,
+    },
+    /// A peer confirmed it received our revocation notice
+    ///
+    /// 对端确认已收到撤销通知，可删除待送达记录。
+    PairingRevocationAcknowledged {
+        peer_id: String,
+    },
+    /// A peer claimed a known device id in its hello or announce with an
+    /// identity key that differs from the fingerprint pinned for that device;
+    /// the peer is now quarantined if it was paired
+    ///
+    /// 身份指纹与设备 ID 的记录不一致：设备已隔离，需要用户重新验证（重新配对）或撤销。
+    PeerIdentityMismatch {
+        peer_id: String,
+        expected_fingerprint: String,
+        presented_fingerprint: String,
+    },
 
     // Clipboard events
     ClipboardReceived(ClipboardMessage),
@@ -115,6 +220,30 @@ pub enum NetworkEvent {
         peer_count: usize,
     },
 
+    // Transfer events
+    TransferProgress(TransferProgress),
+    /// A pull gave up after exhausting its retries; received chunks are kept
+    /// so a later fetch of the same blob resumes.
+    TransferFailed {
+        peer_id: String,
+        content_hash: String,
+        error: String,
+    },
+
+    // Key management events
+    /// A trusted peer pushed a rewrapped keyslot (passphrase change)
+    KeySlotUpdateReceived {
+        peer_id: String,
+        message: KeySlotUpdateMessage,
+    },
+
+    // History events
+    /// A trusted peer asked for clipboard history it missed while offline
+    CatchUpRequested {
+        peer_id: String,
+        request: CatchUpRequest,
+    },
+
     // Status events
     StatusChanged(NetworkStatus),
     ProtocolDenied {
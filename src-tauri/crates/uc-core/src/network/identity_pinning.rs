@@ -0,0 +1,162 @@
+//! Trust-on-first-use pinning of peer identity keys
+//!
+//! A libp2p peer id is derived from the identity key, so a key change always
+//! shows up as a new peer id. Pins are therefore looked up by the device id a
+//! peer claims: a known device id arriving from a new peer id is a changed key.
+//!
+//! 首次配对时按设备 ID 记录对端身份指纹（TOFU）。已知设备 ID 以新的 peer id 出现
+//! （重新配对或握手）时比对指纹；不一致即提醒用户，重新配对会隔离旧记录。
+
+use serde::{Deserialize, Serialize};
+
+use crate::crypto::IdentityFingerprint;
+use crate::network::{PairedDevice, PairingState};
+use crate::PeerId;
+
+/// Outcome of checking a connection's identity key against the pinned fingerprint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IdentityCheck {
+    /// No pin to check against: unknown or revoked peer
+    Unpinned,
+    /// Presented key matches the pinned fingerprint
+    Verified,
+    /// Already quarantined by an earlier mismatch
+    Quarantined,
+    /// Presented key differs from the pinned fingerprint
+    Mismatch {
+        expected_fingerprint: String,
+        presented_fingerprint: String,
+    },
+}
+
+/// The record pinned for `device_id`, preferring the one paired as `peer_id`.
+///
+/// Revoked records pin nothing. Among the rest, a trusted or pending record
+/// wins over a quarantined one.
+///
+/// 查找 `device_id` 对应的配对记录，优先匹配同一 peer id。
+pub fn pinned_device<'a>(
+    devices: &'a [PairedDevice],
+    peer_id: &PeerId,
+    device_id: &str,
+) -> Option<&'a PairedDevice> {
+    let candidates = || {
+        devices.iter().filter(move |device| {
+            device.device_id == device_id && device.pairing_state != PairingState::Revoked
+        })
+    };
+    candidates()
+        .find(|device| &device.peer_id == peer_id)
+        .or_else(|| {
+            candidates().find(|device| {
+                matches!(
+                    device.pairing_state,
+                    PairingState::Trusted | PairingState::Pending
+                )
+            })
+        })
+        .or_else(|| candidates().next())
+}
+
+impl IdentityCheck {
+    /// Compare the identity presented on a connection with the pinned device record.
+    ///
+    /// A pinned value that no longer parses cannot vouch for the peer and is
+    /// treated as a mismatch.
+    pub fn evaluate(device: Option<&PairedDevice>, presented: &IdentityFingerprint) -> Self {
+        let Some(device) = device else {
+            return Self::Unpinned;
+        };
+        match device.pairing_state {
+            PairingState::Revoked => return Self::Unpinned,
+            PairingState::Quarantined => return Self::Quarantined,
+            PairingState::Pending | PairingState::Trusted => {}
+        }
+
+        let pinned = device.identity_fingerprint.parse::<IdentityFingerprint>();
+        match pinned {
+            Ok(pinned) if pinned.verify(presented).is_ok() => Self::Verified,
+            _ => Self::Mismatch {
+                expected_fingerprint: device.identity_fingerprint.clone(),
+                presented_fingerprint: presented.to_string(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn device(state: PairingState, pubkey: &[u8]) -> PairedDevice {
+        PairedDevice {
+            peer_id: PeerId::from("peer-1"),
+            pairing_state: state,
+            identity_fingerprint: IdentityFingerprint::from_public_key(pubkey)
+                .unwrap()
+                .to_string(),
+            paired_at: Utc::now(),
+            last_seen_at: None,
+            device_name: "Peer".to_string(),
+            device_id: "device-peer".to_string(),
+        }
+    }
+
+    #[test]
+    fn same_key_is_verified_and_unknown_peer_is_unpinned() {
+        let presented = IdentityFingerprint::from_public_key(&[1; 32]).unwrap();
+
+        assert_eq!(
+            IdentityCheck::evaluate(Some(&device(PairingState::Trusted, &[1; 32])), &presented),
+            IdentityCheck::Verified
+        );
+        assert_eq!(
+            IdentityCheck::evaluate(None, &presented),
+            IdentityCheck::Unpinned
+        );
+        assert_eq!(
+            IdentityCheck::evaluate(Some(&device(PairingState::Revoked, &[2; 32])), &presented),
+            IdentityCheck::Unpinned
+        );
+    }
+
+    #[test]
+    fn known_device_id_from_new_peer_is_a_mismatch() {
+        let pinned = device(PairingState::Trusted, &[1; 32]);
+        let devices = vec![pinned.clone()];
+        let presented = IdentityFingerprint::from_public_key(&[2; 32]).unwrap();
+
+        let found = pinned_device(&devices, &PeerId::from("peer-2"), "device-peer");
+        assert_eq!(found, Some(&pinned));
+        assert!(matches!(
+            IdentityCheck::evaluate(found, &presented),
+            IdentityCheck::Mismatch { .. }
+        ));
+        assert_eq!(
+            pinned_device(&devices, &PeerId::from("peer-2"), "device-other"),
+            None
+        );
+    }
+
+    #[test]
+    fn changed_key_is_a_mismatch_until_quarantined() {
+        let presented = IdentityFingerprint::from_public_key(&[2; 32]).unwrap();
+        let trusted = device(PairingState::Trusted, &[1; 32]);
+
+        assert_eq!(
+            IdentityCheck::evaluate(Some(&trusted), &presented),
+            IdentityCheck::Mismatch {
+                expected_fingerprint: trusted.identity_fingerprint.clone(),
+                presented_fingerprint: presented.to_string(),
+            }
+        );
+        assert_eq!(
+            IdentityCheck::evaluate(
+                Some(&device(PairingState::Quarantined, &[1; 32])),
+                &presented
+            ),
+            IdentityCheck::Quarantined
+        );
+    }
+}
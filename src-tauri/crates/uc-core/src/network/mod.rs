@@ -1,27 +1,46 @@
 //! Network protocol types.
 
+pub mod capabilities;
 pub mod connection_policy;
+pub mod device_sync_policy;
 pub mod events;
+pub mod identity_pinning;
 pub mod paired_device;
+pub mod pairing_invitation;
 pub mod pairing_state_machine;
+pub mod peer_address;
+pub mod presence;
 pub mod protocol;
 pub mod protocol_ids;
 
+pub use capabilities::{
+    PeerCapabilities, ProtocolFeature, BUSINESS_PROTOCOL_VERSION, LOCAL_FEATURES,
+    SUPPORTED_PROTOCOL_VERSIONS,
+};
 pub use connection_policy::{
     AllowedProtocols, ConnectionPolicy, ProtocolKind, ResolvedConnectionPolicy,
 };
+pub use device_sync_policy::{ClipboardApplyMode, DeviceSyncPolicy, SyncDirection};
 pub use events::{
-    ConnectedPeer, DiscoveredPeer, NetworkEvent, NetworkStatus, ProtocolDenyReason,
-    ProtocolDirection,
+    ConnectedPeer, DiscoveredPeer, LocalNetworkInfo, NetworkEvent, NetworkInterfaceInfo,
+    NetworkStatus, ProtocolDenyReason, ProtocolDirection, TransferProgress, TransportKind,
 };
+pub use identity_pinning::{pinned_device, IdentityCheck};
 pub use paired_device::{PairedDevice, PairingState};
+pub use pairing_invitation::{
+    InvitationSecret, PairingInvitation, PairingInvitationError, PAIRING_INVITATION_TTL_SECS,
+};
 pub use pairing_state_machine::{
     CancellationBy, FailureReason, PairingAction, PairingEvent, PairingRole, PairingStateMachine,
     SessionId, TimeoutKind,
 };
+pub use peer_address::{normalize_peer_address, PeerAddressError};
+pub use presence::{PeerLiveness, PeerPresence, HEARTBEAT_INTERVAL};
 pub use protocol::{
-    ClipboardMessage, DeviceAnnounceMessage, HeartbeatMessage, PairingBusy, PairingCancel,
-    PairingChallenge, PairingChallengeResponse, PairingConfirm, PairingKeyslotOffer,
-    PairingMessage, PairingReject, PairingRequest, PairingResponse, ProtocolMessage,
+    BlobManifest, CatchUpRequest, ChunkRequest, ClipboardMessage, ClipboardPayload,
+    DeviceAnnounceMessage, HeartbeatMessage, HelloMessage, KeySlotUpdateMessage, PairingBusy,
+    PairingCancel, PairingChallenge, PairingChallengeResponse, PairingConfirm, PairingKeyslotOffer,
+    PairingMessage, PairingReject, PairingRequest, PairingResponse, PairingRevoke, PairingRevokeAck, ProtocolMessage,
+    LAZY_PAYLOAD_MIN_BYTES, MAX_TRANSFER_CHUNK_BYTES, TRANSFER_CHUNK_BYTES,
 };
 pub use protocol_ids::ProtocolId;
@@ -7,6 +7,9 @@ pub enum PairingState {
     Pending,
     Trusted,
     Revoked,
+    /// The peer presented an identity key that differs from the pinned one;
+    /// business traffic stays blocked until it is re-paired or revoked.
+    Quarantined,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -17,6 +20,11 @@ pub struct PairedDevice {
     pub paired_at: DateTime<Utc>,
     pub last_seen_at: Option<DateTime<Utc>>,
     pub device_name: String,
+    /// Stable device id the peer presented while pairing; stamped as
+    /// `origin_device_id` on everything it syncs. Empty for devices paired
+    /// before it was recorded.
+    #[serde(default)]
+    pub device_id: String,
 }
 
 #[cfg(test)]
@@ -32,6 +40,7 @@ mod tests {
             paired_at: Utc::now(),
             last_seen_at: None,
             device_name: "Test Device".to_string(),
+            device_id: "device-peer".to_string(),
         };
 
         let json = serde_json::to_string(&device).unwrap();
@@ -0,0 +1,382 @@
+//! Out-of-band pairing invitations.
+//! 带外配对邀请（二维码 / 配对 URI）
+//!
+//! The responder shows a pairing URI, usually as a QR code:
+//!
+//! ```text
+//! uniclipboard://pair?v=1&peer=<peer id>&fp=<identity fingerprint>
+//!     &secret=<base64url, 32 bytes>&exp=<unix seconds>&addr=<multiaddr>...
+//! ```
+//!
+//! The initiator dials the embedded addresses directly and proves knowledge
+//! of the one-time secret in its `PairingRequest`. The responder answers with
+//! a proof over the full transcript, and the initiator checks the responder's
+//! identity key against the embedded fingerprint. Both sides are then
+//! authenticated without a short-code comparison.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use subtle::ConstantTimeEq;
+use thiserror::Error;
+
+use crate::crypto::IdentityFingerprint;
+
+/// URI scheme of pairing invitations
+pub const PAIRING_URI_SCHEME: &str = "uniclipboard";
+
+/// Invitation format version carried in the `v` parameter
+pub const PAIRING_INVITATION_VERSION: &str = "1";
+
+/// How long a freshly created invitation stays valid
+pub const PAIRING_INVITATION_TTL_SECS: i64 = 600;
+
+const SECRET_LEN: usize = 32;
+const REQUEST_PROOF_LABEL: &[u8] = b"uc-pairing-invite-request-v1";
+const CHALLENGE_PROOF_LABEL: &[u8] = b"uc-pairing-invite-challenge-v1";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PairingInvitationError {
+    #[error("not a pairing URI")]
+    NotPairingUri,
+
+    #[error("unsupported invitation version: {0}")]
+    UnsupportedVersion(String),
+
+    #[error("missing invitation parameter: {0}")]
+    MissingParameter(&'static str),
+
+    #[error("invalid invitation parameter {name}: {reason}")]
+    InvalidParameter { name: &'static str, reason: String },
+
+    #[error("invitation has expired")]
+    Expired,
+}
+
+/// One-time secret shared through the invitation
+///
+/// 一次性邀请密钥，仅通过二维码/URI 传递，用于对配对记录做密钥化证明。
+#[derive(Clone)]
+pub struct InvitationSecret([u8; SECRET_LEN]);
+
+impl InvitationSecret {
+    pub fn generate() -> Self {
+        let mut bytes = [0u8; SECRET_LEN];
+        rand::rng().fill_bytes(&mut bytes);
+        Self(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        <[u8; SECRET_LEN]>::try_from(bytes).ok().map(Self)
+    }
+
+    /// Proof sent by the initiator in `PairingRequest.invitation_proof`.
+    pub fn request_proof(
+        &self,
+        session_id: &str,
+        initiator_nonce: &[u8],
+        initiator_pubkey: &[u8],
+        responder_peer_id: &str,
+    ) -> Vec<u8> {
+        self.prove(
+            REQUEST_PROOF_LABEL,
+            &[
+                session_id.as_bytes(),
+                initiator_nonce,
+                initiator_pubkey,
+                responder_peer_id.as_bytes(),
+            ],
+        )
+    }
+
+    /// Proof sent by the responder in `PairingChallenge.invitation_proof`.
+    pub fn challenge_proof(
+        &self,
+        session_id: &str,
+        initiator_nonce: &[u8],
+        responder_nonce: &[u8],
+        initiator_pubkey: &[u8],
+        responder_pubkey: &[u8],
+    ) -> Vec<u8> {
+        self.prove(
+            CHALLENGE_PROOF_LABEL,
+            &[
+                session_id.as_bytes(),
+                initiator_nonce,
+                responder_nonce,
+                initiator_pubkey,
+                responder_pubkey,
+            ],
+        )
+    }
+
+    /// Constant-time comparison of a received proof with the expected one.
+    pub fn proof_matches(expected: &[u8], received: &[u8]) -> bool {
+        expected.ct_eq(received).into()
+    }
+
+    /// Keyed BLAKE3 over length-prefixed transcript parts.
+    fn prove(&self, label: &[u8], parts: &[&[u8]]) -> Vec<u8> {
+        let mut hasher = blake3::Hasher::new_keyed(&self.0);
+        hasher.update(label);
+        for part in parts {
+            hasher.update(&(part.len() as u64).to_be_bytes());
+            hasher.update(part);
+        }
+        hasher.finalize().as_bytes().to_vec()
+    }
+}
+
+impl std::fmt::Debug for InvitationSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("InvitationSecret([REDACTED])")
+    }
+}
+
+impl Drop for InvitationSecret {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+        self.0.zeroize();
+    }
+}
+
+/// Everything needed to reach and authenticate the responder
+#[derive(Debug, Clone)]
+pub struct PairingInvitation {
+    pub peer_id: String,
+    /// Listen multiaddrs of the responder
+    pub addresses: Vec<String>,
+    /// Identity fingerprint of the responder
+    pub fingerprint: IdentityFingerprint,
+    pub secret: InvitationSecret,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl PairingInvitation {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Render as a `uniclipboard://pair?...` URI.
+    pub fn to_uri(&self) -> String {
+        let mut uri = format!(
+            "{}://pair?v={}&peer={}&fp={}&secret={}&exp={}",
+            PAIRING_URI_SCHEME,
+            PAIRING_INVITATION_VERSION,
+            encode_component(&self.peer_id),
+            encode_component(&self.fingerprint.to_string()),
+            URL_SAFE_NO_PAD.encode(self.secret.0),
+            self.expires_at.timestamp(),
+        );
+        for address in &self.addresses {
+            uri.push_str("&addr=");
+            uri.push_str(&encode_component(address));
+        }
+        uri
+    }
+
+    /// Parse a pasted or scanned pairing URI.
+    ///
+    /// 解析粘贴或扫描得到的配对 URI；过期的邀请直接拒绝。
+    pub fn parse_uri(input: &str, now: DateTime<Utc>) -> Result<Self, PairingInvitationError> {
+        let query = input
+            .trim()
+            .strip_prefix(PAIRING_URI_SCHEME)
+            .and_then(|rest| rest.strip_prefix("://pair?"))
+            .ok_or(PairingInvitationError::NotPairingUri)?;
+
+        let mut version = None;
+        let mut peer_id = None;
+        let mut fingerprint = None;
+        let mut secret = None;
+        let mut expires_at = None;
+        let mut addresses = Vec::new();
+
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let value = decode_component(value);
+            match name {
+                "v" => version = Some(value),
+                "peer" => peer_id = Some(value),
+                "fp" => fingerprint = Some(value),
+                "secret" => secret = Some(value),
+                "exp" => expires_at = Some(value),
+                "addr" => addresses.push(value),
+                // Parameters added by newer builds are ignored
+                _ => {}
+            }
+        }
+
+        let version = version.ok_or(PairingInvitationError::MissingParameter("v"))?;
+        if version != PAIRING_INVITATION_VERSION {
+            return Err(PairingInvitationError::UnsupportedVersion(version));
+        }
+
+        let peer_id = peer_id
+            .filter(|peer_id| !peer_id.is_empty())
+            .ok_or(PairingInvitationError::MissingParameter("peer"))?;
+        let fingerprint = fingerprint
+            .ok_or(PairingInvitationError::MissingParameter("fp"))
+            .and_then(|value| {
+                IdentityFingerprint::from_str(&value).map_err(|err| {
+                    PairingInvitationError::InvalidParameter {
+                        name: "fp",
+                        reason: err.to_string(),
+                    }
+                })
+            })?;
+        let secret = secret
+            .ok_or(PairingInvitationError::MissingParameter("secret"))
+            .and_then(|value| {
+                URL_SAFE_NO_PAD
+                    .decode(value)
+                    .ok()
+                    .and_then(|bytes| InvitationSecret::from_bytes(&bytes))
+                    .ok_or(PairingInvitationError::InvalidParameter {
+                        name: "secret",
+                        reason: format!("expected {SECRET_LEN} bytes of base64url"),
+                    })
+            })?;
+        let expires_at = expires_at
+            .ok_or(PairingInvitationError::MissingParameter("exp"))
+            .and_then(|value| {
+                value
+                    .parse::<i64>()
+                    .ok()
+                    .and_then(|secs| DateTime::from_timestamp(secs, 0))
+                    .ok_or(PairingInvitationError::InvalidParameter {
+                        name: "exp",
+                        reason: "expected unix seconds".to_string(),
+                    })
+            })?;
+        if addresses.is_empty() {
+            return Err(PairingInvitationError::MissingParameter("addr"));
+        }
+
+        let invitation = Self {
+            peer_id,
+            addresses,
+            fingerprint,
+            secret,
+            expires_at,
+        };
+        if invitation.is_expired(now) {
+            return Err(PairingInvitationError::Expired);
+        }
+        Ok(invitation)
+    }
+}
+
+/// Percent-encode the characters that carry meaning in a query string.
+fn encode_component(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'%' | b'&' | b'=' | b'#' | b'+' | b'?' | b' ' => {
+                encoded.push_str(&format!("%{byte:02X}"));
+            }
+            _ => encoded.push(byte as char),
+        }
+    }
+    encoded
+}
+
+fn decode_component(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' && index + 2 < bytes.len() {
+            if let Ok(byte) = u8::from_str_radix(&value[index + 1..index + 3], 16) {
+                decoded.push(byte);
+                index += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[index]);
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn invitation(now: DateTime<Utc>) -> PairingInvitation {
+        PairingInvitation {
+            peer_id: "12D3KooWResponder".to_string(),
+            addresses: vec![
+                "/ip4/192.168.1.20/tcp/4001".to_string(),
+                "/ip6/fe80::1/udp/4001/quic-v1".to_string(),
+            ],
+            fingerprint: IdentityFingerprint::from_public_key(&[7; 32]).unwrap(),
+            secret: InvitationSecret::generate(),
+            expires_at: now + Duration::seconds(PAIRING_INVITATION_TTL_SECS),
+        }
+    }
+
+    #[test]
+    fn uri_round_trips() {
+        let now = Utc::now();
+        let original = invitation(now);
+
+        let parsed = PairingInvitation::parse_uri(&original.to_uri(), now).expect("parse");
+
+        assert_eq!(parsed.peer_id, original.peer_id);
+        assert_eq!(parsed.addresses, original.addresses);
+        assert_eq!(parsed.fingerprint, original.fingerprint);
+        assert_eq!(parsed.expires_at.timestamp(), original.expires_at.timestamp());
+        assert_eq!(
+            parsed.secret.request_proof("s", b"n", b"k", "p"),
+            original.secret.request_proof("s", b"n", b"k", "p")
+        );
+    }
+
+    #[test]
+    fn parse_rejects_expired_and_foreign_uris() {
+        let now = Utc::now();
+        let uri = invitation(now).to_uri();
+
+        assert_eq!(
+            PairingInvitation::parse_uri(&uri, now + Duration::hours(1)).unwrap_err(),
+            PairingInvitationError::Expired
+        );
+        assert_eq!(
+            PairingInvitation::parse_uri("https://example.com/pair?v=1", now).unwrap_err(),
+            PairingInvitationError::NotPairingUri
+        );
+        assert_eq!(
+            PairingInvitation::parse_uri(&uri.replace("v=1", "v=9"), now).unwrap_err(),
+            PairingInvitationError::UnsupportedVersion("9".to_string())
+        );
+    }
+
+    #[test]
+    fn proofs_depend_on_secret_and_transcript() {
+        let secret = InvitationSecret::generate();
+        let other = InvitationSecret::generate();
+
+        let proof = secret.challenge_proof("s", b"a", b"b", b"k1", b"k2");
+
+        assert!(InvitationSecret::proof_matches(
+            &proof,
+            &secret.challenge_proof("s", b"a", b"b", b"k1", b"k2")
+        ));
+        assert!(!InvitationSecret::proof_matches(
+            &proof,
+            &other.challenge_proof("s", b"a", b"b", b"k1", b"k2")
+        ));
+        assert!(!InvitationSecret::proof_matches(
+            &proof,
+            &secret.challenge_proof("s", b"a", b"b", b"k1", b"k3")
+        ));
+        // Length prefixes keep part boundaries unambiguous
+        assert_ne!(
+            secret.request_proof("ab", b"c", b"k", "p"),
+            secret.request_proof("a", b"bc", b"k", "p")
+        );
+    }
+}
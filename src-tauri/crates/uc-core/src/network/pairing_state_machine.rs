@@ -28,6 +28,7 @@ use crate::crypto::pin_hash::{hash_pin, verify_pin};
 use crate::crypto::{IdentityFingerprint, ShortCodeGenerator};
 use crate::network::{
     paired_device::{PairedDevice, PairingState as PairedDeviceState},
+    pairing_invitation::{InvitationSecret, PairingInvitation},
     protocol::{
         PairingCancel, PairingChallenge, PairingConfirm, PairingMessage, PairingReject,
         PairingRequest, PairingResponse,
@@ -284,6 +285,15 @@ pub enum PairingAction {
         device: PairedDevice,
     },
 
+    /// 已配对设备以新的身份密钥重新配对，旧记录已被隔离，需提醒用户
+    IdentityChanged {
+        session_id: SessionId,
+        /// Peer id of the quarantined earlier pairing
+        peer_id: String,
+        expected_fingerprint: String,
+        presented_fingerprint: String,
+    },
+
     /// 记录状态转换日志 (用于审计)
     LogTransition {
         session_id: SessionId,
@@ -380,6 +390,8 @@ struct PairingContext {
     peer_identity_pubkey: Option<Vec<u8>>,
     /// 对端设备名称
     peer_device_name: Option<String>,
+    /// 对端设备ID
+    peer_device_id: Option<String>,
     /// 短码 (用户确认码)
     short_code: Option<String>,
     /// 当前 PIN
@@ -390,6 +402,12 @@ struct PairingContext {
     peer_fingerprint: Option<String>,
     /// 会话创建时间
     created_at: Option<DateTime<Utc>>,
+    /// 配对邀请密钥 (带外配对)
+    invitation_secret: Option<InvitationSecret>,
+    /// 邀请中携带的响应方指纹 (Initiator)
+    invitation_fingerprint: Option<IdentityFingerprint>,
+    /// 对端已通过邀请证明,无需比对短码
+    invitation_verified: bool,
 }
 
 impl Default for PairingContext {
@@ -405,11 +423,15 @@ impl Default for PairingContext {
             local_identity_pubkey: None,
             peer_identity_pubkey: None,
             peer_device_name: None,
+            peer_device_id: None,
             short_code: None,
             pin: None,
             local_fingerprint: None,
             peer_fingerprint: None,
             created_at: None,
+            invitation_secret: None,
+            invitation_fingerprint: None,
+            invitation_verified: false,
         }
     }
 }
@@ -462,6 +484,24 @@ impl PairingStateMachine {
         }
     }
 
+    /// 响应方:接受持有该邀请密钥的配对请求,跳过用户审批与短码比对
+    pub fn with_invitation_secret(mut self, secret: InvitationSecret) -> Self {
+        self.context.invitation_secret = Some(secret);
+        self
+    }
+
+    /// 发起方:按扫描到的邀请配对,校验响应方指纹与邀请证明
+    pub fn with_invitation(mut self, invitation: &PairingInvitation) -> Self {
+        self.context.invitation_secret = Some(invitation.secret.clone());
+        self.context.invitation_fingerprint = Some(invitation.fingerprint.clone());
+        self
+    }
+
+    /// 对端是否已通过邀请证明
+    pub fn invitation_verified(&self) -> bool {
+        self.context.invitation_verified
+    }
+
     /// 获取当前状态
     pub fn state(&self) -> &PairingState {
         &self.state
@@ -576,6 +616,14 @@ impl PairingStateMachine {
                     }
                 };
 
+                let invitation_proof = self.context.invitation_secret.as_ref().map(|secret| {
+                    secret.request_proof(
+                        &session_id,
+                        &local_nonce,
+                        &local_identity_pubkey,
+                        &peer_id,
+                    )
+                });
                 let request = PairingRequest {
                     session_id: session_id.clone(),
                     device_name: local_device_name,
@@ -583,6 +631,7 @@ impl PairingStateMachine {
                     peer_id: peer_id.clone(),
                     identity_pubkey: local_identity_pubkey,
                     nonce: local_nonce,
+                    invitation_proof,
                 };
 
                 let deadline = now + Duration::seconds(self.policy.step_timeout_secs);
@@ -619,8 +668,40 @@ impl PairingStateMachine {
                 self.context.peer_nonce = Some(request.nonce.clone());
                 self.context.peer_identity_pubkey = Some(request.identity_pubkey.clone());
                 self.context.peer_device_name = Some(request.device_name.clone());
+                self.context.peer_device_id = Some(request.device_id.clone());
                 self.context.created_at = Some(now);
 
+                if let Some(proof) = request.invitation_proof.as_deref() {
+                    let verified = self
+                        .context
+                        .invitation_secret
+                        .as_ref()
+                        .is_some_and(|secret| {
+                            let expected = secret.request_proof(
+                                &request.session_id,
+                                &request.nonce,
+                                &request.identity_pubkey,
+                                &request.peer_id,
+                            );
+                            InvitationSecret::proof_matches(&expected, proof)
+                        });
+                    if !verified {
+                        return self.reject_invitation(request.session_id, None);
+                    }
+
+                    // 扫描邀请即视为用户已批准,直接发送 Challenge
+                    self.context.invitation_verified = true;
+                    self.state = PairingState::AwaitingUserApproval {
+                        session_id: request.session_id.clone(),
+                    };
+                    return self.transition(
+                        PairingEvent::UserAccept {
+                            session_id: request.session_id,
+                        },
+                        now,
+                    );
+                }
+
                 let deadline = now + Duration::seconds(self.policy.user_verification_timeout_secs);
                 let actions = vec![PairingAction::StartTimer {
                     session_id: request.session_id.clone(),
@@ -649,10 +730,19 @@ impl PairingStateMachine {
                     }
                 };
 
+                if self.context.invitation_secret.is_some() {
+                    if !self.invited_challenge_is_authentic(&challenge, &local_identity_pubkey) {
+                        return self
+                            .reject_invitation(session_id, Some(TimeoutKind::WaitingChallenge));
+                    }
+                    self.context.invitation_verified = true;
+                }
+
                 self.context.peer_nonce = Some(challenge.nonce.clone());
                 self.context.peer_identity_pubkey = Some(challenge.identity_pubkey.clone());
                 self.context.pin = Some(challenge.pin.clone());
                 self.context.peer_device_name = Some(challenge.device_name.clone());
+                self.context.peer_device_id = Some(challenge.device_id.clone());
                 self.context.created_at = Some(now);
 
                 let local_nonce = self
@@ -705,6 +795,35 @@ impl PairingStateMachine {
 
                 let expires_at =
                     now + Duration::seconds(self.policy.user_verification_timeout_secs);
+
+                if self.context.invitation_verified {
+                    // 邀请已认证响应方,无需用户比对短码
+                    self.state = PairingState::AwaitingUserConfirm {
+                        session_id: session_id.clone(),
+                        short_code,
+                        peer_fingerprint,
+                        expires_at,
+                    };
+                    let (state, accept_actions) = self.transition(
+                        PairingEvent::UserAccept {
+                            session_id: session_id.clone(),
+                        },
+                        now,
+                    );
+                    let mut actions = vec![
+                        PairingAction::CancelTimer {
+                            session_id: session_id.clone(),
+                            kind: TimeoutKind::WaitingChallenge,
+                        },
+                        PairingAction::ShowVerifying {
+                            session_id,
+                            peer_display_name: challenge.device_name,
+                        },
+                    ];
+                    actions.extend(accept_actions);
+                    return (state, actions);
+                }
+
                 let actions = vec![
                     PairingAction::CancelTimer {
                         session_id: session_id.clone(),
@@ -1081,6 +1200,20 @@ impl PairingStateMachine {
                 self.context.local_fingerprint = Some(local_fingerprint.clone());
                 self.context.peer_fingerprint = Some(peer_fingerprint.clone());
 
+                let invitation_proof = self
+                    .context
+                    .invitation_secret
+                    .as_ref()
+                    .filter(|_| self.context.invitation_verified)
+                    .map(|secret| {
+                        secret.challenge_proof(
+                            &session_id,
+                            &peer_nonce,
+                            &nonce,
+                            &peer_identity_pubkey,
+                            &local_identity_pubkey,
+                        )
+                    });
                 let challenge = PairingChallenge {
                     session_id: session_id.clone(),
                     pin,
@@ -1088,6 +1221,7 @@ impl PairingStateMachine {
                     device_id: local_device_id,
                     identity_pubkey: local_identity_pubkey.clone(),
                     nonce,
+                    invitation_proof,
                 };
 
                 let deadline = now + Duration::seconds(self.policy.step_timeout_secs);
@@ -1096,18 +1230,26 @@ impl PairingStateMachine {
                     .peer_device_name
                     .clone()
                     .unwrap_or_else(|| "Unknown Device".to_string());
-                let actions = vec![
-                    PairingAction::CancelTimer {
+                let show = if self.context.invitation_verified {
+                    PairingAction::ShowVerifying {
                         session_id: session_id.clone(),
-                        kind: TimeoutKind::UserApproval,
-                    },
+                        peer_display_name,
+                    }
+                } else {
                     PairingAction::ShowVerification {
                         session_id: session_id.clone(),
                         short_code,
                         local_fingerprint,
                         peer_fingerprint,
                         peer_display_name,
+                    }
+                };
+                let actions = vec![
+                    PairingAction::CancelTimer {
+                        session_id: session_id.clone(),
+                        kind: TimeoutKind::UserApproval,
                     },
+                    show,
                     PairingAction::Send {
                         peer_id,
                         message: PairingMessage::Challenge(challenge),
@@ -1478,6 +1620,60 @@ impl PairingStateMachine {
         (PairingState::Cancelled { session_id, by }, actions)
     }
 
+    /// 校验 Challenge 是否来自邀请中的响应方:身份指纹一致且持有邀请密钥
+    fn invited_challenge_is_authentic(
+        &self,
+        challenge: &PairingChallenge,
+        local_identity_pubkey: &[u8],
+    ) -> bool {
+        let (Some(secret), Some(expected_fingerprint), Some(local_nonce)) = (
+            self.context.invitation_secret.as_ref(),
+            self.context.invitation_fingerprint.as_ref(),
+            self.context.local_nonce.as_ref(),
+        ) else {
+            return false;
+        };
+        let fingerprint_matches = IdentityFingerprint::from_public_key(&challenge.identity_pubkey)
+            .is_ok_and(|fingerprint| &fingerprint == expected_fingerprint);
+        let proof_matches = challenge.invitation_proof.as_deref().is_some_and(|proof| {
+            let expected = secret.challenge_proof(
+                &challenge.session_id,
+                local_nonce,
+                &challenge.nonce,
+                local_identity_pubkey,
+                &challenge.identity_pubkey,
+            );
+            InvitationSecret::proof_matches(&expected, proof)
+        });
+        fingerprint_matches && proof_matches
+    }
+
+    /// 邀请证明校验失败:拒绝对端并结束会话
+    fn reject_invitation(
+        &self,
+        session_id: SessionId,
+        cancel_timer: Option<TimeoutKind>,
+    ) -> (PairingState, Vec<PairingAction>) {
+        let (state, mut actions) = self.fail_with_reason(
+            session_id.clone(),
+            FailureReason::CryptoError("Invitation verification failed".to_string()),
+        );
+        actions.insert(
+            0,
+            PairingAction::Send {
+                peer_id: self.context.peer_id.clone().unwrap_or_default(),
+                message: PairingMessage::Reject(PairingReject {
+                    session_id: session_id.clone(),
+                    reason: Some("invitation_invalid".to_string()),
+                }),
+            },
+        );
+        if let Some(kind) = cancel_timer {
+            actions.insert(0, PairingAction::CancelTimer { session_id, kind });
+        }
+        (state, actions)
+    }
+
     fn build_paired_device(&self, now: DateTime<Utc>) -> Result<PairedDevice, FailureReason> {
         let peer_id = self
             .context
@@ -1499,6 +1695,7 @@ impl PairingStateMachine {
             .peer_device_name
             .clone()
             .unwrap_or_else(|| "Unknown Device".to_string());
+        let device_id = self.context.peer_device_id.clone().unwrap_or_default();
 
         Ok(PairedDevice {
             peer_id: PeerId::from(peer_id),
@@ -1507,6 +1704,7 @@ impl PairingStateMachine {
             paired_at: now,
             last_seen_at: None,
             device_name,
+            device_id,
         })
     }
 }
@@ -1544,6 +1742,7 @@ mod tests {
             peer_id: "peer-remote".to_string(),
             identity_pubkey: vec![2; 32],
             nonce: vec![9; 16],
+            invitation_proof: None,
         }
     }
 
@@ -1555,6 +1754,7 @@ mod tests {
             device_id: "device-2".to_string(),
             identity_pubkey: vec![2; 32],
             nonce: vec![9; 16],
+            invitation_proof: None,
         }
     }
 
@@ -1587,6 +1787,7 @@ mod tests {
             peer_id: "peer-initiator".to_string(),
             identity_pubkey: initiator_pubkey.clone(),
             nonce: initiator_nonce.clone(),
+            invitation_proof: None,
         };
 
         let (state, _actions) = sm.handle_event(
@@ -2066,6 +2267,7 @@ mod tests {
             peer_id: "peer-remote".to_string(),
             identity_pubkey: vec![2; 32],
             nonce: vec![9; 16],
+            invitation_proof: None,
         };
 
         sm.handle_event(
@@ -2114,6 +2316,7 @@ mod tests {
         // Extract the paired_device from Finalizing state
         if let PairingState::Finalizing { paired_device, .. } = state {
             assert_eq!(paired_device.device_name, "PeerDevice");
+            assert_eq!(paired_device.device_id, "device-2");
         } else {
             panic!("Expected Finalizing state, got {:?}", state);
         }
@@ -2170,6 +2373,7 @@ mod tests {
             device_id: "device-2".to_string(),
             identity_pubkey: vec![2; 32],
             nonce: vec![9; 16],
+            invitation_proof: None,
         };
 
         sm.handle_event(
@@ -2209,6 +2413,7 @@ mod tests {
             device_id: "device-2".to_string(),
             identity_pubkey: vec![2; 32],
             nonce: vec![9; 16],
+            invitation_proof: None,
         };
         sm.handle_event(
             PairingEvent::RecvChallenge {
@@ -2258,6 +2463,7 @@ mod tests {
             peer_id: "spoofed-peer-id".to_string(), // Malicious/Wrong ID
             identity_pubkey: vec![2; 32],
             nonce: vec![9; 16],
+            invitation_proof: None,
         };
 
         let sender_peer_id = "trusted-sender-id".to_string();
@@ -2274,4 +2480,163 @@ mod tests {
         // The context should reflect the trusted sender_peer_id, not the spoofed one
         assert_eq!(sm.context.peer_id, Some(sender_peer_id));
     }
+
+    fn invitation_for(responder_pubkey: &[u8]) -> PairingInvitation {
+        PairingInvitation {
+            peer_id: "peer-responder".to_string(),
+            addresses: vec!["/ip4/10.0.0.2/tcp/4001".to_string()],
+            fingerprint: IdentityFingerprint::from_public_key(responder_pubkey).unwrap(),
+            secret: InvitationSecret::generate(),
+            expires_at: Utc::now() + Duration::minutes(10),
+        }
+    }
+
+    fn sent_message(actions: &[PairingAction]) -> Option<PairingMessage> {
+        actions.iter().find_map(|action| match action {
+            PairingAction::Send { message, .. } => Some(message.clone()),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn invited_pairing_skips_user_approval_and_short_code() {
+        let invitation = invitation_for(&[1; 32]);
+        let mut responder = PairingStateMachine::new_with_local_identity(
+            "Responder".to_string(),
+            "device-responder".to_string(),
+            vec![1; 32],
+        )
+        .with_invitation_secret(invitation.secret.clone());
+        let mut initiator = PairingStateMachine::new_with_local_identity(
+            "Initiator".to_string(),
+            "device-initiator".to_string(),
+            vec![2; 32],
+        )
+        .with_invitation(&invitation);
+
+        let (_, actions) = initiator.handle_event(
+            PairingEvent::StartPairing {
+                role: PairingRole::Initiator,
+                peer_id: invitation.peer_id.clone(),
+            },
+            Utc::now(),
+        );
+        let Some(PairingMessage::Request(request)) = sent_message(&actions) else {
+            panic!("expected request");
+        };
+        assert!(request.invitation_proof.is_some());
+        let session_id = request.session_id.clone();
+
+        let (state, actions) = responder.handle_event(
+            PairingEvent::RecvRequest {
+                session_id: session_id.clone(),
+                sender_peer_id: "peer-initiator".to_string(),
+                request,
+            },
+            Utc::now(),
+        );
+        assert!(matches!(state, PairingState::ChallengeSent { .. }));
+        assert!(responder.invitation_verified());
+        assert!(!actions
+            .iter()
+            .any(|action| matches!(action, PairingAction::ShowVerification { .. })));
+        let Some(PairingMessage::Challenge(challenge)) = sent_message(&actions) else {
+            panic!("expected challenge");
+        };
+
+        let (state, actions) = initiator.handle_event(
+            PairingEvent::RecvChallenge {
+                session_id: session_id.clone(),
+                challenge,
+            },
+            Utc::now(),
+        );
+        assert!(matches!(state, PairingState::ResponseSent { .. }));
+        assert!(!actions
+            .iter()
+            .any(|action| matches!(action, PairingAction::ShowVerification { .. })));
+        assert!(matches!(
+            sent_message(&actions),
+            Some(PairingMessage::Response(PairingResponse {
+                accepted: true,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn invited_request_with_wrong_secret_is_rejected() {
+        let invitation = invitation_for(&[1; 32]);
+        let mut responder = PairingStateMachine::new_with_local_identity(
+            "Responder".to_string(),
+            "device-responder".to_string(),
+            vec![1; 32],
+        )
+        .with_invitation_secret(InvitationSecret::generate());
+
+        let mut request = build_request("session-1");
+        request.invitation_proof = Some(invitation.secret.request_proof(
+            "session-1",
+            &request.nonce,
+            &[2; 32],
+            "x",
+        ));
+
+        let (state, actions) = responder.handle_event(
+            PairingEvent::RecvRequest {
+                session_id: "session-1".to_string(),
+                sender_peer_id: "peer-initiator".to_string(),
+                request,
+            },
+            Utc::now(),
+        );
+
+        assert!(matches!(state, PairingState::Failed { .. }));
+        assert!(matches!(
+            sent_message(&actions),
+            Some(PairingMessage::Reject(PairingReject { .. }))
+        ));
+    }
+
+    #[test]
+    fn invited_challenge_from_other_identity_is_rejected() {
+        let invitation = invitation_for(&[1; 32]);
+        let mut initiator = PairingStateMachine::new_with_local_identity(
+            "Initiator".to_string(),
+            "device-initiator".to_string(),
+            vec![2; 32],
+        )
+        .with_invitation(&invitation);
+        initiator.handle_event(
+            PairingEvent::StartPairing {
+                role: PairingRole::Initiator,
+                peer_id: invitation.peer_id.clone(),
+            },
+            Utc::now(),
+        );
+        let session_id = initiator.context.session_id.clone().unwrap();
+        let local_nonce = initiator.context.local_nonce.clone().unwrap();
+
+        // Knows the secret but presents a different identity key
+        let mut challenge = build_challenge(&session_id);
+        challenge.identity_pubkey = vec![3; 32];
+        challenge.invitation_proof = Some(invitation.secret.challenge_proof(
+            &session_id,
+            &local_nonce,
+            &challenge.nonce,
+            &[2; 32],
+            &[3; 32],
+        ));
+
+        let (state, _actions) = initiator.handle_event(
+            PairingEvent::RecvChallenge {
+                session_id,
+                challenge,
+            },
+            Utc::now(),
+        );
+
+        assert!(matches!(state, PairingState::Failed { .. }));
+        assert!(!initiator.invitation_verified());
+    }
 }
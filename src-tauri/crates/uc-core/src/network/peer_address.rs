@@ -0,0 +1,133 @@
+//! Manually entered peer addresses.
+//! 手动输入的对端地址
+//!
+//! Users type either a multiaddr (`/ip4/10.0.0.5/tcp/4001`) or a plain
+//! `host:port`. Everything is stored and dialed in multiaddr form.
+
+use std::net::SocketAddr;
+
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PeerAddressError {
+    #[error("peer address is empty")]
+    Empty,
+
+    #[error("invalid peer address: {0}")]
+    Invalid(String),
+}
+
+/// Normalize user input into a multiaddr string.
+///
+/// 将用户输入规范化为 multiaddr 字符串。
+///
+/// - Input starting with `/` is taken as a multiaddr and kept as is.
+/// - `1.2.3.4:4001` / `[::1]:4001` become `/ip4/…/tcp/…` / `/ip6/…/tcp/…`.
+/// - `host.local:4001` becomes `/dns/host.local/tcp/4001`.
+///
+/// # Examples
+///
+/// ```
+/// use uc_core::network::normalize_peer_address;
+///
+/// assert_eq!(
+///     normalize_peer_address("192.168.1.20:4001").unwrap(),
+///     "/ip4/192.168.1.20/tcp/4001"
+/// );
+/// ```
+pub fn normalize_peer_address(input: &str) -> Result<String, PeerAddressError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(PeerAddressError::Empty);
+    }
+
+    if input.starts_with('/') {
+        let components = input.trim_end_matches('/').split('/').skip(1).count();
+        if components < 2 || input.contains("//") {
+            return Err(PeerAddressError::Invalid(input.to_string()));
+        }
+        return Ok(input.trim_end_matches('/').to_string());
+    }
+
+    if let Ok(socket) = input.parse::<SocketAddr>() {
+        if socket.port() == 0 {
+            return Err(PeerAddressError::Invalid(input.to_string()));
+        }
+        return Ok(match socket {
+            SocketAddr::V4(v4) => format!("/ip4/{}/tcp/{}", v4.ip(), v4.port()),
+            SocketAddr::V6(v6) => format!("/ip6/{}/tcp/{}", v6.ip(), v6.port()),
+        });
+    }
+
+    let (host, port) = input
+        .rsplit_once(':')
+        .ok_or_else(|| PeerAddressError::Invalid(input.to_string()))?;
+    let port = port
+        .parse::<u16>()
+        .ok()
+        .filter(|port| *port != 0)
+        .ok_or_else(|| PeerAddressError::Invalid(input.to_string()))?;
+    let host_is_valid = !host.is_empty()
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.');
+    if !host_is_valid {
+        return Err(PeerAddressError::Invalid(input.to_string()));
+    }
+
+    Ok(format!("/dns/{}/tcp/{}", host, port))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn socket_addresses_become_ip_multiaddrs() {
+        assert_eq!(
+            normalize_peer_address(" 10.0.0.5:4001 ").unwrap(),
+            "/ip4/10.0.0.5/tcp/4001"
+        );
+        assert_eq!(
+            normalize_peer_address("[fe80::1]:4001").unwrap(),
+            "/ip6/fe80::1/tcp/4001"
+        );
+    }
+
+    #[test]
+    fn host_names_become_dns_multiaddrs() {
+        assert_eq!(
+            normalize_peer_address("office-mac.local:4001").unwrap(),
+            "/dns/office-mac.local/tcp/4001"
+        );
+    }
+
+    #[test]
+    fn multiaddrs_are_kept() {
+        assert_eq!(
+            normalize_peer_address("/ip4/10.0.0.5/tcp/4001/").unwrap(),
+            "/ip4/10.0.0.5/tcp/4001"
+        );
+    }
+
+    #[test]
+    fn malformed_input_is_rejected() {
+        assert_eq!(normalize_peer_address("  "), Err(PeerAddressError::Empty));
+        for input in [
+            "10.0.0.5",
+            "host:0",
+            "host:port",
+            ":4001",
+            "/ip4",
+            "bad host:1",
+        ] {
+            assert!(
+                matches!(
+                    normalize_peer_address(input),
+                    Err(PeerAddressError::Invalid(_))
+                ),
+                "{input} should be rejected"
+            );
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! Peer presence derived from heartbeats.
+//! 基于心跳的对端在线状态
+//!
+//! Trusted peers exchange a heartbeat every [`HEARTBEAT_INTERVAL`] on the
+//! business protocol. A peer that answered recently is `Online`; one that
+//! stays connected but stopped answering, or was seen a short while ago,
+//! is `Idle`; anything older is `Unreachable`.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Interval between heartbeat probes to each ready peer
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+
+/// A peer is `Online` while its last heartbeat is at most this old
+/// (three missed probes).
+pub const ONLINE_WINDOW: Duration = Duration::from_secs(45);
+
+/// A disconnected peer stays `Idle` for this long after its last heartbeat.
+pub const IDLE_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+/// Latest heartbeat observed from a peer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerLiveness {
+    pub peer_id: String,
+    pub last_heartbeat_at: DateTime<Utc>,
+    /// Round-trip time of the latest answered probe
+    pub rtt_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PeerPresence {
+    Online,
+    Idle,
+    Unreachable,
+}
+
+impl PeerPresence {
+    /// Presence of a peer last seen at `last_seen`.
+    ///
+    /// 根据最近一次心跳时间与当前连接状态计算在线状态。
+    pub fn evaluate(connected: bool, last_seen: Option<DateTime<Utc>>, now: DateTime<Utc>) -> Self {
+        let age = last_seen.map(|seen| (now - seen).to_std().unwrap_or(Duration::ZERO));
+        match age {
+            Some(age) if connected && age <= ONLINE_WINDOW => Self::Online,
+            _ if connected => Self::Idle,
+            Some(age) if age <= IDLE_WINDOW => Self::Idle,
+            _ => Self::Unreachable,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ago(now: DateTime<Utc>, secs: i64) -> Option<DateTime<Utc>> {
+        Some(now - chrono::Duration::seconds(secs))
+    }
+
+    #[test]
+    fn connected_peer_is_online_until_heartbeats_stop() {
+        let now = Utc::now();
+
+        assert_eq!(
+            PeerPresence::evaluate(true, ago(now, 10), now),
+            PeerPresence::Online
+        );
+        assert_eq!(
+            PeerPresence::evaluate(true, ago(now, 60), now),
+            PeerPresence::Idle
+        );
+        assert_eq!(PeerPresence::evaluate(true, None, now), PeerPresence::Idle);
+    }
+
+    #[test]
+    fn disconnected_peer_goes_idle_then_unreachable() {
+        let now = Utc::now();
+
+        assert_eq!(
+            PeerPresence::evaluate(false, ago(now, 10), now),
+            PeerPresence::Idle
+        );
+        assert_eq!(
+            PeerPresence::evaluate(false, ago(now, 10 * 60), now),
+            PeerPresence::Unreachable
+        );
+        assert_eq!(
+            PeerPresence::evaluate(false, None, now),
+            PeerPresence::Unreachable
+        );
+    }
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// Summary of recent history sent to a peer that just became ready.
+///
+/// 对端重新上线后发送的近期历史摘要。对端据此补发本设备错过的、由其自身捕获的条目。
+///
+/// `known_hashes` lists the `SnapshotHash` of every live entry captured at or
+/// after `since_ms`; the peer skips entries whose outgoing snapshot matches one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatchUpRequest {
+    pub origin_device_id: String,
+    /// Start of the catch-up window (ms since epoch)
+    pub since_ms: i64,
+    pub known_hashes: Vec<String>,
+}
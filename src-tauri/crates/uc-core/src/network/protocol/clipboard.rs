@@ -1,6 +1,14 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::BlobManifest;
+use crate::clipboard::{RemotePayloadRef, SystemClipboardSnapshot};
+
+/// Representations at least this large are synced metadata-first.
+///
+/// 超过该大小的表示形式只同步元数据，完整内容在首次使用时按内容哈希拉取。
+pub const LAZY_PAYLOAD_MIN_BYTES: usize = 8 * 1024 * 1024;
+
 /// Clipboard content broadcast via GossipSub
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClipboardMessage {
@@ -10,4 +18,69 @@ pub struct ClipboardMessage {
     pub timestamp: DateTime<Utc>,
     pub origin_device_id: String,
     pub origin_device_name: String,
+    /// Set for large payloads: `encrypted_content` is empty and the bytes are
+    /// pulled from the sender over the transfer protocol.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_manifest: Option<BlobManifest>,
+    /// Resent in answer to a [`super::CatchUpRequest`]; receivers only add
+    /// it to history and never write it to the system clipboard.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub catch_up: bool,
+    /// Peer the message arrived from, stamped by the transport on receipt.
+    /// Never sent on the wire.
+    #[serde(skip)]
+    pub sender_peer_id: Option<String>,
+}
+
+/// Plaintext of [`ClipboardMessage::encrypted_content`].
+///
+/// Representations listed in `remote_payloads` are sent with empty bytes.
+/// Receivers built before lazy sync read this as a plain snapshot and ignore
+/// the extra field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipboardPayload {
+    #[serde(flatten)]
+    pub snapshot: SystemClipboardSnapshot,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remote_payloads: Vec<RemotePayloadRef>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clipboard::{ContentHash, ObservedClipboardRepresentation};
+    use crate::ids::{FormatId, RepresentationId};
+
+    #[test]
+    fn payload_without_remote_refs_reads_as_plain_snapshot() {
+        let rep_id = RepresentationId::new();
+        let payload = ClipboardPayload {
+            snapshot: SystemClipboardSnapshot {
+                ts_ms: 1,
+                representations: vec![ObservedClipboardRepresentation {
+                    id: rep_id.clone(),
+                    format_id: FormatId::from("public.png"),
+                    mime: None,
+                    bytes: vec![],
+                }],
+            },
+            remote_payloads: vec![RemotePayloadRef {
+                representation_id: rep_id.clone(),
+                content_hash: ContentHash::from(&[7; 32]),
+                size_bytes: 10,
+                manifest: BlobManifest::from_bytes(&[7; 10], 4),
+            }],
+        };
+        let json = serde_json::to_vec(&payload).unwrap();
+
+        let snapshot: SystemClipboardSnapshot = serde_json::from_slice(&json).unwrap();
+        assert_eq!(snapshot.representations[0].id, rep_id);
+
+        let decoded: ClipboardPayload = serde_json::from_slice(&json).unwrap();
+        assert_eq!(decoded.remote_payloads, payload.remote_payloads);
+
+        let legacy = serde_json::to_vec(&payload.snapshot).unwrap();
+        let decoded: ClipboardPayload = serde_json::from_slice(&legacy).unwrap();
+        assert!(decoded.remote_payloads.is_empty());
+    }
 }
@@ -5,6 +5,10 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeviceAnnounceMessage {
     pub peer_id: String,
+    /// Stable device id the receiver checks against its identity pins; empty
+    /// from builds that predate it.
+    #[serde(default)]
+    pub device_id: String,
     pub device_name: String,
     pub timestamp: DateTime<Utc>,
 }
@@ -2,8 +2,25 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Heartbeat message for connection liveness
+///
+/// 心跳消息：`reply_to` 为空表示探测，非空表示应答并回传探测的发送时间，
+/// 发送方据此用本地时钟计算往返时延。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HeartbeatMessage {
     pub device_id: String,
     pub timestamp: DateTime<Utc>,
+    /// `timestamp` of the probe this message answers
+    #[serde(default)]
+    pub reply_to: Option<DateTime<Utc>>,
+}
+
+impl HeartbeatMessage {
+    /// Reply to this probe on behalf of `device_id`.
+    pub fn reply(&self, device_id: String, now: DateTime<Utc>) -> Self {
+        Self {
+            device_id,
+            timestamp: now,
+            reply_to: Some(self.timestamp),
+        }
+    }
 }
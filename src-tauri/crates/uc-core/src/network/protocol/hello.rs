@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+use crate::network::capabilities::{LOCAL_FEATURES, SUPPORTED_PROTOCOL_VERSIONS};
+
+/// Version and capability handshake sent when a connection becomes usable
+///
+/// 握手消息：交换应用版本、支持的协议版本与特性。`reply` 为真表示应答，
+/// 收到后不再回复，避免双方互相回应。
+///
+/// Features travel as plain names so a newer peer's unknown flags are
+/// ignored instead of failing to decode.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HelloMessage {
+    pub device_id: String,
+    pub app_version: String,
+    /// Supported business protocol versions, newest first
+    pub protocol_versions: Vec<String>,
+    pub features: Vec<String>,
+    #[serde(default)]
+    pub reply: bool,
+}
+
+impl HelloMessage {
+    /// Hello advertising what this build supports.
+    pub fn local(device_id: String, app_version: String, reply: bool) -> Self {
+        Self {
+            device_id,
+            app_version,
+            protocol_versions: SUPPORTED_PROTOCOL_VERSIONS
+                .iter()
+                .map(|version| version.to_string())
+                .collect(),
+            features: LOCAL_FEATURES
+                .iter()
+                .map(|feature| feature.as_str().to_string())
+                .collect(),
+            reply,
+        }
+    }
+}
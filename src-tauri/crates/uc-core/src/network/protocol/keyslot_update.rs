@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Rewrapped keyslot pushed to trusted peers after a passphrase change.
+///
+/// `encrypted_payload` is sealed with the space `MasterKey`, so only devices
+/// that already hold the key can read the new keyslot. The KEK is never sent;
+/// receivers unlock the keyslot with the passphrase entered on that device.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct KeySlotUpdateMessage {
+    pub id: String,
+    pub origin_device_id: String,
+    pub encrypted_payload: Vec<u8>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl std::fmt::Debug for KeySlotUpdateMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeySlotUpdateMessage")
+            .field("id", &self.id)
+            .field("origin_device_id", &self.origin_device_id)
+            .field("encrypted_payload_len", &self.encrypted_payload.len())
+            .field("timestamp", &self.timestamp)
+            .finish()
+    }
+}
@@ -1,15 +1,24 @@
+mod catch_up;
 mod clipboard;
 mod device_announce;
 mod heartbeat;
+mod hello;
+mod keyslot_update;
 mod pairing;
 mod protocol_message;
 mod space;
+mod transfer;
 
-pub use clipboard::ClipboardMessage;
+pub use catch_up::CatchUpRequest;
+pub use clipboard::{ClipboardMessage, ClipboardPayload, LAZY_PAYLOAD_MIN_BYTES};
 pub use device_announce::DeviceAnnounceMessage;
 pub use heartbeat::HeartbeatMessage;
+pub use hello::HelloMessage;
+pub use keyslot_update::KeySlotUpdateMessage;
 pub use pairing::{
     PairingBusy, PairingCancel, PairingChallenge, PairingChallengeResponse, PairingConfirm,
     PairingKeyslotOffer, PairingMessage, PairingReject, PairingRequest, PairingResponse,
+    PairingRevoke, PairingRevokeAck,
 };
 pub use protocol_message::ProtocolMessage;
+pub use transfer::{BlobManifest, ChunkRequest, MAX_TRANSFER_CHUNK_BYTES, TRANSFER_CHUNK_BYTES};
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::security::model::KeySlotFile;
@@ -14,6 +15,8 @@ pub enum PairingMessage {
     Reject(PairingReject),
     Cancel(PairingCancel),
     Busy(PairingBusy),
+    Revoke(PairingRevoke),
+    RevokeAck(PairingRevokeAck),
 }
 
 /// Initial pairing request sent by initiator
@@ -37,6 +40,9 @@ pub struct PairingRequest {
     pub identity_pubkey: Vec<u8>,
     /// Random nonce for short-code transcript
     pub nonce: Vec<u8>,
+    /// Proof of the pairing invitation secret (out-of-band pairing only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invitation_proof: Option<Vec<u8>>,
 }
 
 /// Pairing challenge sent by responder with PIN
@@ -57,6 +63,9 @@ pub struct PairingChallenge {
     pub identity_pubkey: Vec<u8>,
     /// Random nonce for short-code transcript
     pub nonce: Vec<u8>,
+    /// Transcript proof of the invitation secret, answering an invited request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invitation_proof: Option<Vec<u8>>,
 }
 
 /// Keyslot offer sent by responder for join flow
@@ -165,6 +174,26 @@ pub struct PairingBusy {
     pub reason: Option<String>,
 }
 
+/// Revocation notice: the sender has unpaired the receiver.
+///
+/// 撤销通知：发送方已取消与接收方的配对，接收方应将其标记为 `Revoked`。
+/// Sent on the pairing protocol, which stays open to revoked peers, with a
+/// one-off `session_id` used only for stream framing.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PairingRevoke {
+    pub session_id: String,
+    pub revoked_at: DateTime<Utc>,
+}
+
+/// Acknowledgement of a [`PairingRevoke`]; the sender stops retrying once it
+/// arrives.
+///
+/// 撤销通知的确认：发送方收到后才删除待送达的撤销记录。
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PairingRevokeAck {
+    pub session_id: String,
+}
+
 impl PairingMessage {
     pub fn session_id(&self) -> &str {
         match self {
@@ -177,6 +206,8 @@ impl PairingMessage {
             PairingMessage::Reject(msg) => &msg.session_id,
             PairingMessage::Cancel(msg) => &msg.session_id,
             PairingMessage::Busy(msg) => &msg.session_id,
+            PairingMessage::Revoke(msg) => &msg.session_id,
+            PairingMessage::RevokeAck(msg) => &msg.session_id,
         }
     }
 }
@@ -195,6 +226,8 @@ impl std::fmt::Debug for PairingMessage {
             Self::Reject(msg) => f.debug_tuple("Reject").field(msg).finish(),
             Self::Cancel(msg) => f.debug_tuple("Cancel").field(msg).finish(),
             Self::Busy(msg) => f.debug_tuple("Busy").field(msg).finish(),
+            Self::Revoke(msg) => f.debug_tuple("Revoke").field(msg).finish(),
+            Self::RevokeAck(msg) => f.debug_tuple("RevokeAck").field(msg).finish(),
         }
     }
 }
@@ -208,6 +241,7 @@ impl std::fmt::Debug for PairingRequest {
             .field("peer_id", &self.peer_id)
             .field("identity_pubkey_len", &self.identity_pubkey.len())
             .field("nonce_len", &self.nonce.len())
+            .field("invitation_proof", &self.invitation_proof.is_some())
             .finish()
     }
 }
@@ -221,6 +255,7 @@ impl std::fmt::Debug for PairingChallenge {
             .field("device_id", &self.device_id)
             .field("identity_pubkey_len", &self.identity_pubkey.len())
             .field("nonce_len", &self.nonce.len())
+            .field("invitation_proof", &self.invitation_proof.is_some())
             .finish()
     }
 }
@@ -297,3 +332,20 @@ impl std::fmt::Debug for PairingBusy {
             .finish()
     }
 }
+
+impl std::fmt::Debug for PairingRevoke {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PairingRevoke")
+            .field("session_id", &self.session_id)
+            .field("revoked_at", &self.revoked_at)
+            .finish()
+    }
+}
+
+impl std::fmt::Debug for PairingRevokeAck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PairingRevokeAck")
+            .field("session_id", &self.session_id)
+            .finish()
+    }
+}
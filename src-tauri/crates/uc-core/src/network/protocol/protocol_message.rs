@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use super::{ClipboardMessage, DeviceAnnounceMessage, HeartbeatMessage, PairingMessage};
+use super::{
+    CatchUpRequest, ClipboardMessage, DeviceAnnounceMessage, HeartbeatMessage, HelloMessage,
+    KeySlotUpdateMessage, PairingMessage,
+};
 
 /// P2P protocol messages for UniClipboard
 /// Based on decentpaste protocol with UniClipboard-specific adaptations
@@ -12,6 +15,12 @@ pub enum ProtocolMessage {
     /// Announces device name to all peers on the network.
     /// Used when device name is changed in settings.
     DeviceAnnounce(DeviceAnnounceMessage),
+    /// Pushes a rewrapped keyslot to trusted peers after a passphrase change.
+    KeySlotUpdate(KeySlotUpdateMessage),
+    /// Asks a trusted peer to resend clipboard history missed while offline.
+    CatchUp(CatchUpRequest),
+    /// Exchanges app version, protocol versions and features on connect.
+    Hello(HelloMessage),
 }
 
 impl ProtocolMessage {
@@ -33,6 +42,14 @@ impl std::fmt::Debug for ProtocolMessage {
             Self::Clipboard(msg) => f.debug_tuple("Clipboard").field(msg).finish(),
             Self::Heartbeat(msg) => f.debug_tuple("Heartbeat").field(msg).finish(),
             Self::DeviceAnnounce(msg) => f.debug_tuple("DeviceAnnounce").field(msg).finish(),
+            Self::KeySlotUpdate(msg) => f.debug_tuple("KeySlotUpdate").field(msg).finish(),
+            Self::CatchUp(msg) => f
+                .debug_struct("CatchUp")
+                .field("origin_device_id", &msg.origin_device_id)
+                .field("since_ms", &msg.since_ms)
+                .field("known_hashes", &msg.known_hashes.len())
+                .finish(),
+            Self::Hello(msg) => f.debug_tuple("Hello").field(msg).finish(),
         }
     }
 }
@@ -0,0 +1,134 @@
+//! Chunked blob transfer protocol.
+//! 分块传输协议
+//!
+//! Large payloads are not inlined in business messages. The sender keeps the
+//! bytes and advertises a [`BlobManifest`]; the receiver opens a transfer
+//! stream and pulls the chunks it is missing with [`ChunkRequest`], verifying
+//! each chunk against the manifest before accepting it.
+
+use serde::{Deserialize, Serialize};
+
+use crate::clipboard::ContentHash;
+
+/// Default chunk size for new manifests (256 KiB).
+pub const TRANSFER_CHUNK_BYTES: u32 = 256 * 1024;
+
+/// Upper bound on a chunk size accepted from a peer's manifest.
+pub const MAX_TRANSFER_CHUNK_BYTES: u32 = 4 * 1024 * 1024;
+
+/// Description of a blob available from the peer that advertised it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobManifest {
+    /// blake3 of the whole blob
+    pub content_hash: ContentHash,
+    pub total_bytes: u64,
+    pub chunk_bytes: u32,
+    /// Hex blake3 of each chunk, in order
+    pub chunk_hashes: Vec<String>,
+}
+
+impl BlobManifest {
+    /// Build the manifest of `bytes`, split into `chunk_bytes` chunks.
+    pub fn from_bytes(bytes: &[u8], chunk_bytes: u32) -> Self {
+        let chunk_hashes = bytes
+            .chunks(chunk_bytes.max(1) as usize)
+            .map(|chunk| blake3::hash(chunk).to_hex().to_string())
+            .collect();
+        Self {
+            content_hash: ContentHash::from(blake3::hash(bytes).as_bytes()),
+            total_bytes: bytes.len() as u64,
+            chunk_bytes,
+            chunk_hashes,
+        }
+    }
+
+    pub fn chunk_count(&self) -> u32 {
+        self.chunk_hashes.len() as u32
+    }
+
+    /// Byte offset and length of chunk `index`, if it exists.
+    pub fn chunk_span(&self, index: u32) -> Option<(u64, usize)> {
+        if index >= self.chunk_count() {
+            return None;
+        }
+        let offset = u64::from(index) * u64::from(self.chunk_bytes);
+        let len = (self.total_bytes - offset).min(u64::from(self.chunk_bytes));
+        Some((offset, len as usize))
+    }
+
+    /// Whether the manifest is self-consistent and within transfer limits.
+    pub fn is_well_formed(&self) -> bool {
+        if self.chunk_bytes == 0 || self.chunk_bytes > MAX_TRANSFER_CHUNK_BYTES {
+            return false;
+        }
+        let expected = self.total_bytes.div_ceil(u64::from(self.chunk_bytes));
+        expected == self.chunk_hashes.len() as u64
+    }
+
+    pub fn verify_chunk(&self, index: u32, data: &[u8]) -> bool {
+        match (
+            self.chunk_span(index),
+            self.chunk_hashes.get(index as usize),
+        ) {
+            (Some((_, len)), Some(expected)) => {
+                data.len() == len && blake3::hash(data).to_hex().as_str() == expected
+            }
+            _ => false,
+        }
+    }
+
+    pub fn verify_blob(&self, bytes: &[u8]) -> bool {
+        bytes.len() as u64 == self.total_bytes
+            && blake3::hash(bytes).as_bytes() == &self.content_hash.bytes
+    }
+}
+
+/// Receiver → sender: send these chunks of a blob, in order.
+///
+/// The receiver asks for a bounded window at a time, so the sender never has
+/// more than one window in flight per transfer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkRequest {
+    pub content_hash: ContentHash,
+    pub indices: Vec<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_splits_and_verifies_chunks() {
+        let bytes: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        let manifest = BlobManifest::from_bytes(&bytes, 4096);
+
+        assert!(manifest.is_well_formed());
+        assert_eq!(manifest.chunk_count(), 3);
+        assert_eq!(manifest.chunk_span(2), Some((8192, 10_000 - 8192)));
+        assert_eq!(manifest.chunk_span(3), None);
+        assert!(manifest.verify_chunk(1, &bytes[4096..8192]));
+        assert!(!manifest.verify_chunk(1, &bytes[0..4096]));
+        assert!(manifest.verify_blob(&bytes));
+    }
+
+    #[test]
+    fn manifest_rejects_inconsistent_chunk_list() {
+        let mut manifest = BlobManifest::from_bytes(&[1, 2, 3, 4, 5], 2);
+        assert!(manifest.is_well_formed());
+
+        manifest.chunk_hashes.pop();
+        assert!(!manifest.is_well_formed());
+
+        manifest.chunk_bytes = MAX_TRANSFER_CHUNK_BYTES + 1;
+        assert!(!manifest.is_well_formed());
+    }
+
+    #[test]
+    fn empty_blob_has_no_chunks() {
+        let manifest = BlobManifest::from_bytes(&[], TRANSFER_CHUNK_BYTES);
+
+        assert!(manifest.is_well_formed());
+        assert_eq!(manifest.chunk_count(), 0);
+        assert!(manifest.verify_blob(&[]));
+    }
+}
@@ -3,6 +3,7 @@ pub enum ProtocolId {
     Pairing,
     PairingStream,
     Business,
+    Transfer,
 }
 
 impl ProtocolId {
@@ -11,6 +12,7 @@ impl ProtocolId {
             ProtocolId::Pairing => "/uc-pairing/1.0.0",
             ProtocolId::PairingStream => "/uniclipboard/pairing-stream/1.0.0",
             ProtocolId::Business => "/uniclipboard/business/1.0.0",
+            ProtocolId::Transfer => "/uniclipboard/transfer/1.0.0",
         }
     }
 }
@@ -30,5 +32,9 @@ mod tests {
             ProtocolId::Business.as_str(),
             "/uniclipboard/business/1.0.0"
         );
+        assert_eq!(
+            ProtocolId::Transfer.as_str(),
+            "/uniclipboard/transfer/1.0.0"
+        );
     }
 }
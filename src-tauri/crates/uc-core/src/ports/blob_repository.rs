@@ -4,12 +4,43 @@ use async_trait::async_trait;
 use std::sync::Arc;
 
 use crate::Blob;
+use crate::BlobId;
 use crate::ContentHash;
 
 #[async_trait]
 pub trait BlobRepositoryPort: Send + Sync {
     async fn insert_blob(&self, blob: &Blob) -> Result<()>;
     async fn find_by_hash(&self, content_hash: &ContentHash) -> Result<Option<Blob>>;
+
+    /// Count stored blobs.
+    async fn count_blobs(&self) -> Result<usize>;
+
+    /// List blob ids ordered by id, starting after `after`.
+    async fn list_blob_ids(&self, after: Option<&BlobId>, limit: usize) -> Result<Vec<BlobId>>;
+
+    /// Blobs created before `created_before_ms` that no representation or
+    /// thumbnail references any more, ordered by id.
+    async fn list_unreferenced_blobs(
+        &self,
+        created_before_ms: i64,
+        limit: usize,
+    ) -> Result<Vec<Blob>>;
+
+    /// Mark a blob as reused at `now_ms`, so collection treats it as new for
+    /// another grace period. Returns `false` if the record no longer exists.
+    ///
+    /// 标记 blob 在 `now_ms` 被复用，使回收在宽限期内跳过它；记录不存在时返回 `false`。
+    async fn touch_blob(&self, blob_id: &BlobId, now_ms: i64) -> Result<bool>;
+
+    /// Delete a blob record in one statement, but only while it is older than
+    /// `created_before_ms` and still unreferenced. Returns whether it was removed.
+    ///
+    /// 以单条语句删除仍早于 `created_before_ms` 且未被引用的 blob 记录，返回是否删除。
+    async fn delete_unreferenced_blob(
+        &self,
+        blob_id: &BlobId,
+        created_before_ms: i64,
+    ) -> Result<bool>;
 }
 
 #[async_trait]
@@ -21,4 +52,36 @@ impl<T: BlobRepositoryPort + ?Sized> BlobRepositoryPort for Arc<T> {
     async fn find_by_hash(&self, content_hash: &ContentHash) -> Result<Option<Blob>> {
         (**self).find_by_hash(content_hash).await
     }
+
+    async fn count_blobs(&self) -> Result<usize> {
+        (**self).count_blobs().await
+    }
+
+    async fn list_blob_ids(&self, after: Option<&BlobId>, limit: usize) -> Result<Vec<BlobId>> {
+        (**self).list_blob_ids(after, limit).await
+    }
+
+    async fn list_unreferenced_blobs(
+        &self,
+        created_before_ms: i64,
+        limit: usize,
+    ) -> Result<Vec<Blob>> {
+        (**self)
+            .list_unreferenced_blobs(created_before_ms, limit)
+            .await
+    }
+
+    async fn touch_blob(&self, blob_id: &BlobId, now_ms: i64) -> Result<bool> {
+        (**self).touch_blob(blob_id, now_ms).await
+    }
+
+    async fn delete_unreferenced_blob(
+        &self,
+        blob_id: &BlobId,
+        created_before_ms: i64,
+    ) -> Result<bool> {
+        (**self)
+            .delete_unreferenced_blob(blob_id, created_before_ms)
+            .await
+    }
 }
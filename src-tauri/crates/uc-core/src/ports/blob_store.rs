@@ -2,9 +2,24 @@ use anyhow::Result;
 use async_trait::async_trait;
 use std::path::PathBuf;
 use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 
 use crate::BlobId;
 
+/// Owned reader over blob bytes returned by [`BlobStorePort::open_read`].
+/// [`BlobStorePort::open_read`] 返回的 blob 字节读取器。
+pub type BlobReader = Box<dyn AsyncRead + Send + Unpin>;
+
+/// An object found in blob storage, used to reconcile storage with the database.
+/// 存储中的 blob 对象，用于与数据库对账。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredBlobObject {
+    pub blob_id: BlobId,
+    /// Size on disk (ciphertext size for encrypted stores).
+    pub size_bytes: u64,
+    pub modified_at_ms: i64,
+}
+
 #[async_trait]
 pub trait BlobStorePort: Send + Sync {
     // 把 bytes 写入 blob 存储，返回 storage_path（或 key）
@@ -12,6 +27,43 @@ pub trait BlobStorePort: Send + Sync {
 
     // 从 blob 存储读取 bytes
     async fn get(&self, blob_id: &BlobId) -> Result<Vec<u8>>;
+
+    /// Write a blob by draining `reader`, returning the storage path.
+    ///
+    /// Implementations must not buffer the whole input when they can write
+    /// incrementally; large payloads arrive through this method.
+    ///
+    /// 从 `reader` 流式写入 blob，返回 storage_path。
+    async fn put_stream(
+        &self,
+        blob_id: &BlobId,
+        reader: &mut (dyn AsyncRead + Send + Unpin),
+    ) -> Result<PathBuf>;
+
+    /// Open a blob for incremental reading.
+    /// 以流的方式打开 blob。
+    async fn open_read(&self, blob_id: &BlobId) -> Result<BlobReader>;
+
+    /// Copy a blob into `writer`, returning the number of bytes written.
+    /// 将 blob 内容复制到 `writer`，返回写入的字节数。
+    async fn read_into(
+        &self,
+        blob_id: &BlobId,
+        writer: &mut (dyn AsyncWrite + Send + Unpin),
+    ) -> Result<u64> {
+        let mut reader = self.open_read(blob_id).await?;
+        let copied = tokio::io::copy(&mut reader, writer).await?;
+        writer.flush().await?;
+        Ok(copied)
+    }
+
+    /// Delete a blob, returning the number of bytes reclaimed (0 if it was absent).
+    /// 删除 blob，返回回收的字节数（不存在时为 0）。
+    async fn delete(&self, blob_id: &BlobId) -> Result<u64>;
+
+    /// List every object currently held by the store.
+    /// 列出存储中的全部对象。
+    async fn list_objects(&self) -> Result<Vec<StoredBlobObject>>;
 }
 
 #[async_trait]
@@ -23,4 +75,32 @@ impl<T: BlobStorePort + ?Sized> BlobStorePort for Arc<T> {
     async fn get(&self, blob_id: &BlobId) -> Result<Vec<u8>> {
         (**self).get(blob_id).await
     }
+
+    async fn put_stream(
+        &self,
+        blob_id: &BlobId,
+        reader: &mut (dyn AsyncRead + Send + Unpin),
+    ) -> Result<PathBuf> {
+        (**self).put_stream(blob_id, reader).await
+    }
+
+    async fn open_read(&self, blob_id: &BlobId) -> Result<BlobReader> {
+        (**self).open_read(blob_id).await
+    }
+
+    async fn read_into(
+        &self,
+        blob_id: &BlobId,
+        writer: &mut (dyn AsyncWrite + Send + Unpin),
+    ) -> Result<u64> {
+        (**self).read_into(blob_id, writer).await
+    }
+
+    async fn delete(&self, blob_id: &BlobId) -> Result<u64> {
+        (**self).delete(blob_id).await
+    }
+
+    async fn list_objects(&self) -> Result<Vec<StoredBlobObject>> {
+        (**self).list_objects().await
+    }
 }
@@ -1,6 +1,10 @@
 use anyhow::Result;
 
-use crate::{clipboard::ClipboardEntry, ids::EntryId, ClipboardSelectionDecision};
+use crate::{
+    clipboard::{CatchUpCandidate, ClipboardEntry, RetentionCandidate},
+    ids::{DeviceId, EntryId},
+    ClipboardSelectionDecision,
+};
 
 #[async_trait::async_trait]
 pub trait ClipboardEntryRepositoryPort: Send + Sync {
@@ -9,6 +13,9 @@ pub trait ClipboardEntryRepositoryPort: Send + Sync {
         entry: &ClipboardEntry,
         selection: &ClipboardSelectionDecision,
     ) -> Result<()>;
+
+    /// Get a live entry; soft-deleted entries are reported as missing.
+    /// 获取未被软删除的条目，已软删除的条目视为不存在。
     async fn get_entry(&self, entry_id: &EntryId) -> Result<Option<ClipboardEntry>>;
 
     /// List clipboard entries with pagination
@@ -21,6 +28,17 @@ pub trait ClipboardEntryRepositoryPort: Send + Sync {
         Ok(false)
     }
 
+    /// Set the pinned flag of an entry.
+    /// 设置条目的置顶标记。
+    ///
+    /// Returns `false` if no live entry matched.
+    async fn set_entry_pinned(&self, entry_id: &EntryId, pinned: bool) -> Result<bool>;
+
+    /// List pinned clipboard entries with pagination, ordered like `list_entries`.
+    /// 分页列出置顶条目（排序与 `list_entries` 一致）。
+    async fn list_pinned_entries(&self, limit: usize, offset: usize)
+        -> Result<Vec<ClipboardEntry>>;
+
     /// Delete a clipboard entry.
     /// 删除剪贴板条目。
     ///
@@ -30,4 +48,34 @@ pub trait ClipboardEntryRepositoryPort: Send + Sync {
     /// # Errors
     /// Returns error if database operation fails
     async fn delete_entry(&self, entry_id: &EntryId) -> Result<()>;
+
+    /// List live entries with the metadata needed to evaluate retention rules.
+    /// 列出未被软删除的条目及保留策略评估所需的元数据。
+    async fn list_retention_candidates(&self) -> Result<Vec<RetentionCandidate>>;
+
+    /// Mark entries as deleted without removing their rows.
+    /// 软删除条目（仅写入 deleted_at_ms）。
+    ///
+    /// Returns the number of entries that were marked.
+    async fn soft_delete_entries(&self, entry_ids: &[EntryId], deleted_at_ms: i64)
+        -> Result<usize>;
+
+    /// List entries soft-deleted before `deleted_before_ms`.
+    /// 列出在 `deleted_before_ms` 之前被软删除的条目，用于彻底清除。
+    async fn list_soft_deleted_entries(
+        &self,
+        deleted_before_ms: i64,
+    ) -> Result<Vec<ClipboardEntry>>;
+
+    /// List live entries created at or after `since_ms`, newest first.
+    /// 列出 `since_ms` 之后创建的未删除条目（按创建时间倒序），用于离线补同步。
+    async fn list_catch_up_candidates(
+        &self,
+        since_ms: i64,
+        limit: usize,
+    ) -> Result<Vec<CatchUpCandidate>>;
+
+    /// Look up the device that produced the entry's capture event.
+    /// 查询条目对应捕获事件的来源设备。
+    async fn get_entry_source_device(&self, entry_id: &EntryId) -> Result<Option<DeviceId>>;
 }
@@ -3,14 +3,18 @@ mod clipboard_entry_repository;
 mod clipboard_event_repository;
 mod clipboard_selection_repository;
 mod local_clipboard;
+mod outbound_sync_queue;
 mod payload_resolver;
 mod platform_clipboard;
+mod remote_payload_repository;
 mod representation_cache;
 mod representation_normalizer;
 mod representation_repository;
+mod search_index;
 mod select_representation_policy;
 mod selection_resolver;
 mod spool_queue;
+mod sync_filter_policy;
 mod thumbnail_generator;
 mod thumbnail_repository;
 
@@ -19,15 +23,19 @@ pub use clipboard_entry_repository::ClipboardEntryRepositoryPort;
 pub use clipboard_event_repository::ClipboardEventRepositoryPort;
 pub use clipboard_selection_repository::ClipboardSelectionRepositoryPort;
 pub use local_clipboard::SystemClipboardPort;
+pub use outbound_sync_queue::{OutboundSyncQueuePort, QueuedClipboardMessage};
 pub use payload_resolver::{ClipboardPayloadResolverPort, ResolvedClipboardPayload};
 pub use platform_clipboard::PlatformClipboardPort;
+pub use remote_payload_repository::RemotePayloadRepositoryPort;
 pub use representation_cache::RepresentationCachePort;
 pub use representation_normalizer::ClipboardRepresentationNormalizerPort;
 pub use representation_repository::{
     ClipboardRepresentationRepositoryPort, ProcessingUpdateOutcome,
 };
+pub use search_index::ClipboardSearchIndexPort;
 pub use select_representation_policy::SelectRepresentationPolicyPort;
 pub use selection_resolver::SelectionResolverPort;
 pub use spool_queue::{SpoolQueuePort, SpoolRequest};
+pub use sync_filter_policy::SyncFilterPolicyPort;
 pub use thumbnail_generator::{GeneratedThumbnail, ThumbnailGeneratorPort};
 pub use thumbnail_repository::ThumbnailRepositoryPort;
@@ -0,0 +1,30 @@
+/// 待发送的剪贴板同步消息
+///
+/// `payload` is the snapshot to send, sealed with the space key under
+/// [`for_outbound_queue`](crate::security::aad::for_outbound_queue), so the
+/// queue never holds plaintext. The wire frame is built at flush time, per
+/// device and with the key current then.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedClipboardMessage {
+    pub message_id: String,
+    pub payload: Vec<u8>,
+    pub enqueued_at_ms: i64,
+}
+
+/// 间隔同步模式下的出站队列
+///
+/// Persistent so that content captured shortly before a restart is still
+/// delivered on the next flush.
+#[async_trait::async_trait]
+pub trait OutboundSyncQueuePort: Send + Sync {
+    async fn enqueue(&self, message: QueuedClipboardMessage) -> anyhow::Result<()>;
+
+    /// All queued messages, oldest first.
+    async fn list_pending(&self) -> anyhow::Result<Vec<QueuedClipboardMessage>>;
+
+    /// Remove the given messages and return how many were removed.
+    async fn remove(&self, message_ids: &[String]) -> anyhow::Result<usize>;
+
+    /// Drop everything except the `keep` most recent messages.
+    async fn retain_latest(&self, keep: usize) -> anyhow::Result<usize>;
+}
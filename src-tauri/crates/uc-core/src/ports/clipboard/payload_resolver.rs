@@ -27,10 +27,13 @@ pub trait ClipboardPayloadResolverPort: Send + Sync {
     /// 3. **Staged/Processing/Failed**: Best-effort return of bytes from cache/spool
     ///    - If bytes are available, return `Inline`
     ///    - Otherwise return an error (data not currently available)
-    /// 4. **Lost**: Return an unrecoverable error
+    /// 4. **Remote**: Pull the bytes from a trusted peer holding the content hash
+    ///    - On success, stage the bytes for the background worker and return `Inline`
+    ///    - Otherwise return an error (no peer currently holds the data)
+    /// 5. **Lost**: Return an unrecoverable error
     ///
     /// # Notes
-    /// - Resolver must be read-only; no lazy blob writes here.
+    /// - Resolver must not write blobs; `Remote` only moves to `Staged`.
     /// - Background workers are responsible for materializing blobs.
     async fn resolve(
         &self,
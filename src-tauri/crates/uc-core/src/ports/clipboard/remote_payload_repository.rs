@@ -0,0 +1,27 @@
+use crate::clipboard::RemotePayloadRef;
+use crate::ids::RepresentationId;
+use anyhow::Result;
+
+/// Repository port for representations synced without their bytes.
+///
+/// 延迟同步表示形式的清单仓储端口。
+#[async_trait::async_trait]
+pub trait RemotePayloadRepositoryPort: Send + Sync {
+    /// Insert or replace the manifest of a remote representation.
+    ///
+    /// 插入或替换远程表示形式的清单。
+    async fn insert_remote_payload(&self, remote: &RemotePayloadRef) -> Result<()>;
+
+    /// Fetch the manifest by representation id.
+    ///
+    /// 通过表示标识符获取清单。
+    async fn get_by_representation_id(
+        &self,
+        representation_id: &RepresentationId,
+    ) -> Result<Option<RemotePayloadRef>>;
+
+    /// Forget the manifest once the bytes are local.
+    ///
+    /// 数据已拉取到本地后删除清单。
+    async fn delete_remote_payload(&self, representation_id: &RepresentationId) -> Result<()>;
+}
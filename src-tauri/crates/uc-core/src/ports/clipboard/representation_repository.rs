@@ -90,4 +90,23 @@ pub trait ClipboardRepresentationRepositoryPort: Send + Sync {
         new_state: PayloadAvailability,
         last_error: Option<&str>,
     ) -> Result<ProcessingUpdateOutcome>;
+
+    /// Count representations that carry inline data.
+    async fn count_inline_representations(&self) -> Result<usize>;
+
+    /// List representations that carry inline data, ordered by representation id.
+    ///
+    /// Used by key rotation to walk inline payloads with a resumable cursor.
+    async fn list_inline_representation_ids(
+        &self,
+        after: Option<&RepresentationId>,
+        limit: usize,
+    ) -> Result<Vec<(EventId, RepresentationId)>>;
+
+    /// Overwrite the stored inline data of a representation.
+    async fn update_inline_data(
+        &self,
+        representation_id: &RepresentationId,
+        inline_data: &[u8],
+    ) -> Result<()>;
 }
@@ -0,0 +1,37 @@
+use anyhow::Result;
+
+use crate::clipboard::{ClipboardSearchDocument, ClipboardSearchHit, ClipboardSearchQuery};
+use crate::ids::EntryId;
+
+/// Full-text search index over clipboard history.
+///
+/// 剪贴板历史的全文检索索引端口。
+///
+/// Clipboard text may be encrypted at rest, so the index is expected to hold
+/// plaintext only while the encryption session is unlocked: it starts closed,
+/// is opened (and rebuilt) after unlock, and writes to a closed index are
+/// silently dropped.
+#[async_trait::async_trait]
+pub trait ClipboardSearchIndexPort: Send + Sync {
+    /// Whether the index is open and can be queried.
+    async fn is_open(&self) -> bool;
+
+    /// Open an empty index, discarding any previous content.
+    ///
+    /// 打开一个空索引（会丢弃已有内容）。
+    async fn open(&self) -> Result<()>;
+
+    /// Close the index and drop all indexed text.
+    ///
+    /// 关闭索引并丢弃所有已索引文本。
+    async fn close(&self) -> Result<()>;
+
+    /// Insert or replace the document for an entry. No-op when closed.
+    async fn index_document(&self, document: &ClipboardSearchDocument) -> Result<()>;
+
+    /// Remove an entry from the index. No-op when closed.
+    async fn remove_entry(&self, entry_id: &EntryId) -> Result<()>;
+
+    /// Run a query; fails when the index is closed.
+    async fn search(&self, query: &ClipboardSearchQuery) -> Result<Vec<ClipboardSearchHit>>;
+}
@@ -0,0 +1,13 @@
+use crate::clipboard::{SnapshotFilterDecision, SystemClipboardSnapshot};
+use crate::settings::model::SyncSettings;
+
+/// Decide per representation whether a snapshot is sent to peers.
+///
+/// 逐个 representation 决定快照是否发送给其他设备。
+pub trait SyncFilterPolicyPort: Send + Sync {
+    fn evaluate(
+        &self,
+        snapshot: &SystemClipboardSnapshot,
+        settings: &SyncSettings,
+    ) -> SnapshotFilterDecision;
+}
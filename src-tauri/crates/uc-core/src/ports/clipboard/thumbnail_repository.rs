@@ -19,4 +19,10 @@ pub trait ThumbnailRepositoryPort: Send + Sync {
     ///
     /// 插入或更新缩略图元数据。
     async fn insert_thumbnail(&self, metadata: &ThumbnailMetadata) -> Result<()>;
+
+    /// Delete thumbnail metadata whose representation no longer exists,
+    /// returning the number of rows removed.
+    ///
+    /// 删除其表示已不存在的缩略图元数据，返回删除的行数。
+    async fn delete_orphaned_thumbnails(&self) -> Result<usize>;
 }
@@ -1,5 +1,6 @@
 use crate::ids::PeerId;
 use crate::network::connection_policy::ResolvedConnectionPolicy;
+use crate::network::IdentityCheck;
 use async_trait::async_trait;
 
 #[derive(Debug, thiserror::Error)]
@@ -14,6 +15,17 @@ pub trait ConnectionPolicyResolverPort: Send + Sync {
         &self,
         peer_id: &PeerId,
     ) -> Result<ResolvedConnectionPolicy, ConnectionPolicyResolverError>;
+
+    /// Check the identity key a peer presented against the fingerprint pinned
+    /// for the device id it claims, quarantining the peer on mismatch.
+    ///
+    /// 按对端声明的设备 ID 校验其身份公钥；不一致时将设备隔离。
+    async fn verify_peer_identity(
+        &self,
+        peer_id: &PeerId,
+        device_id: &str,
+        identity_pubkey: &[u8],
+    ) -> Result<IdentityCheck, ConnectionPolicyResolverError>;
 }
 
 #[cfg(test)]
@@ -36,6 +48,15 @@ mod tests {
                     allowed: ConnectionPolicy::allowed_protocols(PairingState::Pending),
                 })
             }
+
+            async fn verify_peer_identity(
+                &self,
+                _peer_id: &PeerId,
+                _device_id: &str,
+                _identity_pubkey: &[u8],
+            ) -> Result<IdentityCheck, ConnectionPolicyResolverError> {
+                Ok(IdentityCheck::Unpinned)
+            }
         }
 
         let _resolver: &dyn ConnectionPolicyResolverPort = &Dummy;
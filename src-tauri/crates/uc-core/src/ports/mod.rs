@@ -47,7 +47,7 @@ pub mod ui_port;
 pub mod watcher_control;
 
 pub use blob_repository::BlobRepositoryPort;
-pub use blob_store::BlobStorePort;
+pub use blob_store::{BlobReader, BlobStorePort, StoredBlobObject};
 pub use blob_writer::BlobWriterPort;
 pub use clipboard_event::*;
 pub use clock::*;
@@ -72,6 +72,7 @@ pub use paired_device_repository::PairedDeviceRepositoryPort;
 pub use security::encryption::EncryptionPort;
 pub use security::encryption_session::EncryptionSessionPort;
 pub use security::key_material::KeyMaterialPort;
+pub use security::payload_reencryption::PayloadReencryptionPort;
 pub use security::secure_storage::{SecureStorageError, SecureStoragePort};
 pub use settings::{SettingsMigrationPort, SettingsPort};
 pub use setup::SetupStatusPort;
@@ -4,7 +4,8 @@
 //! clipboard messaging, peer discovery, device pairing, and event subscriptions.
 
 use crate::network::{
-    ClipboardMessage, ConnectedPeer, DiscoveredPeer, NetworkEvent, PairingMessage,
+    BlobManifest, ClipboardMessage, ConnectedPeer, DiscoveredPeer, LocalNetworkInfo, NetworkEvent,
+    PairingMessage, PeerCapabilities, PeerLiveness,
 };
 use anyhow::Result;
 use async_trait::async_trait;
@@ -21,14 +22,37 @@ pub trait NetworkPort: Send + Sync {
     /// Send clipboard message to a specific peer
     async fn send_clipboard(&self, peer_id: &str, encrypted_data: Vec<u8>) -> Result<()>;
 
-    /// Broadcast clipboard message to all connected peers
-    async fn broadcast_clipboard(&self, encrypted_data: Vec<u8>) -> Result<()>;
-
     /// Subscribe to incoming clipboard messages
     ///
     /// Returns a receiver that will yield clipboard messages received from remote peers.
     async fn subscribe_clipboard(&self) -> Result<tokio::sync::mpsc::Receiver<ClipboardMessage>>;
 
+    /// Pull a blob advertised by `peer_id` over the transfer protocol
+    ///
+    /// 按清单分块拉取；断线后只补拉缺失的分块。
+    async fn fetch_blob(&self, peer_id: &str, manifest: BlobManifest) -> Result<Vec<u8>>;
+
+    /// Pull a blob from whichever connected peer still holds it
+    ///
+    /// 依次尝试已连接的设备，直到某个设备提供了该内容哈希对应的数据。
+    async fn fetch_blob_from_any(&self, manifest: BlobManifest) -> Result<Vec<u8>> {
+        let mut last_error =
+            anyhow::anyhow!("no connected peer holds blob {}", manifest.content_hash);
+        for peer in self.get_connected_peers().await? {
+            match self.fetch_blob(&peer.peer_id, manifest.clone()).await {
+                Ok(bytes) => return Ok(bytes),
+                Err(err) => last_error = err,
+            }
+        }
+        Err(last_error)
+    }
+
+    /// Keep `bytes` available to `peer_ids` and return its manifest.
+    /// Other peers asking for the same hash are told the blob is missing.
+    ///
+    /// 将数据交给传输协议，仅对 `peer_ids` 中的设备提供，返回对应清单。
+    async fn offer_blob(&self, bytes: Vec<u8>, peer_ids: &[String]) -> Result<BlobManifest>;
+
     // === Peer operations ===
 
     /// Get all discovered peers (from mDNS)
@@ -43,6 +67,38 @@ pub trait NetworkPort: Send + Sync {
     /// Announce local device name to peers
     async fn announce_device_name(&self, device_name: String) -> Result<()>;
 
+    /// Latest heartbeat seen from each peer since the network started
+    ///
+    /// 各对端最近一次心跳及往返时延（仅内存，重启后清空）
+    async fn get_peer_liveness(&self) -> Result<Vec<PeerLiveness>>;
+
+    /// Capabilities negotiated with each peer since the network started
+    ///
+    /// 各对端协商得到的协议版本与特性（仅内存，断开后需重新协商）
+    async fn get_peer_capabilities(&self) -> Result<Vec<PeerCapabilities>>;
+
+    // === Manual addressing ===
+
+    /// Dial a peer by multiaddr, without knowing its peer ID
+    ///
+    /// 按地址手动拨号（mDNS 不可用时使用）
+    async fn dial_address(&self, address: String) -> Result<()>;
+
+    /// Check that a normalized address is a multiaddr the transport can dial
+    ///
+    /// Returns the canonical form. A trailing `/p2p/<peer id>` must hold a valid peer ID.
+    ///
+    /// 校验手动地址能否被传输层拨号，并返回规范形式
+    fn parse_peer_address(&self, address: &str) -> Result<String>;
+
+    /// Dial a known peer on any of its remembered addresses
+    ///
+    /// Skipped by implementations when the peer is already connected.
+    async fn dial_peer(&self, peer_id: String, addresses: Vec<String>) -> Result<()>;
+
+    /// Local interfaces and the addresses the node is listening on
+    async fn get_local_network_info(&self) -> Result<LocalNetworkInfo>;
+
     // === Pairing operations ===
 
     /// Open a pairing session-specific stream toward a peer. Best-effort.
@@ -1,4 +1,4 @@
-use crate::network::{PairedDevice, PairingState};
+use crate::network::{DeviceSyncPolicy, PairedDevice, PairingState, PeerCapabilities};
 use crate::PeerId;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -14,6 +14,11 @@ pub trait PairedDeviceRepositoryPort: Send + Sync {
 
     async fn list_all(&self) -> Result<Vec<PairedDevice>, PairedDeviceRepositoryError>;
 
+    /// Insert or update a paired device.
+    ///
+    /// Pairing with a peer again drops a revocation notice still pending for it.
+    ///
+    /// 重新配对时同时清除该设备待送达的撤销通知。
     async fn upsert(&self, device: PairedDevice) -> Result<(), PairedDeviceRepositoryError>;
 
     async fn set_state(
@@ -29,4 +34,74 @@ pub trait PairedDeviceRepositoryPort: Send + Sync {
     ) -> Result<(), PairedDeviceRepositoryError>;
 
     async fn delete(&self, peer_id: &PeerId) -> Result<(), PairedDeviceRepositoryError>;
+
+    /// Remember an address a paired device was reached on.
+    ///
+    /// 记录已配对设备的可达地址，用于 mDNS 不可用时重连。
+    /// Implementations keep only the most recently confirmed addresses.
+    async fn record_address(
+        &self,
+        peer_id: &PeerId,
+        address: &str,
+        confirmed_at: DateTime<Utc>,
+    ) -> Result<(), PairedDeviceRepositoryError>;
+
+    /// Remembered addresses of a paired device, most recent first.
+    async fn list_addresses(
+        &self,
+        peer_id: &PeerId,
+    ) -> Result<Vec<String>, PairedDeviceRepositoryError>;
+
+    /// Remember that `peer_id` still has to be told it was unpaired.
+    ///
+    /// 记录待送达的撤销通知；对端离线时在重连后重试。
+    /// Survives `delete` of the paired device itself.
+    async fn mark_revocation_pending(
+        &self,
+        peer_id: &PeerId,
+        revoked_at: DateTime<Utc>,
+    ) -> Result<(), PairedDeviceRepositoryError>;
+
+    /// Whether a revocation notice for `peer_id` is still undelivered.
+    async fn is_revocation_pending(
+        &self,
+        peer_id: &PeerId,
+    ) -> Result<bool, PairedDeviceRepositoryError>;
+
+    /// Forget a pending revocation once the peer acknowledged it.
+    async fn clear_revocation_pending(
+        &self,
+        peer_id: &PeerId,
+    ) -> Result<(), PairedDeviceRepositoryError>;
+
+    /// Sync policy of a paired device; the default policy when none was stored.
+    ///
+    /// 读取设备的同步策略（方向、内容类型、是否写入系统剪贴板）。
+    async fn get_sync_policy(
+        &self,
+        peer_id: &PeerId,
+    ) -> Result<DeviceSyncPolicy, PairedDeviceRepositoryError>;
+
+    /// Store the sync policy of a paired device; removed together with the device.
+    async fn set_sync_policy(
+        &self,
+        peer_id: &PeerId,
+        policy: &DeviceSyncPolicy,
+    ) -> Result<(), PairedDeviceRepositoryError>;
+
+    /// Capabilities negotiated with a paired device the last time it said hello;
+    /// `None` when it never did.
+    ///
+    /// 最近一次握手协商出的设备能力，在连接建立、握手完成之前即可查询。
+    async fn get_capabilities(
+        &self,
+        peer_id: &PeerId,
+    ) -> Result<Option<PeerCapabilities>, PairedDeviceRepositoryError>;
+
+    /// Store negotiated capabilities; removed together with the device.
+    async fn set_capabilities(
+        &self,
+        peer_id: &PeerId,
+        capabilities: &PeerCapabilities,
+    ) -> Result<(), PairedDeviceRepositoryError>;
 }
@@ -31,7 +31,7 @@ pub trait EncryptionPort: Send + Sync {
     ///
     /// Used only in:
     /// - InitializeEncryption
-    /// - ChangePassphrase (future)
+    /// - ChangePassphrase
     async fn wrap_master_key(
         &self,
         kek: &Kek,
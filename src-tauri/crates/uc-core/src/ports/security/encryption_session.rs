@@ -1,4 +1,4 @@
-use crate::security::model::{EncryptionError, MasterKey};
+use crate::security::model::{EncryptionError, KeyEpoch, MasterKey};
 use async_trait::async_trait;
 
 #[async_trait]
@@ -22,4 +22,28 @@ pub trait EncryptionSessionPort: Send + Sync {
     ///
     /// You might call this on app shutdown, or not call it at all—still useful for tests.
     async fn clear(&self) -> Result<(), EncryptionError>;
+
+    /// Get the current MasterKey together with its key epoch.
+    ///
+    /// Writers tag new ciphertext with the returned epoch.
+    async fn get_current_key(&self) -> Result<(KeyEpoch, MasterKey), EncryptionError>;
+
+    /// Get the MasterKey that sealed data tagged with `epoch`.
+    ///
+    /// Resolves any epoch held in the keyring (the retired key of a running
+    /// rotation and keys retained for data that could not be re-encrypted);
+    /// any other epoch resolves to the current key.
+    async fn get_master_key_for_epoch(&self, epoch: KeyEpoch)
+        -> Result<MasterKey, EncryptionError>;
+
+    /// Install the current MasterKey for `epoch`, plus the keys of older epochs
+    /// that still seal stored data.
+    ///
+    /// Called by unlock flows and by key rotation.
+    async fn set_epoch_keys(
+        &self,
+        epoch: KeyEpoch,
+        master_key: MasterKey,
+        older: Vec<(KeyEpoch, MasterKey)>,
+    ) -> Result<(), EncryptionError>;
 }
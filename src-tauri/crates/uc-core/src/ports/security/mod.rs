@@ -3,4 +3,5 @@ pub mod encryption_session;
 pub mod encryption_state;
 pub mod key_material;
 pub mod key_scope;
+pub mod payload_reencryption;
 pub mod secure_storage;
@@ -0,0 +1,43 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::ids::{EventId, RepresentationId};
+use crate::BlobId;
+
+/// Re-encrypts data at rest after a MasterKey rotation.
+///
+/// Implementations read the stored ciphertext directly, decrypt it with the key
+/// of the epoch it was sealed under and write it back sealed with the current
+/// key. Items already at the current epoch are left untouched, so a pass can be
+/// interrupted and restarted at any point.
+///
+/// 密钥轮换后重新加密静态数据。已处于当前纪元的数据不会被改写，因此可随时中断并重新开始。
+#[async_trait]
+pub trait PayloadReencryptionPort: Send + Sync {
+    /// Number of representations with inline data.
+    async fn count_inline_payloads(&self) -> Result<usize>;
+
+    /// Inline representations ordered by id, starting after `after`.
+    async fn list_inline_payloads(
+        &self,
+        after: Option<&RepresentationId>,
+        limit: usize,
+    ) -> Result<Vec<(EventId, RepresentationId)>>;
+
+    /// Number of stored blobs.
+    async fn count_blob_payloads(&self) -> Result<usize>;
+
+    /// Blob ids ordered by id, starting after `after`.
+    async fn list_blob_payloads(&self, after: Option<&BlobId>, limit: usize)
+        -> Result<Vec<BlobId>>;
+
+    /// Re-encrypt one inline payload; returns `false` if it was already current.
+    async fn reencrypt_inline(
+        &self,
+        event_id: &EventId,
+        representation_id: &RepresentationId,
+    ) -> Result<bool>;
+
+    /// Re-encrypt one blob; returns `false` if it was already current.
+    async fn reencrypt_blob(&self, blob_id: &BlobId) -> Result<bool>;
+}
@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use crate::ids::SpaceId;
+
+/// Currently active space.
+/// 当前活动空间。
+///
+/// Read synchronously on every repository query and key scope lookup, so
+/// implementations must be cheap and non-blocking.
+pub trait ActiveSpacePort: Send + Sync {
+    fn active_space(&self) -> SpaceId;
+
+    fn set_active_space(&self, space_id: SpaceId);
+}
+
+impl<T: ActiveSpacePort + ?Sized> ActiveSpacePort for Arc<T> {
+    fn active_space(&self) -> SpaceId {
+        (**self).active_space()
+    }
+
+    fn set_active_space(&self, space_id: SpaceId) {
+        (**self).set_active_space(space_id)
+    }
+}
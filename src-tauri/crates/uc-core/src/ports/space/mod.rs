@@ -1,8 +1,10 @@
+mod active_space;
 mod crypto;
 mod persistence;
 mod proof;
 mod transport;
 
+pub use active_space::*;
 pub use crypto::*;
 pub use persistence::*;
 pub use proof::*;
@@ -4,4 +4,8 @@ use crate::ids::SessionId;
 pub trait TimerPort: Send {
     async fn start(&mut self, session_id: &SessionId, ttl_secs: u64) -> anyhow::Result<()>;
     async fn stop(&mut self, session_id: &SessionId) -> anyhow::Result<()>;
+    /// Wait for `duration`; background loops use this as their tick.
+    ///
+    /// 等待指定时长，后台周期任务以此作为节拍。
+    async fn sleep(&mut self, duration: std::time::Duration) -> anyhow::Result<()>;
 }
@@ -8,7 +8,7 @@
 //! AAD follows the pattern: `uc:<type>:v1|<identifiers>`
 //!
 //! - `uc:` - Application namespace prefix
-//! - `<type>` - Data type (inline, blob)
+//! - `<type>` - Data type (inline, blob, clipboard)
 //! - `:v1` - Format version
 //! - `|<identifiers>` - Pipe-separated context identifiers
 //!
@@ -26,6 +26,9 @@
 //! // For blob storage
 //! let blob_id = BlobId::new();
 //! let aad = aad::for_blob(&blob_id);
+//!
+//! // For clipboard messages synced to peers
+//! let aad = aad::for_clipboard_message("message-id");
 //! ```
 
 use crate::ids::{BlobId, EventId, RepresentationId};
@@ -99,6 +102,103 @@ pub fn for_blob(blob_id: &BlobId) -> Vec<u8> {
     format!("{AAD_NAMESPACE}:blob:{AAD_VERSION}|{}", blob_id.as_ref()).into_bytes()
 }
 
+/// Generates AAD for clipboard sync messages sent over the business protocol.
+///
+/// # Format
+///
+/// `uc:clipboard:v1|{message_id}`
+///
+/// # Arguments
+///
+/// * `message_id` - The `ClipboardMessage` identifier
+///
+/// # Returns
+///
+/// AAD as bytes for use with AEAD encryption.
+///
+/// # Examples
+///
+/// ```rust
+/// use uc_core::security::aad::for_clipboard_message;
+///
+/// let aad = for_clipboard_message("test-message");
+/// assert_eq!(aad, b"uc:clipboard:v1|test-message".to_vec());
+/// ```
+pub fn for_clipboard_message(message_id: &str) -> Vec<u8> {
+    format!("{AAD_NAMESPACE}:clipboard:{AAD_VERSION}|{message_id}").into_bytes()
+}
+
+/// Generates AAD for keyslot updates pushed to trusted peers.
+///
+/// # Format
+///
+/// `uc:keyslot:v1|{message_id}`
+///
+/// # Arguments
+///
+/// * `message_id` - The `KeySlotUpdateMessage` identifier
+///
+/// # Examples
+///
+/// ```rust
+/// use uc_core::security::aad::for_keyslot_update;
+///
+/// let aad = for_keyslot_update("update-1");
+/// assert_eq!(aad, b"uc:keyslot:v1|update-1".to_vec());
+/// ```
+pub fn for_keyslot_update(message_id: &str) -> Vec<u8> {
+    format!("{AAD_NAMESPACE}:keyslot:{AAD_VERSION}|{message_id}").into_bytes()
+}
+
+/// Generates AAD for snapshots sealed in the outbound sync queue.
+///
+/// # Format
+///
+/// `uc:outbound-queue:v1|{message_id}`
+///
+/// # Arguments
+///
+/// * `message_id` - The `QueuedClipboardMessage` identifier
+///
+/// # Examples
+///
+/// ```rust
+/// use uc_core::security::aad::for_outbound_queue;
+///
+/// let aad = for_outbound_queue("queued-1");
+/// assert_eq!(aad, b"uc:outbound-queue:v1|queued-1".to_vec());
+/// ```
+pub fn for_outbound_queue(message_id: &str) -> Vec<u8> {
+    format!("{AAD_NAMESPACE}:outbound-queue:{AAD_VERSION}|{message_id}").into_bytes()
+}
+
+/// Generates AAD for representations offered to peers for lazy fetch.
+///
+/// # Format
+///
+/// `uc:remote-payload:v1|{representation_id}`
+///
+/// # Arguments
+///
+/// * `rep_id` - The representation the sealed bytes belong to
+///
+/// # Examples
+///
+/// ```rust
+/// use uc_core::security::aad::for_remote_payload;
+/// use uc_core::ids::RepresentationId;
+///
+/// let aad = for_remote_payload(&RepresentationId::from("rep-1"));
+/// assert_eq!(aad, b"uc:remote-payload:v1|rep-1".to_vec());
+/// ```
+pub fn for_remote_payload(rep_id: &RepresentationId) -> Vec<u8> {
+    format!(
+        "{AAD_NAMESPACE}:remote-payload:{AAD_VERSION}|{}",
+        rep_id.as_ref()
+    )
+    .into_bytes()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -203,6 +303,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_for_clipboard_message_differs_by_message_id() {
+        let aad1 = for_clipboard_message("message-1");
+        let aad2 = for_clipboard_message("message-2");
+
+        assert_ne!(aad1, aad2, "AAD should differ for different message IDs");
+        assert!(
+            String::from_utf8(aad1)
+                .unwrap()
+                .starts_with("uc:clipboard:v1|"),
+            "AAD should have correct prefix"
+        );
+    }
+
+    #[test]
+    fn test_keyslot_update_aad_is_distinct_from_clipboard() {
+        assert_ne!(
+            for_keyslot_update("message-1"),
+            for_clipboard_message("message-1"),
+            "Keyslot and clipboard AAD must not collide"
+        );
+    }
+
     #[test]
     fn test_aad_format_version() {
         // This test ensures version consistency across AAD types.
@@ -13,6 +13,8 @@ use rand::{rngs::OsRng, TryRngCore};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+use crate::ids::SpaceId;
+
 /// Versions
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum KeySlotVersion {
@@ -127,6 +129,34 @@ pub struct KeySlot {
 
     /// MasterKey encrypted (wrapped) by KEK.
     pub wrapped_master_key: Option<WrappedMasterKey>,
+
+    /// Key epoch of the wrapped MasterKey; bumped on every rotation.
+    #[serde(default)]
+    pub epoch: KeyEpoch,
+
+    /// Keyslot generation within an epoch; bumped on every passphrase change.
+    ///
+    /// Together with `epoch` it orders keyslot updates pushed by peers.
+    #[serde(default)]
+    pub revision: u64,
+
+    /// Previous MasterKey (epoch - 1), wrapped by the same KEK.
+    ///
+    /// Present only while history is being re-encrypted after a rotation.
+    #[serde(default)]
+    pub retired_master_key: Option<WrappedMasterKey>,
+
+    /// Keys of older epochs kept after their rotation finished because some
+    /// stored data could not be re-encrypted; wrapped by the same KEK.
+    #[serde(default)]
+    pub retained_master_keys: Vec<RetainedMasterKey>,
+}
+
+/// MasterKey of a finished epoch that still seals some stored data.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetainedMasterKey {
+    pub epoch: KeyEpoch,
+    pub wrapped: WrappedMasterKey,
 }
 
 impl KeySlot {
@@ -143,6 +173,10 @@ impl KeySlot {
             kdf,
             salt,
             wrapped_master_key: None,
+            epoch: 0,
+            revision: 0,
+            retired_master_key: None,
+            retained_master_keys: Vec::new(),
         })
     }
 
@@ -152,8 +186,42 @@ impl KeySlot {
             ..self
         }
     }
+
+    /// Whether this keyslot supersedes `other`: a later epoch, or a later
+    /// revision of the same epoch.
+    pub fn is_newer_than(&self, other: &KeySlot) -> bool {
+        (self.epoch, self.revision) > (other.epoch, other.revision)
+    }
+
+    /// Whether a rotation is still re-encrypting data sealed with the retired key.
+    pub fn is_rotating(&self) -> bool {
+        self.retired_master_key.is_some()
+    }
+
+    /// Wrapped keys of every older epoch that still seals stored data: the
+    /// retired key of a running rotation plus the retained keys.
+    pub fn older_master_keys(&self) -> Vec<(KeyEpoch, &WrappedMasterKey)> {
+        let retired = self
+            .retired_master_key
+            .as_ref()
+            .and_then(|wrapped| Some((self.epoch.checked_sub(1)?, wrapped)));
+        retired
+            .into_iter()
+            .chain(
+                self.retained_master_keys
+                    .iter()
+                    .map(|retained| (retained.epoch, &retained.wrapped)),
+            )
+            .collect()
+    }
 }
 
+/// Master key generation counter.
+///
+/// Every `EncryptedBlob` sealed with the MasterKey records the epoch it was
+/// sealed under, so readers can pick the right key while a rotation runs.
+pub type KeyEpoch = u32;
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct KeyScope {
     /// Profile ID (user profile)
@@ -161,6 +229,14 @@ pub struct KeyScope {
 }
 
 impl KeyScope {
+    /// Key scope of a space; the default space keeps the original
+    /// `profile:default` identifier.
+    pub fn for_space(space_id: &SpaceId) -> Self {
+        Self {
+            profile_id: space_id.as_ref().to_string(),
+        }
+    }
+
     pub fn to_identifier(&self) -> String {
         format!("profile:{}", self.profile_id)
     }
@@ -193,6 +269,11 @@ pub struct EncryptedBlob {
     /// to help debugging "wrong AAD" vs "wrong key" scenarios.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub aad_fingerprint: Option<Vec<u8>>,
+
+    /// Epoch of the MasterKey that sealed this blob (0 for wrapped keys and
+    /// data written before key rotation existed).
+    #[serde(default)]
+    pub key_epoch: KeyEpoch,
 }
 
 /// Secrets (newtypes)
@@ -308,6 +389,15 @@ pub struct KeySlotFile {
     pub created_at: Option<DateTime<Utc>>,
     #[serde(default)]
     pub updated_at: Option<DateTime<Utc>>,
+
+    #[serde(default)]
+    pub epoch: KeyEpoch,
+    #[serde(default)]
+    pub revision: u64,
+    #[serde(default)]
+    pub retired_master_key: Option<EncryptedBlob>,
+    #[serde(default)]
+    pub retained_master_keys: Vec<RetainedMasterKey>,
 }
 
 impl TryFrom<&KeySlot> for KeySlotFile {
@@ -327,6 +417,10 @@ impl TryFrom<&KeySlot> for KeySlotFile {
             wrapped_master_key: wrapped_master_key.blob.clone(),
             created_at: None,
             updated_at: None,
+            epoch: ks.epoch,
+            revision: ks.revision,
+            retired_master_key: ks.retired_master_key.as_ref().map(|w| w.blob.clone()),
+            retained_master_keys: ks.retained_master_keys.clone(),
         })
     }
 }
@@ -341,6 +435,10 @@ impl From<KeySlotFile> for KeySlot {
             wrapped_master_key: Some(WrappedMasterKey {
                 blob: ksf.wrapped_master_key,
             }),
+            epoch: ksf.epoch,
+            revision: ksf.revision,
+            retired_master_key: ksf.retired_master_key.map(|blob| WrappedMasterKey { blob }),
+            retained_master_keys: ksf.retained_master_keys,
         }
     }
 }
@@ -1,6 +1,9 @@
 use std::time::Duration;
 
 use super::model::*;
+use crate::ids::SpaceId;
+use crate::network::BUSINESS_PROTOCOL_VERSION;
+use crate::space::SpaceProfile;
 
 impl Default for GeneralSettings {
     /// Returns the default `GeneralSettings` used when no user preferences are configured.
@@ -44,8 +47,11 @@ impl Default for GeneralSettings {
 impl Default for SyncSettings {
     /// Creates a `SyncSettings` populated with sensible defaults.
     ///
-    /// The defaults enable automatic syncing, use realtime sync frequency, include the
-    /// default content types, and limit individual files to 100 MB.
+    /// The defaults enable automatic syncing, use realtime sync frequency, include every
+    /// content type, limit individual files to 100 MB, and write content
+    /// received from peers to the system clipboard. Interval mode, when
+    /// selected, flushes the latest capture once a minute. History missed
+    /// while offline is caught up for the last 24 hours.
     ///
     /// # Examples
     ///
@@ -56,13 +62,20 @@ impl Default for SyncSettings {
     /// assert!(s.auto_sync);
     /// assert_eq!(s.sync_frequency, SyncFrequency::Realtime);
     /// assert_eq!(s.max_file_size_mb, 100);
+    /// assert!(s.apply_remote_to_clipboard);
+    /// assert_eq!(s.sync_interval_secs, 60);
+    /// assert_eq!(s.catch_up_window_secs, 24 * 60 * 60);
     /// ```
     fn default() -> Self {
         Self {
             auto_sync: true,
             sync_frequency: SyncFrequency::Realtime,
-            content_types: ContentTypes::default(),
+            content_types: ContentTypes::all(),
             max_file_size_mb: 100,
+            apply_remote_to_clipboard: true,
+            sync_interval_secs: 60,
+            interval_batch_mode: IntervalBatchMode::LatestOnly,
+            catch_up_window_secs: 24 * 60 * 60,
         }
     }
 }
@@ -97,6 +110,7 @@ impl Default for RetentionPolicy {
             enabled: true,
             skip_pinned: true,
             evaluation: RuleEvaluation::AnyMatch,
+            delete_mode: RetentionDeleteMode::default(),
             rules: vec![
                 RetentionRule::ByAge {
                     max_age: Duration::from_secs(60 * 60 * 24 * 30), // 30 days
@@ -107,6 +121,22 @@ impl Default for RetentionPolicy {
     }
 }
 
+impl Default for RetentionDeleteMode {
+    /// Expired entries are soft-deleted by default, so an upgrade never removes
+    /// history outright. The retention pass purges them after a grace period.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uc_core::settings::model::RetentionDeleteMode;
+    ///
+    /// assert_eq!(RetentionDeleteMode::default(), RetentionDeleteMode::Soft);
+    /// ```
+    fn default() -> Self {
+        RetentionDeleteMode::Soft
+    }
+}
+
 impl Default for SecuritySettings {
     /// Creates default security settings with encryption disabled and no passphrase configured.
     ///
@@ -140,14 +170,25 @@ impl Default for PairingSettings {
     /// - `user_verification_timeout`: 120 seconds
     /// - `session_timeout`: 300 seconds
     /// - `max_retries`: 3
-    /// - `protocol_version`: "1.0.0"
+    /// - `protocol_version`: [`BUSINESS_PROTOCOL_VERSION`] ("1.0.0")
     fn default() -> Self {
         Self {
             step_timeout: Duration::from_secs(15),
             user_verification_timeout: Duration::from_secs(120),
             session_timeout: Duration::from_secs(300),
             max_retries: 3,
-            protocol_version: "1.0.0".to_string(),
+            protocol_version: BUSINESS_PROTOCOL_VERSION.to_string(),
+        }
+    }
+}
+
+impl Default for SpaceSettings {
+    /// Only the default space exists and is active, with no routing rules.
+    fn default() -> Self {
+        Self {
+            active_space_id: SpaceId::default_space(),
+            spaces: vec![SpaceProfile::default_space()],
+            routing_rules: Vec::new(),
         }
     }
 }
@@ -156,7 +197,7 @@ impl Default for Settings {
     /// Constructs a Settings instance populated with the current schema version and sensible nested defaults.
     ///
     /// The created `Settings` uses `CURRENT_SCHEMA_VERSION` for `schema_version` and the `Default` implementations
-    /// of the nested settings types for `general`, `sync`, `retention_policy`, `security`, and `pairing`.
+    /// of the nested settings types for `general`, `sync`, `retention_policy`, `security`, `pairing`, `network`, and `spaces`.
     ///
     /// # Examples
     ///
@@ -171,11 +212,13 @@ impl Default for Settings {
     /// let _ = settings.retention_policy;
     /// let _ = settings.security;
     /// let _ = settings.pairing;
+    /// let _ = settings.network;
+    /// let _ = settings.spaces;
     /// ```
     ///
     /// # Returns
     ///
-    /// `Settings` initialized with `CURRENT_SCHEMA_VERSION` and default values for `general`, `sync`, `retention_policy`, `security`, and `pairing`.
+    /// `Settings` initialized with `CURRENT_SCHEMA_VERSION` and default values for `general`, `sync`, `retention_policy`, `security`, `pairing`, `network`, and `spaces`.
     fn default() -> Self {
         Self {
             schema_version: CURRENT_SCHEMA_VERSION,
@@ -184,6 +227,8 @@ impl Default for Settings {
             retention_policy: RetentionPolicy::default(),
             security: SecuritySettings::default(),
             pairing: PairingSettings::default(),
+            network: NetworkSettings::default(),
+            spaces: SpaceSettings::default(),
         }
     }
 }
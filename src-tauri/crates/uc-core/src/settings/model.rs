@@ -3,7 +3,11 @@ use std::time::Duration;
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DurationSeconds};
 
-pub const CURRENT_SCHEMA_VERSION: u32 = 1;
+use crate::clipboard::ContentKind;
+use crate::ids::SpaceId;
+use crate::space::{SpaceProfile, SpaceRoutingRule};
+
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GeneralSettings {
@@ -24,7 +28,7 @@ pub enum Theme {
     System,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct ContentTypes {
     pub text: bool,
     pub image: bool,
@@ -34,15 +38,83 @@ pub struct ContentTypes {
     pub rich_text: bool,
 }
 
+impl ContentTypes {
+    /// Every content kind switched on.
+    pub fn all() -> Self {
+        Self {
+            text: true,
+            image: true,
+            link: true,
+            file: true,
+            code_snippet: true,
+            rich_text: true,
+        }
+    }
+
+    /// Whether every content kind is switched off.
+    pub fn is_empty(&self) -> bool {
+        !(self.text || self.image || self.link || self.file || self.code_snippet || self.rich_text)
+    }
+
+    /// Whether the given content kind is switched on.
+    /// `Unknown` never matches.
+    pub fn includes(&self, kind: ContentKind) -> bool {
+        match kind {
+            ContentKind::Text => self.text,
+            ContentKind::RichText => self.rich_text,
+            ContentKind::Image => self.image,
+            ContentKind::Link => self.link,
+            ContentKind::File => self.file,
+            ContentKind::CodeSnippet => self.code_snippet,
+            ContentKind::Unknown => false,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncSettings {
     pub auto_sync: bool,
     pub sync_frequency: SyncFrequency,
 
-    #[serde(default)]
+    #[serde(default = "ContentTypes::all")]
     pub content_types: ContentTypes,
 
     pub max_file_size_mb: u32,
+
+    /// 收到远端同步内容后是否写入系统剪贴板
+    ///
+    /// 关闭时仅写入历史记录
+    #[serde(default = "default_apply_remote_to_clipboard")]
+    pub apply_remote_to_clipboard: bool,
+
+    /// 间隔同步模式下的发送周期（秒）
+    ///
+    /// Only used when `sync_frequency` is `Interval`.
+    #[serde(default = "default_sync_interval_secs")]
+    pub sync_interval_secs: u64,
+
+    /// 间隔同步模式下排队内容的合并方式
+    #[serde(default)]
+    pub interval_batch_mode: IntervalBatchMode,
+
+    /// 设备重新上线时补同步的历史时间窗口（秒），0 表示关闭
+    ///
+    /// Entries captured on a peer within this window and missed while offline
+    /// are pulled when the peer becomes ready again.
+    #[serde(default = "default_catch_up_window_secs")]
+    pub catch_up_window_secs: u64,
+}
+
+fn default_apply_remote_to_clipboard() -> bool {
+    true
+}
+
+fn default_catch_up_window_secs() -> u64 {
+    24 * 60 * 60
+}
+
+fn default_sync_interval_secs() -> u64 {
+    60
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -52,6 +124,17 @@ pub enum SyncFrequency {
     Interval,
 }
 
+/// 间隔同步时如何合并一个周期内捕获的内容
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IntervalBatchMode {
+    /// 只发送周期内最后一次捕获的内容
+    #[default]
+    LatestOnly,
+    /// 按捕获顺序发送周期内的全部内容
+    All,
+}
+
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -96,6 +179,19 @@ pub struct RetentionPolicy {
     pub rules: Vec<RetentionRule>,
     pub skip_pinned: bool,
     pub evaluation: RuleEvaluation,
+
+    /// 命中规则的条目如何删除
+    #[serde(default)]
+    pub delete_mode: RetentionDeleteMode,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionDeleteMode {
+    /// 仅标记 deleted_at，宽限期过后由保留任务彻底删除
+    Soft,
+    /// 删除条目、事件与表示
+    Hard,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -131,6 +227,112 @@ pub struct PairingSettings {
     pub protocol_version: String,
 }
 
+/// 网络设置
+///
+/// Used when multicast discovery (mDNS) is blocked, e.g. on segmented office
+/// networks.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NetworkSettings {
+    /// 固定监听端口，0 表示由系统随机分配
+    #[serde(default)]
+    pub listen_port: u16,
+
+    /// 手动添加的对端地址（multiaddr），启动时及周期性重连时拨号
+    #[serde(default)]
+    pub bootstrap_peers: Vec<String>,
+
+    /// 仅使用 TCP；在屏蔽 UDP 的网络上关闭 QUIC（重启后生效）
+    #[serde(default)]
+    pub tcp_only: bool,
+
+    /// 允许监听的接口（接口名或 CIDR），为空表示所有符合条件的接口
+    #[serde(default)]
+    pub allowed_interfaces: Vec<String>,
+
+    /// 禁止监听的接口（接口名或 CIDR），优先于允许列表
+    #[serde(default)]
+    pub denied_interfaces: Vec<String>,
+
+    /// 跨网络同步（中继 + 打洞 + rendezvous）
+    #[serde(default)]
+    pub relay: RelaySettings,
+}
+
+/// 中继设置
+///
+/// Connects devices on different networks through a libp2p relay-v2 node,
+/// which also serves as the rendezvous point. Relayed connections are
+/// upgraded to direct ones via DCUtR hole punching when possible.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RelaySettings {
+    /// 是否启用中继（重启后生效）
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// 中继节点 multiaddr，需以 `/p2p/<peer id>` 结尾
+    #[serde(default)]
+    pub address: String,
+
+    /// rendezvous 命名空间，相同命名空间的设备互相发现；为空时由当前空间的密钥派生
+    #[serde(default)]
+    pub namespace: String,
+}
+
+/// 空间设置
+///
+/// Known spaces, the active one, and rules naming the space captures belong to.
+///
+/// Captures are always recorded in the active space, the only one whose
+/// master key is unlocked, so a new rule naming another space is rejected
+/// (see [`SpaceSettings::new_rule_for_inactive_space`]).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SpaceSettings {
+    /// 当前活动空间
+    #[serde(default = "SpaceId::default_space")]
+    pub active_space_id: SpaceId,
+
+    /// 本机已知的空间，始终包含默认空间
+    #[serde(default)]
+    pub spaces: Vec<SpaceProfile>,
+
+    /// 按顺序匹配的捕获路由规则，首个命中者生效
+    #[serde(default)]
+    pub routing_rules: Vec<SpaceRoutingRule>,
+}
+
+impl SpaceSettings {
+    pub fn find(&self, space_id: &SpaceId) -> Option<&SpaceProfile> {
+        self.spaces.iter().find(|space| &space.id == space_id)
+    }
+
+    /// Space a capture with these kinds and text belongs to.
+    ///
+    /// 首个命中且指向已知空间的规则生效，否则归入活动空间。规则只能在保存时指向活动空间，
+    /// 切换空间后遗留的规则可能指向非活动空间。
+    pub fn route_capture(&self, kinds: &[ContentKind], text: Option<&str>) -> SpaceId {
+        self.routing_rules
+            .iter()
+            .filter(|rule| self.find(&rule.space_id).is_some())
+            .find(|rule| rule.matches(kinds, text))
+            .map(|rule| rule.space_id.clone())
+            .unwrap_or_else(|| self.active_space_id.clone())
+    }
+
+    /// First rule not already in `previous` that names a space other than the
+    /// active one.
+    ///
+    /// 返回新增的、指向非活动空间的首条规则；非活动空间的主密钥未解锁，
+    /// 捕获内容无法写入其历史分区，因此保存设置时拒绝此类规则。
+    pub fn new_rule_for_inactive_space(
+        &self,
+        previous: &SpaceSettings,
+    ) -> Option<&SpaceRoutingRule> {
+        self.routing_rules.iter().find(|rule| {
+            rule.space_id != self.active_space_id && !previous.routing_rules.contains(rule)
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Settings {
     #[serde(default = "current_schema_version")]
@@ -150,8 +352,12 @@ pub struct Settings {
 
     #[serde(default)]
     pub pairing: PairingSettings,
-    // #[serde(default)]
-    // pub network: NetworkSettings,
+
+    #[serde(default)]
+    pub network: NetworkSettings,
+
+    #[serde(default)]
+    pub spaces: SpaceSettings,
 }
 
 /// The current schema version used for settings persistence.
@@ -174,7 +380,13 @@ pub fn current_schema_version() -> u32 {
 
 #[cfg(test)]
 mod tests {
-    use super::{RuleEvaluation, SecuritySettings, Settings, SyncFrequency};
+    use super::{
+        IntervalBatchMode, RelaySettings, RuleEvaluation, SecuritySettings, Settings,
+        SpaceSettings, SyncFrequency, SyncSettings,
+    };
+    use crate::clipboard::ContentKind;
+    use crate::ids::SpaceId;
+    use crate::space::{SpaceProfile, SpaceRoutingRule};
     use serde_json::json;
 
     #[test]
@@ -204,6 +416,23 @@ mod tests {
         assert!(!settings.auto_unlock_enabled);
     }
 
+    #[test]
+    fn test_sync_settings_defaults_apply_remote_on_missing_field() {
+        let value = json!({
+            "auto_sync": true,
+            "sync_frequency": "realtime",
+            "max_file_size_mb": 10
+        });
+
+        let settings: SyncSettings =
+            serde_json::from_value(value).expect("deserialize sync settings");
+
+        assert!(settings.apply_remote_to_clipboard);
+        assert_eq!(settings.sync_interval_secs, 60);
+        assert_eq!(settings.interval_batch_mode, IntervalBatchMode::LatestOnly);
+        assert_eq!(settings.catch_up_window_secs, 24 * 60 * 60);
+    }
+
     #[test]
     fn test_pairing_settings_defaults_when_missing() {
         let value = serde_json::json!({
@@ -216,5 +445,75 @@ mod tests {
         assert_eq!(settings.pairing.session_timeout.as_secs(), 300);
         assert_eq!(settings.pairing.max_retries, 3);
         assert_eq!(settings.pairing.protocol_version, "1.0.0");
+        assert_eq!(settings.network.listen_port, 0);
+        assert!(settings.network.bootstrap_peers.is_empty());
+        assert!(!settings.network.tcp_only);
+        assert!(settings.network.allowed_interfaces.is_empty());
+        assert!(settings.network.denied_interfaces.is_empty());
+        assert_eq!(settings.network.relay, RelaySettings::default());
+    }
+
+    #[test]
+    fn test_space_settings_default_when_missing() {
+        let settings: Settings =
+            serde_json::from_value(json!({ "schema_version": 1 })).expect("deserialize settings");
+
+        assert!(settings.spaces.active_space_id.is_default());
+        assert_eq!(settings.spaces.spaces, vec![SpaceProfile::default_space()]);
+        assert!(settings.spaces.routing_rules.is_empty());
+    }
+
+    #[test]
+    fn test_new_rule_for_inactive_space_ignores_stored_rules() {
+        let work_rule = SpaceRoutingRule {
+            space_id: SpaceId::from("work"),
+            content_kinds: vec![ContentKind::Image],
+            text_contains: None,
+        };
+        let mut previous = SpaceSettings::default();
+        let mut spaces = previous.clone();
+        spaces.routing_rules.push(work_rule.clone());
+
+        assert_eq!(
+            spaces.new_rule_for_inactive_space(&previous),
+            Some(&work_rule)
+        );
+
+        previous.routing_rules.push(work_rule);
+        assert_eq!(spaces.new_rule_for_inactive_space(&previous), None);
+
+        spaces.routing_rules.push(SpaceRoutingRule {
+            space_id: SpaceId::default_space(),
+            content_kinds: vec![],
+            text_contains: Some("jira".to_string()),
+        });
+        assert_eq!(spaces.new_rule_for_inactive_space(&previous), None);
+    }
+
+    #[test]
+    fn test_route_capture_ignores_rules_for_unknown_spaces() {
+        let mut spaces = SpaceSettings::default();
+        spaces.routing_rules.push(SpaceRoutingRule {
+            space_id: SpaceId::from("work"),
+            content_kinds: vec![ContentKind::Image],
+            text_contains: None,
+        });
+
+        assert!(spaces
+            .route_capture(&[ContentKind::Image], None)
+            .is_default());
+
+        spaces.spaces.push(SpaceProfile {
+            id: SpaceId::from("work"),
+            name: "Work".to_string(),
+            created_at_ms: 1,
+        });
+        assert_eq!(
+            spaces.route_capture(&[ContentKind::Image], None),
+            SpaceId::from("work")
+        );
+        assert!(spaces
+            .route_capture(&[ContentKind::Text], None)
+            .is_default());
     }
 }
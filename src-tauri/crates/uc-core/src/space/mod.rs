@@ -0,0 +1,108 @@
+//! Spaces: isolated clipboard domains.
+//! 空间：相互隔离的剪贴板域
+//!
+//! Each space has its own key scope (keyslot + master key), its own set of
+//! paired devices and its own history partition. Exactly one space is active
+//! at a time and captures are recorded there: other spaces stay locked, so
+//! routing rules may only name the active space when they are saved.
+
+use serde::{Deserialize, Serialize};
+
+use crate::clipboard::ContentKind;
+use crate::ids::SpaceId;
+
+/// A space known to this device
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpaceProfile {
+    pub id: SpaceId,
+    pub name: String,
+    #[serde(default)]
+    pub created_at_ms: i64,
+}
+
+impl SpaceProfile {
+    /// The space every installation starts with.
+    pub fn default_space() -> Self {
+        Self {
+            id: SpaceId::default_space(),
+            name: "Default".to_string(),
+            created_at_ms: 0,
+        }
+    }
+}
+
+/// 捕获路由规则：匹配的剪贴板内容归入 `space_id`
+///
+/// All present criteria must match; a rule without criteria never matches.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpaceRoutingRule {
+    pub space_id: SpaceId,
+
+    /// 内容类别，任一命中即可；为空表示不按类别筛选
+    #[serde(default)]
+    pub content_kinds: Vec<ContentKind>,
+
+    /// 文本包含的子串（不区分大小写）
+    #[serde(default)]
+    pub text_contains: Option<String>,
+}
+
+impl SpaceRoutingRule {
+    pub fn matches(&self, kinds: &[ContentKind], text: Option<&str>) -> bool {
+        let needle = self
+            .text_contains
+            .as_deref()
+            .map(str::trim)
+            .filter(|needle| !needle.is_empty());
+        if self.content_kinds.is_empty() && needle.is_none() {
+            return false;
+        }
+
+        let kind_matches = self.content_kinds.is_empty()
+            || kinds.iter().any(|kind| self.content_kinds.contains(kind));
+        let text_matches = match needle {
+            None => true,
+            Some(needle) => text
+                .map(|text| text.to_lowercase().contains(&needle.to_lowercase()))
+                .unwrap_or(false),
+        };
+        kind_matches && text_matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(kinds: Vec<ContentKind>, text: Option<&str>) -> SpaceRoutingRule {
+        SpaceRoutingRule {
+            space_id: SpaceId::from("work"),
+            content_kinds: kinds,
+            text_contains: text.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn rule_without_criteria_never_matches() {
+        assert!(!rule(vec![], None).matches(&[ContentKind::Text], Some("anything")));
+        assert!(!rule(vec![], Some("  ")).matches(&[ContentKind::Text], Some("anything")));
+    }
+
+    #[test]
+    fn rule_requires_all_present_criteria() {
+        let rule = rule(vec![ContentKind::Text], Some("JIRA-"));
+
+        assert!(rule.matches(&[ContentKind::Text], Some("see jira-123")));
+        assert!(!rule.matches(&[ContentKind::Image], Some("see jira-123")));
+        assert!(!rule.matches(&[ContentKind::Text], Some("nothing here")));
+        assert!(!rule.matches(&[ContentKind::Text], None));
+    }
+
+    #[test]
+    fn kind_only_rule_matches_any_listed_kind() {
+        let rule = rule(vec![ContentKind::Image, ContentKind::File], None);
+
+        assert!(rule.matches(&[ContentKind::Text, ContentKind::File], None));
+        assert!(!rule.matches(&[ContentKind::Text], Some("text")));
+    }
+}
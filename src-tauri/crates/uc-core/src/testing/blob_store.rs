@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::ports::{BlobReader, BlobStorePort, StoredBlobObject};
+use crate::BlobId;
+
+/// In-memory [`BlobStorePort`] holding plaintext bytes by blob id.
+///
+/// 内存中的 blob 存储；读取不存在的 blob 返回错误，删除不存在的 blob 返回 0。
+#[derive(Default)]
+pub struct InMemoryBlobStore {
+    blobs: Mutex<HashMap<BlobId, Vec<u8>>>,
+}
+
+impl InMemoryBlobStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_blob(self, blob_id: &BlobId, bytes: &[u8]) -> Self {
+        self.insert(blob_id, bytes);
+        self
+    }
+
+    pub fn insert(&self, blob_id: &BlobId, bytes: &[u8]) {
+        self.blobs
+            .lock()
+            .unwrap()
+            .insert(blob_id.clone(), bytes.to_vec());
+    }
+
+    pub fn contains(&self, blob_id: &BlobId) -> bool {
+        self.blobs.lock().unwrap().contains_key(blob_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.blobs.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn path(blob_id: &BlobId) -> PathBuf {
+        PathBuf::from(format!("mem://{}", blob_id))
+    }
+}
+
+#[async_trait]
+impl BlobStorePort for InMemoryBlobStore {
+    async fn put(&self, blob_id: &BlobId, data: &[u8]) -> Result<PathBuf> {
+        self.insert(blob_id, data);
+        Ok(Self::path(blob_id))
+    }
+
+    async fn get(&self, blob_id: &BlobId) -> Result<Vec<u8>> {
+        self.blobs
+            .lock()
+            .unwrap()
+            .get(blob_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("blob not found: {}", blob_id))
+    }
+
+    async fn put_stream(
+        &self,
+        blob_id: &BlobId,
+        reader: &mut (dyn AsyncRead + Send + Unpin),
+    ) -> Result<PathBuf> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).await?;
+        self.put(blob_id, &data).await
+    }
+
+    async fn open_read(&self, blob_id: &BlobId) -> Result<BlobReader> {
+        Ok(Box::new(Cursor::new(self.get(blob_id).await?)))
+    }
+
+    async fn delete(&self, blob_id: &BlobId) -> Result<u64> {
+        Ok(self
+            .blobs
+            .lock()
+            .unwrap()
+            .remove(blob_id)
+            .map_or(0, |bytes| bytes.len() as u64))
+    }
+
+    async fn list_objects(&self) -> Result<Vec<StoredBlobObject>> {
+        Ok(self
+            .blobs
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(blob_id, bytes)| StoredBlobObject {
+                blob_id: blob_id.clone(),
+                size_bytes: bytes.len() as u64,
+                modified_at_ms: 0,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,11 @@
+//! Shared in-memory port doubles for tests
+//!
+//! 供各 crate 测试共用的端口替身，仅在测试或启用 `test-support` 特性时编译。
+
+mod blob_store;
+mod network;
+mod paired_device_repository;
+
+pub use blob_store::InMemoryBlobStore;
+pub use network::TestNetwork;
+pub use paired_device_repository::InMemoryPairedDeviceRepository;
@@ -0,0 +1,362 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use tokio::sync::mpsc;
+
+use crate::network::{
+    BlobManifest, ClipboardMessage, ConnectedPeer, DiscoveredPeer, LocalNetworkInfo, NetworkEvent,
+    PairingMessage, PeerCapabilities, PeerLiveness, TRANSFER_CHUNK_BYTES,
+};
+use crate::ports::NetworkPort;
+use crate::ContentHash;
+
+/// In-memory [`NetworkPort`] that records every outgoing call.
+///
+/// 记录所有调用的内存网络端口。连接的设备、可拉取的 blob 以及需要失败的操作在构造时配置，
+/// 其余调用一律成功并记录下来供断言使用。
+///
+/// Operations are named after the port method (`"send_clipboard"`,
+/// `"dial_address"`, ...) when configuring failures.
+pub struct TestNetwork {
+    local_peer_id: String,
+    connected: Mutex<Vec<String>>,
+    discovered: Mutex<Vec<DiscoveredPeer>>,
+    liveness: Mutex<Vec<PeerLiveness>>,
+    capabilities: Mutex<Vec<PeerCapabilities>>,
+    local_network_info: Mutex<LocalNetworkInfo>,
+    blobs: Mutex<HashMap<ContentHash, Vec<u8>>>,
+    failures: Mutex<HashMap<&'static str, String>>,
+    offline_peers: Mutex<HashSet<String>>,
+    sent: Mutex<Vec<(String, Vec<u8>)>>,
+    announced_names: Mutex<Vec<String>>,
+    offered: Mutex<Vec<(Vec<u8>, Vec<String>)>>,
+    dialed_addresses: Mutex<Vec<String>>,
+    dialed_peers: Mutex<Vec<(String, Vec<String>)>>,
+    opened_sessions: Mutex<Vec<(String, String)>>,
+    pairing_messages: Mutex<Vec<(String, PairingMessage)>>,
+    closed_sessions: Mutex<Vec<(String, Option<String>)>>,
+    unpaired: Mutex<Vec<String>>,
+}
+
+impl Default for TestNetwork {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestNetwork {
+    pub fn new() -> Self {
+        Self {
+            local_peer_id: "local-peer".to_string(),
+            connected: Mutex::new(Vec::new()),
+            discovered: Mutex::new(Vec::new()),
+            liveness: Mutex::new(Vec::new()),
+            capabilities: Mutex::new(Vec::new()),
+            local_network_info: Mutex::new(LocalNetworkInfo::default()),
+            blobs: Mutex::new(HashMap::new()),
+            failures: Mutex::new(HashMap::new()),
+            offline_peers: Mutex::new(HashSet::new()),
+            sent: Mutex::new(Vec::new()),
+            announced_names: Mutex::new(Vec::new()),
+            offered: Mutex::new(Vec::new()),
+            dialed_addresses: Mutex::new(Vec::new()),
+            dialed_peers: Mutex::new(Vec::new()),
+            opened_sessions: Mutex::new(Vec::new()),
+            pairing_messages: Mutex::new(Vec::new()),
+            closed_sessions: Mutex::new(Vec::new()),
+            unpaired: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn with_local_peer_id(mut self, peer_id: impl Into<String>) -> Self {
+        self.local_peer_id = peer_id.into();
+        self
+    }
+
+    /// Peers reported by `get_connected_peers`.
+    pub fn with_connected<I, S>(self, peer_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.set_connected(peer_ids);
+        self
+    }
+
+    pub fn with_discovered(self, peers: Vec<DiscoveredPeer>) -> Self {
+        *self.discovered.lock().unwrap() = peers;
+        self
+    }
+
+    pub fn with_liveness(self, liveness: Vec<PeerLiveness>) -> Self {
+        *self.liveness.lock().unwrap() = liveness;
+        self
+    }
+
+    pub fn with_capabilities(self, capabilities: Vec<PeerCapabilities>) -> Self {
+        *self.capabilities.lock().unwrap() = capabilities;
+        self
+    }
+
+    pub fn with_local_network_info(self, info: LocalNetworkInfo) -> Self {
+        *self.local_network_info.lock().unwrap() = info;
+        self
+    }
+
+    /// Make `bytes` available to `fetch_blob` / `fetch_blob_from_any`.
+    pub fn serving(self, bytes: &[u8]) -> Self {
+        let manifest = BlobManifest::from_bytes(bytes, TRANSFER_CHUNK_BYTES);
+        self.blobs
+            .lock()
+            .unwrap()
+            .insert(manifest.content_hash, bytes.to_vec());
+        self
+    }
+
+    /// Fail every call to `operation` with `message`.
+    pub fn failing(self, operation: &'static str, message: impl Into<String>) -> Self {
+        self.failures
+            .lock()
+            .unwrap()
+            .insert(operation, message.into());
+        self
+    }
+
+    /// Fail sends and revocation notices addressed to `peer_id`.
+    pub fn with_offline_peer(self, peer_id: impl Into<String>) -> Self {
+        self.set_online(&peer_id.into(), false);
+        self
+    }
+
+    pub fn set_connected<I, S>(&self, peer_ids: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        *self.connected.lock().unwrap() = peer_ids.into_iter().map(Into::into).collect();
+    }
+
+    pub fn set_online(&self, peer_id: &str, online: bool) {
+        let mut offline = self.offline_peers.lock().unwrap();
+        if online {
+            offline.remove(peer_id);
+        } else {
+            offline.insert(peer_id.to_string());
+        }
+    }
+
+    /// Clipboard frames sent so far, as `(peer_id, bytes)`.
+    pub fn sent_clipboard(&self) -> Vec<(String, Vec<u8>)> {
+        self.sent.lock().unwrap().clone()
+    }
+
+    /// Forget recorded clipboard frames.
+    pub fn clear_sent(&self) {
+        self.sent.lock().unwrap().clear();
+    }
+
+    pub fn announced_names(&self) -> Vec<String> {
+        self.announced_names.lock().unwrap().clone()
+    }
+
+    /// Bytes handed to `offer_blob`, in order.
+    /// Offered bytes with the peers allowed to pull them.
+    pub fn offered_blobs(&self) -> Vec<(Vec<u8>, Vec<String>)> {
+        self.offered.lock().unwrap().clone()
+    }
+
+    pub fn dialed_addresses(&self) -> Vec<String> {
+        self.dialed_addresses.lock().unwrap().clone()
+    }
+
+    pub fn dialed_peers(&self) -> Vec<(String, Vec<String>)> {
+        self.dialed_peers.lock().unwrap().clone()
+    }
+
+    /// Pairing sessions opened, as `(peer_id, session_id)`.
+    pub fn opened_sessions(&self) -> Vec<(String, String)> {
+        self.opened_sessions.lock().unwrap().clone()
+    }
+
+    /// Pairing messages sent, as `(session_id, message)`.
+    pub fn pairing_messages(&self) -> Vec<(String, PairingMessage)> {
+        self.pairing_messages.lock().unwrap().clone()
+    }
+
+    pub fn closed_sessions(&self) -> Vec<(String, Option<String>)> {
+        self.closed_sessions.lock().unwrap().clone()
+    }
+
+    /// Peers a revocation notice was delivered to.
+    pub fn unpaired_peers(&self) -> Vec<String> {
+        self.unpaired.lock().unwrap().clone()
+    }
+
+    fn check(&self, operation: &'static str) -> Result<()> {
+        match self.failures.lock().unwrap().get(operation) {
+            Some(message) => Err(anyhow::anyhow!(message.clone())),
+            None => Ok(()),
+        }
+    }
+
+    fn check_reachable(&self, operation: &'static str, peer_id: &str) -> Result<()> {
+        self.check(operation)?;
+        if self.offline_peers.lock().unwrap().contains(peer_id) {
+            return Err(anyhow::anyhow!("peer {peer_id} offline"));
+        }
+        Ok(())
+    }
+
+    fn blob(&self, manifest: &BlobManifest) -> Result<Vec<u8>> {
+        self.blobs
+            .lock()
+            .unwrap()
+            .get(&manifest.content_hash)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("no peer holds blob {}", manifest.content_hash))
+    }
+}
+
+#[async_trait]
+impl NetworkPort for TestNetwork {
+    async fn send_clipboard(&self, peer_id: &str, encrypted_data: Vec<u8>) -> Result<()> {
+        self.check_reachable("send_clipboard", peer_id)?;
+        self.sent
+            .lock()
+            .unwrap()
+            .push((peer_id.to_string(), encrypted_data));
+        Ok(())
+    }
+
+    async fn subscribe_clipboard(&self) -> Result<mpsc::Receiver<ClipboardMessage>> {
+        self.check("subscribe_clipboard")?;
+        let (_tx, rx) = mpsc::channel(1);
+        Ok(rx)
+    }
+
+    async fn fetch_blob(&self, peer_id: &str, manifest: BlobManifest) -> Result<Vec<u8>> {
+        self.check_reachable("fetch_blob", peer_id)?;
+        self.blob(&manifest)
+    }
+
+    async fn fetch_blob_from_any(&self, manifest: BlobManifest) -> Result<Vec<u8>> {
+        self.check("fetch_blob_from_any")?;
+        self.blob(&manifest)
+    }
+
+    async fn offer_blob(&self, bytes: Vec<u8>, peer_ids: &[String]) -> Result<BlobManifest> {
+        self.check("offer_blob")?;
+        let manifest = BlobManifest::from_bytes(&bytes, TRANSFER_CHUNK_BYTES);
+        self.offered
+            .lock()
+            .unwrap()
+            .push((bytes, peer_ids.to_vec()));
+        Ok(manifest)
+    }
+
+    async fn get_discovered_peers(&self) -> Result<Vec<DiscoveredPeer>> {
+        self.check("get_discovered_peers")?;
+        Ok(self.discovered.lock().unwrap().clone())
+    }
+
+    async fn get_connected_peers(&self) -> Result<Vec<ConnectedPeer>> {
+        self.check("get_connected_peers")?;
+        Ok(self
+            .connected
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|peer_id| ConnectedPeer {
+                peer_id: peer_id.clone(),
+                device_name: "Peer".to_string(),
+                connected_at: Utc::now(),
+            })
+            .collect())
+    }
+
+    fn local_peer_id(&self) -> String {
+        self.local_peer_id.clone()
+    }
+
+    async fn announce_device_name(&self, device_name: String) -> Result<()> {
+        self.announced_names.lock().unwrap().push(device_name);
+        self.check("announce_device_name")
+    }
+
+    async fn get_peer_liveness(&self) -> Result<Vec<PeerLiveness>> {
+        self.check("get_peer_liveness")?;
+        Ok(self.liveness.lock().unwrap().clone())
+    }
+
+    async fn get_peer_capabilities(&self) -> Result<Vec<PeerCapabilities>> {
+        self.check("get_peer_capabilities")?;
+        Ok(self.capabilities.lock().unwrap().clone())
+    }
+
+    async fn dial_address(&self, address: String) -> Result<()> {
+        self.dialed_addresses.lock().unwrap().push(address);
+        self.check("dial_address")
+    }
+
+    fn parse_peer_address(&self, address: &str) -> Result<String> {
+        self.check("parse_peer_address")?;
+        Ok(address.to_string())
+    }
+
+    async fn dial_peer(&self, peer_id: String, addresses: Vec<String>) -> Result<()> {
+        self.dialed_peers.lock().unwrap().push((peer_id, addresses));
+        self.check("dial_peer")
+    }
+
+    async fn get_local_network_info(&self) -> Result<LocalNetworkInfo> {
+        self.check("get_local_network_info")?;
+        Ok(self.local_network_info.lock().unwrap().clone())
+    }
+
+    async fn open_pairing_session(&self, peer_id: String, session_id: String) -> Result<()> {
+        self.opened_sessions
+            .lock()
+            .unwrap()
+            .push((peer_id, session_id));
+        self.check("open_pairing_session")
+    }
+
+    async fn send_pairing_on_session(
+        &self,
+        session_id: String,
+        message: PairingMessage,
+    ) -> Result<()> {
+        self.pairing_messages
+            .lock()
+            .unwrap()
+            .push((session_id, message));
+        self.check("send_pairing_on_session")
+    }
+
+    async fn close_pairing_session(
+        &self,
+        session_id: String,
+        reason: Option<String>,
+    ) -> Result<()> {
+        self.closed_sessions
+            .lock()
+            .unwrap()
+            .push((session_id, reason));
+        self.check("close_pairing_session")
+    }
+
+    async fn unpair_device(&self, peer_id: String) -> Result<()> {
+        self.check_reachable("unpair_device", &peer_id)?;
+        self.unpaired.lock().unwrap().push(peer_id);
+        Ok(())
+    }
+
+    async fn subscribe_events(&self) -> Result<mpsc::Receiver<NetworkEvent>> {
+        self.check("subscribe_events")?;
+        let (_tx, rx) = mpsc::channel(1);
+        Ok(rx)
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::network::{DeviceSyncPolicy, PairedDevice, PairingState, PeerCapabilities};
+use crate::ports::{PairedDeviceRepositoryError, PairedDeviceRepositoryPort};
+use crate::PeerId;
+
+/// In-memory [`PairedDeviceRepositoryPort`].
+///
+/// 内存中的已配对设备仓储，行为与数据库实现一致：更新不存在的设备返回 `NotFound`，
+/// 删除设备时一并删除其地址、同步策略与能力，待送达的撤销通知保留。
+#[derive(Default)]
+pub struct InMemoryPairedDeviceRepository {
+    devices: Mutex<Vec<PairedDevice>>,
+    addresses: Mutex<HashMap<PeerId, Vec<String>>>,
+    pending_revocations: Mutex<HashMap<PeerId, DateTime<Utc>>>,
+    policies: Mutex<HashMap<PeerId, DeviceSyncPolicy>>,
+    capabilities: Mutex<HashMap<PeerId, PeerCapabilities>>,
+}
+
+impl InMemoryPairedDeviceRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_devices(devices: Vec<PairedDevice>) -> Self {
+        let repo = Self::new();
+        *repo.devices.lock().unwrap() = devices;
+        repo
+    }
+
+    pub fn with_sync_policy(self, peer_id: &str, policy: DeviceSyncPolicy) -> Self {
+        self.policies
+            .lock()
+            .unwrap()
+            .insert(PeerId::from(peer_id), policy);
+        self
+    }
+
+    pub fn with_capabilities(self, capabilities: PeerCapabilities) -> Self {
+        self.capabilities
+            .lock()
+            .unwrap()
+            .insert(PeerId::from(capabilities.peer_id.as_str()), capabilities);
+        self
+    }
+
+    /// Snapshot of the stored devices.
+    pub fn devices(&self) -> Vec<PairedDevice> {
+        self.devices.lock().unwrap().clone()
+    }
+
+    pub fn device(&self, peer_id: &str) -> Option<PairedDevice> {
+        self.devices
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|device| device.peer_id.as_str() == peer_id)
+            .cloned()
+    }
+
+    fn update<F>(&self, peer_id: &PeerId, apply: F) -> Result<(), PairedDeviceRepositoryError>
+    where
+        F: FnOnce(&mut PairedDevice),
+    {
+        let mut devices = self.devices.lock().unwrap();
+        let device = devices
+            .iter_mut()
+            .find(|device| &device.peer_id == peer_id)
+            .ok_or(PairedDeviceRepositoryError::NotFound)?;
+        apply(device);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PairedDeviceRepositoryPort for InMemoryPairedDeviceRepository {
+    async fn get_by_peer_id(
+        &self,
+        peer_id: &PeerId,
+    ) -> Result<Option<PairedDevice>, PairedDeviceRepositoryError> {
+        Ok(self.device(peer_id.as_str()))
+    }
+
+    async fn list_all(&self) -> Result<Vec<PairedDevice>, PairedDeviceRepositoryError> {
+        Ok(self.devices())
+    }
+
+    async fn upsert(&self, device: PairedDevice) -> Result<(), PairedDeviceRepositoryError> {
+        self.pending_revocations
+            .lock()
+            .unwrap()
+            .remove(&device.peer_id);
+        let mut devices = self.devices.lock().unwrap();
+        devices.retain(|existing| existing.peer_id != device.peer_id);
+        devices.push(device);
+        Ok(())
+    }
+
+    async fn set_state(
+        &self,
+        peer_id: &PeerId,
+        state: PairingState,
+    ) -> Result<(), PairedDeviceRepositoryError> {
+        self.update(peer_id, |device| device.pairing_state = state)
+    }
+
+    async fn update_last_seen(
+        &self,
+        peer_id: &PeerId,
+        last_seen_at: DateTime<Utc>,
+    ) -> Result<(), PairedDeviceRepositoryError> {
+        self.update(peer_id, |device| device.last_seen_at = Some(last_seen_at))
+    }
+
+    async fn delete(&self, peer_id: &PeerId) -> Result<(), PairedDeviceRepositoryError> {
+        let mut devices = self.devices.lock().unwrap();
+        let before = devices.len();
+        devices.retain(|device| &device.peer_id != peer_id);
+        if devices.len() == before {
+            return Err(PairedDeviceRepositoryError::NotFound);
+        }
+        self.addresses.lock().unwrap().remove(peer_id);
+        self.policies.lock().unwrap().remove(peer_id);
+        self.capabilities.lock().unwrap().remove(peer_id);
+        Ok(())
+    }
+
+    async fn record_address(
+        &self,
+        peer_id: &PeerId,
+        address: &str,
+        _confirmed_at: DateTime<Utc>,
+    ) -> Result<(), PairedDeviceRepositoryError> {
+        let mut addresses = self.addresses.lock().unwrap();
+        let known = addresses.entry(peer_id.clone()).or_default();
+        known.retain(|existing| existing != address);
+        known.insert(0, address.to_string());
+        Ok(())
+    }
+
+    async fn list_addresses(
+        &self,
+        peer_id: &PeerId,
+    ) -> Result<Vec<String>, PairedDeviceRepositoryError> {
+        Ok(self
+            .addresses
+            .lock()
+            .unwrap()
+            .get(peer_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn mark_revocation_pending(
+        &self,
+        peer_id: &PeerId,
+        revoked_at: DateTime<Utc>,
+    ) -> Result<(), PairedDeviceRepositoryError> {
+        self.pending_revocations
+            .lock()
+            .unwrap()
+            .insert(peer_id.clone(), revoked_at);
+        Ok(())
+    }
+
+    async fn is_revocation_pending(
+        &self,
+        peer_id: &PeerId,
+    ) -> Result<bool, PairedDeviceRepositoryError> {
+        Ok(self
+            .pending_revocations
+            .lock()
+            .unwrap()
+            .contains_key(peer_id))
+    }
+
+    async fn clear_revocation_pending(
+        &self,
+        peer_id: &PeerId,
+    ) -> Result<(), PairedDeviceRepositoryError> {
+        self.pending_revocations.lock().unwrap().remove(peer_id);
+        Ok(())
+    }
+
+    async fn get_sync_policy(
+        &self,
+        peer_id: &PeerId,
+    ) -> Result<DeviceSyncPolicy, PairedDeviceRepositoryError> {
+        Ok(self
+            .policies
+            .lock()
+            .unwrap()
+            .get(peer_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn set_sync_policy(
+        &self,
+        peer_id: &PeerId,
+        policy: &DeviceSyncPolicy,
+    ) -> Result<(), PairedDeviceRepositoryError> {
+        self.policies
+            .lock()
+            .unwrap()
+            .insert(peer_id.clone(), policy.clone());
+        Ok(())
+    }
+
+    async fn get_capabilities(
+        &self,
+        peer_id: &PeerId,
+    ) -> Result<Option<PeerCapabilities>, PairedDeviceRepositoryError> {
+        Ok(self.capabilities.lock().unwrap().get(peer_id).cloned())
+    }
+
+    async fn set_capabilities(
+        &self,
+        peer_id: &PeerId,
+        capabilities: &PeerCapabilities,
+    ) -> Result<(), PairedDeviceRepositoryError> {
+        self.capabilities
+            .lock()
+            .unwrap()
+            .insert(peer_id.clone(), capabilities.clone());
+        Ok(())
+    }
+}
@@ -0,0 +1 @@
+ALTER TABLE clipboard_entry DROP COLUMN sync_skip_reason;
@@ -0,0 +1,2 @@
+ALTER TABLE clipboard_entry
+ADD COLUMN sync_skip_reason TEXT;
@@ -0,0 +1,9 @@
+-- Snapshots waiting for the next interval flush. `payload` holds the snapshot
+-- sealed with the space key; the wire frame is built per device at flush time.
+CREATE TABLE outbound_sync_queue (
+    message_id TEXT PRIMARY KEY NOT NULL,
+    payload BLOB NOT NULL,
+    enqueued_at_ms INTEGER NOT NULL
+);
+
+CREATE INDEX idx_outbound_sync_queue_enqueued_at ON outbound_sync_queue(enqueued_at_ms);
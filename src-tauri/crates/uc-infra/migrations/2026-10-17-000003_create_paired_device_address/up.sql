@@ -0,0 +1,8 @@
+CREATE TABLE paired_device_address (
+    peer_id TEXT NOT NULL,
+    address TEXT NOT NULL,
+    last_confirmed_at INTEGER NOT NULL,
+    PRIMARY KEY (peer_id, address)
+);
+
+CREATE INDEX idx_paired_device_address_peer ON paired_device_address(peer_id, last_confirmed_at);
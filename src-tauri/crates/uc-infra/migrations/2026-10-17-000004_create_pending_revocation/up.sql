@@ -0,0 +1,4 @@
+CREATE TABLE pending_revocation (
+    peer_id TEXT PRIMARY KEY NOT NULL,
+    revoked_at INTEGER NOT NULL
+);
@@ -0,0 +1 @@
+DROP TABLE clipboard_representation_remote;
@@ -0,0 +1,11 @@
+-- Payloads synced metadata-first and fetched from the sender on demand.
+-- `manifest` and `size_bytes` describe the bytes sealed with the space key,
+-- which is what peers serve.
+CREATE TABLE clipboard_representation_remote (
+    representation_id TEXT PRIMARY KEY NOT NULL,
+    content_hash TEXT NOT NULL,
+    manifest TEXT NOT NULL,
+    size_bytes BIGINT NOT NULL
+);
+
+CREATE INDEX idx_clipboard_representation_remote_hash ON clipboard_representation_remote(content_hash);
@@ -0,0 +1,4 @@
+DROP TABLE paired_device_space;
+
+DROP INDEX idx_clipboard_entry_space;
+ALTER TABLE clipboard_entry DROP COLUMN space_id;
@@ -0,0 +1,15 @@
+-- History partition per space; existing entries belong to the default space.
+ALTER TABLE clipboard_entry
+ADD COLUMN space_id TEXT NOT NULL DEFAULT 'default';
+
+CREATE INDEX idx_clipboard_entry_space ON clipboard_entry(space_id, active_time_ms);
+
+-- Which spaces a paired device belongs to.
+CREATE TABLE paired_device_space (
+    space_id TEXT NOT NULL,
+    peer_id TEXT NOT NULL,
+    PRIMARY KEY (space_id, peer_id)
+);
+
+INSERT INTO paired_device_space (space_id, peer_id)
+SELECT 'default', peer_id FROM paired_device;
@@ -0,0 +1,8 @@
+-- Per-device sync policy; devices without a row use the default
+-- (bidirectional, every content type, auto-apply).
+CREATE TABLE paired_device_sync_policy (
+    peer_id TEXT PRIMARY KEY NOT NULL,
+    direction TEXT NOT NULL,
+    content_types TEXT NOT NULL,
+    apply_mode TEXT NOT NULL
+);
@@ -0,0 +1 @@
+ALTER TABLE paired_device DROP COLUMN device_id;
@@ -0,0 +1,3 @@
+-- Stable device id presented at pairing; empty for devices paired earlier.
+ALTER TABLE paired_device
+ADD COLUMN device_id TEXT NOT NULL DEFAULT '';
@@ -0,0 +1,10 @@
+-- Capabilities negotiated in the last hello with each paired device, so sync
+-- can honour them before the next handshake completes. Devices without a row
+-- are treated as supporting no optional features.
+CREATE TABLE paired_device_capabilities (
+    peer_id TEXT PRIMARY KEY NOT NULL,
+    app_version TEXT NOT NULL,
+    protocol_version TEXT NOT NULL,
+    features TEXT NOT NULL,
+    negotiated_at BIGINT NOT NULL
+);
@@ -51,14 +51,28 @@ where
             content_hash = %content_id,
         );
         async {
-            if let Some(blob) = self.blob_repo.find_by_hash(content_id).await? {
-                return Ok(blob);
+            if let Some(mut blob) = self.blob_repo.find_by_hash(content_id).await? {
+                // Refresh the row before handing it out, so garbage collection
+                // cannot remove it before the caller stores its reference. If
+                // collection won the race, the row is gone and we write anew.
+                let now_ms = self.clock.now_ms();
+                if self.blob_repo.touch_blob(&blob.blob_id, now_ms).await? {
+                    blob.created_at_ms = now_ms;
+                    return Ok(blob);
+                }
+                debug!(
+                    blob_id = %blob.blob_id,
+                    "Deduplicated blob was collected; writing a new copy",
+                );
             }
 
             let blob_id = BlobId::new();
 
             // Encryption is handled by the injected BlobStorePort decorator (if any).
-            let storage_path = self.blob_store.put(&blob_id, plaintext_bytes).await?;
+            // Streaming lets it seal chunk by chunk instead of building the whole
+            // ciphertext next to the plaintext.
+            let mut reader = plaintext_bytes;
+            let storage_path = self.blob_store.put_stream(&blob_id, &mut reader).await?;
 
             let created_at_ms = self.clock.now_ms();
             let blob_storage_locator = BlobStorageLocator::new_local_fs(storage_path);
@@ -111,18 +125,44 @@ mod tests {
     #[async_trait]
     impl BlobStorePort for MockBlobStore {
         async fn put(&self, _blob_id: &BlobId, _data: &[u8]) -> Result<PathBuf> {
-            Ok(self.storage_path.clone())
+            Err(anyhow::anyhow!("blob writer must stream through put_stream"))
         }
 
         async fn get(&self, _blob_id: &BlobId) -> Result<Vec<u8>> {
             Ok(vec![])
         }
+
+        async fn put_stream(
+            &self,
+            _blob_id: &uc_core::BlobId,
+            reader: &mut (dyn tokio::io::AsyncRead + Send + Unpin),
+        ) -> anyhow::Result<std::path::PathBuf> {
+            let mut sink = tokio::io::sink();
+            tokio::io::copy(reader, &mut sink).await?;
+            Ok(self.storage_path.clone())
+        }
+
+        async fn open_read(
+            &self,
+            blob_id: &uc_core::BlobId,
+        ) -> anyhow::Result<uc_core::ports::BlobReader> {
+            Ok(Box::new(std::io::Cursor::new(self.get(blob_id).await?)))
+        }
+
+        async fn delete(&self, _blob_id: &uc_core::BlobId) -> anyhow::Result<u64> {
+            Ok(0)
+        }
+
+        async fn list_objects(&self) -> anyhow::Result<Vec<uc_core::ports::StoredBlobObject>> {
+            Ok(Vec::new())
+        }
     }
 
     /// Mock BlobRepositoryPort
     struct MockBlobRepo {
         existing_blob: Option<Blob>,
         should_fail_insert: bool,
+        existing_collected: bool,
     }
 
     impl MockBlobRepo {
@@ -130,6 +170,7 @@ mod tests {
             Self {
                 existing_blob: None,
                 should_fail_insert: false,
+                existing_collected: false,
             }
         }
 
@@ -142,6 +183,11 @@ mod tests {
             self.should_fail_insert = true;
             self
         }
+
+        fn with_existing_collected(mut self) -> Self {
+            self.existing_collected = true;
+            self
+        }
     }
 
     #[async_trait]
@@ -157,6 +203,38 @@ mod tests {
         async fn find_by_hash(&self, _content_hash: &ContentHash) -> Result<Option<Blob>> {
             Ok(self.existing_blob.clone())
         }
+
+        async fn count_blobs(&self) -> Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_blob_ids(
+            &self,
+            _after: Option<&BlobId>,
+            _limit: usize,
+        ) -> Result<Vec<BlobId>> {
+            Ok(vec![])
+        }
+
+        async fn list_unreferenced_blobs(
+            &self,
+            _created_before_ms: i64,
+            _limit: usize,
+        ) -> Result<Vec<Blob>> {
+            Ok(vec![])
+        }
+
+        async fn touch_blob(&self, _blob_id: &BlobId, _now_ms: i64) -> Result<bool> {
+            Ok(self.existing_blob.is_some() && !self.existing_collected)
+        }
+
+        async fn delete_unreferenced_blob(
+            &self,
+            _blob_id: &BlobId,
+            _created_before_ms: i64,
+        ) -> Result<bool> {
+            Ok(false)
+        }
     }
 
     /// Mock ClockPort
@@ -223,7 +301,37 @@ mod tests {
         assert!(result.is_ok(), "write_if_absent should succeed");
         let blob = result.unwrap();
         assert_eq!(blob.size_bytes, 100);
-        assert_eq!(blob.created_at_ms, 1111111111);
+        assert_eq!(blob.created_at_ms, 1234567890, "reuse refreshes the row");
+    }
+
+    #[tokio::test]
+    async fn test_write_if_absent_rewrites_blob_collected_during_dedup() {
+        let blob_store = MockBlobStore::new();
+        let existing_id = BlobId::new();
+        let existing_blob = Blob::new(
+            existing_id.clone(),
+            BlobStorageLocator::new_local_fs(PathBuf::from("/existing")),
+            100,
+            ContentHash::from(
+                "blake3v1:4444444444444444444444444444444444444444444444444444444444444444",
+            ),
+            1111111111,
+        );
+        let blob_repo = MockBlobRepo::new()
+            .with_existing_blob(existing_blob)
+            .with_existing_collected();
+        let writer = BlobWriter::new(blob_store, blob_repo, MockClock::new());
+
+        let content_id = ContentHash::from(
+            "blake3v1:4444444444444444444444444444444444444444444444444444444444444444",
+        );
+        let blob = writer
+            .write_if_absent(&content_id, b"test data")
+            .await
+            .unwrap();
+
+        assert_ne!(blob.blob_id, existing_id);
+        assert_eq!(blob.size_bytes, 9);
     }
 
     #[tokio::test]
@@ -259,7 +367,7 @@ mod tests {
         );
         let blob = result.unwrap();
         assert_eq!(blob.size_bytes, 200);
-        assert_eq!(blob.created_at_ms, 2222222222);
+        assert_eq!(blob.created_at_ms, 1234567890);
     }
 
     #[tokio::test]
@@ -498,6 +498,10 @@ mod tests {
             );
             Ok(())
         }
+
+        async fn delete_orphaned_thumbnails(&self) -> anyhow::Result<usize> {
+            Ok(0)
+        }
     }
 
     struct MockThumbnailGenerator {
@@ -634,6 +638,26 @@ mod tests {
 
             Ok(ProcessingUpdateOutcome::Updated(current.clone()))
         }
+
+        async fn count_inline_representations(&self) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_inline_representation_ids(
+            &self,
+            _after: Option<&uc_core::ids::RepresentationId>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<(uc_core::ids::EventId, uc_core::ids::RepresentationId)>> {
+            Ok(Vec::new())
+        }
+
+        async fn update_inline_data(
+            &self,
+            _representation_id: &uc_core::ids::RepresentationId,
+            _inline_data: &[u8],
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
     }
 
     fn create_representation(rep_id: &RepresentationId) -> PersistedClipboardRepresentation {
@@ -3,6 +3,7 @@ mod change_origin;
 mod normalizer;
 mod payload_resolver;
 mod representation_cache;
+mod search_index;
 mod selection_resolver;
 mod spool_janitor;
 mod spool_manager;
@@ -16,6 +17,7 @@ pub use change_origin::InMemoryClipboardChangeOrigin;
 pub use normalizer::ClipboardRepresentationNormalizer;
 pub use payload_resolver::ClipboardPayloadResolver;
 pub use representation_cache::{CacheEntryStatus, RepresentationCache};
+pub use search_index::InMemoryClipboardSearchIndex;
 pub use selection_resolver::SelectionResolver;
 pub use spool_janitor::SpoolJanitor;
 pub use spool_manager::{SpoolEntry, SpoolManager};
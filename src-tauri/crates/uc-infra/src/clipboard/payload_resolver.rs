@@ -2,6 +2,7 @@
 //!
 //! Resolves persisted clipboard representations into usable payloads.
 //! Read-only: returns inline data, blob references, or cache/spool bytes.
+//! Remote payloads are the exception: they are pulled from a peer on first use.
 
 use anyhow::Result;
 use async_trait::async_trait;
@@ -9,10 +10,18 @@ use std::sync::Arc;
 use tokio::sync::mpsc;
 use tracing::{debug, error, info_span, warn, Instrument};
 
-use uc_core::clipboard::{PayloadAvailability, PersistedClipboardRepresentation};
+use uc_core::clipboard::{
+    decode_sealed_payload, PayloadAvailability, PersistedClipboardRepresentation, RemotePayloadRef,
+};
 use uc_core::ids::RepresentationId;
-use uc_core::ports::clipboard::ResolvedClipboardPayload;
-use uc_core::ports::ClipboardPayloadResolverPort;
+use uc_core::ports::clipboard::{
+    ProcessingUpdateOutcome, RemotePayloadRepositoryPort, ResolvedClipboardPayload,
+};
+use uc_core::ports::{
+    ClipboardPayloadResolverPort, ClipboardRepresentationRepositoryPort, EncryptionPort,
+    EncryptionSessionPort, NetworkPort,
+};
+use uc_core::security::aad;
 
 use crate::clipboard::{RepresentationCache, SpoolManager};
 
@@ -21,6 +30,18 @@ pub struct ClipboardPayloadResolver {
     cache: Arc<RepresentationCache>,
     spool: Arc<SpoolManager>,
     worker_tx: mpsc::Sender<RepresentationId>,
+    remote: Option<RemoteFetch>,
+}
+
+/// Dependencies needed to pull `Remote` payloads from peers.
+///
+/// 拉取远端数据所需的依赖：网络、解密、远端清单与表示仓库。
+struct RemoteFetch {
+    network: Arc<dyn NetworkPort>,
+    encryption: Arc<dyn EncryptionPort>,
+    encryption_session: Arc<dyn EncryptionSessionPort>,
+    remote_payloads: Arc<dyn RemotePayloadRepositoryPort>,
+    representation_repo: Arc<dyn ClipboardRepresentationRepositoryPort>,
 }
 
 impl ClipboardPayloadResolver {
@@ -33,8 +54,55 @@ impl ClipboardPayloadResolver {
             cache,
             spool,
             worker_tx,
+            remote: None,
         }
     }
+
+    /// Enable on-demand fetching of `Remote` payloads from trusted peers.
+    ///
+    /// 启用按需拉取：未配置时 `Remote` 表示解析失败。
+    pub fn with_remote_fetch(
+        mut self,
+        network: Arc<dyn NetworkPort>,
+        encryption: Arc<dyn EncryptionPort>,
+        encryption_session: Arc<dyn EncryptionSessionPort>,
+        remote_payloads: Arc<dyn RemotePayloadRepositoryPort>,
+        representation_repo: Arc<dyn ClipboardRepresentationRepositoryPort>,
+    ) -> Self {
+        self.remote = Some(RemoteFetch {
+            network,
+            encryption,
+            encryption_session,
+            remote_payloads,
+            representation_repo,
+        });
+        self
+    }
+}
+
+impl RemoteFetch {
+    /// Pull the sealed payload and open it with the space key.
+    ///
+    /// 拉取加密数据并用空间密钥解密，AAD 绑定表示 ID。
+    async fn fetch(&self, remote_ref: RemotePayloadRef) -> Result<Vec<u8>> {
+        let sealed = self
+            .network
+            .fetch_blob_from_any(remote_ref.manifest)
+            .await?;
+        let sealed = decode_sealed_payload(&sealed)?;
+        let master_key = self
+            .encryption_session
+            .get_master_key_for_epoch(sealed.key_epoch)
+            .await?;
+        Ok(self
+            .encryption
+            .decrypt_blob(
+                &master_key,
+                &sealed,
+                &aad::for_remote_payload(&remote_ref.representation_id),
+            )
+            .await?)
+    }
 }
 
 #[async_trait]
@@ -132,6 +200,10 @@ impl ClipboardPayloadResolverPort for ClipboardPayloadResolver {
                         }
                     }
                 }
+                PayloadAvailability::Remote => {
+                    let bytes = self.fetch_remote(&representation.id).await?;
+                    Ok(ResolvedClipboardPayload::Inline { mime, bytes })
+                }
                 PayloadAvailability::Lost => {
                     let details = representation
                         .last_error
@@ -159,6 +231,64 @@ impl ClipboardPayloadResolver {
             .unwrap_or_else(|| "application/octet-stream".to_string())
     }
 
+    /// Pull a `Remote` payload, then hand it to the blob worker like a fresh capture.
+    async fn fetch_remote(&self, rep_id: &RepresentationId) -> Result<Vec<u8>> {
+        // A concurrent resolve may already have fetched it.
+        if let Some(bytes) = self.cache.get(rep_id).await {
+            return Ok(bytes);
+        }
+
+        let remote = self.remote.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("remote payload fetch is not configured for {}", rep_id)
+        })?;
+        let remote_ref = remote
+            .remote_payloads
+            .get_by_representation_id(rep_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("no remote manifest recorded for {}", rep_id))?;
+
+        debug!(
+            content_hash = %remote_ref.content_hash,
+            size_bytes = remote_ref.size_bytes,
+            "Fetching remote payload from peers"
+        );
+        let bytes = remote.fetch(remote_ref).await.map_err(|err| {
+            warn!(representation_id = %rep_id, error = %err, "Remote payload fetch failed");
+            err
+        })?;
+
+        self.cache.put(rep_id, bytes.clone()).await;
+        if let Err(err) = self.spool.write(rep_id, &bytes).await {
+            // Stay Remote so the next resolve fetches again instead of losing the payload.
+            warn!(representation_id = %rep_id, error = %err, "Failed to spool fetched payload");
+            return Ok(bytes);
+        }
+
+        match remote
+            .representation_repo
+            .update_processing_result(
+                rep_id,
+                &[PayloadAvailability::Remote],
+                None,
+                PayloadAvailability::Staged,
+                None,
+            )
+            .await?
+        {
+            ProcessingUpdateOutcome::Updated(_) => {
+                if let Err(err) = remote.remote_payloads.delete_remote_payload(rep_id).await {
+                    warn!(representation_id = %rep_id, error = %err, "Failed to drop remote manifest");
+                }
+                self.try_requeue(rep_id);
+            }
+            ProcessingUpdateOutcome::StateMismatch | ProcessingUpdateOutcome::NotFound => {
+                debug!(representation_id = %rep_id, "Representation left Remote state during fetch");
+            }
+        }
+
+        Ok(bytes)
+    }
+
     fn try_requeue(&self, rep_id: &RepresentationId) {
         if let Err(err) = self.worker_tx.try_send(rep_id.clone()) {
             warn!(
@@ -176,8 +306,15 @@ mod tests {
     use std::str::FromStr;
     use std::time::Duration;
 
+    use std::sync::Mutex;
     use tempfile::tempdir;
-    use uc_core::ids::{BlobId, FormatId, RepresentationId};
+    use uc_core::clipboard::{encode_sealed_payload, ContentHash};
+    use uc_core::ids::{BlobId, EventId, FormatId, RepresentationId};
+    use uc_core::network::BlobManifest;
+    use uc_core::security::model::{EncryptionAlgo, MasterKey};
+
+    use crate::security::{EncryptionRepository, InMemoryEncryptionSession};
+    use uc_core::testing::TestNetwork;
     use uc_core::MimeType;
 
     #[tokio::test]
@@ -345,4 +482,199 @@ mod tests {
         assert!(result.is_err(), "Expected error when payload is lost");
         Ok(())
     }
+
+    struct FakeRemotePayloads {
+        entries: Mutex<Vec<RemotePayloadRef>>,
+    }
+
+    #[async_trait]
+    impl RemotePayloadRepositoryPort for FakeRemotePayloads {
+        async fn insert_remote_payload(&self, remote: &RemotePayloadRef) -> Result<()> {
+            self.entries.lock().unwrap().push(remote.clone());
+            Ok(())
+        }
+        async fn get_by_representation_id(
+            &self,
+            representation_id: &RepresentationId,
+        ) -> Result<Option<RemotePayloadRef>> {
+            Ok(self
+                .entries
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|entry| &entry.representation_id == representation_id)
+                .cloned())
+        }
+        async fn delete_remote_payload(&self, representation_id: &RepresentationId) -> Result<()> {
+            self.entries
+                .lock()
+                .unwrap()
+                .retain(|entry| &entry.representation_id != representation_id);
+            Ok(())
+        }
+    }
+
+    struct FakeRepresentationRepo {
+        transitions: Mutex<Vec<(PayloadAvailability, PayloadAvailability)>>,
+    }
+
+    #[async_trait]
+    impl ClipboardRepresentationRepositoryPort for FakeRepresentationRepo {
+        async fn get_representation(
+            &self,
+            _event_id: &EventId,
+            _representation_id: &RepresentationId,
+        ) -> Result<Option<PersistedClipboardRepresentation>> {
+            Ok(None)
+        }
+        async fn get_representation_by_id(
+            &self,
+            _representation_id: &RepresentationId,
+        ) -> Result<Option<PersistedClipboardRepresentation>> {
+            Ok(None)
+        }
+        async fn get_representation_by_blob_id(
+            &self,
+            _blob_id: &BlobId,
+        ) -> Result<Option<PersistedClipboardRepresentation>> {
+            Ok(None)
+        }
+        async fn update_blob_id(
+            &self,
+            _representation_id: &RepresentationId,
+            _blob_id: &BlobId,
+        ) -> Result<()> {
+            Ok(())
+        }
+        async fn update_blob_id_if_none(
+            &self,
+            _representation_id: &RepresentationId,
+            _blob_id: &BlobId,
+        ) -> Result<bool> {
+            Ok(false)
+        }
+        async fn update_processing_result(
+            &self,
+            rep_id: &RepresentationId,
+            expected_states: &[PayloadAvailability],
+            _blob_id: Option<&BlobId>,
+            new_state: PayloadAvailability,
+            _last_error: Option<&str>,
+        ) -> Result<ProcessingUpdateOutcome> {
+            self.transitions
+                .lock()
+                .unwrap()
+                .push((expected_states[0].clone(), new_state.clone()));
+            Ok(ProcessingUpdateOutcome::Updated(
+                PersistedClipboardRepresentation::new_staged(
+                    rep_id.clone(),
+                    FormatId::from("public.png"),
+                    None,
+                    0,
+                ),
+            ))
+        }
+
+        async fn count_inline_representations(&self) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_inline_representation_ids(
+            &self,
+            _after: Option<&uc_core::ids::RepresentationId>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<(uc_core::ids::EventId, uc_core::ids::RepresentationId)>> {
+            Ok(Vec::new())
+        }
+
+        async fn update_inline_data(
+            &self,
+            _representation_id: &uc_core::ids::RepresentationId,
+            _inline_data: &[u8],
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_remote_fetches_stages_and_requeues() -> Result<()> {
+        let cache = Arc::new(RepresentationCache::new(10, 1024));
+        let temp_dir = tempdir()?;
+        let spool = Arc::new(SpoolManager::new(temp_dir.path(), 1024)?);
+        let (worker_tx, mut worker_rx) = mpsc::channel(1);
+        let bytes = vec![5; 64];
+        let rep_id = RepresentationId::new();
+        let master_key = MasterKey::generate()?;
+        let session = Arc::new(InMemoryEncryptionSession::new());
+        session.set_master_key(master_key.clone()).await?;
+        let sealed = EncryptionRepository
+            .encrypt_blob(
+                &master_key,
+                &bytes,
+                &aad::for_remote_payload(&rep_id),
+                EncryptionAlgo::XChaCha20Poly1305,
+            )
+            .await?;
+        let sealed = encode_sealed_payload(sealed)?;
+        let remote_payloads = Arc::new(FakeRemotePayloads {
+            entries: Mutex::new(vec![RemotePayloadRef {
+                representation_id: rep_id.clone(),
+                content_hash: ContentHash::from(blake3::hash(&bytes).as_bytes()),
+                size_bytes: bytes.len() as u64,
+                manifest: BlobManifest::from_bytes(&sealed, 16),
+            }]),
+        });
+        let representation_repo = Arc::new(FakeRepresentationRepo {
+            transitions: Mutex::new(vec![]),
+        });
+        let resolver = ClipboardPayloadResolver::new(cache.clone(), spool.clone(), worker_tx)
+            .with_remote_fetch(
+                Arc::new(TestNetwork::new().serving(&sealed)),
+                Arc::new(EncryptionRepository),
+                session,
+                remote_payloads.clone(),
+                representation_repo.clone(),
+            );
+
+        let rep = PersistedClipboardRepresentation::new_remote(
+            rep_id.clone(),
+            FormatId::from("public.png"),
+            Some(MimeType::from_str("image/png")?),
+            bytes.len() as i64,
+        );
+
+        match resolver.resolve(&rep).await? {
+            ResolvedClipboardPayload::Inline { bytes: out, .. } => assert_eq!(out, bytes),
+            _ => panic!("Expected inline payload from remote fetch"),
+        }
+        assert_eq!(spool.read(&rep_id).await?, Some(bytes.clone()));
+        assert_eq!(
+            representation_repo.transitions.lock().unwrap().as_slice(),
+            &[(PayloadAvailability::Remote, PayloadAvailability::Staged)]
+        );
+        assert!(remote_payloads.entries.lock().unwrap().is_empty());
+
+        let requeued = tokio::time::timeout(Duration::from_millis(50), worker_rx.recv()).await?;
+        assert_eq!(requeued, Some(rep_id));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_resolve_remote_without_fetch_configured_returns_error() -> Result<()> {
+        let cache = Arc::new(RepresentationCache::new(10, 1024));
+        let temp_dir = tempdir()?;
+        let spool = Arc::new(SpoolManager::new(temp_dir.path(), 1024)?);
+        let (worker_tx, _worker_rx) = mpsc::channel(1);
+        let resolver = ClipboardPayloadResolver::new(cache, spool, worker_tx);
+
+        let rep = PersistedClipboardRepresentation::new_remote(
+            RepresentationId::new(),
+            FormatId::from("public.png"),
+            Some(MimeType::from_str("image/png")?),
+            10,
+        );
+
+        assert!(resolver.resolve(&rep).await.is_err());
+        Ok(())
+    }
 }
@@ -0,0 +1,465 @@
+//! In-memory SQLite FTS5 index for clipboard full-text search
+//! 基于内存 SQLite FTS5 的剪贴板全文检索索引
+//!
+//! Clipboard payloads are encrypted at rest, so the index never touches disk:
+//! it lives in a private `:memory:` database that is created when the
+//! encryption session is unlocked and dropped on close. Content is rebuilt
+//! from the (decrypting) repositories after each open.
+//!
+//! The `trigram` tokenizer is used so that CJK text and substrings match
+//! without word segmentation. Terms shorter than three characters cannot be
+//! served by the trigram index and fall back to `LIKE`.
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use diesel::sql_types::{BigInt, Double, Text};
+use diesel::sqlite::Sqlite;
+use diesel::{Connection, QueryableByName, RunQueryDsl, SqliteConnection};
+use std::sync::Mutex;
+use uc_core::clipboard::{
+    ClipboardSearchDocument, ClipboardSearchHit, ClipboardSearchQuery, ContentKind, SnippetSegment,
+};
+use uc_core::ids::EntryId;
+use uc_core::ports::clipboard::ClipboardSearchIndexPort;
+
+const TRIGRAM_MIN_CHARS: usize = 3;
+/// FTS5 caps snippets at 64 tokens; with trigrams one token is roughly one character.
+const SNIPPET_TOKENS: i64 = 64;
+const FALLBACK_SNIPPET_CHARS: usize = 160;
+const HIGHLIGHT_START: &str = "\u{2}";
+const HIGHLIGHT_END: &str = "\u{3}";
+
+const CREATE_TABLE_SQL: &str = "CREATE VIRTUAL TABLE clipboard_search USING fts5(\
+     content, \
+     entry_id UNINDEXED, \
+     content_kind UNINDEXED, \
+     source_device UNINDEXED, \
+     created_at_ms UNINDEXED, \
+     tokenize = 'trigram')";
+
+#[derive(QueryableByName)]
+struct SearchRow {
+    #[diesel(sql_type = Text)]
+    entry_id: String,
+    #[diesel(sql_type = Text)]
+    snippet: String,
+    #[diesel(sql_type = Text)]
+    content_kind: String,
+    #[diesel(sql_type = Text)]
+    source_device: String,
+    #[diesel(sql_type = BigInt)]
+    created_at_ms: i64,
+    #[diesel(sql_type = Double)]
+    rank: f64,
+}
+
+enum Bind {
+    Text(String),
+    Int(i64),
+}
+
+/// In-memory FTS5 clipboard search index.
+/// 内存中的 FTS5 剪贴板检索索引。
+#[derive(Default)]
+pub struct InMemoryClipboardSearchIndex {
+    conn: Mutex<Option<SqliteConnection>>,
+}
+
+impl InMemoryClipboardSearchIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn with_conn<T>(
+        &self,
+        f: impl FnOnce(&mut SqliteConnection) -> Result<T>,
+    ) -> Result<Option<T>> {
+        let mut guard = self
+            .conn
+            .lock()
+            .map_err(|_| anyhow!("search index lock poisoned"))?;
+        match guard.as_mut() {
+            Some(conn) => f(conn).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+#[async_trait]
+impl ClipboardSearchIndexPort for InMemoryClipboardSearchIndex {
+    async fn is_open(&self) -> bool {
+        self.conn
+            .lock()
+            .map(|guard| guard.is_some())
+            .unwrap_or(false)
+    }
+
+    async fn open(&self) -> Result<()> {
+        let mut conn = SqliteConnection::establish(":memory:")?;
+        diesel::sql_query(CREATE_TABLE_SQL).execute(&mut conn)?;
+        let mut guard = self
+            .conn
+            .lock()
+            .map_err(|_| anyhow!("search index lock poisoned"))?;
+        *guard = Some(conn);
+        Ok(())
+    }
+
+    async fn close(&self) -> Result<()> {
+        let mut guard = self
+            .conn
+            .lock()
+            .map_err(|_| anyhow!("search index lock poisoned"))?;
+        *guard = None;
+        Ok(())
+    }
+
+    async fn index_document(&self, document: &ClipboardSearchDocument) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.transaction(|conn| {
+                diesel::sql_query("DELETE FROM clipboard_search WHERE entry_id = ?")
+                    .bind::<Text, _>(document.entry_id.as_ref())
+                    .execute(conn)?;
+                diesel::sql_query(
+                    "INSERT INTO clipboard_search \
+                     (content, entry_id, content_kind, source_device, created_at_ms) \
+                     VALUES (?, ?, ?, ?, ?)",
+                )
+                .bind::<Text, _>(&document.text)
+                .bind::<Text, _>(document.entry_id.as_ref())
+                .bind::<Text, _>(document.content_kind.as_str())
+                .bind::<Text, _>(&document.source_device)
+                .bind::<BigInt, _>(document.created_at_ms)
+                .execute(conn)?;
+                Ok(())
+            })
+        })?;
+        Ok(())
+    }
+
+    async fn remove_entry(&self, entry_id: &EntryId) -> Result<()> {
+        self.with_conn(|conn| {
+            diesel::sql_query("DELETE FROM clipboard_search WHERE entry_id = ?")
+                .bind::<Text, _>(entry_id.as_ref())
+                .execute(conn)?;
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    async fn search(&self, query: &ClipboardSearchQuery) -> Result<Vec<ClipboardSearchHit>> {
+        let terms = query.terms();
+        let match_expr = terms
+            .iter()
+            .filter(|t| t.text.chars().count() >= TRIGRAM_MIN_CHARS)
+            .map(|t| format!("\"{}\"", t.text.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(" AND ");
+        let short_terms: Vec<&str> = terms
+            .iter()
+            .filter(|t| t.text.chars().count() < TRIGRAM_MIN_CHARS)
+            .map(|t| t.text.as_str())
+            .collect();
+
+        let mut binds = Vec::new();
+        let mut sql = if match_expr.is_empty() {
+            binds.push(Bind::Int(FALLBACK_SNIPPET_CHARS as i64));
+            "SELECT entry_id, substr(content, 1, ?) AS snippet, content_kind, source_device, \
+             CAST(created_at_ms AS INTEGER) AS created_at_ms, 0.0 AS rank \
+             FROM clipboard_search WHERE 1 = 1"
+                .to_string()
+        } else {
+            binds.push(Bind::Text(HIGHLIGHT_START.to_string()));
+            binds.push(Bind::Text(HIGHLIGHT_END.to_string()));
+            binds.push(Bind::Int(SNIPPET_TOKENS));
+            binds.push(Bind::Text(match_expr));
+            "SELECT entry_id, snippet(clipboard_search, 0, ?, ?, '…', ?) AS snippet, \
+             content_kind, source_device, CAST(created_at_ms AS INTEGER) AS created_at_ms, \
+             bm25(clipboard_search) AS rank \
+             FROM clipboard_search WHERE clipboard_search MATCH ?"
+                .to_string()
+        };
+
+        for term in &short_terms {
+            sql.push_str(" AND content LIKE ? ESCAPE '\\'");
+            binds.push(Bind::Text(format!("%{}%", escape_like(term))));
+        }
+        if !query.content_kinds.is_empty() {
+            sql.push_str(&format!(
+                " AND content_kind IN ({})",
+                placeholders(query.content_kinds.len())
+            ));
+            binds.extend(
+                query
+                    .content_kinds
+                    .iter()
+                    .map(|kind| Bind::Text(kind.as_str().to_string())),
+            );
+        }
+        if !query.source_devices.is_empty() {
+            sql.push_str(&format!(
+                " AND source_device IN ({})",
+                placeholders(query.source_devices.len())
+            ));
+            binds.extend(query.source_devices.iter().cloned().map(Bind::Text));
+        }
+        if let Some(from_ms) = query.from_ms {
+            sql.push_str(" AND CAST(created_at_ms AS INTEGER) >= ?");
+            binds.push(Bind::Int(from_ms));
+        }
+        if let Some(to_ms) = query.to_ms {
+            sql.push_str(" AND CAST(created_at_ms AS INTEGER) <= ?");
+            binds.push(Bind::Int(to_ms));
+        }
+        sql.push_str(" ORDER BY rank, CAST(created_at_ms AS INTEGER) DESC LIMIT ? OFFSET ?");
+        binds.push(Bind::Int(query.limit as i64));
+        binds.push(Bind::Int(query.offset as i64));
+
+        let rows = self
+            .with_conn(|conn| {
+                let mut statement = diesel::sql_query(sql).into_boxed::<Sqlite>();
+                for bind in binds {
+                    statement = match bind {
+                        Bind::Text(value) => statement.bind::<Text, _>(value),
+                        Bind::Int(value) => statement.bind::<BigInt, _>(value),
+                    };
+                }
+                Ok(statement.load::<SearchRow>(conn)?)
+            })?
+            .ok_or_else(|| anyhow!("search index is not open"))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let snippet = if row.snippet.contains(HIGHLIGHT_START) {
+                    split_marked_snippet(&row.snippet)
+                } else {
+                    highlight_terms(&row.snippet, &short_terms)
+                };
+                Ok(ClipboardSearchHit {
+                    entry_id: EntryId::from(row.entry_id),
+                    snippet,
+                    content_kind: row.content_kind.parse::<ContentKind>()?,
+                    source_device: row.source_device,
+                    created_at_ms: row.created_at_ms,
+                    rank: row.rank,
+                })
+            })
+            .collect()
+    }
+}
+
+fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
+
+fn escape_like(term: &str) -> String {
+    term.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Split an FTS5 snippet with control-character markers into segments.
+fn split_marked_snippet(snippet: &str) -> Vec<SnippetSegment> {
+    let mut segments = Vec::new();
+    let mut rest = snippet;
+    while let Some(start) = rest.find(HIGHLIGHT_START) {
+        push_segment(&mut segments, &rest[..start], false);
+        rest = &rest[start + HIGHLIGHT_START.len()..];
+        let end = rest.find(HIGHLIGHT_END).unwrap_or(rest.len());
+        push_segment(&mut segments, &rest[..end], true);
+        rest = rest.get(end + HIGHLIGHT_END.len()..).unwrap_or("");
+    }
+    push_segment(&mut segments, rest, false);
+    segments
+}
+
+/// Highlight case-insensitive occurrences of short terms in plain text.
+fn highlight_terms(text: &str, terms: &[&str]) -> Vec<SnippetSegment> {
+    let lower = text.to_lowercase();
+    // Lowercasing may change byte lengths for some scripts; only highlight
+    // when offsets stay aligned with the original text.
+    if terms.is_empty() || lower.len() != text.len() {
+        return vec![SnippetSegment {
+            text: text.to_string(),
+            highlighted: false,
+        }];
+    }
+
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for term in terms {
+        let needle = term.to_lowercase();
+        if needle.is_empty() {
+            continue;
+        }
+        ranges.extend(
+            lower
+                .match_indices(&needle)
+                .map(|(start, m)| (start, start + m.len())),
+        );
+    }
+    ranges.sort_unstable();
+
+    let mut segments = Vec::new();
+    let mut cursor = 0;
+    for (start, end) in ranges {
+        if start < cursor {
+            continue;
+        }
+        push_segment(&mut segments, &text[cursor..start], false);
+        push_segment(&mut segments, &text[start..end], true);
+        cursor = end;
+    }
+    push_segment(&mut segments, &text[cursor..], false);
+    segments
+}
+
+fn push_segment(segments: &mut Vec<SnippetSegment>, text: &str, highlighted: bool) {
+    if !text.is_empty() {
+        segments.push(SnippetSegment {
+            text: text.to_string(),
+            highlighted,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(
+        entry_id: &str,
+        text: &str,
+        kind: ContentKind,
+        created_at_ms: i64,
+    ) -> ClipboardSearchDocument {
+        ClipboardSearchDocument {
+            entry_id: EntryId::from(entry_id),
+            text: text.to_string(),
+            content_kind: kind,
+            source_device: "device-a".to_string(),
+            created_at_ms,
+        }
+    }
+
+    fn query(text: &str) -> ClipboardSearchQuery {
+        ClipboardSearchQuery {
+            text: text.to_string(),
+            limit: 20,
+            ..Default::default()
+        }
+    }
+
+    fn ids(hits: &[ClipboardSearchHit]) -> Vec<&str> {
+        hits.iter().map(|hit| hit.entry_id.as_ref()).collect()
+    }
+
+    async fn open_index() -> InMemoryClipboardSearchIndex {
+        let index = InMemoryClipboardSearchIndex::new();
+        index.open().await.expect("open index");
+        index
+            .index_document(&document(
+                "e1",
+                "quarterly invoice for March",
+                ContentKind::Text,
+                1_000,
+            ))
+            .await
+            .unwrap();
+        index
+            .index_document(&document(
+                "e2",
+                "fn main() { println!(\"invoice\") }",
+                ContentKind::CodeSnippet,
+                2_000,
+            ))
+            .await
+            .unwrap();
+        index
+            .index_document(&document(
+                "e3",
+                "会议纪要：明天下午三点开会",
+                ContentKind::Text,
+                3_000,
+            ))
+            .await
+            .unwrap();
+        index
+    }
+
+    #[tokio::test]
+    async fn search_matches_words_phrases_and_cjk() {
+        let index = open_index().await;
+
+        let hits = index.search(&query("invoice")).await.unwrap();
+        assert_eq!(hits.len(), 2);
+
+        let hits = index.search(&query("\"invoice for march\"")).await.unwrap();
+        assert_eq!(ids(&hits), vec!["e1"]);
+        assert!(hits[0]
+            .snippet
+            .iter()
+            .any(|segment| segment.highlighted
+                && segment.text.eq_ignore_ascii_case("invoice for march")));
+
+        let hits = index.search(&query("下午三点")).await.unwrap();
+        assert_eq!(ids(&hits), vec!["e3"]);
+
+        let hits = index.search(&query("会议")).await.unwrap();
+        assert_eq!(ids(&hits), vec!["e3"]);
+        assert!(hits[0]
+            .snippet
+            .iter()
+            .any(|s| s.highlighted && s.text == "会议"));
+    }
+
+    #[tokio::test]
+    async fn search_applies_kind_and_date_filters() {
+        let index = open_index().await;
+
+        let mut q = query("invoice");
+        q.content_kinds = vec![ContentKind::CodeSnippet];
+        assert_eq!(ids(&index.search(&q).await.unwrap()), vec!["e2"]);
+
+        let mut q = query("invoice");
+        q.to_ms = Some(1_500);
+        assert_eq!(ids(&index.search(&q).await.unwrap()), vec!["e1"]);
+
+        let mut q = query("invoice");
+        q.source_devices = vec!["device-b".to_string()];
+        assert!(index.search(&q).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn reindex_replaces_and_remove_deletes() {
+        let index = open_index().await;
+
+        index
+            .index_document(&document("e1", "updated text", ContentKind::Text, 1_000))
+            .await
+            .unwrap();
+        assert_eq!(
+            ids(&index.search(&query("invoice")).await.unwrap()),
+            vec!["e2"]
+        );
+
+        index.remove_entry(&EntryId::from("e2")).await.unwrap();
+        assert!(index.search(&query("invoice")).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn closed_index_drops_writes_and_rejects_search() {
+        let index = InMemoryClipboardSearchIndex::new();
+        assert!(!index.is_open().await);
+
+        index
+            .index_document(&document("e1", "secret", ContentKind::Text, 1))
+            .await
+            .expect("write to closed index is a no-op");
+        assert!(index.search(&query("secret")).await.is_err());
+
+        index.open().await.unwrap();
+        assert!(index.search(&query("secret")).await.unwrap().is_empty());
+        index.close().await.unwrap();
+        assert!(!index.is_open().await);
+    }
+}
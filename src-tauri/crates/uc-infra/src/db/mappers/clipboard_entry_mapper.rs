@@ -2,6 +2,7 @@ use crate::db::models::clipboard_entry::{ClipboardEntryRow, NewClipboardEntryRow
 use crate::db::ports::{InsertMapper, RowMapper};
 use anyhow::Result;
 use uc_core::clipboard::ClipboardEntry;
+use uc_core::ids::space_id::DEFAULT_SPACE_ID;
 
 pub struct ClipboardEntryRowMapper;
 
@@ -14,20 +15,32 @@ impl InsertMapper<ClipboardEntry, NewClipboardEntryRow> for ClipboardEntryRowMap
             active_time_ms: domain.active_time_ms,
             title: domain.title.clone(),
             total_size: domain.total_size,
-            pinned: false, // TODO: implement
+            pinned: domain.pinned,
+            sync_skip_reason: domain
+                .sync_skip_reason
+                .map(|reason| reason.as_str().to_string()),
+            // The repository stamps the active space before inserting.
+            space_id: DEFAULT_SPACE_ID.to_string(),
         })
     }
 }
 
 impl RowMapper<ClipboardEntryRow, ClipboardEntry> for ClipboardEntryRowMapper {
     fn to_domain(&self, row: &ClipboardEntryRow) -> Result<ClipboardEntry> {
-        Ok(ClipboardEntry::new_with_active_time(
+        let mut entry = ClipboardEntry::new_with_active_time(
             row.entry_id.clone().into(),
             row.event_id.clone().into(),
             row.created_at_ms,
             row.active_time_ms,
             row.title.clone(),
             row.total_size,
-        ))
+        );
+        entry.pinned = row.pinned;
+        entry.sync_skip_reason = row
+            .sync_skip_reason
+            .as_deref()
+            .map(str::parse)
+            .transpose()?;
+        Ok(entry)
     }
 }
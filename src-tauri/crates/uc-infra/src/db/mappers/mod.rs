@@ -3,6 +3,7 @@ pub mod clipboard_entry_mapper;
 pub mod clipboard_event_mapper;
 pub mod clipboard_selection_mapper;
 pub mod device_mapper;
+pub mod outbound_sync_queue_mapper;
 pub mod paired_device_mapper;
 pub mod snapshot_representation_mapper;
 pub mod thumbnail_mapper;
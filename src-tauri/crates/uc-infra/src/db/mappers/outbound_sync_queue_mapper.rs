@@ -0,0 +1,26 @@
+use crate::db::models::{NewOutboundSyncQueueRow, OutboundSyncQueueRow};
+use crate::db::ports::{InsertMapper, RowMapper};
+use anyhow::Result;
+use uc_core::ports::QueuedClipboardMessage;
+
+pub struct OutboundSyncQueueRowMapper;
+
+impl InsertMapper<QueuedClipboardMessage, NewOutboundSyncQueueRow> for OutboundSyncQueueRowMapper {
+    fn to_row(&self, domain: &QueuedClipboardMessage) -> Result<NewOutboundSyncQueueRow> {
+        Ok(NewOutboundSyncQueueRow {
+            message_id: domain.message_id.clone(),
+            payload: domain.payload.clone(),
+            enqueued_at_ms: domain.enqueued_at_ms,
+        })
+    }
+}
+
+impl RowMapper<OutboundSyncQueueRow, QueuedClipboardMessage> for OutboundSyncQueueRowMapper {
+    fn to_domain(&self, row: &OutboundSyncQueueRow) -> Result<QueuedClipboardMessage> {
+        Ok(QueuedClipboardMessage {
+            message_id: row.message_id.clone(),
+            payload: row.payload.clone(),
+            enqueued_at_ms: row.enqueued_at_ms,
+        })
+    }
+}
@@ -16,6 +16,7 @@ impl InsertMapper<PairedDevice, NewPairedDeviceRow> for PairedDeviceRowMapper {
             paired_at: domain.paired_at.timestamp(),
             last_seen_at: domain.last_seen_at.map(|dt| dt.timestamp()),
             device_name: domain.device_name.clone(),
+            device_id: domain.device_id.clone(),
         })
     }
 }
@@ -35,6 +36,7 @@ impl RowMapper<PairedDeviceRow, PairedDevice> for PairedDeviceRowMapper {
             paired_at,
             last_seen_at,
             device_name: row.device_name.clone(),
+            device_id: row.device_id.clone(),
         })
     }
 }
@@ -44,6 +46,7 @@ fn pairing_state_to_str(state: &PairingState) -> &'static str {
         PairingState::Pending => "Pending",
         PairingState::Trusted => "Trusted",
         PairingState::Revoked => "Revoked",
+        PairingState::Quarantined => "Quarantined",
     }
 }
 
@@ -52,6 +55,7 @@ fn pairing_state_from_str(value: &str) -> Result<PairingState> {
         "Pending" => Ok(PairingState::Pending),
         "Trusted" => Ok(PairingState::Trusted),
         "Revoked" => Ok(PairingState::Revoked),
+        "Quarantined" => Ok(PairingState::Quarantined),
         _ => Err(anyhow!("invalid pairing_state: {}", value)),
     }
 }
@@ -100,6 +100,7 @@ fn parse_payload_state(row: &SnapshotRepresentationRow) -> Result<PayloadAvailab
             })
         }
         "Lost" => Ok(PayloadAvailability::Lost),
+        "Remote" => Ok(PayloadAvailability::Remote),
         other => Err(anyhow::anyhow!("unknown payload_state: {}", other)),
     }
 }
@@ -12,6 +12,8 @@ pub struct ClipboardEntryRow {
     pub total_size: i64,
     pub pinned: bool,
     pub deleted_at_ms: Option<i64>,
+    pub sync_skip_reason: Option<String>,
+    pub space_id: String,
 }
 
 #[derive(Insertable)]
@@ -24,4 +26,6 @@ pub struct NewClipboardEntryRow {
     pub title: Option<String>,
     pub total_size: i64,
     pub pinned: bool,
+    pub sync_skip_reason: Option<String>,
+    pub space_id: String,
 }
@@ -0,0 +1,23 @@
+use diesel::prelude::*;
+
+use crate::db::schema::clipboard_representation_remote;
+
+#[derive(Debug, Clone, Queryable)]
+#[diesel(table_name = clipboard_representation_remote)]
+pub struct ClipboardRepresentationRemoteRow {
+    pub representation_id: String,
+    pub content_hash: String,
+    /// `BlobManifest` of the sealed payload, as JSON
+    pub manifest: String,
+    /// Plaintext size
+    pub size_bytes: i64,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = clipboard_representation_remote)]
+pub struct NewClipboardRepresentationRemoteRow {
+    pub representation_id: String,
+    pub content_hash: String,
+    pub manifest: String,
+    pub size_bytes: i64,
+}
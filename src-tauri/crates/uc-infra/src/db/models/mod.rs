@@ -1,19 +1,28 @@
 pub mod blob;
 pub mod clipboard_entry;
 pub mod clipboard_event;
+pub mod clipboard_representation_remote;
 pub mod clipboard_representation_thumbnail;
 pub mod clipboard_selection;
 pub mod device_row;
+pub mod outbound_sync_queue;
 pub mod paired_device_row;
 pub mod snapshot_representation;
 
 pub use blob::{BlobRow, NewBlobRow};
 pub use clipboard_entry::{ClipboardEntryRow, NewClipboardEntryRow};
 pub use clipboard_event::{ClipboardEventRow, NewClipboardEventRow};
+pub use clipboard_representation_remote::{
+    ClipboardRepresentationRemoteRow, NewClipboardRepresentationRemoteRow,
+};
 pub use clipboard_representation_thumbnail::{
     ClipboardRepresentationThumbnailRow, NewClipboardRepresentationThumbnailRow,
 };
 pub use clipboard_selection::{ClipboardSelectionRow, NewClipboardSelectionRow};
 pub use device_row::{DeviceRow, NewDeviceRow};
-pub use paired_device_row::{NewPairedDeviceRow, PairedDeviceRow};
+pub use outbound_sync_queue::{NewOutboundSyncQueueRow, OutboundSyncQueueRow};
+pub use paired_device_row::{
+    NewPairedDeviceAddressRow, NewPairedDeviceRow, NewPendingRevocationRow,
+    PairedDeviceCapabilitiesRow, PairedDeviceRow, PairedDeviceSyncPolicyRow,
+};
 pub use snapshot_representation::{NewSnapshotRepresentationRow, SnapshotRepresentationRow};
@@ -0,0 +1,19 @@
+use diesel::prelude::*;
+
+use crate::db::schema::outbound_sync_queue;
+
+#[derive(Debug, Clone, Queryable)]
+#[diesel(table_name = outbound_sync_queue)]
+pub struct OutboundSyncQueueRow {
+    pub message_id: String,
+    pub payload: Vec<u8>,
+    pub enqueued_at_ms: i64,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = outbound_sync_queue)]
+pub struct NewOutboundSyncQueueRow {
+    pub message_id: String,
+    pub payload: Vec<u8>,
+    pub enqueued_at_ms: i64,
+}
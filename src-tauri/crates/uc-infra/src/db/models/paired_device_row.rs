@@ -1,4 +1,7 @@
-use crate::db::schema::paired_device;
+use crate::db::schema::{
+    paired_device, paired_device_address, paired_device_capabilities, paired_device_sync_policy,
+    pending_revocation,
+};
 use diesel::prelude::*;
 
 #[derive(Debug, Queryable)]
@@ -10,6 +13,7 @@ pub struct PairedDeviceRow {
     pub paired_at: i64,
     pub last_seen_at: Option<i64>,
     pub device_name: String,
+    pub device_id: String,
 }
 
 #[derive(Debug, Insertable)]
@@ -21,4 +25,41 @@ pub struct NewPairedDeviceRow {
     pub paired_at: i64,
     pub last_seen_at: Option<i64>,
     pub device_name: String,
+    pub device_id: String,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = paired_device_address)]
+pub struct NewPairedDeviceAddressRow {
+    pub peer_id: String,
+    pub address: String,
+    pub last_confirmed_at: i64,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = pending_revocation)]
+pub struct NewPendingRevocationRow {
+    pub peer_id: String,
+    pub revoked_at: i64,
+}
+
+/// `content_types` holds the JSON of `ContentTypes`.
+#[derive(Debug, Queryable, Insertable)]
+#[diesel(table_name = paired_device_sync_policy)]
+pub struct PairedDeviceSyncPolicyRow {
+    pub peer_id: String,
+    pub direction: String,
+    pub content_types: String,
+    pub apply_mode: String,
+}
+
+/// `features` holds the JSON of the negotiated `ProtocolFeature` list.
+#[derive(Debug, Queryable, Insertable)]
+#[diesel(table_name = paired_device_capabilities)]
+pub struct PairedDeviceCapabilitiesRow {
+    pub peer_id: String,
+    pub app_version: String,
+    pub protocol_version: String,
+    pub features: String,
+    pub negotiated_at: i64,
 }
@@ -3,7 +3,9 @@ use crate::db::models::NewClipboardEntryRow;
 use crate::db::models::NewClipboardSelectionRow;
 use crate::db::ports::DbExecutor;
 use crate::db::ports::{InsertMapper, RowMapper};
-use crate::db::schema::{clipboard_entry, clipboard_selection};
+use crate::db::schema::{
+    clipboard_entry, clipboard_event, clipboard_selection, clipboard_snapshot_representation,
+};
 use anyhow::Result;
 use diesel::query_dsl::methods::FilterDsl;
 use diesel::query_dsl::methods::LimitDsl;
@@ -13,9 +15,15 @@ use diesel::Connection;
 use diesel::ExpressionMethods;
 use diesel::OptionalExtension;
 use diesel::RunQueryDsl;
+use std::collections::HashMap;
+use std::sync::Arc;
 use tracing::debug_span;
-use uc_core::clipboard::{ClipboardEntry, ClipboardSelectionDecision};
-use uc_core::ids::EntryId;
+use uc_core::clipboard::{
+    CatchUpCandidate, ClipboardEntry, ClipboardSelectionDecision, MimeType, RetentionCandidate,
+    RetentionRepresentation,
+};
+use uc_core::ids::{DeviceId, EntryId, FormatId, SpaceId};
+use uc_core::ports::space::ActiveSpacePort;
 use uc_core::ports::ClipboardEntryRepositoryPort;
 
 pub struct DieselClipboardEntryRepository<E, ME, MS, RE> {
@@ -23,6 +31,7 @@ pub struct DieselClipboardEntryRepository<E, ME, MS, RE> {
     entry_mapper: ME,
     selection_mapper: MS,
     row_entry_mapper: RE,
+    active_space: Option<Arc<dyn ActiveSpacePort>>,
 }
 
 impl<E, ME, MS, RE> DieselClipboardEntryRepository<E, ME, MS, RE> {
@@ -32,8 +41,27 @@ impl<E, ME, MS, RE> DieselClipboardEntryRepository<E, ME, MS, RE> {
             entry_mapper,
             selection_mapper,
             row_entry_mapper,
+            active_space: None,
         }
     }
+
+    /// Partition history by the active space.
+    /// 按活动空间划分历史记录。
+    ///
+    /// New entries are stamped with the active space and listings only return
+    /// entries of that space. Without it everything lives in the default space.
+    pub fn with_active_space(mut self, active_space: Arc<dyn ActiveSpacePort>) -> Self {
+        self.active_space = Some(active_space);
+        self
+    }
+
+    fn space_id(&self) -> String {
+        self.active_space
+            .as_ref()
+            .map(|active_space| active_space.active_space())
+            .unwrap_or_else(SpaceId::default_space)
+            .into_inner()
+    }
 }
 
 #[async_trait::async_trait]
@@ -56,7 +84,8 @@ where
         );
         span.in_scope(|| {
             self.executor.run(|conn| {
-                let new_entry_row = self.entry_mapper.to_row(entry)?;
+                let mut new_entry_row = self.entry_mapper.to_row(entry)?;
+                new_entry_row.space_id = self.space_id();
                 let new_selection_row = self.selection_mapper.to_row(selection)?;
 
                 conn.transaction(|conn| {
@@ -85,6 +114,7 @@ where
             self.executor.run(|conn| {
                 let entry_row = clipboard_entry::table
                     .filter(clipboard_entry::entry_id.eq(&entry_id_str))
+                    .filter(clipboard_entry::deleted_at_ms.is_null())
                     .first::<ClipboardEntryRow>(conn)
                     .optional()?;
 
@@ -101,6 +131,8 @@ where
 
     /// Lists clipboard entries ordered by active time (newest first) with pagination.
     ///
+    /// Soft-deleted entries and entries of other spaces are excluded.
+    ///
     /// # Parameters
     ///
     /// - `limit`: Maximum number of entries to return.
@@ -130,6 +162,8 @@ where
         span.in_scope(|| {
             self.executor.run(|conn| {
                 let entry_rows = clipboard_entry::table
+                    .filter(clipboard_entry::space_id.eq(self.space_id()))
+                    .filter(clipboard_entry::deleted_at_ms.is_null())
                     .order(clipboard_entry::active_time_ms.desc())
                     .limit(limit as i64)
                     .offset(offset as i64)
@@ -165,6 +199,57 @@ where
         })
     }
 
+    async fn set_entry_pinned(&self, entry_id: &EntryId, pinned: bool) -> Result<bool> {
+        let span = debug_span!(
+            "infra.sqlite.set_clipboard_entry_pinned",
+            table = "clipboard_entry",
+            entry_id = %entry_id,
+            pinned = pinned,
+        );
+
+        span.in_scope(|| {
+            self.executor.run(|conn| {
+                let affected = diesel::update(clipboard_entry::table)
+                    .filter(clipboard_entry::entry_id.eq(entry_id.to_string()))
+                    .filter(clipboard_entry::deleted_at_ms.is_null())
+                    .set(clipboard_entry::pinned.eq(pinned))
+                    .execute(conn)?;
+
+                Ok(affected > 0)
+            })
+        })
+    }
+
+    async fn list_pinned_entries(
+        &self,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<ClipboardEntry>> {
+        let span = debug_span!(
+            "infra.sqlite.query_pinned_clipboard_entries",
+            table = "clipboard_entry",
+            limit = limit,
+            offset = offset,
+        );
+        span.in_scope(|| {
+            self.executor.run(|conn| {
+                let entry_rows = clipboard_entry::table
+                    .filter(clipboard_entry::space_id.eq(self.space_id()))
+                    .filter(clipboard_entry::deleted_at_ms.is_null())
+                    .filter(clipboard_entry::pinned.eq(true))
+                    .order(clipboard_entry::active_time_ms.desc())
+                    .limit(limit as i64)
+                    .offset(offset as i64)
+                    .load::<ClipboardEntryRow>(conn)?;
+
+                entry_rows
+                    .into_iter()
+                    .map(|row| self.row_entry_mapper.to_domain(&row))
+                    .collect()
+            })
+        })
+    }
+
     /// Deletes the clipboard entry with the given `EntryId` from the database.
     ///
     /// Attempts to remove the entry row whose `entry_id` matches `entry_id`. The operation returns `Ok(())` on success; if no row matches the provided `entry_id` the call still succeeds and returns `Ok(())`.
@@ -196,4 +281,191 @@ where
             })
         })
     }
+
+    async fn list_retention_candidates(&self) -> Result<Vec<RetentionCandidate>> {
+        let span = debug_span!(
+            "infra.sqlite.query_retention_candidates",
+            table = "clipboard_entry",
+        );
+        span.in_scope(|| {
+            self.executor.run(|conn| {
+                let entry_rows = clipboard_entry::table
+                    .filter(clipboard_entry::space_id.eq(self.space_id()))
+                    .filter(clipboard_entry::deleted_at_ms.is_null())
+                    .order(clipboard_entry::active_time_ms.desc())
+                    .load::<ClipboardEntryRow>(conn)?;
+
+                let event_ids: Vec<&str> =
+                    entry_rows.iter().map(|row| row.event_id.as_str()).collect();
+                let rep_rows = diesel::QueryDsl::select(
+                    clipboard_snapshot_representation::table
+                        .filter(clipboard_snapshot_representation::event_id.eq_any(&event_ids)),
+                    (
+                        clipboard_snapshot_representation::event_id,
+                        clipboard_snapshot_representation::format_id,
+                        clipboard_snapshot_representation::mime_type,
+                    ),
+                )
+                .load::<(String, String, Option<String>)>(conn)?;
+
+                let mut reps_by_event: HashMap<String, Vec<RetentionRepresentation>> =
+                    HashMap::new();
+                for (event_id, format_id, mime_type) in rep_rows {
+                    reps_by_event
+                        .entry(event_id)
+                        .or_default()
+                        .push(RetentionRepresentation {
+                            format_id: FormatId::from_string(format_id),
+                            mime_type: mime_type.map(MimeType),
+                        });
+                }
+
+                entry_rows
+                    .iter()
+                    .map(|row| {
+                        Ok(RetentionCandidate {
+                            entry: self.row_entry_mapper.to_domain(row)?,
+                            representations: reps_by_event
+                                .remove(&row.event_id)
+                                .unwrap_or_default(),
+                        })
+                    })
+                    .collect()
+            })
+        })
+    }
+
+    async fn soft_delete_entries(
+        &self,
+        entry_ids: &[EntryId],
+        deleted_at_ms: i64,
+    ) -> Result<usize> {
+        let span = debug_span!(
+            "infra.sqlite.soft_delete_clipboard_entries",
+            table = "clipboard_entry",
+            count = entry_ids.len(),
+        );
+        span.in_scope(|| {
+            let ids: Vec<String> = entry_ids.iter().map(|id| id.to_string()).collect();
+            self.executor.run(|conn| {
+                let affected = diesel::update(clipboard_entry::table)
+                    .filter(clipboard_entry::entry_id.eq_any(&ids))
+                    .filter(clipboard_entry::deleted_at_ms.is_null())
+                    .set(clipboard_entry::deleted_at_ms.eq(Some(deleted_at_ms)))
+                    .execute(conn)?;
+                Ok(affected)
+            })
+        })
+    }
+
+    async fn list_soft_deleted_entries(
+        &self,
+        deleted_before_ms: i64,
+    ) -> Result<Vec<ClipboardEntry>> {
+        let span = debug_span!(
+            "infra.sqlite.query_soft_deleted_entries",
+            table = "clipboard_entry",
+            deleted_before_ms,
+        );
+        span.in_scope(|| {
+            self.executor.run(|conn| {
+                let entry_rows = clipboard_entry::table
+                    .filter(clipboard_entry::space_id.eq(self.space_id()))
+                    .filter(clipboard_entry::deleted_at_ms.lt(deleted_before_ms))
+                    .load::<ClipboardEntryRow>(conn)?;
+
+                entry_rows
+                    .iter()
+                    .map(|row| self.row_entry_mapper.to_domain(row))
+                    .collect()
+            })
+        })
+    }
+
+    async fn list_catch_up_candidates(
+        &self,
+        since_ms: i64,
+        limit: usize,
+    ) -> Result<Vec<CatchUpCandidate>> {
+        let span = debug_span!(
+            "infra.sqlite.query_catch_up_candidates",
+            table = "clipboard_entry",
+            since_ms,
+            limit,
+        );
+        span.in_scope(|| {
+            self.executor.run(|conn| {
+                let entry_rows = clipboard_entry::table
+                    .filter(clipboard_entry::space_id.eq(self.space_id()))
+                    .filter(clipboard_entry::deleted_at_ms.is_null())
+                    .filter(clipboard_entry::created_at_ms.ge(since_ms))
+                    .order(clipboard_entry::created_at_ms.desc())
+                    .limit(limit as i64)
+                    .load::<ClipboardEntryRow>(conn)?;
+
+                let event_ids: Vec<&str> =
+                    entry_rows.iter().map(|row| row.event_id.as_str()).collect();
+                let mut events: HashMap<String, (String, String)> = diesel::QueryDsl::select(
+                    clipboard_event::table.filter(clipboard_event::event_id.eq_any(&event_ids)),
+                    (
+                        clipboard_event::event_id,
+                        clipboard_event::source_device,
+                        clipboard_event::snapshot_hash,
+                    ),
+                )
+                .load::<(String, String, String)>(conn)?
+                .into_iter()
+                .map(|(event_id, source_device, snapshot_hash)| {
+                    (event_id, (source_device, snapshot_hash))
+                })
+                .collect();
+
+                entry_rows
+                    .iter()
+                    .filter_map(|row| {
+                        let (source_device, snapshot_hash) = events.remove(&row.event_id)?;
+                        Some(
+                            self.row_entry_mapper
+                                .to_domain(row)
+                                .map(|entry| CatchUpCandidate {
+                                    entry,
+                                    source_device: DeviceId::new(source_device),
+                                    snapshot_hash,
+                                }),
+                        )
+                    })
+                    .collect()
+            })
+        })
+    }
+
+    async fn get_entry_source_device(&self, entry_id: &EntryId) -> Result<Option<DeviceId>> {
+        let span = debug_span!(
+            "infra.sqlite.query_entry_source_device",
+            table = "clipboard_event",
+            entry_id = %entry_id,
+        );
+        span.in_scope(|| {
+            let entry_id_str = entry_id.to_string();
+            self.executor.run(|conn| {
+                let event_id = diesel::QueryDsl::select(
+                    clipboard_entry::table.filter(clipboard_entry::entry_id.eq(&entry_id_str)),
+                    clipboard_entry::event_id,
+                )
+                .first::<String>(conn)
+                .optional()?;
+                let Some(event_id) = event_id else {
+                    return Ok(None);
+                };
+
+                let source_device = diesel::QueryDsl::select(
+                    clipboard_event::table.filter(clipboard_event::event_id.eq(&event_id)),
+                    clipboard_event::source_device,
+                )
+                .first::<String>(conn)
+                .optional()?;
+                Ok(source_device.map(DeviceId::new))
+            })
+        })
+    }
 }
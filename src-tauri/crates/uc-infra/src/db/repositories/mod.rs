@@ -3,7 +3,9 @@ mod clipboard_entry_repo;
 mod clipboard_event_repo;
 mod clipboard_selection_repo;
 mod device_repo;
+mod outbound_sync_queue_repo;
 mod paired_device_repo;
+mod remote_payload_repo;
 mod representation_repo;
 mod thumbnail_repo;
 
@@ -12,7 +14,9 @@ pub use clipboard_entry_repo::*;
 pub use clipboard_event_repo::*;
 pub use clipboard_selection_repo::*;
 pub use device_repo::*;
+pub use outbound_sync_queue_repo::*;
 pub use paired_device_repo::*;
+pub use remote_payload_repo::*;
 pub use representation_repo::*;
 pub use thumbnail_repo::*;
 
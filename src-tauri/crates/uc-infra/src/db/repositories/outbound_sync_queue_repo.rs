@@ -0,0 +1,139 @@
+use crate::db::mappers::outbound_sync_queue_mapper::OutboundSyncQueueRowMapper;
+use crate::db::models::OutboundSyncQueueRow;
+use crate::db::ports::{DbExecutor, InsertMapper, RowMapper};
+use crate::db::schema::outbound_sync_queue;
+use anyhow::Result;
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use uc_core::ports::{OutboundSyncQueuePort, QueuedClipboardMessage};
+
+pub struct DieselOutboundSyncQueueRepository<E>
+where
+    E: DbExecutor,
+{
+    executor: E,
+}
+
+impl<E> DieselOutboundSyncQueueRepository<E>
+where
+    E: DbExecutor,
+{
+    pub fn new(executor: E) -> Self {
+        Self { executor }
+    }
+}
+
+#[async_trait::async_trait]
+impl<E> OutboundSyncQueuePort for DieselOutboundSyncQueueRepository<E>
+where
+    E: DbExecutor,
+{
+    async fn enqueue(&self, message: QueuedClipboardMessage) -> Result<()> {
+        let new_row = OutboundSyncQueueRowMapper.to_row(&message)?;
+        self.executor.run(|conn| {
+            diesel::insert_into(outbound_sync_queue::table)
+                .values(&new_row)
+                .execute(conn)?;
+            Ok(())
+        })
+    }
+
+    async fn list_pending(&self) -> Result<Vec<QueuedClipboardMessage>> {
+        let rows: Vec<OutboundSyncQueueRow> = self.executor.run(|conn| {
+            let rows = outbound_sync_queue::table
+                .order((
+                    outbound_sync_queue::enqueued_at_ms.asc(),
+                    outbound_sync_queue::message_id.asc(),
+                ))
+                .load::<OutboundSyncQueueRow>(conn)?;
+            Ok(rows)
+        })?;
+
+        rows.iter()
+            .map(|row| OutboundSyncQueueRowMapper.to_domain(row))
+            .collect()
+    }
+
+    async fn remove(&self, message_ids: &[String]) -> Result<usize> {
+        if message_ids.is_empty() {
+            return Ok(0);
+        }
+        self.executor.run(|conn| {
+            let removed = diesel::delete(
+                outbound_sync_queue::table
+                    .filter(outbound_sync_queue::message_id.eq_any(message_ids)),
+            )
+            .execute(conn)?;
+            Ok(removed)
+        })
+    }
+
+    async fn retain_latest(&self, keep: usize) -> Result<usize> {
+        let keep = i64::try_from(keep)?;
+        self.executor.run(|conn| {
+            let latest = outbound_sync_queue::table
+                .select(outbound_sync_queue::message_id)
+                .order((
+                    outbound_sync_queue::enqueued_at_ms.desc(),
+                    outbound_sync_queue::message_id.desc(),
+                ))
+                .limit(keep)
+                .load::<String>(conn)?;
+            let removed = diesel::delete(
+                outbound_sync_queue::table.filter(outbound_sync_queue::message_id.ne_all(latest)),
+            )
+            .execute(conn)?;
+            Ok(removed)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uc_core::ports::{OutboundSyncQueuePort, QueuedClipboardMessage};
+
+    use crate::db::executor::DieselSqliteExecutor;
+    use crate::db::pool::init_db_pool;
+
+    use super::DieselOutboundSyncQueueRepository;
+
+    fn message(id: &str, enqueued_at_ms: i64) -> QueuedClipboardMessage {
+        QueuedClipboardMessage {
+            message_id: id.to_string(),
+            payload: id.as_bytes().to_vec(),
+            enqueued_at_ms,
+        }
+    }
+
+    fn repo() -> DieselOutboundSyncQueueRepository<DieselSqliteExecutor> {
+        let pool = init_db_pool(":memory:").unwrap();
+        DieselOutboundSyncQueueRepository::new(DieselSqliteExecutor::new(pool))
+    }
+
+    #[tokio::test]
+    async fn test_list_pending_returns_oldest_first_and_remove_drops_messages() {
+        let repo = repo();
+        repo.enqueue(message("b", 20)).await.unwrap();
+        repo.enqueue(message("a", 10)).await.unwrap();
+
+        let pending = repo.list_pending().await.unwrap();
+        assert_eq!(pending, vec![message("a", 10), message("b", 20)]);
+
+        assert_eq!(repo.remove(&["a".to_string()]).await.unwrap(), 1);
+        assert_eq!(repo.list_pending().await.unwrap(), vec![message("b", 20)]);
+        assert_eq!(repo.remove(&[]).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_retain_latest_keeps_most_recent_messages() {
+        let repo = repo();
+        for (id, ts) in [("a", 10), ("b", 20), ("c", 30)] {
+            repo.enqueue(message(id, ts)).await.unwrap();
+        }
+
+        assert_eq!(repo.retain_latest(2).await.unwrap(), 1);
+        assert_eq!(
+            repo.list_pending().await.unwrap(),
+            vec![message("b", 20), message("c", 30)]
+        );
+    }
+}
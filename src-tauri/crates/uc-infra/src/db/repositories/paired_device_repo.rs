@@ -1,22 +1,60 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
+use chrono::TimeZone;
 use diesel::prelude::*;
 
-use uc_core::network::{PairedDevice, PairingState};
+use uc_core::ids::SpaceId;
+use uc_core::network::{DeviceSyncPolicy, PairedDevice, PairingState, PeerCapabilities};
+use uc_core::ports::space::ActiveSpacePort;
 use uc_core::ports::{PairedDeviceRepositoryError, PairedDeviceRepositoryPort};
 use uc_core::PeerId;
 
-use crate::db::models::{NewPairedDeviceRow, PairedDeviceRow};
+use crate::db::models::{
+    NewPairedDeviceAddressRow, NewPairedDeviceRow, NewPendingRevocationRow,
+    PairedDeviceCapabilitiesRow, PairedDeviceRow, PairedDeviceSyncPolicyRow,
+};
 use crate::db::ports::{DbExecutor, InsertMapper, RowMapper};
 use crate::db::schema::paired_device::dsl::*;
+use crate::db::schema::{
+    paired_device_address, paired_device_capabilities, paired_device_space,
+    paired_device_sync_policy, pending_revocation,
+};
+
+/// 每个设备保留的地址数量上限
+const MAX_ADDRESSES_PER_DEVICE: i64 = 8;
 
 pub struct DieselPairedDeviceRepository<E, M> {
     executor: E,
     mapper: M,
+    active_space: Option<Arc<dyn ActiveSpacePort>>,
 }
 
 impl<E, M> DieselPairedDeviceRepository<E, M> {
     pub fn new(executor: E, mapper: M) -> Self {
-        Self { executor, mapper }
+        Self {
+            executor,
+            mapper,
+            active_space: None,
+        }
+    }
+
+    /// Scope lookups to devices paired in the active space.
+    /// 仅返回活动空间中配对的设备。
+    ///
+    /// Devices of other spaces look unpaired, so the connection policy keeps
+    /// them off the business protocol. Without it the default space is used.
+    pub fn with_active_space(mut self, active_space: Arc<dyn ActiveSpacePort>) -> Self {
+        self.active_space = Some(active_space);
+        self
+    }
+
+    fn space_id(&self) -> String {
+        self.active_space
+            .as_ref()
+            .map(|active_space| active_space.active_space())
+            .unwrap_or_else(SpaceId::default_space)
+            .into_inner()
     }
 }
 
@@ -34,10 +72,18 @@ where
         peer_id_value: &PeerId,
     ) -> Result<Option<PairedDevice>, PairedDeviceRepositoryError> {
         let peer_id_str = peer_id_value.as_str().to_string();
+        let space = self.space_id();
         self.executor
             .run(move |conn| {
                 let row = paired_device
                     .filter(peer_id.eq(&peer_id_str))
+                    .filter(
+                        peer_id.eq_any(
+                            paired_device_space::table
+                                .filter(paired_device_space::space_id.eq(&space))
+                                .select(paired_device_space::peer_id),
+                        ),
+                    )
                     .first::<PairedDeviceRow>(conn)
                     .optional()
                     .map_err(|e| PairedDeviceRepositoryError::Storage(e.to_string()))?;
@@ -57,9 +103,17 @@ where
     }
 
     async fn list_all(&self) -> Result<Vec<PairedDevice>, PairedDeviceRepositoryError> {
+        let space = self.space_id();
         self.executor
-            .run(|conn| {
+            .run(move |conn| {
                 let rows = paired_device
+                    .filter(
+                        peer_id.eq_any(
+                            paired_device_space::table
+                                .filter(paired_device_space::space_id.eq(&space))
+                                .select(paired_device_space::peer_id),
+                        ),
+                    )
                     .load::<PairedDeviceRow>(conn)
                     .map_err(|e| anyhow::anyhow!(e.to_string()))?;
 
@@ -86,23 +140,39 @@ where
             .mapper
             .to_row(&device)
             .map_err(|e| PairedDeviceRepositoryError::Storage(e.to_string()))?;
+        let space = self.space_id();
 
         self.executor
             .run(move |conn| {
-                diesel::insert_into(paired_device)
-                    .values(&row)
-                    .on_conflict(peer_id)
-                    .do_update()
-                    .set((
-                        pairing_state.eq(row.pairing_state.clone()),
-                        identity_fingerprint.eq(row.identity_fingerprint.clone()),
-                        paired_at.eq(row.paired_at),
-                        last_seen_at.eq(row.last_seen_at),
-                        device_name.eq(row.device_name.clone()),
-                    ))
-                    .execute(conn)
-                    .map_err(|e| PairedDeviceRepositoryError::Storage(e.to_string()))?;
-                Ok(())
+                conn.transaction(|conn| {
+                    diesel::insert_into(paired_device)
+                        .values(&row)
+                        .on_conflict(peer_id)
+                        .do_update()
+                        .set((
+                            pairing_state.eq(row.pairing_state.clone()),
+                            identity_fingerprint.eq(row.identity_fingerprint.clone()),
+                            paired_at.eq(row.paired_at),
+                            last_seen_at.eq(row.last_seen_at),
+                            device_name.eq(row.device_name.clone()),
+                            device_id.eq(row.device_id.clone()),
+                        ))
+                        .execute(conn)?;
+                    diesel::insert_or_ignore_into(paired_device_space::table)
+                        .values((
+                            paired_device_space::space_id.eq(&space),
+                            paired_device_space::peer_id.eq(&row.peer_id),
+                        ))
+                        .execute(conn)?;
+                    // Re-pairing supersedes an undelivered unpair notice.
+                    diesel::delete(
+                        pending_revocation::table
+                            .filter(pending_revocation::peer_id.eq(&row.peer_id)),
+                    )
+                    .execute(conn)?;
+                    Ok(())
+                })
+                .map_err(|e: diesel::result::Error| anyhow::anyhow!(e.to_string()))
             })
             .map_err(|e| PairedDeviceRepositoryError::Storage(e.to_string()))
     }
@@ -155,14 +225,50 @@ where
         Ok(())
     }
 
+    /// Removes the device from the active space; the device row, its
+    /// addresses, sync policy and capabilities go away once no space
+    /// references it.
     async fn delete(&self, peer_id_value: &PeerId) -> Result<(), PairedDeviceRepositoryError> {
         let peer_id_str = peer_id_value.as_str().to_string();
+        let space = self.space_id();
         let affected = self
             .executor
             .run(move |conn| {
-                diesel::delete(paired_device.filter(peer_id.eq(&peer_id_str)))
-                    .execute(conn)
-                    .map_err(|e| anyhow::anyhow!(e.to_string()))
+                conn.transaction(|conn| {
+                    let removed = diesel::delete(
+                        paired_device_space::table
+                            .filter(paired_device_space::space_id.eq(&space))
+                            .filter(paired_device_space::peer_id.eq(&peer_id_str)),
+                    )
+                    .execute(conn)?;
+                    let remaining: i64 = paired_device_space::table
+                        .filter(paired_device_space::peer_id.eq(&peer_id_str))
+                        .count()
+                        .get_result(conn)?;
+                    if remaining > 0 {
+                        return Ok(removed);
+                    }
+
+                    diesel::delete(
+                        paired_device_address::table
+                            .filter(paired_device_address::peer_id.eq(&peer_id_str)),
+                    )
+                    .execute(conn)?;
+                    diesel::delete(
+                        paired_device_sync_policy::table
+                            .filter(paired_device_sync_policy::peer_id.eq(&peer_id_str)),
+                    )
+                    .execute(conn)?;
+                    diesel::delete(
+                        paired_device_capabilities::table
+                            .filter(paired_device_capabilities::peer_id.eq(&peer_id_str)),
+                    )
+                    .execute(conn)?;
+                    let deleted = diesel::delete(paired_device.filter(peer_id.eq(&peer_id_str)))
+                        .execute(conn)?;
+                    Ok(removed.max(deleted))
+                })
+                .map_err(|e: diesel::result::Error| anyhow::anyhow!(e.to_string()))
             })
             .map_err(|e| PairedDeviceRepositoryError::Storage(e.to_string()))?;
 
@@ -172,6 +278,265 @@ where
 
         Ok(())
     }
+
+    async fn record_address(
+        &self,
+        peer_id_value: &PeerId,
+        address_value: &str,
+        confirmed_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), PairedDeviceRepositoryError> {
+        let row = NewPairedDeviceAddressRow {
+            peer_id: peer_id_value.as_str().to_string(),
+            address: address_value.to_string(),
+            last_confirmed_at: confirmed_at.timestamp(),
+        };
+
+        self.executor
+            .run(move |conn| {
+                conn.transaction(|conn| {
+                    diesel::insert_into(paired_device_address::table)
+                        .values(&row)
+                        .on_conflict((
+                            paired_device_address::peer_id,
+                            paired_device_address::address,
+                        ))
+                        .do_update()
+                        .set(paired_device_address::last_confirmed_at.eq(row.last_confirmed_at))
+                        .execute(conn)?;
+
+                    // Drop the oldest entries beyond the cap
+                    let stale: Vec<String> = paired_device_address::table
+                        .filter(paired_device_address::peer_id.eq(&row.peer_id))
+                        .order((
+                            paired_device_address::last_confirmed_at.desc(),
+                            paired_device_address::address.asc(),
+                        ))
+                        .offset(MAX_ADDRESSES_PER_DEVICE)
+                        .limit(i64::MAX)
+                        .select(paired_device_address::address)
+                        .load(conn)?;
+                    if !stale.is_empty() {
+                        diesel::delete(
+                            paired_device_address::table
+                                .filter(paired_device_address::peer_id.eq(&row.peer_id))
+                                .filter(paired_device_address::address.eq_any(&stale)),
+                        )
+                        .execute(conn)?;
+                    }
+                    Ok(())
+                })
+                .map_err(|e: diesel::result::Error| anyhow::anyhow!(e.to_string()))
+            })
+            .map_err(|e| PairedDeviceRepositoryError::Storage(e.to_string()))
+    }
+
+    async fn list_addresses(
+        &self,
+        peer_id_value: &PeerId,
+    ) -> Result<Vec<String>, PairedDeviceRepositoryError> {
+        let peer_id_str = peer_id_value.as_str().to_string();
+        self.executor
+            .run(move |conn| {
+                paired_device_address::table
+                    .filter(paired_device_address::peer_id.eq(&peer_id_str))
+                    .order((
+                        paired_device_address::last_confirmed_at.desc(),
+                        paired_device_address::address.asc(),
+                    ))
+                    .select(paired_device_address::address)
+                    .load::<String>(conn)
+                    .map_err(|e| anyhow::anyhow!(e.to_string()))
+            })
+            .map_err(|e| PairedDeviceRepositoryError::Storage(e.to_string()))
+    }
+
+    async fn mark_revocation_pending(
+        &self,
+        peer_id_value: &PeerId,
+        revoked_at_value: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), PairedDeviceRepositoryError> {
+        let row = NewPendingRevocationRow {
+            peer_id: peer_id_value.as_str().to_string(),
+            revoked_at: revoked_at_value.timestamp(),
+        };
+
+        self.executor
+            .run(move |conn| {
+                diesel::insert_into(pending_revocation::table)
+                    .values(&row)
+                    .on_conflict(pending_revocation::peer_id)
+                    .do_update()
+                    .set(pending_revocation::revoked_at.eq(row.revoked_at))
+                    .execute(conn)
+                    .map(|_| ())
+                    .map_err(|e| anyhow::anyhow!(e.to_string()))
+            })
+            .map_err(|e| PairedDeviceRepositoryError::Storage(e.to_string()))
+    }
+
+    async fn is_revocation_pending(
+        &self,
+        peer_id_value: &PeerId,
+    ) -> Result<bool, PairedDeviceRepositoryError> {
+        let peer_id_str = peer_id_value.as_str().to_string();
+        self.executor
+            .run(move |conn| {
+                diesel::select(diesel::dsl::exists(
+                    pending_revocation::table.filter(pending_revocation::peer_id.eq(&peer_id_str)),
+                ))
+                .get_result::<bool>(conn)
+                .map_err(|e| anyhow::anyhow!(e.to_string()))
+            })
+            .map_err(|e| PairedDeviceRepositoryError::Storage(e.to_string()))
+    }
+
+    async fn clear_revocation_pending(
+        &self,
+        peer_id_value: &PeerId,
+    ) -> Result<(), PairedDeviceRepositoryError> {
+        let peer_id_str = peer_id_value.as_str().to_string();
+        self.executor
+            .run(move |conn| {
+                diesel::delete(
+                    pending_revocation::table.filter(pending_revocation::peer_id.eq(&peer_id_str)),
+                )
+                .execute(conn)
+                .map(|_| ())
+                .map_err(|e| anyhow::anyhow!(e.to_string()))
+            })
+            .map_err(|e| PairedDeviceRepositoryError::Storage(e.to_string()))
+    }
+
+    async fn get_sync_policy(
+        &self,
+        peer_id_value: &PeerId,
+    ) -> Result<DeviceSyncPolicy, PairedDeviceRepositoryError> {
+        let peer_id_str = peer_id_value.as_str().to_string();
+        let row = self
+            .executor
+            .run(move |conn| {
+                paired_device_sync_policy::table
+                    .filter(paired_device_sync_policy::peer_id.eq(&peer_id_str))
+                    .first::<PairedDeviceSyncPolicyRow>(conn)
+                    .optional()
+                    .map_err(|e| anyhow::anyhow!(e.to_string()))
+            })
+            .map_err(|e| PairedDeviceRepositoryError::Storage(e.to_string()))?;
+
+        match row {
+            Some(row) => sync_policy_from_row(&row)
+                .map_err(|e| PairedDeviceRepositoryError::Storage(e.to_string())),
+            None => Ok(DeviceSyncPolicy::default()),
+        }
+    }
+
+    async fn set_sync_policy(
+        &self,
+        peer_id_value: &PeerId,
+        policy: &DeviceSyncPolicy,
+    ) -> Result<(), PairedDeviceRepositoryError> {
+        let content_types_json = serde_json::to_string(&policy.content_types)
+            .map_err(|e| PairedDeviceRepositoryError::Storage(e.to_string()))?;
+        let row = PairedDeviceSyncPolicyRow {
+            peer_id: peer_id_value.as_str().to_string(),
+            direction: policy.direction.as_str().to_string(),
+            content_types: content_types_json,
+            apply_mode: policy.apply_mode.as_str().to_string(),
+        };
+
+        self.executor
+            .run(move |conn| {
+                diesel::insert_into(paired_device_sync_policy::table)
+                    .values(&row)
+                    .on_conflict(paired_device_sync_policy::peer_id)
+                    .do_update()
+                    .set((
+                        paired_device_sync_policy::direction.eq(&row.direction),
+                        paired_device_sync_policy::content_types.eq(&row.content_types),
+                        paired_device_sync_policy::apply_mode.eq(&row.apply_mode),
+                    ))
+                    .execute(conn)
+                    .map(|_| ())
+                    .map_err(|e| anyhow::anyhow!(e.to_string()))
+            })
+            .map_err(|e| PairedDeviceRepositoryError::Storage(e.to_string()))
+    }
+
+    async fn get_capabilities(
+        &self,
+        peer_id_value: &PeerId,
+    ) -> Result<Option<PeerCapabilities>, PairedDeviceRepositoryError> {
+        let peer_id_str = peer_id_value.as_str().to_string();
+        let row = self
+            .executor
+            .run(move |conn| {
+                paired_device_capabilities::table
+                    .filter(paired_device_capabilities::peer_id.eq(&peer_id_str))
+                    .first::<PairedDeviceCapabilitiesRow>(conn)
+                    .optional()
+                    .map_err(|e| anyhow::anyhow!(e.to_string()))
+            })
+            .map_err(|e| PairedDeviceRepositoryError::Storage(e.to_string()))?;
+
+        row.map(capabilities_from_row)
+            .transpose()
+            .map_err(|e| PairedDeviceRepositoryError::Storage(e.to_string()))
+    }
+
+    async fn set_capabilities(
+        &self,
+        peer_id_value: &PeerId,
+        capabilities: &PeerCapabilities,
+    ) -> Result<(), PairedDeviceRepositoryError> {
+        let features_json = serde_json::to_string(&capabilities.features)
+            .map_err(|e| PairedDeviceRepositoryError::Storage(e.to_string()))?;
+        let row = PairedDeviceCapabilitiesRow {
+            peer_id: peer_id_value.as_str().to_string(),
+            app_version: capabilities.app_version.clone(),
+            protocol_version: capabilities.protocol_version.clone(),
+            features: features_json,
+            negotiated_at: capabilities.negotiated_at.timestamp(),
+        };
+
+        self.executor
+            .run(move |conn| {
+                diesel::insert_into(paired_device_capabilities::table)
+                    .values(&row)
+                    .on_conflict(paired_device_capabilities::peer_id)
+                    .do_update()
+                    .set((
+                        paired_device_capabilities::app_version.eq(&row.app_version),
+                        paired_device_capabilities::protocol_version.eq(&row.protocol_version),
+                        paired_device_capabilities::features.eq(&row.features),
+                        paired_device_capabilities::negotiated_at.eq(row.negotiated_at),
+                    ))
+                    .execute(conn)
+                    .map(|_| ())
+                    .map_err(|e| anyhow::anyhow!(e.to_string()))
+            })
+            .map_err(|e| PairedDeviceRepositoryError::Storage(e.to_string()))
+    }
+}
+
+fn capabilities_from_row(row: PairedDeviceCapabilitiesRow) -> anyhow::Result<PeerCapabilities> {
+    Ok(PeerCapabilities {
+        features: serde_json::from_str(&row.features)?,
+        negotiated_at: chrono::Utc
+            .timestamp_opt(row.negotiated_at, 0)
+            .single()
+            .ok_or_else(|| anyhow::anyhow!("invalid negotiated_at {}", row.negotiated_at))?,
+        peer_id: row.peer_id,
+        app_version: row.app_version,
+        protocol_version: row.protocol_version,
+    })
+}
+
+fn sync_policy_from_row(row: &PairedDeviceSyncPolicyRow) -> anyhow::Result<DeviceSyncPolicy> {
+    Ok(DeviceSyncPolicy {
+        direction: row.direction.parse()?,
+        content_types: serde_json::from_str(&row.content_types)?,
+        apply_mode: row.apply_mode.parse()?,
+    })
 }
 
 fn pairing_state_to_str(state: &PairingState) -> &'static str {
@@ -179,6 +544,7 @@ fn pairing_state_to_str(state: &PairingState) -> &'static str {
         PairingState::Pending => "Pending",
         PairingState::Trusted => "Trusted",
         PairingState::Revoked => "Revoked",
+        PairingState::Quarantined => "Quarantined",
     }
 }
 
@@ -207,6 +573,7 @@ mod tests {
             paired_at: chrono::Utc::now(),
             last_seen_at: None,
             device_name: "Test Device".to_string(),
+            device_id: "device-peer".to_string(),
         };
 
         repo.upsert(device.clone()).await.unwrap();
@@ -222,4 +589,245 @@ mod tests {
         let loaded_device = loaded.unwrap();
         assert_eq!(loaded_device.device_name, "Test Device");
     }
+
+    #[tokio::test]
+    async fn test_addresses_are_capped_and_removed_with_device() {
+        let pool = init_db_pool(":memory:").expect("Failed to init db");
+        let repo = DieselPairedDeviceRepository::new(
+            DieselSqliteExecutor::new(pool),
+            PairedDeviceRowMapper,
+        );
+        let peer = PeerId::from("peer-1");
+        repo.upsert(PairedDevice {
+            peer_id: peer.clone(),
+            pairing_state: PairingState::Trusted,
+            identity_fingerprint: "fp".to_string(),
+            paired_at: chrono::Utc::now(),
+            last_seen_at: None,
+            device_name: "Test Device".to_string(),
+            device_id: "device-peer".to_string(),
+        })
+        .await
+        .unwrap();
+
+        let base = chrono::Utc::now();
+        for i in 0..10 {
+            let address = format!("/ip4/10.0.0.{i}/tcp/4001");
+            let at = base + chrono::Duration::seconds(i);
+            repo.record_address(&peer, &address, at).await.unwrap();
+        }
+        // Re-confirming an old address moves it to the front
+        repo.record_address(
+            &peer,
+            "/ip4/10.0.0.3/tcp/4001",
+            base + chrono::Duration::seconds(60),
+        )
+        .await
+        .unwrap();
+
+        let addresses = repo.list_addresses(&peer).await.unwrap();
+        assert_eq!(addresses.len(), MAX_ADDRESSES_PER_DEVICE as usize);
+        assert_eq!(addresses[0], "/ip4/10.0.0.3/tcp/4001");
+        assert_eq!(addresses[1], "/ip4/10.0.0.9/tcp/4001");
+        assert!(!addresses.contains(&"/ip4/10.0.0.0/tcp/4001".to_string()));
+
+        repo.delete(&peer).await.unwrap();
+        assert!(repo.list_addresses(&peer).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pending_revocation_outlives_device_until_cleared() {
+        let pool = init_db_pool(":memory:").expect("Failed to init db");
+        let repo = DieselPairedDeviceRepository::new(
+            DieselSqliteExecutor::new(pool),
+            PairedDeviceRowMapper,
+        );
+        let peer = PeerId::from("peer-1");
+        repo.upsert(PairedDevice {
+            peer_id: peer.clone(),
+            pairing_state: PairingState::Trusted,
+            identity_fingerprint: "fp".to_string(),
+            paired_at: chrono::Utc::now(),
+            last_seen_at: None,
+            device_name: "Test Device".to_string(),
+            device_id: "device-peer".to_string(),
+        })
+        .await
+        .unwrap();
+
+        repo.mark_revocation_pending(&peer, chrono::Utc::now())
+            .await
+            .unwrap();
+        repo.mark_revocation_pending(&peer, chrono::Utc::now())
+            .await
+            .unwrap();
+        repo.delete(&peer).await.unwrap();
+        assert!(repo.is_revocation_pending(&peer).await.unwrap());
+        assert!(!repo
+            .is_revocation_pending(&PeerId::from("peer-2"))
+            .await
+            .unwrap());
+
+        repo.clear_revocation_pending(&peer).await.unwrap();
+        assert!(!repo.is_revocation_pending(&peer).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_repairing_clears_pending_revocation() {
+        let pool = init_db_pool(":memory:").expect("Failed to init db");
+        let repo = DieselPairedDeviceRepository::new(
+            DieselSqliteExecutor::new(pool),
+            PairedDeviceRowMapper,
+        );
+        let peer = PeerId::from("peer-1");
+        let device = PairedDevice {
+            peer_id: peer.clone(),
+            pairing_state: PairingState::Trusted,
+            identity_fingerprint: "fp".to_string(),
+            paired_at: chrono::Utc::now(),
+            last_seen_at: None,
+            device_name: "Test Device".to_string(),
+            device_id: "device-peer".to_string(),
+        };
+        repo.upsert(device.clone()).await.unwrap();
+        repo.delete(&peer).await.unwrap();
+        repo.mark_revocation_pending(&peer, chrono::Utc::now())
+            .await
+            .unwrap();
+
+        repo.upsert(device).await.unwrap();
+
+        assert!(!repo.is_revocation_pending(&peer).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_sync_policy_defaults_round_trips_and_is_removed_with_device() {
+        use uc_core::network::{ClipboardApplyMode, SyncDirection};
+        use uc_core::settings::model::ContentTypes;
+
+        let pool = init_db_pool(":memory:").expect("Failed to init db");
+        let repo = DieselPairedDeviceRepository::new(
+            DieselSqliteExecutor::new(pool),
+            PairedDeviceRowMapper,
+        );
+        let peer = PeerId::from("peer-1");
+        repo.upsert(PairedDevice {
+            peer_id: peer.clone(),
+            pairing_state: PairingState::Trusted,
+            identity_fingerprint: "fp".to_string(),
+            paired_at: chrono::Utc::now(),
+            last_seen_at: None,
+            device_name: "Test Device".to_string(),
+            device_id: "device-peer".to_string(),
+        })
+        .await
+        .unwrap();
+        assert_eq!(
+            repo.get_sync_policy(&peer).await.unwrap(),
+            DeviceSyncPolicy::default()
+        );
+
+        let policy = DeviceSyncPolicy {
+            direction: SyncDirection::SendOnly,
+            content_types: ContentTypes {
+                image: false,
+                ..ContentTypes::all()
+            },
+            apply_mode: ClipboardApplyMode::HistoryOnly,
+        };
+        repo.set_sync_policy(&peer, &DeviceSyncPolicy::default())
+            .await
+            .unwrap();
+        repo.set_sync_policy(&peer, &policy).await.unwrap();
+        assert_eq!(repo.get_sync_policy(&peer).await.unwrap(), policy);
+
+        repo.delete(&peer).await.unwrap();
+        assert_eq!(
+            repo.get_sync_policy(&peer).await.unwrap(),
+            DeviceSyncPolicy::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_capabilities_round_trip_and_are_removed_with_device() {
+        use uc_core::network::ProtocolFeature;
+
+        let pool = init_db_pool(":memory:").expect("Failed to init db");
+        let repo = DieselPairedDeviceRepository::new(
+            DieselSqliteExecutor::new(pool),
+            PairedDeviceRowMapper,
+        );
+        let peer = PeerId::from("peer-1");
+        repo.upsert(PairedDevice {
+            peer_id: peer.clone(),
+            pairing_state: PairingState::Trusted,
+            identity_fingerprint: "fp".to_string(),
+            paired_at: chrono::Utc::now(),
+            last_seen_at: None,
+            device_name: "Test Device".to_string(),
+            device_id: "device-peer".to_string(),
+        })
+        .await
+        .unwrap();
+        assert_eq!(repo.get_capabilities(&peer).await.unwrap(), None);
+
+        let negotiated_at = chrono::Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let mut capabilities = PeerCapabilities {
+            peer_id: "peer-1".to_string(),
+            app_version: "0.2.0".to_string(),
+            protocol_version: "1.0.0".to_string(),
+            features: vec![ProtocolFeature::ChunkedTransfer],
+            negotiated_at,
+        };
+        repo.set_capabilities(&peer, &capabilities).await.unwrap();
+        capabilities.features.push(ProtocolFeature::CatchUp);
+        repo.set_capabilities(&peer, &capabilities).await.unwrap();
+        assert_eq!(
+            repo.get_capabilities(&peer).await.unwrap(),
+            Some(capabilities)
+        );
+
+        repo.delete(&peer).await.unwrap();
+        assert_eq!(repo.get_capabilities(&peer).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_devices_are_scoped_to_active_space() {
+        use crate::space::InMemoryActiveSpace;
+
+        let pool = init_db_pool(":memory:").expect("Failed to init db");
+        let active_space = Arc::new(InMemoryActiveSpace::default());
+        let repo = DieselPairedDeviceRepository::new(
+            DieselSqliteExecutor::new(pool),
+            PairedDeviceRowMapper,
+        )
+        .with_active_space(active_space.clone());
+        let peer = PeerId::from("peer-1");
+        let device = PairedDevice {
+            peer_id: peer.clone(),
+            pairing_state: PairingState::Trusted,
+            identity_fingerprint: "fp".to_string(),
+            paired_at: chrono::Utc::now(),
+            last_seen_at: None,
+            device_name: "Test Device".to_string(),
+            device_id: "device-peer".to_string(),
+        };
+        repo.upsert(device.clone()).await.unwrap();
+
+        active_space.set_active_space(SpaceId::from("work"));
+        assert!(repo.get_by_peer_id(&peer).await.unwrap().is_none());
+        assert!(repo.list_all().await.unwrap().is_empty());
+
+        repo.upsert(device).await.unwrap();
+        repo.record_address(&peer, "/ip4/10.0.0.1/tcp/4001", chrono::Utc::now())
+            .await
+            .unwrap();
+        repo.delete(&peer).await.unwrap();
+        assert!(repo.get_by_peer_id(&peer).await.unwrap().is_none());
+
+        // Still paired in the default space, addresses kept
+        active_space.set_active_space(SpaceId::default_space());
+        assert!(repo.get_by_peer_id(&peer).await.unwrap().is_some());
+        assert_eq!(repo.list_addresses(&peer).await.unwrap().len(), 1);
+    }
 }
@@ -0,0 +1,132 @@
+use crate::db::models::{ClipboardRepresentationRemoteRow, NewClipboardRepresentationRemoteRow};
+use crate::db::ports::DbExecutor;
+use crate::db::schema::clipboard_representation_remote;
+use anyhow::Result;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use uc_core::clipboard::{ContentHash, RemotePayloadRef};
+use uc_core::ids::RepresentationId;
+use uc_core::ports::clipboard::RemotePayloadRepositoryPort;
+
+pub struct DieselRemotePayloadRepository<E>
+where
+    E: DbExecutor,
+{
+    executor: E,
+}
+
+impl<E> DieselRemotePayloadRepository<E>
+where
+    E: DbExecutor,
+{
+    pub fn new(executor: E) -> Self {
+        Self { executor }
+    }
+}
+
+#[async_trait::async_trait]
+impl<E> RemotePayloadRepositoryPort for DieselRemotePayloadRepository<E>
+where
+    E: DbExecutor,
+{
+    async fn insert_remote_payload(&self, remote: &RemotePayloadRef) -> Result<()> {
+        let row = NewClipboardRepresentationRemoteRow {
+            representation_id: remote.representation_id.to_string(),
+            content_hash: remote.content_hash.to_string(),
+            manifest: serde_json::to_string(&remote.manifest)?,
+            size_bytes: remote.size_bytes as i64,
+        };
+        self.executor.run(|conn| {
+            diesel::insert_into(clipboard_representation_remote::table)
+                .values(&row)
+                .on_conflict(clipboard_representation_remote::representation_id)
+                .do_update()
+                .set((
+                    clipboard_representation_remote::content_hash.eq(&row.content_hash),
+                    clipboard_representation_remote::manifest.eq(&row.manifest),
+                    clipboard_representation_remote::size_bytes.eq(row.size_bytes),
+                ))
+                .execute(conn)?;
+            Ok(())
+        })
+    }
+
+    async fn get_by_representation_id(
+        &self,
+        representation_id: &RepresentationId,
+    ) -> Result<Option<RemotePayloadRef>> {
+        let rep_id_str = representation_id.to_string();
+        let row: Option<ClipboardRepresentationRemoteRow> = self.executor.run(|conn| {
+            let row = clipboard_representation_remote::table
+                .filter(clipboard_representation_remote::representation_id.eq(&rep_id_str))
+                .first::<ClipboardRepresentationRemoteRow>(conn)
+                .optional()?;
+            Ok(row)
+        })?;
+
+        row.map(|row| {
+            Ok(RemotePayloadRef {
+                representation_id: RepresentationId::from(row.representation_id),
+                content_hash: ContentHash::from(row.content_hash),
+                size_bytes: row.size_bytes as u64,
+                manifest: serde_json::from_str(&row.manifest).map_err(|e| {
+                    anyhow::anyhow!("invalid remote payload manifest for {}: {}", rep_id_str, e)
+                })?,
+            })
+        })
+        .transpose()
+    }
+
+    async fn delete_remote_payload(&self, representation_id: &RepresentationId) -> Result<()> {
+        let rep_id_str = representation_id.to_string();
+        self.executor.run(|conn| {
+            diesel::delete(
+                clipboard_representation_remote::table
+                    .filter(clipboard_representation_remote::representation_id.eq(&rep_id_str)),
+            )
+            .execute(conn)?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uc_core::clipboard::{ContentHash, RemotePayloadRef};
+    use uc_core::ids::RepresentationId;
+    use uc_core::network::BlobManifest;
+    use uc_core::ports::clipboard::RemotePayloadRepositoryPort;
+
+    use crate::db::executor::DieselSqliteExecutor;
+    use crate::db::pool::init_db_pool;
+
+    use super::DieselRemotePayloadRepository;
+
+    #[tokio::test]
+    async fn test_remote_payload_round_trip_and_delete() {
+        let pool = init_db_pool(":memory:").unwrap();
+        let repo = DieselRemotePayloadRepository::new(DieselSqliteExecutor::new(pool));
+        let remote = RemotePayloadRef {
+            representation_id: RepresentationId::new(),
+            content_hash: ContentHash::from(&[9; 32]),
+            size_bytes: 84,
+            manifest: BlobManifest::from_bytes(&[3; 100], 32),
+        };
+
+        repo.insert_remote_payload(&remote).await.unwrap();
+        repo.insert_remote_payload(&remote).await.unwrap();
+        let fetched = repo
+            .get_by_representation_id(&remote.representation_id)
+            .await
+            .unwrap();
+        assert_eq!(fetched, Some(remote.clone()));
+
+        repo.delete_remote_payload(&remote.representation_id)
+            .await
+            .unwrap();
+        assert!(repo
+            .get_by_representation_id(&remote.representation_id)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
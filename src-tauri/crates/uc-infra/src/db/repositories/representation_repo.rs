@@ -262,4 +262,63 @@ where
         let representation = mapper.to_domain(&row)?;
         Ok(ProcessingUpdateOutcome::Updated(representation))
     }
+
+    async fn count_inline_representations(&self) -> Result<usize> {
+        let count: i64 = self.executor.run(|conn| {
+            let result: Result<i64, diesel::result::Error> =
+                clipboard_snapshot_representation::table
+                    .filter(clipboard_snapshot_representation::inline_data.is_not_null())
+                    .count()
+                    .get_result(conn);
+            result.map_err(|e| anyhow::anyhow!("Database error: {}", e))
+        })?;
+        Ok(count as usize)
+    }
+
+    async fn list_inline_representation_ids(
+        &self,
+        after: Option<&RepresentationId>,
+        limit: usize,
+    ) -> Result<Vec<(EventId, RepresentationId)>> {
+        let after_str = after.map(|id| id.to_string());
+        let rows: Vec<(String, String)> = self.executor.run(|conn| {
+            let mut query = clipboard_snapshot_representation::table
+                .filter(clipboard_snapshot_representation::inline_data.is_not_null())
+                .select((
+                    clipboard_snapshot_representation::event_id,
+                    clipboard_snapshot_representation::id,
+                ))
+                .order(clipboard_snapshot_representation::id.asc())
+                .limit(limit as i64)
+                .into_boxed();
+            if let Some(after) = after_str.as_ref() {
+                query = query.filter(clipboard_snapshot_representation::id.gt(after));
+            }
+            let result: Result<Vec<(String, String)>, diesel::result::Error> = query.load(conn);
+            result.map_err(|e| anyhow::anyhow!("Database error: {}", e))
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(event_id, rep_id)| (EventId::from(event_id), RepresentationId::from(rep_id)))
+            .collect())
+    }
+
+    async fn update_inline_data(
+        &self,
+        representation_id: &RepresentationId,
+        inline_data: &[u8],
+    ) -> Result<()> {
+        let rep_id_str = representation_id.to_string();
+
+        self.executor.run(|conn| {
+            diesel::update(
+                clipboard_snapshot_representation::table
+                    .filter(clipboard_snapshot_representation::id.eq(&rep_id_str)),
+            )
+            .set(clipboard_snapshot_representation::inline_data.eq(inline_data))
+            .execute(conn)?;
+            Ok(())
+        })
+    }
 }
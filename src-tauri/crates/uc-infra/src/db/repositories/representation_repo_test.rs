@@ -179,3 +179,63 @@ async fn test_update_processing_result_returns_state_mismatch() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_list_and_update_inline_representations() -> Result<()> {
+    let executor = TestDbExecutor::new();
+    let repo = DieselClipboardRepresentationRepository::new(executor.clone());
+
+    executor.run(|conn| {
+        diesel::insert_into(clipboard_event::table)
+            .values((
+                clipboard_event::event_id.eq("test-event-1"),
+                clipboard_event::captured_at_ms.eq(1704067200000i64),
+                clipboard_event::source_device.eq("test-device"),
+                clipboard_event::snapshot_hash.eq("blake3v1:testhash"),
+            ))
+            .execute(conn)?;
+
+        for (id, inline_data) in [
+            ("rep-a", Some(b"aaa".to_vec())),
+            ("rep-b", None),
+            ("rep-c", Some(b"ccc".to_vec())),
+        ] {
+            diesel::insert_into(clipboard_snapshot_representation::table)
+                .values(NewSnapshotRepresentationRow {
+                    id: id.to_string(),
+                    event_id: "test-event-1".to_string(),
+                    format_id: "public.text".to_string(),
+                    mime_type: Some("text/plain".to_string()),
+                    size_bytes: 3,
+                    inline_data,
+                    blob_id: None,
+                    payload_state: PayloadAvailability::Inline.as_str().to_string(),
+                    last_error: None,
+                })
+                .execute(conn)?;
+        }
+
+        Ok(())
+    })?;
+
+    assert_eq!(repo.count_inline_representations().await?, 2);
+
+    let first_page = repo.list_inline_representation_ids(None, 1).await?;
+    assert_eq!(first_page.len(), 1);
+    assert_eq!(first_page[0].1, RepresentationId::from("rep-a"));
+    let second_page = repo
+        .list_inline_representation_ids(Some(&first_page[0].1), 10)
+        .await?;
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(second_page[0].1, RepresentationId::from("rep-c"));
+
+    repo.update_inline_data(&RepresentationId::from("rep-c"), b"new")
+        .await?;
+    let updated = repo
+        .get_representation_by_id(&RepresentationId::from("rep-c"))
+        .await?
+        .expect("representation exists");
+    assert_eq!(updated.inline_data, Some(b"new".to_vec()));
+
+    Ok(())
+}
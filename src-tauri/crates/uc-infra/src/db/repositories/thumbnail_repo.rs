@@ -1,7 +1,7 @@
 use crate::db::mappers::thumbnail_mapper::ThumbnailRowMapper;
 use crate::db::models::clipboard_representation_thumbnail::ClipboardRepresentationThumbnailRow;
 use crate::db::ports::{DbExecutor, InsertMapper, RowMapper};
-use crate::db::schema::clipboard_representation_thumbnail;
+use crate::db::schema::{clipboard_representation_thumbnail, clipboard_snapshot_representation};
 use anyhow::Result;
 use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
 use uc_core::clipboard::ThumbnailMetadata;
@@ -63,6 +63,18 @@ where
             Ok(())
         })
     }
+
+    async fn delete_orphaned_thumbnails(&self) -> Result<usize> {
+        self.executor.run(|conn| {
+            let live_representations = clipboard_snapshot_representation::table
+                .select(clipboard_snapshot_representation::id);
+            let deleted = diesel::delete(clipboard_representation_thumbnail::table.filter(
+                clipboard_representation_thumbnail::representation_id.ne_all(live_representations),
+            ))
+            .execute(conn)?;
+            Ok(deleted)
+        })
+    }
 }
 
 #[cfg(test)]
@@ -108,4 +120,31 @@ mod tests {
         assert_eq!(fetched.original_size_bytes, metadata.original_size_bytes);
         assert_eq!(fetched.created_at_ms, metadata.created_at_ms);
     }
+
+    #[tokio::test]
+    async fn test_delete_orphaned_thumbnails() {
+        let pool = init_db_pool(":memory:").unwrap();
+        let executor = DieselSqliteExecutor::new(pool);
+        let repo = DieselThumbnailRepository::new(executor);
+
+        // No snapshot representation exists for this thumbnail.
+        let metadata = ThumbnailMetadata::new(
+            RepresentationId::new(),
+            BlobId::new(),
+            MimeType("image/webp".to_string()),
+            640,
+            480,
+            1234,
+            None,
+        );
+        repo.insert_thumbnail(&metadata).await.unwrap();
+
+        assert_eq!(repo.delete_orphaned_thumbnails().await.unwrap(), 1);
+        assert!(repo
+            .get_by_representation_id(&metadata.representation_id)
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(repo.delete_orphaned_thumbnails().await.unwrap(), 0);
+    }
 }
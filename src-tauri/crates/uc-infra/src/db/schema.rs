@@ -22,6 +22,8 @@ diesel::table! {
         total_size -> BigInt,
         pinned -> Bool,
         deleted_at_ms -> Nullable<BigInt>,
+        sync_skip_reason -> Nullable<Text>,
+        space_id -> Text,
     }
 }
 
@@ -45,6 +47,15 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    clipboard_representation_remote (representation_id) {
+        representation_id -> Text,
+        content_hash -> Text,
+        manifest -> Text,
+        size_bytes -> BigInt,
+    }
+}
+
 diesel::table! {
     clipboard_representation_thumbnail (representation_id) {
         representation_id -> Text,
@@ -71,6 +82,14 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    outbound_sync_queue (message_id) {
+        message_id -> Text,
+        payload -> Binary,
+        enqueued_at_ms -> BigInt,
+    }
+}
+
 diesel::table! {
     t_device (id) {
         id -> Text,
@@ -89,6 +108,48 @@ diesel::table! {
         paired_at -> BigInt,
         last_seen_at -> Nullable<BigInt>,
         device_name -> Text,
+        device_id -> Text,
+    }
+}
+
+diesel::table! {
+    paired_device_address (peer_id, address) {
+        peer_id -> Text,
+        address -> Text,
+        last_confirmed_at -> BigInt,
+    }
+}
+
+diesel::table! {
+    paired_device_space (space_id, peer_id) {
+        space_id -> Text,
+        peer_id -> Text,
+    }
+}
+
+diesel::table! {
+    paired_device_capabilities (peer_id) {
+        peer_id -> Text,
+        app_version -> Text,
+        protocol_version -> Text,
+        features -> Text,
+        negotiated_at -> BigInt,
+    }
+}
+
+diesel::table! {
+    paired_device_sync_policy (peer_id) {
+        peer_id -> Text,
+        direction -> Text,
+        content_types -> Text,
+        apply_mode -> Text,
+    }
+}
+
+diesel::table! {
+    pending_revocation (peer_id) {
+        peer_id -> Text,
+        revoked_at -> BigInt,
     }
 }
 
@@ -102,8 +163,15 @@ diesel::allow_tables_to_appear_in_same_query!(
     clipboard_entry,
     clipboard_event,
     clipboard_selection,
+    clipboard_representation_remote,
     clipboard_representation_thumbnail,
     clipboard_snapshot_representation,
+    outbound_sync_queue,
     paired_device,
+    paired_device_address,
+    paired_device_capabilities,
+    paired_device_space,
+    paired_device_sync_policy,
+    pending_revocation,
     t_device,
 );
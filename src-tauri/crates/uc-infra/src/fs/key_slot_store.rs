@@ -1,5 +1,8 @@
 use async_trait::async_trait;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use uc_core::ids::SpaceId;
+use uc_core::ports::space::ActiveSpacePort;
 use uc_core::security::model::{EncryptionError, KeySlotFile};
 
 #[async_trait]
@@ -72,6 +75,63 @@ impl KeySlotStore for JsonKeySlotStore {
     }
 }
 
+/// Directory holding the keyslot of a non-default space.
+/// 非默认空间的 keyslot 目录。
+pub fn space_keyslot_dir(root: &Path, space_id: &SpaceId) -> PathBuf {
+    root.join("spaces").join(space_id.as_ref())
+}
+
+/// Keyslot store that follows the active space.
+///
+/// The default space keeps the original store; other spaces live under
+/// [`space_keyslot_dir`].
+pub struct ActiveSpaceKeySlotStore {
+    default_store: Arc<dyn KeySlotStore>,
+    space_root: PathBuf,
+    active_space: Arc<dyn ActiveSpacePort>,
+}
+
+impl ActiveSpaceKeySlotStore {
+    pub fn new(
+        default_store: Arc<dyn KeySlotStore>,
+        space_root: PathBuf,
+        active_space: Arc<dyn ActiveSpacePort>,
+    ) -> Self {
+        Self {
+            default_store,
+            space_root,
+            active_space,
+        }
+    }
+
+    fn current(&self) -> Arc<dyn KeySlotStore> {
+        let space_id = self.active_space.active_space();
+        if space_id.is_default() {
+            self.default_store.clone()
+        } else {
+            Arc::new(JsonKeySlotStore::new(space_keyslot_dir(
+                &self.space_root,
+                &space_id,
+            )))
+        }
+    }
+}
+
+#[async_trait]
+impl KeySlotStore for ActiveSpaceKeySlotStore {
+    async fn load(&self) -> Result<KeySlotFile, EncryptionError> {
+        self.current().load().await
+    }
+
+    async fn store(&self, slot: &KeySlotFile) -> Result<(), EncryptionError> {
+        self.current().store(slot).await
+    }
+
+    async fn delete(&self) -> Result<(), EncryptionError> {
+        self.current().delete().await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -93,12 +153,17 @@ mod tests {
             },
             salt: vec![1u8; 32],
             kdf: KdfParams::for_initialization(),
+            epoch: 0,
+            revision: 0,
+            retired_master_key: None,
+            retained_master_keys: Vec::new(),
             wrapped_master_key: uc_core::security::model::EncryptedBlob {
                 version: EncryptionFormatVersion::V1,
                 aead: EncryptionAlgo::XChaCha20Poly1305,
                 nonce: vec![1u8; 24],
                 ciphertext: vec![2u8; 32],
                 aad_fingerprint: None,
+                key_epoch: 0,
             },
             created_at: None,
             updated_at: None,
@@ -10,6 +10,7 @@ pub mod fs;
 pub mod network;
 pub mod security;
 pub mod settings;
+pub mod space;
 pub mod setup_status;
 pub mod time;
 
@@ -0,0 +1,608 @@
+//! Chunked AEAD container for blobs at rest.
+//!
+//! Blobs are sealed as a sequence of XChaCha20-Poly1305 chunks (a STREAM-style
+//! construction), so they can be encrypted and decrypted in bounded memory
+//! instead of as a single JSON `EncryptedBlob` envelope.
+//!
+//! # Layout
+//!
+//! ```text
+//! header: magic "UCB\x01" (4) | aead (1) | chunk_size u32 BE | key_epoch u32 BE | nonce_prefix (19)
+//! chunk:  last flag (1) | ciphertext_len u32 BE | ciphertext (plaintext + 16-byte tag)
+//! ```
+//!
+//! Chunk `i` is sealed with nonce `nonce_prefix || i || last` and AAD
+//! `blob_aad || header || i || last`, so chunks cannot be reordered, dropped,
+//! truncated or moved to another blob without failing authentication.
+//!
+//! Blobs written before this format are JSON `EncryptedBlob` envelopes; they
+//! start with `{` and are reported as [`StoredBlob::Legacy`].
+//!
+//! 分块 AEAD 容器：blob 以多个 XChaCha20-Poly1305 分块存储，可在有限内存中流式加解密。
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
+
+use anyhow::{Context, Result};
+use chacha20poly1305::aead::{Aead, Payload};
+use chacha20poly1305::{KeyInit, XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use tokio::io::{AsyncRead, AsyncReadExt, ReadBuf};
+
+use uc_core::security::model::{EncryptedBlob, EncryptionError, KeyEpoch, MasterKey};
+
+/// Leading bytes of a chunked blob; the last byte is the container version.
+pub const MAGIC: [u8; 4] = *b"UCB\x01";
+
+/// Plaintext bytes per chunk used when sealing new blobs.
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Largest chunk size accepted from a stored header.
+const MAX_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+const AEAD_XCHACHA20_POLY1305: u8 = 1;
+const NONCE_PREFIX_LEN: usize = 19;
+const HEADER_LEN: usize = 4 + 1 + 4 + 4 + NONCE_PREFIX_LEN;
+const FRAME_HEADER_LEN: usize = 1 + 4;
+const TAG_LEN: usize = 16;
+
+/// Parsed header of a chunked blob.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkHeader {
+    pub key_epoch: KeyEpoch,
+    chunk_size: usize,
+    nonce_prefix: [u8; NONCE_PREFIX_LEN],
+}
+
+impl ChunkHeader {
+    fn new(key_epoch: KeyEpoch, chunk_size: usize) -> Self {
+        let mut nonce_prefix = [0u8; NONCE_PREFIX_LEN];
+        rand::rng().fill_bytes(&mut nonce_prefix);
+        Self {
+            key_epoch,
+            chunk_size,
+            nonce_prefix,
+        }
+    }
+
+    fn encode(&self) -> [u8; HEADER_LEN] {
+        let mut out = [0u8; HEADER_LEN];
+        out[..4].copy_from_slice(&MAGIC);
+        out[4] = AEAD_XCHACHA20_POLY1305;
+        out[5..9].copy_from_slice(&(self.chunk_size as u32).to_be_bytes());
+        out[9..13].copy_from_slice(&self.key_epoch.to_be_bytes());
+        out[13..].copy_from_slice(&self.nonce_prefix);
+        out
+    }
+
+    fn decode(bytes: &[u8; HEADER_LEN]) -> Result<Self, EncryptionError> {
+        if bytes[..4] != MAGIC {
+            return Err(EncryptionError::UnsupportedBlobVersion);
+        }
+        if bytes[4] != AEAD_XCHACHA20_POLY1305 {
+            return Err(EncryptionError::UnsupportedBlobVersion);
+        }
+        let chunk_size = u32::from_be_bytes(bytes[5..9].try_into().unwrap()) as usize;
+        if chunk_size == 0 || chunk_size > MAX_CHUNK_SIZE {
+            return Err(EncryptionError::CorruptedBlob);
+        }
+        let key_epoch = u32::from_be_bytes(bytes[9..13].try_into().unwrap());
+        let mut nonce_prefix = [0u8; NONCE_PREFIX_LEN];
+        nonce_prefix.copy_from_slice(&bytes[13..]);
+        Ok(Self {
+            key_epoch,
+            chunk_size,
+            nonce_prefix,
+        })
+    }
+
+    fn nonce(&self, index: u32, last: bool) -> XNonce {
+        let mut nonce = [0u8; 24];
+        nonce[..NONCE_PREFIX_LEN].copy_from_slice(&self.nonce_prefix);
+        nonce[NONCE_PREFIX_LEN..23].copy_from_slice(&index.to_be_bytes());
+        nonce[23] = last as u8;
+        XNonce::clone_from_slice(&nonce)
+    }
+}
+
+/// Stored blob as found at the start of a blob stream.
+pub enum StoredBlob {
+    /// Chunked container; the reader is positioned at the first chunk.
+    Chunked(ChunkHeader),
+    /// Whole-file JSON envelope written before the chunked format.
+    Legacy(EncryptedBlob),
+}
+
+/// Read the container header, or the whole JSON envelope of a legacy blob.
+pub async fn read_stored_blob<R>(reader: &mut R) -> Result<StoredBlob>
+where
+    R: AsyncRead + Unpin + ?Sized,
+{
+    let mut header = [0u8; HEADER_LEN];
+    reader
+        .read_exact(&mut header[..MAGIC.len()])
+        .await
+        .context("failed to read encrypted blob header")?;
+
+    if header[..MAGIC.len()] == MAGIC {
+        reader
+            .read_exact(&mut header[MAGIC.len()..])
+            .await
+            .context("failed to read encrypted blob header")?;
+        return Ok(StoredBlob::Chunked(ChunkHeader::decode(&header)?));
+    }
+
+    let mut bytes = header[..MAGIC.len()].to_vec();
+    reader
+        .read_to_end(&mut bytes)
+        .await
+        .context("failed to read encrypted blob")?;
+    let blob = serde_json::from_slice(&bytes)
+        .context("failed to deserialize encrypted blob - data may be corrupted or unencrypted")?;
+    Ok(StoredBlob::Legacy(blob))
+}
+
+fn new_cipher(key: &MasterKey) -> Result<XChaCha20Poly1305, EncryptionError> {
+    XChaCha20Poly1305::new_from_slice(key.as_bytes()).map_err(|_| EncryptionError::InvalidKey)
+}
+
+fn chunk_aad(blob_aad: &[u8], header: &[u8; HEADER_LEN], index: u32, last: bool) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(blob_aad.len() + HEADER_LEN + 5);
+    aad.extend_from_slice(blob_aad);
+    aad.extend_from_slice(header);
+    aad.extend_from_slice(&index.to_be_bytes());
+    aad.push(last as u8);
+    aad
+}
+
+fn crypto_error(err: EncryptionError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+/// Reader that yields the chunked container for the plaintext read from `inner`.
+///
+/// 从 `inner` 读取明文并输出分块加密容器的读取器。
+pub struct EncryptingReader<R> {
+    inner: R,
+    cipher: XChaCha20Poly1305,
+    header: ChunkHeader,
+    header_bytes: [u8; HEADER_LEN],
+    aad: Vec<u8>,
+    plain: Vec<u8>,
+    out: Vec<u8>,
+    out_pos: usize,
+    index: u32,
+    eof: bool,
+    done: bool,
+}
+
+impl<R: AsyncRead + Unpin> EncryptingReader<R> {
+    pub fn new(
+        inner: R,
+        key: &MasterKey,
+        key_epoch: KeyEpoch,
+        aad: Vec<u8>,
+    ) -> Result<Self, EncryptionError> {
+        Self::with_chunk_size(inner, key, key_epoch, aad, DEFAULT_CHUNK_SIZE)
+    }
+
+    pub(crate) fn with_chunk_size(
+        inner: R,
+        key: &MasterKey,
+        key_epoch: KeyEpoch,
+        aad: Vec<u8>,
+        chunk_size: usize,
+    ) -> Result<Self, EncryptionError> {
+        if chunk_size == 0 || chunk_size > MAX_CHUNK_SIZE {
+            return Err(EncryptionError::InvalidParameter(format!(
+                "chunk size {chunk_size}"
+            )));
+        }
+        let header = ChunkHeader::new(key_epoch, chunk_size);
+        let header_bytes = header.encode();
+        Ok(Self {
+            inner,
+            cipher: new_cipher(key)?,
+            header,
+            header_bytes,
+            aad,
+            plain: Vec::with_capacity(chunk_size + 1),
+            out: header_bytes.to_vec(),
+            out_pos: 0,
+            index: 0,
+            eof: false,
+            done: false,
+        })
+    }
+
+    /// Seal the next chunk into `out`. A chunk is only sealed once one byte
+    /// past it has been read (or the input ended), so the last flag is exact.
+    fn seal_next(&mut self) -> io::Result<()> {
+        let chunk_size = self.header.chunk_size;
+        let last = self.eof;
+        let take = if last { self.plain.len() } else { chunk_size };
+
+        let aad = chunk_aad(&self.aad, &self.header_bytes, self.index, last);
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                &self.header.nonce(self.index, last),
+                Payload {
+                    msg: &self.plain[..take],
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| crypto_error(EncryptionError::EncryptFailed))?;
+        self.plain.drain(..take);
+
+        self.out.clear();
+        self.out.push(last as u8);
+        self.out
+            .extend_from_slice(&(ciphertext.len() as u32).to_be_bytes());
+        self.out.extend_from_slice(&ciphertext);
+        self.out_pos = 0;
+
+        if last {
+            self.done = true;
+        } else {
+            self.index = self.index.checked_add(1).ok_or_else(|| {
+                crypto_error(EncryptionError::InvalidParameter(
+                    "blob exceeds chunk limit".to_string(),
+                ))
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for EncryptingReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            if this.out_pos < this.out.len() {
+                let n = buf.remaining().min(this.out.len() - this.out_pos);
+                buf.put_slice(&this.out[this.out_pos..this.out_pos + n]);
+                this.out_pos += n;
+                return Poll::Ready(Ok(()));
+            }
+            if this.done {
+                return Poll::Ready(Ok(()));
+            }
+
+            while !this.eof && this.plain.len() <= this.header.chunk_size {
+                let start = this.plain.len();
+                this.plain.resize(this.header.chunk_size + 1, 0);
+                let mut read_buf = ReadBuf::new(&mut this.plain[start..]);
+                let polled = Pin::new(&mut this.inner).poll_read(cx, &mut read_buf);
+                let filled = read_buf.filled().len();
+                this.plain.truncate(start + filled);
+                match polled {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                    Poll::Ready(Ok(())) if filled == 0 => this.eof = true,
+                    Poll::Ready(Ok(())) => {}
+                }
+            }
+
+            this.seal_next()?;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DecryptState {
+    Chunks,
+    Trailing,
+    Done,
+}
+
+/// Reader that yields the plaintext of a chunked container read from `inner`.
+///
+/// `inner` must already be positioned after the header (see
+/// [`read_stored_blob`]). Truncated or tampered input surfaces as an
+/// `InvalidData`/`UnexpectedEof` error instead of short plaintext.
+///
+/// 从 `inner` 读取分块加密容器并输出明文的读取器；截断或篡改会返回错误。
+pub struct DecryptingReader<R> {
+    inner: R,
+    cipher: XChaCha20Poly1305,
+    header: ChunkHeader,
+    header_bytes: [u8; HEADER_LEN],
+    aad: Vec<u8>,
+    frame: Vec<u8>,
+    out: Vec<u8>,
+    out_pos: usize,
+    index: u32,
+    state: DecryptState,
+}
+
+impl<R: AsyncRead + Unpin> DecryptingReader<R> {
+    pub fn new(
+        inner: R,
+        key: &MasterKey,
+        header: &ChunkHeader,
+        aad: Vec<u8>,
+    ) -> Result<Self, EncryptionError> {
+        Ok(Self {
+            inner,
+            cipher: new_cipher(key)?,
+            header_bytes: header.encode(),
+            header: header.clone(),
+            aad,
+            frame: Vec::new(),
+            out: Vec::new(),
+            out_pos: 0,
+            index: 0,
+            state: DecryptState::Chunks,
+        })
+    }
+
+    /// Bytes of the current frame still missing, once its length is known.
+    fn frame_len(&self) -> io::Result<usize> {
+        if self.frame.len() < FRAME_HEADER_LEN {
+            return Ok(FRAME_HEADER_LEN);
+        }
+        let last = match self.frame[0] {
+            0 => false,
+            1 => true,
+            _ => return Err(crypto_error(EncryptionError::CorruptedBlob)),
+        };
+        let len = u32::from_be_bytes(self.frame[1..FRAME_HEADER_LEN].try_into().unwrap()) as usize;
+        let full = self.header.chunk_size + TAG_LEN;
+        let valid = if last {
+            (TAG_LEN..=full).contains(&len)
+        } else {
+            len == full
+        };
+        if !valid {
+            return Err(crypto_error(EncryptionError::CorruptedBlob));
+        }
+        Ok(FRAME_HEADER_LEN + len)
+    }
+
+    fn open_frame(&mut self) -> io::Result<()> {
+        let last = self.frame[0] == 1;
+        let aad = chunk_aad(&self.aad, &self.header_bytes, self.index, last);
+        self.out = self
+            .cipher
+            .decrypt(
+                &self.header.nonce(self.index, last),
+                Payload {
+                    msg: &self.frame[FRAME_HEADER_LEN..],
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| crypto_error(EncryptionError::CorruptedBlob))?;
+        self.out_pos = 0;
+        self.frame.clear();
+
+        if last {
+            self.state = DecryptState::Trailing;
+        } else {
+            self.index = self
+                .index
+                .checked_add(1)
+                .ok_or_else(|| crypto_error(EncryptionError::CorruptedBlob))?;
+        }
+        Ok(())
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for DecryptingReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            if this.out_pos < this.out.len() {
+                let n = buf.remaining().min(this.out.len() - this.out_pos);
+                buf.put_slice(&this.out[this.out_pos..this.out_pos + n]);
+                this.out_pos += n;
+                return Poll::Ready(Ok(()));
+            }
+
+            match this.state {
+                DecryptState::Done => return Poll::Ready(Ok(())),
+                DecryptState::Trailing => {
+                    let mut probe = [0u8; 1];
+                    let mut read_buf = ReadBuf::new(&mut probe);
+                    match Pin::new(&mut this.inner).poll_read(cx, &mut read_buf) {
+                        Poll::Pending => return Poll::Pending,
+                        Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                        Poll::Ready(Ok(())) if read_buf.filled().is_empty() => {
+                            this.state = DecryptState::Done;
+                        }
+                        Poll::Ready(Ok(())) => {
+                            return Poll::Ready(Err(crypto_error(EncryptionError::CorruptedBlob)))
+                        }
+                    }
+                }
+                DecryptState::Chunks => {
+                    let need = this.frame_len()?;
+                    if this.frame.len() == need {
+                        this.open_frame()?;
+                        continue;
+                    }
+
+                    let start = this.frame.len();
+                    this.frame.resize(need, 0);
+                    let mut read_buf = ReadBuf::new(&mut this.frame[start..]);
+                    let polled = Pin::new(&mut this.inner).poll_read(cx, &mut read_buf);
+                    let filled = read_buf.filled().len();
+                    this.frame.truncate(start + filled);
+                    match polled {
+                        Poll::Pending => return Poll::Pending,
+                        Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                        Poll::Ready(Ok(())) if filled == 0 => {
+                            return Poll::Ready(Err(io::Error::new(
+                                io::ErrorKind::UnexpectedEof,
+                                "encrypted blob is truncated",
+                            )))
+                        }
+                        Poll::Ready(Ok(())) => {}
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AAD: &[u8] = b"uc:blob:v1|blob-1";
+
+    fn key(byte: u8) -> MasterKey {
+        MasterKey([byte; 32])
+    }
+
+    async fn seal(plaintext: &[u8], chunk_size: usize) -> Vec<u8> {
+        let mut reader =
+            EncryptingReader::with_chunk_size(plaintext, &key(7), 3, AAD.to_vec(), chunk_size)
+                .unwrap();
+        let mut sealed = Vec::new();
+        reader.read_to_end(&mut sealed).await.unwrap();
+        sealed
+    }
+
+    async fn open(sealed: &[u8], aad: &[u8]) -> io::Result<Vec<u8>> {
+        let mut input = sealed;
+        let StoredBlob::Chunked(header) = read_stored_blob(&mut input).await.unwrap() else {
+            panic!("expected chunked blob");
+        };
+        assert_eq!(header.key_epoch, 3);
+        let mut reader = DecryptingReader::new(input, &key(7), &header, aad.to_vec()).unwrap();
+        let mut plaintext = Vec::new();
+        reader.read_to_end(&mut plaintext).await?;
+        Ok(plaintext)
+    }
+
+    #[tokio::test]
+    async fn round_trips_across_chunk_boundaries() {
+        for len in [0usize, 1, 15, 16, 17, 48, 100] {
+            let plaintext: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let sealed = seal(&plaintext, 16).await;
+            assert!(sealed.starts_with(&MAGIC));
+            assert_eq!(open(&sealed, AAD).await.unwrap(), plaintext, "len {len}");
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_wrong_aad_and_tampering() {
+        let plaintext = vec![42u8; 40];
+        let sealed = seal(&plaintext, 16).await;
+
+        assert!(open(&sealed, b"uc:blob:v1|blob-2").await.is_err());
+
+        let mut tampered = sealed.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        assert!(open(&tampered, AAD).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn rejects_truncated_and_extended_blobs() {
+        let plaintext = vec![42u8; 40];
+        let sealed = seal(&plaintext, 16).await;
+        let frame = FRAME_HEADER_LEN + 16 + TAG_LEN;
+
+        // Dropping the final chunk leaves only non-final chunks.
+        let truncated = &sealed[..HEADER_LEN + 2 * frame];
+        let err = open(truncated, AAD).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+        let mut extended = sealed.clone();
+        extended.push(0);
+        assert!(open(&extended, AAD).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn decrypting_reader_holds_at_most_one_frame() {
+        let chunk_size = 16;
+        let plaintext: Vec<u8> = (0..chunk_size * 64).map(|i| i as u8).collect();
+        let sealed = seal(&plaintext, chunk_size).await;
+        let frame = FRAME_HEADER_LEN + chunk_size + TAG_LEN;
+
+        let mut input = sealed.as_slice();
+        let StoredBlob::Chunked(header) = read_stored_blob(&mut input).await.unwrap() else {
+            panic!("expected chunked blob");
+        };
+        let mut reader = DecryptingReader::new(input, &key(7), &header, AAD.to_vec()).unwrap();
+
+        let mut produced = Vec::new();
+        let mut buf = [0u8; 5];
+        loop {
+            let n = reader.read(&mut buf).await.unwrap();
+            if n == 0 {
+                break;
+            }
+            produced.extend_from_slice(&buf[..n]);
+
+            // Only the frame being handed out has been pulled from storage.
+            let consumed = sealed.len() - HEADER_LEN - reader.inner.len();
+            assert!(consumed <= produced.len().div_ceil(chunk_size) * frame);
+            assert!(reader.frame.capacity() <= frame);
+            assert!(reader.out.capacity() <= frame);
+        }
+        assert_eq!(produced, plaintext);
+    }
+
+    #[tokio::test]
+    async fn encrypting_reader_holds_at_most_one_chunk() {
+        let chunk_size = 16;
+        let plaintext: Vec<u8> = (0..chunk_size * 64).map(|i| i as u8).collect();
+        let frame = FRAME_HEADER_LEN + chunk_size + TAG_LEN;
+        let mut reader = EncryptingReader::with_chunk_size(
+            plaintext.as_slice(),
+            &key(7),
+            3,
+            AAD.to_vec(),
+            chunk_size,
+        )
+        .unwrap();
+
+        let mut sealed = Vec::new();
+        let mut buf = [0u8; 5];
+        loop {
+            let n = reader.read(&mut buf).await.unwrap();
+            if n == 0 {
+                break;
+            }
+            sealed.extend_from_slice(&buf[..n]);
+
+            // At most one chunk plus the look-ahead byte is read before sealing.
+            let consumed = plaintext.len() - reader.inner.len();
+            assert!(consumed <= (reader.index as usize + 1) * chunk_size + 1);
+            assert!(reader.plain.capacity() <= chunk_size + 1);
+            assert!(reader.out.len() <= frame.max(HEADER_LEN));
+        }
+        assert_eq!(open(&sealed, AAD).await.unwrap(), plaintext);
+    }
+
+    #[tokio::test]
+    async fn reports_legacy_json_blobs() {
+        let legacy = EncryptedBlob {
+            version: uc_core::security::model::EncryptionFormatVersion::V1,
+            aead: uc_core::security::model::EncryptionAlgo::XChaCha20Poly1305,
+            nonce: vec![0u8; 24],
+            ciphertext: vec![1, 2, 3],
+            aad_fingerprint: None,
+            key_epoch: 2,
+        };
+        let bytes = serde_json::to_vec(&legacy).unwrap();
+
+        let mut input = bytes.as_slice();
+        match read_stored_blob(&mut input).await.unwrap() {
+            StoredBlob::Legacy(blob) => assert_eq!(blob.key_epoch, 2),
+            StoredBlob::Chunked(_) => panic!("expected legacy blob"),
+        }
+    }
+}
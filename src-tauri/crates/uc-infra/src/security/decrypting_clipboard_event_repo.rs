@@ -54,10 +54,10 @@ impl ClipboardEventRepositoryPort for DecryptingClipboardEventRepository {
             // Try to deserialize as encrypted blob
             match serde_json::from_slice::<EncryptedBlob>(&observed.bytes) {
                 Ok(encrypted_blob) => {
-                    // Get master key
+                    // Get the master key of the epoch that sealed the data
                     let master_key = self
                         .session
-                        .get_master_key()
+                        .get_master_key_for_epoch(encrypted_blob.key_epoch)
                         .await
                         .context("encryption session not ready - cannot decrypt")?;
 
@@ -181,6 +181,7 @@ mod tests {
                 nonce: vec![0u8; 24],
                 ciphertext: vec![0u8; 32],
                 aad_fingerprint: None,
+                key_epoch: 0,
             })
         }
 
@@ -205,6 +206,7 @@ mod tests {
                 nonce: vec![0u8; 24],
                 ciphertext: vec![],
                 aad_fingerprint: None,
+                key_epoch: 0,
             })
         }
 
@@ -261,6 +263,38 @@ mod tests {
         async fn clear(&self) -> Result<(), uc_core::security::model::EncryptionError> {
             Ok(())
         }
+
+        async fn get_current_key(
+            &self,
+        ) -> Result<
+            (
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            ),
+            uc_core::security::model::EncryptionError,
+        > {
+            Ok((0, self.get_master_key().await?))
+        }
+
+        async fn get_master_key_for_epoch(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+        ) -> Result<uc_core::security::model::MasterKey, uc_core::security::model::EncryptionError>
+        {
+            self.get_master_key().await
+        }
+
+        async fn set_epoch_keys(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+            master_key: uc_core::security::model::MasterKey,
+            _older: Vec<(
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            )>,
+        ) -> Result<(), uc_core::security::model::EncryptionError> {
+            self.set_master_key(master_key).await
+        }
     }
 
     /// Creates an encrypted representation for testing
@@ -273,6 +307,7 @@ mod tests {
             nonce: vec![0u8; 24],
             ciphertext: plaintext.to_vec(),
             aad_fingerprint: None,
+            key_epoch: 0,
         };
         let encrypted_bytes = serde_json::to_vec(&encrypted_blob).unwrap();
 
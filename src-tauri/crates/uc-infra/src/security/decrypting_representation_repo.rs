@@ -61,10 +61,10 @@ impl ClipboardRepresentationRepositoryPort for DecryptingClipboardRepresentation
             let encrypted_blob: EncryptedBlob = serde_json::from_slice(encrypted_bytes)
                 .context("failed to deserialize encrypted inline_data - data may be corrupted")?;
 
-            // Get master key
+            // Get the master key of the epoch that sealed the data
             let master_key = self
                 .session
-                .get_master_key()
+                .get_master_key_for_epoch(encrypted_blob.key_epoch)
                 .await
                 .context("encryption session not ready - cannot decrypt")?;
 
@@ -176,6 +176,31 @@ impl ClipboardRepresentationRepositoryPort for DecryptingClipboardRepresentation
             .update_processing_result(rep_id, expected_states, blob_id, new_state, last_error)
             .await
     }
+
+    // Inline payload walking and rewriting work on the stored ciphertext.
+    async fn count_inline_representations(&self) -> Result<usize> {
+        self.inner.count_inline_representations().await
+    }
+
+    async fn list_inline_representation_ids(
+        &self,
+        after: Option<&RepresentationId>,
+        limit: usize,
+    ) -> Result<Vec<(EventId, RepresentationId)>> {
+        self.inner
+            .list_inline_representation_ids(after, limit)
+            .await
+    }
+
+    async fn update_inline_data(
+        &self,
+        representation_id: &RepresentationId,
+        inline_data: &[u8],
+    ) -> Result<()> {
+        self.inner
+            .update_inline_data(representation_id, inline_data)
+            .await
+    }
 }
 
 #[cfg(test)]
@@ -323,6 +348,26 @@ mod tests {
             }
             Ok(ProcessingUpdateOutcome::NotFound)
         }
+
+        async fn count_inline_representations(&self) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_inline_representation_ids(
+            &self,
+            _after: Option<&uc_core::ids::RepresentationId>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<(uc_core::ids::EventId, uc_core::ids::RepresentationId)>> {
+            Ok(Vec::new())
+        }
+
+        async fn update_inline_data(
+            &self,
+            _representation_id: &uc_core::ids::RepresentationId,
+            _inline_data: &[u8],
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
     }
 
     /// Mock EncryptionPort
@@ -362,6 +407,7 @@ mod tests {
                 nonce: vec![0u8; 24],
                 ciphertext: vec![0u8; 32],
                 aad_fingerprint: None,
+                key_epoch: 0,
             })
         }
 
@@ -386,6 +432,7 @@ mod tests {
                 nonce: vec![0u8; 24],
                 ciphertext: vec![],
                 aad_fingerprint: None,
+                key_epoch: 0,
             })
         }
 
@@ -442,6 +489,38 @@ mod tests {
         async fn clear(&self) -> Result<(), uc_core::security::model::EncryptionError> {
             Ok(())
         }
+
+        async fn get_current_key(
+            &self,
+        ) -> Result<
+            (
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            ),
+            uc_core::security::model::EncryptionError,
+        > {
+            Ok((0, self.get_master_key().await?))
+        }
+
+        async fn get_master_key_for_epoch(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+        ) -> Result<uc_core::security::model::MasterKey, uc_core::security::model::EncryptionError>
+        {
+            self.get_master_key().await
+        }
+
+        async fn set_epoch_keys(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+            master_key: uc_core::security::model::MasterKey,
+            _older: Vec<(
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            )>,
+        ) -> Result<(), uc_core::security::model::EncryptionError> {
+            self.set_master_key(master_key).await
+        }
     }
 
     /// Creates an encrypted representation for testing
@@ -455,6 +534,7 @@ mod tests {
             nonce: vec![0u8; 24],
             ciphertext: plaintext.to_vec(),
             aad_fingerprint: None,
+            key_epoch: 0,
         };
         let encrypted_bytes = serde_json::to_vec(&encrypted_blob).unwrap();
 
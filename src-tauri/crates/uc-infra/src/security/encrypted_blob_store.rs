@@ -1,21 +1,27 @@
 //! Encrypted blob store decorator.
 //!
 //! Wraps an inner BlobStorePort and transparently encrypts/decrypts
-//! blob data using the session's MasterKey.
+//! blob data using the session's MasterKey. Blobs are written in the chunked
+//! container format (see `chunked_blob`) and streamed through the inner
+//! store, so memory use does not grow with the blob size. Legacy JSON
+//! `EncryptedBlob` envelopes remain readable.
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use std::io::Cursor;
 use std::path::PathBuf;
 use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncReadExt};
 use tracing::debug;
 
 use uc_core::{
-    ports::{BlobStorePort, EncryptionPort, EncryptionSessionPort},
+    ports::{BlobReader, BlobStorePort, EncryptionPort, EncryptionSessionPort, StoredBlobObject},
     security::aad,
-    security::model::EncryptionAlgo,
     BlobId,
 };
 
+use super::chunked_blob::{read_stored_blob, DecryptingReader, EncryptingReader, StoredBlob};
+
 /// Decorator that encrypts/decrypts blob data transparently.
 pub struct EncryptedBlobStore {
     inner: Arc<dyn BlobStorePort>,
@@ -40,72 +46,105 @@ impl EncryptedBlobStore {
 #[async_trait]
 impl BlobStorePort for EncryptedBlobStore {
     async fn put(&self, blob_id: &BlobId, data: &[u8]) -> Result<PathBuf> {
-        // 1. Get master key from session
-        let master_key = self
+        let mut reader = data;
+        self.put_stream(blob_id, &mut reader).await
+    }
+
+    async fn get(&self, blob_id: &BlobId) -> Result<Vec<u8>> {
+        let mut reader = self.open_read(blob_id).await?;
+        let mut plaintext = Vec::new();
+        reader
+            .read_to_end(&mut plaintext)
+            .await
+            .context("failed to decrypt blob - key mismatch or data corrupted")?;
+
+        debug!(
+            "Decrypted blob {} ({} bytes)",
+            blob_id.as_ref(),
+            plaintext.len()
+        );
+
+        Ok(plaintext)
+    }
+
+    async fn put_stream(
+        &self,
+        blob_id: &BlobId,
+        reader: &mut (dyn AsyncRead + Send + Unpin),
+    ) -> Result<PathBuf> {
+        // 1. Get current master key (and its epoch) from session
+        let (key_epoch, master_key) = self
             .session
-            .get_master_key()
+            .get_current_key()
             .await
             .context("encryption session not ready - cannot encrypt blob")?;
 
-        // 2. Encrypt the data
-        let aad = aad::for_blob(blob_id);
-        let encrypted_blob = self
-            .encryption
-            .encrypt_blob(&master_key, data, &aad, EncryptionAlgo::XChaCha20Poly1305)
+        // 2. Seal the plaintext chunk by chunk while the inner store drains it
+        let mut sealed =
+            EncryptingReader::new(reader, &master_key, key_epoch, aad::for_blob(blob_id))
+                .context("failed to encrypt blob data")?;
+        let path = self
+            .inner
+            .put_stream(blob_id, &mut sealed)
             .await
             .context("failed to encrypt blob data")?;
 
-        // 3. Serialize encrypted blob to bytes
-        let encrypted_bytes =
-            serde_json::to_vec(&encrypted_blob).context("failed to serialize encrypted blob")?;
-
         debug!(
-            "Encrypted blob {} ({} bytes plaintext -> {} bytes ciphertext)",
+            "Encrypted blob {} under key epoch {}",
             blob_id.as_ref(),
-            data.len(),
-            encrypted_bytes.len()
+            key_epoch
         );
 
-        // 4. Store encrypted bytes
-        self.inner.put(blob_id, &encrypted_bytes).await
+        Ok(path)
     }
 
-    async fn get(&self, blob_id: &BlobId) -> Result<Vec<u8>> {
-        // 1. Get encrypted bytes from inner store
-        let encrypted_bytes = self
+    async fn open_read(&self, blob_id: &BlobId) -> Result<BlobReader> {
+        // 1. Open the stored bytes and detect the container format
+        let mut stored = self
             .inner
-            .get(blob_id)
+            .open_read(blob_id)
             .await
             .context("failed to read encrypted blob from storage")?;
-
-        // 2. Deserialize encrypted blob
-        let encrypted_blob: uc_core::security::model::EncryptedBlob = serde_json::from_slice(
-            &encrypted_bytes,
-        )
-        .context("failed to deserialize encrypted blob - data may be corrupted or unencrypted")?;
-
-        // 3. Get master key from session
-        let master_key = self
-            .session
-            .get_master_key()
-            .await
-            .context("encryption session not ready - cannot decrypt blob")?;
-
-        // 4. Decrypt the data
         let aad = aad::for_blob(blob_id);
-        let plaintext = self
-            .encryption
-            .decrypt_blob(&master_key, &encrypted_blob, &aad)
-            .await
-            .context("failed to decrypt blob - key mismatch or data corrupted")?;
 
-        debug!(
-            "Decrypted blob {} ({} bytes)",
-            blob_id.as_ref(),
-            plaintext.len()
-        );
+        match read_stored_blob(&mut stored).await? {
+            StoredBlob::Chunked(header) => {
+                // 2. Get the master key of the epoch that sealed this blob
+                let master_key = self
+                    .session
+                    .get_master_key_for_epoch(header.key_epoch)
+                    .await
+                    .context("encryption session not ready - cannot decrypt blob")?;
+
+                // 3. Decrypt chunk by chunk as the caller reads
+                let reader = DecryptingReader::new(stored, &master_key, &header, aad)
+                    .context("failed to decrypt blob - key mismatch or data corrupted")?;
+                Ok(Box::new(reader))
+            }
+            StoredBlob::Legacy(encrypted_blob) => {
+                // Whole-file JSON envelope written before the chunked format
+                let master_key = self
+                    .session
+                    .get_master_key_for_epoch(encrypted_blob.key_epoch)
+                    .await
+                    .context("encryption session not ready - cannot decrypt blob")?;
+
+                let plaintext = self
+                    .encryption
+                    .decrypt_blob(&master_key, &encrypted_blob, &aad)
+                    .await
+                    .context("failed to decrypt blob - key mismatch or data corrupted")?;
+                Ok(Box::new(Cursor::new(plaintext)))
+            }
+        }
+    }
 
-        Ok(plaintext)
+    async fn delete(&self, blob_id: &BlobId) -> Result<u64> {
+        self.inner.delete(blob_id).await
+    }
+
+    async fn list_objects(&self) -> Result<Vec<StoredBlobObject>> {
+        self.inner.list_objects().await
     }
 }
 
@@ -156,6 +195,31 @@ mod tests {
                 .cloned()
                 .ok_or_else(|| anyhow::anyhow!("blob not found"))
         }
+
+        async fn put_stream(
+            &self,
+            blob_id: &uc_core::BlobId,
+            reader: &mut (dyn tokio::io::AsyncRead + Send + Unpin),
+        ) -> anyhow::Result<std::path::PathBuf> {
+            let mut data = Vec::new();
+            tokio::io::AsyncReadExt::read_to_end(reader, &mut data).await?;
+            self.put(blob_id, &data).await
+        }
+
+        async fn open_read(
+            &self,
+            blob_id: &uc_core::BlobId,
+        ) -> anyhow::Result<uc_core::ports::BlobReader> {
+            Ok(Box::new(std::io::Cursor::new(self.get(blob_id).await?)))
+        }
+
+        async fn delete(&self, _blob_id: &uc_core::BlobId) -> anyhow::Result<u64> {
+            Ok(0)
+        }
+
+        async fn list_objects(&self) -> anyhow::Result<Vec<uc_core::ports::StoredBlobObject>> {
+            Ok(Vec::new())
+        }
     }
 
     /// Mock EncryptionPort
@@ -172,8 +236,8 @@ mod tests {
             }
         }
 
-        fn fail_on_encrypt(mut self) -> Self {
-            self.should_fail_encrypt = true;
+        fn fail_on_decrypt(mut self) -> Self {
+            self.should_fail_decrypt = true;
             self
         }
     }
@@ -202,6 +266,7 @@ mod tests {
                 nonce: vec![0u8; 24],
                 ciphertext: vec![0u8; 32],
                 aad_fingerprint: None,
+                key_epoch: 0,
             })
         }
 
@@ -230,6 +295,7 @@ mod tests {
                 nonce: vec![0u8; 24],
                 ciphertext: plaintext.to_vec(),
                 aad_fingerprint: None,
+                key_epoch: 0,
             })
         }
 
@@ -286,6 +352,38 @@ mod tests {
         async fn clear(&self) -> Result<(), uc_core::security::model::EncryptionError> {
             Ok(())
         }
+
+        async fn get_current_key(
+            &self,
+        ) -> Result<
+            (
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            ),
+            uc_core::security::model::EncryptionError,
+        > {
+            Ok((0, self.get_master_key().await?))
+        }
+
+        async fn get_master_key_for_epoch(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+        ) -> Result<uc_core::security::model::MasterKey, uc_core::security::model::EncryptionError>
+        {
+            self.get_master_key().await
+        }
+
+        async fn set_epoch_keys(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+            master_key: uc_core::security::model::MasterKey,
+            _older: Vec<(
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            )>,
+        ) -> Result<(), uc_core::security::model::EncryptionError> {
+            self.set_master_key(master_key).await
+        }
     }
 
     #[tokio::test]
@@ -307,17 +405,12 @@ mod tests {
 
         assert!(result.is_ok(), "put should succeed");
 
-        // Verify the stored data is an encrypted blob (JSON serializable)
+        // Verify the stored data is a chunked container, not the plaintext
         let stored_data = inner.get_stored(&blob_id).expect("blob should be stored");
-        let encrypted_blob: EncryptedBlob = serde_json::from_slice(&stored_data)
-            .expect("stored data should be a valid encrypted blob");
-
-        assert_eq!(encrypted_blob.version, EncryptionFormatVersion::V1);
-        assert_eq!(
-            encrypted_blob.aead,
-            uc_core::security::model::EncryptionAlgo::XChaCha20Poly1305
-        );
-        assert_eq!(encrypted_blob.ciphertext, data.to_vec());
+        assert!(stored_data.starts_with(&crate::security::chunked_blob::MAGIC));
+        assert!(!stored_data
+            .windows(data.len())
+            .any(|window| window == data.as_slice()));
     }
 
     #[tokio::test]
@@ -408,31 +501,117 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_encrypted_store_propagates_encrypt_errors() {
-        // Test that encryption errors are propagated
+    async fn test_encrypted_store_rejects_tampered_blob() {
         let inner = Arc::new(MockBlobStore::new());
-        let encryption = Arc::new(MockEncryption::new().fail_on_encrypt());
+        let encryption = Arc::new(MockEncryption::new());
         let session = Arc::new(
             MockEncryptionSession::new()
                 .with_master_key(MasterKey::from_bytes(&[0u8; 32]).unwrap()),
         );
 
-        let store = EncryptedBlobStore::new(inner, encryption, session);
+        let store = EncryptedBlobStore::new(inner.clone(), encryption, session);
 
         let blob_id = BlobId::from("test-blob");
-        let data = b"test data";
+        store.put(&blob_id, b"test data").await.unwrap();
 
-        let result = store.put(&blob_id, data).await;
+        let mut stored = inner.get_stored(&blob_id).unwrap();
+        let last = stored.len() - 1;
+        stored[last] ^= 0x01;
+        inner.put(&blob_id, &stored).await.unwrap();
+
+        let result = store.get(&blob_id).await;
+
+        assert!(result.is_err(), "get should fail on tampered data");
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_store_reads_legacy_json_blobs() {
+        // Blobs written before the chunked format are JSON EncryptedBlob envelopes
+        let inner = Arc::new(MockBlobStore::new());
+        let encryption = Arc::new(MockEncryption::new());
+        let session = Arc::new(
+            MockEncryptionSession::new()
+                .with_master_key(MasterKey::from_bytes(&[0u8; 32]).unwrap()),
+        );
+
+        let blob_id = BlobId::from("legacy-blob");
+        let legacy = EncryptedBlob {
+            version: EncryptionFormatVersion::V1,
+            aead: uc_core::security::model::EncryptionAlgo::XChaCha20Poly1305,
+            nonce: vec![0u8; 24],
+            ciphertext: b"legacy data".to_vec(),
+            aad_fingerprint: None,
+            key_epoch: 0,
+        };
+        inner
+            .put(&blob_id, &serde_json::to_vec(&legacy).unwrap())
+            .await
+            .unwrap();
+
+        let store = EncryptedBlobStore::new(inner, encryption, session);
 
-        assert!(result.is_err(), "put should fail when encryption fails");
+        assert_eq!(store.get(&blob_id).await.unwrap(), b"legacy data".to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_store_propagates_legacy_decrypt_errors() {
+        let inner = Arc::new(MockBlobStore::new());
+        let encryption = Arc::new(MockEncryption::new().fail_on_decrypt());
+        let session = Arc::new(
+            MockEncryptionSession::new()
+                .with_master_key(MasterKey::from_bytes(&[0u8; 32]).unwrap()),
+        );
+
+        let blob_id = BlobId::from("legacy-blob");
+        let legacy = EncryptedBlob {
+            version: EncryptionFormatVersion::V1,
+            aead: uc_core::security::model::EncryptionAlgo::XChaCha20Poly1305,
+            nonce: vec![0u8; 24],
+            ciphertext: b"legacy data".to_vec(),
+            aad_fingerprint: None,
+            key_epoch: 0,
+        };
+        inner
+            .put(&blob_id, &serde_json::to_vec(&legacy).unwrap())
+            .await
+            .unwrap();
+
+        let store = EncryptedBlobStore::new(inner, encryption, session);
+
+        let result = store.get(&blob_id).await;
+
+        assert!(result.is_err(), "get should fail when decryption fails");
         let err_msg = result.unwrap_err().to_string();
         assert!(
-            err_msg.contains("failed to encrypt blob data"),
-            "error should indicate encryption failure: {}",
+            err_msg.contains("failed to decrypt blob"),
+            "error should indicate decryption failure: {}",
             err_msg
         );
     }
 
+    #[tokio::test]
+    async fn test_encrypted_store_streams_large_blob() {
+        let inner = Arc::new(MockBlobStore::new());
+        let encryption = Arc::new(MockEncryption::new());
+        let session = Arc::new(
+            MockEncryptionSession::new()
+                .with_master_key(MasterKey::from_bytes(&[3u8; 32]).unwrap()),
+        );
+
+        let store = EncryptedBlobStore::new(inner, encryption, session);
+
+        let blob_id = BlobId::from("large-blob");
+        let data: Vec<u8> = (0..300 * 1024).map(|i| (i % 251) as u8).collect();
+        let mut reader = data.as_slice();
+        store.put_stream(&blob_id, &mut reader).await.unwrap();
+
+        let mut restored = Vec::new();
+        let copied = store.read_into(&blob_id, &mut restored).await.unwrap();
+
+        assert_eq!(copied, data.len() as u64);
+        assert_eq!(restored, data);
+    }
+
     #[tokio::test]
     async fn test_aad_generation_includes_blob_id() {
         // Test that AAD generation includes the blob ID
@@ -43,10 +43,10 @@ impl ClipboardEventWriterPort for EncryptingClipboardEventWriter {
         event: &ClipboardEvent,
         representations: &Vec<PersistedClipboardRepresentation>,
     ) -> Result<()> {
-        // Get master key from session
-        let master_key = self
+        // Get current master key (and its epoch) from session
+        let (key_epoch, master_key) = self
             .session
-            .get_master_key()
+            .get_current_key()
             .await
             .context("encryption session not ready - cannot encrypt clipboard data")?;
 
@@ -57,7 +57,7 @@ impl ClipboardEventWriterPort for EncryptingClipboardEventWriter {
             let encrypted_inline_data = if let Some(ref plaintext) = rep.inline_data {
                 // Encrypt the inline data
                 let aad = aad::for_inline(&event.event_id, &rep.id);
-                let encrypted_blob = self
+                let mut encrypted_blob = self
                     .encryption
                     .encrypt_blob(
                         &master_key,
@@ -67,6 +67,7 @@ impl ClipboardEventWriterPort for EncryptingClipboardEventWriter {
                     )
                     .await
                     .context("failed to encrypt inline_data")?;
+                encrypted_blob.key_epoch = key_epoch;
 
                 // Serialize to bytes
                 let encrypted_bytes = serde_json::to_vec(&encrypted_blob)
@@ -204,6 +205,7 @@ mod tests {
                 nonce: vec![0u8; 24],
                 ciphertext: vec![0u8; 32],
                 aad_fingerprint: None,
+                key_epoch: 0,
             })
         }
 
@@ -232,6 +234,7 @@ mod tests {
                 nonce: vec![0u8; 24],
                 ciphertext: plaintext.to_vec(),
                 aad_fingerprint: None,
+                key_epoch: 0,
             })
         }
 
@@ -285,6 +288,38 @@ mod tests {
         async fn clear(&self) -> Result<(), uc_core::security::model::EncryptionError> {
             Ok(())
         }
+
+        async fn get_current_key(
+            &self,
+        ) -> Result<
+            (
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            ),
+            uc_core::security::model::EncryptionError,
+        > {
+            Ok((0, self.get_master_key().await?))
+        }
+
+        async fn get_master_key_for_epoch(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+        ) -> Result<uc_core::security::model::MasterKey, uc_core::security::model::EncryptionError>
+        {
+            self.get_master_key().await
+        }
+
+        async fn set_epoch_keys(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+            master_key: uc_core::security::model::MasterKey,
+            _older: Vec<(
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            )>,
+        ) -> Result<(), uc_core::security::model::EncryptionError> {
+            self.set_master_key(master_key).await
+        }
     }
 
     /// Creates a test clipboard event
@@ -74,6 +74,7 @@ impl EncryptionPort for EncryptionRepository {
             ciphertext,
             aead,
             aad_fingerprint: None,
+            key_epoch: 0,
         })
     }
 
@@ -132,6 +133,7 @@ impl EncryptionPort for EncryptionRepository {
             ciphertext,
             aead,
             aad_fingerprint: aad_fp,
+            key_epoch: 0,
         })
     }
 
@@ -1,17 +1,20 @@
 use async_trait::async_trait;
+use std::collections::BTreeMap;
 use tokio::sync::RwLock;
 
 use uc_core::ports::EncryptionSessionPort;
-use uc_core::security::model::{EncryptionError, MasterKey};
+use uc_core::security::model::{EncryptionError, KeyEpoch, MasterKey};
 
 pub struct InMemoryEncryptionSession {
     key: RwLock<Option<MasterKey>>,
+    epoch: RwLock<(KeyEpoch, BTreeMap<KeyEpoch, MasterKey>)>,
 }
 
 impl InMemoryEncryptionSession {
     pub fn new() -> Self {
         Self {
             key: RwLock::new(None),
+            epoch: RwLock::new((0, BTreeMap::new())),
         }
     }
 }
@@ -32,11 +35,38 @@ impl EncryptionSessionPort for InMemoryEncryptionSession {
 
     async fn set_master_key(&self, master_key: MasterKey) -> Result<(), EncryptionError> {
         *self.key.write().await = Some(master_key);
+        *self.epoch.write().await = (0, BTreeMap::new());
         Ok(())
     }
 
     async fn clear(&self) -> Result<(), EncryptionError> {
         *self.key.write().await = None;
+        self.epoch.write().await.1.clear();
+        Ok(())
+    }
+
+    async fn get_current_key(&self) -> Result<(KeyEpoch, MasterKey), EncryptionError> {
+        let key = self.get_master_key().await?;
+        Ok((self.epoch.read().await.0, key))
+    }
+
+    async fn get_master_key_for_epoch(
+        &self,
+        epoch: KeyEpoch,
+    ) -> Result<MasterKey, EncryptionError> {
+        let key = self.get_master_key().await?;
+        let (_, older) = &*self.epoch.read().await;
+        Ok(older.get(&epoch).cloned().unwrap_or(key))
+    }
+
+    async fn set_epoch_keys(
+        &self,
+        epoch: KeyEpoch,
+        master_key: MasterKey,
+        older: Vec<(KeyEpoch, MasterKey)>,
+    ) -> Result<(), EncryptionError> {
+        *self.key.write().await = Some(master_key);
+        *self.epoch.write().await = (epoch, older.into_iter().collect());
         Ok(())
     }
 }
@@ -108,4 +138,22 @@ mod tests {
         let stored = session.get_master_key().await.expect("get master key");
         assert_eq!(stored, key_b);
     }
+
+    #[tokio::test]
+    async fn epoch_keys_resolve_every_retained_epoch() {
+        let session = InMemoryEncryptionSession::new();
+        let oldest = MasterKey([1u8; 32]);
+        let old = MasterKey([2u8; 32]);
+        let new = MasterKey([3u8; 32]);
+
+        session
+            .set_epoch_keys(2, new.clone(), vec![(0, oldest.clone()), (1, old.clone())])
+            .await
+            .expect("set epoch keys");
+
+        assert_eq!(session.get_current_key().await.unwrap(), (2, new.clone()));
+        assert_eq!(session.get_master_key_for_epoch(0).await.unwrap(), oldest);
+        assert_eq!(session.get_master_key_for_epoch(1).await.unwrap(), old);
+        assert_eq!(session.get_master_key_for_epoch(2).await.unwrap(), new);
+    }
 }
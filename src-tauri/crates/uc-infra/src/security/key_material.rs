@@ -1,15 +1,18 @@
 use async_trait::async_trait;
+use std::path::PathBuf;
 use std::sync::Arc;
 use uc_core::{
+    ids::SpaceId,
     ports::{KeyMaterialPort, SecureStoragePort},
     security::model::{EncryptionError, Kek, KeyScope, KeySlot, KeySlotFile},
 };
 
-use crate::fs::key_slot_store::KeySlotStore;
+use crate::fs::key_slot_store::{space_keyslot_dir, JsonKeySlotStore, KeySlotStore};
 
 pub struct DefaultKeyMaterialService {
     secure_storage: Arc<dyn SecureStoragePort>,
     keyslot_store: Arc<dyn KeySlotStore>,
+    space_keyslot_root: Option<PathBuf>,
 }
 
 impl DefaultKeyMaterialService {
@@ -22,6 +25,26 @@ impl DefaultKeyMaterialService {
         Self {
             secure_storage,
             keyslot_store,
+            space_keyslot_root: None,
+        }
+    }
+
+    /// Keep keyslots of non-default spaces under [`space_keyslot_dir`].
+    /// 非默认空间的 keyslot 存放在 `root/spaces/<space id>/` 下。
+    ///
+    /// The default space keeps using the store passed to [`Self::new`].
+    pub fn with_space_keyslot_root(mut self, root: PathBuf) -> Self {
+        self.space_keyslot_root = Some(root);
+        self
+    }
+
+    fn keyslot_store_for(&self, scope: &KeyScope) -> Arc<dyn KeySlotStore> {
+        let space_id = SpaceId::from(scope.profile_id.as_str());
+        match &self.space_keyslot_root {
+            Some(root) if !space_id.is_default() => {
+                Arc::new(JsonKeySlotStore::new(space_keyslot_dir(root, &space_id)))
+            }
+            _ => self.keyslot_store.clone(),
         }
     }
 }
@@ -67,7 +90,7 @@ impl KeyMaterialPort for DefaultKeyMaterialService {
     }
 
     async fn load_keyslot(&self, scope: &KeyScope) -> Result<KeySlot, EncryptionError> {
-        let file = self.keyslot_store.load().await?;
+        let file = self.keyslot_store_for(scope).load().await?;
         if &file.scope != scope {
             return Err(EncryptionError::KeyMaterialCorrupt);
         }
@@ -76,15 +99,16 @@ impl KeyMaterialPort for DefaultKeyMaterialService {
 
     async fn store_keyslot(&self, keyslot: &KeySlot) -> Result<(), EncryptionError> {
         let file = KeySlotFile::try_from(keyslot).map_err(|_| EncryptionError::CorruptedKeySlot)?;
-        self.keyslot_store.store(&file).await
+        self.keyslot_store_for(&keyslot.scope).store(&file).await
     }
 
     async fn delete_keyslot(&self, scope: &KeyScope) -> Result<(), EncryptionError> {
-        let file = self.keyslot_store.load().await?;
+        let store = self.keyslot_store_for(scope);
+        let file = store.load().await?;
         if &file.scope != scope {
             return Err(EncryptionError::KeyMaterialCorrupt);
         }
-        self.keyslot_store.delete().await
+        store.delete().await
     }
 }
 
@@ -239,6 +263,10 @@ mod tests {
             scope,
             kdf: KdfParams::for_initialization(),
             salt: vec![1u8; 32],
+            epoch: 0,
+            revision: 0,
+            retired_master_key: None,
+            retained_master_keys: Vec::new(),
             wrapped_master_key: Some(WrappedMasterKey {
                 blob: uc_core::security::model::EncryptedBlob {
                     version: EncryptionFormatVersion::V1,
@@ -246,6 +274,7 @@ mod tests {
                     nonce: vec![1u8; 24],
                     ciphertext: vec![2u8; 32],
                     aad_fingerprint: None,
+                    key_epoch: 0,
                 },
             }),
         }
@@ -423,4 +452,37 @@ mod tests {
         let guard = state.lock().expect("lock keyslot state");
         assert!(guard.delete_called);
     }
+
+    #[tokio::test]
+    async fn space_keyslots_are_stored_apart_from_default() {
+        let (storage, _) = TestSecureStorage::new();
+        let (keyslot_store, state) = TestKeySlotStore::new();
+        let root = tempfile::tempdir().expect("tempdir");
+        let service = DefaultKeyMaterialService::new(
+            Arc::new(storage) as Arc<dyn SecureStoragePort>,
+            Arc::new(keyslot_store) as Arc<dyn KeySlotStore>,
+        )
+        .with_space_keyslot_root(root.path().to_path_buf());
+        let scope = sample_scope("work");
+        let keyslot = sample_keyslot(scope.clone());
+
+        service
+            .store_keyslot(&keyslot)
+            .await
+            .expect("store keyslot");
+
+        assert!(state
+            .lock()
+            .expect("lock keyslot state")
+            .stored_slot
+            .is_none());
+        assert!(root
+            .path()
+            .join("spaces")
+            .join("work")
+            .join("keyslot.json")
+            .exists());
+        let loaded = service.load_keyslot(&scope).await.expect("load keyslot");
+        assert_eq!(loaded.scope, scope);
+    }
 }
@@ -1,3 +1,4 @@
+mod chunked_blob;
 mod decrypting_clipboard_event_repo;
 mod decrypting_representation_repo;
 mod encrypted_blob_store;
@@ -8,6 +9,8 @@ mod encryption_state;
 mod encryption_state_repo;
 mod hashing;
 mod key_material;
+mod payload_reencryptor;
+mod search_index_closing_session;
 
 pub use decrypting_clipboard_event_repo::DecryptingClipboardEventRepository;
 pub use decrypting_representation_repo::DecryptingClipboardRepresentationRepository;
@@ -18,3 +21,5 @@ pub use encryption_session::InMemoryEncryptionSession;
 pub use encryption_state_repo::FileEncryptionStateRepository;
 pub use hashing::Blake3Hasher;
 pub use key_material::DefaultKeyMaterialService;
+pub use payload_reencryptor::PayloadReencryptor;
+pub use search_index_closing_session::SearchIndexClosingSession;
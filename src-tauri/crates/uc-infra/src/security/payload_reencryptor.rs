@@ -0,0 +1,552 @@
+//! Re-encryption of stored clipboard payloads after a MasterKey rotation.
+//!
+//! Works on the raw (undecorated) repositories and blob store: it reads the
+//! stored ciphertext, decrypts it with the key of the epoch that sealed it
+//! and writes it back sealed with the current key. Blobs are streamed, and
+//! legacy JSON blobs are migrated to the chunked container on the way.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::sync::Arc;
+use tracing::debug;
+
+use uc_core::{
+    ids::{EventId, RepresentationId},
+    ports::{
+        clipboard::ClipboardRepresentationRepositoryPort, BlobRepositoryPort, BlobStorePort,
+        EncryptionPort, EncryptionSessionPort, PayloadReencryptionPort,
+    },
+    security::aad,
+    security::model::{EncryptedBlob, EncryptionAlgo},
+    BlobId,
+};
+
+use super::chunked_blob::{read_stored_blob, DecryptingReader, EncryptingReader, StoredBlob};
+
+/// Rewrites inline representation data and blobs under the current key epoch.
+pub struct PayloadReencryptor {
+    representation_repo: Arc<dyn ClipboardRepresentationRepositoryPort>,
+    blob_repo: Arc<dyn BlobRepositoryPort>,
+    blob_store: Arc<dyn BlobStorePort>,
+    encryption: Arc<dyn EncryptionPort>,
+    session: Arc<dyn EncryptionSessionPort>,
+}
+
+impl PayloadReencryptor {
+    /// `representation_repo` and `blob_store` must be the raw storage adapters,
+    /// not the decrypting/encrypting decorators.
+    pub fn new(
+        representation_repo: Arc<dyn ClipboardRepresentationRepositoryPort>,
+        blob_repo: Arc<dyn BlobRepositoryPort>,
+        blob_store: Arc<dyn BlobStorePort>,
+        encryption: Arc<dyn EncryptionPort>,
+        session: Arc<dyn EncryptionSessionPort>,
+    ) -> Self {
+        Self {
+            representation_repo,
+            blob_repo,
+            blob_store,
+            encryption,
+            session,
+        }
+    }
+
+    /// Re-seal `bytes` under the current epoch; `None` if already current.
+    async fn reseal(&self, bytes: &[u8], aad: &[u8]) -> Result<Option<Vec<u8>>> {
+        let stored: EncryptedBlob = serde_json::from_slice(bytes)
+            .context("failed to deserialize encrypted payload - data may be corrupted")?;
+
+        let (current_epoch, current_key) = self
+            .session
+            .get_current_key()
+            .await
+            .context("encryption session not ready - cannot re-encrypt")?;
+        if stored.key_epoch == current_epoch {
+            return Ok(None);
+        }
+
+        let old_key = self
+            .session
+            .get_master_key_for_epoch(stored.key_epoch)
+            .await
+            .context("encryption session not ready - cannot re-encrypt")?;
+        let plaintext = self
+            .encryption
+            .decrypt_blob(&old_key, &stored, aad)
+            .await
+            .context("failed to decrypt payload with its epoch key")?;
+
+        let mut resealed = self
+            .encryption
+            .encrypt_blob(
+                &current_key,
+                &plaintext,
+                aad,
+                EncryptionAlgo::XChaCha20Poly1305,
+            )
+            .await
+            .context("failed to re-encrypt payload")?;
+        resealed.key_epoch = current_epoch;
+
+        Ok(Some(
+            serde_json::to_vec(&resealed).context("failed to serialize encrypted payload")?,
+        ))
+    }
+}
+
+#[async_trait]
+impl PayloadReencryptionPort for PayloadReencryptor {
+    async fn count_inline_payloads(&self) -> Result<usize> {
+        self.representation_repo
+            .count_inline_representations()
+            .await
+    }
+
+    async fn list_inline_payloads(
+        &self,
+        after: Option<&RepresentationId>,
+        limit: usize,
+    ) -> Result<Vec<(EventId, RepresentationId)>> {
+        self.representation_repo
+            .list_inline_representation_ids(after, limit)
+            .await
+    }
+
+    async fn count_blob_payloads(&self) -> Result<usize> {
+        self.blob_repo.count_blobs().await
+    }
+
+    async fn list_blob_payloads(
+        &self,
+        after: Option<&BlobId>,
+        limit: usize,
+    ) -> Result<Vec<BlobId>> {
+        self.blob_repo.list_blob_ids(after, limit).await
+    }
+
+    async fn reencrypt_inline(
+        &self,
+        event_id: &EventId,
+        representation_id: &RepresentationId,
+    ) -> Result<bool> {
+        let Some(inline_data) = self
+            .representation_repo
+            .get_representation(event_id, representation_id)
+            .await?
+            .and_then(|rep| rep.inline_data)
+        else {
+            return Ok(false);
+        };
+
+        let aad = aad::for_inline(event_id, representation_id);
+        let Some(resealed) = self.reseal(&inline_data, &aad).await? else {
+            return Ok(false);
+        };
+        self.representation_repo
+            .update_inline_data(representation_id, &resealed)
+            .await?;
+
+        debug!(
+            "Re-encrypted inline_data for rep {}",
+            representation_id.as_ref()
+        );
+        Ok(true)
+    }
+
+    async fn reencrypt_blob(&self, blob_id: &BlobId) -> Result<bool> {
+        let mut stored = self
+            .blob_store
+            .open_read(blob_id)
+            .await
+            .context("failed to read encrypted blob from storage")?;
+
+        let aad = aad::for_blob(blob_id);
+        let (current_epoch, current_key) = self
+            .session
+            .get_current_key()
+            .await
+            .context("encryption session not ready - cannot re-encrypt")?;
+
+        // Both formats are written back as a chunked container; the old one is
+        // decrypted as it is streamed into the new file.
+        let path = match read_stored_blob(&mut stored).await? {
+            StoredBlob::Chunked(header) => {
+                if header.key_epoch == current_epoch {
+                    return Ok(false);
+                }
+                let old_key = self
+                    .session
+                    .get_master_key_for_epoch(header.key_epoch)
+                    .await
+                    .context("encryption session not ready - cannot re-encrypt")?;
+                let plaintext = DecryptingReader::new(stored, &old_key, &header, aad.clone())
+                    .context("failed to decrypt payload with its epoch key")?;
+                let mut resealed =
+                    EncryptingReader::new(plaintext, &current_key, current_epoch, aad)
+                        .context("failed to re-encrypt payload")?;
+                self.blob_store.put_stream(blob_id, &mut resealed).await?
+            }
+            StoredBlob::Legacy(legacy) => {
+                if legacy.key_epoch == current_epoch {
+                    return Ok(false);
+                }
+                let old_key = self
+                    .session
+                    .get_master_key_for_epoch(legacy.key_epoch)
+                    .await
+                    .context("encryption session not ready - cannot re-encrypt")?;
+                let plaintext = self
+                    .encryption
+                    .decrypt_blob(&old_key, &legacy, &aad)
+                    .await
+                    .context("failed to decrypt payload with its epoch key")?;
+                let mut resealed =
+                    EncryptingReader::new(plaintext.as_slice(), &current_key, current_epoch, aad)
+                        .context("failed to re-encrypt payload")?;
+                self.blob_store.put_stream(blob_id, &mut resealed).await?
+            }
+        };
+
+        debug!("Re-encrypted blob {} at {:?}", blob_id.as_ref(), path);
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use std::sync::Mutex;
+    use uc_core::clipboard::{PayloadAvailability, PersistedClipboardRepresentation};
+    use uc_core::ports::clipboard::ProcessingUpdateOutcome;
+    use uc_core::security::model::MasterKey;
+    use uc_core::{Blob, ContentHash};
+
+    use crate::security::{EncryptionRepository, InMemoryEncryptionSession};
+
+    #[derive(Default)]
+    struct MemRepresentationRepo {
+        inline: Mutex<HashMap<RepresentationId, Vec<u8>>>,
+    }
+
+    #[async_trait]
+    impl ClipboardRepresentationRepositoryPort for MemRepresentationRepo {
+        async fn get_representation(
+            &self,
+            _event_id: &EventId,
+            representation_id: &RepresentationId,
+        ) -> Result<Option<PersistedClipboardRepresentation>> {
+            Ok(self
+                .inline
+                .lock()
+                .unwrap()
+                .get(representation_id)
+                .map(|data| {
+                    PersistedClipboardRepresentation::new(
+                        representation_id.clone(),
+                        "public.text".into(),
+                        None,
+                        data.len() as i64,
+                        Some(data.clone()),
+                        None,
+                    )
+                }))
+        }
+
+        async fn get_representation_by_id(
+            &self,
+            _representation_id: &RepresentationId,
+        ) -> Result<Option<PersistedClipboardRepresentation>> {
+            unimplemented!()
+        }
+
+        async fn get_representation_by_blob_id(
+            &self,
+            _blob_id: &BlobId,
+        ) -> Result<Option<PersistedClipboardRepresentation>> {
+            unimplemented!()
+        }
+
+        async fn update_blob_id(
+            &self,
+            _representation_id: &RepresentationId,
+            _blob_id: &BlobId,
+        ) -> Result<()> {
+            unimplemented!()
+        }
+
+        async fn update_blob_id_if_none(
+            &self,
+            _representation_id: &RepresentationId,
+            _blob_id: &BlobId,
+        ) -> Result<bool> {
+            unimplemented!()
+        }
+
+        async fn update_processing_result(
+            &self,
+            _rep_id: &RepresentationId,
+            _expected_states: &[PayloadAvailability],
+            _blob_id: Option<&BlobId>,
+            _new_state: PayloadAvailability,
+            _last_error: Option<&str>,
+        ) -> Result<ProcessingUpdateOutcome> {
+            unimplemented!()
+        }
+
+        async fn update_inline_data(
+            &self,
+            representation_id: &RepresentationId,
+            inline_data: &[u8],
+        ) -> Result<()> {
+            self.inline
+                .lock()
+                .unwrap()
+                .insert(representation_id.clone(), inline_data.to_vec());
+            Ok(())
+        }
+
+        async fn count_inline_representations(&self) -> Result<usize> {
+            Ok(self.inline.lock().unwrap().len())
+        }
+
+        async fn list_inline_representation_ids(
+            &self,
+            _after: Option<&RepresentationId>,
+            _limit: usize,
+        ) -> Result<Vec<(EventId, RepresentationId)>> {
+            unimplemented!()
+        }
+    }
+
+    struct NoBlobs;
+
+    #[async_trait]
+    impl BlobRepositoryPort for NoBlobs {
+        async fn insert_blob(&self, _blob: &Blob) -> Result<()> {
+            Ok(())
+        }
+
+        async fn find_by_hash(&self, _content_hash: &ContentHash) -> Result<Option<Blob>> {
+            Ok(None)
+        }
+
+        async fn count_blobs(&self) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_blob_ids(
+            &self,
+            _after: Option<&uc_core::BlobId>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<uc_core::BlobId>> {
+            Ok(vec![])
+        }
+
+        async fn list_unreferenced_blobs(
+            &self,
+            _created_before_ms: i64,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<uc_core::Blob>> {
+            Ok(vec![])
+        }
+
+        async fn touch_blob(
+            &self,
+            _blob_id: &uc_core::BlobId,
+            _now_ms: i64,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn delete_unreferenced_blob(
+            &self,
+            _blob_id: &uc_core::BlobId,
+            _created_before_ms: i64,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+    }
+
+    #[derive(Default)]
+    struct MemBlobStore {
+        storage: Mutex<HashMap<BlobId, Vec<u8>>>,
+    }
+
+    #[async_trait]
+    impl BlobStorePort for MemBlobStore {
+        async fn put(&self, blob_id: &BlobId, data: &[u8]) -> Result<PathBuf> {
+            self.storage
+                .lock()
+                .unwrap()
+                .insert(blob_id.clone(), data.to_vec());
+            Ok(PathBuf::from(blob_id.as_ref()))
+        }
+
+        async fn get(&self, blob_id: &BlobId) -> Result<Vec<u8>> {
+            self.storage
+                .lock()
+                .unwrap()
+                .get(blob_id)
+                .cloned()
+                .context("blob not found")
+        }
+
+        async fn put_stream(
+            &self,
+            blob_id: &uc_core::BlobId,
+            reader: &mut (dyn tokio::io::AsyncRead + Send + Unpin),
+        ) -> anyhow::Result<std::path::PathBuf> {
+            let mut data = Vec::new();
+            tokio::io::AsyncReadExt::read_to_end(reader, &mut data).await?;
+            self.put(blob_id, &data).await
+        }
+
+        async fn open_read(
+            &self,
+            blob_id: &uc_core::BlobId,
+        ) -> anyhow::Result<uc_core::ports::BlobReader> {
+            Ok(Box::new(std::io::Cursor::new(self.get(blob_id).await?)))
+        }
+
+        async fn delete(&self, _blob_id: &uc_core::BlobId) -> anyhow::Result<u64> {
+            Ok(0)
+        }
+
+        async fn list_objects(&self) -> anyhow::Result<Vec<uc_core::ports::StoredBlobObject>> {
+            Ok(Vec::new())
+        }
+    }
+
+    async fn seal(key: &MasterKey, epoch: u32, plaintext: &[u8], aad: &[u8]) -> Vec<u8> {
+        let mut blob = EncryptionRepository
+            .encrypt_blob(key, plaintext, aad, EncryptionAlgo::XChaCha20Poly1305)
+            .await
+            .unwrap();
+        blob.key_epoch = epoch;
+        serde_json::to_vec(&blob).unwrap()
+    }
+
+    async fn open(key: &MasterKey, bytes: &[u8], aad: &[u8]) -> (u32, Vec<u8>) {
+        let blob: EncryptedBlob = serde_json::from_slice(bytes).unwrap();
+        let plaintext = EncryptionRepository
+            .decrypt_blob(key, &blob, aad)
+            .await
+            .unwrap();
+        (blob.key_epoch, plaintext)
+    }
+
+    async fn open_chunked(key: &MasterKey, bytes: &[u8], aad: &[u8]) -> (u32, Vec<u8>) {
+        use tokio::io::AsyncReadExt;
+
+        let mut input = bytes;
+        let StoredBlob::Chunked(header) = read_stored_blob(&mut input).await.unwrap() else {
+            panic!("expected chunked blob");
+        };
+        let mut reader = DecryptingReader::new(input, key, &header, aad.to_vec()).unwrap();
+        let mut plaintext = Vec::new();
+        reader.read_to_end(&mut plaintext).await.unwrap();
+        (header.key_epoch, plaintext)
+    }
+
+    #[tokio::test]
+    async fn reencrypts_previous_epoch_payloads_once() {
+        let old_key = MasterKey([1u8; 32]);
+        let new_key = MasterKey([2u8; 32]);
+        let session = Arc::new(InMemoryEncryptionSession::new());
+        session
+            .set_epoch_keys(1, new_key.clone(), Some(old_key.clone()))
+            .await
+            .unwrap();
+
+        let event_id = EventId::from("event-1");
+        let rep_id = RepresentationId::from("rep-1");
+        let blob_id = BlobId::from("blob-1");
+        let inline_aad = aad::for_inline(&event_id, &rep_id);
+        let blob_aad = aad::for_blob(&blob_id);
+
+        let representation_repo = Arc::new(MemRepresentationRepo::default());
+        let sealed_inline = seal(&old_key, 0, b"inline", &inline_aad).await;
+        representation_repo
+            .inline
+            .lock()
+            .unwrap()
+            .insert(rep_id.clone(), sealed_inline);
+        let blob_store = Arc::new(MemBlobStore::default());
+        blob_store
+            .put(&blob_id, &seal(&old_key, 0, b"blob", &blob_aad).await)
+            .await
+            .unwrap();
+
+        let reencryptor = PayloadReencryptor::new(
+            representation_repo.clone(),
+            Arc::new(NoBlobs),
+            blob_store.clone(),
+            Arc::new(EncryptionRepository),
+            session,
+        );
+
+        assert!(reencryptor
+            .reencrypt_inline(&event_id, &rep_id)
+            .await
+            .unwrap());
+        assert!(reencryptor.reencrypt_blob(&blob_id).await.unwrap());
+
+        let inline = representation_repo.inline.lock().unwrap()[&rep_id].clone();
+        assert_eq!(
+            open(&new_key, &inline, &inline_aad).await,
+            (1, b"inline".to_vec())
+        );
+        // The legacy JSON blob comes back as a chunked container.
+        let blob = blob_store.get(&blob_id).await.unwrap();
+        assert_eq!(
+            open_chunked(&new_key, &blob, &blob_aad).await,
+            (1, b"blob".to_vec())
+        );
+
+        // A second pass leaves current-epoch payloads untouched.
+        assert!(!reencryptor
+            .reencrypt_inline(&event_id, &rep_id)
+            .await
+            .unwrap());
+        assert!(!reencryptor.reencrypt_blob(&blob_id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn reencrypts_chunked_blobs() {
+        let old_key = MasterKey([1u8; 32]);
+        let new_key = MasterKey([2u8; 32]);
+        let session = Arc::new(InMemoryEncryptionSession::new());
+        session.set_master_key(old_key.clone()).await.unwrap();
+
+        let blob_id = BlobId::from("blob-1");
+        let blob_aad = aad::for_blob(&blob_id);
+        let raw_store = Arc::new(MemBlobStore::default());
+        let sealed_store = crate::security::EncryptedBlobStore::new(
+            raw_store.clone(),
+            Arc::new(EncryptionRepository),
+            session.clone(),
+        );
+        let data = vec![9u8; 200 * 1024];
+        sealed_store.put(&blob_id, &data).await.unwrap();
+
+        session
+            .set_epoch_keys(1, new_key.clone(), Some(old_key))
+            .await
+            .unwrap();
+        let reencryptor = PayloadReencryptor::new(
+            Arc::new(MemRepresentationRepo::default()),
+            Arc::new(NoBlobs),
+            raw_store.clone(),
+            Arc::new(EncryptionRepository),
+            session,
+        );
+
+        assert!(reencryptor.reencrypt_blob(&blob_id).await.unwrap());
+        assert!(!reencryptor.reencrypt_blob(&blob_id).await.unwrap());
+
+        let blob = raw_store.get(&blob_id).await.unwrap();
+        assert_eq!(open_chunked(&new_key, &blob, &blob_aad).await, (1, data));
+    }
+}
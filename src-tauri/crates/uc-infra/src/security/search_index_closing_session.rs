@@ -0,0 +1,103 @@
+//! Encryption session decorator that closes the search index on clear
+//! 清除加密会话时一并关闭全文检索索引的装饰器
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use tracing::warn;
+
+use uc_core::ports::clipboard::ClipboardSearchIndexPort;
+use uc_core::ports::EncryptionSessionPort;
+use uc_core::security::model::{EncryptionError, KeyEpoch, MasterKey};
+
+/// Forwards to the wrapped session. Whenever the master key is cleared, the
+/// search index is closed as well, so no decrypted text stays searchable
+/// while the session is locked.
+///
+/// 会话被清除（锁定、切换空间）时关闭检索索引，锁定期间内存中不保留可检索的明文。
+pub struct SearchIndexClosingSession {
+    inner: Arc<dyn EncryptionSessionPort>,
+    search_index: Arc<dyn ClipboardSearchIndexPort>,
+}
+
+impl SearchIndexClosingSession {
+    pub fn new(
+        inner: Arc<dyn EncryptionSessionPort>,
+        search_index: Arc<dyn ClipboardSearchIndexPort>,
+    ) -> Self {
+        Self {
+            inner,
+            search_index,
+        }
+    }
+}
+
+#[async_trait]
+impl EncryptionSessionPort for SearchIndexClosingSession {
+    async fn is_ready(&self) -> bool {
+        self.inner.is_ready().await
+    }
+
+    async fn get_master_key(&self) -> Result<MasterKey, EncryptionError> {
+        self.inner.get_master_key().await
+    }
+
+    async fn set_master_key(&self, master_key: MasterKey) -> Result<(), EncryptionError> {
+        self.inner.set_master_key(master_key).await
+    }
+
+    async fn clear(&self) -> Result<(), EncryptionError> {
+        self.inner.clear().await?;
+        if self.search_index.is_open().await {
+            if let Err(err) = self.search_index.close().await {
+                warn!(error = %err, "Failed to close clipboard search index");
+            }
+        }
+        Ok(())
+    }
+
+    async fn get_current_key(&self) -> Result<(KeyEpoch, MasterKey), EncryptionError> {
+        self.inner.get_current_key().await
+    }
+
+    async fn get_master_key_for_epoch(
+        &self,
+        epoch: KeyEpoch,
+    ) -> Result<MasterKey, EncryptionError> {
+        self.inner.get_master_key_for_epoch(epoch).await
+    }
+
+    async fn set_epoch_keys(
+        &self,
+        epoch: KeyEpoch,
+        master_key: MasterKey,
+        older: Vec<(KeyEpoch, MasterKey)>,
+    ) -> Result<(), EncryptionError> {
+        self.inner.set_epoch_keys(epoch, master_key, older).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clipboard::InMemoryClipboardSearchIndex;
+    use crate::security::InMemoryEncryptionSession;
+
+    #[tokio::test]
+    async fn clear_closes_open_search_index() {
+        let search_index = Arc::new(InMemoryClipboardSearchIndex::new());
+        let session = SearchIndexClosingSession::new(
+            Arc::new(InMemoryEncryptionSession::new()),
+            search_index.clone(),
+        );
+        session
+            .set_master_key(MasterKey([7u8; 32]))
+            .await
+            .expect("set master key");
+        search_index.open().await.expect("open index");
+
+        session.clear().await.expect("clear session");
+
+        assert!(!session.is_ready().await);
+        assert!(!search_index.is_open().await);
+    }
+}
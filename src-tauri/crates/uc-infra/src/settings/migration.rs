@@ -1,5 +1,5 @@
 use uc_core::ports::SettingsMigrationPort;
-use uc_core::settings::model::{Settings, CURRENT_SCHEMA_VERSION};
+use uc_core::settings::model::{ContentTypes, Settings, CURRENT_SCHEMA_VERSION};
 
 /// Error type for settings migration failures.
 #[derive(thiserror::Error, Debug)]
@@ -17,6 +17,33 @@ pub enum MigrationError {
     MaxIterationsExceeded { iterations: u32 },
 }
 
+/// v1 → v2: `sync.content_types` becomes an enforced allow-list.
+///
+/// v1 never honored the switches, and files written by it often carry an
+/// all-off mask. From v2 an empty mask means "sync nothing", so such files are
+/// switched to every kind to keep syncing as before.
+///
+/// v1 未生效的类型开关在 v2 中开始生效；全部关闭的旧配置迁移为全部开启，避免静默停止同步。
+pub struct MigrationV1ToV2;
+
+impl SettingsMigrationPort for MigrationV1ToV2 {
+    fn from_version(&self) -> u32 {
+        1
+    }
+
+    fn to_version(&self) -> u32 {
+        2
+    }
+
+    fn migrate(&self, mut settings: Settings) -> Settings {
+        if settings.sync.content_types.is_empty() {
+            settings.sync.content_types = ContentTypes::all();
+        }
+        settings.schema_version = self.to_version();
+        settings
+    }
+}
+
 pub struct SettingsMigrator {
     migrations: Vec<Box<dyn SettingsMigrationPort>>,
 }
@@ -24,8 +51,7 @@ pub struct SettingsMigrator {
 impl SettingsMigrator {
     /// Creates a new `SettingsMigrator` with its migrations list initialized.
     ///
-    /// The migrations vector is currently empty with placeholder comments where future
-    /// migrations can be added.
+    /// Migrations are listed in schema order; add new ones after the last entry.
     ///
     /// # Examples
     ///
@@ -35,10 +61,7 @@ impl SettingsMigrator {
     /// ```
     pub fn new() -> Self {
         Self {
-            migrations: vec![
-                // Box::new(MigrationV1ToV2),
-                // Box::new(MigrationV2ToV3),
-            ],
+            migrations: vec![Box::new(MigrationV1ToV2)],
         }
     }
 
@@ -100,3 +123,48 @@ impl SettingsMigrator {
         Ok(settings)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v1_settings(content_types: ContentTypes) -> Settings {
+        let mut settings = Settings::default();
+        settings.schema_version = 1;
+        settings.sync.content_types = content_types;
+        settings
+    }
+
+    #[test]
+    fn v1_all_off_mask_migrates_to_all_on() {
+        let migrated = SettingsMigrator::new()
+            .migrate_to_latest(v1_settings(ContentTypes::default()))
+            .unwrap();
+
+        assert_eq!(migrated.schema_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(migrated.sync.content_types, ContentTypes::all());
+    }
+
+    #[test]
+    fn v1_partial_mask_is_kept() {
+        let text_only = ContentTypes {
+            text: true,
+            ..ContentTypes::default()
+        };
+        let migrated = SettingsMigrator::new()
+            .migrate_to_latest(v1_settings(text_only.clone()))
+            .unwrap();
+
+        assert_eq!(migrated.sync.content_types, text_only);
+    }
+
+    #[test]
+    fn current_all_off_mask_is_not_touched() {
+        let mut settings = Settings::default();
+        settings.sync.content_types = ContentTypes::default();
+
+        let migrated = SettingsMigrator::new().migrate_to_latest(settings).unwrap();
+
+        assert!(migrated.sync.content_types.is_empty());
+    }
+}
@@ -0,0 +1,44 @@
+//! In-memory active space holder
+//! 内存中的活动空间
+
+use std::sync::RwLock;
+
+use uc_core::ids::SpaceId;
+use uc_core::ports::space::ActiveSpacePort;
+
+/// Active space shared by repositories and the key scope.
+///
+/// Seeded from settings at startup; the switch use case persists changes.
+pub struct InMemoryActiveSpace {
+    current: RwLock<SpaceId>,
+}
+
+impl InMemoryActiveSpace {
+    pub fn new(space_id: SpaceId) -> Self {
+        Self {
+            current: RwLock::new(space_id),
+        }
+    }
+}
+
+impl Default for InMemoryActiveSpace {
+    fn default() -> Self {
+        Self::new(SpaceId::default_space())
+    }
+}
+
+impl ActiveSpacePort for InMemoryActiveSpace {
+    fn active_space(&self) -> SpaceId {
+        self.current
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    fn set_active_space(&self, space_id: SpaceId) {
+        *self
+            .current
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = space_id;
+    }
+}
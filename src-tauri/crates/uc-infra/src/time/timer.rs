@@ -48,6 +48,11 @@ impl TimerPort for Timer {
         }
         Ok(())
     }
+
+    async fn sleep(&mut self, duration: Duration) -> anyhow::Result<()> {
+        sleep(duration).await;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -4,9 +4,13 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use std::path::PathBuf;
-use uc_core::ports::BlobStorePort;
+use tokio::io::AsyncRead;
+use uc_core::ports::{BlobReader, BlobStorePort, StoredBlobObject};
 use uc_core::BlobId;
 
+/// Suffix of the temp file a blob is written to before it is renamed into place.
+const TMP_SUFFIX: &str = ".tmp";
+
 /// Filesystem-based blob storage
 /// 基于文件系统的 blob 存储
 pub struct FilesystemBlobStore {
@@ -38,31 +42,118 @@ impl FilesystemBlobStore {
 #[async_trait::async_trait]
 impl BlobStorePort for FilesystemBlobStore {
     async fn put(&self, blob_id: &BlobId, data: &[u8]) -> Result<PathBuf> {
+        let mut reader = data;
+        self.put_stream(blob_id, &mut reader).await
+    }
+
+    async fn get(&self, blob_id: &BlobId) -> Result<Vec<u8>> {
+        let path = self.blob_path(blob_id);
+        let mut file = tokio::fs::File::open(&path)
+            .await
+            .context("Failed to open blob file")?;
+
+        let mut data = Vec::new();
+        tokio::io::AsyncReadExt::read_to_end(&mut file, &mut data)
+            .await
+            .context("Failed to read blob data")?;
+
+        Ok(data)
+    }
+
+    async fn put_stream(
+        &self,
+        blob_id: &BlobId,
+        reader: &mut (dyn AsyncRead + Send + Unpin),
+    ) -> Result<PathBuf> {
         self.ensure_dir().await?;
         let path = self.blob_path(blob_id);
+        // Write to a temp file and rename so that overwriting an existing blob
+        // (e.g. re-encryption after key rotation) never leaves a torn file.
+        // 先写临时文件再重命名，覆盖已有 blob 时不会留下半写入的文件。
+        let tmp_path = self
+            .base_dir
+            .join(format!("{}{}", blob_id.as_str(), TMP_SUFFIX));
 
-        let mut file = tokio::fs::File::create(&path)
+        let mut file = tokio::fs::File::create(&tmp_path)
             .await
             .context("Failed to create blob file")?;
-        tokio::io::AsyncWriteExt::write_all(&mut file, data)
+        if let Err(err) = tokio::io::copy(reader, &mut file).await {
+            drop(file);
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+            return Err(anyhow::Error::new(err).context("Failed to write blob data"));
+        }
+        file.sync_all().await.context("Failed to flush blob data")?;
+        drop(file);
+
+        tokio::fs::rename(&tmp_path, &path)
             .await
-            .context("Failed to write blob data")?;
+            .context("Failed to move blob file into place")?;
 
         Ok(path)
     }
 
-    async fn get(&self, blob_id: &BlobId) -> Result<Vec<u8>> {
+    async fn open_read(&self, blob_id: &BlobId) -> Result<BlobReader> {
         let path = self.blob_path(blob_id);
-        let mut file = tokio::fs::File::open(&path)
+        let file = tokio::fs::File::open(&path)
             .await
             .context("Failed to open blob file")?;
+        Ok(Box::new(tokio::io::BufReader::new(file)))
+    }
 
-        let mut data = Vec::new();
-        tokio::io::AsyncReadExt::read_to_end(&mut file, &mut data)
-            .await
-            .context("Failed to read blob data")?;
+    async fn delete(&self, blob_id: &BlobId) -> Result<u64> {
+        let path = self.blob_path(blob_id);
+        let size = match tokio::fs::metadata(&path).await {
+            Ok(metadata) => metadata.len(),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(err) => return Err(anyhow::Error::new(err).context("Failed to stat blob file")),
+        };
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(size),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(0),
+            Err(err) => Err(anyhow::Error::new(err).context("Failed to delete blob file")),
+        }
+    }
 
-        Ok(data)
+    async fn list_objects(&self) -> Result<Vec<StoredBlobObject>> {
+        let mut entries = match tokio::fs::read_dir(&self.base_dir).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => {
+                return Err(anyhow::Error::new(err).context("Failed to list blob directory"))
+            }
+        };
+
+        let mut objects = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .context("Failed to list blob directory")?
+        {
+            let metadata = entry.metadata().await.context("Failed to stat blob file")?;
+            if !metadata.is_file() {
+                continue;
+            }
+            let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            // In-flight writes are not blobs yet; reporting them would let
+            // garbage collection delete a file that is still being written.
+            if name.ends_with(TMP_SUFFIX) {
+                continue;
+            }
+            let modified_at_ms = metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|duration| duration.as_millis() as i64)
+                .unwrap_or(0);
+            objects.push(StoredBlobObject {
+                blob_id: BlobId::from(name),
+                size_bytes: metadata.len(),
+                modified_at_ms,
+            });
+        }
+        Ok(objects)
     }
 }
 
@@ -80,6 +171,26 @@ impl BlobStorePort for PlaceholderBlobStorePort {
     async fn get(&self, _blob_id: &BlobId) -> Result<Vec<u8>> {
         Err(anyhow::anyhow!("BlobStorePort not implemented yet"))
     }
+
+    async fn put_stream(
+        &self,
+        _blob_id: &BlobId,
+        _reader: &mut (dyn AsyncRead + Send + Unpin),
+    ) -> Result<PathBuf> {
+        Err(anyhow::anyhow!("BlobStorePort not implemented yet"))
+    }
+
+    async fn open_read(&self, _blob_id: &BlobId) -> Result<BlobReader> {
+        Err(anyhow::anyhow!("BlobStorePort not implemented yet"))
+    }
+
+    async fn delete(&self, _blob_id: &BlobId) -> Result<u64> {
+        Err(anyhow::anyhow!("BlobStorePort not implemented yet"))
+    }
+
+    async fn list_objects(&self) -> Result<Vec<StoredBlobObject>> {
+        Err(anyhow::anyhow!("BlobStorePort not implemented yet"))
+    }
 }
 
 #[cfg(test)]
@@ -106,4 +217,69 @@ mod tests {
         assert!(blob_dir.exists());
         assert!(blob_dir.is_dir());
     }
+
+    #[tokio::test]
+    async fn test_put_overwrites_existing_blob() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let store = FilesystemBlobStore::new(temp_dir.path().join("blobs"));
+        let blob_id = BlobId::from("blob-1");
+
+        store.put(&blob_id, b"first").await.unwrap();
+        store.put(&blob_id, b"second").await.unwrap();
+
+        assert_eq!(store.get(&blob_id).await.unwrap(), b"second");
+        assert!(!temp_dir.path().join("blobs").join("blob-1.tmp").exists());
+    }
+
+    #[tokio::test]
+    async fn test_failed_stream_keeps_previous_blob() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let store = FilesystemBlobStore::new(temp_dir.path().join("blobs"));
+        let blob_id = BlobId::from("blob-1");
+        store.put(&blob_id, b"first").await.unwrap();
+
+        let mut failing = ErrorReader;
+        assert!(store.put_stream(&blob_id, &mut failing).await.is_err());
+
+        assert_eq!(store.get(&blob_id).await.unwrap(), b"first");
+        assert!(!temp_dir.path().join("blobs").join("blob-1.tmp").exists());
+    }
+
+    #[tokio::test]
+    async fn test_delete_and_list_objects() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let store = FilesystemBlobStore::new(temp_dir.path().join("blobs"));
+        assert!(store.list_objects().await.unwrap().is_empty());
+
+        store.put(&BlobId::from("blob-1"), b"12345").await.unwrap();
+        store.put(&BlobId::from("blob-2"), b"12").await.unwrap();
+
+        let mut objects = store.list_objects().await.unwrap();
+        objects.sort_by(|a, b| a.blob_id.as_str().cmp(b.blob_id.as_str()));
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[0].blob_id, BlobId::from("blob-1"));
+        assert_eq!(objects[0].size_bytes, 5);
+        assert!(objects[0].modified_at_ms > 0);
+
+        tokio::fs::write(temp_dir.path().join("blobs").join("blob-3.tmp"), b"partial")
+            .await
+            .unwrap();
+        assert_eq!(store.list_objects().await.unwrap().len(), 2);
+
+        assert_eq!(store.delete(&BlobId::from("blob-1")).await.unwrap(), 5);
+        assert_eq!(store.delete(&BlobId::from("blob-1")).await.unwrap(), 0);
+        assert_eq!(store.list_objects().await.unwrap().len(), 1);
+    }
+
+    struct ErrorReader;
+
+    impl AsyncRead for ErrorReader {
+        fn poll_read(
+            self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+            _buf: &mut tokio::io::ReadBuf<'_>,
+        ) -> std::task::Poll<std::io::Result<()>> {
+            std::task::Poll::Ready(Err(std::io::Error::other("source failed")))
+        }
+    }
 }
@@ -2,10 +2,11 @@
 //! 内存加密会话端口实现
 
 use async_trait::async_trait;
+use std::collections::BTreeMap;
 use std::sync::{Arc, Mutex};
 use tracing::{debug, debug_span};
 use uc_core::ports::EncryptionSessionPort;
-use uc_core::security::model::{EncryptionError, MasterKey};
+use uc_core::security::model::{EncryptionError, KeyEpoch, MasterKey};
 
 #[async_trait]
 impl EncryptionSessionPort for InMemoryEncryptionSessionPort {
@@ -30,6 +31,8 @@ impl EncryptionSessionPort for InMemoryEncryptionSessionPort {
             // Replace old key - MasterKey will be dropped and zeroized automatically
             // 替换旧密钥 - MasterKey 将被丢弃并自动零化
             state.master_key = Some(master_key);
+            state.epoch = 0;
+            state.older_master_keys.clear();
             debug!("Master key set successfully");
             Ok(())
         })
@@ -42,10 +45,59 @@ impl EncryptionSessionPort for InMemoryEncryptionSessionPort {
             // Drop old key - MasterKey will be zeroized automatically
             // 丢弃旧密钥 - MasterKey 将自动零化
             state.master_key = None;
+            state.older_master_keys.clear();
             debug!("Master key cleared");
             Ok(())
         })
     }
+
+    async fn get_current_key(&self) -> Result<(KeyEpoch, MasterKey), EncryptionError> {
+        let state = self.state.lock().expect("lock state");
+        let master_key = state
+            .master_key
+            .as_ref()
+            .cloned()
+            .ok_or(EncryptionError::NotInitialized)?;
+        Ok((state.epoch, master_key))
+    }
+
+    async fn get_master_key_for_epoch(
+        &self,
+        epoch: KeyEpoch,
+    ) -> Result<MasterKey, EncryptionError> {
+        let state = self.state.lock().expect("lock state");
+        let current = state
+            .master_key
+            .as_ref()
+            .ok_or(EncryptionError::NotInitialized)?;
+        // Data sealed under an older epoch uses the key kept in the keyring
+        // 旧纪元的数据使用密钥环中保留的密钥解密
+        Ok(state
+            .older_master_keys
+            .get(&epoch)
+            .unwrap_or(current)
+            .clone())
+    }
+
+    async fn set_epoch_keys(
+        &self,
+        epoch: KeyEpoch,
+        master_key: MasterKey,
+        older: Vec<(KeyEpoch, MasterKey)>,
+    ) -> Result<(), EncryptionError> {
+        let span = debug_span!("platform.encryption.set_epoch_keys", epoch);
+        span.in_scope(|| {
+            let mut state = self.state.lock().expect("lock state");
+            state.master_key = Some(master_key);
+            state.epoch = epoch;
+            state.older_master_keys = older.into_iter().collect();
+            debug!(
+                older_epochs = state.older_master_keys.len(),
+                "Epoch keys set successfully"
+            );
+            Ok(())
+        })
+    }
 }
 
 /// In-memory encryption session port implementation
@@ -63,7 +115,6 @@ impl EncryptionSessionPort for InMemoryEncryptionSessionPort {
 ///
 /// Future Enhancement (Phase 3+):
 /// - Persist master key to system keyring / 将主密钥持久化到系统密钥环
-/// - Implement key rotation / 实现密钥轮换
 /// - Add session timeout / 添加会话超时
 ///
 /// # Security / 安全性
@@ -82,6 +133,8 @@ pub struct InMemoryEncryptionSessionPort {
 #[derive(Debug)]
 struct EncryptionSessionState {
     master_key: Option<MasterKey>,
+    epoch: KeyEpoch,
+    older_master_keys: BTreeMap<KeyEpoch, MasterKey>,
 }
 
 impl InMemoryEncryptionSessionPort {
@@ -89,7 +142,11 @@ impl InMemoryEncryptionSessionPort {
     /// 创建新的内存加密会话
     pub fn new() -> Self {
         Self {
-            state: Arc::new(Mutex::new(EncryptionSessionState { master_key: None })),
+            state: Arc::new(Mutex::new(EncryptionSessionState {
+                master_key: None,
+                epoch: 0,
+                older_master_keys: BTreeMap::new(),
+            })),
         }
     }
 }
@@ -169,4 +226,32 @@ mod tests {
         let retrieved = session2.get_master_key().await.unwrap();
         assert_eq!(retrieved.as_bytes(), key.as_bytes());
     }
+
+    #[tokio::test]
+    async fn test_encryption_session_resolves_retired_epoch_key() {
+        let session = InMemoryEncryptionSessionPort::new();
+
+        let old_key = MasterKey::generate().unwrap();
+        let new_key = MasterKey::generate().unwrap();
+        session
+            .set_epoch_keys(3, new_key.clone(), vec![(2, old_key.clone())])
+            .await
+            .unwrap();
+
+        let (epoch, current) = session.get_current_key().await.unwrap();
+        assert_eq!(epoch, 3);
+        assert_eq!(current.as_bytes(), new_key.as_bytes());
+        let retired = session.get_master_key_for_epoch(2).await.unwrap();
+        assert_eq!(retired.as_bytes(), old_key.as_bytes());
+        let same = session.get_master_key_for_epoch(3).await.unwrap();
+        assert_eq!(same.as_bytes(), new_key.as_bytes());
+
+        // Finishing the rotation drops the retired key
+        session
+            .set_epoch_keys(3, new_key.clone(), Vec::new())
+            .await
+            .unwrap();
+        let resolved = session.get_master_key_for_epoch(2).await.unwrap();
+        assert_eq!(resolved.as_bytes(), new_key.as_bytes());
+    }
 }
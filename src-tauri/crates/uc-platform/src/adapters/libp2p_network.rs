@@ -3,34 +3,57 @@ use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use libp2p::{
     core::ConnectedPoint,
+    dcutr,
     futures::{AsyncReadExt, AsyncWriteExt, StreamExt},
-    identity, mdns, noise,
-    swarm::{NetworkBehaviour, Swarm, SwarmEvent},
+    identify, identity, mdns,
+    multiaddr::Protocol,
+    noise, relay, rendezvous,
+    swarm::{
+        behaviour::toggle::Toggle,
+        dial_opts::{DialOpts, PeerCondition},
+        ConnectionId, ListenerId, NetworkBehaviour, Swarm, SwarmEvent,
+    },
     tcp, yamux, Multiaddr, PeerId, StreamProtocol, SwarmBuilder,
 };
 use libp2p_stream as stream;
 use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicU8, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::sync::{mpsc, RwLock};
 use tracing::{debug, error, info, warn};
 use uc_core::network::{
-    ClipboardMessage, ConnectedPeer, DeviceAnnounceMessage, DiscoveredPeer, NetworkEvent,
-    PairingMessage, PairingState, ProtocolDenyReason, ProtocolDirection, ProtocolId, ProtocolKind,
-    ProtocolMessage, ResolvedConnectionPolicy,
+    BlobManifest, ClipboardMessage, ConnectedPeer, DeviceAnnounceMessage, DiscoveredPeer,
+    HeartbeatMessage, HelloMessage, IdentityCheck, LocalNetworkInfo, NetworkEvent,
+    NetworkInterfaceInfo, PairingMessage, PairingRevoke, PairingState, PeerCapabilities,
+    PeerLiveness, ProtocolDenyReason, ProtocolDirection, ProtocolFeature, ProtocolId, ProtocolKind,
+    ProtocolMessage, ResolvedConnectionPolicy, TransportKind, HEARTBEAT_INTERVAL,
 };
 use uc_core::ports::{
-    ConnectionPolicyResolverPort, IdentityStorePort, NetworkControlPort, NetworkPort,
+    ConnectionPolicyResolverPort, EncryptionSessionPort, IdentityStorePort, NetworkControlPort,
+    NetworkPort, SettingsPort,
 };
+use uc_core::security::model::MasterKey;
+use uc_core::settings::model::{NetworkSettings, RelaySettings};
 
 use super::pairing_stream::service::{
     PairingStreamConfig, PairingStreamError, PairingStreamService,
 };
+use super::transfer_stream::service::{TransferConfig, TransferService};
 use crate::identity_store::load_or_create_identity;
+use crate::net_utils::{eligible_listen_ips, list_interface_addresses, InterfaceFilter};
 const BUSINESS_PROTOCOL_ID: &str = ProtocolId::Business.as_str();
 const BUSINESS_PAYLOAD_MAX_BYTES: u64 = 100 * 1024 * 1024;
 const BUSINESS_READ_TIMEOUT: Duration = Duration::from_secs(30);
+/// App version advertised in the hello handshake
+const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Clipboard payloads above this size are offered over the transfer protocol
+/// instead of being inlined in the business message.
+const INLINE_PAYLOAD_MAX_BYTES: usize = 1024 * 1024;
+/// Time for a revocation notice to be flushed before the peer is disconnected.
+const REVOCATION_DISCONNECT_DELAY: Duration = Duration::from_secs(1);
 const START_STATE_IDLE: u8 = 0;
 const START_STATE_STARTING: u8 = 1;
 const START_STATE_STARTED: u8 = 2;
@@ -45,12 +68,35 @@ enum BusinessCommand {
     AnnounceDeviceName {
         device_name: String,
     },
+    DialAddress {
+        address: Multiaddr,
+    },
+    DialPeer {
+        peer_id: PeerId,
+        addresses: Vec<Multiaddr>,
+    },
+    /// Trusted peer found via rendezvous, dialed through the relay state
+    DialRendezvousPeer {
+        peer_id: PeerId,
+        addresses: Vec<Multiaddr>,
+    },
+    DisconnectPeer {
+        peer_id: PeerId,
+    },
 }
 
 pub struct PeerCaches {
     discovered_peers: HashMap<String, DiscoveredPeer>,
     reachable_peers: HashSet<String>,
     connected_at: HashMap<String, DateTime<Utc>>,
+    /// Open connections per peer and the transport each one uses
+    connections: HashMap<String, HashMap<ConnectionId, TransportKind>>,
+    /// Latest heartbeat per peer, kept across disconnects
+    liveness: HashMap<String, PeerLiveness>,
+    /// Hello handshake results, cleared when the peer disconnects
+    capabilities: HashMap<String, PeerCapabilities>,
+    /// Peers that share no protocol version with this build
+    incompatible_peers: HashSet<String>,
 }
 
 impl PeerCaches {
@@ -59,6 +105,10 @@ impl PeerCaches {
             discovered_peers: HashMap::new(),
             reachable_peers: HashSet::new(),
             connected_at: HashMap::new(),
+            connections: HashMap::new(),
+            liveness: HashMap::new(),
+            capabilities: HashMap::new(),
+            incompatible_peers: HashSet::new(),
         }
     }
 
@@ -132,6 +182,9 @@ impl PeerCaches {
     pub fn mark_unreachable(&mut self, peer_id: &str) -> bool {
         let removed = self.reachable_peers.remove(peer_id);
         self.connected_at.remove(peer_id);
+        // The peer may come back on another build; negotiate again.
+        self.capabilities.remove(peer_id);
+        self.incompatible_peers.remove(peer_id);
         removed
     }
 
@@ -162,19 +215,107 @@ impl PeerCaches {
     pub fn is_reachable(&self, peer_id: &str) -> bool {
         self.reachable_peers.contains(peer_id)
     }
+
+    pub fn add_connection(
+        &mut self,
+        peer_id: &str,
+        connection_id: ConnectionId,
+        transport: TransportKind,
+    ) {
+        self.connections
+            .entry(peer_id.to_string())
+            .or_default()
+            .insert(connection_id, transport);
+    }
+
+    pub fn remove_connection(&mut self, peer_id: &str, connection_id: ConnectionId) {
+        if let Some(connections) = self.connections.get_mut(peer_id) {
+            connections.remove(&connection_id);
+            if connections.is_empty() {
+                self.connections.remove(peer_id);
+            }
+        }
+    }
+
+    /// Record a heartbeat; a probe without RTT keeps the last measured RTT.
+    pub fn record_heartbeat(&mut self, peer_id: &str, seen_at: DateTime<Utc>, rtt_ms: Option<u64>) {
+        let entry = self
+            .liveness
+            .entry(peer_id.to_string())
+            .or_insert_with(|| PeerLiveness {
+                peer_id: peer_id.to_string(),
+                last_heartbeat_at: seen_at,
+                rtt_ms: None,
+            });
+        entry.last_heartbeat_at = entry.last_heartbeat_at.max(seen_at);
+        if rtt_ms.is_some() {
+            entry.rtt_ms = rtt_ms;
+        }
+    }
+
+    /// Store the outcome of a hello handshake; `None` means no common version.
+    pub fn record_negotiation(&mut self, peer_id: &str, capabilities: Option<PeerCapabilities>) {
+        match capabilities {
+            Some(capabilities) => {
+                self.incompatible_peers.remove(peer_id);
+                self.capabilities.insert(peer_id.to_string(), capabilities);
+            }
+            None => {
+                self.capabilities.remove(peer_id);
+                self.incompatible_peers.insert(peer_id.to_string());
+            }
+        }
+    }
+
+    pub fn is_incompatible(&self, peer_id: &str) -> bool {
+        self.incompatible_peers.contains(peer_id)
+    }
+
+    /// Whether `peer_id` lacks `feature`. Peers that have not answered a
+    /// hello (older builds, or not yet negotiated) support no optional
+    /// features.
+    pub fn lacks_feature(&self, peer_id: &str, feature: ProtocolFeature) -> bool {
+        !self
+            .capabilities
+            .get(peer_id)
+            .is_some_and(|capabilities| capabilities.supports(feature))
+    }
+
+    fn needs_hello(&self, peer_id: &str) -> bool {
+        !self.capabilities.contains_key(peer_id) && !self.incompatible_peers.contains(peer_id)
+    }
+
+    /// Transport of the best open connection: QUIC, then TCP, then relayed.
+    pub fn preferred_transport(&self, peer_id: &str) -> Option<TransportKind> {
+        self.connections
+            .get(peer_id)?
+            .values()
+            .copied()
+            .min_by_key(|transport| transport_rank(*transport))
+    }
 }
 
 #[derive(NetworkBehaviour)]
 #[behaviour(out_event = "Libp2pBehaviourEvent")]
 struct Libp2pBehaviour {
-    mdns: mdns::tokio::Behaviour,
+    /// One mDNS responder per IP family; see [`MdnsFamilies`].
+    mdns: Toggle<mdns::tokio::Behaviour>,
+    mdns_v6: Toggle<mdns::tokio::Behaviour>,
     stream: stream::Behaviour,
+    identify: identify::Behaviour,
+    relay_client: relay::client::Behaviour,
+    dcutr: dcutr::Behaviour,
+    rendezvous: rendezvous::client::Behaviour,
 }
 
 #[derive(Debug)]
 enum Libp2pBehaviourEvent {
     Mdns(mdns::Event),
     Stream,
+    Identify(Box<identify::Event>),
+    Relay(relay::client::Event),
+    Dcutr(dcutr::Event),
+    Rendezvous(rendezvous::client::Event),
 }
 
 impl From<mdns::Event> for Libp2pBehaviourEvent {
@@ -183,18 +324,78 @@ impl From<mdns::Event> for Libp2pBehaviourEvent {
     }
 }
 
+impl From<identify::Event> for Libp2pBehaviourEvent {
+    fn from(event: identify::Event) -> Self {
+        Self::Identify(Box::new(event))
+    }
+}
+
+impl From<relay::client::Event> for Libp2pBehaviourEvent {
+    fn from(event: relay::client::Event) -> Self {
+        Self::Relay(event)
+    }
+}
+
+impl From<dcutr::Event> for Libp2pBehaviourEvent {
+    fn from(event: dcutr::Event) -> Self {
+        Self::Dcutr(event)
+    }
+}
+
+impl From<rendezvous::client::Event> for Libp2pBehaviourEvent {
+    fn from(event: rendezvous::client::Event) -> Self {
+        Self::Rendezvous(event)
+    }
+}
+
 impl From<()> for Libp2pBehaviourEvent {
     fn from(_: ()) -> Self {
         Self::Stream
     }
 }
 
-fn build_mdns_config() -> mdns::Config {
+/// IP families mDNS discovery runs on.
+///
+/// A libp2p mDNS behaviour speaks either IPv4 or IPv6 multicast, so one is
+/// started per family that has a listen address. IPv4 stays on when nothing
+/// is eligible yet, matching the listener fallback.
+///
+/// 每个有监听地址的 IP 协议族各启动一个 mDNS 实例；双栈主机同时在 IPv4 与 IPv6 上发现设备。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct MdnsFamilies {
+    ipv4: bool,
+    ipv6: bool,
+}
+
+impl MdnsFamilies {
+    fn for_listen_ips(listen_ips: &[IpAddr]) -> Self {
+        Self {
+            ipv4: listen_ips.is_empty() || listen_ips.iter().any(IpAddr::is_ipv4),
+            ipv6: listen_ips.iter().any(IpAddr::is_ipv6),
+        }
+    }
+}
+
+fn build_mdns_config(enable_ipv6: bool) -> mdns::Config {
     let mut config = mdns::Config::default();
     config.query_interval = Duration::from_secs(5);
+    config.enable_ipv6 = enable_ipv6;
     config
 }
 
+fn build_mdns(
+    enabled: bool,
+    enable_ipv6: bool,
+    local_peer_id: PeerId,
+) -> Result<Toggle<mdns::tokio::Behaviour>> {
+    if !enabled {
+        return Ok(Toggle::from(None));
+    }
+    let mdns = mdns::tokio::Behaviour::new(build_mdns_config(enable_ipv6), local_peer_id)
+        .map_err(|e| anyhow!("failed to create mdns behaviour: {e}"))?;
+    Ok(Toggle::from(Some(mdns)))
+}
+
 fn start_state_name(state: u8) -> &'static str {
     match state {
         START_STATE_IDLE => "idle",
@@ -206,16 +407,46 @@ fn start_state_name(state: u8) -> &'static str {
 }
 
 impl Libp2pBehaviour {
-    fn new(local_peer_id: PeerId) -> Result<Self> {
-        let mdns = mdns::tokio::Behaviour::new(build_mdns_config(), local_peer_id)
-            .map_err(|e| anyhow!("failed to create mdns behaviour: {e}"))?;
+    /// `relay_client` comes from the swarm builder, which wires its transport.
+    /// Relay, DCUtR and rendezvous stay idle until a relay node is configured.
+    fn new(
+        keypair: &identity::Keypair,
+        relay_client: relay::client::Behaviour,
+        mdns_families: MdnsFamilies,
+    ) -> Result<Self> {
+        let local_peer_id = keypair.public().to_peer_id();
+        let mdns = build_mdns(mdns_families.ipv4, false, local_peer_id)?;
+        let mdns_v6 = build_mdns(mdns_families.ipv6, true, local_peer_id)?;
         let stream = stream::Behaviour::new();
-        Ok(Self { mdns, stream })
+        let identify = identify::Behaviour::new(identify::Config::new(
+            IDENTIFY_PROTOCOL_VERSION.to_string(),
+            keypair.public(),
+        ));
+        Ok(Self {
+            mdns,
+            mdns_v6,
+            stream,
+            identify,
+            relay_client,
+            dcutr: dcutr::Behaviour::new(local_peer_id),
+            rendezvous: rendezvous::client::Behaviour::new(keypair.clone()),
+        })
+    }
+
+    /// Adapter for `SwarmBuilder::with_behaviour`, which expects a boxed error.
+    fn build(
+        keypair: &identity::Keypair,
+        relay_client: relay::client::Behaviour,
+        mdns_families: MdnsFamilies,
+    ) -> std::result::Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Self::new(keypair, relay_client, mdns_families).map_err(Into::into)
     }
 }
 
 pub struct Libp2pNetworkAdapter {
     local_peer_id: String,
+    /// Stable device id announced to peers, used by them to look up identity pins
+    local_device_id: String,
     local_identity_pubkey: Vec<u8>,
     caches: Arc<RwLock<PeerCaches>>,
     event_tx: mpsc::Sender<NetworkEvent>,
@@ -227,14 +458,23 @@ pub struct Libp2pNetworkAdapter {
     keypair: Mutex<identity::Keypair>,
     start_state: AtomicU8,
     policy_resolver: Arc<dyn ConnectionPolicyResolverPort>,
+    settings: Arc<dyn SettingsPort>,
+    encryption_session: Arc<dyn EncryptionSessionPort>,
+    listen_addresses: Arc<RwLock<Vec<String>>>,
     stream_control: Mutex<Option<stream::Control>>,
     pairing_service: Mutex<Option<PairingStreamService>>,
+    transfer_service: Mutex<Option<TransferService>>,
+    transfer_spool_dir: PathBuf,
 }
 
 impl Libp2pNetworkAdapter {
     pub fn new(
         identity_store: Arc<dyn IdentityStorePort>,
+        local_device_id: String,
         policy_resolver: Arc<dyn ConnectionPolicyResolverPort>,
+        settings: Arc<dyn SettingsPort>,
+        encryption_session: Arc<dyn EncryptionSessionPort>,
+        transfer_spool_dir: PathBuf,
     ) -> Result<Self> {
         let keypair = load_or_create_identity(identity_store.as_ref())
             .map_err(|e| anyhow!("failed to load libp2p identity: {e}"))?;
@@ -252,6 +492,7 @@ impl Libp2pNetworkAdapter {
 
         Ok(Self {
             local_peer_id,
+            local_device_id,
             local_identity_pubkey,
             caches: Arc::new(RwLock::new(PeerCaches::new())),
             event_tx,
@@ -263,8 +504,13 @@ impl Libp2pNetworkAdapter {
             keypair: Mutex::new(keypair),
             start_state: AtomicU8::new(START_STATE_IDLE),
             policy_resolver,
+            settings,
+            encryption_session,
+            listen_addresses: Arc::new(RwLock::new(Vec::new())),
             stream_control: Mutex::new(None),
             pairing_service,
+            transfer_service: Mutex::new(None),
+            transfer_spool_dir,
         })
     }
 
@@ -272,31 +518,65 @@ impl Libp2pNetworkAdapter {
         self.local_identity_pubkey.clone()
     }
 
-    pub fn spawn_swarm(&self) -> Result<()> {
-        let mdns_config = build_mdns_config();
+    /// Start the swarm, listening on `network.listen_port` (0 lets the OS pick one).
+    ///
+    /// One listener is opened per eligible local address (IPv4 and IPv6, see
+    /// [`InterfaceFilter`]), and the set follows interface changes at runtime.
+    /// TCP and QUIC listen side by side on the same port number unless
+    /// `network.tcp_only` is set. When a peer is connected over both, QUIC is
+    /// the preferred transport.
+    ///
+    /// With a `relay` target the node also reserves a slot on the relay,
+    /// registers there for rendezvous and dials the `Trusted` peers found in
+    /// its space's namespace.
+    pub fn spawn_swarm(&self, network: &NetworkSettings, relay: Option<RelayTarget>) -> Result<()> {
+        let mut listen = ListenState::new(
+            network,
+            self.listen_addresses.clone(),
+            self.settings.clone(),
+        );
+        let listen_ips = listen.desired_ips();
+        let mdns_config = build_mdns_config(false);
+        let mdns_families = MdnsFamilies::for_listen_ips(&listen_ips);
         info!(
             query_interval_secs = mdns_config.query_interval.as_secs(),
             ttl_secs = mdns_config.ttl.as_secs(),
-            enable_ipv6 = mdns_config.enable_ipv6,
+            mdns_ipv4 = mdns_families.ipv4,
+            mdns_ipv6 = mdns_families.ipv6,
             local_peer_id = %self.local_peer_id,
             "preparing libp2p swarm"
         );
         let keypair = self.take_keypair()?;
-        let local_peer_id = PeerId::from(keypair.public());
-        let behaviour = Libp2pBehaviour::new(local_peer_id)
-            .map_err(|e| anyhow!("failed to create libp2p behaviour: {e}"))?;
 
-        let mut swarm = SwarmBuilder::with_existing_identity(keypair)
+        let builder = SwarmBuilder::with_existing_identity(keypair)
             .with_tokio()
             .with_tcp(
                 tcp::Config::default().nodelay(true),
                 noise::Config::new,
                 yamux::Config::default,
             )
-            .map_err(|e| anyhow!("failed to configure tcp transport: {e}"))?
-            .with_behaviour(move |_| behaviour)
-            .map_err(|e| anyhow!("failed to attach libp2p behaviour: {e}"))?
-            .build();
+            .map_err(|e| anyhow!("failed to configure tcp transport: {e}"))?;
+        let mut swarm = if network.tcp_only {
+            info!("QUIC disabled by settings, using TCP only");
+            builder
+                .with_relay_client(noise::Config::new, yamux::Config::default)
+                .map_err(|e| anyhow!("failed to configure relay transport: {e}"))?
+                .with_behaviour(|keypair, relay_client| {
+                    Libp2pBehaviour::build(keypair, relay_client, mdns_families)
+                })
+                .map_err(|e| anyhow!("failed to attach libp2p behaviour: {e}"))?
+                .build()
+        } else {
+            builder
+                .with_quic()
+                .with_relay_client(noise::Config::new, yamux::Config::default)
+                .map_err(|e| anyhow!("failed to configure relay transport: {e}"))?
+                .with_behaviour(|keypair, relay_client| {
+                    Libp2pBehaviour::build(keypair, relay_client, mdns_families)
+                })
+                .map_err(|e| anyhow!("failed to attach libp2p behaviour: {e}"))?
+                .build()
+        };
 
         let stream_control = swarm.behaviour().stream.new_control();
         let pairing_service = PairingStreamService::new(
@@ -319,6 +599,24 @@ impl Libp2pNetworkAdapter {
                 .map_err(|_| anyhow!("pairing service mutex poisoned"))?;
             *guard = Some(pairing_service);
         }
+        let transfer_service = TransferService::new(
+            stream_control.clone(),
+            self.event_tx.clone(),
+            self.policy_resolver.clone(),
+            self.settings.clone(),
+            TransferConfig {
+                spool_dir: self.transfer_spool_dir.clone(),
+                ..TransferConfig::default()
+            },
+        );
+        transfer_service.spawn_accept_loop();
+        {
+            let mut guard = self
+                .transfer_service
+                .lock()
+                .map_err(|_| anyhow!("transfer service mutex poisoned"))?;
+            *guard = Some(transfer_service.clone());
+        }
 
         spawn_business_stream_handler(
             stream_control.clone(),
@@ -326,47 +624,97 @@ impl Libp2pNetworkAdapter {
             self.event_tx.clone(),
             self.clipboard_tx.clone(),
             self.policy_resolver.clone(),
+            self.local_device_id.clone(),
+            Some(transfer_service),
         );
 
-        let listen_ip = match crate::net_utils::get_physical_lan_ip() {
-            Some(ip) => ip.to_string(),
-            None => {
-                warn!(
-                    local_peer_id = %self.local_peer_id,
-                    "no physical LAN IP detected, fallback to 0.0.0.0"
-                );
-                "0.0.0.0".to_string()
-            }
-        };
-        let listen_addr_str = format!("/ip4/{listen_ip}/tcp/0");
-        info!(address = %listen_addr_str, "selected listen address");
-        listen_on_swarm(
-            &mut swarm,
-            listen_addr_str
-                .parse()
-                .map_err(|e| anyhow!("failed to parse listen address: {e}"))?,
-            &self.event_tx,
-        )?;
+        info!(
+            addresses = ?listen_ips,
+            port = network.listen_port,
+            "selected listen addresses"
+        );
+        if listen.sync(&mut swarm, &listen_ips, &self.event_tx) == 0 {
+            return Err(anyhow!("failed to listen on any local address"));
+        }
+        let mut relay =
+            relay.map(|target| RelayState::new(target, self.encryption_session.clone()));
+        if let Some(relay) = relay.as_mut() {
+            relay.connect(&mut swarm);
+        }
 
         let caches = self.caches.clone();
         let event_tx = self.event_tx.clone();
         let policy_resolver = self.policy_resolver.clone();
+        let business_tx = self.business_tx.clone();
         let business_rx = Self::take_receiver(&self.business_rx, "business command")?;
         let local_peer_id = self.local_peer_id.clone();
+        let local_device_id = self.local_device_id.clone();
         tokio::spawn(async move {
             run_swarm(
                 swarm,
                 caches,
                 event_tx,
                 policy_resolver,
+                business_tx,
                 business_rx,
                 local_peer_id,
+                local_device_id,
+                listen,
+                relay,
             )
             .await;
         });
         Ok(())
     }
 
+    fn transfer_service(&self) -> Option<TransferService> {
+        self.transfer_service
+            .lock()
+            .ok()
+            .and_then(|guard| guard.as_ref().cloned())
+    }
+
+    /// Move a large clipboard payload out of the business message.
+    ///
+    /// 大于 [`INLINE_PAYLOAD_MAX_BYTES`] 的剪贴板内容改为通过传输协议提供给
+    /// `peer_id`，业务消息中只携带清单，接收端按需分块拉取。
+    fn offload_large_payload(&self, peer_id: &str, data: Vec<u8>) -> Vec<u8> {
+        if data.len() <= INLINE_PAYLOAD_MAX_BYTES {
+            return data;
+        }
+        let Some(transfer) = self.transfer_service() else {
+            return data;
+        };
+        let mut message = match ProtocolMessage::from_bytes(&data) {
+            Ok(ProtocolMessage::Clipboard(message)) if message.payload_manifest.is_none() => {
+                message
+            }
+            _ => return data,
+        };
+        let manifest = match transfer.offer(&message.encrypted_content, &[peer_id.to_string()]) {
+            Ok(manifest) => manifest,
+            Err(err) => {
+                warn!("failed to spool clipboard payload, sending inline: {err}");
+                return data;
+            }
+        };
+        message.encrypted_content = Vec::new();
+        debug!(
+            message_id = %message.id,
+            total_bytes = manifest.total_bytes,
+            chunk_count = manifest.chunk_count(),
+            "offering clipboard payload over transfer protocol"
+        );
+        message.payload_manifest = Some(manifest);
+        match ProtocolMessage::Clipboard(message).to_bytes() {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                warn!("failed to encode clipboard manifest, sending inline: {err}");
+                data
+            }
+        }
+    }
+
     fn take_keypair(&self) -> Result<identity::Keypair> {
         let guard = self
             .keypair
@@ -392,21 +740,25 @@ impl Libp2pNetworkAdapter {
 impl NetworkPort for Libp2pNetworkAdapter {
     async fn send_clipboard(&self, _peer_id: &str, _encrypted_data: Vec<u8>) -> Result<()> {
         let peer = uc_core::PeerId::from(_peer_id);
+        let chunked = !self
+            .caches
+            .read()
+            .await
+            .lacks_feature(_peer_id, ProtocolFeature::ChunkedTransfer);
+        let data = if chunked {
+            self.offload_large_payload(_peer_id, _encrypted_data)
+        } else {
+            _encrypted_data
+        };
         self.business_tx
             .send(BusinessCommand::SendClipboard {
                 peer_id: peer,
-                data: _encrypted_data,
+                data,
             })
             .await
             .map_err(|err| anyhow!("failed to queue business stream: {err}"))
     }
 
-    async fn broadcast_clipboard(&self, _encrypted_data: Vec<u8>) -> Result<()> {
-        Err(anyhow!(
-            "NetworkPort::broadcast_clipboard not implemented yet"
-        ))
-    }
-
     async fn subscribe_clipboard(&self) -> Result<mpsc::Receiver<ClipboardMessage>> {
         if self.clipboard_tx.is_closed() {
             warn!("clipboard channel sender is closed");
@@ -414,6 +766,28 @@ impl NetworkPort for Libp2pNetworkAdapter {
         Self::take_receiver(&self.clipboard_rx, "clipboard")
     }
 
+    async fn fetch_blob(&self, peer_id: &str, manifest: BlobManifest) -> Result<Vec<u8>> {
+        let transfer = self
+            .transfer_service()
+            .ok_or_else(|| anyhow!("transfer service not initialized"))?;
+        check_business_allowed(
+            &self.policy_resolver,
+            &self.caches,
+            &self.event_tx,
+            peer_id,
+            ProtocolDirection::Outbound,
+        )
+        .await?;
+        transfer.fetch(peer_id, manifest).await
+    }
+
+    async fn offer_blob(&self, bytes: Vec<u8>, peer_ids: &[String]) -> Result<BlobManifest> {
+        let transfer = self
+            .transfer_service()
+            .ok_or_else(|| anyhow!("transfer service not initialized"))?;
+        transfer.offer(&bytes, peer_ids)
+    }
+
     async fn get_discovered_peers(&self) -> Result<Vec<DiscoveredPeer>> {
         let caches = self.caches.read().await;
         let peers: Vec<DiscoveredPeer> = caches.discovered_peers.values().cloned().collect();
@@ -452,6 +826,16 @@ impl NetworkPort for Libp2pNetworkAdapter {
         self.local_peer_id.clone()
     }
 
+    async fn get_peer_liveness(&self) -> Result<Vec<PeerLiveness>> {
+        let caches = self.caches.read().await;
+        Ok(caches.liveness.values().cloned().collect())
+    }
+
+    async fn get_peer_capabilities(&self) -> Result<Vec<PeerCapabilities>> {
+        let caches = self.caches.read().await;
+        Ok(caches.capabilities.values().cloned().collect())
+    }
+
     async fn announce_device_name(&self, device_name: String) -> Result<()> {
         self.business_tx
             .send(BusinessCommand::AnnounceDeviceName { device_name })
@@ -459,6 +843,63 @@ impl NetworkPort for Libp2pNetworkAdapter {
             .map_err(|err| anyhow!("failed to queue device announce: {err}"))
     }
 
+    async fn dial_address(&self, address: String) -> Result<()> {
+        let address = resolve_dial_address(&address).await?;
+        self.business_tx
+            .send(BusinessCommand::DialAddress { address })
+            .await
+            .map_err(|err| anyhow!("failed to queue dial: {err}"))
+    }
+
+    fn parse_peer_address(&self, address: &str) -> Result<String> {
+        Ok(parse_peer_multiaddr(address)?.to_string())
+    }
+
+    async fn dial_peer(&self, peer_id: String, addresses: Vec<String>) -> Result<()> {
+        let peer = peer_id
+            .parse::<PeerId>()
+            .map_err(|err| anyhow!("invalid peer id {peer_id}: {err}"))?;
+        let mut resolved = Vec::with_capacity(addresses.len());
+        for address in addresses {
+            match resolve_dial_address(&address).await {
+                Ok(address) => resolved.push(address),
+                Err(err) => debug!(peer_id = %peer_id, "skip unusable peer address: {err}"),
+            }
+        }
+        if resolved.is_empty() {
+            return Err(anyhow!("no usable address for peer {peer_id}"));
+        }
+        self.business_tx
+            .send(BusinessCommand::DialPeer {
+                peer_id: peer,
+                addresses: resolved,
+            })
+            .await
+            .map_err(|err| anyhow!("failed to queue dial: {err}"))
+    }
+
+    async fn get_local_network_info(&self) -> Result<LocalNetworkInfo> {
+        let filter = match self.settings.load().await {
+            Ok(settings) => InterfaceFilter::from_settings(&settings.network),
+            Err(err) => {
+                warn!(error = %err, "failed to load network settings, using default interface rules");
+                InterfaceFilter::default()
+            }
+        };
+        let interfaces = list_interface_addresses()
+            .into_iter()
+            .map(|(name, ip)| NetworkInterfaceInfo {
+                eligible: filter.is_eligible(&name, ip),
+                name,
+                address: ip.to_string(),
+            })
+            .collect();
+        Ok(LocalNetworkInfo {
+            listen_addresses: self.listen_addresses.read().await.clone(),
+            interfaces,
+        })
+    }
+
     async fn open_pairing_session(&self, peer_id: String, session_id: String) -> Result<()> {
         let service = {
             let guard = self
@@ -519,8 +960,51 @@ impl NetworkPort for Libp2pNetworkAdapter {
         service.close_pairing_session(session_id, reason).await
     }
 
-    async fn unpair_device(&self, _peer_id: String) -> Result<()> {
-        Err(anyhow!("NetworkPort::unpair_device not implemented yet"))
+    /// Tell `peer_id` it was unpaired, then drop our connections to it.
+    ///
+    /// The notice goes over the pairing protocol, which stays open to
+    /// non-trusted peers. Fails when the peer cannot be reached. Either way
+    /// the caller keeps the notice pending until the peer acknowledges it
+    /// and resends it on reconnect.
+    async fn unpair_device(&self, peer_id: String) -> Result<()> {
+        let peer = peer_id
+            .parse::<PeerId>()
+            .map_err(|err| anyhow!("invalid peer id {peer_id}: {err}"))?;
+        let session_id = format!("revoke-{}", uuid::Uuid::new_v4());
+        self.open_pairing_session(peer_id.clone(), session_id.clone())
+            .await?;
+        let notice = PairingMessage::Revoke(PairingRevoke {
+            session_id: session_id.clone(),
+            revoked_at: Utc::now(),
+        });
+        let sent = self
+            .send_pairing_on_session(session_id.clone(), notice)
+            .await;
+        self.close_pairing_session(session_id, Some("revoked".to_string()))
+            .await?;
+        sent?;
+
+        let business_tx = self.business_tx.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(REVOCATION_DISCONNECT_DELAY).await;
+            if let Err(err) = business_tx
+                .send(BusinessCommand::DisconnectPeer { peer_id: peer })
+                .await
+            {
+                warn!("failed to queue disconnect after revocation: {err}");
+            }
+        });
+        if let Err(err) = try_send_event(
+            &self.event_tx,
+            NetworkEvent::PairingRevoked {
+                peer_id,
+                direction: ProtocolDirection::Outbound,
+            },
+            "PairingRevoked",
+        ) {
+            warn!("failed to send PairingRevoked event: {err}");
+        }
+        Ok(())
     }
 
     async fn subscribe_events(&self) -> Result<mpsc::Receiver<NetworkEvent>> {
@@ -587,7 +1071,26 @@ impl NetworkControlPort for Libp2pNetworkAdapter {
             }
         }
 
-        match self.spawn_swarm() {
+        let network = match self.settings.load().await {
+            Ok(settings) => settings.network,
+            Err(err) => {
+                warn!(error = %err, "failed to load network settings, using defaults");
+                NetworkSettings::default()
+            }
+        };
+        let relay = if network.relay.enabled {
+            match RelayTarget::resolve(&network.relay).await {
+                Ok(target) => Some(target),
+                Err(err) => {
+                    warn!(error = %err, "relay disabled: invalid relay settings");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        match self.spawn_swarm(&network, relay) {
             Ok(()) => {
                 self.start_state
                     .store(START_STATE_STARTED, Ordering::Release);
@@ -619,6 +1122,8 @@ fn spawn_business_stream_handler(
     event_tx: mpsc::Sender<NetworkEvent>,
     clipboard_tx: mpsc::Sender<ClipboardMessage>,
     policy_resolver: Arc<dyn ConnectionPolicyResolverPort>,
+    local_device_id: String,
+    transfer: Option<TransferService>,
 ) {
     let mut incoming = match control.accept(StreamProtocol::new(BUSINESS_PROTOCOL_ID)) {
         Ok(incoming) => incoming,
@@ -635,9 +1140,13 @@ fn spawn_business_stream_handler(
             let clipboard_tx = clipboard_tx.clone();
             let policy_resolver = policy_resolver.clone();
             let caches = caches.clone();
+            let local_device_id = local_device_id.clone();
+            let transfer = transfer.clone();
+            let mut control = control.clone();
             tokio::spawn(async move {
                 if check_business_allowed(
                     &policy_resolver,
+                    &caches,
                     &event_tx,
                     &peer_id,
                     ProtocolDirection::Inbound,
@@ -682,19 +1191,54 @@ fn spawn_business_stream_handler(
                 if let Err(err) = limited.into_inner().close().await {
                     warn!("business stream close failed: {err}");
                 }
-                handle_business_payload(caches, event_tx, clipboard_tx, peer_id, payload).await;
+                let reply = handle_business_payload(
+                    caches,
+                    event_tx,
+                    clipboard_tx,
+                    &policy_resolver,
+                    transfer,
+                    &local_device_id,
+                    peer_id,
+                    payload,
+                )
+                .await;
+                if let Some(reply) = reply {
+                    send_business_message(&mut control, _peer, &reply).await;
+                }
             });
         }
     });
 }
 
+async fn forward_clipboard(
+    clipboard_tx: &mpsc::Sender<ClipboardMessage>,
+    event_tx: &mpsc::Sender<NetworkEvent>,
+    message: ClipboardMessage,
+) {
+    if let Err(err) = clipboard_tx.send(message.clone()).await {
+        warn!("Failed to forward clipboard payload: {err}");
+    }
+    if let Err(err) = try_send_event(
+        event_tx,
+        NetworkEvent::ClipboardReceived(message),
+        "ClipboardReceived",
+    ) {
+        warn!("failed to send ClipboardReceived event: {err}");
+    }
+}
+
+/// Handle one inbound business payload; returns a message to send back, if any.
+#[allow(clippy::too_many_arguments)]
 async fn handle_business_payload(
     caches: Arc<RwLock<PeerCaches>>,
     event_tx: mpsc::Sender<NetworkEvent>,
     clipboard_tx: mpsc::Sender<ClipboardMessage>,
+    policy_resolver: &Arc<dyn ConnectionPolicyResolverPort>,
+    transfer: Option<TransferService>,
+    local_device_id: &str,
     peer_id: String,
     payload: Vec<u8>,
-) {
+) -> Option<ProtocolMessage> {
     let message = match ProtocolMessage::from_bytes(&payload) {
         Ok(message) => message,
         Err(err) => {
@@ -704,7 +1248,7 @@ async fn handle_business_payload(
                 payload.len(),
                 err
             );
-            return;
+            return None;
         }
     };
 
@@ -716,6 +1260,11 @@ async fn handle_business_payload(
                     peer_id, announce.peer_id
                 );
             }
+            if !verify_peer_identity(policy_resolver, &event_tx, &peer_id, &announce.device_id)
+                .await
+            {
+                return None;
+            }
             let changed = {
                 let mut caches = caches.write().await;
                 caches.upsert_device_name(
@@ -737,20 +1286,137 @@ async fn handle_business_payload(
                 }
             }
         }
-        ProtocolMessage::Clipboard(message) => {
-            if let Err(err) = clipboard_tx.send(message.clone()).await {
-                warn!("Failed to forward clipboard payload: {err}");
+        ProtocolMessage::Clipboard(mut message) => {
+            message.sender_peer_id = Some(peer_id.clone());
+            match message.payload_manifest.take() {
+                None => forward_clipboard(&clipboard_tx, &event_tx, message).await,
+                Some(manifest) => {
+                    let Some(transfer) = transfer else {
+                        warn!(
+                            "Dropping clipboard manifest without transfer service: peer_id={}",
+                            peer_id
+                        );
+                        return None;
+                    };
+                    // Pull in the background so the business stream is not held open.
+                    tokio::spawn(async move {
+                        match transfer.fetch(&peer_id, manifest).await {
+                            Ok(content) => {
+                                message.encrypted_content = content;
+                                forward_clipboard(&clipboard_tx, &event_tx, message).await;
+                            }
+                            Err(err) => {
+                                warn!(peer_id = %peer_id, message_id = %message.id, "clipboard payload transfer failed: {err}");
+                            }
+                        }
+                    });
+                }
+            }
+        }
+        ProtocolMessage::KeySlotUpdate(message) => {
+            if let Err(err) = try_send_event(
+                &event_tx,
+                NetworkEvent::KeySlotUpdateReceived {
+                    peer_id: peer_id.clone(),
+                    message,
+                },
+                "KeySlotUpdateReceived",
+            ) {
+                warn!("failed to send KeySlotUpdateReceived event: {err}");
+            }
+        }
+        ProtocolMessage::CatchUp(request) => {
+            if let Err(err) = try_send_event(
+                &event_tx,
+                NetworkEvent::CatchUpRequested {
+                    peer_id: peer_id.clone(),
+                    request,
+                },
+                "CatchUpRequested",
+            ) {
+                warn!("failed to send CatchUpRequested event: {err}");
             }
+        }
+        ProtocolMessage::Heartbeat(heartbeat) => {
+            let now = Utc::now();
+            // RTT uses only our own clock: the reply echoes our probe timestamp.
+            let rtt_ms = heartbeat
+                .reply_to
+                .and_then(|sent_at| (now - sent_at).to_std().ok())
+                .map(|rtt| rtt.as_millis() as u64);
+            debug!(peer_id = %peer_id, rtt_ms = ?rtt_ms, "Received heartbeat");
+            caches
+                .write()
+                .await
+                .record_heartbeat(peer_id.as_str(), now, rtt_ms);
             if let Err(err) = try_send_event(
                 &event_tx,
-                NetworkEvent::ClipboardReceived(message),
-                "ClipboardReceived",
+                NetworkEvent::PeerHeartbeat {
+                    peer_id,
+                    seen_at: now,
+                    rtt_ms,
+                },
+                "PeerHeartbeat",
             ) {
-                warn!("failed to send ClipboardReceived event: {err}");
+                warn!("failed to send PeerHeartbeat event: {err}");
+            }
+            if heartbeat.reply_to.is_none() {
+                return Some(ProtocolMessage::Heartbeat(
+                    heartbeat.reply(local_device_id.to_string(), now),
+                ));
             }
         }
-        ProtocolMessage::Heartbeat(_) => {
-            debug!("Received heartbeat payload from peer_id={}", peer_id);
+        ProtocolMessage::Hello(hello) => {
+            if !verify_peer_identity(policy_resolver, &event_tx, &peer_id, &hello.device_id).await {
+                return None;
+            }
+            let local =
+                HelloMessage::local(local_device_id.to_string(), APP_VERSION.to_string(), true);
+            let negotiated =
+                PeerCapabilities::negotiate(peer_id.clone(), &local, &hello, Utc::now());
+            caches
+                .write()
+                .await
+                .record_negotiation(&peer_id, negotiated.clone());
+            match negotiated {
+                Some(capabilities) => {
+                    info!(
+                        peer_id = %peer_id,
+                        app_version = %capabilities.app_version,
+                        protocol_version = %capabilities.protocol_version,
+                        features = ?capabilities.features,
+                        "Negotiated peer capabilities"
+                    );
+                    if let Err(err) = try_send_event(
+                        &event_tx,
+                        NetworkEvent::PeerCapabilitiesNegotiated(capabilities),
+                        "PeerCapabilitiesNegotiated",
+                    ) {
+                        warn!("failed to send PeerCapabilitiesNegotiated event: {err}");
+                    }
+                }
+                None => {
+                    warn!(
+                        peer_id = %peer_id,
+                        app_version = %hello.app_version,
+                        remote_versions = ?hello.protocol_versions,
+                        "No common business protocol version with peer"
+                    );
+                    // Business traffic is only accepted from trusted peers.
+                    emit_protocol_denied(
+                        &event_tx,
+                        peer_id.clone(),
+                        BUSINESS_PROTOCOL_ID,
+                        PairingState::Trusted,
+                        ProtocolDirection::Inbound,
+                        ProtocolDenyReason::NotSupported,
+                    )
+                    .await;
+                }
+            }
+            if !hello.reply {
+                return Some(ProtocolMessage::Hello(local));
+            }
         }
         ProtocolMessage::Pairing(_) => {
             warn!(
@@ -759,6 +1425,60 @@ async fn handle_business_payload(
             );
         }
     }
+    None
+}
+
+/// Write `message` on a new business stream to `peer`. Failures are logged.
+async fn send_business_message(
+    control: &mut stream::Control,
+    peer: PeerId,
+    message: &ProtocolMessage,
+) {
+    let payload = match message.to_bytes() {
+        Ok(payload) => payload,
+        Err(err) => {
+            warn!("Failed to serialize business payload: {err}");
+            return;
+        }
+    };
+    match control
+        .open_stream(peer, StreamProtocol::new(BUSINESS_PROTOCOL_ID))
+        .await
+    {
+        Ok(mut stream) => {
+            if let Err(err) = stream.write_all(&payload).await {
+                warn!("business stream write failed: {err}");
+            } else if let Err(err) = stream.close().await {
+                warn!("business stream close failed: {err}");
+            }
+        }
+        Err(err) => {
+            debug!(peer_id = %peer, "business stream open failed: {err}");
+        }
+    }
+}
+
+/// Send a heartbeat or hello probe to a peer that is allowed the business protocol.
+///
+/// Untrusted peers are skipped silently instead of raising `ProtocolDenied`
+/// on every tick.
+async fn send_business_probe(
+    mut control: stream::Control,
+    policy_resolver: Arc<dyn ConnectionPolicyResolverPort>,
+    peer_id: String,
+    probe: ProtocolMessage,
+) {
+    let Ok(peer) = peer_id.parse::<PeerId>() else {
+        return;
+    };
+    match policy_resolver
+        .resolve_for_peer(&uc_core::PeerId::from(peer_id.as_str()))
+        .await
+    {
+        Ok(resolved) if resolved.allowed.allows(ProtocolKind::Business) => {}
+        _ => return,
+    }
+    send_business_message(&mut control, peer, &probe).await;
 }
 
 async fn emit_protocol_denied(
@@ -812,21 +1532,90 @@ async fn handle_pairing_open_error(
     }
 }
 
-async fn check_business_allowed(
+/// Multihash code of peer ids that inline their public key.
+const IDENTITY_MULTIHASH_CODE: u64 = 0x00;
+
+/// Ed25519 identity key carried by a peer id; the transport handshake has
+/// already proven possession of it.
+fn identity_pubkey_of(peer_id: &str) -> Option<Vec<u8>> {
+    let peer = peer_id.parse::<PeerId>().ok()?;
+    let multihash = peer.as_ref();
+    if multihash.code() != IDENTITY_MULTIHASH_CODE {
+        return None;
+    }
+    let public_key = identity::PublicKey::try_decode_protobuf(multihash.digest()).ok()?;
+    Some(public_key.try_into_ed25519().ok()?.to_bytes().to_vec())
+}
+
+/// Check the connection's identity key against the fingerprint pinned for
+/// the device id the peer claims in its hello or announce.
+///
+/// Returns `false` when the key does not match; the peer is quarantined, so
+/// later business traffic from it is denied.
+async fn verify_peer_identity(
     policy_resolver: &Arc<dyn ConnectionPolicyResolverPort>,
     event_tx: &mpsc::Sender<NetworkEvent>,
     peer_id: &str,
-    direction: ProtocolDirection,
-) -> Result<ResolvedConnectionPolicy> {
+    device_id: &str,
+) -> bool {
+    if device_id.is_empty() {
+        return true;
+    }
+    let Some(identity_pubkey) = identity_pubkey_of(peer_id) else {
+        return true;
+    };
     let peer = uc_core::PeerId::from(peer_id);
-    match policy_resolver.resolve_for_peer(&peer).await {
-        Ok(resolved) => {
-            if resolved.allowed.allows(ProtocolKind::Business) {
-                Ok(resolved)
-            } else {
-                emit_protocol_denied(
-                    event_tx,
-                    peer_id.to_string(),
+    match policy_resolver
+        .verify_peer_identity(&peer, device_id, &identity_pubkey)
+        .await
+    {
+        Ok(IdentityCheck::Mismatch {
+            expected_fingerprint,
+            presented_fingerprint,
+        }) => {
+            warn!(
+                peer_id = %peer_id,
+                device_id = %device_id,
+                expected_fingerprint = %expected_fingerprint,
+                presented_fingerprint = %presented_fingerprint,
+                "Peer identity does not match pinned fingerprint"
+            );
+            if let Err(err) = try_send_event(
+                event_tx,
+                NetworkEvent::PeerIdentityMismatch {
+                    peer_id: peer_id.to_string(),
+                    expected_fingerprint,
+                    presented_fingerprint,
+                },
+                "PeerIdentityMismatch",
+            ) {
+                warn!("failed to send PeerIdentityMismatch event: {err}");
+            }
+            false
+        }
+        Ok(IdentityCheck::Quarantined) => false,
+        Ok(IdentityCheck::Verified | IdentityCheck::Unpinned) => true,
+        Err(err) => {
+            warn!(peer_id = %peer_id, error = %err, "peer identity check failed");
+            true
+        }
+    }
+}
+
+async fn check_business_allowed(
+    policy_resolver: &Arc<dyn ConnectionPolicyResolverPort>,
+    caches: &RwLock<PeerCaches>,
+    event_tx: &mpsc::Sender<NetworkEvent>,
+    peer_id: &str,
+    direction: ProtocolDirection,
+) -> Result<ResolvedConnectionPolicy> {
+    let peer = uc_core::PeerId::from(peer_id);
+    match policy_resolver.resolve_for_peer(&peer).await {
+        Ok(resolved) => {
+            if !resolved.allowed.allows(ProtocolKind::Business) {
+                emit_protocol_denied(
+                    event_tx,
+                    peer_id.to_string(),
                     BUSINESS_PROTOCOL_ID,
                     resolved.pairing_state,
                     direction,
@@ -834,6 +1623,19 @@ async fn check_business_allowed(
                 )
                 .await;
                 Err(anyhow!("business protocol denied"))
+            } else if caches.read().await.is_incompatible(peer_id) {
+                emit_protocol_denied(
+                    event_tx,
+                    peer_id.to_string(),
+                    BUSINESS_PROTOCOL_ID,
+                    resolved.pairing_state,
+                    direction,
+                    ProtocolDenyReason::NotSupported,
+                )
+                .await;
+                Err(anyhow!("no common business protocol version"))
+            } else {
+                Ok(resolved)
             }
         }
         Err(err) => {
@@ -851,18 +1653,86 @@ async fn check_business_allowed(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn run_swarm(
     mut swarm: Swarm<Libp2pBehaviour>,
     caches: Arc<RwLock<PeerCaches>>,
     event_tx: mpsc::Sender<NetworkEvent>,
     policy_resolver: Arc<dyn ConnectionPolicyResolverPort>,
+    business_tx: mpsc::Sender<BusinessCommand>,
     mut business_rx: mpsc::Receiver<BusinessCommand>,
     local_peer_id: String,
+    local_device_id: String,
+    mut listen: ListenState,
+    mut relay: Option<RelayState>,
 ) {
     info!(local_peer_id = %local_peer_id, "libp2p mDNS swarm started");
+    // Peer reached through each manually dialed address, to avoid redialing
+    // an address whose peer is still connected.
+    let mut dialed_peers: HashMap<Multiaddr, PeerId> = HashMap::new();
+    let mut interface_poll = tokio::time::interval(INTERFACE_POLL_INTERVAL);
+    interface_poll.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut rendezvous_poll = tokio::time::interval(RENDEZVOUS_DISCOVER_INTERVAL);
+    rendezvous_poll.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut heartbeat_tick = tokio::time::interval(HEARTBEAT_INTERVAL);
+    heartbeat_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
 
     loop {
         tokio::select! {
+            _ = interface_poll.tick() => {
+                listen.reload_filter().await;
+                let desired = listen.desired_ips();
+                listen.sync(&mut swarm, &desired, &event_tx);
+            }
+            _ = rendezvous_poll.tick(), if relay.is_some() => {
+                if let Some(relay) = relay.as_mut() {
+                    relay.refresh(&mut swarm).await;
+                }
+            }
+            _ = heartbeat_tick.tick() => {
+                let (peer_ids, hello_peer_ids) = {
+                    let caches = caches.read().await;
+                    let peer_ids = caches
+                        .reachable_peers
+                        .iter()
+                        .filter(|peer_id| !caches.is_incompatible(peer_id))
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    // Retry the handshake with peers trusted after they connected.
+                    let hello_peer_ids = peer_ids
+                        .iter()
+                        .filter(|peer_id| caches.needs_hello(peer_id))
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    (peer_ids, hello_peer_ids)
+                };
+                let hello = ProtocolMessage::Hello(HelloMessage::local(
+                    local_device_id.clone(),
+                    APP_VERSION.to_string(),
+                    false,
+                ));
+                for peer_id in hello_peer_ids {
+                    tokio::spawn(send_business_probe(
+                        swarm.behaviour().stream.new_control(),
+                        policy_resolver.clone(),
+                        peer_id,
+                        hello.clone(),
+                    ));
+                }
+                let probe = ProtocolMessage::Heartbeat(HeartbeatMessage {
+                    device_id: local_device_id.clone(),
+                    timestamp: Utc::now(),
+                    reply_to: None,
+                });
+                for peer_id in peer_ids {
+                    tokio::spawn(send_business_probe(
+                        swarm.behaviour().stream.new_control(),
+                        policy_resolver.clone(),
+                        peer_id,
+                        probe.clone(),
+                    ));
+                }
+            }
             event = swarm.select_next_some() => {
                 match event {
                     SwarmEvent::Behaviour(Libp2pBehaviourEvent::Mdns(event)) => match event {
@@ -927,9 +1797,54 @@ async fn run_swarm(
                         }
                     },
                     SwarmEvent::Behaviour(Libp2pBehaviourEvent::Stream) => {}
-                    SwarmEvent::ConnectionEstablished { peer_id, endpoint, .. } => {
+                    SwarmEvent::Behaviour(Libp2pBehaviourEvent::Identify(_)) => {}
+                    SwarmEvent::Behaviour(Libp2pBehaviourEvent::Relay(event)) => {
+                        if let Some(relay) = relay.as_mut() {
+                            relay.on_relay_event(&mut swarm, event);
+                        }
+                    }
+                    SwarmEvent::Behaviour(Libp2pBehaviourEvent::Dcutr(event)) => {
+                        match event.result {
+                            Ok(_) => info!(peer_id = %event.remote_peer_id, "hole punch succeeded, direct connection established"),
+                            Err(err) => debug!(peer_id = %event.remote_peer_id, "hole punch failed, staying on relay: {err}"),
+                        }
+                    }
+                    SwarmEvent::Behaviour(Libp2pBehaviourEvent::Rendezvous(event)) => {
+                        if let Some(relay) = relay.as_mut() {
+                            for (peer_id, addresses) in relay.on_rendezvous_event(&mut swarm, event) {
+                                // The policy lookup hits storage; keep it off the swarm loop.
+                                let policy_resolver = policy_resolver.clone();
+                                let business_tx = business_tx.clone();
+                                tokio::spawn(async move {
+                                    if !is_trusted_peer(policy_resolver.as_ref(), &peer_id).await {
+                                        debug!(peer_id = %peer_id, "ignoring untrusted peer found via rendezvous");
+                                        return;
+                                    }
+                                    if let Err(err) = business_tx
+                                        .send(BusinessCommand::DialRendezvousPeer { peer_id, addresses })
+                                        .await
+                                    {
+                                        warn!("failed to queue rendezvous dial: {err}");
+                                    }
+                                });
+                            }
+                        }
+                    }
+                    SwarmEvent::ConnectionEstablished {
+                        peer_id,
+                        connection_id,
+                        endpoint,
+                        ..
+                    } => {
+                        if let Some(relay) = relay.as_mut() {
+                            if relay.is_relay(&peer_id) {
+                                // The relay node is infrastructure, not a device.
+                                relay.on_connected(&mut swarm);
+                                continue;
+                            }
+                        }
                         let peer_id_string = peer_id.to_string();
-                        let address = match endpoint {
+                        let address = match &endpoint {
                             ConnectedPoint::Dialer { address, .. } => Some(address.clone()),
                             ConnectedPoint::Listener { send_back_addr, .. } => {
                                 Some(send_back_addr.clone())
@@ -938,36 +1853,75 @@ async fn run_swarm(
                         if let Some(address) = address.as_ref() {
                             swarm.add_peer_address(peer_id, address.clone());
                         }
+                        if let ConnectedPoint::Dialer { address, .. } = &endpoint {
+                            dialed_peers.insert(without_p2p(address), peer_id);
+                            let _ = try_send_event(
+                                &event_tx,
+                                NetworkEvent::PeerAddressConfirmed {
+                                    peer_id: peer_id_string.clone(),
+                                    address: without_p2p(address).to_string(),
+                                },
+                                "PeerAddressConfirmed",
+                            );
+                        }
+                        let transport = transport_of(endpoint.get_remote_address());
                         let event = {
                             let mut caches = caches.write().await;
                             apply_peer_ready_from_connection(
                                 &mut caches,
                                 &peer_id_string,
+                                connection_id,
+                                transport,
                                 Utc::now(),
                                 address,
                             )
                         };
 
                         if let Some(event) = event {
-                            let _ = try_send_event(&event_tx, event, "PeerReady");
+                            let label = match &event {
+                                NetworkEvent::PeerTransportChanged { .. } => "PeerTransportChanged",
+                                _ => "PeerReady",
+                            };
+                            let _ = try_send_event(&event_tx, event, label);
                             info!(
                                 peer_id = %peer_id_string,
+                                transport = transport.as_str(),
                                 local_peer_id = %local_peer_id,
                                 "peer connection established"
                             );
+                            if caches.read().await.needs_hello(&peer_id_string) {
+                                tokio::spawn(send_business_probe(
+                                    swarm.behaviour().stream.new_control(),
+                                    policy_resolver.clone(),
+                                    peer_id_string.clone(),
+                                    ProtocolMessage::Hello(HelloMessage::local(
+                                        local_device_id.clone(),
+                                        APP_VERSION.to_string(),
+                                        false,
+                                    )),
+                                ));
+                            }
                         } else {
                             debug!("connection established for unknown peer {peer_id_string}");
                         }
                     }
-                    SwarmEvent::ConnectionClosed { peer_id, .. } => {
+                    SwarmEvent::ConnectionClosed {
+                        peer_id,
+                        connection_id,
+                        ..
+                    } => {
                         let peer_id = peer_id.to_string();
                         let event = {
                             let mut caches = caches.write().await;
-                            apply_peer_not_ready(&mut caches, &peer_id)
+                            apply_peer_not_ready(&mut caches, &peer_id, connection_id)
                         };
 
                         if let Some(event) = event {
-                            let _ = try_send_event(&event_tx, event, "PeerNotReady");
+                            let label = match &event {
+                                NetworkEvent::PeerTransportChanged { .. } => "PeerTransportChanged",
+                                _ => "PeerNotReady",
+                            };
+                            let _ = try_send_event(&event_tx, event, label);
                             info!(
                                 peer_id = %peer_id,
                                 local_peer_id = %local_peer_id,
@@ -1002,6 +1956,37 @@ async fn run_swarm(
                     }
                     SwarmEvent::NewListenAddr { address, .. } => {
                         info!("libp2p listening on {address}");
+                        let mut addresses = listen.addresses.write().await;
+                        let address = address.to_string();
+                        if !addresses.contains(&address) {
+                            addresses.push(address);
+                        }
+                    }
+                    SwarmEvent::ExpiredListenAddr { address, .. } => {
+                        info!("libp2p stopped listening on {address}");
+                        let address = address.to_string();
+                        listen.addresses.write().await.retain(|known| *known != address);
+                    }
+                    SwarmEvent::ListenerClosed {
+                        listener_id,
+                        addresses,
+                        reason,
+                    } => {
+                        match &reason {
+                            Ok(()) => debug!(?listener_id, "libp2p listener closed"),
+                            Err(err) => warn!(?listener_id, "libp2p listener closed: {err}"),
+                        }
+                        listen.remove_listener(listener_id);
+                        if let Some(relay) = relay.as_mut() {
+                            relay.on_listener_closed(listener_id);
+                        }
+                        let closed: Vec<String> =
+                            addresses.iter().map(|address| address.to_string()).collect();
+                        listen
+                            .addresses
+                            .write()
+                            .await
+                            .retain(|known| !closed.contains(known));
                     }
                     _ => {}
                 }
@@ -1018,6 +2003,7 @@ async fn run_swarm(
                         };
                         if check_business_allowed(
                             &policy_resolver,
+                            &caches,
                             &event_tx,
                             peer_id.as_str(),
                             ProtocolDirection::Outbound,
@@ -1063,6 +2049,7 @@ async fn run_swarm(
                         );
                         let message = ProtocolMessage::DeviceAnnounce(DeviceAnnounceMessage {
                             peer_id: local_peer_id.clone(),
+                            device_id: local_device_id.clone(),
                             device_name: device_name.clone(),
                             timestamp: Utc::now(),
                         });
@@ -1083,6 +2070,7 @@ async fn run_swarm(
                             };
                             if check_business_allowed(
                                 &policy_resolver,
+                                &caches,
                                 &event_tx,
                                 peer_id.as_str(),
                                 ProtocolDirection::Outbound,
@@ -1110,27 +2098,606 @@ async fn run_swarm(
                             }
                         }
                     }
+                    BusinessCommand::DialAddress { address } => {
+                        if let Some(peer) = dialed_peers.get(&without_p2p(&address)) {
+                            if swarm.is_connected(peer) {
+                                debug!(address = %address, peer_id = %peer, "skip dial, peer already connected");
+                                continue;
+                            }
+                        }
+                        info!(address = %address, "dialing manual peer address");
+                        if let Err(err) = swarm.dial(address.clone()) {
+                            warn!(address = %address, "manual dial failed: {err}");
+                        }
+                    }
+                    BusinessCommand::DialPeer { peer_id, addresses } => {
+                        if swarm.is_connected(&peer_id) {
+                            continue;
+                        }
+                        debug!(peer_id = %peer_id, address_count = addresses.len(), "redialing known peer");
+                        let opts = DialOpts::peer_id(peer_id)
+                            .addresses(prefer_quic(addresses))
+                            .condition(PeerCondition::DisconnectedAndNotDialing)
+                            .build();
+                        if let Err(err) = swarm.dial(opts) {
+                            debug!(peer_id = %peer_id, "redial skipped: {err}");
+                        }
+                    }
+                    BusinessCommand::DialRendezvousPeer { peer_id, addresses } => {
+                        if let Some(relay) = relay.as_mut() {
+                            relay.dial_peer(&mut swarm, peer_id, addresses);
+                        }
+                    }
+                    BusinessCommand::DisconnectPeer { peer_id } => {
+                        if swarm.disconnect_peer_id(peer_id).is_ok() {
+                            info!(peer_id = %peer_id, "disconnected revoked peer");
+                        }
+                    }
                 }
             }
         }
     }
 }
 
+/// Parse a stored peer address, resolving a leading `/dns*` component.
+///
+/// The transport has no DNS support, so `host:port` entries saved as
+/// `/dns/<host>/tcp/<port>` are resolved here before dialing.
+async fn resolve_dial_address(address: &str) -> Result<Multiaddr> {
+    let parsed: Multiaddr = address
+        .parse()
+        .map_err(|err| anyhow!("invalid multiaddr {address}: {err}"))?;
+    let mut components = parsed.iter();
+    let host = match components.next() {
+        Some(Protocol::Dns(host) | Protocol::Dns4(host) | Protocol::Dns6(host)) => host.to_string(),
+        _ => return Ok(parsed),
+    };
+    let port = match components.next() {
+        Some(Protocol::Tcp(port)) => port,
+        _ => return Err(anyhow!("dns address without tcp port: {address}")),
+    };
+    let socket = tokio::net::lookup_host((host.as_str(), port))
+        .await
+        .map_err(|err| anyhow!("failed to resolve {host}: {err}"))?
+        .next()
+        .ok_or_else(|| anyhow!("no address found for {host}"))?;
+
+    let mut resolved = Multiaddr::from(socket.ip());
+    resolved.push(Protocol::Tcp(port));
+    for component in components {
+        resolved.push(component);
+    }
+    Ok(resolved)
+}
+
+/// Parse a manually entered peer address.
+///
+/// Accepts an IP or DNS host followed by a TCP or UDP port, optionally
+/// ending in `/p2p/<peer id>`. Anything else is refused before it is stored.
+pub(crate) fn parse_peer_multiaddr(address: &str) -> Result<Multiaddr> {
+    let parsed: Multiaddr = address
+        .parse()
+        .map_err(|err| anyhow!("invalid multiaddr {address}: {err}"))?;
+    let components = parsed.iter().collect::<Vec<_>>();
+    let dialable = match components.as_slice() {
+        [host, port, rest @ ..] => {
+            matches!(
+                host,
+                Protocol::Ip4(_)
+                    | Protocol::Ip6(_)
+                    | Protocol::Dns(_)
+                    | Protocol::Dns4(_)
+                    | Protocol::Dns6(_)
+            ) && matches!(port, Protocol::Tcp(port) | Protocol::Udp(port) if *port != 0)
+                && rest
+                    .iter()
+                    .rev()
+                    .skip(1)
+                    .all(|component| !matches!(component, Protocol::P2p(_)))
+        }
+        _ => false,
+    };
+    if !dialable {
+        return Err(anyhow!("not a dialable peer address: {address}"));
+    }
+    Ok(parsed)
+}
+
+fn without_p2p(address: &Multiaddr) -> Multiaddr {
+    address
+        .iter()
+        .filter(|component| !matches!(component, Protocol::P2p(_)))
+        .collect()
+}
+
+/// Transport used by a connection to `address`.
+fn transport_of(address: &Multiaddr) -> TransportKind {
+    if address
+        .iter()
+        .any(|component| matches!(component, Protocol::P2pCircuit))
+    {
+        TransportKind::Relay
+    } else if address
+        .iter()
+        .any(|component| matches!(component, Protocol::QuicV1 | Protocol::Quic))
+    {
+        TransportKind::Quic
+    } else {
+        TransportKind::Tcp
+    }
+}
+
+/// Preference order between transports, lower is better.
+fn transport_rank(transport: TransportKind) -> u8 {
+    match transport {
+        TransportKind::Quic => 0,
+        TransportKind::Tcp => 1,
+        TransportKind::Relay => 2,
+    }
+}
+
+/// Order dial candidates QUIC first and relayed last; addresses are tried in order.
+fn prefer_quic(mut addresses: Vec<Multiaddr>) -> Vec<Multiaddr> {
+    addresses.sort_by_key(|address| transport_rank(transport_of(address)));
+    addresses
+}
+
+const IDENTIFY_PROTOCOL_VERSION: &str = "/uniclipboard/id/1.0.0";
+/// Prefix of namespaces derived from space key material.
+const SPACE_NAMESPACE_PREFIX: &str = "uc-space-";
+/// How often the rendezvous point is queried for peers, and the relay redialed.
+const RENDEZVOUS_DISCOVER_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Relay node used to reach peers on other networks; it is also the
+/// rendezvous point.
+///
+/// 中继节点：提供 relay-v2 预约与 rendezvous 注册/发现。
+///
+/// Relayed connections get no special treatment: the connection policy still
+/// gates business streams on the peer being `Trusted`.
+#[derive(Debug, Clone)]
+pub struct RelayTarget {
+    address: Multiaddr,
+    peer_id: PeerId,
+    /// Namespace set in the settings; `None` derives one from the space key.
+    namespace: Option<rendezvous::Namespace>,
+}
+
+impl RelayTarget {
+    /// Resolve `settings.address` (DNS allowed) into a dialable target.
+    pub async fn resolve(settings: &RelaySettings) -> Result<Self> {
+        let address = resolve_dial_address(settings.address.trim()).await?;
+        Self::from_address(address, &settings.namespace)
+    }
+
+    fn from_address(address: Multiaddr, namespace: &str) -> Result<Self> {
+        let peer_id = match address.iter().last() {
+            Some(Protocol::P2p(peer_id)) => peer_id,
+            _ => return Err(anyhow!("relay address must end with /p2p/<peer id>")),
+        };
+        let namespace = match namespace.trim() {
+            "" => None,
+            namespace => Some(
+                rendezvous::Namespace::new(namespace.to_string())
+                    .map_err(|e| anyhow!("invalid rendezvous namespace: {e}"))?,
+            ),
+        };
+        Ok(Self {
+            address: without_p2p(&address),
+            peer_id,
+            namespace,
+        })
+    }
+
+    /// Address peers use to reach us through the relay.
+    fn circuit_address(&self) -> Multiaddr {
+        self.address
+            .clone()
+            .with(Protocol::P2p(self.peer_id))
+            .with(Protocol::P2pCircuit)
+    }
+}
+
+/// Rendezvous namespace of a space, derived from its `MasterKey`.
+///
+/// Only devices holding the space key can compute it, so the relay cannot be
+/// used to enumerate devices of other spaces.
+///
+/// 由空间主密钥派生 rendezvous 命名空间，不同空间的设备互不可见。
+fn space_namespace(master_key: &MasterKey) -> rendezvous::Namespace {
+    use sha2::{Digest, Sha256};
+
+    let digest = Sha256::new()
+        .chain_update(b"uc:rendezvous:v1|")
+        .chain_update(master_key.as_bytes())
+        .finalize();
+    let suffix: String = digest[..16].iter().map(|b| format!("{:02x}", b)).collect();
+    let namespace = format!("{SPACE_NAMESPACE_PREFIX}{suffix}");
+    rendezvous::Namespace::new(namespace).expect("derived namespace is within length limit")
+}
+
+/// Namespaces of the active space: the current key epoch first, then the
+/// previous epoch while its key is still held, so peers that missed a key
+/// rotation are still found. Empty while the session is locked.
+async fn space_namespaces(
+    encryption_session: &dyn EncryptionSessionPort,
+) -> Vec<rendezvous::Namespace> {
+    let Ok((epoch, master_key)) = encryption_session.get_current_key().await else {
+        return Vec::new();
+    };
+    let mut namespaces = vec![space_namespace(&master_key)];
+    if let Some(previous) = epoch.checked_sub(1) {
+        if let Ok(previous_key) = encryption_session.get_master_key_for_epoch(previous).await {
+            let namespace = space_namespace(&previous_key);
+            if !namespaces.contains(&namespace) {
+                namespaces.push(namespace);
+            }
+        }
+    }
+    namespaces
+}
+
+/// Whether a peer found via rendezvous may be dialed: only paired peers in
+/// `PairingState::Trusted` are.
+async fn is_trusted_peer(
+    policy_resolver: &dyn ConnectionPolicyResolverPort,
+    peer_id: &PeerId,
+) -> bool {
+    match policy_resolver
+        .resolve_for_peer(&uc_core::PeerId::from(peer_id.to_string()))
+        .await
+    {
+        Ok(resolved) => resolved.pairing_state == PairingState::Trusted,
+        Err(err) => {
+            debug!(peer_id = %peer_id, "policy lookup failed for rendezvous peer: {err}");
+            false
+        }
+    }
+}
+
+/// Relay reservation and rendezvous bookkeeping owned by the swarm loop.
+struct RelayState {
+    target: RelayTarget,
+    encryption_session: Arc<dyn EncryptionSessionPort>,
+    circuit_listener: Option<ListenerId>,
+    reserved: bool,
+    /// Namespaces to discover in; the first one is also registered.
+    namespaces: Vec<rendezvous::Namespace>,
+    registered: Option<rendezvous::Namespace>,
+    cookies: HashMap<rendezvous::Namespace, rendezvous::Cookie>,
+}
+
+impl RelayState {
+    fn new(target: RelayTarget, encryption_session: Arc<dyn EncryptionSessionPort>) -> Self {
+        let namespaces = target.namespace.clone().into_iter().collect();
+        Self {
+            target,
+            encryption_session,
+            circuit_listener: None,
+            reserved: false,
+            namespaces,
+            registered: None,
+            cookies: HashMap::new(),
+        }
+    }
+
+    fn is_relay(&self, peer_id: &PeerId) -> bool {
+        *peer_id == self.target.peer_id
+    }
+
+    fn connect(&mut self, swarm: &mut Swarm<Libp2pBehaviour>) {
+        let opts = DialOpts::peer_id(self.target.peer_id)
+            .addresses(vec![self.target.address.clone()])
+            .condition(PeerCondition::DisconnectedAndNotDialing)
+            .build();
+        if let Err(err) = swarm.dial(opts) {
+            debug!(relay = %self.target.peer_id, "relay dial skipped: {err}");
+        }
+    }
+
+    /// Reserve a relay slot and look for peers once the relay is reachable.
+    fn on_connected(&mut self, swarm: &mut Swarm<Libp2pBehaviour>) {
+        info!(relay = %self.target.peer_id, "connected to relay");
+        if self.circuit_listener.is_none() {
+            match swarm.listen_on(self.target.circuit_address()) {
+                Ok(listener_id) => self.circuit_listener = Some(listener_id),
+                Err(err) => warn!("failed to request relay reservation: {err}"),
+            }
+        }
+        self.discover(swarm);
+    }
+
+    fn on_listener_closed(&mut self, listener_id: ListenerId) {
+        if self.circuit_listener == Some(listener_id) {
+            warn!(relay = %self.target.peer_id, "relay reservation closed");
+            self.circuit_listener = None;
+            self.reserved = false;
+            self.registered = None;
+        }
+    }
+
+    /// Periodic tick: follow key changes, then reconnect to the relay or
+    /// query for new peers.
+    async fn refresh(&mut self, swarm: &mut Swarm<Libp2pBehaviour>) {
+        if self.target.namespace.is_none() {
+            let namespaces = space_namespaces(self.encryption_session.as_ref()).await;
+            if namespaces != self.namespaces {
+                self.cookies
+                    .retain(|namespace, _| namespaces.contains(namespace));
+                self.namespaces = namespaces;
+                self.register(swarm);
+            }
+        }
+        if swarm.is_connected(&self.target.peer_id) {
+            self.discover(swarm);
+        } else {
+            self.connect(swarm);
+        }
+    }
+
+    /// Register under the current namespace, replacing an older registration.
+    fn register(&mut self, swarm: &mut Swarm<Libp2pBehaviour>) {
+        let Some(namespace) = self.namespaces.first().cloned() else {
+            return;
+        };
+        if !self.reserved || self.registered.as_ref() == Some(&namespace) {
+            return;
+        }
+        if let Some(previous) = self.registered.take() {
+            swarm
+                .behaviour_mut()
+                .rendezvous
+                .unregister(previous, self.target.peer_id);
+        }
+        match swarm.behaviour_mut().rendezvous.register(
+            namespace.clone(),
+            self.target.peer_id,
+            None,
+        ) {
+            Ok(()) => self.registered = Some(namespace),
+            Err(err) => warn!("failed to register at rendezvous point: {err}"),
+        }
+    }
+
+    fn discover(&mut self, swarm: &mut Swarm<Libp2pBehaviour>) {
+        for namespace in &self.namespaces {
+            swarm.behaviour_mut().rendezvous.discover(
+                Some(namespace.clone()),
+                self.cookies.get(namespace).cloned(),
+                None,
+                self.target.peer_id,
+            );
+        }
+    }
+
+    fn dial_peer(
+        &mut self,
+        swarm: &mut Swarm<Libp2pBehaviour>,
+        peer_id: PeerId,
+        addresses: Vec<Multiaddr>,
+    ) {
+        debug!(peer_id = %peer_id, address_count = addresses.len(), "dialing peer found via rendezvous");
+        let opts = DialOpts::peer_id(peer_id)
+            .addresses(addresses)
+            .condition(PeerCondition::DisconnectedAndNotDialing)
+            .build();
+        if let Err(err) = swarm.dial(opts) {
+            debug!(peer_id = %peer_id, "rendezvous dial skipped: {err}");
+        }
+    }
+
+    fn on_relay_event(&mut self, swarm: &mut Swarm<Libp2pBehaviour>, event: relay::client::Event) {
+        match event {
+            relay::client::Event::ReservationReqAccepted { renewal, .. } => {
+                info!(relay = %self.target.peer_id, renewal, "relay reservation accepted");
+                // Rendezvous registers our external addresses; the circuit
+                // address is the one peers on other networks can use.
+                swarm.add_external_address(self.target.circuit_address());
+                // Renew the registration along with the reservation.
+                self.reserved = true;
+                self.registered = None;
+                self.register(swarm);
+            }
+            other => debug!(event = ?other, "relay client event"),
+        }
+    }
+
+    /// Handle a rendezvous event; returns the peers found, which the caller
+    /// dials only if they are trusted.
+    fn on_rendezvous_event(
+        &mut self,
+        swarm: &mut Swarm<Libp2pBehaviour>,
+        event: rendezvous::client::Event,
+    ) -> Vec<(PeerId, Vec<Multiaddr>)> {
+        match event {
+            rendezvous::client::Event::Discovered {
+                registrations,
+                cookie,
+                ..
+            } => {
+                if let Some(namespace) = cookie.namespace().cloned() {
+                    self.cookies.insert(namespace, cookie);
+                }
+                let local_peer_id = *swarm.local_peer_id();
+                registrations
+                    .into_iter()
+                    .map(|registration| registration.record)
+                    .filter(|record| {
+                        record.peer_id() != local_peer_id && !swarm.is_connected(&record.peer_id())
+                    })
+                    .map(|record| (record.peer_id(), prefer_quic(record.addresses().to_vec())))
+                    .collect()
+            }
+            rendezvous::client::Event::Registered { ttl, .. } => {
+                info!(ttl_secs = ttl, "registered at rendezvous point");
+                Vec::new()
+            }
+            rendezvous::client::Event::RegisterFailed {
+                namespace, error, ..
+            } => {
+                warn!(?error, "rendezvous registration failed");
+                if self.registered.as_ref() == Some(&namespace) {
+                    self.registered = None;
+                }
+                Vec::new()
+            }
+            rendezvous::client::Event::DiscoverFailed { error, .. } => {
+                debug!(?error, "rendezvous discovery failed");
+                Vec::new()
+            }
+            rendezvous::client::Event::Expired { peer } => {
+                debug!(peer_id = %peer, "rendezvous registration expired");
+                Vec::new()
+            }
+        }
+    }
+}
+
+/// How often local interfaces are re-enumerated to follow network changes.
+const INTERFACE_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Listener bookkeeping owned by the swarm loop.
+///
+/// 按本机 IP 记录 listener，并周期性与当前网卡地址对齐
+/// （新增网卡开始监听，消失的网卡关闭监听）。
+struct ListenState {
+    port: u16,
+    tcp_only: bool,
+    filter: InterfaceFilter,
+    listeners: HashMap<IpAddr, Vec<ListenerId>>,
+    addresses: Arc<RwLock<Vec<String>>>,
+    settings: Arc<dyn SettingsPort>,
+}
+
+impl ListenState {
+    fn new(
+        network: &NetworkSettings,
+        addresses: Arc<RwLock<Vec<String>>>,
+        settings: Arc<dyn SettingsPort>,
+    ) -> Self {
+        Self {
+            port: network.listen_port,
+            tcp_only: network.tcp_only,
+            filter: InterfaceFilter::from_settings(network),
+            listeners: HashMap::new(),
+            addresses,
+            settings,
+        }
+    }
+
+    /// Pick up edited allow/deny rules; port and transport changes need a restart.
+    async fn reload_filter(&mut self) {
+        match self.settings.load().await {
+            Ok(settings) => self.filter = InterfaceFilter::from_settings(&settings.network),
+            Err(err) => debug!(error = %err, "failed to reload interface rules"),
+        }
+    }
+
+    /// Eligible local addresses, or the unspecified addresses when none are.
+    fn desired_ips(&self) -> Vec<IpAddr> {
+        let ips = eligible_listen_ips(&self.filter);
+        if ips.is_empty() {
+            vec![
+                IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+                IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+            ]
+        } else {
+            ips
+        }
+    }
+
+    /// Close listeners for vanished addresses and open new ones.
+    /// Returns the number of addresses with an active listener.
+    fn sync(
+        &mut self,
+        swarm: &mut Swarm<Libp2pBehaviour>,
+        desired: &[IpAddr],
+        event_tx: &mpsc::Sender<NetworkEvent>,
+    ) -> usize {
+        let current: HashSet<IpAddr> = self.listeners.keys().copied().collect();
+        let (added, removed) = diff_listen_ips(&current, desired);
+
+        for ip in removed {
+            info!(ip = %ip, "local address gone, closing listeners");
+            for listener_id in self.listeners.remove(&ip).unwrap_or_default() {
+                swarm.remove_listener(listener_id);
+            }
+        }
+
+        for ip in added {
+            let mut listener_ids = Vec::new();
+            for address in listen_multiaddrs(ip, self.port, self.tcp_only) {
+                let result = if transport_of(&address) == TransportKind::Quic {
+                    // QUIC is best effort: UDP may be blocked while TCP still works.
+                    swarm.listen_on(address.clone()).map_err(|e| {
+                        warn!(address = %address, "failed to listen on quic, continuing with tcp: {e}");
+                    })
+                } else {
+                    listen_on_swarm(swarm, address, event_tx).map_err(|_| ())
+                };
+                if let Ok(listener_id) = result {
+                    listener_ids.push(listener_id);
+                }
+            }
+            if !listener_ids.is_empty() {
+                self.listeners.insert(ip, listener_ids);
+            }
+        }
+
+        self.listeners.len()
+    }
+
+    /// Forget a closed listener so the next sync can reopen its address.
+    fn remove_listener(&mut self, listener_id: ListenerId) {
+        for listener_ids in self.listeners.values_mut() {
+            listener_ids.retain(|id| *id != listener_id);
+        }
+        self.listeners
+            .retain(|_, listener_ids| !listener_ids.is_empty());
+    }
+}
+
+/// Split the wanted addresses into `(to_add, to_remove)` against the current ones.
+fn diff_listen_ips(current: &HashSet<IpAddr>, desired: &[IpAddr]) -> (Vec<IpAddr>, Vec<IpAddr>) {
+    let added = desired
+        .iter()
+        .filter(|ip| !current.contains(ip))
+        .copied()
+        .collect();
+    let mut removed: Vec<IpAddr> = current
+        .iter()
+        .filter(|ip| !desired.contains(ip))
+        .copied()
+        .collect();
+    removed.sort();
+    (added, removed)
+}
+
+fn listen_multiaddrs(ip: IpAddr, port: u16, tcp_only: bool) -> Vec<Multiaddr> {
+    let base = Multiaddr::empty().with(match ip {
+        IpAddr::V4(v4) => Protocol::Ip4(v4),
+        IpAddr::V6(v6) => Protocol::Ip6(v6),
+    });
+    let mut addresses = vec![base.clone().with(Protocol::Tcp(port))];
+    if !tcp_only {
+        addresses.push(base.with(Protocol::Udp(port)).with(Protocol::QuicV1));
+    }
+    addresses
+}
+
 fn listen_on_swarm(
     swarm: &mut Swarm<Libp2pBehaviour>,
     listen_addr: Multiaddr,
     event_tx: &mpsc::Sender<NetworkEvent>,
-) -> Result<()> {
-    if let Err(e) = swarm.listen_on(listen_addr) {
+) -> Result<ListenerId> {
+    swarm.listen_on(listen_addr).map_err(|e| {
         let message = format!("failed to listen on tcp: {e}");
         warn!("{message}");
         if let Err(err) = event_tx.try_send(NetworkEvent::Error(message.clone())) {
             warn!("failed to publish network error event: {err}");
         }
-        return Err(anyhow!(message));
-    }
-
-    Ok(())
+        anyhow!(message)
+    })
 }
 
 fn try_send_event(
@@ -1196,36 +2763,67 @@ fn apply_mdns_expired(caches: &mut PeerCaches, expired: HashSet<String>) -> Vec<
 fn apply_peer_ready(
     caches: &mut PeerCaches,
     peer_id: &str,
+    transport: TransportKind,
     connected_at: DateTime<Utc>,
 ) -> Option<NetworkEvent> {
     if caches.mark_reachable(peer_id, connected_at) {
         Some(NetworkEvent::PeerReady {
             peer_id: peer_id.to_string(),
+            transport,
         })
     } else {
         None
     }
 }
 
+/// Track a new connection; emits `PeerReady` for the first connection and
+/// `PeerTransportChanged` when it upgrades the peer's preferred transport.
 fn apply_peer_ready_from_connection(
     caches: &mut PeerCaches,
     peer_id: &str,
+    connection_id: ConnectionId,
+    transport: TransportKind,
     connected_at: DateTime<Utc>,
     address: Option<Multiaddr>,
 ) -> Option<NetworkEvent> {
     if let Some(address) = address {
         caches.upsert_discovered_from_connection(peer_id, address, connected_at);
     }
-    apply_peer_ready(caches, peer_id, connected_at)
+    let previous = caches.preferred_transport(peer_id);
+    caches.add_connection(peer_id, connection_id, transport);
+    let preferred = caches.preferred_transport(peer_id)?;
+    if !caches.is_reachable(peer_id) {
+        return apply_peer_ready(caches, peer_id, preferred, connected_at);
+    }
+    (previous != Some(preferred)).then(|| NetworkEvent::PeerTransportChanged {
+        peer_id: peer_id.to_string(),
+        transport: preferred,
+    })
 }
 
-fn apply_peer_not_ready(caches: &mut PeerCaches, peer_id: &str) -> Option<NetworkEvent> {
-    if caches.mark_unreachable(peer_id) {
-        Some(NetworkEvent::PeerNotReady {
+/// Drop a closed connection; emits `PeerNotReady` once the last one is gone,
+/// or `PeerTransportChanged` with the fallback transport when the preferred one closed.
+fn apply_peer_not_ready(
+    caches: &mut PeerCaches,
+    peer_id: &str,
+    connection_id: ConnectionId,
+) -> Option<NetworkEvent> {
+    let previous = caches.preferred_transport(peer_id);
+    caches.remove_connection(peer_id, connection_id);
+    match caches.preferred_transport(peer_id) {
+        Some(preferred) if previous == Some(preferred) => None,
+        Some(preferred) => {
+            caches
+                .is_reachable(peer_id)
+                .then(|| NetworkEvent::PeerTransportChanged {
+                    peer_id: peer_id.to_string(),
+                    transport: preferred,
+                })
+        }
+        None if caches.mark_unreachable(peer_id) => Some(NetworkEvent::PeerNotReady {
             peer_id: peer_id.to_string(),
-        })
-    } else {
-        None
+        }),
+        None => None,
     }
 }
 
@@ -1241,21 +2839,82 @@ mod tests {
     use uc_core::network::{ConnectionPolicy, PairingState, ResolvedConnectionPolicy};
     use uc_core::ports::{ConnectionPolicyResolverError, ConnectionPolicyResolverPort};
 
-    async fn echo_payload<Stream>(stream: &mut Stream) -> anyhow::Result<()>
-    where
-        Stream: libp2p::futures::AsyncRead + libp2p::futures::AsyncWrite + Unpin,
-    {
-        let mut buffer = Vec::new();
-        stream.read_to_end(&mut buffer).await?;
-        stream.write_all(&buffer).await?;
-        stream.close().await?;
-        Ok(())
+    use crate::adapters::InMemoryEncryptionSessionPort;
+
+    async fn echo_payload<Stream>(stream: &mut Stream) -> anyhow::Result<()>
+    where
+        Stream: libp2p::futures::AsyncRead + libp2p::futures::AsyncWrite + Unpin,
+    {
+        let mut buffer = Vec::new();
+        stream.read_to_end(&mut buffer).await?;
+        stream.write_all(&buffer).await?;
+        stream.close().await?;
+        Ok(())
+    }
+
+    #[test]
+    fn mdns_config_has_5s_query_interval() {
+        let config = build_mdns_config(false);
+        assert_eq!(config.query_interval, Duration::from_secs(5));
+        assert!(!config.enable_ipv6);
+        assert!(build_mdns_config(true).enable_ipv6);
+    }
+
+    #[test]
+    fn mdns_runs_on_every_family_with_a_listen_address() {
+        let v4: IpAddr = "192.168.1.20".parse().unwrap();
+        let v6: IpAddr = "fd00::20".parse().unwrap();
+
+        assert_eq!(
+            MdnsFamilies::for_listen_ips(&[v4, v6]),
+            MdnsFamilies {
+                ipv4: true,
+                ipv6: true
+            }
+        );
+        assert_eq!(
+            MdnsFamilies::for_listen_ips(&[v6]),
+            MdnsFamilies {
+                ipv4: false,
+                ipv6: true
+            }
+        );
+        assert_eq!(
+            MdnsFamilies::for_listen_ips(&[v4]),
+            MdnsFamilies {
+                ipv4: true,
+                ipv6: false
+            }
+        );
+        assert!(MdnsFamilies::for_listen_ips(&[]).ipv4);
+    }
+
+    #[test]
+    fn listen_ip_diff_adds_new_and_removes_vanished_addresses() {
+        let wifi: IpAddr = "192.168.1.20".parse().unwrap();
+        let ethernet: IpAddr = "10.0.0.5".parse().unwrap();
+        let ula: IpAddr = "fd00::5".parse().unwrap();
+        let current: HashSet<IpAddr> = [wifi, ethernet].into_iter().collect();
+
+        let (added, removed) = diff_listen_ips(&current, &[ethernet, ula]);
+
+        assert_eq!(added, vec![ula]);
+        assert_eq!(removed, vec![wifi]);
     }
 
     #[test]
-    fn mdns_config_has_5s_query_interval() {
-        let config = build_mdns_config();
-        assert_eq!(config.query_interval, Duration::from_secs(5));
+    fn listen_multiaddrs_cover_ipv6_and_quic() {
+        let ip: IpAddr = "fd00::5".parse().unwrap();
+
+        let addresses: Vec<String> = listen_multiaddrs(ip, 4001, false)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            addresses,
+            vec!["/ip6/fd00::5/tcp/4001", "/ip6/fd00::5/udp/4001/quic-v1"]
+        );
+        assert_eq!(listen_multiaddrs(ip, 4001, true).len(), 1);
     }
 
     #[test]
@@ -1342,11 +3001,11 @@ mod tests {
             Utc::now(),
         );
 
-        let event = apply_peer_ready(&mut caches, "peer-1", Utc::now());
+        let event = apply_peer_ready(&mut caches, "peer-1", TransportKind::Tcp, Utc::now());
 
         assert!(matches!(
             event,
-            Some(NetworkEvent::PeerReady { peer_id }) if peer_id == "peer-1"
+            Some(NetworkEvent::PeerReady { peer_id, .. }) if peer_id == "peer-1"
         ));
         assert!(caches.is_reachable("peer-1"));
     }
@@ -1359,13 +3018,15 @@ mod tests {
         let event = apply_peer_ready_from_connection(
             &mut caches,
             "peer-1",
+            ConnectionId::new_unchecked(1),
+            TransportKind::Tcp,
             Utc::now(),
             Some(address.clone()),
         );
 
         assert!(matches!(
             event,
-            Some(NetworkEvent::PeerReady { peer_id }) if peer_id == "peer-1"
+            Some(NetworkEvent::PeerReady { peer_id, transport: TransportKind::Tcp }) if peer_id == "peer-1"
         ));
         assert!(caches.is_reachable("peer-1"));
         let discovered = caches
@@ -1384,10 +3045,11 @@ mod tests {
             Utc::now(),
         );
 
-        assert!(apply_peer_not_ready(&mut caches, "peer-1").is_none());
-        let _ = apply_peer_ready(&mut caches, "peer-1", Utc::now());
+        let connection_id = ConnectionId::new_unchecked(1);
+        assert!(apply_peer_not_ready(&mut caches, "peer-1", connection_id).is_none());
+        let _ = apply_peer_ready(&mut caches, "peer-1", TransportKind::Tcp, Utc::now());
 
-        let event = apply_peer_not_ready(&mut caches, "peer-1");
+        let event = apply_peer_not_ready(&mut caches, "peer-1", connection_id);
 
         assert!(matches!(
             event,
@@ -1396,6 +3058,155 @@ mod tests {
         assert!(!caches.is_reachable("peer-1"));
     }
 
+    #[test]
+    fn preferred_transport_switches_between_quic_and_tcp() {
+        let mut caches = PeerCaches::new();
+        let tcp_addr: Multiaddr = "/ip4/10.0.0.2/tcp/4001".parse().expect("valid multiaddr");
+        let quic_addr: Multiaddr = "/ip4/10.0.0.2/udp/4001/quic-v1"
+            .parse()
+            .expect("valid multiaddr");
+        let tcp = ConnectionId::new_unchecked(1);
+        let quic = ConnectionId::new_unchecked(2);
+
+        let event = apply_peer_ready_from_connection(
+            &mut caches,
+            "peer-1",
+            tcp,
+            transport_of(&tcp_addr),
+            Utc::now(),
+            Some(tcp_addr.clone()),
+        );
+        assert!(matches!(
+            event,
+            Some(NetworkEvent::PeerReady {
+                transport: TransportKind::Tcp,
+                ..
+            })
+        ));
+
+        let event = apply_peer_ready_from_connection(
+            &mut caches,
+            "peer-1",
+            quic,
+            transport_of(&quic_addr),
+            Utc::now(),
+            Some(quic_addr),
+        );
+        assert!(matches!(
+            event,
+            Some(NetworkEvent::PeerTransportChanged {
+                transport: TransportKind::Quic,
+                ..
+            })
+        ));
+
+        // Another TCP connection does not downgrade the preferred transport.
+        assert!(apply_peer_ready_from_connection(
+            &mut caches,
+            "peer-1",
+            ConnectionId::new_unchecked(3),
+            TransportKind::Tcp,
+            Utc::now(),
+            Some(tcp_addr),
+        )
+        .is_none());
+
+        let event = apply_peer_not_ready(&mut caches, "peer-1", quic);
+        assert!(matches!(
+            event,
+            Some(NetworkEvent::PeerTransportChanged {
+                transport: TransportKind::Tcp,
+                ..
+            })
+        ));
+        assert!(apply_peer_not_ready(&mut caches, "peer-1", tcp).is_none());
+        assert!(matches!(
+            apply_peer_not_ready(&mut caches, "peer-1", ConnectionId::new_unchecked(3)),
+            Some(NetworkEvent::PeerNotReady { .. })
+        ));
+        assert!(!caches.is_reachable("peer-1"));
+    }
+
+    #[test]
+    fn relay_target_requires_peer_id_and_builds_circuit_address() {
+        let relay_peer = PeerId::random();
+        let address: Multiaddr = format!("/ip4/203.0.113.7/tcp/4001/p2p/{relay_peer}")
+            .parse()
+            .unwrap();
+
+        let target = RelayTarget::from_address(address, " ").expect("relay target");
+
+        assert_eq!(target.peer_id, relay_peer);
+        assert!(target.namespace.is_none());
+        assert_eq!(
+            target.circuit_address().to_string(),
+            format!("/ip4/203.0.113.7/tcp/4001/p2p/{relay_peer}/p2p-circuit")
+        );
+        assert_eq!(
+            transport_of(&target.circuit_address()),
+            TransportKind::Relay
+        );
+        assert!(
+            RelayTarget::from_address("/ip4/203.0.113.7/tcp/4001".parse().unwrap(), "team")
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn space_namespaces_follow_the_space_key() {
+        let session = InMemoryEncryptionSessionPort::new();
+        assert!(space_namespaces(&session).await.is_empty());
+
+        let old_key = MasterKey::generate().unwrap();
+        let new_key = MasterKey::generate().unwrap();
+        session.set_master_key(old_key.clone()).await.unwrap();
+        let namespaces = space_namespaces(&session).await;
+        assert_eq!(namespaces, vec![space_namespace(&old_key)]);
+        assert!(namespaces[0]
+            .to_string()
+            .starts_with(SPACE_NAMESPACE_PREFIX));
+
+        session
+            .set_epoch_keys(1, new_key.clone(), vec![(0, old_key.clone())])
+            .await
+            .unwrap();
+        assert_eq!(
+            space_namespaces(&session).await,
+            vec![space_namespace(&new_key), space_namespace(&old_key)]
+        );
+        assert_ne!(space_namespace(&new_key), space_namespace(&old_key));
+    }
+
+    #[tokio::test]
+    async fn only_trusted_rendezvous_peers_are_dialed() {
+        let peer_id = PeerId::random();
+
+        assert!(is_trusted_peer(&FakeResolver, &peer_id).await);
+        assert!(!is_trusted_peer(&PendingResolver, &peer_id).await);
+    }
+
+    #[test]
+    fn prefer_quic_orders_quic_addresses_first() {
+        let addresses: Vec<Multiaddr> = vec![
+            "/ip4/203.0.113.7/tcp/4001/p2p/12D3KooWDpJ7As7BWAwRMfu1VU2WCqNjvq387JEYKDBj4kx6nXTN/p2p-circuit".parse().unwrap(),
+            "/ip4/10.0.0.2/tcp/4001".parse().unwrap(),
+            "/ip4/10.0.0.2/udp/4001/quic-v1".parse().unwrap(),
+            "/ip4/10.0.0.3/tcp/4001".parse().unwrap(),
+        ];
+
+        let ordered = prefer_quic(addresses.clone());
+
+        assert_eq!(
+            ordered,
+            vec![
+                addresses[2].clone(),
+                addresses[1].clone(),
+                addresses[3].clone(),
+                addresses[0].clone()
+            ]
+        );
+    }
+
     #[test]
     fn mdns_discovery_and_expiry_emit_events() {
         let mut caches = PeerCaches::new();
@@ -1447,6 +3258,19 @@ mod tests {
         }
     }
 
+    struct TestSettings;
+
+    #[async_trait::async_trait]
+    impl SettingsPort for TestSettings {
+        async fn load(&self) -> anyhow::Result<uc_core::settings::model::Settings> {
+            Ok(uc_core::settings::model::Settings::default())
+        }
+
+        async fn save(&self, _settings: &uc_core::settings::model::Settings) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
     struct FakeResolver;
 
     #[async_trait::async_trait]
@@ -1460,6 +3284,15 @@ mod tests {
                 allowed: ConnectionPolicy::allowed_protocols(PairingState::Trusted),
             })
         }
+
+        async fn verify_peer_identity(
+            &self,
+            _peer_id: &uc_core::PeerId,
+            _device_id: &str,
+            _identity_pubkey: &[u8],
+        ) -> Result<IdentityCheck, ConnectionPolicyResolverError> {
+            Ok(IdentityCheck::Unpinned)
+        }
     }
 
     struct PendingResolver;
@@ -1475,12 +3308,28 @@ mod tests {
                 allowed: ConnectionPolicy::allowed_protocols(PairingState::Pending),
             })
         }
+
+        async fn verify_peer_identity(
+            &self,
+            _peer_id: &uc_core::PeerId,
+            _device_id: &str,
+            _identity_pubkey: &[u8],
+        ) -> Result<IdentityCheck, ConnectionPolicyResolverError> {
+            Ok(IdentityCheck::Unpinned)
+        }
     }
 
     #[tokio::test]
     async fn adapter_constructs_with_policy_resolver() {
         let resolver: Arc<dyn ConnectionPolicyResolverPort> = Arc::new(FakeResolver);
-        let adapter = Libp2pNetworkAdapter::new(Arc::new(TestIdentityStore::default()), resolver);
+        let adapter = Libp2pNetworkAdapter::new(
+            Arc::new(TestIdentityStore::default()),
+            "local-device".to_string(),
+            resolver,
+            Arc::new(TestSettings),
+            Arc::new(InMemoryEncryptionSessionPort::new()),
+            std::env::temp_dir().join(format!("uc-transfer-test-{}", uuid::Uuid::new_v4())),
+        );
         assert!(adapter.is_ok());
     }
 
@@ -1488,7 +3337,11 @@ mod tests {
     async fn start_network_is_idempotent_when_called_twice() {
         let adapter = Libp2pNetworkAdapter::new(
             Arc::new(TestIdentityStore::default()),
+            "local-device".to_string(),
             Arc::new(FakeResolver),
+            Arc::new(TestSettings),
+            Arc::new(InMemoryEncryptionSessionPort::new()),
+            std::env::temp_dir().join(format!("uc-transfer-test-{}", uuid::Uuid::new_v4())),
         )
         .expect("create adapter");
 
@@ -1506,7 +3359,11 @@ mod tests {
     async fn start_network_can_retry_after_failed_start() {
         let adapter = Libp2pNetworkAdapter::new(
             Arc::new(TestIdentityStore::default()),
+            "local-device".to_string(),
             Arc::new(FakeResolver),
+            Arc::new(TestSettings),
+            Arc::new(InMemoryEncryptionSessionPort::new()),
+            std::env::temp_dir().join(format!("uc-transfer-test-{}", uuid::Uuid::new_v4())),
         )
         .expect("create adapter");
 
@@ -1537,24 +3394,30 @@ mod tests {
 
     #[tokio::test]
     async fn device_announce_updates_cache_and_emits_event() {
+        let resolver: Arc<dyn ConnectionPolicyResolverPort> = Arc::new(FakeResolver);
         let caches = Arc::new(RwLock::new(PeerCaches::new()));
         let (event_tx, mut event_rx) = mpsc::channel(1);
         let (clipboard_tx, _clipboard_rx) = mpsc::channel(1);
         let announce = ProtocolMessage::DeviceAnnounce(DeviceAnnounceMessage {
             peer_id: "peer-1".to_string(),
+            device_id: "device-1".to_string(),
             device_name: "Desk".to_string(),
             timestamp: Utc::now(),
         });
         let payload = announce.to_bytes().expect("serialize announce");
 
-        handle_business_payload(
+        let reply = handle_business_payload(
             caches.clone(),
             event_tx,
             clipboard_tx,
+            &resolver,
+            None,
+            "local-device",
             "peer-1".to_string(),
             payload,
         )
         .await;
+        assert!(reply.is_none());
 
         let event = event_rx.recv().await.expect("peer name updated event");
         match event {
@@ -1577,11 +3440,186 @@ mod tests {
         assert_eq!(cached_name, Some("Desk".to_string()));
     }
 
+    #[tokio::test]
+    async fn heartbeat_probe_is_answered_and_reply_measures_rtt() {
+        let resolver: Arc<dyn ConnectionPolicyResolverPort> = Arc::new(FakeResolver);
+        let caches = Arc::new(RwLock::new(PeerCaches::new()));
+        let (event_tx, mut event_rx) = mpsc::channel(2);
+        let (clipboard_tx, _clipboard_rx) = mpsc::channel(1);
+        let sent_at = Utc::now() - chrono::Duration::milliseconds(40);
+        let probe = HeartbeatMessage {
+            device_id: "device-1".to_string(),
+            timestamp: sent_at,
+            reply_to: None,
+        };
+
+        let reply = handle_business_payload(
+            caches.clone(),
+            event_tx.clone(),
+            clipboard_tx.clone(),
+            &resolver,
+            None,
+            "local-device",
+            "peer-1".to_string(),
+            ProtocolMessage::Heartbeat(probe.clone())
+                .to_bytes()
+                .unwrap(),
+        )
+        .await;
+        match reply {
+            Some(ProtocolMessage::Heartbeat(reply)) => {
+                assert_eq!(reply.device_id, "local-device");
+                assert_eq!(reply.reply_to, Some(sent_at));
+            }
+            other => panic!("expected heartbeat reply, got {other:?}"),
+        }
+        match event_rx.recv().await.expect("heartbeat event") {
+            NetworkEvent::PeerHeartbeat { rtt_ms, .. } => assert_eq!(rtt_ms, None),
+            other => panic!("unexpected event: {other:?}"),
+        }
+
+        let answer = probe.reply("device-1".to_string(), Utc::now());
+        let reply = handle_business_payload(
+            caches.clone(),
+            event_tx,
+            clipboard_tx,
+            &resolver,
+            None,
+            "local-device",
+            "peer-1".to_string(),
+            ProtocolMessage::Heartbeat(answer).to_bytes().unwrap(),
+        )
+        .await;
+        assert!(reply.is_none());
+        match event_rx.recv().await.expect("heartbeat event") {
+            NetworkEvent::PeerHeartbeat { rtt_ms, .. } => assert!(rtt_ms >= Some(40)),
+            other => panic!("unexpected event: {other:?}"),
+        }
+        let liveness = caches.read().await.liveness.get("peer-1").cloned().unwrap();
+        assert!(liveness.rtt_ms >= Some(40));
+    }
+
+    #[tokio::test]
+    async fn hello_is_answered_and_negotiated_capabilities_cached() {
+        let resolver: Arc<dyn ConnectionPolicyResolverPort> = Arc::new(FakeResolver);
+        let caches = Arc::new(RwLock::new(PeerCaches::new()));
+        let (event_tx, mut event_rx) = mpsc::channel(2);
+        let (clipboard_tx, _clipboard_rx) = mpsc::channel(1);
+        let mut hello = HelloMessage::local("peer-1".to_string(), "9.9.9".to_string(), false);
+        hello.features = vec!["chunked_transfer".to_string(), "telepathy".to_string()];
+
+        let reply = handle_business_payload(
+            caches.clone(),
+            event_tx,
+            clipboard_tx,
+            &resolver,
+            None,
+            "local-device",
+            "peer-1".to_string(),
+            ProtocolMessage::Hello(hello).to_bytes().unwrap(),
+        )
+        .await;
+
+        match reply {
+            Some(ProtocolMessage::Hello(reply)) => {
+                assert_eq!(reply.device_id, "local-device");
+                assert_eq!(reply.app_version, APP_VERSION);
+                assert!(reply.reply);
+            }
+            other => panic!("expected hello reply, got {other:?}"),
+        }
+        match event_rx.recv().await.expect("capabilities event") {
+            NetworkEvent::PeerCapabilitiesNegotiated(capabilities) => {
+                assert_eq!(capabilities.app_version, "9.9.9");
+                assert_eq!(
+                    capabilities.features,
+                    vec![ProtocolFeature::ChunkedTransfer]
+                );
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+        let caches = caches.read().await;
+        assert!(!caches.needs_hello("peer-1"));
+        assert!(caches.lacks_feature("peer-1", ProtocolFeature::LazyFetch));
+        assert!(caches.lacks_feature("peer-2", ProtocolFeature::LazyFetch));
+    }
+
+    #[tokio::test]
+    async fn hello_without_common_version_denies_business_until_reconnect() {
+        let resolver: Arc<dyn ConnectionPolicyResolverPort> = Arc::new(FakeResolver);
+        let caches = Arc::new(RwLock::new(PeerCaches::new()));
+        let (event_tx, mut event_rx) = mpsc::channel(2);
+        let (clipboard_tx, _clipboard_rx) = mpsc::channel(1);
+        let mut hello = HelloMessage::local("peer-1".to_string(), "9.9.9".to_string(), true);
+        hello.protocol_versions = vec!["9.0.0".to_string()];
+
+        let reply = handle_business_payload(
+            caches.clone(),
+            event_tx.clone(),
+            clipboard_tx,
+            &resolver,
+            None,
+            "local-device",
+            "peer-1".to_string(),
+            ProtocolMessage::Hello(hello).to_bytes().unwrap(),
+        )
+        .await;
+        assert!(reply.is_none());
+        match event_rx.recv().await.expect("protocol denied event") {
+            NetworkEvent::ProtocolDenied { reason, .. } => {
+                assert_eq!(reason, ProtocolDenyReason::NotSupported)
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+
+        let result = check_business_allowed(
+            &resolver,
+            &caches,
+            &event_tx,
+            "peer-1",
+            ProtocolDirection::Outbound,
+        )
+        .await;
+        assert!(result.is_err());
+        match event_rx.recv().await.expect("protocol denied event") {
+            NetworkEvent::ProtocolDenied {
+                reason, direction, ..
+            } => {
+                assert_eq!(reason, ProtocolDenyReason::NotSupported);
+                assert_eq!(direction, ProtocolDirection::Outbound);
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+
+        caches.write().await.mark_unreachable("peer-1");
+        assert!(caches.read().await.needs_hello("peer-1"));
+    }
+
+    #[test]
+    fn heartbeat_without_rtt_keeps_last_measurement() {
+        let mut caches = PeerCaches::new();
+        let now = Utc::now();
+
+        caches.record_heartbeat("peer-1", now, Some(12));
+        caches.record_heartbeat("peer-1", now + chrono::Duration::seconds(15), None);
+
+        let liveness = caches.liveness.get("peer-1").unwrap();
+        assert_eq!(liveness.rtt_ms, Some(12));
+        assert_eq!(
+            liveness.last_heartbeat_at,
+            now + chrono::Duration::seconds(15)
+        );
+    }
+
     #[tokio::test]
     async fn announce_device_name_queues_command() {
         let adapter = Libp2pNetworkAdapter::new(
             Arc::new(TestIdentityStore::default()),
+            "local-device".to_string(),
             Arc::new(FakeResolver),
+            Arc::new(TestSettings),
+            Arc::new(InMemoryEncryptionSessionPort::new()),
+            std::env::temp_dir().join(format!("uc-transfer-test-{}", uuid::Uuid::new_v4())),
         )
         .expect("create adapter");
 
@@ -1597,9 +3635,7 @@ mod tests {
             BusinessCommand::AnnounceDeviceName { device_name } => {
                 assert_eq!(device_name, "Desk");
             }
-            BusinessCommand::SendClipboard { .. } => {
-                panic!("unexpected clipboard command")
-            }
+            other => panic!("unexpected command: {other:?}"),
         }
     }
 
@@ -1626,14 +3662,128 @@ mod tests {
         assert_eq!(response, payload);
     }
 
+    /// Trusted until a mismatching key is presented, then quarantined.
+    struct PinningResolver {
+        quarantined: std::sync::atomic::AtomicBool,
+    }
+
+    #[async_trait::async_trait]
+    impl ConnectionPolicyResolverPort for PinningResolver {
+        async fn resolve_for_peer(
+            &self,
+            _peer_id: &uc_core::PeerId,
+        ) -> Result<ResolvedConnectionPolicy, ConnectionPolicyResolverError> {
+            let state = if self.quarantined.load(Ordering::SeqCst) {
+                PairingState::Quarantined
+            } else {
+                PairingState::Trusted
+            };
+            Ok(ResolvedConnectionPolicy {
+                pairing_state: state.clone(),
+                allowed: ConnectionPolicy::allowed_protocols(state),
+            })
+        }
+
+        async fn verify_peer_identity(
+            &self,
+            _peer_id: &uc_core::PeerId,
+            device_id: &str,
+            identity_pubkey: &[u8],
+        ) -> Result<IdentityCheck, ConnectionPolicyResolverError> {
+            assert_eq!(device_id, "device-1");
+            assert_eq!(identity_pubkey.len(), 32);
+            self.quarantined.store(true, Ordering::SeqCst);
+            Ok(IdentityCheck::Mismatch {
+                expected_fingerprint: "AAAA-BBBB-CCCC-DDDD".to_string(),
+                presented_fingerprint: "EEEE-FFFF-GGGG-HHHH".to_string(),
+            })
+        }
+    }
+
+    #[test]
+    fn identity_pubkey_is_recovered_from_ed25519_peer_id() {
+        let keypair = identity::Keypair::generate_ed25519();
+        let expected = keypair
+            .public()
+            .try_into_ed25519()
+            .expect("ed25519 key")
+            .to_bytes()
+            .to_vec();
+        let peer_id = PeerId::from(keypair.public()).to_string();
+
+        assert_eq!(identity_pubkey_of(&peer_id), Some(expected));
+        assert_eq!(identity_pubkey_of("peer-1"), None);
+    }
+
+    #[tokio::test]
+    async fn hello_with_mismatched_identity_quarantines_and_denies_business() {
+        let resolver: Arc<dyn ConnectionPolicyResolverPort> = Arc::new(PinningResolver {
+            quarantined: std::sync::atomic::AtomicBool::new(false),
+        });
+        let caches = Arc::new(RwLock::new(PeerCaches::new()));
+        let (event_tx, mut event_rx) = mpsc::channel(4);
+        let (clipboard_tx, _clipboard_rx) = mpsc::channel(1);
+        let peer_id = PeerId::from(identity::Keypair::generate_ed25519().public()).to_string();
+        let hello = HelloMessage::local("device-1".to_string(), "9.9.9".to_string(), false);
+
+        let reply = handle_business_payload(
+            caches.clone(),
+            event_tx.clone(),
+            clipboard_tx,
+            &resolver,
+            None,
+            "local-device",
+            peer_id.clone(),
+            ProtocolMessage::Hello(hello).to_bytes().unwrap(),
+        )
+        .await;
+        assert!(reply.is_none());
+        assert!(caches.read().await.needs_hello(&peer_id));
+
+        let result = check_business_allowed(
+            &resolver,
+            &caches,
+            &event_tx,
+            &peer_id,
+            ProtocolDirection::Inbound,
+        )
+        .await;
+
+        assert!(result.is_err());
+        match event_rx.recv().await.expect("mismatch event") {
+            NetworkEvent::PeerIdentityMismatch {
+                peer_id: mismatched,
+                ..
+            } => assert_eq!(mismatched, peer_id),
+            other => panic!("expected PeerIdentityMismatch, got {other:?}"),
+        }
+        match event_rx.recv().await.expect("protocol denied event") {
+            NetworkEvent::ProtocolDenied {
+                pairing_state,
+                reason,
+                ..
+            } => {
+                assert_eq!(pairing_state, PairingState::Quarantined);
+                assert_eq!(reason, ProtocolDenyReason::NotTrusted);
+            }
+            other => panic!("expected ProtocolDenied, got {other:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn outbound_business_denied_emits_event() {
         let resolver: Arc<dyn ConnectionPolicyResolverPort> = Arc::new(PendingResolver);
+        let caches = RwLock::new(PeerCaches::new());
         let (event_tx, mut event_rx) = mpsc::channel(1);
 
-        let result =
-            check_business_allowed(&resolver, &event_tx, "peer-1", ProtocolDirection::Outbound)
-                .await;
+        let result = check_business_allowed(
+            &resolver,
+            &caches,
+            &event_tx,
+            "peer-1",
+            ProtocolDirection::Outbound,
+        )
+        .await;
 
         assert!(result.is_err());
 
@@ -1656,11 +3806,17 @@ mod tests {
     #[tokio::test]
     async fn inbound_business_denied_drops_stream_and_emits_event() {
         let resolver: Arc<dyn ConnectionPolicyResolverPort> = Arc::new(PendingResolver);
+        let caches = RwLock::new(PeerCaches::new());
         let (event_tx, mut event_rx) = mpsc::channel(1);
 
-        let result =
-            check_business_allowed(&resolver, &event_tx, "peer-2", ProtocolDirection::Inbound)
-                .await;
+        let result = check_business_allowed(
+            &resolver,
+            &caches,
+            &event_tx,
+            "peer-2",
+            ProtocolDirection::Inbound,
+        )
+        .await;
 
         assert!(result.is_err());
 
@@ -1711,7 +3867,11 @@ mod tests {
     async fn send_clipboard_opens_business_stream() {
         let adapter = Libp2pNetworkAdapter::new(
             Arc::new(TestIdentityStore::default()),
+            "local-device".to_string(),
             Arc::new(FakeResolver),
+            Arc::new(TestSettings),
+            Arc::new(InMemoryEncryptionSessionPort::new()),
+            std::env::temp_dir().join(format!("uc-transfer-test-{}", uuid::Uuid::new_v4())),
         )
         .expect("create adapter");
         let payload = vec![1, 2, 3, 4];
@@ -1729,9 +3889,7 @@ mod tests {
                 assert_eq!(peer_id.as_str(), "peer-2");
                 assert_eq!(data, payload);
             }
-            BusinessCommand::AnnounceDeviceName { .. } => {
-                panic!("unexpected announce command")
-            }
+            other => panic!("unexpected command: {other:?}"),
         }
     }
 
@@ -1739,7 +3897,11 @@ mod tests {
     async fn subscribe_clipboard_receiver_is_open() {
         let adapter = Libp2pNetworkAdapter::new(
             Arc::new(TestIdentityStore::default()),
+            "local-device".to_string(),
             Arc::new(FakeResolver),
+            Arc::new(TestSettings),
+            Arc::new(InMemoryEncryptionSessionPort::new()),
+            std::env::temp_dir().join(format!("uc-transfer-test-{}", uuid::Uuid::new_v4())),
         )
         .expect("create adapter");
 
@@ -1755,7 +3917,11 @@ mod tests {
     fn adapter_exposes_raw_identity_pubkey() {
         let adapter = Libp2pNetworkAdapter::new(
             Arc::new(TestIdentityStore::default()),
+            "local-device".to_string(),
             Arc::new(FakeResolver),
+            Arc::new(TestSettings),
+            Arc::new(InMemoryEncryptionSessionPort::new()),
+            std::env::temp_dir().join(format!("uc-transfer-test-{}", uuid::Uuid::new_v4())),
         )
         .expect("create adapter");
 
@@ -1781,16 +3947,28 @@ mod tests {
     async fn mdns_e2e_discovers_peers() {
         let adapter_a = Libp2pNetworkAdapter::new(
             Arc::new(TestIdentityStore::default()),
+            "local-device".to_string(),
             Arc::new(FakeResolver),
+            Arc::new(TestSettings),
+            Arc::new(InMemoryEncryptionSessionPort::new()),
+            std::env::temp_dir().join(format!("uc-transfer-test-{}", uuid::Uuid::new_v4())),
         )
         .expect("create adapter a");
         let adapter_b = Libp2pNetworkAdapter::new(
             Arc::new(TestIdentityStore::default()),
+            "local-device".to_string(),
             Arc::new(FakeResolver),
+            Arc::new(TestSettings),
+            Arc::new(InMemoryEncryptionSessionPort::new()),
+            std::env::temp_dir().join(format!("uc-transfer-test-{}", uuid::Uuid::new_v4())),
         )
         .expect("create adapter b");
-        adapter_a.spawn_swarm().expect("start swarm a");
-        adapter_b.spawn_swarm().expect("start swarm b");
+        adapter_a
+            .spawn_swarm(&NetworkSettings::default(), None)
+            .expect("start swarm a");
+        adapter_b
+            .spawn_swarm(&NetworkSettings::default(), None)
+            .expect("start swarm b");
 
         let peer_a = adapter_a.local_peer_id();
         let peer_b = adapter_b.local_peer_id();
@@ -1819,6 +3997,58 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn resolve_dial_address_resolves_dns_hosts() {
+        let ip: Multiaddr = "/ip4/10.0.0.5/tcp/4001".parse().expect("ip addr");
+        assert_eq!(
+            resolve_dial_address("/ip4/10.0.0.5/tcp/4001")
+                .await
+                .expect("ip address"),
+            ip
+        );
+
+        let resolved = resolve_dial_address("/dns/localhost/tcp/4001")
+            .await
+            .expect("resolve localhost");
+        let components: Vec<_> = resolved.iter().collect();
+        assert!(matches!(
+            components.first(),
+            Some(Protocol::Ip4(_) | Protocol::Ip6(_))
+        ));
+        assert_eq!(components.get(1), Some(&Protocol::Tcp(4001)));
+
+        assert!(resolve_dial_address("/dns/localhost").await.is_err());
+    }
+
+    #[test]
+    fn parse_peer_multiaddr_requires_dialable_address() {
+        let peer = PeerId::random();
+        let with_peer = format!("/ip4/10.0.0.5/tcp/4001/p2p/{peer}");
+        assert_eq!(
+            parse_peer_multiaddr(&with_peer)
+                .expect("address with peer id")
+                .to_string(),
+            with_peer
+        );
+        assert!(parse_peer_multiaddr("/dns/office-mac.local/tcp/4001").is_ok());
+        assert!(parse_peer_multiaddr("/ip4/10.0.0.5/udp/4001/quic-v1").is_ok());
+
+        let peer_not_last = format!("/ip4/10.0.0.5/tcp/4001/p2p/{peer}/tcp/4002");
+        for input in [
+            "/foo/bar",
+            "/ip4/10.0.0.5",
+            "/ip4/10.0.0.5/tcp/0",
+            "/tcp/4001/ip4/10.0.0.5",
+            "/ip4/10.0.0.5/tcp/4001/p2p/not-a-peer-id",
+            peer_not_last.as_str(),
+        ] {
+            assert!(
+                parse_peer_multiaddr(input).is_err(),
+                "{input} should be rejected"
+            );
+        }
+    }
+
     #[test]
     fn try_send_event_reports_backpressure() {
         let (event_tx, _event_rx) = mpsc::channel(1);
@@ -1838,8 +4068,6 @@ mod tests {
     #[tokio::test]
     async fn listen_on_failure_emits_error_event_and_returns_err() {
         let keypair = identity::Keypair::generate_ed25519();
-        let local_peer_id = PeerId::from(keypair.public());
-        let behaviour = Libp2pBehaviour::new(local_peer_id).expect("behaviour");
         let mut swarm = SwarmBuilder::with_existing_identity(keypair)
             .with_tokio()
             .with_tcp(
@@ -1848,7 +4076,11 @@ mod tests {
                 yamux::Config::default,
             )
             .expect("tcp config")
-            .with_behaviour(move |_| behaviour)
+            .with_relay_client(noise::Config::new, yamux::Config::default)
+            .expect("relay config")
+            .with_behaviour(|keypair, relay_client| {
+                Libp2pBehaviour::build(keypair, relay_client, false)
+            })
             .expect("attach behaviour")
             .build();
 
@@ -21,6 +21,7 @@ pub mod in_memory_watcher_control;
 pub mod libp2p_network;
 pub mod network;
 pub mod pairing_stream;
+pub mod transfer_stream;
 pub mod ui;
 
 pub use autostart::PlaceholderAutostartPort;
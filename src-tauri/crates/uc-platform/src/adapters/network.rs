@@ -5,11 +5,13 @@ use anyhow::Result;
 use async_trait::async_trait;
 use libp2p::PeerId;
 use uc_core::network::{
-    ClipboardMessage, ConnectedPeer, DiscoveredPeer, NetworkEvent, PairingMessage,
+    BlobManifest, ClipboardMessage, ConnectedPeer, DiscoveredPeer, LocalNetworkInfo, NetworkEvent,
+    PairingMessage, PeerCapabilities, PeerLiveness,
 };
 use uc_core::ports::IdentityStorePort;
 use uc_core::ports::{NetworkControlPort, NetworkPort};
 
+use super::libp2p_network::parse_peer_multiaddr;
 use crate::identity_store::load_or_create_identity;
 
 /// Placeholder network port implementation
@@ -42,15 +44,21 @@ impl NetworkPort for PlaceholderNetworkPort {
         ))
     }
 
-    async fn broadcast_clipboard(&self, _encrypted_data: Vec<u8>) -> Result<()> {
+    async fn subscribe_clipboard(&self) -> Result<tokio::sync::mpsc::Receiver<ClipboardMessage>> {
+        let (_tx, rx) = tokio::sync::mpsc::channel(1);
+        Ok(rx)
+    }
+
+    async fn fetch_blob(&self, _peer_id: &str, _manifest: BlobManifest) -> Result<Vec<u8>> {
         Err(anyhow::anyhow!(
-            "NetworkPort::broadcast_clipboard not implemented yet"
+            "NetworkPort::fetch_blob not implemented yet"
         ))
     }
 
-    async fn subscribe_clipboard(&self) -> Result<tokio::sync::mpsc::Receiver<ClipboardMessage>> {
-        let (_tx, rx) = tokio::sync::mpsc::channel(1);
-        Ok(rx)
+    async fn offer_blob(&self, _bytes: Vec<u8>, _peer_ids: &[String]) -> Result<BlobManifest> {
+        Err(anyhow::anyhow!(
+            "NetworkPort::offer_blob not implemented yet"
+        ))
     }
 
     // === Peer operations ===
@@ -73,6 +81,36 @@ impl NetworkPort for PlaceholderNetworkPort {
         ))
     }
 
+    async fn get_peer_liveness(&self) -> Result<Vec<PeerLiveness>> {
+        Ok(Vec::new())
+    }
+
+    async fn get_peer_capabilities(&self) -> Result<Vec<PeerCapabilities>> {
+        Ok(Vec::new())
+    }
+
+    // === Manual addressing ===
+
+    async fn dial_address(&self, _address: String) -> Result<()> {
+        Err(anyhow::anyhow!(
+            "NetworkPort::dial_address not implemented yet"
+        ))
+    }
+
+    fn parse_peer_address(&self, address: &str) -> Result<String> {
+        Ok(parse_peer_multiaddr(address)?.to_string())
+    }
+
+    async fn dial_peer(&self, _peer_id: String, _addresses: Vec<String>) -> Result<()> {
+        Err(anyhow::anyhow!(
+            "NetworkPort::dial_peer not implemented yet"
+        ))
+    }
+
+    async fn get_local_network_info(&self) -> Result<LocalNetworkInfo> {
+        Ok(LocalNetworkInfo::default())
+    }
+
     // === Pairing operations ===
 
     async fn open_pairing_session(&self, _peer_id: String, _session_id: String) -> Result<()> {
@@ -11,7 +11,7 @@ use tokio::sync::{mpsc, watch, Mutex as AsyncMutex, OwnedSemaphorePermit, Semaph
 use tokio::time::{timeout, Duration};
 use tokio_util::compat::FuturesAsyncReadCompatExt;
 use tracing::{info_span, Instrument, Span};
-use uc_core::network::{NetworkEvent, PairingMessage, ProtocolId};
+use uc_core::network::{NetworkEvent, PairingMessage, ProtocolDirection, ProtocolId};
 use uc_core::ports::observability::TraceMetadata;
 
 pub const MAX_PAIRING_CONCURRENCY: usize = 16;
@@ -603,11 +603,22 @@ async fn emit_pairing_event(
     peer_id: &str,
     message: PairingMessage,
 ) -> Result<()> {
-    event_tx
-        .send(NetworkEvent::PairingMessageReceived {
+    // A revocation and its acknowledgement are not part of any pairing session.
+    let event = match message {
+        PairingMessage::Revoke(_) => NetworkEvent::PairingRevoked {
+            peer_id: peer_id.to_string(),
+            direction: ProtocolDirection::Inbound,
+        },
+        PairingMessage::RevokeAck(_) => NetworkEvent::PairingRevocationAcknowledged {
+            peer_id: peer_id.to_string(),
+        },
+        message => NetworkEvent::PairingMessageReceived {
             peer_id: peer_id.to_string(),
             message,
-        })
+        },
+    };
+    event_tx
+        .send(event)
         .await
         .map_err(|err| anyhow!("failed to emit pairing message: {err}"))
 }
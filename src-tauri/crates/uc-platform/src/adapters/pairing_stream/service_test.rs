@@ -3,7 +3,10 @@ use super::service::{PairingStreamConfig, PairingStreamService};
 use tokio::io::AsyncWriteExt;
 use tokio::sync::mpsc;
 use tokio::time::{timeout, Duration};
-use uc_core::network::{NetworkEvent, PairingMessage, PairingRequest};
+use uc_core::network::{
+    NetworkEvent, PairingMessage, PairingRequest, PairingRevoke, PairingRevokeAck,
+    ProtocolDirection,
+};
 
 #[tokio::test]
 async fn shutdown_drains_outbound_queue() {
@@ -21,6 +24,7 @@ async fn shutdown_drains_outbound_queue() {
         peer_id: "peer-drain".to_string(),
         identity_pubkey: vec![5; 32],
         nonce: vec![6; 16],
+        invitation_proof: None,
     });
     let payload = serde_json::to_vec(&init_msg).expect("serialize");
     write_length_prefixed(&mut client, &payload)
@@ -73,6 +77,7 @@ async fn inbound_stream_emits_pairing_message() {
         peer_id: "peer-a".to_string(),
         identity_pubkey: vec![1; 32],
         nonce: vec![2; 16],
+        invitation_proof: None,
     });
     let payload = serde_json::to_vec(&message).expect("serialize message");
     let write_task =
@@ -99,6 +104,66 @@ async fn inbound_stream_emits_pairing_message() {
     result.expect("pairing stream ok");
 }
 
+#[tokio::test]
+async fn inbound_revoke_emits_pairing_revoked() {
+    let (event_tx, mut event_rx) = mpsc::channel(1);
+    let service = PairingStreamService::for_tests(event_tx, PairingStreamConfig::default());
+    let (mut client, server) = tokio::io::duplex(64 * 1024);
+
+    let handle: tokio::task::JoinHandle<anyhow::Result<()>> =
+        service.handle_incoming_stream("peer-5".to_string(), server);
+    let message = PairingMessage::Revoke(PairingRevoke {
+        session_id: "revoke-1".to_string(),
+        revoked_at: chrono::Utc::now(),
+    });
+    let payload = serde_json::to_vec(&message).expect("serialize message");
+    write_length_prefixed(&mut client, &payload)
+        .await
+        .expect("write payload");
+
+    let event = timeout(Duration::from_secs(2), event_rx.recv())
+        .await
+        .expect("event timeout")
+        .expect("event");
+    assert!(matches!(
+        event,
+        NetworkEvent::PairingRevoked { peer_id, direction: ProtocolDirection::Inbound }
+            if peer_id == "peer-5"
+    ));
+    client.shutdown().await.expect("shutdown");
+
+    let _result = handle.await.expect("pairing stream task");
+}
+
+#[tokio::test]
+async fn inbound_revoke_ack_emits_acknowledgement() {
+    let (event_tx, mut event_rx) = mpsc::channel(1);
+    let service = PairingStreamService::for_tests(event_tx, PairingStreamConfig::default());
+    let (mut client, server) = tokio::io::duplex(64 * 1024);
+
+    let handle: tokio::task::JoinHandle<anyhow::Result<()>> =
+        service.handle_incoming_stream("peer-6".to_string(), server);
+    let message = PairingMessage::RevokeAck(PairingRevokeAck {
+        session_id: "revoke-ack-1".to_string(),
+    });
+    let payload = serde_json::to_vec(&message).expect("serialize message");
+    write_length_prefixed(&mut client, &payload)
+        .await
+        .expect("write payload");
+
+    let event = timeout(Duration::from_secs(2), event_rx.recv())
+        .await
+        .expect("event timeout")
+        .expect("event");
+    assert!(matches!(
+        event,
+        NetworkEvent::PairingRevocationAcknowledged { peer_id } if peer_id == "peer-6"
+    ));
+    client.shutdown().await.expect("shutdown");
+
+    let _result = handle.await.expect("pairing stream task");
+}
+
 #[tokio::test]
 async fn oversize_frame_closes_session() {
     let (event_tx, mut event_rx) = mpsc::channel(1);
@@ -137,6 +202,7 @@ async fn early_eof_does_not_panic_session_task() {
         peer_id: "peer-b".to_string(),
         identity_pubkey: vec![3; 32],
         nonce: vec![4; 16],
+        invitation_proof: None,
     });
     let payload = serde_json::to_vec(&message).expect("serialize message");
     write_length_prefixed(&mut client, &payload)
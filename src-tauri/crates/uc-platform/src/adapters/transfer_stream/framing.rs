@@ -0,0 +1,99 @@
+//! Chunk frames for transfer streams.
+//!
+//! Requests reuse the length-prefixed JSON frames of the pairing stream.
+//! Each requested chunk is answered by one length-prefixed frame laid out
+//! as `[tag: u8][index: u32 BE][data]`, where tag `0` carries the chunk and
+//! tag `1` means the sender no longer has the blob.
+
+use anyhow::{anyhow, Result};
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use crate::adapters::pairing_stream::framing::{read_length_prefixed, write_length_prefixed};
+
+const TAG_CHUNK: u8 = 0;
+const TAG_MISSING: u8 = 1;
+const HEADER_BYTES: usize = 5;
+
+/// Maximum size of a JSON chunk request frame.
+pub const MAX_REQUEST_FRAME_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ChunkFrame {
+    Chunk { index: u32, data: Vec<u8> },
+    Missing { index: u32 },
+}
+
+pub async fn write_chunk_frame<W>(writer: &mut W, frame: &ChunkFrame) -> Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let (tag, index, data) = match frame {
+        ChunkFrame::Chunk { index, data } => (TAG_CHUNK, *index, data.as_slice()),
+        ChunkFrame::Missing { index } => (TAG_MISSING, *index, &[][..]),
+    };
+    let mut payload = Vec::with_capacity(HEADER_BYTES + data.len());
+    payload.push(tag);
+    payload.extend_from_slice(&index.to_be_bytes());
+    payload.extend_from_slice(data);
+    write_length_prefixed(writer, &payload).await
+}
+
+/// Read one chunk frame; `max_chunk_bytes` bounds the data part.
+pub async fn read_chunk_frame<R>(reader: &mut R, max_chunk_bytes: usize) -> Result<ChunkFrame>
+where
+    R: AsyncRead + Unpin,
+{
+    let payload = read_length_prefixed(reader, HEADER_BYTES + max_chunk_bytes)
+        .await?
+        .ok_or_else(|| anyhow!("transfer stream closed by peer"))?;
+    if payload.len() < HEADER_BYTES {
+        return Err(anyhow!("chunk frame too short: {} bytes", payload.len()));
+    }
+    let index = u32::from_be_bytes([payload[1], payload[2], payload[3], payload[4]]);
+    match payload[0] {
+        TAG_CHUNK => Ok(ChunkFrame::Chunk {
+            index,
+            data: payload[HEADER_BYTES..].to_vec(),
+        }),
+        TAG_MISSING => Ok(ChunkFrame::Missing { index }),
+        tag => Err(anyhow!("unknown chunk frame tag: {tag}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn chunk_frames_round_trip() {
+        let (mut client, mut server) = tokio::io::duplex(64 * 1024);
+        let chunk = ChunkFrame::Chunk {
+            index: 7,
+            data: vec![1, 2, 3],
+        };
+
+        write_chunk_frame(&mut client, &chunk).await.unwrap();
+        write_chunk_frame(&mut client, &ChunkFrame::Missing { index: 8 })
+            .await
+            .unwrap();
+
+        assert_eq!(read_chunk_frame(&mut server, 16).await.unwrap(), chunk);
+        assert_eq!(
+            read_chunk_frame(&mut server, 16).await.unwrap(),
+            ChunkFrame::Missing { index: 8 }
+        );
+    }
+
+    #[tokio::test]
+    async fn oversize_chunk_is_rejected() {
+        let (mut client, mut server) = tokio::io::duplex(64 * 1024);
+        let chunk = ChunkFrame::Chunk {
+            index: 0,
+            data: vec![0; 32],
+        };
+
+        write_chunk_frame(&mut client, &chunk).await.unwrap();
+
+        assert!(read_chunk_frame(&mut server, 16).await.is_err());
+    }
+}
@@ -0,0 +1,12 @@
+//! Chunked blob transfer over `/uniclipboard/transfer/1.0.0`.
+//!
+//! The sender spools offered blobs to disk in a bounded
+//! [`store::TransferOutbox`] that survives restarts and serves each blob only
+//! to the peers it was offered to; the receiver refuses manifests above
+//! `sync.max_file_size_mb`, pulls missing chunks window by window and writes
+//! verified chunks to a [`store::PartialBlob`] spool file so an interrupted
+//! pull resumes.
+
+pub mod framing;
+pub mod service;
+pub mod store;
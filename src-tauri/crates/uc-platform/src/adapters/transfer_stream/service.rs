@@ -0,0 +1,719 @@
+use super::framing::{read_chunk_frame, write_chunk_frame, ChunkFrame, MAX_REQUEST_FRAME_BYTES};
+use super::store::{PartialBlob, SpooledBlob, TransferOutbox};
+use crate::adapters::pairing_stream::framing::{read_length_prefixed, write_length_prefixed};
+use anyhow::{anyhow, Result};
+use libp2p::{futures::StreamExt, PeerId, StreamProtocol};
+use libp2p_stream as stream;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::{mpsc, Mutex as AsyncMutex};
+use tokio::time::{timeout, Duration};
+use tokio_util::compat::FuturesAsyncReadCompatExt;
+use tracing::{debug, info, warn};
+use uc_core::clipboard::ContentHash;
+use uc_core::network::{
+    BlobManifest, ChunkRequest, NetworkEvent, ProtocolDirection, ProtocolId, ProtocolKind,
+    TransferProgress, TRANSFER_CHUNK_BYTES,
+};
+use uc_core::ports::{ConnectionPolicyResolverPort, SettingsPort};
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// Room above `sync.max_file_size_mb` for the encryption envelope around a
+/// payload that passed the sender's own size filter.
+const ENVELOPE_ALLOWANCE_BYTES: u64 = BYTES_PER_MB;
+
+/// Largest blob accepted when `sync.max_file_size_mb` is 0 (no limit).
+const MAX_TRANSFER_BLOB_BYTES: u64 = 4 * 1024 * BYTES_PER_MB;
+
+#[derive(Debug, Error)]
+pub enum TransferError {
+    #[error("sender no longer has blob {content_hash}")]
+    BlobMissing { content_hash: String },
+}
+
+#[derive(Clone)]
+pub struct TransferConfig {
+    /// Chunks requested per round trip; bounds the data in flight per transfer
+    pub window_chunks: usize,
+    pub chunk_bytes: u32,
+    pub idle_timeout: Duration,
+    /// Pull attempts before giving up; the delay doubles after each failure
+    pub max_attempts: u32,
+    pub retry_delay: Duration,
+    pub outbox_max_bytes: u64,
+    /// Directory holding offered blobs and partially received ones
+    pub spool_dir: PathBuf,
+}
+
+impl Default for TransferConfig {
+    fn default() -> Self {
+        Self {
+            window_chunks: 8,
+            chunk_bytes: TRANSFER_CHUNK_BYTES,
+            idle_timeout: Duration::from_secs(30),
+            max_attempts: 6,
+            retry_delay: Duration::from_secs(2),
+            outbox_max_bytes: 256 * BYTES_PER_MB,
+            spool_dir: std::env::temp_dir().join("uniclipboard-transfer"),
+        }
+    }
+}
+
+impl TransferConfig {
+    fn outbox_dir(&self) -> PathBuf {
+        self.spool_dir.join("outbox")
+    }
+
+    fn partial_dir(&self) -> PathBuf {
+        self.spool_dir.join("partial")
+    }
+}
+
+#[derive(Clone)]
+pub struct TransferService {
+    inner: Arc<TransferServiceInner>,
+}
+
+struct TransferServiceInner {
+    control: AsyncMutex<stream::Control>,
+    event_tx: mpsc::Sender<NetworkEvent>,
+    policy_resolver: Arc<dyn ConnectionPolicyResolverPort>,
+    settings: Arc<dyn SettingsPort>,
+    outbox: Mutex<TransferOutbox>,
+    partials: Mutex<HashMap<ContentHash, Arc<AsyncMutex<PartialBlob>>>>,
+    config: TransferConfig,
+}
+
+impl TransferService {
+    pub fn new(
+        control: stream::Control,
+        event_tx: mpsc::Sender<NetworkEvent>,
+        policy_resolver: Arc<dyn ConnectionPolicyResolverPort>,
+        settings: Arc<dyn SettingsPort>,
+        config: TransferConfig,
+    ) -> Self {
+        // Offers outlive the process, partial transfers do not: whatever an
+        // earlier run left half received is stale.
+        let partial_dir = config.partial_dir();
+        if let Err(err) = std::fs::remove_dir_all(&partial_dir) {
+            if err.kind() != std::io::ErrorKind::NotFound {
+                warn!(
+                    "failed to clear transfer spool {}: {err}",
+                    partial_dir.display()
+                );
+            }
+        }
+        Self {
+            inner: Arc::new(TransferServiceInner {
+                control: AsyncMutex::new(control),
+                event_tx,
+                policy_resolver,
+                settings,
+                outbox: Mutex::new(TransferOutbox::open(
+                    config.outbox_dir(),
+                    config.outbox_max_bytes,
+                )),
+                partials: Mutex::new(HashMap::new()),
+                config,
+            }),
+        }
+    }
+
+    pub fn spawn_accept_loop(&self) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            service.run_accept_loop().await;
+        });
+    }
+
+    async fn run_accept_loop(&self) {
+        let mut incoming = {
+            let mut control = self.inner.control.lock().await;
+            match control.accept(StreamProtocol::new(ProtocolId::Transfer.as_str())) {
+                Ok(incoming) => incoming,
+                Err(err) => {
+                    warn!("failed to accept transfer stream: {err}");
+                    return;
+                }
+            }
+        };
+        while let Some((peer, stream)) = incoming.next().await {
+            let peer_id = peer.to_string();
+            let service = self.clone();
+            tokio::spawn(async move {
+                if !service.business_allowed(&peer_id).await {
+                    warn!(peer_id = %peer_id, "transfer stream denied for untrusted peer");
+                    return;
+                }
+                let mut stream = stream.compat();
+                if let Err(err) = service.serve(&peer_id, &mut stream).await {
+                    debug!(peer_id = %peer_id, "transfer stream ended: {err}");
+                }
+            });
+        }
+    }
+
+    async fn business_allowed(&self, peer_id: &str) -> bool {
+        matches!(
+            self.inner
+                .policy_resolver
+                .resolve_for_peer(&uc_core::PeerId::from(peer_id))
+                .await,
+            Ok(resolved) if resolved.allowed.allows(ProtocolKind::Business)
+        )
+    }
+
+    /// Spool `bytes` so `peer_ids` can pull them and return their manifest.
+    pub fn offer(&self, bytes: &[u8], peer_ids: &[String]) -> Result<BlobManifest> {
+        let mut outbox = self
+            .inner
+            .outbox
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        outbox.insert(bytes, self.inner.config.chunk_bytes, peer_ids)
+    }
+
+    /// Look up a blob offered to `peer_id`; anything else is answered as missing.
+    fn find_blob(
+        &self,
+        content_hash: &ContentHash,
+        peer_id: &str,
+    ) -> Option<(BlobManifest, PathBuf)> {
+        self.inner
+            .outbox
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(content_hash, peer_id)
+    }
+
+    /// Answer chunk requests on one stream until the peer closes it.
+    pub async fn serve<S>(&self, peer_id: &str, stream: &mut S) -> Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        loop {
+            let frame = match timeout(
+                self.inner.config.idle_timeout,
+                read_length_prefixed(stream, MAX_REQUEST_FRAME_BYTES),
+            )
+            .await
+            {
+                Ok(Ok(Some(frame))) => frame,
+                Ok(Ok(None)) => return Ok(()),
+                Ok(Err(err)) => return Err(err),
+                Err(_) => return Err(anyhow!("transfer stream idle timeout")),
+            };
+            let request: ChunkRequest = serde_json::from_slice(&frame)?;
+            self.serve_request(peer_id, &request, stream).await?;
+        }
+    }
+
+    async fn serve_request<S>(
+        &self,
+        peer_id: &str,
+        request: &ChunkRequest,
+        stream: &mut S,
+    ) -> Result<()>
+    where
+        S: AsyncWrite + Unpin,
+    {
+        if request.indices.len() > self.inner.config.window_chunks * 4 {
+            return Err(anyhow!(
+                "chunk request too large: {} chunks",
+                request.indices.len()
+            ));
+        }
+        let mut blob = match self.find_blob(&request.content_hash, peer_id) {
+            // An entry evicted since the lookup is answered as missing.
+            Some((manifest, path)) => SpooledBlob::open(&path)
+                .await
+                .ok()
+                .map(|file| (manifest, file)),
+            None => None,
+        };
+        let mut served_to = 0;
+        for &index in &request.indices {
+            let frame = match blob.as_mut() {
+                Some((manifest, file)) => match manifest.chunk_span(index) {
+                    Some((offset, len)) => {
+                        let data = file.read_span(offset, len).await?;
+                        served_to = served_to.max(offset + len as u64);
+                        ChunkFrame::Chunk { index, data }
+                    }
+                    None => ChunkFrame::Missing { index },
+                },
+                None => ChunkFrame::Missing { index },
+            };
+            write_chunk_frame(stream, &frame).await?;
+        }
+        if let Some((manifest, _)) = &blob {
+            self.emit_progress(peer_id, manifest, ProtocolDirection::Outbound, served_to);
+        }
+        Ok(())
+    }
+
+    /// Pull the blob described by `manifest` from `peer_id`.
+    ///
+    /// Verified chunks survive failed attempts, so each retry (and any later
+    /// fetch of the same blob) only requests what is still missing.
+    pub async fn fetch(&self, peer_id: &str, manifest: BlobManifest) -> Result<Vec<u8>> {
+        let content_hash = manifest.content_hash.clone();
+        let partial = match self.partial_for(manifest).await {
+            Ok(partial) => partial,
+            Err(err) => {
+                self.emit_failed(peer_id, &content_hash, &err).await;
+                return Err(err);
+            }
+        };
+        let mut partial = partial.lock().await;
+        let config = &self.inner.config;
+        let mut attempt = 0;
+        while !partial.is_complete() {
+            let Err(err) = self.pull_once(peer_id, &mut partial).await else {
+                continue;
+            };
+            attempt += 1;
+            let missing = err.downcast_ref::<TransferError>().is_some();
+            if missing || attempt >= config.max_attempts {
+                if missing {
+                    self.forget_partial(&content_hash);
+                }
+                self.emit_failed(peer_id, &content_hash, &err).await;
+                return Err(err);
+            }
+            let delay = config.retry_delay * 2u32.saturating_pow(attempt - 1);
+            info!(
+                peer_id = %peer_id,
+                content_hash = %content_hash,
+                attempt,
+                received_bytes = partial.received_bytes(),
+                "transfer interrupted, resuming in {delay:?}: {err}"
+            );
+            tokio::time::sleep(delay).await;
+        }
+
+        self.forget_partial(&content_hash);
+        partial.assemble().await
+    }
+
+    /// Largest blob this device accepts, from `sync.max_file_size_mb`.
+    async fn max_blob_bytes(&self) -> Result<u64> {
+        let settings = self
+            .inner
+            .settings
+            .load()
+            .await
+            .map_err(|err| anyhow!("failed to load transfer size limit: {err}"))?;
+        Ok(match u64::from(settings.sync.max_file_size_mb) {
+            0 => MAX_TRANSFER_BLOB_BYTES,
+            mb => (mb * BYTES_PER_MB + ENVELOPE_ALLOWANCE_BYTES).min(MAX_TRANSFER_BLOB_BYTES),
+        })
+    }
+
+    async fn partial_for(&self, manifest: BlobManifest) -> Result<Arc<AsyncMutex<PartialBlob>>> {
+        if let Some(partial) = self.existing_partial(&manifest.content_hash) {
+            return Ok(partial);
+        }
+        let max_bytes = self.max_blob_bytes().await?;
+        let content_hash = manifest.content_hash.clone();
+        let partial =
+            PartialBlob::create(manifest, &self.inner.config.partial_dir(), max_bytes).await?;
+        // A concurrent fetch of the same blob may have won the race; its
+        // partial is kept and this one is dropped with its spool file.
+        Ok(self
+            .inner
+            .partials
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry(content_hash)
+            .or_insert_with(|| Arc::new(AsyncMutex::new(partial)))
+            .clone())
+    }
+
+    fn existing_partial(&self, content_hash: &ContentHash) -> Option<Arc<AsyncMutex<PartialBlob>>> {
+        self.inner
+            .partials
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(content_hash)
+            .cloned()
+    }
+
+    fn forget_partial(&self, content_hash: &ContentHash) {
+        self.inner
+            .partials
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(content_hash);
+    }
+
+    async fn pull_once(&self, peer_id: &str, partial: &mut PartialBlob) -> Result<()> {
+        let peer = peer_id
+            .parse::<PeerId>()
+            .map_err(|err| anyhow!("invalid peer id for transfer stream: {err}"))?;
+        let stream = {
+            let mut control = self.inner.control.lock().await;
+            control
+                .open_stream(peer, StreamProtocol::new(ProtocolId::Transfer.as_str()))
+                .await
+                .map_err(|err| anyhow!("transfer stream open failed: {err}"))?
+        };
+        let mut stream = stream.compat();
+        self.pull_over(peer_id, &mut stream, partial).await
+    }
+
+    /// Request the missing chunks of `partial` window by window.
+    pub async fn pull_over<S>(
+        &self,
+        peer_id: &str,
+        stream: &mut S,
+        partial: &mut PartialBlob,
+    ) -> Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let missing = partial.missing();
+        let content_hash = partial.manifest().content_hash.clone();
+        let chunk_bytes = partial.manifest().chunk_bytes as usize;
+        for window in missing.chunks(self.inner.config.window_chunks.max(1)) {
+            let request = ChunkRequest {
+                content_hash: content_hash.clone(),
+                indices: window.to_vec(),
+            };
+            write_length_prefixed(stream, &serde_json::to_vec(&request)?).await?;
+            for _ in window {
+                let frame = timeout(
+                    self.inner.config.idle_timeout,
+                    read_chunk_frame(stream, chunk_bytes),
+                )
+                .await
+                .map_err(|_| anyhow!("transfer stream idle timeout"))??;
+                match frame {
+                    ChunkFrame::Chunk { index, data } if window.contains(&index) => {
+                        partial.insert(index, &data).await?;
+                    }
+                    ChunkFrame::Chunk { index, .. } => {
+                        return Err(anyhow!("unexpected chunk {index}"));
+                    }
+                    ChunkFrame::Missing { .. } => {
+                        return Err(TransferError::BlobMissing {
+                            content_hash: content_hash.to_string(),
+                        }
+                        .into());
+                    }
+                }
+            }
+            self.emit_progress(
+                peer_id,
+                partial.manifest(),
+                ProtocolDirection::Inbound,
+                partial.received_bytes(),
+            );
+        }
+        Ok(())
+    }
+
+    fn emit_progress(
+        &self,
+        peer_id: &str,
+        manifest: &BlobManifest,
+        direction: ProtocolDirection,
+        transferred_bytes: u64,
+    ) {
+        let event = NetworkEvent::TransferProgress(TransferProgress {
+            peer_id: peer_id.to_string(),
+            content_hash: manifest.content_hash.to_string(),
+            direction,
+            transferred_bytes,
+            total_bytes: manifest.total_bytes,
+        });
+        // Progress is advisory; drop it rather than stall the transfer.
+        if let Err(err) = self.inner.event_tx.try_send(event) {
+            debug!("dropped transfer progress event: {err}");
+        }
+    }
+
+    async fn emit_failed(&self, peer_id: &str, content_hash: &ContentHash, err: &anyhow::Error) {
+        warn!(peer_id = %peer_id, content_hash = %content_hash, "transfer failed: {err}");
+        let event = NetworkEvent::TransferFailed {
+            peer_id: peer_id.to_string(),
+            content_hash: content_hash.to_string(),
+            error: err.to_string(),
+        };
+        if let Err(err) = self.inner.event_tx.send(event).await {
+            warn!("failed to send TransferFailed event: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uc_core::network::{
+        ConnectionPolicy, IdentityCheck, PairingState, ResolvedConnectionPolicy,
+    };
+    use uc_core::ports::ConnectionPolicyResolverError;
+    use uc_core::settings::model::Settings;
+
+    struct TrustedResolver;
+
+    #[async_trait::async_trait]
+    impl ConnectionPolicyResolverPort for TrustedResolver {
+        async fn resolve_for_peer(
+            &self,
+            _peer_id: &uc_core::PeerId,
+        ) -> Result<ResolvedConnectionPolicy, ConnectionPolicyResolverError> {
+            Ok(ResolvedConnectionPolicy {
+                pairing_state: PairingState::Trusted,
+                allowed: ConnectionPolicy::allowed_protocols(PairingState::Trusted),
+            })
+        }
+
+        async fn verify_peer_identity(
+            &self,
+            _peer_id: &uc_core::PeerId,
+            _device_id: &str,
+            _identity_pubkey: &[u8],
+        ) -> Result<IdentityCheck, ConnectionPolicyResolverError> {
+            Ok(IdentityCheck::Verified)
+        }
+    }
+
+    struct MaxFileSize(u32);
+
+    #[async_trait::async_trait]
+    impl SettingsPort for MaxFileSize {
+        async fn load(&self) -> Result<Settings> {
+            let mut settings = Settings::default();
+            settings.sync.max_file_size_mb = self.0;
+            Ok(settings)
+        }
+
+        async fn save(&self, _settings: &Settings) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    struct TestService {
+        service: TransferService,
+        events: mpsc::Receiver<NetworkEvent>,
+        spool: tempfile::TempDir,
+    }
+
+    fn service_with(window_chunks: usize, max_file_size_mb: u32) -> TestService {
+        let (event_tx, events) = mpsc::channel(64);
+        let spool = tempfile::tempdir().unwrap();
+        let config = TransferConfig {
+            window_chunks,
+            chunk_bytes: 4,
+            spool_dir: spool.path().to_path_buf(),
+            ..TransferConfig::default()
+        };
+        let control = stream::Behaviour::new().new_control();
+        let service = TransferService::new(
+            control,
+            event_tx,
+            Arc::new(TrustedResolver),
+            Arc::new(MaxFileSize(max_file_size_mb)),
+            config,
+        );
+        TestService {
+            service,
+            events,
+            spool,
+        }
+    }
+
+    fn service(window_chunks: usize) -> TestService {
+        service_with(window_chunks, 100)
+    }
+
+    async fn partial_for(manifest: BlobManifest, spool: &tempfile::TempDir) -> PartialBlob {
+        PartialBlob::create(manifest, spool.path(), u64::MAX)
+            .await
+            .unwrap()
+    }
+
+    fn blob() -> Vec<u8> {
+        (0..10u8).collect()
+    }
+
+    #[tokio::test]
+    async fn pull_transfers_all_chunks_and_reports_progress() {
+        let sender = service(2);
+        let TestService {
+            service: receiver,
+            mut events,
+            spool,
+        } = service(2);
+        let manifest = sender
+            .service
+            .offer(&blob(), &["receiver".to_string()])
+            .unwrap();
+        let mut partial = partial_for(manifest, &spool).await;
+        let sender = sender.service.clone();
+        let (mut client, mut server) = tokio::io::duplex(64 * 1024);
+        let serve = tokio::spawn(async move { sender.serve("receiver", &mut server).await });
+
+        receiver
+            .pull_over("sender", &mut client, &mut partial)
+            .await
+            .unwrap();
+        drop(client);
+
+        assert_eq!(partial.assemble().await.unwrap(), blob());
+        serve.await.unwrap().unwrap();
+        let mut last = None;
+        while let Ok(NetworkEvent::TransferProgress(progress)) = events.try_recv() {
+            last = Some(progress);
+        }
+        let last = last.expect("progress event");
+        assert_eq!(last.direction, ProtocolDirection::Inbound);
+        assert_eq!((last.transferred_bytes, last.total_bytes), (10, 10));
+    }
+
+    #[tokio::test]
+    async fn interrupted_pull_resumes_with_missing_chunks_only() {
+        let sender_side = service(1);
+        let receiver_side = service(1);
+        let (sender, receiver) = (&sender_side.service, &receiver_side.service);
+        let manifest = sender.offer(&blob(), &["receiver".to_string()]).unwrap();
+        let mut partial = partial_for(manifest, &receiver_side.spool).await;
+
+        // The sender answers one window and then the connection drops.
+        let (mut client, mut server) = tokio::io::duplex(64 * 1024);
+        let first_sender = sender.clone();
+        tokio::spawn(async move {
+            let frame = read_length_prefixed(&mut server, MAX_REQUEST_FRAME_BYTES)
+                .await
+                .unwrap()
+                .unwrap();
+            let request: ChunkRequest = serde_json::from_slice(&frame).unwrap();
+            first_sender
+                .serve_request("receiver", &request, &mut server)
+                .await
+                .unwrap();
+        });
+        assert!(receiver
+            .pull_over("sender", &mut client, &mut partial)
+            .await
+            .is_err());
+        assert_eq!(partial.missing(), vec![1, 2]);
+
+        let (mut client, mut server) = tokio::io::duplex(64 * 1024);
+        let sender = sender.clone();
+        tokio::spawn(async move { sender.serve("receiver", &mut server).await });
+        receiver
+            .pull_over("sender", &mut client, &mut partial)
+            .await
+            .unwrap();
+
+        assert_eq!(partial.assemble().await.unwrap(), blob());
+    }
+
+    #[tokio::test]
+    async fn offered_blobs_are_spooled_to_disk() {
+        let sender = service(2);
+        let manifest = sender
+            .service
+            .offer(&blob(), &["receiver".to_string()])
+            .unwrap();
+
+        let (_, path) = sender
+            .service
+            .find_blob(&manifest.content_hash, "receiver")
+            .unwrap();
+        assert!(path.starts_with(sender.spool.path()));
+        assert_eq!(std::fs::read(path).unwrap(), blob());
+    }
+
+    #[tokio::test]
+    async fn fetch_rejects_manifest_above_max_file_size() {
+        let TestService {
+            service: receiver,
+            mut events,
+            spool,
+        } = service_with(2, 1);
+        let manifest = BlobManifest {
+            content_hash: ContentHash::from(&[7; 32]),
+            total_bytes: 3 * BYTES_PER_MB,
+            chunk_bytes: uc_core::network::MAX_TRANSFER_CHUNK_BYTES,
+            chunk_hashes: vec!["00".repeat(32)],
+        };
+
+        assert!(receiver.fetch("sender", manifest).await.is_err());
+        assert!(matches!(
+            events.try_recv(),
+            Ok(NetworkEvent::TransferFailed { .. })
+        ));
+        assert!(!spool.path().join("partial").exists());
+    }
+
+    #[tokio::test]
+    async fn pull_of_unknown_blob_reports_missing() {
+        let sender = service(2).service;
+        let receiver_side = service(2);
+        let receiver = &receiver_side.service;
+        let mut partial =
+            partial_for(BlobManifest::from_bytes(&blob(), 4), &receiver_side.spool).await;
+        let (mut client, mut server) = tokio::io::duplex(64 * 1024);
+        tokio::spawn(async move { sender.serve("receiver", &mut server).await });
+
+        let err = receiver
+            .pull_over("sender", &mut client, &mut partial)
+            .await
+            .unwrap_err();
+
+        assert!(err.downcast_ref::<TransferError>().is_some());
+    }
+
+    #[tokio::test]
+    async fn pull_of_blob_offered_to_another_peer_reports_missing() {
+        let sender_side = service(2);
+        let receiver_side = service(2);
+        let manifest = sender_side
+            .service
+            .offer(&blob(), &["someone-else".to_string()])
+            .unwrap();
+        let mut partial = partial_for(manifest, &receiver_side.spool).await;
+        let sender = sender_side.service.clone();
+        let (mut client, mut server) = tokio::io::duplex(64 * 1024);
+        tokio::spawn(async move { sender.serve("receiver", &mut server).await });
+
+        let err = receiver_side
+            .service
+            .pull_over("sender", &mut client, &mut partial)
+            .await
+            .unwrap_err();
+
+        assert!(err.downcast_ref::<TransferError>().is_some());
+    }
+
+    #[tokio::test]
+    async fn offers_survive_a_restart() {
+        let sender_side = service(2);
+        let manifest = sender_side
+            .service
+            .offer(&blob(), &["receiver".to_string()])
+            .unwrap();
+        let config = sender_side.service.inner.config.clone();
+
+        let (event_tx, _events) = mpsc::channel(64);
+        let restarted = TransferService::new(
+            stream::Behaviour::new().new_control(),
+            event_tx,
+            Arc::new(TrustedResolver),
+            Arc::new(MaxFileSize(100)),
+            config,
+        );
+
+        let (_, path) = restarted
+            .find_blob(&manifest.content_hash, "receiver")
+            .unwrap();
+        assert_eq!(std::fs::read(path).unwrap(), blob());
+    }
+}
@@ -0,0 +1,458 @@
+//! Spooled state of transfers on both sides.
+//!
+//! Blob bytes live in files under the transfer spool directory, never in
+//! memory for the lifetime of a transfer. Offers survive a restart, so a
+//! peer holding a lazily synced manifest can still pull it later.
+
+use std::collections::{BTreeSet, VecDeque};
+use std::io::{SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use uc_core::clipboard::ContentHash;
+use uc_core::network::BlobManifest;
+
+/// Extension of the sidecar file describing an offered blob.
+const META_EXTENSION: &str = "json";
+
+/// File-name friendly form of a content hash.
+fn spool_name(content_hash: &ContentHash) -> String {
+    content_hash
+        .bytes
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Sidecar record of an offered blob, stored next to its spool file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct OutboxEntry {
+    manifest: BlobManifest,
+    /// Peers the blob was offered to; nobody else may pull it
+    peers: BTreeSet<String>,
+    /// Offer order, used to evict the oldest entry first
+    seq: u64,
+}
+
+/// Blobs this device has offered and still serves, oldest first.
+///
+/// 发送端缓存：数据及其提供对象写入磁盘暂存文件，重启后继续提供；
+/// 超出容量时淘汰最早提供的数据，对端再拉取会收到“缺失”应答。
+pub struct TransferOutbox {
+    dir: PathBuf,
+    entries: VecDeque<OutboxEntry>,
+    total_bytes: u64,
+    max_bytes: u64,
+    next_seq: u64,
+}
+
+impl TransferOutbox {
+    /// Load the offers an earlier run left in `dir`.
+    ///
+    /// Entries whose spool file is missing or truncated are dropped along
+    /// with leftover temporary files.
+    pub fn open(dir: PathBuf, max_bytes: u64) -> Self {
+        let mut outbox = Self {
+            dir,
+            entries: VecDeque::new(),
+            total_bytes: 0,
+            max_bytes,
+            next_seq: 0,
+        };
+        let mut entries = outbox.load_entries();
+        entries.sort_by_key(|entry| entry.seq);
+        for entry in entries {
+            outbox.next_seq = outbox.next_seq.max(entry.seq + 1);
+            outbox.total_bytes += entry.manifest.total_bytes;
+            outbox.entries.push_back(entry);
+        }
+        outbox.evict_to_capacity();
+        outbox
+    }
+
+    fn load_entries(&self) -> Vec<OutboxEntry> {
+        let Ok(dir) = std::fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut entries = Vec::new();
+        let mut orphans = Vec::new();
+        for path in dir.filter_map(|item| item.ok().map(|item| item.path())) {
+            if path.extension().is_some_and(|ext| ext == META_EXTENSION) {
+                match self.read_entry(&path) {
+                    Some(entry) => entries.push(entry),
+                    None => orphans.push(path),
+                }
+            } else if !path.with_extension(META_EXTENSION).exists() {
+                orphans.push(path);
+            }
+        }
+        for path in orphans {
+            let _ = std::fs::remove_file(&path);
+            let _ = std::fs::remove_file(path.with_extension(""));
+        }
+        entries
+    }
+
+    fn read_entry(&self, meta_path: &Path) -> Option<OutboxEntry> {
+        let entry: OutboxEntry = serde_json::from_slice(&std::fs::read(meta_path).ok()?).ok()?;
+        let data_path = self.path_of(&entry.manifest.content_hash);
+        let len = std::fs::metadata(data_path).ok()?.len();
+        (len == entry.manifest.total_bytes).then_some(entry)
+    }
+
+    fn path_of(&self, content_hash: &ContentHash) -> PathBuf {
+        self.dir.join(spool_name(content_hash))
+    }
+
+    fn meta_path_of(&self, content_hash: &ContentHash) -> PathBuf {
+        self.path_of(content_hash).with_extension(META_EXTENSION)
+    }
+
+    /// Spool `bytes` to disk and offer them to `peers`; offering the same
+    /// content twice keeps one copy and serves it to both sets of peers.
+    pub fn insert(
+        &mut self,
+        bytes: &[u8],
+        chunk_bytes: u32,
+        peers: &[String],
+    ) -> Result<BlobManifest> {
+        let manifest = BlobManifest::from_bytes(bytes, chunk_bytes);
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        if let Some(pos) = self
+            .entries
+            .iter()
+            .position(|existing| existing.manifest.content_hash == manifest.content_hash)
+        {
+            // Refresh so the entry is evicted last.
+            if let Some(mut entry) = self.entries.remove(pos) {
+                entry.peers.extend(peers.iter().cloned());
+                entry.seq = seq;
+                let written = self.write_entry(&entry);
+                self.entries.push_back(entry);
+                written?;
+            }
+            return Ok(manifest);
+        }
+
+        let entry = OutboxEntry {
+            manifest: manifest.clone(),
+            peers: peers.iter().cloned().collect(),
+            seq,
+        };
+        let path = self.path_of(&manifest.content_hash);
+        write_spool_file(&self.dir, &path, bytes)?;
+        if let Err(err) = self.write_entry(&entry) {
+            let _ = std::fs::remove_file(&path);
+            return Err(err);
+        }
+        self.total_bytes += manifest.total_bytes;
+        self.entries.push_back(entry);
+        self.evict_to_capacity();
+        Ok(manifest)
+    }
+
+    fn write_entry(&self, entry: &OutboxEntry) -> Result<()> {
+        let meta = serde_json::to_vec(entry)?;
+        write_spool_file(
+            &self.dir,
+            &self.meta_path_of(&entry.manifest.content_hash),
+            &meta,
+        )
+    }
+
+    fn evict_to_capacity(&mut self) {
+        while self.total_bytes > self.max_bytes && self.entries.len() > 1 {
+            if let Some(evicted) = self.entries.pop_front() {
+                self.total_bytes -= evicted.manifest.total_bytes;
+                let content_hash = &evicted.manifest.content_hash;
+                let _ = std::fs::remove_file(self.meta_path_of(content_hash));
+                let _ = std::fs::remove_file(self.path_of(content_hash));
+            }
+        }
+    }
+
+    /// Manifest and spool file of a blob offered to `peer_id`.
+    pub fn get(
+        &self,
+        content_hash: &ContentHash,
+        peer_id: &str,
+    ) -> Option<(BlobManifest, PathBuf)> {
+        self.entries
+            .iter()
+            .find(|entry| &entry.manifest.content_hash == content_hash)
+            .filter(|entry| entry.peers.contains(peer_id))
+            .map(|entry| (entry.manifest.clone(), self.path_of(content_hash)))
+    }
+}
+
+/// Write `bytes` to `path` through a temporary file so readers never see a
+/// partially written blob.
+fn write_spool_file(dir: &Path, path: &Path, bytes: &[u8]) -> Result<()> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("failed to create transfer spool {}", dir.display()))?;
+    let tmp = path.with_extension(format!("{}.tmp", uuid::Uuid::new_v4()));
+    let mut file = std::fs::File::create(&tmp)
+        .with_context(|| format!("failed to create spool file {}", tmp.display()))?;
+    if let Err(err) = file.write_all(bytes).and_then(|_| file.flush()) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(anyhow!(
+            "failed to write spool file {}: {err}",
+            tmp.display()
+        ));
+    }
+    std::fs::rename(&tmp, path).map_err(|err| {
+        let _ = std::fs::remove_file(&tmp);
+        anyhow!("failed to finalize spool file {}: {err}", path.display())
+    })
+}
+
+/// Open reader over the spool file of an offered blob.
+pub struct SpooledBlob {
+    file: File,
+}
+
+impl SpooledBlob {
+    pub async fn open(path: &Path) -> Result<Self> {
+        let file = File::open(path)
+            .await
+            .with_context(|| format!("failed to open spool file {}", path.display()))?;
+        Ok(Self { file })
+    }
+
+    pub async fn read_span(&mut self, offset: u64, len: usize) -> Result<Vec<u8>> {
+        self.file.seek(SeekFrom::Start(offset)).await?;
+        let mut data = vec![0; len];
+        self.file.read_exact(&mut data).await?;
+        Ok(data)
+    }
+}
+
+/// Chunks received so far for one blob.
+///
+/// 接收端的部分数据：校验通过的分块写入磁盘暂存文件，断线重连后只请求缺失部分。
+pub struct PartialBlob {
+    manifest: BlobManifest,
+    path: PathBuf,
+    file: File,
+    received: Vec<bool>,
+    received_bytes: u64,
+}
+
+impl PartialBlob {
+    /// Start receiving `manifest` into a new spool file under `dir`.
+    ///
+    /// Manifests larger than `max_bytes` are refused before anything is
+    /// allocated or written.
+    pub async fn create(manifest: BlobManifest, dir: &Path, max_bytes: u64) -> Result<Self> {
+        if !manifest.is_well_formed() {
+            return Err(anyhow!(
+                "malformed blob manifest for {}",
+                manifest.content_hash
+            ));
+        }
+        if manifest.total_bytes > max_bytes {
+            return Err(anyhow!(
+                "blob {} too large: {} bytes, limit {} bytes",
+                manifest.content_hash,
+                manifest.total_bytes,
+                max_bytes
+            ));
+        }
+        tokio::fs::create_dir_all(dir)
+            .await
+            .with_context(|| format!("failed to create transfer spool {}", dir.display()))?;
+        // A unique name keeps a concurrent transfer of the same blob from
+        // deleting this file when it is dropped.
+        let path = dir.join(format!(
+            "{}-{}.part",
+            spool_name(&manifest.content_hash),
+            uuid::Uuid::new_v4()
+        ));
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .await
+            .with_context(|| format!("failed to create spool file {}", path.display()))?;
+        file.set_len(manifest.total_bytes).await?;
+        Ok(Self {
+            received: vec![false; manifest.chunk_count() as usize],
+            received_bytes: 0,
+            manifest,
+            path,
+            file,
+        })
+    }
+
+    pub fn manifest(&self) -> &BlobManifest {
+        &self.manifest
+    }
+
+    pub fn received_bytes(&self) -> u64 {
+        self.received_bytes
+    }
+
+    pub fn missing(&self) -> Vec<u32> {
+        self.received
+            .iter()
+            .enumerate()
+            .filter(|(_, received)| !**received)
+            .map(|(index, _)| index as u32)
+            .collect()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.received.iter().all(|received| *received)
+    }
+
+    /// Store a chunk after checking it against the manifest.
+    pub async fn insert(&mut self, index: u32, data: &[u8]) -> Result<()> {
+        if !self.manifest.verify_chunk(index, data) {
+            return Err(anyhow!("chunk {index} failed verification"));
+        }
+        let Some((offset, len)) = self.manifest.chunk_span(index) else {
+            return Err(anyhow!("chunk {index} out of range"));
+        };
+        if self.received[index as usize] {
+            return Ok(());
+        }
+        self.file.seek(SeekFrom::Start(offset)).await?;
+        self.file.write_all(data).await?;
+        self.received[index as usize] = true;
+        self.received_bytes += len as u64;
+        Ok(())
+    }
+
+    /// The assembled blob, verified against the manifest hash.
+    pub async fn assemble(&mut self) -> Result<Vec<u8>> {
+        if !self.is_complete() {
+            return Err(anyhow!("blob {} is incomplete", self.manifest.content_hash));
+        }
+        self.file.flush().await?;
+        self.file.seek(SeekFrom::Start(0)).await?;
+        let mut bytes = Vec::with_capacity(self.manifest.total_bytes as usize);
+        self.file.read_to_end(&mut bytes).await?;
+        if !self.manifest.verify_blob(&bytes) {
+            return Err(anyhow!(
+                "blob {} failed verification",
+                self.manifest.content_hash
+            ));
+        }
+        Ok(bytes)
+    }
+}
+
+impl Drop for PartialBlob {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spool_entries(dir: &Path) -> usize {
+        std::fs::read_dir(dir).map(|dir| dir.count()).unwrap_or(0)
+    }
+
+    fn peers(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn outbox_dedupes_and_evicts_oldest() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut outbox = TransferOutbox::open(dir.path().to_path_buf(), 10);
+
+        let first = outbox.insert(&[1; 6], 4, &peers(&["a"])).unwrap();
+        outbox.insert(&[1; 6], 4, &peers(&["a"])).unwrap();
+        let second = outbox.insert(&[2; 6], 4, &peers(&["a"])).unwrap();
+
+        assert!(outbox.get(&first.content_hash, "a").is_none());
+        let (_, path) = outbox.get(&second.content_hash, "a").unwrap();
+        assert_eq!(std::fs::read(path).unwrap(), vec![2; 6]);
+        // One spool file plus its sidecar.
+        assert_eq!(spool_entries(dir.path()), 2);
+    }
+
+    #[test]
+    fn outbox_serves_only_peers_the_blob_was_offered_to() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut outbox = TransferOutbox::open(dir.path().to_path_buf(), 1024);
+
+        let manifest = outbox.insert(&[1; 6], 4, &peers(&["a"])).unwrap();
+        assert!(outbox.get(&manifest.content_hash, "b").is_none());
+
+        outbox.insert(&[1; 6], 4, &peers(&["b"])).unwrap();
+        assert!(outbox.get(&manifest.content_hash, "a").is_some());
+        assert!(outbox.get(&manifest.content_hash, "b").is_some());
+    }
+
+    #[test]
+    fn outbox_survives_reopen_and_drops_damaged_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut outbox = TransferOutbox::open(dir.path().to_path_buf(), 1024);
+        let kept = outbox.insert(&[1; 6], 4, &peers(&["a"])).unwrap();
+        let damaged = outbox.insert(&[2; 6], 4, &peers(&["a"])).unwrap();
+        let (_, damaged_path) = outbox.get(&damaged.content_hash, "a").unwrap();
+        drop(outbox);
+        std::fs::write(&damaged_path, [2; 3]).unwrap();
+        std::fs::write(dir.path().join("stray.tmp"), [0; 3]).unwrap();
+
+        let outbox = TransferOutbox::open(dir.path().to_path_buf(), 1024);
+
+        let (manifest, path) = outbox.get(&kept.content_hash, "a").unwrap();
+        assert_eq!(manifest, kept);
+        assert_eq!(std::fs::read(path).unwrap(), vec![1; 6]);
+        assert!(outbox.get(&kept.content_hash, "b").is_none());
+        assert!(outbox.get(&damaged.content_hash, "a").is_none());
+        assert_eq!(spool_entries(dir.path()), 2);
+    }
+
+    #[tokio::test]
+    async fn partial_blob_accepts_only_verified_chunks() {
+        let dir = tempfile::tempdir().unwrap();
+        let bytes: Vec<u8> = (0..10u8).collect();
+        let manifest = BlobManifest::from_bytes(&bytes, 4);
+        let mut partial = PartialBlob::create(manifest, dir.path(), 1024)
+            .await
+            .unwrap();
+
+        assert!(partial.insert(0, &bytes[4..8]).await.is_err());
+        partial.insert(2, &bytes[8..]).await.unwrap();
+        assert_eq!(partial.missing(), vec![0, 1]);
+        assert!(partial.assemble().await.is_err());
+
+        partial.insert(0, &bytes[..4]).await.unwrap();
+        partial.insert(1, &bytes[4..8]).await.unwrap();
+        assert_eq!(partial.received_bytes(), 10);
+        assert_eq!(partial.assemble().await.unwrap(), bytes);
+
+        drop(partial);
+        assert_eq!(spool_entries(dir.path()), 0);
+    }
+
+    #[tokio::test]
+    async fn partial_blob_rejects_manifest_above_limit_before_writing() {
+        let dir = tempfile::tempdir().unwrap();
+        let spool = dir.path().join("partial");
+        let manifest = BlobManifest {
+            content_hash: ContentHash::from(&[7; 32]),
+            total_bytes: 3 * 1024 * 1024,
+            chunk_bytes: uc_core::network::MAX_TRANSFER_CHUNK_BYTES,
+            chunk_hashes: vec!["00".repeat(32)],
+        };
+        assert!(manifest.is_well_formed());
+
+        assert!(PartialBlob::create(manifest, &spool, 1024 * 1024)
+            .await
+            .is_err());
+        assert!(!spool.exists());
+    }
+}
@@ -4,9 +4,14 @@ use std::path::PathBuf;
 
 use uc_core::ports::{SecureStorageError, SecureStoragePort};
 
-/// File-based secure storage for development or headless environments.
+/// Plaintext file-based secure storage for development or headless environments.
 ///
-/// 基于文件的安全存储（开发/无桌面环境回退）。
+/// Superseded by [`SealedFileSecureStorage`], which migrates files written
+/// by this type.
+///
+/// 基于文件的明文安全存储（开发/无桌面环境回退）。
+///
+/// [`SealedFileSecureStorage`]: crate::sealed_file_secure_storage::SealedFileSecureStorage
 #[derive(Clone)]
 pub struct FileSecureStorage {
     base_dir: PathBuf,
@@ -1,23 +1,31 @@
 //! Default key scope implementation
 //! 默认密钥范围实现
 
+use std::sync::Arc;
+
 use anyhow::Result;
+use uc_core::ids::SpaceId;
 use uc_core::ports::security::key_scope::KeyScopePort;
 use uc_core::ports::security::key_scope::ScopeError;
+use uc_core::ports::space::ActiveSpacePort;
 use uc_core::security::model::KeyScope;
 
 /// Default key scope implementation
+///
+/// Follows the active space when one is attached, so each space gets its own
+/// keyslot and KEK; otherwise the scope is fixed to the default space.
 pub struct DefaultKeyScope {
-    scope: KeyScope,
+    active_space: Option<Arc<dyn ActiveSpacePort>>,
 }
 
 impl DefaultKeyScope {
     pub fn new() -> Self {
-        Self {
-            scope: KeyScope {
-                profile_id: "default".to_string(),
-            },
-        }
+        Self { active_space: None }
+    }
+
+    pub fn with_active_space(mut self, active_space: Arc<dyn ActiveSpacePort>) -> Self {
+        self.active_space = Some(active_space);
+        self
     }
 }
 
@@ -30,6 +38,11 @@ impl Default for DefaultKeyScope {
 #[async_trait::async_trait]
 impl KeyScopePort for DefaultKeyScope {
     async fn current_scope(&self) -> Result<KeyScope, ScopeError> {
-        Ok(self.scope.clone())
+        let space_id = self
+            .active_space
+            .as_ref()
+            .map(|active_space| active_space.active_space())
+            .unwrap_or_else(SpaceId::default_space);
+        Ok(KeyScope::for_space(&space_id))
     }
 }
@@ -20,5 +20,6 @@ pub mod key_scope;
 pub mod net_utils;
 pub mod ports;
 pub mod runtime;
+pub mod sealed_file_secure_storage;
 pub mod secure_storage;
 pub mod system_secure_storage;
@@ -1,60 +1,155 @@
 use local_ip_address::list_afinet_netifas;
-use std::net::{IpAddr, Ipv4Addr};
-use tracing::{debug, info, warn};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use tracing::{debug, warn};
+use uc_core::settings::model::NetworkSettings;
 
-/// Detect the best physical LAN IPv4 address for libp2p to listen on.
+/// Allow/deny rule for listen interfaces: an interface name or a CIDR block.
 ///
-/// 检测最佳的物理局域网 IPv4 地址，供 libp2p 监听使用。
-///
-/// # Filtering rules / 过滤规则
-/// - Exclude loopback (127.*)
-/// - Exclude link-local (169.254.*)
-/// - Exclude tunnel interfaces (utun, tun, tap)
-/// - Exclude Clash TUN addresses (198.18.0.0/15)
-/// - Only keep private IPv4 addresses (10.*, 172.16-31.*, 192.168.*)
-pub fn get_physical_lan_ip() -> Option<Ipv4Addr> {
-    let interfaces = match list_afinet_netifas() {
-        Ok(ifaces) => ifaces,
-        Err(e) => {
-            warn!(error = %e, "failed to enumerate network interfaces");
+/// 监听接口规则：接口名（如 `en0`）或 CIDR（如 `192.168.1.0/24`、`fd00::/8`）。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InterfaceRule {
+    Name(String),
+    Cidr { network: IpAddr, prefix: u8 },
+}
+
+impl InterfaceRule {
+    /// Parse a rule; entries containing `/` must be valid CIDR blocks.
+    pub fn parse(rule: &str) -> Option<Self> {
+        let rule = rule.trim();
+        if rule.is_empty() {
             return None;
         }
-    };
+        let Some((network, prefix)) = rule.split_once('/') else {
+            return Some(Self::Name(rule.to_string()));
+        };
+        let network: IpAddr = network.parse().ok()?;
+        let prefix: u8 = prefix.parse().ok()?;
+        let max_prefix = if network.is_ipv4() { 32 } else { 128 };
+        (prefix <= max_prefix).then_some(Self::Cidr { network, prefix })
+    }
 
-    for (iface_name, ip) in interfaces {
-        if let IpAddr::V4(v4) = ip {
-            if v4.is_loopback() || v4.is_link_local() {
-                debug!(interface = %iface_name, ip = %v4, "skip non-routable interface address");
-                continue;
-            }
+    fn matches(&self, interface: &str, ip: IpAddr) -> bool {
+        match self {
+            Self::Name(name) => name == interface,
+            Self::Cidr { network, prefix } => cidr_contains(*network, *prefix, ip),
+        }
+    }
+}
 
-            if is_tunnel_interface(&iface_name) {
-                debug!(interface = %iface_name, ip = %v4, "skip tunnel interface for p2p listen");
-                continue;
-            }
+/// Interface selection built from `network.allowed_interfaces` /
+/// `network.denied_interfaces`.
+///
+/// 接口过滤：拒绝列表优先；允许列表非空时只保留命中的地址，
+/// 并跳过默认的隧道/虚拟网桥排除规则。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InterfaceFilter {
+    allow: Vec<InterfaceRule>,
+    deny: Vec<InterfaceRule>,
+}
 
-            if is_clash_tun_address(v4) {
-                debug!(interface = %iface_name, ip = %v4, "skip clash tun address for p2p listen");
-                continue;
-            }
+impl InterfaceFilter {
+    pub fn from_settings(settings: &NetworkSettings) -> Self {
+        Self {
+            allow: parse_rules(&settings.allowed_interfaces),
+            deny: parse_rules(&settings.denied_interfaces),
+        }
+    }
 
-            if is_private_ipv4(v4) {
-                info!(ip = %v4, interface = %iface_name, "detected physical LAN IP");
-                return Some(v4);
+    /// Whether `ip` on `interface` should get a listener.
+    pub fn is_eligible(&self, interface: &str, ip: IpAddr) -> bool {
+        if !is_listenable(ip) {
+            return false;
+        }
+        if self.deny.iter().any(|rule| rule.matches(interface, ip)) {
+            return false;
+        }
+        if !self.allow.is_empty() {
+            return self.allow.iter().any(|rule| rule.matches(interface, ip));
+        }
+
+        if is_tunnel_interface(interface) || is_virtual_bridge_interface(interface) {
+            return false;
+        }
+        match ip {
+            IpAddr::V4(v4) => is_private_ipv4(v4) && !is_clash_tun_address(v4),
+            IpAddr::V6(v6) => is_unique_local_ipv6(v6) || is_global_unicast_ipv6(v6),
+        }
+    }
+}
+
+fn parse_rules(entries: &[String]) -> Vec<InterfaceRule> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let rule = InterfaceRule::parse(entry);
+            if rule.is_none() {
+                warn!(rule = %entry, "ignoring invalid interface rule");
             }
+            rule
+        })
+        .collect()
+}
 
-            debug!(interface = %iface_name, ip = %v4, "skip non-private ipv4 for p2p listen");
+/// All IPv4/IPv6 addresses of local interfaces as `(interface, ip)`.
+///
+/// 枚举本机所有网卡地址（IPv4 与 IPv6）。
+pub fn list_interface_addresses() -> Vec<(String, IpAddr)> {
+    match list_afinet_netifas() {
+        Ok(interfaces) => interfaces,
+        Err(e) => {
+            warn!(error = %e, "failed to enumerate network interfaces");
+            Vec::new()
+        }
+    }
+}
+
+/// Local addresses libp2p should listen on, one listener per address.
+///
+/// 返回所有符合过滤规则的本机地址，供 libp2p 逐一监听。
+///
+/// # Default rules / 默认规则
+/// - Exclude loopback, link-local, multicast and unspecified addresses
+/// - Exclude tunnel interfaces (utun, tun, tap) and virtual bridges (docker, veth, …)
+/// - Exclude Clash TUN addresses (198.18.0.0/15)
+/// - IPv4: only private addresses (10.*, 172.16-31.*, 192.168.*)
+/// - IPv6: unique local (fc00::/7) and global unicast (2000::/3)
+pub fn eligible_listen_ips(filter: &InterfaceFilter) -> Vec<IpAddr> {
+    let mut ips = Vec::new();
+    for (interface, ip) in list_interface_addresses() {
+        if !filter.is_eligible(&interface, ip) {
+            debug!(interface = %interface, ip = %ip, "skip interface address for p2p listen");
+            continue;
+        }
+        if !ips.contains(&ip) {
+            ips.push(ip);
         }
     }
+    ips
+}
 
-    warn!("no suitable physical LAN IP found");
-    None
+fn is_listenable(ip: IpAddr) -> bool {
+    if ip.is_loopback() || ip.is_unspecified() || ip.is_multicast() {
+        return false;
+    }
+    match ip {
+        IpAddr::V4(v4) => !v4.is_link_local() && !v4.is_broadcast(),
+        // Link-local IPv6 needs a zone id, which multiaddrs cannot carry.
+        IpAddr::V6(v6) => !is_link_local_ipv6(v6),
+    }
 }
 
 fn is_tunnel_interface(name: &str) -> bool {
     name.contains("utun") || name.contains("tun") || name.contains("tap")
 }
 
+fn is_virtual_bridge_interface(name: &str) -> bool {
+    [
+        "docker", "br-", "veth", "virbr", "vmnet", "vboxnet", "cni", "flannel",
+    ]
+    .iter()
+    .any(|prefix| name.starts_with(prefix))
+}
+
 fn is_clash_tun_address(ip: Ipv4Addr) -> bool {
     let octets = ip.octets();
     octets[0] == 198 && octets[1] >= 18
@@ -70,10 +165,48 @@ fn is_private_ipv4(ip: Ipv4Addr) -> bool {
     }
 }
 
+fn is_link_local_ipv6(ip: Ipv6Addr) -> bool {
+    (ip.segments()[0] & 0xffc0) == 0xfe80
+}
+
+fn is_unique_local_ipv6(ip: Ipv6Addr) -> bool {
+    (ip.segments()[0] & 0xfe00) == 0xfc00
+}
+
+fn is_global_unicast_ipv6(ip: Ipv6Addr) -> bool {
+    (ip.segments()[0] & 0xe000) == 0x2000
+}
+
+fn cidr_contains(network: IpAddr, prefix: u8, ip: IpAddr) -> bool {
+    match (network, ip) {
+        (IpAddr::V4(network), IpAddr::V4(ip)) => {
+            let mask = u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0);
+            u32::from(network) & mask == u32::from(ip) & mask
+        }
+        (IpAddr::V6(network), IpAddr::V6(ip)) => {
+            let mask = u128::MAX.checked_shl(128 - u32::from(prefix)).unwrap_or(0);
+            u128::from(network) & mask == u128::from(ip) & mask
+        }
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn filter(allow: &[&str], deny: &[&str]) -> InterfaceFilter {
+        InterfaceFilter::from_settings(&NetworkSettings {
+            allowed_interfaces: allow.iter().map(|rule| rule.to_string()).collect(),
+            denied_interfaces: deny.iter().map(|rule| rule.to_string()).collect(),
+            ..NetworkSettings::default()
+        })
+    }
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
     #[test]
     fn loopback_is_not_private() {
         assert!(!is_private_ipv4(Ipv4Addr::LOCALHOST));
@@ -114,9 +247,54 @@ mod tests {
     }
 
     #[test]
-    fn get_physical_lan_ip_returns_some_on_dev_machine() {
+    fn default_filter_keeps_lan_and_routable_ipv6_only() {
+        let filter = InterfaceFilter::default();
+
+        assert!(filter.is_eligible("en0", ip("192.168.1.20")));
+        assert!(filter.is_eligible("en0", ip("fd12:3456::1")));
+        assert!(filter.is_eligible("en0", ip("2001:db8::1")));
+        assert!(!filter.is_eligible("en0", ip("fe80::1")));
+        assert!(!filter.is_eligible("lo0", ip("::1")));
+        assert!(!filter.is_eligible("en0", ip("169.254.3.4")));
+        assert!(!filter.is_eligible("docker0", ip("172.17.0.1")));
+        assert!(!filter.is_eligible("br-5f2a", ip("172.18.0.1")));
+        assert!(!filter.is_eligible("utun4", ip("10.8.0.2")));
+    }
+
+    #[test]
+    fn allow_list_overrides_defaults_and_deny_list_wins() {
+        let filter = filter(&["docker0", "10.0.0.0/8"], &["10.9.0.0/16"]);
+
+        assert!(filter.is_eligible("docker0", ip("172.17.0.1")));
+        assert!(filter.is_eligible("eth1", ip("10.1.2.3")));
+        assert!(!filter.is_eligible("eth1", ip("10.9.2.3")));
+        assert!(!filter.is_eligible("en0", ip("192.168.1.20")));
+        assert!(!filter.is_eligible("docker0", ip("127.0.0.1")));
+    }
+
+    #[test]
+    fn rules_parse_names_and_cidrs() {
+        assert_eq!(
+            InterfaceRule::parse(" en0 "),
+            Some(InterfaceRule::Name("en0".to_string()))
+        );
+        assert_eq!(
+            InterfaceRule::parse("fd00::/8"),
+            Some(InterfaceRule::Cidr {
+                network: ip("fd00::"),
+                prefix: 8
+            })
+        );
+        assert_eq!(InterfaceRule::parse("10.0.0.0/33"), None);
+        assert_eq!(InterfaceRule::parse("eth0/24"), None);
+        assert_eq!(InterfaceRule::parse(""), None);
+        assert!(cidr_contains(ip("0.0.0.0"), 0, ip("8.8.8.8")));
+    }
+
+    #[test]
+    fn eligible_listen_ips_runs_on_dev_machine() {
         // This test validates the function runs without panic.
-        // On CI without a LAN, it may return None — that's acceptable.
-        let _result = get_physical_lan_ip();
+        // On CI without a LAN, it may return an empty list — that's acceptable.
+        let _result = eligible_listen_ips(&InterfaceFilter::default());
     }
 }
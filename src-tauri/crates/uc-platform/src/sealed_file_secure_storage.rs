@@ -0,0 +1,920 @@
+//! Sealed file-based secure storage for headless environments.
+//! 无桌面环境下的加密文件密钥库
+//!
+//! Every entry is sealed with XChaCha20-Poly1305 under a key stretched with
+//! Argon2id, either from a user-supplied PIN or from the machine secret plus
+//! a password. The entry key name is bound as associated data, so edited,
+//! truncated or swapped files fail authentication and surface as
+//! [`SecureStorageError::Corrupt`].
+//!
+//! Without a PIN or password the key comes from the machine secret alone.
+//! That only stops the files from being read on another host; anyone who can
+//! read the machine id on this host can unseal them, so it is logged as
+//! insecure.
+//!
+//! Layout under the base directory:
+//! - `keystore.json`: mode, KDF parameters, salt and a verifier sealed with
+//!   the derived key, so a wrong PIN is told apart from a damaged entry.
+//! - `<hex(key)>.sealed`: `MAGIC | VERSION | nonce(24) | ciphertext`.
+//!
+//! Plaintext `<hex(key)>.bin` files written by [`FileSecureStorage`] are
+//! sealed and removed when the keystore is opened.
+//!
+//! # Changing the secret / 更换密钥
+//! Start once with the current secret in the environment and the new one in
+//! [`KEYSTORE_NEW_PIN_ENV`] or [`KEYSTORE_NEW_PASSWORD_ENV`];
+//! [`SealedFileSecureStorage::rekey`] reseals every entry and only then
+//! swaps the header, so an interrupted re-key leaves the old secret valid.
+//! Afterwards start with the new secret only.
+//!
+//! # Lost secret / 密钥丢失
+//! Entries cannot be recovered without the secret. Remove the `keyring`
+//! directory and start again: the device gets a fresh identity and has to
+//! be paired and rejoin the space, like a new install.
+//!
+//! [`FileSecureStorage`]: crate::file_secure_storage::FileSecureStorage
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chacha20poly1305::aead::{Aead, Payload};
+use chacha20poly1305::{Key, KeyInit, XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use uc_core::ports::{SecureStorageError, SecureStoragePort};
+use zeroize::Zeroizing;
+
+/// Environment variable holding the keystore PIN
+pub const KEYSTORE_PIN_ENV: &str = "UNICLIPBOARD_KEYSTORE_PIN";
+
+/// Environment variable holding the password combined with the machine secret
+pub const KEYSTORE_PASSWORD_ENV: &str = "UNICLIPBOARD_KEYSTORE_PASSWORD";
+
+/// Environment variable holding a PIN to re-key the keystore to on open
+pub const KEYSTORE_NEW_PIN_ENV: &str = "UNICLIPBOARD_KEYSTORE_NEW_PIN";
+
+/// Environment variable holding a password to re-key the keystore to on open
+pub const KEYSTORE_NEW_PASSWORD_ENV: &str = "UNICLIPBOARD_KEYSTORE_NEW_PASSWORD";
+
+const HEADER_FILE: &str = "keystore.json";
+const MACHINE_SECRET_FILE: &str = "machine.secret";
+const MACHINE_ID_PATHS: &[&str] = &["/etc/machine-id", "/var/lib/dbus/machine-id"];
+
+const FORMAT_VERSION: u8 = 1;
+const ENTRY_MAGIC: &[u8; 4] = b"UCKS";
+const NONCE_SIZE: usize = 24;
+const SALT_SIZE: usize = 16;
+const KEY_SIZE: usize = 32;
+const RESEALED_EXTENSION: &str = "resealed";
+const ENTRY_AAD_PREFIX: &[u8] = b"uniclipboard-keystore:v1:";
+const VERIFIER_PLAINTEXT: &[u8] = b"uniclipboard-keystore-verifier";
+
+/// Source of the secret the sealing key is derived from.
+///
+/// 派生封装密钥所用的秘密来源。
+#[derive(Clone)]
+pub enum KeystoreSecret {
+    /// User-supplied PIN, which must not be empty
+    Pin(Zeroizing<String>),
+    /// Machine secret (no TPM) combined with a password, which must not be empty
+    Machine {
+        machine_secret: Zeroizing<Vec<u8>>,
+        password: Zeroizing<String>,
+    },
+    /// Machine secret alone; insecure, kept for hosts without a PIN or password
+    MachineOnly { machine_secret: Zeroizing<Vec<u8>> },
+}
+
+impl KeystoreSecret {
+    /// Resolve the secret from the environment.
+    ///
+    /// Uses [`KEYSTORE_PIN_ENV`] when set; otherwise the machine id combined
+    /// with [`KEYSTORE_PASSWORD_ENV`]. Without either, falls back to the
+    /// machine id alone and logs that the keystore is insecure. Hosts without
+    /// a machine id get a random secret persisted in `base_dir`.
+    ///
+    /// 优先使用 PIN；否则使用机器标识加密码；两者都未设置时仅用机器标识并记录不安全警告。
+    pub fn from_env(base_dir: &Path) -> Result<Self, io::Error> {
+        if let Some(secret) = Self::resolve(base_dir, KEYSTORE_PIN_ENV, KEYSTORE_PASSWORD_ENV)? {
+            return Ok(secret);
+        }
+
+        warn!(
+            "No {KEYSTORE_PIN_ENV} or {KEYSTORE_PASSWORD_ENV} set; the file keystore is sealed \
+             with the machine id only (insecure dev fallback for WSL/headless environments)"
+        );
+        Ok(Self::MachineOnly {
+            machine_secret: Zeroizing::new(load_machine_secret(base_dir)?),
+        })
+    }
+
+    /// Resolve the secret to re-key to from [`KEYSTORE_NEW_PIN_ENV`] or
+    /// [`KEYSTORE_NEW_PASSWORD_ENV`], or `None` when neither is set.
+    ///
+    /// 从环境变量读取要更换成的新密钥；未设置时返回 `None`。
+    pub fn rekey_target_from_env(base_dir: &Path) -> Result<Option<Self>, io::Error> {
+        Self::resolve(base_dir, KEYSTORE_NEW_PIN_ENV, KEYSTORE_NEW_PASSWORD_ENV)
+    }
+
+    fn resolve(
+        base_dir: &Path,
+        pin_env: &str,
+        password_env: &str,
+    ) -> Result<Option<Self>, io::Error> {
+        if let Ok(pin) = std::env::var(pin_env) {
+            if !pin.is_empty() {
+                return Ok(Some(Self::Pin(Zeroizing::new(pin))));
+            }
+        }
+        match std::env::var(password_env) {
+            Ok(password) if !password.is_empty() => Ok(Some(Self::Machine {
+                machine_secret: Zeroizing::new(load_machine_secret(base_dir)?),
+                password: Zeroizing::new(password),
+            })),
+            _ => Ok(None),
+        }
+    }
+
+    fn mode(&self) -> KeystoreMode {
+        match self {
+            KeystoreSecret::Pin(_) => KeystoreMode::Pin,
+            KeystoreSecret::Machine { .. } => KeystoreMode::Machine,
+            KeystoreSecret::MachineOnly { .. } => KeystoreMode::MachineOnly,
+        }
+    }
+
+    /// Reject a PIN or machine password that is empty.
+    fn validate(&self) -> Result<(), SecureStorageError> {
+        match self {
+            KeystoreSecret::Pin(pin) if pin.is_empty() => Err(
+                SecureStorageError::PermissionDenied("keystore PIN must not be empty".to_string()),
+            ),
+            KeystoreSecret::Machine { password, .. } if password.is_empty() => {
+                Err(SecureStorageError::PermissionDenied(format!(
+                    "keystore password must not be empty; set {KEYSTORE_PASSWORD_ENV}"
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn load_machine_secret(base_dir: &Path) -> Result<Vec<u8>, io::Error> {
+    for path in MACHINE_ID_PATHS {
+        if let Ok(contents) = fs::read_to_string(path) {
+            let id = contents.trim();
+            if !id.is_empty() {
+                return Ok(id.as_bytes().to_vec());
+            }
+        }
+    }
+
+    let path = base_dir.join(MACHINE_SECRET_FILE);
+    match fs::read(&path) {
+        Ok(bytes) if !bytes.is_empty() => return Ok(bytes),
+        Ok(_) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+
+    warn!("No machine id found; generating a keystore machine secret");
+    let mut secret = vec![0u8; KEY_SIZE];
+    rand::rng().fill_bytes(&mut secret);
+    write_private(&path, &secret)?;
+    Ok(secret)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum KeystoreMode {
+    Pin,
+    Machine,
+    MachineOnly,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct KdfParams {
+    mem_kib: u32,
+    iters: u32,
+    parallelism: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            mem_kib: 65536,
+            iters: 3,
+            parallelism: 4,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct KeystoreHeader {
+    version: u8,
+    mode: KeystoreMode,
+    kdf: KdfParams,
+    /// Base64 Argon2id salt
+    salt: String,
+    /// Base64 `nonce | ciphertext` of a fixed plaintext
+    verifier: String,
+}
+
+/// Encrypted file-based secure storage.
+///
+/// 加密的文件安全存储（WSL/无桌面 Linux 环境）。
+#[derive(Clone)]
+pub struct SealedFileSecureStorage {
+    base_dir: PathBuf,
+    key: Zeroizing<[u8; KEY_SIZE]>,
+}
+
+impl SealedFileSecureStorage {
+    /// Open the sealed keystore at `<app_data_root>/keyring`, using the secret
+    /// from the environment.
+    ///
+    /// 在 `<app_data_root>/keyring` 下打开加密密钥库。
+    pub fn open_in_app_data_root(app_data_root: PathBuf) -> Result<Self, SecureStorageError> {
+        let base_dir = app_data_root.join("keyring");
+        fs::create_dir_all(&base_dir)
+            .map_err(|err| map_io_error("failed to create keystore directory", err))?;
+        Self::open_from_env(base_dir)
+    }
+
+    /// Open the sealed keystore in `base_dir` with the secret from the
+    /// environment, re-keying it when a new secret is set.
+    ///
+    /// 使用环境变量中的密钥打开；设置了新密钥时随即更换。
+    pub fn open_from_env(base_dir: PathBuf) -> Result<Self, SecureStorageError> {
+        let secret = KeystoreSecret::from_env(&base_dir)
+            .map_err(|err| map_io_error("failed to load keystore machine secret", err))?;
+        let new_secret = KeystoreSecret::rekey_target_from_env(&base_dir)
+            .map_err(|err| map_io_error("failed to load keystore machine secret", err))?;
+        match (Self::open(base_dir.clone(), &secret), new_secret) {
+            (Ok(storage), Some(new_secret)) => storage.rekey(&new_secret),
+            // A re-key that already replaced the header before being interrupted.
+            (Err(SecureStorageError::PermissionDenied(_)), Some(new_secret)) => {
+                Self::open(base_dir, &new_secret)
+            }
+            (result, _) => result,
+        }
+    }
+
+    /// Open (or initialise) the sealed keystore in `base_dir`.
+    ///
+    /// Returns `PermissionDenied` when `secret` does not unlock an existing
+    /// keystore. Legacy plaintext entries are migrated.
+    ///
+    /// 打开或初始化密钥库；密钥不匹配时返回 `PermissionDenied`，并迁移旧的明文条目。
+    pub fn open(base_dir: PathBuf, secret: &KeystoreSecret) -> Result<Self, SecureStorageError> {
+        Self::open_with_params(base_dir, secret, KdfParams::default())
+    }
+
+    fn open_with_params(
+        base_dir: PathBuf,
+        secret: &KeystoreSecret,
+        params: KdfParams,
+    ) -> Result<Self, SecureStorageError> {
+        secret.validate()?;
+        let header_path = base_dir.join(HEADER_FILE);
+        let storage = match fs::read(&header_path) {
+            Ok(bytes) => {
+                let header: KeystoreHeader = serde_json::from_slice(&bytes).map_err(|err| {
+                    SecureStorageError::Corrupt(format!("invalid keystore header: {err}"))
+                })?;
+                Self::unlock(base_dir, secret, &header)?
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                Self::initialise(base_dir, secret, params)?
+            }
+            Err(err) => return Err(map_io_error("failed to read keystore header", err)),
+        };
+
+        storage.finish_interrupted_rekey()?;
+        storage.migrate_plaintext_entries()?;
+        Ok(storage)
+    }
+
+    /// Reseal every entry under `new_secret` and return the re-keyed keystore.
+    ///
+    /// Entries are first written beside the originals as `.resealed` files;
+    /// replacing the header is the commit point, after which they are moved
+    /// into place. A crash before the header is replaced leaves the old secret
+    /// valid, and the next open finishes or discards the leftovers.
+    ///
+    /// 在新密钥下重新封装所有条目；替换头文件前中断时旧密钥仍然有效。
+    pub fn rekey(self, new_secret: &KeystoreSecret) -> Result<Self, SecureStorageError> {
+        self.rekey_with_params(new_secret, KdfParams::default())
+    }
+
+    fn rekey_with_params(
+        self,
+        new_secret: &KeystoreSecret,
+        params: KdfParams,
+    ) -> Result<Self, SecureStorageError> {
+        new_secret.validate()?;
+        let mut salt = [0u8; SALT_SIZE];
+        rand::rng().fill_bytes(&mut salt);
+        let rekeyed = Self {
+            base_dir: self.base_dir.clone(),
+            key: derive_key(new_secret, &salt, params)?,
+        };
+
+        let keys = self.list_entry_keys()?;
+        for key in &keys {
+            let Some(value) = self.get(key)?.map(Zeroizing::new) else {
+                continue;
+            };
+            write_private(
+                &rekeyed.resealed_path(key),
+                &rekeyed.encode_entry(key, &value)?,
+            )
+            .map_err(|err| map_io_error("failed to write resealed keystore entry", err))?;
+        }
+
+        rekeyed.write_header(new_secret.mode(), params, &salt)?;
+        rekeyed.finish_interrupted_rekey()?;
+        info!(count = keys.len(), mode = ?new_secret.mode(), "Re-keyed sealed file keystore");
+        Ok(rekeyed)
+    }
+
+    /// Move `.resealed` entries that open under the current key into place and
+    /// drop the ones left by a re-key that never reached its commit point.
+    fn finish_interrupted_rekey(&self) -> Result<(), SecureStorageError> {
+        let entries = fs::read_dir(&self.base_dir)
+            .map_err(|err| map_io_error("failed to list keystore directory", err))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(RESEALED_EXTENSION) {
+                continue;
+            }
+            let committed = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(decode_key)
+                .is_some_and(|key| {
+                    fs::read(&path)
+                        .ok()
+                        .is_some_and(|bytes| self.decode_entry(&key, &bytes).is_ok())
+                });
+            if committed {
+                fs::rename(&path, path.with_extension("sealed"))
+                    .map_err(|err| map_io_error("failed to finish keystore re-key", err))?;
+            } else {
+                warn!(path = %path.display(), "Discarding entry from an interrupted keystore re-key");
+                fs::remove_file(&path)
+                    .map_err(|err| map_io_error("failed to remove resealed keystore entry", err))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn list_entry_keys(&self) -> Result<Vec<String>, SecureStorageError> {
+        let entries = fs::read_dir(&self.base_dir)
+            .map_err(|err| map_io_error("failed to list keystore directory", err))?;
+        Ok(entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("sealed"))
+            .filter_map(|path| {
+                path.file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(decode_key)
+            })
+            .collect())
+    }
+
+    fn initialise(
+        base_dir: PathBuf,
+        secret: &KeystoreSecret,
+        params: KdfParams,
+    ) -> Result<Self, SecureStorageError> {
+        let mut salt = [0u8; SALT_SIZE];
+        rand::rng().fill_bytes(&mut salt);
+        let key = derive_key(secret, &salt, params)?;
+        let storage = Self { base_dir, key };
+        storage.write_header(secret.mode(), params, &salt)?;
+
+        info!(mode = ?secret.mode(), "Initialised sealed file keystore");
+        Ok(storage)
+    }
+
+    fn write_header(
+        &self,
+        mode: KeystoreMode,
+        params: KdfParams,
+        salt: &[u8],
+    ) -> Result<(), SecureStorageError> {
+        let header = KeystoreHeader {
+            version: FORMAT_VERSION,
+            mode,
+            kdf: params,
+            salt: BASE64.encode(salt),
+            verifier: BASE64.encode(self.seal(VERIFIER_PLAINTEXT, VERIFIER_PLAINTEXT)?),
+        };
+        let bytes = serde_json::to_vec_pretty(&header)
+            .map_err(|err| SecureStorageError::Other(format!("encode keystore header: {err}")))?;
+        write_private(&self.base_dir.join(HEADER_FILE), &bytes)
+            .map_err(|err| map_io_error("failed to write keystore header", err))
+    }
+
+    fn unlock(
+        base_dir: PathBuf,
+        secret: &KeystoreSecret,
+        header: &KeystoreHeader,
+    ) -> Result<Self, SecureStorageError> {
+        if header.version != FORMAT_VERSION {
+            return Err(SecureStorageError::Corrupt(format!(
+                "unsupported keystore version {}",
+                header.version
+            )));
+        }
+        if header.mode != secret.mode() {
+            return Err(SecureStorageError::PermissionDenied(match header.mode {
+                KeystoreMode::Pin => {
+                    format!("keystore is sealed with a PIN; set {KEYSTORE_PIN_ENV}")
+                }
+                KeystoreMode::Machine => format!(
+                    "keystore is sealed with the machine secret and a password; \
+                     set {KEYSTORE_PASSWORD_ENV} and unset {KEYSTORE_PIN_ENV}"
+                ),
+                KeystoreMode::MachineOnly => format!(
+                    "keystore is sealed with the machine secret only; unset {KEYSTORE_PIN_ENV} \
+                     and {KEYSTORE_PASSWORD_ENV}, or re-key it with {KEYSTORE_NEW_PIN_ENV} or \
+                     {KEYSTORE_NEW_PASSWORD_ENV}"
+                ),
+            }));
+        }
+
+        let salt = BASE64
+            .decode(&header.salt)
+            .map_err(|err| SecureStorageError::Corrupt(format!("invalid keystore salt: {err}")))?;
+        let verifier = BASE64.decode(&header.verifier).map_err(|err| {
+            SecureStorageError::Corrupt(format!("invalid keystore verifier: {err}"))
+        })?;
+
+        let storage = Self {
+            base_dir,
+            key: derive_key(secret, &salt, header.kdf)?,
+        };
+        match storage.open_sealed(&verifier, VERIFIER_PLAINTEXT) {
+            Ok(plaintext) if plaintext.as_slice() == VERIFIER_PLAINTEXT => Ok(storage),
+            _ => Err(SecureStorageError::PermissionDenied(
+                "keystore secret does not match".to_string(),
+            )),
+        }
+    }
+
+    /// Seal every plaintext `.bin` entry and remove the original.
+    fn migrate_plaintext_entries(&self) -> Result<(), SecureStorageError> {
+        let entries = fs::read_dir(&self.base_dir)
+            .map_err(|err| map_io_error("failed to list keystore directory", err))?;
+
+        let mut migrated = 0usize;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("bin") {
+                continue;
+            }
+            let Some(key) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(decode_key)
+            else {
+                warn!(path = %path.display(), "Skipping unrecognised plaintext keystore file");
+                continue;
+            };
+
+            let value = Zeroizing::new(
+                fs::read(&path)
+                    .map_err(|err| map_io_error("failed to read plaintext keystore file", err))?,
+            );
+            self.set(&key, &value)?;
+            fs::remove_file(&path)
+                .map_err(|err| map_io_error("failed to remove plaintext keystore file", err))?;
+            migrated += 1;
+        }
+
+        if migrated > 0 {
+            info!(count = migrated, "Migrated plaintext keystore entries");
+        }
+        Ok(())
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.base_dir.join(format!("{}.sealed", encode_key(key)))
+    }
+
+    fn resealed_path(&self, key: &str) -> PathBuf {
+        self.base_dir
+            .join(format!("{}.{RESEALED_EXTENSION}", encode_key(key)))
+    }
+
+    fn legacy_path(&self, key: &str) -> PathBuf {
+        self.base_dir.join(format!("{}.bin", encode_key(key)))
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(Key::from_slice(&self.key[..]))
+    }
+
+    fn seal(&self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, SecureStorageError> {
+        let mut nonce = [0u8; NONCE_SIZE];
+        rand::rng().fill_bytes(&mut nonce);
+        let ciphertext = self
+            .cipher()
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext,
+                    aad,
+                },
+            )
+            .map_err(|_| SecureStorageError::Other("failed to seal keystore entry".to_string()))?;
+
+        let mut sealed = Vec::with_capacity(NONCE_SIZE + ciphertext.len());
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    fn open_sealed(&self, sealed: &[u8], aad: &[u8]) -> Result<Vec<u8>, SecureStorageError> {
+        if sealed.len() < NONCE_SIZE {
+            return Err(SecureStorageError::Corrupt(
+                "sealed keystore data is truncated".to_string(),
+            ));
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_SIZE);
+        self.cipher()
+            .decrypt(
+                XNonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad,
+                },
+            )
+            .map_err(|_| {
+                SecureStorageError::Corrupt("keystore entry failed integrity check".to_string())
+            })
+    }
+
+    fn encode_entry(&self, key: &str, value: &[u8]) -> Result<Vec<u8>, SecureStorageError> {
+        let sealed = self.seal(value, &entry_aad(key))?;
+        let mut bytes = Vec::with_capacity(ENTRY_MAGIC.len() + 1 + sealed.len());
+        bytes.extend_from_slice(ENTRY_MAGIC);
+        bytes.push(FORMAT_VERSION);
+        bytes.extend_from_slice(&sealed);
+        Ok(bytes)
+    }
+
+    fn decode_entry(&self, key: &str, bytes: &[u8]) -> Result<Vec<u8>, SecureStorageError> {
+        let header_len = ENTRY_MAGIC.len() + 1;
+        if bytes.len() < header_len || &bytes[..ENTRY_MAGIC.len()] != ENTRY_MAGIC {
+            return Err(SecureStorageError::Corrupt(
+                "keystore entry has an invalid header".to_string(),
+            ));
+        }
+        if bytes[ENTRY_MAGIC.len()] != FORMAT_VERSION {
+            return Err(SecureStorageError::Corrupt(format!(
+                "unsupported keystore entry version {}",
+                bytes[ENTRY_MAGIC.len()]
+            )));
+        }
+
+        self.open_sealed(&bytes[header_len..], &entry_aad(key))
+    }
+}
+
+impl SecureStoragePort for SealedFileSecureStorage {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, SecureStorageError> {
+        let bytes = match fs::read(self.entry_path(key)) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(map_io_error("failed to read keystore entry", err)),
+        };
+        self.decode_entry(key, &bytes).map(Some)
+    }
+
+    fn set(&self, key: &str, value: &[u8]) -> Result<(), SecureStorageError> {
+        write_private(&self.entry_path(key), &self.encode_entry(key, value)?)
+            .map_err(|err| map_io_error("failed to write keystore entry", err))
+    }
+
+    fn delete(&self, key: &str) -> Result<(), SecureStorageError> {
+        for path in [self.entry_path(key), self.legacy_path(key)] {
+            match fs::remove_file(&path) {
+                Ok(()) => {}
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(map_io_error("failed to delete keystore entry", err)),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn derive_key(
+    secret: &KeystoreSecret,
+    salt: &[u8],
+    params: KdfParams,
+) -> Result<Zeroizing<[u8; KEY_SIZE]>, SecureStorageError> {
+    let params = Params::new(
+        params.mem_kib,
+        params.iters,
+        params.parallelism,
+        Some(KEY_SIZE),
+    )
+    .map_err(|err| SecureStorageError::Corrupt(format!("invalid keystore KDF params: {err}")))?;
+
+    let mut key = Zeroizing::new([0u8; KEY_SIZE]);
+    let result = match secret {
+        KeystoreSecret::Pin(pin) => Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(pin.as_bytes(), salt, &mut key[..]),
+        KeystoreSecret::Machine {
+            machine_secret,
+            password,
+        } => Argon2::new_with_secret(
+            machine_secret.as_slice(),
+            Algorithm::Argon2id,
+            Version::V0x13,
+            params,
+        )
+        .map_err(|err| {
+            SecureStorageError::Other(format!("invalid keystore machine secret: {err}"))
+        })?
+        .hash_password_into(password.as_bytes(), salt, &mut key[..]),
+        KeystoreSecret::MachineOnly { machine_secret } => Argon2::new_with_secret(
+            machine_secret.as_slice(),
+            Algorithm::Argon2id,
+            Version::V0x13,
+            params,
+        )
+        .map_err(|err| {
+            SecureStorageError::Other(format!("invalid keystore machine secret: {err}"))
+        })?
+        .hash_password_into(&[], salt, &mut key[..]),
+    };
+    result.map_err(|err| SecureStorageError::Other(format!("keystore key derivation: {err}")))?;
+    Ok(key)
+}
+
+fn entry_aad(key: &str) -> Vec<u8> {
+    let mut aad = ENTRY_AAD_PREFIX.to_vec();
+    aad.extend_from_slice(key.as_bytes());
+    aad
+}
+
+fn encode_key(key: &str) -> String {
+    key.as_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn decode_key(hex: &str) -> Option<String> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    String::from_utf8(bytes).ok()
+}
+
+/// Write via a temp file and rename, restricting permissions to the owner.
+///
+/// The temp file is created owner-only (never briefly world-readable) and
+/// synced before the rename, so a crash leaves either the old or the new
+/// contents.
+fn write_private(path: &Path, bytes: &[u8]) -> Result<(), io::Error> {
+    use std::io::Write;
+
+    let temp_path = path.with_extension("tmp");
+    // A leftover temp file may carry looser permissions; `mode` only applies on create.
+    match fs::remove_file(&temp_path) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&temp_path)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(&temp_path, path)?;
+    #[cfg(unix)]
+    if let Some(parent) = path.parent() {
+        fs::File::open(parent)?.sync_all()?;
+    }
+    Ok(())
+}
+
+fn map_io_error(context: &str, err: io::Error) -> SecureStorageError {
+    SecureStorageError::Other(format!("{context}: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cheap KDF parameters to keep tests fast.
+    const TEST_PARAMS: KdfParams = KdfParams {
+        mem_kib: 8,
+        iters: 1,
+        parallelism: 1,
+    };
+
+    fn pin(value: &str) -> KeystoreSecret {
+        KeystoreSecret::Pin(Zeroizing::new(value.to_string()))
+    }
+
+    fn open(
+        dir: &Path,
+        secret: &KeystoreSecret,
+    ) -> Result<SealedFileSecureStorage, SecureStorageError> {
+        SealedFileSecureStorage::open_with_params(dir.to_path_buf(), secret, TEST_PARAMS)
+    }
+
+    #[test]
+    fn roundtrip_stores_sealed_entries() {
+        let temp_dir = tempfile::TempDir::new().expect("temp dir");
+        let secret = KeystoreSecret::Machine {
+            machine_secret: Zeroizing::new(b"machine-id".to_vec()),
+            password: Zeroizing::new("hunter2".to_string()),
+        };
+        let storage = open(temp_dir.path(), &secret).expect("open");
+        storage.set("kek:v1:profile:demo", b"kek").expect("set");
+
+        let reopened = open(temp_dir.path(), &secret).expect("reopen");
+        assert_eq!(
+            reopened.get("kek:v1:profile:demo").expect("get"),
+            Some(b"kek".to_vec())
+        );
+        let raw = fs::read(storage.entry_path("kek:v1:profile:demo")).expect("raw");
+        assert!(!raw.windows(3).any(|window| window == b"kek"));
+        assert!(reopened.get("libp2p-identity:v1").expect("get").is_none());
+    }
+
+    #[test]
+    fn tampered_entry_is_detected() {
+        let temp_dir = tempfile::TempDir::new().expect("temp dir");
+        let storage = open(temp_dir.path(), &pin("1234")).expect("open");
+        storage.set("kek:v1:profile:demo", b"kek").expect("set");
+        storage.set("libp2p-identity:v1", b"identity").expect("set");
+
+        let path = storage.entry_path("kek:v1:profile:demo");
+        let mut raw = fs::read(&path).expect("raw");
+        let last = raw.len() - 1;
+        raw[last] ^= 0x01;
+        fs::write(&path, &raw).expect("write");
+        assert!(matches!(
+            storage.get("kek:v1:profile:demo"),
+            Err(SecureStorageError::Corrupt(_))
+        ));
+
+        // Swapping one entry's file for another's must also fail.
+        fs::copy(storage.entry_path("libp2p-identity:v1"), &path).expect("copy");
+        assert!(matches!(
+            storage.get("kek:v1:profile:demo"),
+            Err(SecureStorageError::Corrupt(_))
+        ));
+    }
+
+    #[test]
+    fn wrong_pin_is_rejected() {
+        let temp_dir = tempfile::TempDir::new().expect("temp dir");
+        open(temp_dir.path(), &pin("1234")).expect("open");
+
+        assert!(matches!(
+            open(temp_dir.path(), &pin("4321")),
+            Err(SecureStorageError::PermissionDenied(_))
+        ));
+    }
+
+    #[test]
+    fn machine_mode_requires_a_password() {
+        let temp_dir = tempfile::TempDir::new().expect("temp dir");
+        let secret = KeystoreSecret::Machine {
+            machine_secret: Zeroizing::new(b"machine-id".to_vec()),
+            password: Zeroizing::new(String::new()),
+        };
+
+        assert!(matches!(
+            open(temp_dir.path(), &secret),
+            Err(SecureStorageError::PermissionDenied(_))
+        ));
+        assert!(matches!(
+            open(temp_dir.path(), &pin("")),
+            Err(SecureStorageError::PermissionDenied(_))
+        ));
+        assert!(!temp_dir.path().join(HEADER_FILE).exists());
+    }
+
+    #[test]
+    fn rekey_reseals_entries_under_the_new_secret() {
+        let temp_dir = tempfile::TempDir::new().expect("temp dir");
+        let machine_only = KeystoreSecret::MachineOnly {
+            machine_secret: Zeroizing::new(b"machine-id".to_vec()),
+        };
+        let storage = open(temp_dir.path(), &machine_only).expect("open");
+        storage.set("kek:v1:profile:demo", b"kek").expect("set");
+
+        let rekeyed = storage
+            .rekey_with_params(&pin("1234"), TEST_PARAMS)
+            .expect("rekey");
+        assert_eq!(
+            rekeyed.get("kek:v1:profile:demo").expect("get"),
+            Some(b"kek".to_vec())
+        );
+
+        assert!(matches!(
+            open(temp_dir.path(), &machine_only),
+            Err(SecureStorageError::PermissionDenied(_))
+        ));
+        let reopened = open(temp_dir.path(), &pin("1234")).expect("reopen");
+        assert_eq!(
+            reopened.get("kek:v1:profile:demo").expect("get"),
+            Some(b"kek".to_vec())
+        );
+    }
+
+    #[test]
+    fn interrupted_rekey_keeps_the_old_secret() {
+        let temp_dir = tempfile::TempDir::new().expect("temp dir");
+        let storage = open(temp_dir.path(), &pin("1234")).expect("open");
+        storage.set("kek:v1:profile:demo", b"kek").expect("set");
+
+        // Simulate a crash after resealing an entry but before the header swap.
+        let other = open(
+            tempfile::TempDir::new().expect("temp dir").path(),
+            &pin("9999"),
+        )
+        .expect("open other");
+        let resealed = other
+            .encode_entry("kek:v1:profile:demo", b"kek")
+            .expect("encode");
+        fs::write(storage.resealed_path("kek:v1:profile:demo"), resealed).expect("write");
+
+        let reopened = open(temp_dir.path(), &pin("1234")).expect("reopen");
+        assert_eq!(
+            reopened.get("kek:v1:profile:demo").expect("get"),
+            Some(b"kek".to_vec())
+        );
+        assert!(!reopened.resealed_path("kek:v1:profile:demo").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn entries_are_written_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::TempDir::new().expect("temp dir");
+        let storage = open(temp_dir.path(), &pin("1234")).expect("open");
+        let path = storage.entry_path("kek:v1:profile:demo");
+        // A stale temp file with loose permissions must not be reused.
+        fs::write(path.with_extension("tmp"), b"stale").expect("write");
+        fs::set_permissions(
+            path.with_extension("tmp"),
+            fs::Permissions::from_mode(0o644),
+        )
+        .expect("chmod");
+
+        storage.set("kek:v1:profile:demo", b"kek").expect("set");
+
+        for path in [path, temp_dir.path().join(HEADER_FILE)] {
+            let mode = fs::metadata(&path).expect("metadata").permissions().mode();
+            assert_eq!(mode & 0o777, 0o600, "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn plaintext_entries_are_migrated() {
+        let temp_dir = tempfile::TempDir::new().expect("temp dir");
+        let legacy = crate::file_secure_storage::FileSecureStorage::with_base_dir(
+            temp_dir.path().to_path_buf(),
+        );
+        legacy
+            .set("kek:v1:profile:demo", b"kek")
+            .expect("legacy set");
+
+        let storage = open(temp_dir.path(), &pin("1234")).expect("open");
+
+        assert_eq!(
+            storage.get("kek:v1:profile:demo").expect("get"),
+            Some(b"kek".to_vec())
+        );
+        assert!(!storage.legacy_path("kek:v1:profile:demo").exists());
+    }
+}
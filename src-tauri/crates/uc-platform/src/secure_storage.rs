@@ -7,7 +7,7 @@ use uc_core::ports::SecureStoragePort;
 
 use crate::{
     capability::{detect_storage_capability, SecureStorageCapability},
-    file_secure_storage::FileSecureStorage,
+    sealed_file_secure_storage::SealedFileSecureStorage,
     system_secure_storage::SystemSecureStorage,
 };
 
@@ -18,6 +18,9 @@ pub enum SecureStorageFactoryError {
 
     #[error("failed to initialize file-based secure storage: {0}")]
     FileBasedInit(#[from] std::io::Error),
+
+    #[error("failed to open sealed file keystore: {0}")]
+    SealedKeystore(#[from] uc_core::ports::SecureStorageError),
 }
 
 fn secure_storage_from_capability(
@@ -29,8 +32,8 @@ fn secure_storage_from_capability(
 /// Create a secure storage instance matching the provided secure storage capability.
 ///
 /// If `capability` indicates system storage, returns a system-backed implementation wrapped in
-/// `Arc<dyn SecureStoragePort>`. If `capability` is `FileBasedKeystore`, returns a file-backed
-/// implementation using the provided `base_dir`. If `base_dir` is `None`,
+/// `Arc<dyn SecureStoragePort>`. If `capability` is `FileBasedKeystore`, returns a sealed
+/// file-backed implementation using the provided `base_dir`. If `base_dir` is `None`,
 /// returns `SecureStorageFactoryError::FileBasedInit` with `std::io::ErrorKind::NotFound`.
 /// If `capability` is `Unsupported`, returns `SecureStorageFactoryError::Unsupported` containing
 /// the provided capability.
@@ -63,7 +66,7 @@ fn secure_storage_from_capability_with_base_dir(
         SecureStorageCapability::FileBasedKeystore => {
             if let Some(base_dir) = base_dir {
                 fs::create_dir_all(&base_dir)?;
-                Ok(Arc::new(FileSecureStorage::with_base_dir(base_dir))
+                Ok(Arc::new(SealedFileSecureStorage::open_from_env(base_dir)?)
                     as Arc<dyn SecureStoragePort>)
             } else {
                 Err(SecureStorageFactoryError::FileBasedInit(
@@ -134,8 +137,8 @@ pub fn create_default_secure_storage(
 ///
 /// Detects the platform's secure storage capability and returns an appropriate `SecureStoragePort`:
 /// - If system secure storage is available, returns the system-backed implementation.
-/// - If a file-based keystore is detected, initializes a file-backed implementation rooted at
-///   `app_data_root`.
+/// - If a file-based keystore is detected, opens the sealed file keystore rooted at
+///   `app_data_root`, migrating any plaintext entries.
 /// - If secure storage is unsupported, returns `SecureStorageFactoryError::Unsupported`.
 ///
 /// # Parameters
@@ -146,6 +149,8 @@ pub fn create_default_secure_storage(
 ///
 /// Returns `SecureStorageFactoryError::Unsupported` when secure storage is not available.
 /// Returns `SecureStorageFactoryError::FileBasedInit` if initialization of file-based storage fails.
+/// Returns `SecureStorageFactoryError::SealedKeystore` if the keystore secret is wrong or the
+/// keystore is damaged.
 ///
 /// # Examples
 ///
@@ -171,11 +176,12 @@ pub fn create_default_secure_storage_in_app_data_root(
             secure_storage_from_capability(capability)
         }
         SecureStorageCapability::FileBasedKeystore => {
-            warn!("Using file-based secure storage (insecure dev fallback for WSL/headless environments)");
-            Ok(
-                Arc::new(FileSecureStorage::new_in_app_data_root(app_data_root)?)
-                    as Arc<dyn SecureStoragePort>,
-            )
+            warn!(
+                "Using sealed file-based secure storage (fallback for WSL/headless environments)"
+            );
+            Ok(Arc::new(SealedFileSecureStorage::open_in_app_data_root(
+                app_data_root,
+            )?) as Arc<dyn SecureStoragePort>)
         }
         SecureStorageCapability::Unsupported => {
             error!(capability = ?capability, "Secure storage unsupported");
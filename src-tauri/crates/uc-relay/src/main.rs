@@ -0,0 +1,173 @@
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+use libp2p::{
+    futures::StreamExt,
+    identify, identity,
+    multiaddr::Protocol,
+    noise, ping, relay, rendezvous,
+    swarm::{NetworkBehaviour, SwarmEvent},
+    tcp, yamux, Multiaddr, SwarmBuilder,
+};
+use std::fs;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Must match the identify protocol version advertised by the app.
+const IDENTIFY_PROTOCOL_VERSION: &str = "/uniclipboard/id/1.0.0";
+
+#[derive(Parser)]
+#[command(name = "uc-relay")]
+#[command(about = "Relay and rendezvous node for UniClipboard", long_about = None)]
+struct Cli {
+    /// Port for both TCP and QUIC listeners
+    #[arg(short, long, default_value_t = 4001)]
+    port: u16,
+    /// IP address to listen on
+    #[arg(long, default_value = "0.0.0.0")]
+    listen_ip: IpAddr,
+    /// Identity key file, created on first run so the peer id stays stable
+    #[arg(long, default_value = "uc-relay.key")]
+    identity: PathBuf,
+    /// Maximum lifetime of a relayed connection
+    #[arg(long, default_value_t = 3600)]
+    max_circuit_duration_secs: u64,
+    /// Maximum bytes per direction of a relayed connection, in MiB
+    #[arg(long, default_value_t = 64)]
+    max_circuit_mib: u64,
+}
+
+#[derive(NetworkBehaviour)]
+struct RelayBehaviour {
+    relay: relay::Behaviour,
+    rendezvous: rendezvous::server::Behaviour,
+    identify: identify::Behaviour,
+    ping: ping::Behaviour,
+}
+
+fn load_or_create_identity(path: &Path) -> Result<identity::Keypair> {
+    if path.exists() {
+        let bytes = fs::read(path).with_context(|| format!("read {}", path.display()))?;
+        return identity::Keypair::from_protobuf_encoding(&bytes)
+            .map_err(|e| anyhow!("invalid identity file {}: {e}", path.display()));
+    }
+
+    let keypair = identity::Keypair::generate_ed25519();
+    let bytes = keypair
+        .to_protobuf_encoding()
+        .map_err(|e| anyhow!("encode identity: {e}"))?;
+    fs::write(path, bytes).with_context(|| format!("write {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    }
+    info!("created new identity at {}", path.display());
+    Ok(keypair)
+}
+
+fn relay_config(cli: &Cli) -> relay::Config {
+    relay::Config {
+        max_circuit_duration: Duration::from_secs(cli.max_circuit_duration_secs),
+        max_circuit_bytes: cli.max_circuit_mib * 1024 * 1024,
+        ..relay::Config::default()
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()),
+        )
+        .init();
+
+    let cli = Cli::parse();
+    let keypair = load_or_create_identity(&cli.identity)?;
+    let relay_config = relay_config(&cli);
+
+    let mut swarm = SwarmBuilder::with_existing_identity(keypair)
+        .with_tokio()
+        .with_tcp(
+            tcp::Config::default().nodelay(true),
+            noise::Config::new,
+            yamux::Config::default,
+        )?
+        .with_quic()
+        .with_behaviour(|key| RelayBehaviour {
+            relay: relay::Behaviour::new(key.public().to_peer_id(), relay_config),
+            rendezvous: rendezvous::server::Behaviour::new(rendezvous::server::Config::default()),
+            identify: identify::Behaviour::new(identify::Config::new(
+                IDENTIFY_PROTOCOL_VERSION.to_string(),
+                key.public(),
+            )),
+            ping: ping::Behaviour::default(),
+        })
+        .map_err(|e| anyhow!("failed to build relay behaviour: {e}"))?
+        .with_swarm_config(|config| config.with_idle_connection_timeout(Duration::from_secs(60)))
+        .build();
+
+    let base = Multiaddr::from(cli.listen_ip);
+    swarm.listen_on(base.clone().with(Protocol::Tcp(cli.port)))?;
+    swarm.listen_on(base.with(Protocol::Udp(cli.port)).with(Protocol::QuicV1))?;
+
+    let local_peer_id = *swarm.local_peer_id();
+    info!(peer_id = %local_peer_id, "relay started");
+
+    loop {
+        match swarm.select_next_some().await {
+            SwarmEvent::NewListenAddr { address, .. } => {
+                // Paste one of these into the app's relay address setting.
+                println!("{}", address.with(Protocol::P2p(local_peer_id)));
+            }
+            SwarmEvent::Behaviour(RelayBehaviourEvent::Identify(identify::Event::Received {
+                peer_id,
+                info,
+                ..
+            })) => {
+                // Reservations require the relay to know its public address.
+                debug!(peer_id = %peer_id, observed = %info.observed_addr, "identify received");
+                swarm.add_external_address(info.observed_addr);
+            }
+            SwarmEvent::Behaviour(RelayBehaviourEvent::Relay(event)) => match event {
+                relay::Event::ReservationReqAccepted { src_peer_id, .. } => {
+                    info!(peer_id = %src_peer_id, "reservation accepted")
+                }
+                relay::Event::CircuitReqAccepted {
+                    src_peer_id,
+                    dst_peer_id,
+                } => info!(src = %src_peer_id, dst = %dst_peer_id, "circuit opened"),
+                other => debug!(event = ?other, "relay event"),
+            },
+            SwarmEvent::Behaviour(RelayBehaviourEvent::Rendezvous(event)) => match event {
+                rendezvous::server::Event::PeerRegistered { peer, registration } => info!(
+                    peer_id = %peer,
+                    namespace = %registration.namespace,
+                    "peer registered"
+                ),
+                other => debug!(event = ?other, "rendezvous event"),
+            },
+            SwarmEvent::IncomingConnectionError { error, .. } => {
+                warn!("incoming connection error: {error}")
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identity_is_created_once_and_reloaded() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("relay.key");
+
+        let created = load_or_create_identity(&path).unwrap();
+        let reloaded = load_or_create_identity(&path).unwrap();
+
+        assert_eq!(created.public(), reloaded.public());
+    }
+}
@@ -3,6 +3,7 @@
 //! This module contains initialization functions that run during application startup.
 
 use std::sync::Arc;
+use uc_core::ports::space::ActiveSpacePort;
 use uc_core::ports::SettingsPort;
 
 /// Ensures the device has a valid name by initializing it with the system hostname if empty.
@@ -60,6 +61,29 @@ pub async fn ensure_default_device_name(
     Ok(())
 }
 
+/// Restores the active space persisted in settings.
+///
+/// Must run before the clipboard watcher, network or auto-unlock start, since
+/// repositories and the key scope read the active space on every call. An
+/// unknown space id falls back to the default space.
+pub async fn restore_active_space(
+    settings: Arc<dyn SettingsPort>,
+    active_space: Arc<dyn ActiveSpacePort>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let current_settings = settings.load().await?;
+    let space_id = &current_settings.spaces.active_space_id;
+
+    if current_settings.spaces.find(space_id).is_some() {
+        tracing::info!(space_id = %space_id, "Restored active space");
+        active_space.set_active_space(space_id.clone());
+    } else {
+        tracing::warn!(space_id = %space_id, "Active space unknown, using default space");
+        active_space.set_active_space(uc_core::ids::SpaceId::default_space());
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -11,7 +11,7 @@ pub mod wiring;
 
 // Re-export commonly used bootstrap functions
 pub use config::load_config;
-pub use init::ensure_default_device_name;
+pub use init::{ensure_default_device_name, restore_active_space};
 pub use runtime::{create_app, create_runtime, AppRuntime, SetupRuntimePorts, UseCases};
 pub use wiring::{
     resolve_pairing_config, resolve_pairing_device_name, start_background_tasks, wire_dependencies,
@@ -35,6 +35,7 @@ use tauri::Emitter;
 use tokio::sync::Mutex;
 use uc_app::{
     usecases::{
+        internal::capture_clipboard::{CaptureClipboardUseCase, CaptureOutcome},
         space_access::{
             DefaultSpaceAccessCryptoFactory, HmacProofAdapter, SpaceAccessNetworkAdapter,
             SpaceAccessOrchestrator, SpaceAccessPersistenceAdapter,
@@ -98,6 +99,11 @@ pub struct AppRuntime {
     /// 缓存的 Setup 编排器 – 在所有 Tauri 命令间共享，
     /// 避免每次调用都重置内存中的 Setup 状态机。
     setup_orchestrator: Arc<SetupOrchestrator>,
+    /// Keyslot update from a peer waiting for the user's passphrase, shared by
+    /// the network event loop and the confirmation commands.
+    ///
+    /// 等待用户输入口令确认的密钥槽更新，由网络事件循环与确认命令共享。
+    pending_keyslot_updates: Arc<uc_app::usecases::PendingKeySlotUpdates>,
 }
 
 /// Setup wiring dependencies for runtime-level orchestrators.
@@ -169,9 +175,16 @@ impl AppRuntime {
             app_handle,
             lifecycle_status,
             setup_orchestrator,
+            pending_keyslot_updates: Arc::new(uc_app::usecases::PendingKeySlotUpdates::new()),
         }
     }
 
+    /// Keyslot updates staged for user confirmation.
+    /// 等待用户确认的密钥槽更新。
+    pub fn pending_keyslot_updates(&self) -> Arc<uc_app::usecases::PendingKeySlotUpdates> {
+        self.pending_keyslot_updates.clone()
+    }
+
     /// Set the Tauri AppHandle for event emission.
     /// This must be called after Tauri setup completes.
     pub fn set_app_handle(&self, handle: tauri::AppHandle) {
@@ -407,6 +420,38 @@ impl<'a> UseCases<'a> {
         )
     }
 
+    /// Pin or unpin a clipboard entry.
+    ///
+    /// 置顶或取消置顶剪贴板条目。
+    pub fn pin_clipboard_entry(&self) -> uc_app::usecases::clipboard::PinClipboardEntryUseCase {
+        uc_app::usecases::clipboard::PinClipboardEntryUseCase::new(
+            self.runtime.deps.clipboard_entry_repo.clone(),
+        )
+    }
+
+    /// Clear clipboard history, keeping pinned entries.
+    ///
+    /// 清空剪贴板历史（保留置顶条目）。
+    pub fn clear_clipboard_history(&self) -> uc_app::usecases::ClearClipboardHistory {
+        uc_app::usecases::ClearClipboardHistory::from_ports(
+            self.runtime.deps.clipboard_entry_repo.clone(),
+            self.runtime.deps.selection_repo.clone(),
+            self.runtime.deps.clipboard_event_repo.clone(),
+        )
+    }
+
+    /// Collect unreferenced blobs, orphan files and orphan thumbnails.
+    ///
+    /// 回收未被引用的 blob、孤立文件和孤立缩略图。
+    pub fn collect_blob_garbage(&self) -> uc_app::usecases::CollectBlobGarbage {
+        uc_app::usecases::CollectBlobGarbage::new(
+            self.runtime.deps.blob_repository.clone(),
+            self.runtime.deps.blob_store.clone(),
+            self.runtime.deps.thumbnail_repo.clone(),
+            self.runtime.deps.clock.clone(),
+        )
+    }
+
     /// Get the GetEntryDetail use case for fetching full clipboard entry content.
     ///
     /// 获取 GetEntryDetail 用例以获取完整剪贴板条目内容。
@@ -431,6 +476,7 @@ impl<'a> UseCases<'a> {
             self.runtime.deps.selection_repo.clone(),
             self.runtime.deps.representation_repo.clone(),
             self.runtime.deps.blob_store.clone(),
+            self.runtime.deps.payload_resolver.clone(),
         )
     }
 
@@ -504,6 +550,20 @@ impl<'a> UseCases<'a> {
         uc_app::usecases::ListConnectedPeers::new(self.runtime.deps.network.clone())
     }
 
+    /// List the latest heartbeat and RTT per peer.
+    ///
+    /// 列出各对端最近一次心跳与往返时延。
+    pub fn list_peer_liveness(&self) -> uc_app::usecases::ListPeerLiveness {
+        uc_app::usecases::ListPeerLiveness::new(self.runtime.deps.network.clone())
+    }
+
+    /// List the protocol version and features negotiated per peer.
+    ///
+    /// 列出与各对端协商得到的协议版本与特性。
+    pub fn list_peer_capabilities(&self) -> uc_app::usecases::ListPeerCapabilities {
+        uc_app::usecases::ListPeerCapabilities::new(self.runtime.deps.network.clone())
+    }
+
     /// Update pairing state for a peer.
     ///
     /// 更新对等端配对状态。
@@ -511,9 +571,23 @@ impl<'a> UseCases<'a> {
         uc_app::usecases::SetPairingState::new(self.runtime.deps.paired_device_repo.clone())
     }
 
-    /// Unpair device and remove from repository.
+    /// Read the sync policy of a paired device.
+    ///
+    /// 读取已配对设备的同步策略。
+    pub fn get_device_sync_policy(&self) -> uc_app::usecases::GetDeviceSyncPolicy {
+        uc_app::usecases::GetDeviceSyncPolicy::new(self.runtime.deps.paired_device_repo.clone())
+    }
+
+    /// Store the sync policy of a paired device.
+    ///
+    /// 保存已配对设备的同步策略。
+    pub fn set_device_sync_policy(&self) -> uc_app::usecases::SetDeviceSyncPolicy {
+        uc_app::usecases::SetDeviceSyncPolicy::new(self.runtime.deps.paired_device_repo.clone())
+    }
+
+    /// Unpair device, remove it from the repository and notify the peer.
     ///
-    /// 取消配对并从存储中删除。
+    /// 取消配对：从存储中删除并通知对端（离线时在重连后补发）。
     pub fn unpair_device(&self) -> uc_app::usecases::UnpairDevice {
         uc_app::usecases::UnpairDevice::new(
             self.runtime.deps.network.clone(),
@@ -521,6 +595,46 @@ impl<'a> UseCases<'a> {
         )
     }
 
+    /// Add a peer address for networks without mDNS.
+    ///
+    /// 手动添加对端地址（mDNS 不可用时）。
+    pub fn add_manual_peer(&self) -> uc_app::usecases::AddManualPeer {
+        uc_app::usecases::AddManualPeer::new(
+            self.runtime.deps.network.clone(),
+            self.runtime.deps.settings.clone(),
+        )
+    }
+
+    /// Get local interfaces and the addresses the node listens on.
+    ///
+    /// 获取本机网卡与监听地址。
+    pub fn get_local_network_info(&self) -> uc_app::usecases::GetLocalNetworkInfo {
+        uc_app::usecases::GetLocalNetworkInfo::new(self.runtime.deps.network.clone())
+    }
+
+    /// Issue a one-time pairing invitation (QR code / URI).
+    ///
+    /// 生成一次性配对邀请（二维码/URI）。
+    pub fn create_pairing_invitation(
+        &self,
+        orchestrator: Arc<PairingOrchestrator>,
+    ) -> uc_app::usecases::CreatePairingInvitation {
+        uc_app::usecases::CreatePairingInvitation::new(
+            self.runtime.deps.network.clone(),
+            orchestrator,
+        )
+    }
+
+    /// Pair with a device from its scanned or pasted invitation.
+    ///
+    /// 使用扫描/粘贴的邀请与对端配对。
+    pub fn pair_with_invitation(
+        &self,
+        orchestrator: Arc<PairingOrchestrator>,
+    ) -> uc_app::usecases::PairWithInvitation {
+        uc_app::usecases::PairWithInvitation::new(self.runtime.deps.network.clone(), orchestrator)
+    }
+
     /// Resolve thumbnail resource content by representation id.
     ///
     /// 通过表示 id 解析缩略图资源内容。
@@ -563,6 +677,76 @@ impl<'a> UseCases<'a> {
         )
     }
 
+    /// Change the encryption passphrase and push the new keyslot to trusted peers.
+    ///
+    /// 修改加密口令并将新的密钥槽推送给受信任设备。
+    pub fn change_passphrase(&self) -> uc_app::usecases::ChangePassphrase {
+        uc_app::usecases::ChangePassphrase::new(
+            self.runtime.deps.encryption.clone(),
+            self.runtime.deps.key_material.clone(),
+            self.runtime.deps.key_scope.clone(),
+            self.runtime.deps.encryption_state.clone(),
+            self.runtime.deps.paired_device_repo.clone(),
+            self.runtime.deps.network.clone(),
+            self.runtime.deps.device_identity.clone(),
+        )
+    }
+
+    /// Stage a keyslot update received from a trusted peer for user confirmation.
+    ///
+    /// 暂存从受信任设备收到的密钥槽更新，等待用户确认。
+    pub fn apply_keyslot_update(&self) -> uc_app::usecases::ApplyKeySlotUpdate {
+        uc_app::usecases::ApplyKeySlotUpdate::new(
+            self.runtime.deps.encryption.clone(),
+            self.runtime.deps.encryption_session.clone(),
+            self.runtime.deps.key_material.clone(),
+            self.runtime.deps.key_scope.clone(),
+            self.runtime.deps.paired_device_repo.clone(),
+            self.runtime.pending_keyslot_updates(),
+        )
+    }
+
+    /// Apply the staged keyslot update with the passphrase entered by the user.
+    ///
+    /// 使用用户输入的口令应用暂存的密钥槽更新。
+    pub fn confirm_keyslot_update(&self) -> uc_app::usecases::ConfirmKeySlotUpdate {
+        uc_app::usecases::ConfirmKeySlotUpdate::new(
+            self.runtime.deps.encryption.clone(),
+            self.runtime.deps.encryption_session.clone(),
+            self.runtime.deps.key_material.clone(),
+            self.runtime.deps.key_scope.clone(),
+            self.runtime.pending_keyslot_updates(),
+        )
+    }
+
+    /// Rotate the master key and push the new keyslot to trusted peers.
+    ///
+    /// 轮换主密钥并将新的密钥槽推送给受信任设备。
+    pub fn rotate_master_key(&self) -> uc_app::usecases::RotateMasterKey {
+        uc_app::usecases::RotateMasterKey::new(
+            self.runtime.deps.encryption.clone(),
+            self.runtime.deps.encryption_session.clone(),
+            self.runtime.deps.key_material.clone(),
+            self.runtime.deps.key_scope.clone(),
+            self.runtime.deps.encryption_state.clone(),
+            self.runtime.deps.paired_device_repo.clone(),
+            self.runtime.deps.network.clone(),
+            self.runtime.deps.device_identity.clone(),
+        )
+    }
+
+    /// Re-encrypt history after a key rotation (run by the background rotation loop).
+    ///
+    /// 密钥轮换后重新加密历史数据（由后台轮换循环执行）。
+    pub fn reencrypt_history(&self) -> uc_app::usecases::ReencryptHistory {
+        uc_app::usecases::ReencryptHistory::new(
+            self.runtime.deps.payload_reencryption.clone(),
+            self.runtime.deps.encryption_session.clone(),
+            self.runtime.deps.key_material.clone(),
+            self.runtime.deps.key_scope.clone(),
+        )
+    }
+
     /// Get the AutoUnlockEncryptionSession use case for startup unlock.
     pub fn auto_unlock_encryption_session(&self) -> uc_app::usecases::AutoUnlockEncryptionSession {
         uc_app::usecases::AutoUnlockEncryptionSession::from_ports(
@@ -574,6 +758,40 @@ impl<'a> UseCases<'a> {
         )
     }
 
+    /// List known spaces and mark the active one.
+    ///
+    /// 列出本机已知空间并标记活动空间。
+    pub fn list_spaces(&self) -> uc_app::usecases::ListSpaces {
+        uc_app::usecases::ListSpaces::new(self.runtime.deps.settings.clone())
+    }
+
+    /// Create a space with its own passphrase and switch to it.
+    ///
+    /// 以独立口令新建空间并切换到该空间。
+    pub fn create_space(&self) -> uc_app::usecases::CreateSpace {
+        uc_app::usecases::CreateSpace::new(
+            self.runtime.deps.settings.clone(),
+            self.runtime.deps.active_space.clone(),
+            self.runtime.deps.encryption_session.clone(),
+            self.runtime.deps.search_index.clone(),
+            Arc::new(self.initialize_encryption()),
+            self.runtime.deps.clock.clone(),
+        )
+    }
+
+    /// Switch the active space, then try to auto-unlock its session.
+    ///
+    /// 切换活动空间，并尝试自动解锁该空间的会话。
+    pub fn switch_space(&self) -> uc_app::usecases::SwitchSpace {
+        uc_app::usecases::SwitchSpace::new(
+            self.runtime.deps.settings.clone(),
+            self.runtime.deps.active_space.clone(),
+            self.runtime.deps.encryption_session.clone(),
+            self.runtime.deps.search_index.clone(),
+        )
+        .with_auto_unlock(Arc::new(self.auto_unlock_encryption_session()))
+    }
+
     pub fn setup_orchestrator(&self) -> Arc<SetupOrchestrator> {
         self.runtime.setup_orchestrator.clone()
     }
@@ -668,6 +886,19 @@ impl<'a> UseCases<'a> {
         )
     }
 
+    /// Full-text search over clipboard history.
+    ///
+    /// 剪贴板历史全文检索。
+    pub fn search_clipboard_entries(&self) -> uc_app::usecases::clipboard::SearchClipboardEntries {
+        uc_app::usecases::clipboard::SearchClipboardEntries::new(
+            self.runtime.deps.clipboard_entry_repo.clone(),
+            self.runtime.deps.selection_repo.clone(),
+            self.runtime.deps.representation_repo.clone(),
+            self.runtime.deps.blob_store.clone(),
+            self.runtime.deps.search_index.clone(),
+        )
+    }
+
     /// Restore clipboard selection to system clipboard.
     ///
     /// 将历史剪贴板条目恢复到系统剪贴板。
@@ -681,10 +912,31 @@ impl<'a> UseCases<'a> {
             self.runtime.deps.selection_repo.clone(),
             self.runtime.deps.representation_repo.clone(),
             self.runtime.deps.blob_store.clone(),
+            self.runtime.deps.payload_resolver.clone(),
             self.runtime.deps.clipboard_change_origin.clone(),
         )
     }
 
+    /// Sync a locally captured snapshot to trusted peers.
+    ///
+    /// 将本地捕获的快照同步到受信任设备。
+    pub fn sync_outbound_clipboard(
+        &self,
+    ) -> uc_app::usecases::clipboard::SyncOutboundClipboardUseCase {
+        uc_app::usecases::clipboard::SyncOutboundClipboardUseCase::new(
+            self.runtime.deps.representation_policy.clone(),
+            self.runtime.deps.sync_filter_policy.clone(),
+            self.runtime.deps.encryption.clone(),
+            self.runtime.deps.encryption_session.clone(),
+            self.runtime.deps.paired_device_repo.clone(),
+            self.runtime.deps.network.clone(),
+            self.runtime.deps.device_identity.clone(),
+            self.runtime.deps.settings.clone(),
+            self.runtime.deps.outbound_sync_queue.clone(),
+            self.runtime.deps.clock.clone(),
+        )
+    }
+
     /// Touch clipboard entry active time.
     ///
     /// 更新剪贴板条目活跃时间。
@@ -840,7 +1092,7 @@ impl ClipboardChangeHandler for AppRuntime {
             .await;
 
         // Create CaptureClipboardUseCase with dependencies
-        let usecase = uc_app::usecases::internal::capture_clipboard::CaptureClipboardUseCase::new(
+        let usecase = CaptureClipboardUseCase::new(
             self.deps.clipboard_entry_repo.clone(),
             self.deps.clipboard_event_repo.clone(),
             self.deps.representation_policy.clone(),
@@ -848,11 +1100,22 @@ impl ClipboardChangeHandler for AppRuntime {
             self.deps.device_identity.clone(),
             self.deps.representation_cache.clone(),
             self.deps.spool_queue.clone(),
+            self.deps.search_index.clone(),
+            self.deps.sync_filter_policy.clone(),
+            self.deps.settings.clone(),
         );
 
+        // Only locally captured content is synced; remote pushes must not echo back.
+        let sync_snapshot =
+            (origin == ClipboardChangeOrigin::LocalCapture).then(|| snapshot.clone());
+
         // Execute capture with the provided snapshot
         match usecase.execute_with_origin(snapshot, origin).await {
-            Ok(event_id) => {
+            Ok(CaptureOutcome::NotRecorded(reason)) => {
+                tracing::debug!(reason = ?reason, "Clipboard change not recorded");
+                Ok(())
+            }
+            Ok(CaptureOutcome::Recorded(event_id)) => {
                 tracing::debug!("Successfully captured clipboard, event_id: {}", event_id);
 
                 // Emit event to frontend if AppHandle is available
@@ -878,6 +1141,17 @@ impl ClipboardChangeHandler for AppRuntime {
                 }
                 drop(app_handle_guard);
 
+                if let Some(sync_snapshot) = sync_snapshot {
+                    if let Err(e) = self
+                        .usecases()
+                        .sync_outbound_clipboard()
+                        .execute(sync_snapshot)
+                        .await
+                    {
+                        tracing::warn!("Failed to sync clipboard to peers: {:?}", e);
+                    }
+                }
+
                 Ok(())
             }
             Err(e) => {
@@ -906,7 +1180,7 @@ mod tests {
         MasterKey, Passphrase,
     };
     use uc_core::security::state::{EncryptionState, EncryptionStateError};
-    use uc_core::PeerId;
+    use uc_core::testing::{InMemoryPairedDeviceRepository, TestNetwork};
     use uc_core::{Blob, BlobId, ClipboardChangeOrigin, ContentHash, DeviceId};
     use uc_infra::clipboard::InMemoryClipboardChangeOrigin;
 
@@ -968,6 +1242,58 @@ mod tests {
         async fn delete_entry(&self, _entry_id: &uc_core::ids::EntryId) -> anyhow::Result<()> {
             Ok(())
         }
+
+        async fn list_retention_candidates(
+            &self,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::RetentionCandidate>> {
+            Ok(Vec::new())
+        }
+
+        async fn soft_delete_entries(
+            &self,
+            _entry_ids: &[uc_core::ids::EntryId],
+            _deleted_at_ms: i64,
+        ) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_soft_deleted_entries(
+            &self,
+            _deleted_before_ms: i64,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_entry_source_device(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+        ) -> anyhow::Result<Option<uc_core::ids::DeviceId>> {
+            Ok(None)
+        }
+
+        async fn set_entry_pinned(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+            _pinned: bool,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn list_pinned_entries(
+            &self,
+            _limit: usize,
+            _offset: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn list_catch_up_candidates(
+            &self,
+            _since_ms: i64,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::CatchUpCandidate>> {
+            Ok(Vec::new())
+        }
     }
 
     #[async_trait]
@@ -1118,6 +1444,26 @@ mod tests {
         ) -> anyhow::Result<uc_core::ports::clipboard::ProcessingUpdateOutcome> {
             Ok(uc_core::ports::clipboard::ProcessingUpdateOutcome::NotFound)
         }
+
+        async fn count_inline_representations(&self) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_inline_representation_ids(
+            &self,
+            _after: Option<&uc_core::ids::RepresentationId>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<(uc_core::ids::EventId, uc_core::ids::RepresentationId)>> {
+            Ok(Vec::new())
+        }
+
+        async fn update_inline_data(
+            &self,
+            _representation_id: &uc_core::ids::RepresentationId,
+            _inline_data: &[u8],
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
     }
 
     #[async_trait]
@@ -1185,6 +1531,38 @@ mod tests {
         async fn clear(&self) -> Result<(), EncryptionError> {
             Ok(())
         }
+
+        async fn get_current_key(
+            &self,
+        ) -> Result<
+            (
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            ),
+            uc_core::security::model::EncryptionError,
+        > {
+            Ok((0, self.get_master_key().await?))
+        }
+
+        async fn get_master_key_for_epoch(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+        ) -> Result<uc_core::security::model::MasterKey, uc_core::security::model::EncryptionError>
+        {
+            self.get_master_key().await
+        }
+
+        async fn set_epoch_keys(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+            master_key: uc_core::security::model::MasterKey,
+            _older: Vec<(
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            )>,
+        ) -> Result<(), uc_core::security::model::EncryptionError> {
+            self.set_master_key(master_key).await
+        }
     }
 
     #[async_trait]
@@ -1278,134 +1656,156 @@ mod tests {
     }
 
     #[async_trait]
-    impl NetworkPort for NoopPort {
-        async fn send_clipboard(
-            &self,
-            _peer_id: &str,
-            _encrypted_data: Vec<u8>,
-        ) -> anyhow::Result<()> {
+    impl uc_core::ports::NetworkControlPort for NoopPort {
+        async fn start_network(&self) -> anyhow::Result<()> {
             Ok(())
         }
+    }
 
-        async fn broadcast_clipboard(&self, _encrypted_data: Vec<u8>) -> anyhow::Result<()> {
-            Ok(())
+    #[async_trait]
+    impl SetupStatusPort for NoopPort {
+        async fn get_status(&self) -> anyhow::Result<uc_core::setup::SetupStatus> {
+            Ok(uc_core::setup::SetupStatus::default())
         }
 
-        async fn subscribe_clipboard(
-            &self,
-        ) -> anyhow::Result<tokio::sync::mpsc::Receiver<uc_core::network::ClipboardMessage>>
-        {
-            let (_tx, rx) = mpsc::channel(1);
-            Ok(rx)
+        async fn set_status(&self, _status: &uc_core::setup::SetupStatus) -> anyhow::Result<()> {
+            Ok(())
         }
+    }
 
-        async fn get_discovered_peers(
-            &self,
-        ) -> anyhow::Result<Vec<uc_core::network::DiscoveredPeer>> {
-            Ok(vec![])
+    impl uc_core::ports::SecureStoragePort for NoopPort {
+        fn get(&self, _key: &str) -> Result<Option<Vec<u8>>, uc_core::ports::SecureStorageError> {
+            Ok(None)
         }
 
-        async fn get_connected_peers(
-            &self,
-        ) -> anyhow::Result<Vec<uc_core::network::ConnectedPeer>> {
-            Ok(vec![])
+        fn set(&self, _key: &str, _value: &[u8]) -> Result<(), uc_core::ports::SecureStorageError> {
+            Ok(())
         }
 
-        fn local_peer_id(&self) -> String {
-            "noop".to_string()
+        fn delete(&self, _key: &str) -> Result<(), uc_core::ports::SecureStorageError> {
+            Ok(())
         }
+    }
 
-        async fn announce_device_name(&self, _device_name: String) -> anyhow::Result<()> {
-            Ok(())
+    #[async_trait]
+    impl BlobStorePort for NoopPort {
+        async fn put(&self, _blob_id: &BlobId, _data: &[u8]) -> anyhow::Result<std::path::PathBuf> {
+            Ok(std::path::PathBuf::from("/tmp/noop"))
         }
 
-        async fn open_pairing_session(
-            &self,
-            _peer_id: String,
-            _session_id: String,
-        ) -> anyhow::Result<()> {
-            Ok(())
+        async fn get(&self, _blob_id: &BlobId) -> anyhow::Result<Vec<u8>> {
+            Ok(vec![])
         }
 
-        async fn send_pairing_on_session(
+        async fn put_stream(
             &self,
-            _session_id: String,
-            _message: uc_core::network::PairingMessage,
-        ) -> anyhow::Result<()> {
-            Ok(())
+            blob_id: &uc_core::BlobId,
+            reader: &mut (dyn tokio::io::AsyncRead + Send + Unpin),
+        ) -> anyhow::Result<std::path::PathBuf> {
+            let mut data = Vec::new();
+            tokio::io::AsyncReadExt::read_to_end(reader, &mut data).await?;
+            self.put(blob_id, &data).await
         }
 
-        async fn close_pairing_session(
+        async fn open_read(
             &self,
-            _session_id: String,
-            _reason: Option<String>,
-        ) -> anyhow::Result<()> {
-            Ok(())
+            blob_id: &uc_core::BlobId,
+        ) -> anyhow::Result<uc_core::ports::BlobReader> {
+            Ok(Box::new(std::io::Cursor::new(self.get(blob_id).await?)))
         }
 
-        async fn unpair_device(&self, _peer_id: String) -> anyhow::Result<()> {
-            Ok(())
+        async fn delete(&self, _blob_id: &uc_core::BlobId) -> anyhow::Result<u64> {
+            Ok(0)
         }
 
-        async fn subscribe_events(
-            &self,
-        ) -> anyhow::Result<tokio::sync::mpsc::Receiver<uc_core::network::NetworkEvent>> {
-            let (_tx, rx) = mpsc::channel(1);
-            Ok(rx)
+        async fn list_objects(&self) -> anyhow::Result<Vec<uc_core::ports::StoredBlobObject>> {
+            Ok(Vec::new())
         }
     }
 
     #[async_trait]
-    impl uc_core::ports::NetworkControlPort for NoopPort {
-        async fn start_network(&self) -> anyhow::Result<()> {
+    impl BlobRepositoryPort for NoopPort {
+        async fn insert_blob(&self, _blob: &Blob) -> anyhow::Result<()> {
             Ok(())
         }
-    }
 
-    #[async_trait]
-    impl SetupStatusPort for NoopPort {
-        async fn get_status(&self) -> anyhow::Result<uc_core::setup::SetupStatus> {
-            Ok(uc_core::setup::SetupStatus::default())
+        async fn find_by_hash(&self, _content_hash: &ContentHash) -> anyhow::Result<Option<Blob>> {
+            Ok(None)
         }
 
-        async fn set_status(&self, _status: &uc_core::setup::SetupStatus) -> anyhow::Result<()> {
-            Ok(())
+        async fn count_blobs(&self) -> anyhow::Result<usize> {
+            Ok(0)
         }
-    }
 
-    impl uc_core::ports::SecureStoragePort for NoopPort {
-        fn get(&self, _key: &str) -> Result<Option<Vec<u8>>, uc_core::ports::SecureStorageError> {
-            Ok(None)
+        async fn list_blob_ids(
+            &self,
+            _after: Option<&uc_core::BlobId>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<uc_core::BlobId>> {
+            Ok(vec![])
         }
 
-        fn set(&self, _key: &str, _value: &[u8]) -> Result<(), uc_core::ports::SecureStorageError> {
-            Ok(())
+        async fn list_unreferenced_blobs(
+            &self,
+            _created_before_ms: i64,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<uc_core::Blob>> {
+            Ok(vec![])
         }
 
-        fn delete(&self, _key: &str) -> Result<(), uc_core::ports::SecureStorageError> {
-            Ok(())
+        async fn touch_blob(
+            &self,
+            _blob_id: &uc_core::BlobId,
+            _now_ms: i64,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn delete_unreferenced_blob(
+            &self,
+            _blob_id: &uc_core::BlobId,
+            _created_before_ms: i64,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
         }
     }
 
     #[async_trait]
-    impl BlobStorePort for NoopPort {
-        async fn put(&self, _blob_id: &BlobId, _data: &[u8]) -> anyhow::Result<std::path::PathBuf> {
-            Ok(std::path::PathBuf::from("/tmp/noop"))
+    impl uc_core::ports::PayloadReencryptionPort for NoopPort {
+        async fn count_inline_payloads(&self) -> anyhow::Result<usize> {
+            Ok(0)
         }
 
-        async fn get(&self, _blob_id: &BlobId) -> anyhow::Result<Vec<u8>> {
+        async fn list_inline_payloads(
+            &self,
+            _after: Option<&uc_core::ids::RepresentationId>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<(uc_core::ids::EventId, uc_core::ids::RepresentationId)>> {
             Ok(vec![])
         }
-    }
 
-    #[async_trait]
-    impl BlobRepositoryPort for NoopPort {
-        async fn insert_blob(&self, _blob: &Blob) -> anyhow::Result<()> {
-            Ok(())
+        async fn count_blob_payloads(&self) -> anyhow::Result<usize> {
+            Ok(0)
         }
 
-        async fn find_by_hash(&self, _content_hash: &ContentHash) -> anyhow::Result<Option<Blob>> {
-            Ok(None)
+        async fn list_blob_payloads(
+            &self,
+            _after: Option<&BlobId>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<BlobId>> {
+            Ok(vec![])
+        }
+
+        async fn reencrypt_inline(
+            &self,
+            _event_id: &uc_core::ids::EventId,
+            _representation_id: &uc_core::ids::RepresentationId,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn reencrypt_blob(&self, _blob_id: &BlobId) -> anyhow::Result<bool> {
+            Ok(false)
         }
     }
 
@@ -1420,6 +1820,25 @@ mod tests {
         }
     }
 
+    #[async_trait]
+    impl OutboundSyncQueuePort for NoopPort {
+        async fn enqueue(&self, _message: QueuedClipboardMessage) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn list_pending(&self) -> anyhow::Result<Vec<QueuedClipboardMessage>> {
+            Ok(vec![])
+        }
+
+        async fn remove(&self, _message_ids: &[String]) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn retain_latest(&self, _keep: usize) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+    }
+
     #[async_trait]
     impl ThumbnailRepositoryPort for NoopPort {
         async fn get_by_representation_id(
@@ -1435,6 +1854,10 @@ mod tests {
         ) -> anyhow::Result<()> {
             Ok(())
         }
+
+        async fn delete_orphaned_thumbnails(&self) -> anyhow::Result<usize> {
+            Ok(0)
+        }
     }
 
     #[async_trait]
@@ -1458,49 +1881,6 @@ mod tests {
         }
     }
 
-    #[async_trait]
-    impl PairedDeviceRepositoryPort for NoopPort {
-        async fn get_by_peer_id(
-            &self,
-            _peer_id: &PeerId,
-        ) -> Result<Option<uc_core::network::PairedDevice>, PairedDeviceRepositoryError> {
-            Ok(None)
-        }
-
-        async fn list_all(
-            &self,
-        ) -> Result<Vec<uc_core::network::PairedDevice>, PairedDeviceRepositoryError> {
-            Ok(Vec::new())
-        }
-
-        async fn upsert(
-            &self,
-            _device: uc_core::network::PairedDevice,
-        ) -> Result<(), PairedDeviceRepositoryError> {
-            Ok(())
-        }
-
-        async fn set_state(
-            &self,
-            _peer_id: &PeerId,
-            _state: uc_core::network::PairingState,
-        ) -> Result<(), PairedDeviceRepositoryError> {
-            Ok(())
-        }
-
-        async fn update_last_seen(
-            &self,
-            _peer_id: &PeerId,
-            _last_seen_at: chrono::DateTime<chrono::Utc>,
-        ) -> Result<(), PairedDeviceRepositoryError> {
-            Ok(())
-        }
-
-        async fn delete(&self, _peer_id: &PeerId) -> Result<(), PairedDeviceRepositoryError> {
-            Ok(())
-        }
-    }
-
     #[async_trait]
     impl UiPort for NoopPort {
         async fn open_settings(&self) -> anyhow::Result<()> {
@@ -1534,6 +1914,40 @@ mod tests {
         }
     }
 
+    #[async_trait]
+    impl ClipboardPayloadResolverPort for NoopPort {
+        async fn resolve(
+            &self,
+            _representation: &uc_core::clipboard::PersistedClipboardRepresentation,
+        ) -> anyhow::Result<ResolvedClipboardPayload> {
+            Err(anyhow::anyhow!("noop payload resolver"))
+        }
+    }
+
+    #[async_trait]
+    impl RemotePayloadRepositoryPort for NoopPort {
+        async fn insert_remote_payload(
+            &self,
+            _remote: &uc_core::clipboard::RemotePayloadRef,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn get_by_representation_id(
+            &self,
+            _representation_id: &uc_core::ids::RepresentationId,
+        ) -> anyhow::Result<Option<uc_core::clipboard::RemotePayloadRef>> {
+            Ok(None)
+        }
+
+        async fn delete_remote_payload(
+            &self,
+            _representation_id: &uc_core::ids::RepresentationId,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
     #[tokio::test]
     async fn runtime_consumes_origin() {
         let save_calls = Arc::new(AtomicUsize::new(0));
@@ -1567,6 +1981,7 @@ mod tests {
             representation_policy: Arc::new(MockRepresentationPolicy {
                 select_calls: select_calls.clone(),
             }),
+            sync_filter_policy: Arc::new(uc_core::clipboard::SyncFilterPolicyV1::new()),
             representation_cache: Arc::new(MockRepresentationCache {
                 put_calls: cache_put_calls.clone(),
             }),
@@ -1574,18 +1989,23 @@ mod tests {
                 enqueue_calls: enqueue_calls.clone(),
             }),
             worker_tx,
+            payload_resolver: Arc::new(NoopPort),
+            remote_payload_repo: Arc::new(NoopPort),
             encryption: Arc::new(NoopPort),
             encryption_session: Arc::new(NoopPort),
             encryption_state: Arc::new(NoopPort),
             key_scope: Arc::new(NoopPort),
             secure_storage: Arc::new(NoopPort),
             key_material: Arc::new(NoopPort),
+            payload_reencryption: Arc::new(NoopPort),
             watcher_control: Arc::new(NoopPort),
             device_repo: Arc::new(NoopPort),
             device_identity: Arc::new(MockDeviceIdentity),
-            paired_device_repo: Arc::new(NoopPort),
-            network: Arc::new(NoopPort),
+            paired_device_repo: Arc::new(InMemoryPairedDeviceRepository::new()),
+            active_space: Arc::new(uc_infra::space::InMemoryActiveSpace::default()),
+            network: Arc::new(TestNetwork::new()),
             network_control: Arc::new(NoopPort),
+            outbound_sync_queue: Arc::new(NoopPort),
             setup_status: Arc::new(NoopPort),
             blob_store: Arc::new(NoopPort),
             blob_repository: Arc::new(NoopPort),
@@ -1598,6 +2018,7 @@ mod tests {
             clock: Arc::new(NoopPort),
             hash: Arc::new(NoopPort),
             clipboard_change_origin: origin_port,
+            search_index: Arc::new(uc_infra::clipboard::InMemoryClipboardSearchIndex::new()),
         };
 
         let runtime = AppRuntime::new(deps);
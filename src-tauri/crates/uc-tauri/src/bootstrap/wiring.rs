@@ -42,27 +42,39 @@ use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
 use crate::events::{
-    P2PPairingVerificationEvent, P2PPeerConnectionEvent, P2PPeerDiscoveryEvent,
-    P2PPeerNameUpdatedEvent,
+    ClipboardEvent, EncryptionEvent, P2PPairingRevokedEvent, P2PPairingVerificationEvent,
+    P2PPeerConnectionEvent, P2PPeerDiscoveryEvent, P2PPeerHeartbeatEvent,
+    P2PPeerIdentityMismatchEvent, P2PPeerNameUpdatedEvent, P2PTransferFailedEvent,
+    P2PTransferProgressEvent,
 };
 use uc_app::app_paths::AppPaths;
+use uc_app::usecases::clipboard::{
+    CatchUpClipboardHistory, InboundClipboardOutcome, SyncInboundClipboardUseCase,
+    SyncOutboundClipboardUseCase,
+};
+use uc_app::usecases::internal::capture_clipboard::CaptureClipboardUseCase;
 use uc_app::usecases::space_access::{
     HmacProofAdapter, SpaceAccessCompletedEvent, SpaceAccessContext, SpaceAccessEventPort,
     SpaceAccessJoinerOffer, SpaceAccessNetworkAdapter, SpaceAccessOrchestrator,
     SpaceAccessPersistenceAdapter,
 };
-use uc_app::usecases::{PairingConfig, PairingOrchestrator, ResolveConnectionPolicy};
+use uc_app::usecases::{
+    ApplyKeySlotUpdate, ApplyPeerRevocation, ApplyRetentionPolicy, CollectBlobGarbage,
+    PairingConfig, PairingOrchestrator, PendingKeySlotUpdates, RecordPeerAddress,
+    RecordPeerCapabilities, RecordPeerHeartbeat, RedialKnownPeers, ReencryptHistory,
+    ReencryptionProgress, ResendKeySlot, ResolveConnectionPolicy, UnpairDevice,
+};
 use uc_app::AppDeps;
-use uc_core::clipboard::SelectRepresentationPolicyV1;
+use uc_core::clipboard::{SelectRepresentationPolicyV1, SyncFilterPolicyV1};
 use uc_core::config::AppConfig;
 use uc_core::ids::RepresentationId;
 use uc_core::network::pairing_state_machine::{PairingAction, PairingRole};
-use uc_core::network::{NetworkEvent, PairingMessage};
+use uc_core::network::{ClipboardMessage, NetworkEvent, PairingMessage, ProtocolDirection};
 use uc_core::ports::clipboard::{
-    ClipboardChangeOriginPort, ClipboardRepresentationNormalizerPort, RepresentationCachePort,
-    SpoolQueuePort, SpoolRequest,
+    ClipboardChangeOriginPort, ClipboardRepresentationNormalizerPort, RemotePayloadRepositoryPort,
+    RepresentationCachePort, SpoolQueuePort, SpoolRequest,
 };
-use uc_core::ports::space::ProofPort;
+use uc_core::ports::space::{ActiveSpacePort, ProofPort};
 use uc_core::ports::*;
 use uc_core::security::model::{KeySlot, KeySlotFile};
 use uc_core::security::space_access::event::SpaceAccessEvent;
@@ -71,9 +83,9 @@ use uc_core::settings::model::Settings;
 use uc_core::setup::SetupState;
 use uc_infra::blob::BlobWriter;
 use uc_infra::clipboard::{
-    BackgroundBlobWorker, ClipboardRepresentationNormalizer, InMemoryClipboardChangeOrigin,
-    InfraThumbnailGenerator, MpscSpoolQueue, RepresentationCache, SpoolJanitor, SpoolManager,
-    SpoolScanner, SpoolerTask,
+    BackgroundBlobWorker, ClipboardPayloadResolver, ClipboardRepresentationNormalizer,
+    InMemoryClipboardChangeOrigin, InMemoryClipboardSearchIndex, InfraThumbnailGenerator,
+    MpscSpoolQueue, RepresentationCache, SpoolJanitor, SpoolManager, SpoolScanner, SpoolerTask,
 };
 use uc_infra::config::ClipboardStorageConfig;
 use uc_infra::db::executor::DieselSqliteExecutor;
@@ -88,16 +100,18 @@ use uc_infra::db::pool::{init_db_pool, DbPool};
 use uc_infra::db::repositories::{
     DieselBlobRepository, DieselClipboardEntryRepository, DieselClipboardEventRepository,
     DieselClipboardRepresentationRepository, DieselClipboardSelectionRepository,
-    DieselDeviceRepository, DieselPairedDeviceRepository, DieselThumbnailRepository,
+    DieselDeviceRepository, DieselOutboundSyncQueueRepository, DieselPairedDeviceRepository,
+    DieselRemotePayloadRepository, DieselThumbnailRepository,
 };
 use uc_infra::device::LocalDeviceIdentity;
 use uc_infra::fs::key_slot_store::{JsonKeySlotStore, KeySlotStore};
 use uc_infra::security::{
     Blake3Hasher, DecryptingClipboardRepresentationRepository, DefaultKeyMaterialService,
     EncryptedBlobStore, EncryptingClipboardEventWriter, EncryptionRepository,
-    FileEncryptionStateRepository,
+    FileEncryptionStateRepository, PayloadReencryptor, SearchIndexClosingSession,
 };
 use uc_infra::settings::repository::FileSettingsRepository;
+use uc_infra::space::InMemoryActiveSpace;
 use uc_infra::{FileSetupStatusRepository, SystemClock, Timer};
 
 use uc_platform::adapters::{
@@ -199,6 +213,15 @@ impl SetupEventPort for TauriSetupEventPort {
 }
 
 const SPOOL_JANITOR_INTERVAL_SECS: u64 = 60 * 60;
+const RETENTION_INTERVAL_SECS: u64 = 5 * 60;
+const BLOB_GC_INTERVAL_SECS: u64 = 6 * 60 * 60;
+/// How often the interval sync queue is checked; the configured interval is
+/// enforced by the use case itself.
+const OUTBOUND_SYNC_FLUSH_TICK_SECS: u64 = 5;
+const KEY_ROTATION_POLL_SECS: u64 = 15;
+/// Redial cadence for manual peer addresses and known `Trusted` peers when
+/// mDNS cannot see them.
+const PEER_REDIAL_INTERVAL_SECS: u64 = 30;
 
 /// Create SQLite database connection pool
 /// 创建 SQLite 数据库连接池
@@ -252,6 +275,7 @@ struct InfraLayer {
     clipboard_event_repo: Arc<dyn ClipboardEventWriterPort>,
     representation_repo: Arc<dyn ClipboardRepresentationRepositoryPort>,
     selection_repo: Arc<dyn ClipboardSelectionRepositoryPort>,
+    remote_payload_repo: Arc<dyn RemotePayloadRepositoryPort>,
 
     // Device repository / 设备仓库
     device_repo: Arc<dyn DeviceRepositoryPort>,
@@ -259,6 +283,12 @@ struct InfraLayer {
     // Pairing repository / 配对仓库
     paired_device_repo: Arc<dyn PairedDeviceRepositoryPort>,
 
+    // Active space / 活动空间
+    active_space: Arc<dyn ActiveSpacePort>,
+
+    // Outbound sync queue / 出站同步队列
+    outbound_sync_queue: Arc<dyn OutboundSyncQueuePort>,
+
     // Blob storage / Blob 存储
     blob_repository: Arc<dyn BlobRepositoryPort>,
     thumbnail_repo: Arc<dyn ThumbnailRepositoryPort>,
@@ -318,6 +348,9 @@ struct PlatformLayer {
     // Blob store / Blob 存储（加密装饰后）
     blob_store: Arc<dyn BlobStorePort>,
 
+    // Raw blob store / 未加密装饰的 Blob 存储（供密钥轮换重新加密）
+    raw_blob_store: Arc<dyn BlobStorePort>,
+
     // Encryption session / 加密会话（占位符）
     encryption_session: Arc<dyn EncryptionSessionPort>,
 
@@ -362,6 +395,10 @@ fn create_infra_layer(
     // 创建数据库执行器并包装在 Arc 中以供克隆
     let db_executor = Arc::new(DieselSqliteExecutor::new(db_pool));
 
+    // Active space shared by repositories and key scope; restored from settings at startup
+    // 仓库与密钥范围共享的活动空间；启动时从设置恢复
+    let active_space: Arc<dyn ActiveSpacePort> = Arc::new(InMemoryActiveSpace::default());
+
     // Create mappers (zero-sized structs, no new() needed)
     // 创建映射器（零大小类型，无需 new()）
     let entry_row_mapper = ClipboardEntryRowMapper;
@@ -378,7 +415,8 @@ fn create_infra_layer(
         entry_row_mapper,
         selection_row_mapper,
         ClipboardEntryRowMapper, // ZST - can instantiate again
-    );
+    )
+    .with_active_space(active_space.clone());
     let clipboard_entry_repo: Arc<dyn ClipboardEntryRepositoryPort> = Arc::new(entry_repo);
 
     // Create clipboard event repository
@@ -395,6 +433,11 @@ fn create_infra_layer(
     let rep_repo = DieselClipboardRepresentationRepository::new(Arc::clone(&db_executor));
     let representation_repo: Arc<dyn ClipboardRepresentationRepositoryPort> = Arc::new(rep_repo);
 
+    // Create remote payload repository for metadata-first sync
+    // 创建延迟同步使用的远端数据仓库
+    let remote_payload_repo: Arc<dyn RemotePayloadRepositoryPort> =
+        Arc::new(DieselRemotePayloadRepository::new(Arc::clone(&db_executor)));
+
     // Create device repository
     // 创建设备仓库
     let dev_repo = DieselDeviceRepository::new(Arc::clone(&db_executor), device_row_mapper);
@@ -403,9 +446,16 @@ fn create_infra_layer(
     // Create paired device repository
     // 创建配对设备仓库
     let paired_repo =
-        DieselPairedDeviceRepository::new(Arc::clone(&db_executor), paired_device_row_mapper);
+        DieselPairedDeviceRepository::new(Arc::clone(&db_executor), paired_device_row_mapper)
+            .with_active_space(active_space.clone());
     let paired_device_repo: Arc<dyn PairedDeviceRepositoryPort> = Arc::new(paired_repo);
 
+    // Create outbound sync queue for interval sync
+    // 创建间隔同步使用的出站队列
+    let outbound_sync_queue: Arc<dyn OutboundSyncQueuePort> = Arc::new(
+        DieselOutboundSyncQueueRepository::new(Arc::clone(&db_executor)),
+    );
+
     // Create blob repository
     // 创建 blob 仓库
     let blob_repo = DieselBlobRepository::new(
@@ -433,7 +483,8 @@ fn create_infra_layer(
     // Create key material service
     // 创建密钥材料服务
     let key_material_service =
-        DefaultKeyMaterialService::new(secure_storage_for_key_material, keyslot_store);
+        DefaultKeyMaterialService::new(secure_storage_for_key_material, keyslot_store)
+            .with_space_keyslot_root(vault_path.clone());
     let key_material: Arc<dyn KeyMaterialPort> = Arc::new(key_material_service);
 
     // Create encryption service
@@ -469,8 +520,11 @@ fn create_infra_layer(
         clipboard_event_repo,
         representation_repo,
         selection_repo,
+        remote_payload_repo,
         device_repo,
         paired_device_repo,
+        active_space,
+        outbound_sync_queue,
         blob_repository,
         thumbnail_repo,
         thumbnail_generator,
@@ -499,12 +553,15 @@ fn create_infra_layer(
 ///
 /// * `secure_storage` - Secure storage instance / 安全存储实例
 /// * `config_dir` - Configuration directory for device identity storage / 用于存储设备身份的配置目录
+/// * `cache_dir` - Cache directory holding the transfer spool / 存放传输暂存文件的缓存目录
 /// * `platform_cmd_tx` - Command sender for platform runtime / 平台运行时命令发送器
 /// * `encryption` - Encryption service for blob store decorator / Blob 存储加密服务
 /// * `blob_repository` - Blob repository for BlobWriter / BlobWriter 依赖的仓库
+/// * `active_space` - Active space the key scope follows / 密钥范围跟随的活动空间
 /// * `clock` - Clock service for BlobWriter timestamps / BlobWriter 时间戳服务
 /// * `storage_config` - Clipboard storage configuration / 剪贴板存储配置
 /// * `identity_store` - Identity store for libp2p keypair persistence / libp2p 身份持久化存储
+/// * `settings` - Settings for the libp2p listen port / libp2p 监听端口所需的设置
 ///
 /// # Note / 注意
 ///
@@ -517,13 +574,16 @@ fn create_infra_layer(
 fn create_platform_layer(
     secure_storage: Arc<dyn SecureStoragePort>,
     config_dir: &PathBuf,
+    cache_dir: &PathBuf,
     platform_cmd_tx: PlatformCommandSender,
     encryption: Arc<dyn EncryptionPort>,
     blob_repository: Arc<dyn BlobRepositoryPort>,
     paired_device_repo: Arc<dyn PairedDeviceRepositoryPort>,
+    active_space: Arc<dyn ActiveSpacePort>,
     clock: Arc<dyn ClockPort>,
     storage_config: Arc<ClipboardStorageConfig>,
     identity_store: Arc<dyn IdentityStorePort>,
+    settings: Arc<dyn SettingsPort>,
 ) -> WiringResult<PlatformLayer> {
     // Create system clipboard implementation (platform-specific)
     // 创建系统剪贴板实现（平台特定）
@@ -555,15 +615,23 @@ fn create_platform_layer(
     let ui: Arc<dyn UiPort> = Arc::new(PlaceholderUiPort);
     let autostart: Arc<dyn AutostartPort> = Arc::new(PlaceholderAutostartPort);
     let policy_resolver = Arc::new(ResolveConnectionPolicy::new(paired_device_repo.clone()));
+    let encryption_session: Arc<dyn EncryptionSessionPort> =
+        Arc::new(InMemoryEncryptionSessionPort::new());
     let libp2p_network = Arc::new(
-        Libp2pNetworkAdapter::new(identity_store, policy_resolver).map_err(|e| {
+        Libp2pNetworkAdapter::new(
+            identity_store,
+            device_identity.current_device_id().to_string(),
+            policy_resolver,
+            settings,
+            encryption_session.clone(),
+            cache_dir.join("transfer"),
+        )
+        .map_err(|e| {
             WiringError::NetworkInit(format!("Failed to initialize libp2p identity: {e}"))
         })?,
     );
     info!(peer_id = %libp2p_network.local_peer_id(), "Loaded libp2p identity");
     let network: Arc<dyn NetworkPort> = libp2p_network.clone();
-    let encryption_session: Arc<dyn EncryptionSessionPort> =
-        Arc::new(InMemoryEncryptionSessionPort::new());
 
     // Wrap blob_store with encryption decorator
     // 用加密装饰器包装 blob_store
@@ -589,7 +657,7 @@ fn create_platform_layer(
     // Create key scope
     // 创建密钥范围
     let key_scope: Arc<dyn uc_core::ports::security::key_scope::KeyScopePort> =
-        Arc::new(uc_platform::key_scope::DefaultKeyScope::new());
+        Arc::new(uc_platform::key_scope::DefaultKeyScope::new().with_active_space(active_space));
 
     Ok(PlatformLayer {
         clipboard,
@@ -603,6 +671,7 @@ fn create_platform_layer(
         representation_normalizer,
         blob_writer,
         blob_store: encrypted_blob_store,
+        raw_blob_store: blob_store,
         encryption_session,
         watcher_control,
         key_scope,
@@ -825,13 +894,16 @@ pub fn wire_dependencies_with_identity_store(
     let platform = create_platform_layer(
         secure_storage,
         &vault_path,
+        &paths.cache_dir,
         platform_cmd_tx,
         infra.encryption.clone(),
         infra.blob_repository.clone(),
         infra.paired_device_repo.clone(),
+        infra.active_space.clone(),
         infra.clock.clone(),
         storage_config.clone(),
         identity_store,
+        infra.settings_repo.clone(),
     )?;
 
     // Step 3.5: Wrap ports with encryption decorators
@@ -853,6 +925,16 @@ pub fn wire_dependencies_with_identity_store(
             platform.encryption_session.clone(),
         ));
 
+    // Re-encrypts stored payloads after a key rotation; works on the raw storage
+    // 密钥轮换后重新加密已存储数据；直接操作未装饰的存储
+    let payload_reencryption: Arc<dyn PayloadReencryptionPort> = Arc::new(PayloadReencryptor::new(
+        infra.representation_repo.clone(),
+        infra.blob_repository.clone(),
+        platform.raw_blob_store.clone(),
+        infra.encryption.clone(),
+        platform.encryption_session.clone(),
+    ));
+
     // Step 3.6: Create background processing components
     // 步骤 3.6：创建后台处理组件
 
@@ -875,9 +957,34 @@ pub fn wire_dependencies_with_identity_store(
     let spool_queue: Arc<dyn SpoolQueuePort> = Arc::new(MpscSpoolQueue::new(spool_tx));
     let (worker_tx, worker_rx) = mpsc::channel::<RepresentationId>(100);
 
+    // Resolve payloads for restore/detail; remote payloads are pulled from peers on demand
+    // 为恢复与详情解析数据；远端数据按需从对端拉取
+    let payload_resolver: Arc<dyn ClipboardPayloadResolverPort> = Arc::new(
+        ClipboardPayloadResolver::new(
+            representation_cache.clone(),
+            spool_manager.clone(),
+            worker_tx.clone(),
+        )
+        .with_remote_fetch(
+            platform.network.clone(),
+            infra.encryption.clone(),
+            platform.encryption_session.clone(),
+            infra.remote_payload_repo.clone(),
+            infra.representation_repo.clone(),
+        ),
+    );
+
     let clipboard_change_origin: Arc<dyn ClipboardChangeOriginPort> =
         Arc::new(InMemoryClipboardChangeOrigin::new());
 
+    // Clearing the session (lock, space switch) also drops the plaintext search index
+    // 清除加密会话（锁定、切换空间）时一并关闭明文检索索引
+    let search_index: Arc<dyn ClipboardSearchIndexPort> =
+        Arc::new(InMemoryClipboardSearchIndex::new());
+    let encryption_session: Arc<dyn EncryptionSessionPort> = Arc::new(
+        SearchIndexClosingSession::new(platform.encryption_session, search_index.clone()),
+    );
+
     // Step 4: Construct AppDeps with all dependencies
     // 步骤 4：使用所有依赖构造 AppDeps
     let deps = AppDeps {
@@ -890,18 +997,23 @@ pub fn wire_dependencies_with_identity_store(
         representation_normalizer: platform.representation_normalizer,
         selection_repo: infra.selection_repo,
         representation_policy: Arc::new(SelectRepresentationPolicyV1::new()),
+        sync_filter_policy: Arc::new(SyncFilterPolicyV1::new()),
         representation_cache: representation_cache_port,
         spool_queue,
         clipboard_change_origin,
+        search_index,
         worker_tx,
+        payload_resolver,
+        remote_payload_repo: infra.remote_payload_repo,
 
         // Security dependencies / 安全依赖
         encryption: infra.encryption,
-        encryption_session: platform.encryption_session,
+        encryption_session,
         encryption_state: infra.encryption_state,
         key_scope: platform.key_scope,
         secure_storage: platform.secure_storage,
         key_material: infra.key_material,
+        payload_reencryption,
         watcher_control: platform.watcher_control,
 
         // Device dependencies / 设备依赖
@@ -911,9 +1023,13 @@ pub fn wire_dependencies_with_identity_store(
         // Pairing dependencies / 配对依赖
         paired_device_repo: infra.paired_device_repo,
 
+        // Space dependencies / 空间依赖
+        active_space: infra.active_space,
+
         // Network dependencies / 网络依赖
         network: platform.network,
         network_control: platform.libp2p_network.clone(),
+        outbound_sync_queue: infra.outbound_sync_queue,
 
         // Setup status dependencies / 设置状态依赖
         setup_status: infra.setup_status,
@@ -963,6 +1079,7 @@ pub fn start_background_tasks<R: Runtime>(
     pairing_action_rx: mpsc::Receiver<PairingAction>,
     space_access_orchestrator: Arc<SpaceAccessOrchestrator>,
     key_slot_store: Arc<dyn KeySlotStore>,
+    pending_keyslot_updates: Arc<PendingKeySlotUpdates>,
 ) {
     let BackgroundRuntimeDeps {
         libp2p_network: _,
@@ -989,6 +1106,130 @@ pub fn start_background_tasks<R: Runtime>(
     let thumbnail_repo = deps.thumbnail_repo.clone();
     let thumbnail_generator = deps.thumbnail_generator.clone();
     let pairing_network = deps.network.clone();
+    let clipboard_sync_app_handle = app_handle.clone();
+    let retention_app_handle = app_handle.clone();
+    let retention_usecase = ApplyRetentionPolicy::new(
+        deps.clipboard_entry_repo.clone(),
+        deps.selection_repo.clone(),
+        deps.clipboard_event_repo.clone(),
+        deps.settings.clone(),
+        deps.clock.clone(),
+    );
+    let retention_search_index = deps.search_index.clone();
+    let blob_gc_usecase = CollectBlobGarbage::new(
+        deps.blob_repository.clone(),
+        deps.blob_store.clone(),
+        deps.thumbnail_repo.clone(),
+        deps.clock.clone(),
+    );
+    let outbound_sync_usecase = SyncOutboundClipboardUseCase::new(
+        deps.representation_policy.clone(),
+        deps.sync_filter_policy.clone(),
+        deps.encryption.clone(),
+        deps.encryption_session.clone(),
+        deps.paired_device_repo.clone(),
+        deps.network.clone(),
+        deps.device_identity.clone(),
+        deps.settings.clone(),
+        deps.outbound_sync_queue.clone(),
+        deps.clock.clone(),
+    );
+    let key_rotation_app_handle = app_handle.clone();
+    let key_rotation_session = deps.encryption_session.clone();
+    let reencrypt_usecase = ReencryptHistory::new(
+        deps.payload_reencryption.clone(),
+        deps.encryption_session.clone(),
+        deps.key_material.clone(),
+        deps.key_scope.clone(),
+    );
+    let redial_usecase = RedialKnownPeers::new(
+        deps.network.clone(),
+        deps.paired_device_repo.clone(),
+        deps.settings.clone(),
+    );
+    let record_peer_address_usecase =
+        Arc::new(RecordPeerAddress::new(deps.paired_device_repo.clone()));
+    let record_peer_heartbeat_usecase =
+        Arc::new(RecordPeerHeartbeat::new(deps.paired_device_repo.clone()));
+    let record_peer_capabilities_usecase =
+        Arc::new(RecordPeerCapabilities::new(deps.paired_device_repo.clone()));
+    let revocation_usecases = RevocationUseCases {
+        unpair: Arc::new(UnpairDevice::new(
+            deps.network.clone(),
+            deps.paired_device_repo.clone(),
+        )),
+        apply: Arc::new(ApplyPeerRevocation::new(
+            deps.network.clone(),
+            deps.paired_device_repo.clone(),
+        )),
+    };
+    let catch_up_usecase = Arc::new(CatchUpClipboardHistory::new(
+        deps.clipboard_entry_repo.clone(),
+        deps.selection_repo.clone(),
+        deps.representation_repo.clone(),
+        deps.blob_store.clone(),
+        deps.payload_resolver.clone(),
+        deps.paired_device_repo.clone(),
+        deps.network.clone(),
+        deps.device_identity.clone(),
+        deps.settings.clone(),
+        deps.clock.clone(),
+        SyncOutboundClipboardUseCase::new(
+            deps.representation_policy.clone(),
+            deps.sync_filter_policy.clone(),
+            deps.encryption.clone(),
+            deps.encryption_session.clone(),
+            deps.paired_device_repo.clone(),
+            deps.network.clone(),
+            deps.device_identity.clone(),
+            deps.settings.clone(),
+            deps.outbound_sync_queue.clone(),
+            deps.clock.clone(),
+        ),
+    ));
+    let clipboard_sync_network = deps.network.clone();
+    let keyslot_usecases = KeySlotUseCases {
+        apply: Arc::new(ApplyKeySlotUpdate::new(
+            deps.encryption.clone(),
+            deps.encryption_session.clone(),
+            deps.key_material.clone(),
+            deps.key_scope.clone(),
+            deps.paired_device_repo.clone(),
+            pending_keyslot_updates,
+        )),
+        resend: Arc::new(ResendKeySlot::new(
+            deps.encryption.clone(),
+            deps.encryption_session.clone(),
+            deps.key_material.clone(),
+            deps.key_scope.clone(),
+            deps.paired_device_repo.clone(),
+            deps.network.clone(),
+            deps.device_identity.clone(),
+        )),
+    };
+    let clipboard_sync_usecase = SyncInboundClipboardUseCase::new(
+        deps.encryption.clone(),
+        deps.encryption_session.clone(),
+        deps.device_identity.clone(),
+        deps.settings.clone(),
+        deps.system_clipboard.clone(),
+        deps.clipboard_change_origin.clone(),
+        deps.network.clone(),
+        deps.remote_payload_repo.clone(),
+        deps.paired_device_repo.clone(),
+        CaptureClipboardUseCase::new(
+            deps.clipboard_entry_repo.clone(),
+            deps.clipboard_event_repo.clone(),
+            deps.representation_policy.clone(),
+            deps.representation_normalizer.clone(),
+            deps.device_identity.clone(),
+            deps.representation_cache.clone(),
+            deps.spool_queue.clone(),
+            deps.search_index.clone(),
+            deps.sync_filter_policy.clone(),
+            deps.settings.clone(),
+        ),
+    );
     let space_access_runtime_ports = RuntimeSpaceAccessPorts {
         transport: Arc::new(tokio::sync::Mutex::new(SpaceAccessNetworkAdapter::new(
             pairing_network.clone(),
@@ -1065,6 +1306,66 @@ pub fn start_background_tasks<R: Runtime>(
         });
     });
 
+    async_runtime::spawn(async move {
+        run_retention_loop(
+            retention_usecase,
+            retention_search_index,
+            retention_app_handle,
+        )
+        .await;
+    });
+
+    async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(BLOB_GC_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            if let Err(err) = blob_gc_usecase.execute().await {
+                warn!(error = %err, "Blob garbage collection failed");
+            }
+        }
+    });
+
+    async_runtime::spawn(async move {
+        run_outbound_sync_flush_loop(outbound_sync_usecase, Box::new(Timer::new())).await;
+    });
+
+    async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(PEER_REDIAL_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            if let Err(err) = redial_usecase.execute().await {
+                warn!(error = %err, "Redialing known peers failed");
+            }
+        }
+    });
+
+    async_runtime::spawn(async move {
+        run_key_rotation_loop(
+            reencrypt_usecase,
+            key_rotation_session,
+            key_rotation_app_handle,
+        )
+        .await;
+    });
+
+    async_runtime::spawn(async move {
+        let clipboard_rx = match clipboard_sync_network.subscribe_clipboard().await {
+            Ok(rx) => rx,
+            Err(err) => {
+                warn!(error = %err, "Failed to subscribe to inbound clipboard messages");
+                return;
+            }
+        };
+
+        run_clipboard_sync_loop(
+            clipboard_rx,
+            clipboard_sync_usecase,
+            clipboard_sync_app_handle,
+        )
+        .await;
+        warn!("Clipboard sync loop stopped");
+    });
+
     async_runtime::spawn(async move {
         let completion_rx =
             match SpaceAccessEventPort::subscribe(space_access_orchestrator.as_ref()).await {
@@ -1114,6 +1415,12 @@ pub fn start_background_tasks<R: Runtime>(
             pairing_network.clone(),
             pairing_space_access_orchestrator,
             space_access_runtime_ports,
+            Some(keyslot_usecases),
+            Some(record_peer_address_usecase),
+            Some(record_peer_heartbeat_usecase),
+            Some(record_peer_capabilities_usecase),
+            Some(revocation_usecases),
+            Some(catch_up_usecase),
         )
         .await;
         warn!("Pairing event loop stopped");
@@ -1367,6 +1674,152 @@ impl uc_core::ports::TimerPort for NoopSpaceAccessTimer {
     async fn stop(&mut self, _session_id: &uc_core::ids::SessionId) -> anyhow::Result<()> {
         Ok(())
     }
+
+    async fn sleep(&mut self, _duration: std::time::Duration) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// Flush the interval sync queue on every tick of `timer`.
+///
+/// 按计时器节拍发送间隔同步队列中的消息。
+async fn run_outbound_sync_flush_loop(
+    usecase: SyncOutboundClipboardUseCase,
+    mut timer: Box<dyn TimerPort>,
+) {
+    loop {
+        if let Err(err) = usecase.flush_pending().await {
+            warn!(error = %err, "Outbound clipboard sync flush failed");
+        }
+        if let Err(err) = timer
+            .sleep(Duration::from_secs(OUTBOUND_SYNC_FLUSH_TICK_SECS))
+            .await
+        {
+            warn!(error = %err, "Outbound clipboard sync timer failed");
+            return;
+        }
+    }
+}
+
+async fn run_clipboard_sync_loop<R: Runtime>(
+    mut clipboard_rx: mpsc::Receiver<ClipboardMessage>,
+    usecase: SyncInboundClipboardUseCase,
+    app_handle: Option<AppHandle<R>>,
+) {
+    while let Some(message) = clipboard_rx.recv().await {
+        let message_id = message.id.clone();
+        match usecase.execute(message).await {
+            Ok(
+                InboundClipboardOutcome::Persisted(event_id)
+                | InboundClipboardOutcome::AppliedToClipboard(event_id),
+            ) => {
+                if let Some(app) = app_handle.as_ref() {
+                    let event = ClipboardEvent::NewContent {
+                        entry_id: event_id.to_string(),
+                        preview: "New clipboard content".to_string(),
+                    };
+                    if let Err(err) = app.emit("clipboard://event", event) {
+                        warn!(error = %err, "Failed to emit clipboard event to frontend");
+                    }
+                }
+            }
+            Ok(_) => {}
+            Err(err) => {
+                warn!(
+                    message_id = %message_id,
+                    error = %err,
+                    "Failed to apply inbound clipboard message"
+                );
+            }
+        }
+    }
+}
+
+async fn run_retention_loop<R: Runtime>(
+    usecase: ApplyRetentionPolicy,
+    search_index: Arc<dyn ClipboardSearchIndexPort>,
+    app_handle: Option<AppHandle<R>>,
+) {
+    let mut interval = tokio::time::interval(Duration::from_secs(RETENTION_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        let report = match usecase.execute().await {
+            Ok(report) => report,
+            Err(err) => {
+                warn!(error = %err, "Retention policy run failed");
+                continue;
+            }
+        };
+        if report.removed_count() == 0 {
+            continue;
+        }
+        for entry_id in &report.removed_entry_ids {
+            if let Err(err) = search_index.remove_entry(entry_id).await {
+                warn!(error = %err, entry_id = %entry_id, "Failed to remove expired entry from search index");
+            }
+        }
+
+        if let Some(app) = app_handle.as_ref() {
+            let event = ClipboardEvent::Expired {
+                count: report.removed_count(),
+                entry_ids: report
+                    .removed_entry_ids
+                    .iter()
+                    .map(|id| id.to_string())
+                    .collect(),
+            };
+            if let Err(err) = app.emit("clipboard://event", event) {
+                warn!(error = %err, "Failed to emit clipboard expired event to frontend");
+            }
+        }
+    }
+}
+
+/// Resume history re-encryption whenever the keyslot still holds a retired key.
+///
+/// Runs after a local rotation, after a rotation received from a peer, and
+/// after a restart that interrupted a previous pass.
+async fn run_key_rotation_loop<R: Runtime>(
+    usecase: ReencryptHistory,
+    encryption_session: Arc<dyn EncryptionSessionPort>,
+    app_handle: Option<AppHandle<R>>,
+) {
+    let emit = |event: EncryptionEvent| {
+        if let Some(app) = app_handle.as_ref() {
+            if let Err(err) = app.emit("encryption://event", event) {
+                warn!(error = %err, "Failed to emit key rotation event to frontend");
+            }
+        }
+    };
+    let report_progress = |progress: ReencryptionProgress| {
+        emit(EncryptionEvent::ReencryptionProgress {
+            epoch: progress.epoch,
+            processed: progress.processed,
+            total: progress.total,
+            failed: progress.failed,
+            abandoned: progress.abandoned,
+        })
+    };
+
+    let mut interval = tokio::time::interval(Duration::from_secs(KEY_ROTATION_POLL_SECS));
+    loop {
+        interval.tick().await;
+        if !encryption_session.is_ready().await {
+            continue;
+        }
+        match usecase.execute(&report_progress).await {
+            Ok(Some(progress)) if progress.is_complete() => {
+                emit(EncryptionEvent::ReencryptionCompleted {
+                    epoch: progress.epoch,
+                    abandoned: progress.abandoned,
+                });
+            }
+            Ok(_) => {}
+            Err(err) => {
+                warn!(error = %err, "History re-encryption pass failed");
+            }
+        }
+    }
 }
 
 async fn run_space_access_completion_loop<R: Runtime>(
@@ -1439,6 +1892,22 @@ async fn resolve_device_name_for_peer(
     }
 }
 
+/// Use cases behind `NetworkEvent::KeySlotUpdateReceived` and keyslot resends.
+#[derive(Clone)]
+struct KeySlotUseCases {
+    apply: Arc<ApplyKeySlotUpdate>,
+    resend: Arc<ResendKeySlot>,
+}
+
+/// Use cases behind `NetworkEvent::PairingRevoked`, pending revocation retries
+/// and their acknowledgements.
+#[derive(Clone)]
+struct RevocationUseCases {
+    unpair: Arc<UnpairDevice>,
+    apply: Arc<ApplyPeerRevocation>,
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn run_pairing_event_loop<R: Runtime>(
     mut event_rx: mpsc::Receiver<NetworkEvent>,
     orchestrator: Arc<PairingOrchestrator>,
@@ -1446,6 +1915,12 @@ async fn run_pairing_event_loop<R: Runtime>(
     network: Arc<dyn NetworkPort>,
     space_access_orchestrator: Arc<SpaceAccessOrchestrator>,
     space_access_runtime_ports: RuntimeSpaceAccessPorts,
+    keyslot: Option<KeySlotUseCases>,
+    peer_address: Option<Arc<RecordPeerAddress>>,
+    peer_heartbeat: Option<Arc<RecordPeerHeartbeat>>,
+    peer_capabilities: Option<Arc<RecordPeerCapabilities>>,
+    revocation: Option<RevocationUseCases>,
+    catch_up: Option<Arc<CatchUpClipboardHistory>>,
 ) {
     while let Some(event) = event_rx.recv().await {
         match event {
@@ -1510,15 +1985,68 @@ async fn run_pairing_event_loop<R: Runtime>(
                     }
                 }
             }
-            NetworkEvent::PeerReady { ref peer_id }
+            NetworkEvent::PeerReady { ref peer_id, .. }
             | NetworkEvent::PeerNotReady { ref peer_id } => {
-                let connected = matches!(event, NetworkEvent::PeerReady { .. });
+                let transport = match event {
+                    NetworkEvent::PeerReady { transport, .. } => Some(transport),
+                    _ => None,
+                };
                 let device_name = resolve_device_name_for_peer(&network, peer_id).await;
                 if let Some(app) = app_handle.as_ref() {
                     let payload = P2PPeerConnectionEvent {
                         peer_id: peer_id.clone(),
                         device_name,
-                        connected,
+                        connected: transport.is_some(),
+                        transport: transport.map(|transport| transport.as_str().to_string()),
+                    };
+                    if let Err(err) = app.emit("p2p-peer-connection-changed", payload) {
+                        warn!(error = %err, "Failed to emit peer connection event");
+                    }
+                }
+                if let (Some(_), Some(revocation)) = (transport, revocation.as_ref()) {
+                    // Deliver an unpair notice the peer missed while offline.
+                    let unpair = revocation.unpair.clone();
+                    let peer_id = peer_id.clone();
+                    tokio::spawn(async move {
+                        if let Err(err) = unpair.deliver_pending(&peer_id).await {
+                            warn!(peer_id = %peer_id, error = %err, "Failed to deliver pending revocation");
+                        }
+                    });
+                }
+                if let (Some(_), Some(keyslot)) = (transport, keyslot.as_ref()) {
+                    // Deliver a passphrase change or key rotation the peer missed while offline.
+                    let resend = keyslot.resend.clone();
+                    let peer_id = peer_id.clone();
+                    tokio::spawn(async move {
+                        if let Err(err) = resend.execute(&peer_id).await {
+                            warn!(peer_id = %peer_id, error = %err, "Failed to resend keyslot");
+                        }
+                    });
+                }
+                if let (Some(_), Some(catch_up)) = (transport, catch_up.as_ref()) {
+                    // Ask for the history this device missed while the peer was unreachable.
+                    let catch_up = catch_up.clone();
+                    let peer_id = peer_id.clone();
+                    tokio::spawn(async move {
+                        if let Err(err) = catch_up.request(&peer_id).await {
+                            warn!(peer_id = %peer_id, error = %err, "Failed to request clipboard catch-up");
+                        }
+                    });
+                }
+            }
+            NetworkEvent::PeerTransportChanged { peer_id, transport } => {
+                debug!(
+                    peer_id = %peer_id,
+                    transport = transport.as_str(),
+                    "Peer transport changed"
+                );
+                let device_name = resolve_device_name_for_peer(&network, &peer_id).await;
+                if let Some(app) = app_handle.as_ref() {
+                    let payload = P2PPeerConnectionEvent {
+                        peer_id,
+                        device_name,
+                        connected: true,
+                        transport: Some(transport.as_str().to_string()),
                     };
                     if let Err(err) = app.emit("p2p-peer-connection-changed", payload) {
                         warn!(error = %err, "Failed to emit peer connection event");
@@ -1531,6 +2059,7 @@ async fn run_pairing_event_loop<R: Runtime>(
                         peer_id: peer.peer_id,
                         device_name: Some(peer.device_name),
                         connected: true,
+                        transport: None,
                     };
                     if let Err(err) = app.emit("p2p-peer-connection-changed", payload) {
                         warn!(error = %err, "Failed to emit peer connection event");
@@ -1544,6 +2073,7 @@ async fn run_pairing_event_loop<R: Runtime>(
                         peer_id,
                         device_name,
                         connected: false,
+                        transport: None,
                     };
                     if let Err(err) = app.emit("p2p-peer-connection-changed", payload) {
                         warn!(error = %err, "Failed to emit peer connection event");
@@ -1564,6 +2094,159 @@ async fn run_pairing_event_loop<R: Runtime>(
                     }
                 }
             }
+            NetworkEvent::KeySlotUpdateReceived { peer_id, message } => {
+                if let Some(keyslot) = keyslot.as_ref() {
+                    match keyslot.apply.execute(&peer_id, message).await {
+                        Ok(Some(pending)) => {
+                            if let Some(app) = app_handle.as_ref() {
+                                let event = EncryptionEvent::KeySlotUpdatePending {
+                                    peer_id: pending.peer_id,
+                                    epoch: pending.epoch,
+                                    revision: pending.revision,
+                                };
+                                if let Err(err) = app.emit("encryption://event", event) {
+                                    warn!(error = %err, "Failed to emit keyslot update event");
+                                }
+                            }
+                        }
+                        Ok(None) => {}
+                        Err(err) => {
+                            warn!(peer_id = %peer_id, error = %err, "Rejected keyslot update");
+                        }
+                    }
+                }
+            }
+            NetworkEvent::CatchUpRequested { peer_id, request } => {
+                if let Some(catch_up) = catch_up.as_ref() {
+                    // Resending can take a while; keep the event loop responsive.
+                    let catch_up = catch_up.clone();
+                    tokio::spawn(async move {
+                        if let Err(err) = catch_up.respond(&peer_id, request).await {
+                            warn!(peer_id = %peer_id, error = %err, "Failed to answer clipboard catch-up");
+                        }
+                    });
+                }
+            }
+            NetworkEvent::PairingRevoked { peer_id, direction } => {
+                let by_peer = direction == ProtocolDirection::Inbound;
+                if by_peer {
+                    if let Some(revocation) = revocation.as_ref() {
+                        if let Err(err) = revocation.apply.execute(&peer_id).await {
+                            warn!(peer_id = %peer_id, error = %err, "Failed to apply peer revocation");
+                        }
+                    }
+                }
+                if let Some(app) = app_handle.as_ref() {
+                    let payload = P2PPairingRevokedEvent { peer_id, by_peer };
+                    if let Err(err) = app.emit("p2p-pairing-revoked", payload) {
+                        warn!(error = %err, "Failed to emit pairing revoked event");
+                    }
+                }
+            }
+            NetworkEvent::PairingRevocationAcknowledged { peer_id } => {
+                if let Some(revocation) = revocation.as_ref() {
+                    if let Err(err) = revocation.unpair.acknowledge(&peer_id).await {
+                        warn!(peer_id = %peer_id, error = %err, "Failed to record revocation acknowledgement");
+                    }
+                }
+            }
+            NetworkEvent::PeerIdentityMismatch {
+                peer_id,
+                expected_fingerprint,
+                presented_fingerprint,
+            } => {
+                if let Some(app) = app_handle.as_ref() {
+                    let payload = P2PPeerIdentityMismatchEvent {
+                        peer_id,
+                        expected_fingerprint,
+                        presented_fingerprint,
+                    };
+                    if let Err(err) = app.emit("p2p-peer-identity-mismatch", payload) {
+                        warn!(error = %err, "Failed to emit peer identity mismatch event");
+                    }
+                }
+            }
+            NetworkEvent::PeerHeartbeat {
+                peer_id,
+                seen_at,
+                rtt_ms,
+            } => {
+                if let Some(usecase) = peer_heartbeat.as_ref() {
+                    if let Err(err) = usecase.execute(&peer_id, seen_at).await {
+                        warn!(peer_id = %peer_id, error = %err, "Failed to record peer heartbeat");
+                    }
+                }
+                if let Some(app) = app_handle.as_ref() {
+                    let payload = P2PPeerHeartbeatEvent { peer_id, rtt_ms };
+                    if let Err(err) = app.emit("p2p-peer-heartbeat", payload) {
+                        warn!(error = %err, "Failed to emit peer heartbeat event");
+                    }
+                }
+            }
+            NetworkEvent::TransferProgress(progress) => {
+                if let Some(app) = app_handle.as_ref() {
+                    let direction = match progress.direction {
+                        ProtocolDirection::Inbound => "inbound",
+                        ProtocolDirection::Outbound => "outbound",
+                    };
+                    let payload = P2PTransferProgressEvent {
+                        peer_id: progress.peer_id,
+                        content_hash: progress.content_hash,
+                        direction: direction.to_string(),
+                        transferred_bytes: progress.transferred_bytes,
+                        total_bytes: progress.total_bytes,
+                    };
+                    if let Err(err) = app.emit("p2p-transfer-progress", payload) {
+                        warn!(error = %err, "Failed to emit transfer progress event");
+                    }
+                }
+            }
+            NetworkEvent::TransferFailed {
+                peer_id,
+                content_hash,
+                error,
+            } => {
+                if let Some(app) = app_handle.as_ref() {
+                    let payload = P2PTransferFailedEvent {
+                        peer_id,
+                        content_hash,
+                        error,
+                    };
+                    if let Err(err) = app.emit("p2p-transfer-failed", payload) {
+                        warn!(error = %err, "Failed to emit transfer failed event");
+                    }
+                }
+            }
+            NetworkEvent::PeerAddressConfirmed { peer_id, address } => {
+                if let Some(usecase) = peer_address.as_ref() {
+                    if let Err(err) = usecase.execute(peer_id.clone(), address).await {
+                        warn!(peer_id = %peer_id, error = %err, "Failed to record peer address");
+                    }
+                }
+            }
+            NetworkEvent::PeerCapabilitiesNegotiated(capabilities) => {
+                if let Some(usecase) = peer_capabilities.as_ref() {
+                    match usecase.execute(&capabilities).await {
+                        Ok(true) => {
+                            // The request skipped on PeerReady can go out now that
+                            // the peer has advertised catch-up.
+                            if let Some(catch_up) = catch_up.as_ref() {
+                                let catch_up = catch_up.clone();
+                                let peer_id = capabilities.peer_id.clone();
+                                tokio::spawn(async move {
+                                    if let Err(err) = catch_up.request(&peer_id).await {
+                                        warn!(peer_id = %peer_id, error = %err, "Failed to request clipboard catch-up");
+                                    }
+                                });
+                            }
+                        }
+                        Ok(false) => {}
+                        Err(err) => {
+                            warn!(peer_id = %capabilities.peer_id, error = %err, "Failed to record peer capabilities");
+                        }
+                    }
+                }
+            }
             _ => {}
         }
     }
@@ -1580,7 +2263,9 @@ async fn handle_pairing_message<R: Runtime>(
 ) {
     match message {
         PairingMessage::Request(request) => {
-            if let Some(app) = app_handle {
+            // 邀请配对由带外密钥认证,不弹出确认请求
+            let invited = request.invitation_proof.is_some();
+            if let Some(app) = app_handle.filter(|_| !invited) {
                 let payload = P2PPairingVerificationEvent::request(
                     &request.session_id,
                     peer_id.clone(),
@@ -1913,6 +2598,11 @@ async fn handle_pairing_message<R: Runtime>(
                 error!(error = %err, session_id = %session_id, "Failed to handle pairing busy");
             }
         }
+        PairingMessage::Revoke(_) | PairingMessage::RevokeAck(_) => {
+            // The network layer reports revocations and their acknowledgements
+            // as dedicated `NetworkEvent`s.
+            debug!(peer_id = %peer_id, "Ignoring revoke on pairing session");
+        }
     }
 }
 
@@ -1939,6 +2629,8 @@ async fn run_pairing_action_loop<R: Runtime>(
                     PairingMessage::Reject(_) => "reject",
                     PairingMessage::Cancel(_) => "cancel",
                     PairingMessage::Busy(_) => "busy",
+                    PairingMessage::Revoke(_) => "revoke",
+                    PairingMessage::RevokeAck(_) => "revoke_ack",
                 };
                 info!(
                     session_id = %session_id,
@@ -2153,6 +2845,28 @@ async fn run_pairing_action_loop<R: Runtime>(
                     }
                 }
             }
+            PairingAction::IdentityChanged {
+                session_id,
+                peer_id,
+                expected_fingerprint,
+                presented_fingerprint,
+            } => {
+                warn!(
+                    session_id = %session_id,
+                    peer_id = %peer_id,
+                    "Paired device presented a new identity key while re-pairing"
+                );
+                if let Some(app) = app_handle.as_ref() {
+                    let payload = P2PPeerIdentityMismatchEvent {
+                        peer_id,
+                        expected_fingerprint,
+                        presented_fingerprint,
+                    };
+                    if let Err(err) = app.emit("p2p-peer-identity-mismatch", payload) {
+                        warn!(error = %err, "Failed to emit peer identity mismatch event");
+                    }
+                }
+            }
             other => {
                 warn!(action = ?other, "Unhandled pairing action received");
             }
@@ -2167,17 +2881,16 @@ mod tests {
     use anyhow::anyhow;
     use async_trait::async_trait;
     use chrono::Utc;
-    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Mutex;
     use std::time::Duration;
     use tauri::{Listener, Wry};
     use tokio::sync::{mpsc, Mutex as TokioMutex};
     use uc_app::usecases::PairingConfig;
-    use uc_core::network::paired_device::{PairedDevice, PairingState};
     use uc_core::network::protocol::{PairingChallenge, PairingRequest};
-    use uc_core::network::{ConnectedPeer, DiscoveredPeer, PairingMessage};
+    use uc_core::network::{DiscoveredPeer, PairingMessage};
     use uc_core::ports::{EncryptionSessionPort, NetworkPort};
     use uc_core::security::model::{EncryptionError, MasterKey};
+    use uc_core::testing::{InMemoryPairedDeviceRepository, TestNetwork};
 
     #[test]
     fn test_wiring_error_display() {
@@ -2186,25 +2899,9 @@ mod tests {
         assert!(err.to_string().contains("connection failed"));
     }
 
-    struct NoopPairedDeviceRepository;
+    struct SuccessSpaceAccessCrypto;
 
-    struct NoopNetwork;
-
-    struct SendFailNetwork {
-        send_called: Arc<AtomicUsize>,
-    }
-
-    struct CloseRecordingNetwork {
-        close_calls: Arc<Mutex<Vec<(String, Option<String>)>>>,
-    }
-
-    struct OfferRecordingNetwork {
-        sent_messages: Arc<Mutex<Vec<(String, PairingMessage)>>>,
-    }
-
-    struct SuccessSpaceAccessCrypto;
-
-    struct SuccessSpaceAccessTransport;
+    struct SuccessSpaceAccessTransport;
 
     struct SuccessSpaceAccessProof;
 
@@ -2230,6 +2927,10 @@ mod tests {
             },
             kdf: uc_core::security::model::KdfParams::for_initialization(),
             salt: vec![1; 16],
+            epoch: 0,
+            revision: 0,
+            retired_master_key: None,
+            retained_master_keys: Vec::new(),
             wrapped_master_key: None,
         }
     }
@@ -2242,12 +2943,17 @@ mod tests {
             },
             kdf: uc_core::security::model::KdfParams::for_initialization(),
             salt: vec![1; 16],
+            epoch: 0,
+            revision: 0,
+            retired_master_key: None,
+            retained_master_keys: Vec::new(),
             wrapped_master_key: uc_core::security::model::EncryptedBlob {
                 version: uc_core::security::model::EncryptionFormatVersion::V1,
                 aead: uc_core::security::model::EncryptionAlgo::XChaCha20Poly1305,
                 nonce: vec![2; 24],
                 ciphertext: vec![3; 32],
                 aad_fingerprint: None,
+                key_epoch: 0,
             },
             created_at: None,
             updated_at: None,
@@ -2264,7 +2970,7 @@ mod tests {
         let mut timer = NoopSpaceAccessTimer;
         let mut store = SuccessSpaceAccessPersistence;
         let crypto = SuccessSpaceAccessCrypto;
-        let network = NoopNetwork;
+        let network = TestNetwork::new();
         let mut executor = uc_app::usecases::space_access::SpaceAccessExecutor {
             crypto: &crypto,
             net: &network,
@@ -2314,7 +3020,7 @@ mod tests {
         let mut timer = NoopSpaceAccessTimer;
         let mut store = SuccessSpaceAccessPersistence;
         let crypto = SuccessSpaceAccessCrypto;
-        let network = NoopNetwork;
+        let network = TestNetwork::new();
         let mut executor = uc_app::usecases::space_access::SpaceAccessExecutor {
             crypto: &crypto,
             net: &network,
@@ -2364,308 +3070,6 @@ mod tests {
         ));
     }
 
-    #[async_trait]
-    impl NetworkPort for NoopNetwork {
-        async fn send_clipboard(
-            &self,
-            _peer_id: &str,
-            _encrypted_data: Vec<u8>,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn broadcast_clipboard(&self, _encrypted_data: Vec<u8>) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn subscribe_clipboard(
-            &self,
-        ) -> anyhow::Result<tokio::sync::mpsc::Receiver<uc_core::network::ClipboardMessage>>
-        {
-            let (_tx, rx) = tokio::sync::mpsc::channel(1);
-            Ok(rx)
-        }
-
-        async fn get_discovered_peers(&self) -> anyhow::Result<Vec<DiscoveredPeer>> {
-            Ok(vec![])
-        }
-
-        async fn get_connected_peers(&self) -> anyhow::Result<Vec<ConnectedPeer>> {
-            Ok(vec![])
-        }
-
-        fn local_peer_id(&self) -> String {
-            "local-peer".to_string()
-        }
-
-        async fn announce_device_name(&self, _device_name: String) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn open_pairing_session(
-            &self,
-            _peer_id: String,
-            _session_id: String,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn send_pairing_on_session(
-            &self,
-            _session_id: String,
-            _message: PairingMessage,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn close_pairing_session(
-            &self,
-            _session_id: String,
-            _reason: Option<String>,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn unpair_device(&self, _peer_id: String) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn subscribe_events(
-            &self,
-        ) -> anyhow::Result<tokio::sync::mpsc::Receiver<NetworkEvent>> {
-            let (_tx, rx) = tokio::sync::mpsc::channel(1);
-            Ok(rx)
-        }
-    }
-
-    #[async_trait]
-    impl NetworkPort for SendFailNetwork {
-        async fn send_clipboard(
-            &self,
-            _peer_id: &str,
-            _encrypted_data: Vec<u8>,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn broadcast_clipboard(&self, _encrypted_data: Vec<u8>) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn subscribe_clipboard(
-            &self,
-        ) -> anyhow::Result<tokio::sync::mpsc::Receiver<uc_core::network::ClipboardMessage>>
-        {
-            let (_tx, rx) = tokio::sync::mpsc::channel(1);
-            Ok(rx)
-        }
-
-        async fn get_discovered_peers(&self) -> anyhow::Result<Vec<DiscoveredPeer>> {
-            Ok(vec![])
-        }
-
-        async fn get_connected_peers(&self) -> anyhow::Result<Vec<ConnectedPeer>> {
-            Ok(vec![])
-        }
-
-        fn local_peer_id(&self) -> String {
-            "local-peer".to_string()
-        }
-
-        async fn announce_device_name(&self, _device_name: String) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn open_pairing_session(
-            &self,
-            _peer_id: String,
-            _session_id: String,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn send_pairing_on_session(
-            &self,
-            _session_id: String,
-            _message: PairingMessage,
-        ) -> anyhow::Result<()> {
-            self.send_called.fetch_add(1, Ordering::SeqCst);
-            Err(anyhow!("send failed"))
-        }
-
-        async fn close_pairing_session(
-            &self,
-            _session_id: String,
-            _reason: Option<String>,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn unpair_device(&self, _peer_id: String) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn subscribe_events(
-            &self,
-        ) -> anyhow::Result<tokio::sync::mpsc::Receiver<NetworkEvent>> {
-            let (_tx, rx) = tokio::sync::mpsc::channel(1);
-            Ok(rx)
-        }
-    }
-
-    #[async_trait]
-    impl NetworkPort for CloseRecordingNetwork {
-        async fn send_clipboard(
-            &self,
-            _peer_id: &str,
-            _encrypted_data: Vec<u8>,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn broadcast_clipboard(&self, _encrypted_data: Vec<u8>) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn subscribe_clipboard(
-            &self,
-        ) -> anyhow::Result<tokio::sync::mpsc::Receiver<uc_core::network::ClipboardMessage>>
-        {
-            let (_tx, rx) = tokio::sync::mpsc::channel(1);
-            Ok(rx)
-        }
-
-        async fn get_discovered_peers(&self) -> anyhow::Result<Vec<DiscoveredPeer>> {
-            Ok(vec![])
-        }
-
-        async fn get_connected_peers(&self) -> anyhow::Result<Vec<ConnectedPeer>> {
-            Ok(vec![])
-        }
-
-        fn local_peer_id(&self) -> String {
-            "local-peer".to_string()
-        }
-
-        async fn announce_device_name(&self, _device_name: String) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn open_pairing_session(
-            &self,
-            _peer_id: String,
-            _session_id: String,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn send_pairing_on_session(
-            &self,
-            _session_id: String,
-            _message: PairingMessage,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn close_pairing_session(
-            &self,
-            session_id: String,
-            reason: Option<String>,
-        ) -> anyhow::Result<()> {
-            self.close_calls.lock().unwrap().push((session_id, reason));
-            Ok(())
-        }
-
-        async fn unpair_device(&self, _peer_id: String) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn subscribe_events(
-            &self,
-        ) -> anyhow::Result<tokio::sync::mpsc::Receiver<NetworkEvent>> {
-            let (_tx, rx) = tokio::sync::mpsc::channel(1);
-            Ok(rx)
-        }
-    }
-
-    #[async_trait]
-    impl NetworkPort for OfferRecordingNetwork {
-        async fn send_clipboard(
-            &self,
-            _peer_id: &str,
-            _encrypted_data: Vec<u8>,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn broadcast_clipboard(&self, _encrypted_data: Vec<u8>) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn subscribe_clipboard(
-            &self,
-        ) -> anyhow::Result<tokio::sync::mpsc::Receiver<uc_core::network::ClipboardMessage>>
-        {
-            let (_tx, rx) = tokio::sync::mpsc::channel(1);
-            Ok(rx)
-        }
-
-        async fn get_discovered_peers(&self) -> anyhow::Result<Vec<DiscoveredPeer>> {
-            Ok(vec![])
-        }
-
-        async fn get_connected_peers(&self) -> anyhow::Result<Vec<ConnectedPeer>> {
-            Ok(vec![])
-        }
-
-        fn local_peer_id(&self) -> String {
-            "local-peer".to_string()
-        }
-
-        async fn announce_device_name(&self, _device_name: String) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn open_pairing_session(
-            &self,
-            _peer_id: String,
-            _session_id: String,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn send_pairing_on_session(
-            &self,
-            session_id: String,
-            message: PairingMessage,
-        ) -> anyhow::Result<()> {
-            self.sent_messages
-                .lock()
-                .unwrap()
-                .push((session_id, message));
-            Ok(())
-        }
-
-        async fn close_pairing_session(
-            &self,
-            _session_id: String,
-            _reason: Option<String>,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn unpair_device(&self, _peer_id: String) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn subscribe_events(
-            &self,
-        ) -> anyhow::Result<tokio::sync::mpsc::Receiver<NetworkEvent>> {
-            let (_tx, rx) = tokio::sync::mpsc::channel(1);
-            Ok(rx)
-        }
-    }
-
     #[async_trait]
     impl EncryptionSessionPort for FixedMasterKeyEncryptionSession {
         async fn is_ready(&self) -> bool {
@@ -2689,6 +3093,38 @@ mod tests {
             *self.master_key.lock().await = None;
             Ok(())
         }
+
+        async fn get_current_key(
+            &self,
+        ) -> Result<
+            (
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            ),
+            uc_core::security::model::EncryptionError,
+        > {
+            Ok((0, self.get_master_key().await?))
+        }
+
+        async fn get_master_key_for_epoch(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+        ) -> Result<uc_core::security::model::MasterKey, uc_core::security::model::EncryptionError>
+        {
+            self.get_master_key().await
+        }
+
+        async fn set_epoch_keys(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+            master_key: uc_core::security::model::MasterKey,
+            _older: Vec<(
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            )>,
+        ) -> Result<(), uc_core::security::model::EncryptionError> {
+            self.set_master_key(master_key).await
+        }
     }
 
     #[async_trait]
@@ -2782,54 +3218,6 @@ mod tests {
         }
     }
 
-    #[async_trait]
-    impl PairedDeviceRepositoryPort for NoopPairedDeviceRepository {
-        async fn get_by_peer_id(
-            &self,
-            _peer_id: &uc_core::ids::PeerId,
-        ) -> Result<Option<PairedDevice>, uc_core::ports::errors::PairedDeviceRepositoryError>
-        {
-            Ok(None)
-        }
-
-        async fn list_all(
-            &self,
-        ) -> Result<Vec<PairedDevice>, uc_core::ports::errors::PairedDeviceRepositoryError>
-        {
-            Ok(vec![])
-        }
-
-        async fn upsert(
-            &self,
-            _device: PairedDevice,
-        ) -> Result<(), uc_core::ports::errors::PairedDeviceRepositoryError> {
-            Ok(())
-        }
-
-        async fn set_state(
-            &self,
-            _peer_id: &uc_core::ids::PeerId,
-            _state: PairingState,
-        ) -> Result<(), uc_core::ports::errors::PairedDeviceRepositoryError> {
-            Ok(())
-        }
-
-        async fn update_last_seen(
-            &self,
-            _peer_id: &uc_core::ids::PeerId,
-            _last_seen_at: chrono::DateTime<chrono::Utc>,
-        ) -> Result<(), uc_core::ports::errors::PairedDeviceRepositoryError> {
-            Ok(())
-        }
-
-        async fn delete(
-            &self,
-            _peer_id: &uc_core::ids::PeerId,
-        ) -> Result<(), uc_core::ports::errors::PairedDeviceRepositoryError> {
-            Ok(())
-        }
-    }
-
     struct NoopKeySlotStore;
 
     struct StaticKeySlotStore {
@@ -2911,7 +3299,7 @@ mod tests {
         let mut timer = NoopSpaceAccessTimer;
         let mut store = SuccessSpaceAccessPersistence;
         let crypto = SuccessSpaceAccessCrypto;
-        let network = NoopNetwork;
+        let network = TestNetwork::new();
         let mut executor = uc_app::usecases::space_access::SpaceAccessExecutor {
             crypto: &crypto,
             net: &network,
@@ -2942,7 +3330,7 @@ mod tests {
     #[tokio::test]
     async fn pairing_event_loop_registers_session_on_request() {
         let (event_tx, event_rx) = mpsc::channel(1);
-        let device_repo = Arc::new(NoopPairedDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             PairingConfig::default(),
             device_repo,
@@ -2952,7 +3340,7 @@ mod tests {
             vec![9; 32],
         );
         let orchestrator = Arc::new(orchestrator);
-        let network: Arc<dyn NetworkPort> = Arc::new(NoopNetwork);
+        let network: Arc<dyn NetworkPort> = Arc::new(TestNetwork::new());
         let space_access_orchestrator = Arc::new(SpaceAccessOrchestrator::new());
         let runtime_ports =
             test_runtime_space_access_ports(network.clone(), space_access_orchestrator.clone());
@@ -2964,6 +3352,12 @@ mod tests {
             network,
             space_access_orchestrator,
             runtime_ports,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         ));
 
         let request = PairingRequest {
@@ -2973,6 +3367,7 @@ mod tests {
             peer_id: "peer-local".to_string(),
             identity_pubkey: vec![1; 32],
             nonce: vec![2; 16],
+            invitation_proof: None,
         };
         let event = NetworkEvent::PairingMessageReceived {
             peer_id: "peer-remote".to_string(),
@@ -2991,7 +3386,7 @@ mod tests {
 
     #[tokio::test]
     async fn busy_offer_payload_routes_to_joiner_offer_context() {
-        let device_repo = Arc::new(NoopPairedDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             PairingConfig::default(),
             device_repo,
@@ -3002,7 +3397,7 @@ mod tests {
         );
         let orchestrator = Arc::new(orchestrator);
         let space_access_orchestrator = Arc::new(SpaceAccessOrchestrator::new());
-        let network: Arc<dyn NetworkPort> = Arc::new(NoopNetwork);
+        let network: Arc<dyn NetworkPort> = Arc::new(TestNetwork::new());
         let runtime_ports =
             test_runtime_space_access_ports(network.clone(), space_access_orchestrator.clone());
 
@@ -3038,7 +3433,7 @@ mod tests {
 
     #[tokio::test]
     async fn busy_offer_payload_dispatches_offer_accepted_when_waiting_offer() {
-        let device_repo = Arc::new(NoopPairedDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             PairingConfig::default(),
             device_repo,
@@ -3049,7 +3444,7 @@ mod tests {
         );
         let orchestrator = Arc::new(orchestrator);
         let space_access_orchestrator = Arc::new(SpaceAccessOrchestrator::new());
-        let network: Arc<dyn NetworkPort> = Arc::new(NoopNetwork);
+        let network: Arc<dyn NetworkPort> = Arc::new(TestNetwork::new());
         let runtime_ports =
             test_runtime_space_access_ports(network.clone(), space_access_orchestrator.clone());
 
@@ -3086,7 +3481,7 @@ mod tests {
 
     #[tokio::test]
     async fn busy_proof_payload_routes_to_proof_branch_and_validates_nonce_length() {
-        let device_repo = Arc::new(NoopPairedDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             PairingConfig::default(),
             device_repo,
@@ -3097,7 +3492,7 @@ mod tests {
         );
         let orchestrator = Arc::new(orchestrator);
         let space_access_orchestrator = Arc::new(SpaceAccessOrchestrator::new());
-        let network: Arc<dyn NetworkPort> = Arc::new(NoopNetwork);
+        let network: Arc<dyn NetworkPort> = Arc::new(TestNetwork::new());
         let runtime_ports =
             test_runtime_space_access_ports(network.clone(), space_access_orchestrator.clone());
         seed_waiting_joiner_proof_state(
@@ -3171,7 +3566,7 @@ mod tests {
 
     #[tokio::test]
     async fn busy_proof_payload_accepts_valid_hmac_with_session_master_key() {
-        let device_repo = Arc::new(NoopPairedDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             PairingConfig::default(),
             device_repo,
@@ -3182,7 +3577,7 @@ mod tests {
         );
         let orchestrator = Arc::new(orchestrator);
         let space_access_orchestrator = Arc::new(SpaceAccessOrchestrator::new());
-        let network: Arc<dyn NetworkPort> = Arc::new(NoopNetwork);
+        let network: Arc<dyn NetworkPort> = Arc::new(TestNetwork::new());
         let master_key = MasterKey::from_bytes(&[4; 32]).expect("master key");
         let proof_port: Arc<dyn uc_core::ports::space::ProofPort> =
             Arc::new(HmacProofAdapter::new_with_encryption_session(Arc::new(
@@ -3255,7 +3650,7 @@ mod tests {
 
     #[tokio::test]
     async fn busy_result_payload_routes_to_access_denied_transition() {
-        let device_repo = Arc::new(NoopPairedDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             PairingConfig::default(),
             device_repo,
@@ -3266,7 +3661,7 @@ mod tests {
         );
         let orchestrator = Arc::new(orchestrator);
         let space_access_orchestrator = Arc::new(SpaceAccessOrchestrator::new());
-        let network: Arc<dyn NetworkPort> = Arc::new(NoopNetwork);
+        let network: Arc<dyn NetworkPort> = Arc::new(TestNetwork::new());
         let runtime_ports =
             test_runtime_space_access_ports(network.clone(), space_access_orchestrator.clone());
         seed_waiting_decision_state(
@@ -3311,7 +3706,7 @@ mod tests {
 
     #[tokio::test]
     async fn busy_result_payload_routes_to_access_granted_transition() {
-        let device_repo = Arc::new(NoopPairedDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             PairingConfig::default(),
             device_repo,
@@ -3322,7 +3717,7 @@ mod tests {
         );
         let orchestrator = Arc::new(orchestrator);
         let space_access_orchestrator = Arc::new(SpaceAccessOrchestrator::new());
-        let network: Arc<dyn NetworkPort> = Arc::new(NoopNetwork);
+        let network: Arc<dyn NetworkPort> = Arc::new(TestNetwork::new());
         let runtime_ports =
             test_runtime_space_access_ports(network.clone(), space_access_orchestrator.clone());
         seed_waiting_decision_state(
@@ -3362,84 +3757,6 @@ mod tests {
         ));
     }
 
-    struct TestNetwork {
-        discovered: Vec<DiscoveredPeer>,
-    }
-
-    #[async_trait]
-    impl NetworkPort for TestNetwork {
-        async fn send_clipboard(
-            &self,
-            _peer_id: &str,
-            _encrypted_data: Vec<u8>,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn broadcast_clipboard(&self, _encrypted_data: Vec<u8>) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn subscribe_clipboard(
-            &self,
-        ) -> anyhow::Result<tokio::sync::mpsc::Receiver<uc_core::network::ClipboardMessage>>
-        {
-            let (_tx, rx) = tokio::sync::mpsc::channel(1);
-            Ok(rx)
-        }
-
-        async fn get_discovered_peers(&self) -> anyhow::Result<Vec<DiscoveredPeer>> {
-            Ok(self.discovered.clone())
-        }
-
-        async fn get_connected_peers(&self) -> anyhow::Result<Vec<ConnectedPeer>> {
-            Ok(vec![])
-        }
-
-        fn local_peer_id(&self) -> String {
-            "peer-local".to_string()
-        }
-
-        async fn announce_device_name(&self, _device_name: String) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn open_pairing_session(
-            &self,
-            _peer_id: String,
-            _session_id: String,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn send_pairing_on_session(
-            &self,
-            _session_id: String,
-            _message: PairingMessage,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn close_pairing_session(
-            &self,
-            _session_id: String,
-            _reason: Option<String>,
-        ) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn unpair_device(&self, _peer_id: String) -> anyhow::Result<()> {
-            Ok(())
-        }
-
-        async fn subscribe_events(
-            &self,
-        ) -> anyhow::Result<tokio::sync::mpsc::Receiver<NetworkEvent>> {
-            let (_tx, rx) = tokio::sync::mpsc::channel(1);
-            Ok(rx)
-        }
-    }
-
     #[tokio::test]
     async fn peer_name_updated_emits_frontend_event() {
         let app = tauri::test::mock_app();
@@ -3451,7 +3768,7 @@ mod tests {
         });
 
         let (event_tx, event_rx) = mpsc::channel(1);
-        let device_repo = Arc::new(NoopPairedDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             PairingConfig::default(),
             device_repo,
@@ -3461,8 +3778,8 @@ mod tests {
             vec![9; 32],
         );
         let orchestrator = Arc::new(orchestrator);
-        let network: Arc<dyn NetworkPort> = Arc::new(TestNetwork {
-            discovered: vec![DiscoveredPeer {
+        let network: Arc<dyn NetworkPort> =
+            Arc::new(TestNetwork::new().with_discovered(vec![DiscoveredPeer {
                 peer_id: "peer-1".to_string(),
                 device_name: Some("Desk".to_string()),
                 device_id: None,
@@ -3470,8 +3787,7 @@ mod tests {
                 discovered_at: Utc::now(),
                 last_seen: Utc::now(),
                 is_paired: true,
-            }],
-        });
+            }]));
         let space_access_orchestrator = Arc::new(SpaceAccessOrchestrator::new());
         let runtime_ports =
             test_runtime_space_access_ports(network.clone(), space_access_orchestrator.clone());
@@ -3483,6 +3799,12 @@ mod tests {
             network,
             space_access_orchestrator,
             runtime_ports,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         ));
 
         event_tx
@@ -3512,7 +3834,7 @@ mod tests {
         });
 
         let (event_tx, event_rx) = mpsc::channel(4);
-        let device_repo = Arc::new(NoopPairedDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             PairingConfig::default(),
             device_repo,
@@ -3522,8 +3844,8 @@ mod tests {
             vec![9; 32],
         );
         let orchestrator = Arc::new(orchestrator);
-        let network: Arc<dyn NetworkPort> = Arc::new(TestNetwork {
-            discovered: vec![DiscoveredPeer {
+        let network: Arc<dyn NetworkPort> =
+            Arc::new(TestNetwork::new().with_discovered(vec![DiscoveredPeer {
                 peer_id: "peer-1".to_string(),
                 device_name: Some("Desk".to_string()),
                 device_id: None,
@@ -3531,8 +3853,7 @@ mod tests {
                 discovered_at: Utc::now(),
                 last_seen: Utc::now(),
                 is_paired: false,
-            }],
-        });
+            }]));
         let space_access_orchestrator = Arc::new(SpaceAccessOrchestrator::new());
         let runtime_ports =
             test_runtime_space_access_ports(network.clone(), space_access_orchestrator.clone());
@@ -3544,6 +3865,12 @@ mod tests {
             network,
             space_access_orchestrator,
             runtime_ports,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         ));
 
         event_tx
@@ -3705,7 +4032,7 @@ mod tests {
             .expect("timeout waiting for probe")
             .expect("probe payload received");
 
-        let device_repo = Arc::new(NoopPairedDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             PairingConfig::default(),
             device_repo,
@@ -3723,6 +4050,7 @@ mod tests {
             peer_id: "peer-local".to_string(),
             identity_pubkey: vec![1; 32],
             nonce: vec![2; 16],
+            invitation_proof: None,
         };
         orchestrator
             .handle_incoming_request("peer-remote".to_string(), request)
@@ -3730,7 +4058,7 @@ mod tests {
             .expect("handle incoming request");
 
         let (action_tx, action_rx) = mpsc::channel(1);
-        let network: Arc<dyn NetworkPort> = Arc::new(NoopNetwork);
+        let network: Arc<dyn NetworkPort> = Arc::new(TestNetwork::new());
         let space_access_orchestrator = Arc::new(SpaceAccessOrchestrator::new());
         let key_slot_store = Arc::new(NoopKeySlotStore);
         let runtime_ports =
@@ -3778,7 +4106,7 @@ mod tests {
             let _ = payload_tx_clone.try_send(event.payload().to_string());
         });
 
-        let device_repo = Arc::new(NoopPairedDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             PairingConfig::default(),
             device_repo,
@@ -3796,6 +4124,7 @@ mod tests {
             peer_id: "peer-local".to_string(),
             identity_pubkey: vec![1; 32],
             nonce: vec![2; 16],
+            invitation_proof: None,
         };
         orchestrator
             .handle_incoming_request("peer-remote".to_string(), request)
@@ -3803,7 +4132,7 @@ mod tests {
             .expect("handle incoming request");
 
         let (action_tx, action_rx) = mpsc::channel(1);
-        let network: Arc<dyn NetworkPort> = Arc::new(NoopNetwork);
+        let network: Arc<dyn NetworkPort> = Arc::new(TestNetwork::new());
         let space_access_orchestrator = Arc::new(SpaceAccessOrchestrator::new());
         let key_slot_store = Arc::new(NoopKeySlotStore);
         let runtime_ports =
@@ -3853,7 +4182,7 @@ mod tests {
             let _ = payload_tx_clone.try_send(event.payload().to_string());
         });
 
-        let device_repo = Arc::new(NoopPairedDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             PairingConfig::default(),
             device_repo,
@@ -3865,7 +4194,7 @@ mod tests {
         let orchestrator = Arc::new(orchestrator);
 
         let (action_tx, action_rx) = mpsc::channel(1);
-        let network: Arc<dyn NetworkPort> = Arc::new(NoopNetwork);
+        let network: Arc<dyn NetworkPort> = Arc::new(TestNetwork::new());
         let space_access_orchestrator = Arc::new(SpaceAccessOrchestrator::new());
         let key_slot_store = Arc::new(NoopKeySlotStore);
         let runtime_ports =
@@ -3911,7 +4240,7 @@ mod tests {
             let _ = payload_tx_clone.try_send(event.payload().to_string());
         });
 
-        let device_repo = Arc::new(NoopPairedDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             PairingConfig::default(),
             device_repo,
@@ -3925,15 +4254,13 @@ mod tests {
         let (action_tx, action_rx) = mpsc::channel(4);
         let space_access_orchestrator = Arc::new(SpaceAccessOrchestrator::new());
         let key_slot_store = Arc::new(NoopKeySlotStore);
-        let send_called = Arc::new(AtomicUsize::new(0));
-        let network: Arc<dyn NetworkPort> = Arc::new(SendFailNetwork {
-            send_called: send_called.clone(),
-        });
+        let network =
+            Arc::new(TestNetwork::new().failing("send_pairing_on_session", "send failed"));
         let runtime_ports =
             test_runtime_space_access_ports(network.clone(), space_access_orchestrator.clone());
         let loop_handle = tokio::spawn(run_pairing_action_loop::<tauri::test::MockRuntime>(
             action_rx,
-            network,
+            network.clone(),
             Some(app_handle.clone()),
             orchestrator.clone(),
             space_access_orchestrator,
@@ -3948,6 +4275,7 @@ mod tests {
             device_id: "device-999".to_string(),
             identity_pubkey: vec![1; 32],
             nonce: vec![2; 16],
+            invitation_proof: None,
         };
         action_tx
             .send(PairingAction::Send {
@@ -3958,7 +4286,7 @@ mod tests {
             .expect("send action");
 
         tokio::task::yield_now().await;
-        assert!(send_called.load(Ordering::SeqCst) > 0);
+        assert!(!network.pairing_messages().is_empty());
 
         let mut failed_event = None;
         for _ in 0..3 {
@@ -3984,7 +4312,7 @@ mod tests {
 
     #[tokio::test]
     async fn pairing_action_loop_closes_session_only_for_failed_emit_result() {
-        let device_repo = Arc::new(NoopPairedDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             PairingConfig::default(),
             device_repo,
@@ -3994,10 +4322,7 @@ mod tests {
             vec![9; 32],
         );
         let orchestrator = Arc::new(orchestrator);
-        let close_calls = Arc::new(Mutex::new(Vec::new()));
-        let network = Arc::new(CloseRecordingNetwork {
-            close_calls: close_calls.clone(),
-        });
+        let network = Arc::new(TestNetwork::new());
         let space_access_orchestrator = Arc::new(SpaceAccessOrchestrator::new());
         let runtime_ports =
             test_runtime_space_access_ports(network.clone(), space_access_orchestrator.clone());
@@ -4005,7 +4330,7 @@ mod tests {
         let (action_tx, action_rx) = mpsc::channel(4);
         let loop_handle = tokio::spawn(run_pairing_action_loop::<Wry>(
             action_rx,
-            network,
+            network.clone(),
             None,
             orchestrator,
             space_access_orchestrator,
@@ -4032,7 +4357,7 @@ mod tests {
 
         tokio::time::sleep(Duration::from_millis(50)).await;
 
-        let calls = close_calls.lock().unwrap().clone();
+        let calls = network.closed_sessions();
         assert_eq!(calls.len(), 1);
         assert_eq!(calls[0].0, "session-failed");
         assert_eq!(calls[0].1, Some("pairing failed".to_string()));
@@ -4043,7 +4368,7 @@ mod tests {
 
     #[tokio::test]
     async fn pairing_action_loop_starts_sponsor_authorization_for_responder_role() {
-        let device_repo = Arc::new(NoopPairedDeviceRepository);
+        let device_repo = Arc::new(InMemoryPairedDeviceRepository::new());
         let (orchestrator, _action_rx) = PairingOrchestrator::new(
             PairingConfig::default(),
             device_repo,
@@ -4061,6 +4386,7 @@ mod tests {
             peer_id: "peer-local".to_string(),
             identity_pubkey: vec![1; 32],
             nonce: vec![2; 16],
+            invitation_proof: None,
         };
         orchestrator
             .handle_incoming_request("peer-remote".to_string(), request)
@@ -4072,10 +4398,7 @@ mod tests {
             Some(PairingRole::Responder)
         );
 
-        let sent_messages = Arc::new(Mutex::new(Vec::new()));
-        let network = Arc::new(OfferRecordingNetwork {
-            sent_messages: sent_messages.clone(),
-        });
+        let network = Arc::new(TestNetwork::new());
         let space_access_orchestrator = Arc::new(SpaceAccessOrchestrator::new());
         let runtime_ports =
             test_runtime_space_access_ports(network.clone(), space_access_orchestrator.clone());
@@ -4083,7 +4406,7 @@ mod tests {
         let (action_tx, action_rx) = mpsc::channel(2);
         let loop_handle = tokio::spawn(run_pairing_action_loop::<Wry>(
             action_rx,
-            network,
+            network.clone(),
             None,
             orchestrator,
             space_access_orchestrator,
@@ -4104,7 +4427,7 @@ mod tests {
 
         tokio::time::sleep(Duration::from_millis(80)).await;
 
-        let calls = sent_messages.lock().unwrap().clone();
+        let calls = network.pairing_messages();
         let busy_offer = calls
             .iter()
             .find_map(|(session_id, message)| match message {
@@ -4354,13 +4677,16 @@ mod tests {
             let result = create_platform_layer(
                 secure_storage,
                 &temp_dir,
+                &temp_dir,
                 cmd_tx,
                 encryption,
                 blob_repository,
                 paired_device_repo,
+                Arc::new(InMemoryActiveSpace::default()),
                 clock,
                 storage_config,
                 test_identity_store(),
+                Arc::new(FileSettingsRepository::new(temp_dir.join("settings.json"))),
             );
 
             match result {
@@ -5,11 +5,14 @@ use crate::bootstrap::AppRuntime;
 use crate::commands::record_trace_fields;
 use crate::models::{
     ClipboardEntriesResponse, ClipboardEntryDetail, ClipboardEntryProjection,
-    ClipboardEntryResource,
+    ClipboardEntryResource, ClipboardSearchHit, ClipboardSearchRequest, ClipboardSearchResponse,
+    ClipboardSearchSnippetSegment,
 };
 use std::sync::Arc;
 use tauri::State;
 use tracing::{info_span, Instrument};
+use uc_app::usecases::{BlobGcReport, EntryProjectionFilter};
+use uc_core::clipboard::{ClipboardContentAction, ClipboardSearchQuery, ContentKind};
 use uc_core::ids::EntryId;
 use uc_core::ports::observability::TraceMetadata;
 use uc_core::security::state::EncryptionState;
@@ -21,10 +24,16 @@ pub async fn get_clipboard_entries(
     runtime: State<'_, Arc<AppRuntime>>,
     limit: Option<usize>,
     offset: Option<usize>,
+    pinned_only: Option<bool>,
     _trace: Option<TraceMetadata>,
 ) -> Result<ClipboardEntriesResponse, String> {
     let resolved_limit = limit.unwrap_or(50);
     let resolved_offset = offset.unwrap_or(0);
+    let filter = if pinned_only.unwrap_or(false) {
+        EntryProjectionFilter::Pinned
+    } else {
+        EntryProjectionFilter::All
+    };
     let device_id = runtime.deps.device_identity.current_device_id();
 
     let span = info_span!(
@@ -34,6 +43,7 @@ pub async fn get_clipboard_entries(
         device_id = %device_id,
         limit = resolved_limit,
         offset = resolved_offset,
+        pinned_only = ?pinned_only,
     );
     record_trace_fields(&span, &_trace);
 
@@ -60,7 +70,7 @@ pub async fn get_clipboard_entries(
 
         let uc = runtime.usecases().list_entry_projections();
         let dtos = uc
-            .execute(resolved_limit, resolved_offset)
+            .execute_with_filter(resolved_limit, resolved_offset, filter)
             .await
             .map_err(|e| {
                 tracing::error!(error = %e, "Failed to get clipboard entry projections");
@@ -82,6 +92,7 @@ pub async fn get_clipboard_entries(
                 is_favorited: dto.is_favorited,
                 updated_at: dto.updated_at,
                 active_time: dto.active_time,
+                sync_skip_reason: dto.sync_skip_reason,
             })
             .collect();
 
@@ -114,6 +125,127 @@ mod restore_tests {
     }
 }
 
+/// Full-text search over clipboard history
+/// 剪贴板历史全文检索
+///
+/// The search index lives in memory only and is rebuilt on first use after
+/// unlock, so nothing searchable is written to disk in plaintext.
+#[tauri::command]
+pub async fn search_clipboard_entries(
+    runtime: State<'_, Arc<AppRuntime>>,
+    request: ClipboardSearchRequest,
+    _trace: Option<TraceMetadata>,
+) -> Result<ClipboardSearchResponse, String> {
+    let span = info_span!(
+        "command.clipboard.search_entries",
+        trace_id = tracing::field::Empty,
+        trace_ts = tracing::field::Empty,
+        limit = request.limit.unwrap_or(50),
+        offset = request.offset.unwrap_or(0),
+    );
+    record_trace_fields(&span, &_trace);
+
+    async move {
+        let encryption_state = runtime
+            .deps
+            .encryption_state
+            .load_state()
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "Failed to check encryption state");
+                format!("Failed to check encryption state: {}", e)
+            })?;
+
+        let uc = runtime.usecases().search_clipboard_entries();
+        let session_ready = runtime.deps.encryption_session.is_ready().await;
+        if should_return_not_ready(encryption_state, session_ready) {
+            // Locked: drop any plaintext still held by the index
+            uc.close_index().await;
+            return Ok(ClipboardSearchResponse::NotReady);
+        }
+
+        let query = to_search_query(request)?;
+        let hits = uc.execute(&query).await.map_err(|e| {
+            tracing::error!(error = %e, "Failed to search clipboard entries");
+            e.to_string()
+        })?;
+
+        let hits: Vec<ClipboardSearchHit> = hits
+            .into_iter()
+            .map(|hit| ClipboardSearchHit {
+                id: hit.entry_id.to_string(),
+                snippet: hit
+                    .snippet
+                    .into_iter()
+                    .map(|segment| ClipboardSearchSnippetSegment {
+                        text: segment.text,
+                        highlighted: segment.highlighted,
+                    })
+                    .collect(),
+                content_type: hit.content_kind.as_str().to_string(),
+                source_device: hit.source_device,
+                captured_at: hit.created_at_ms,
+            })
+            .collect();
+
+        tracing::info!(count = hits.len(), "Searched clipboard entries");
+        Ok(ClipboardSearchResponse::Ready { hits })
+    }
+    .instrument(span)
+    .await
+}
+
+fn to_search_query(request: ClipboardSearchRequest) -> Result<ClipboardSearchQuery, String> {
+    let content_kinds = request
+        .content_types
+        .iter()
+        .map(|kind| kind.parse::<ContentKind>().map_err(|e| e.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ClipboardSearchQuery {
+        text: request.query,
+        content_kinds,
+        from_ms: request.from_ms,
+        to_ms: request.to_ms,
+        source_devices: request.source_devices,
+        limit: request.limit.unwrap_or(50),
+        offset: request.offset.unwrap_or(0),
+    })
+}
+
+#[cfg(test)]
+mod search_tests {
+    use super::to_search_query;
+    use crate::models::ClipboardSearchRequest;
+    use uc_core::clipboard::ContentKind;
+
+    #[test]
+    fn maps_request_to_query_with_defaults() {
+        let query = to_search_query(ClipboardSearchRequest {
+            query: "\"hello world\"".to_string(),
+            content_types: vec!["text".to_string(), "code_snippet".to_string()],
+            ..Default::default()
+        })
+        .expect("valid request");
+
+        assert_eq!(
+            query.content_kinds,
+            vec![ContentKind::Text, ContentKind::CodeSnippet]
+        );
+        assert_eq!(query.limit, 50);
+        assert_eq!(query.offset, 0);
+    }
+
+    #[test]
+    fn rejects_unknown_content_type() {
+        let result = to_search_query(ClipboardSearchRequest {
+            content_types: vec!["video".to_string()],
+            ..Default::default()
+        });
+        assert!(result.is_err());
+    }
+}
+
 /// Deletes a clipboard entry identified by `entry_id`.
 ///
 /// This command converts the provided `entry_id` to the domain `EntryId` type and invokes the runtime's
@@ -161,6 +293,9 @@ pub async fn delete_clipboard_entry(
             tracing::error!(error = %e, entry_id = %entry_id, "Failed to delete entry");
             e.to_string()
         })?;
+        if let Err(e) = runtime.deps.search_index.remove_entry(&parsed_id).await {
+            tracing::warn!(error = %e, entry_id = %entry_id, "Failed to remove entry from search index");
+        }
 
         tracing::info!(entry_id = %entry_id, "Deleted clipboard entry");
         Ok(())
@@ -169,6 +304,112 @@ pub async fn delete_clipboard_entry(
     .await
 }
 
+/// Pin or unpin a clipboard entry
+/// 置顶或取消置顶剪贴板条目
+///
+/// Pinned entries are skipped by the retention policy (when `skip_pinned` is
+/// enabled) and by `clear_clipboard_items`.
+#[tauri::command]
+pub async fn set_clipboard_entry_pinned(
+    runtime: State<'_, Arc<AppRuntime>>,
+    entry_id: String,
+    pinned: bool,
+    _trace: Option<TraceMetadata>,
+) -> Result<bool, String> {
+    let span = info_span!(
+        "command.clipboard.set_entry_pinned",
+        trace_id = tracing::field::Empty,
+        trace_ts = tracing::field::Empty,
+        entry_id = %entry_id,
+        pinned = pinned,
+    );
+    record_trace_fields(&span, &_trace);
+
+    async move {
+        let parsed_id = EntryId::from(entry_id.clone());
+        let action = if pinned {
+            ClipboardContentAction::Pin
+        } else {
+            ClipboardContentAction::Unpin
+        };
+        let use_case = runtime.usecases().pin_clipboard_entry();
+        let updated = use_case.execute(&parsed_id, action).await.map_err(|e| {
+            tracing::error!(error = %e, entry_id = %entry_id, "Failed to update pinned state");
+            e.to_string()
+        })?;
+
+        if !updated {
+            return Err(format!("Clipboard entry not found: {}", entry_id));
+        }
+
+        tracing::info!(entry_id = %entry_id, pinned, "Updated clipboard entry pinned state");
+        Ok(true)
+    }
+    .instrument(span)
+    .await
+}
+
+/// Clear clipboard history, keeping pinned entries
+/// 清空剪贴板历史（保留置顶条目）
+///
+/// Returns the number of entries removed.
+#[tauri::command]
+pub async fn clear_clipboard_items(
+    runtime: State<'_, Arc<AppRuntime>>,
+    _trace: Option<TraceMetadata>,
+) -> Result<usize, String> {
+    let span = info_span!(
+        "command.clipboard.clear_items",
+        trace_id = tracing::field::Empty,
+        trace_ts = tracing::field::Empty,
+    );
+    record_trace_fields(&span, &_trace);
+
+    async move {
+        let use_case = runtime.usecases().clear_clipboard_history();
+        let removed = use_case.execute().await.map_err(|e| {
+            tracing::error!(error = %e, "Failed to clear clipboard history");
+            e.to_string()
+        })?;
+
+        for entry_id in &removed {
+            if let Err(e) = runtime.deps.search_index.remove_entry(entry_id).await {
+                tracing::warn!(error = %e, entry_id = %entry_id, "Failed to remove entry from search index");
+            }
+        }
+
+        tracing::info!(count = removed.len(), "Cleared clipboard history");
+        Ok(removed.len())
+    }
+    .instrument(span)
+    .await
+}
+
+/// Reclaim blob storage no longer referenced by any clipboard entry
+/// 回收不再被任何剪贴板条目引用的 blob 存储
+#[tauri::command]
+pub async fn collect_blob_garbage(
+    runtime: State<'_, Arc<AppRuntime>>,
+    _trace: Option<TraceMetadata>,
+) -> Result<BlobGcReport, String> {
+    let span = info_span!(
+        "command.clipboard.collect_blob_garbage",
+        trace_id = tracing::field::Empty,
+        trace_ts = tracing::field::Empty,
+    );
+    record_trace_fields(&span, &_trace);
+
+    async move {
+        let use_case = runtime.usecases().collect_blob_garbage();
+        use_case.execute().await.map_err(|e| {
+            tracing::error!(error = %e, "Failed to collect blob garbage");
+            e.to_string()
+        })
+    }
+    .instrument(span)
+    .await
+}
+
 /// Get full clipboard entry detail
 /// 获取剪贴板条目完整详情
 #[tauri::command]
@@ -198,7 +439,7 @@ pub async fn get_clipboard_entry_detail(
             content: result.content,
             size_bytes: result.size_bytes,
             content_type: result.mime_type.unwrap_or_else(|| "unknown".to_string()),
-            is_favorited: false,
+            is_favorited: result.pinned,
             updated_at: result.created_at_ms,
             active_time: result.active_time_ms,
         };
@@ -349,6 +590,7 @@ mod tests {
         MasterKey, Passphrase,
     };
     use uc_core::security::state::{EncryptionState, EncryptionStateError};
+    use uc_core::testing::{InMemoryPairedDeviceRepository, TestNetwork};
     use uc_core::{Blob, BlobId, ContentHash, DeviceId};
     use uc_infra::clipboard::InMemoryClipboardChangeOrigin;
 
@@ -411,6 +653,58 @@ mod tests {
         async fn delete_entry(&self, _entry_id: &EntryId) -> anyhow::Result<()> {
             Ok(())
         }
+
+        async fn list_retention_candidates(
+            &self,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::RetentionCandidate>> {
+            Ok(Vec::new())
+        }
+
+        async fn soft_delete_entries(
+            &self,
+            _entry_ids: &[uc_core::ids::EntryId],
+            _deleted_at_ms: i64,
+        ) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_soft_deleted_entries(
+            &self,
+            _deleted_before_ms: i64,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_entry_source_device(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+        ) -> anyhow::Result<Option<uc_core::ids::DeviceId>> {
+            Ok(None)
+        }
+
+        async fn set_entry_pinned(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+            _pinned: bool,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn list_pinned_entries(
+            &self,
+            _limit: usize,
+            _offset: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn list_catch_up_candidates(
+            &self,
+            _since_ms: i64,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::CatchUpCandidate>> {
+            Ok(Vec::new())
+        }
     }
 
     #[async_trait]
@@ -477,6 +771,26 @@ mod tests {
         ) -> anyhow::Result<ProcessingUpdateOutcome> {
             Ok(ProcessingUpdateOutcome::NotFound)
         }
+
+        async fn count_inline_representations(&self) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_inline_representation_ids(
+            &self,
+            _after: Option<&uc_core::ids::RepresentationId>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<(uc_core::ids::EventId, uc_core::ids::RepresentationId)>> {
+            Ok(Vec::new())
+        }
+
+        async fn update_inline_data(
+            &self,
+            _representation_id: &uc_core::ids::RepresentationId,
+            _inline_data: &[u8],
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
     }
 
     impl SystemClipboardPort for MockSystemClipboard {
@@ -641,6 +955,38 @@ mod tests {
         async fn clear(&self) -> Result<(), EncryptionError> {
             Ok(())
         }
+
+        async fn get_current_key(
+            &self,
+        ) -> Result<
+            (
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            ),
+            uc_core::security::model::EncryptionError,
+        > {
+            Ok((0, self.get_master_key().await?))
+        }
+
+        async fn get_master_key_for_epoch(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+        ) -> Result<uc_core::security::model::MasterKey, uc_core::security::model::EncryptionError>
+        {
+            self.get_master_key().await
+        }
+
+        async fn set_epoch_keys(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+            master_key: uc_core::security::model::MasterKey,
+            _older: Vec<(
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            )>,
+        ) -> Result<(), uc_core::security::model::EncryptionError> {
+            self.set_master_key(master_key).await
+        }
     }
 
     #[async_trait]
@@ -734,180 +1080,156 @@ mod tests {
     }
 
     #[async_trait]
-    impl PairedDeviceRepositoryPort for NoopPort {
-        async fn get_by_peer_id(
-            &self,
-            _peer_id: &uc_core::PeerId,
-        ) -> Result<Option<uc_core::network::PairedDevice>, PairedDeviceRepositoryError> {
-            Ok(None)
+    impl uc_core::ports::NetworkControlPort for NoopPort {
+        async fn start_network(&self) -> anyhow::Result<()> {
+            Ok(())
         }
+    }
 
-        async fn list_all(
-            &self,
-        ) -> Result<Vec<uc_core::network::PairedDevice>, PairedDeviceRepositoryError> {
-            Ok(vec![])
+    #[async_trait]
+    impl uc_core::ports::SetupStatusPort for NoopPort {
+        async fn get_status(&self) -> anyhow::Result<uc_core::setup::SetupStatus> {
+            Ok(uc_core::setup::SetupStatus::default())
         }
 
-        async fn upsert(
-            &self,
-            _device: uc_core::network::PairedDevice,
-        ) -> Result<(), PairedDeviceRepositoryError> {
+        async fn set_status(&self, _status: &uc_core::setup::SetupStatus) -> anyhow::Result<()> {
             Ok(())
         }
+    }
 
-        async fn set_state(
-            &self,
-            _peer_id: &uc_core::PeerId,
-            _state: uc_core::network::PairingState,
-        ) -> Result<(), PairedDeviceRepositoryError> {
-            Ok(())
+    impl uc_core::ports::SecureStoragePort for NoopPort {
+        fn get(&self, _key: &str) -> Result<Option<Vec<u8>>, uc_core::ports::SecureStorageError> {
+            Ok(None)
         }
 
-        async fn update_last_seen(
-            &self,
-            _peer_id: &uc_core::PeerId,
-            _last_seen_at: chrono::DateTime<chrono::Utc>,
-        ) -> Result<(), PairedDeviceRepositoryError> {
+        fn set(&self, _key: &str, _value: &[u8]) -> Result<(), uc_core::ports::SecureStorageError> {
             Ok(())
         }
 
-        async fn delete(
-            &self,
-            _peer_id: &uc_core::PeerId,
-        ) -> Result<(), PairedDeviceRepositoryError> {
+        fn delete(&self, _key: &str) -> Result<(), uc_core::ports::SecureStorageError> {
             Ok(())
         }
     }
 
     #[async_trait]
-    impl NetworkPort for NoopPort {
-        async fn send_clipboard(
-            &self,
-            _peer_id: &str,
-            _encrypted_data: Vec<u8>,
-        ) -> anyhow::Result<()> {
-            Ok(())
+    impl BlobStorePort for NoopPort {
+        async fn put(&self, _blob_id: &BlobId, _data: &[u8]) -> anyhow::Result<std::path::PathBuf> {
+            Ok(std::path::PathBuf::from("/tmp/noop"))
         }
 
-        async fn broadcast_clipboard(&self, _encrypted_data: Vec<u8>) -> anyhow::Result<()> {
-            Ok(())
+        async fn get(&self, _blob_id: &BlobId) -> anyhow::Result<Vec<u8>> {
+            Ok(vec![])
         }
 
-        async fn subscribe_clipboard(
+        async fn put_stream(
             &self,
-        ) -> anyhow::Result<tokio::sync::mpsc::Receiver<uc_core::network::ClipboardMessage>>
-        {
-            let (_tx, rx) = mpsc::channel(1);
-            Ok(rx)
+            blob_id: &uc_core::BlobId,
+            reader: &mut (dyn tokio::io::AsyncRead + Send + Unpin),
+        ) -> anyhow::Result<std::path::PathBuf> {
+            let mut data = Vec::new();
+            tokio::io::AsyncReadExt::read_to_end(reader, &mut data).await?;
+            self.put(blob_id, &data).await
         }
 
-        async fn get_discovered_peers(
+        async fn open_read(
             &self,
-        ) -> anyhow::Result<Vec<uc_core::network::DiscoveredPeer>> {
-            Ok(vec![])
+            blob_id: &uc_core::BlobId,
+        ) -> anyhow::Result<uc_core::ports::BlobReader> {
+            Ok(Box::new(std::io::Cursor::new(self.get(blob_id).await?)))
         }
 
-        async fn get_connected_peers(
-            &self,
-        ) -> anyhow::Result<Vec<uc_core::network::ConnectedPeer>> {
-            Ok(vec![])
+        async fn delete(&self, _blob_id: &uc_core::BlobId) -> anyhow::Result<u64> {
+            Ok(0)
         }
 
-        fn local_peer_id(&self) -> String {
-            "noop".to_string()
+        async fn list_objects(&self) -> anyhow::Result<Vec<uc_core::ports::StoredBlobObject>> {
+            Ok(Vec::new())
         }
+    }
 
-        async fn announce_device_name(&self, _device_name: String) -> anyhow::Result<()> {
+    #[async_trait]
+    impl BlobRepositoryPort for NoopPort {
+        async fn insert_blob(&self, _blob: &Blob) -> anyhow::Result<()> {
             Ok(())
         }
 
-        async fn open_pairing_session(
-            &self,
-            _peer_id: String,
-            _session_id: String,
-        ) -> anyhow::Result<()> {
-            Ok(())
+        async fn find_by_hash(&self, _content_hash: &ContentHash) -> anyhow::Result<Option<Blob>> {
+            Ok(None)
         }
 
-        async fn send_pairing_on_session(
-            &self,
-            _session_id: String,
-            _message: uc_core::network::PairingMessage,
-        ) -> anyhow::Result<()> {
-            Ok(())
+        async fn count_blobs(&self) -> anyhow::Result<usize> {
+            Ok(0)
         }
 
-        async fn close_pairing_session(
+        async fn list_blob_ids(
             &self,
-            _session_id: String,
-            _reason: Option<String>,
-        ) -> anyhow::Result<()> {
-            Ok(())
+            _after: Option<&uc_core::BlobId>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<uc_core::BlobId>> {
+            Ok(vec![])
         }
 
-        async fn unpair_device(&self, _peer_id: String) -> anyhow::Result<()> {
-            Ok(())
+        async fn list_unreferenced_blobs(
+            &self,
+            _created_before_ms: i64,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<uc_core::Blob>> {
+            Ok(vec![])
         }
 
-        async fn subscribe_events(
+        async fn touch_blob(
             &self,
-        ) -> anyhow::Result<tokio::sync::mpsc::Receiver<uc_core::network::NetworkEvent>> {
-            let (_tx, rx) = mpsc::channel(1);
-            Ok(rx)
+            _blob_id: &uc_core::BlobId,
+            _now_ms: i64,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
         }
-    }
 
-    #[async_trait]
-    impl uc_core::ports::NetworkControlPort for NoopPort {
-        async fn start_network(&self) -> anyhow::Result<()> {
-            Ok(())
+        async fn delete_unreferenced_blob(
+            &self,
+            _blob_id: &uc_core::BlobId,
+            _created_before_ms: i64,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
         }
     }
 
     #[async_trait]
-    impl uc_core::ports::SetupStatusPort for NoopPort {
-        async fn get_status(&self) -> anyhow::Result<uc_core::setup::SetupStatus> {
-            Ok(uc_core::setup::SetupStatus::default())
-        }
-
-        async fn set_status(&self, _status: &uc_core::setup::SetupStatus) -> anyhow::Result<()> {
-            Ok(())
-        }
-    }
-
-    impl uc_core::ports::SecureStoragePort for NoopPort {
-        fn get(&self, _key: &str) -> Result<Option<Vec<u8>>, uc_core::ports::SecureStorageError> {
-            Ok(None)
-        }
-
-        fn set(&self, _key: &str, _value: &[u8]) -> Result<(), uc_core::ports::SecureStorageError> {
-            Ok(())
+    impl uc_core::ports::PayloadReencryptionPort for NoopPort {
+        async fn count_inline_payloads(&self) -> anyhow::Result<usize> {
+            Ok(0)
         }
 
-        fn delete(&self, _key: &str) -> Result<(), uc_core::ports::SecureStorageError> {
-            Ok(())
+        async fn list_inline_payloads(
+            &self,
+            _after: Option<&uc_core::ids::RepresentationId>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<(uc_core::ids::EventId, uc_core::ids::RepresentationId)>> {
+            Ok(vec![])
         }
-    }
 
-    #[async_trait]
-    impl BlobStorePort for NoopPort {
-        async fn put(&self, _blob_id: &BlobId, _data: &[u8]) -> anyhow::Result<std::path::PathBuf> {
-            Ok(std::path::PathBuf::from("/tmp/noop"))
+        async fn count_blob_payloads(&self) -> anyhow::Result<usize> {
+            Ok(0)
         }
 
-        async fn get(&self, _blob_id: &BlobId) -> anyhow::Result<Vec<u8>> {
+        async fn list_blob_payloads(
+            &self,
+            _after: Option<&BlobId>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<BlobId>> {
             Ok(vec![])
         }
-    }
 
-    #[async_trait]
-    impl BlobRepositoryPort for NoopPort {
-        async fn insert_blob(&self, _blob: &Blob) -> anyhow::Result<()> {
-            Ok(())
+        async fn reencrypt_inline(
+            &self,
+            _event_id: &uc_core::ids::EventId,
+            _representation_id: &uc_core::ids::RepresentationId,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
         }
 
-        async fn find_by_hash(&self, _content_hash: &ContentHash) -> anyhow::Result<Option<Blob>> {
-            Ok(None)
+        async fn reencrypt_blob(&self, _blob_id: &BlobId) -> anyhow::Result<bool> {
+            Ok(false)
         }
     }
 
@@ -922,6 +1244,25 @@ mod tests {
         }
     }
 
+    #[async_trait]
+    impl OutboundSyncQueuePort for NoopPort {
+        async fn enqueue(&self, _message: QueuedClipboardMessage) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn list_pending(&self) -> anyhow::Result<Vec<QueuedClipboardMessage>> {
+            Ok(vec![])
+        }
+
+        async fn remove(&self, _message_ids: &[String]) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn retain_latest(&self, _keep: usize) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+    }
+
     #[async_trait]
     impl ThumbnailRepositoryPort for NoopPort {
         async fn get_by_representation_id(
@@ -937,6 +1278,10 @@ mod tests {
         ) -> anyhow::Result<()> {
             Ok(())
         }
+
+        async fn delete_orphaned_thumbnails(&self) -> anyhow::Result<usize> {
+            Ok(0)
+        }
     }
 
     #[async_trait]
@@ -993,6 +1338,40 @@ mod tests {
         }
     }
 
+    #[async_trait]
+    impl ClipboardPayloadResolverPort for NoopPort {
+        async fn resolve(
+            &self,
+            _representation: &uc_core::clipboard::PersistedClipboardRepresentation,
+        ) -> anyhow::Result<ResolvedClipboardPayload> {
+            Err(anyhow::anyhow!("noop payload resolver"))
+        }
+    }
+
+    #[async_trait]
+    impl RemotePayloadRepositoryPort for NoopPort {
+        async fn insert_remote_payload(
+            &self,
+            _remote: &uc_core::clipboard::RemotePayloadRef,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn get_by_representation_id(
+            &self,
+            _representation_id: &RepresentationId,
+        ) -> anyhow::Result<Option<uc_core::clipboard::RemotePayloadRef>> {
+            Ok(None)
+        }
+
+        async fn delete_remote_payload(
+            &self,
+            _representation_id: &RepresentationId,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
     #[tokio::test]
     async fn restore_entry_returns_error_before_clipboard_write_when_touch_fails() {
         let calls = Arc::new(Mutex::new(Vec::new()));
@@ -1039,22 +1418,29 @@ mod tests {
                 selection: Some(selection),
             }),
             representation_policy: Arc::new(NoopPort),
+            sync_filter_policy: Arc::new(uc_core::clipboard::SyncFilterPolicyV1::new()),
             representation_cache: Arc::new(NoopPort),
             spool_queue: Arc::new(NoopPort),
             clipboard_change_origin: Arc::new(InMemoryClipboardChangeOrigin::new()),
+            search_index: Arc::new(uc_infra::clipboard::InMemoryClipboardSearchIndex::new()),
             worker_tx,
+            payload_resolver: Arc::new(NoopPort),
+            remote_payload_repo: Arc::new(NoopPort),
             encryption: Arc::new(NoopPort),
             encryption_session: Arc::new(NoopPort),
             encryption_state: Arc::new(NoopPort),
             key_scope: Arc::new(NoopPort),
             secure_storage: Arc::new(NoopPort),
             key_material: Arc::new(NoopPort),
+            payload_reencryption: Arc::new(NoopPort),
             watcher_control: Arc::new(NoopPort),
             device_repo: Arc::new(NoopPort),
             device_identity: Arc::new(MockDeviceIdentity),
-            paired_device_repo: Arc::new(NoopPort),
-            network: Arc::new(NoopPort),
+            paired_device_repo: Arc::new(InMemoryPairedDeviceRepository::new()),
+            active_space: Arc::new(uc_infra::space::InMemoryActiveSpace::default()),
+            network: Arc::new(TestNetwork::new()),
             network_control: Arc::new(NoopPort),
+            outbound_sync_queue: Arc::new(NoopPort),
             setup_status: Arc::new(NoopPort),
             blob_store: Arc::new(NoopPort),
             blob_repository: Arc::new(NoopPort),
@@ -173,12 +173,179 @@ pub async fn unlock_encryption_session(
     unlock_encryption_session_with_runtime(runtime.inner(), &app_handle, _trace).await
 }
 
+/// Change the encryption passphrase
+/// 修改加密口令
+///
+/// Rewraps the master key under the new passphrase and pushes the new keyslot
+/// to connected trusted peers. Returns the number of peers that were updated.
+/// 使用新口令重新封装主密钥，并推送给已连接的受信任设备。返回已更新的设备数量。
+#[tauri::command]
+pub async fn change_passphrase(
+    runtime: State<'_, Arc<AppRuntime>>,
+    old_passphrase: String,
+    new_passphrase: String,
+    _trace: Option<TraceMetadata>,
+) -> Result<usize, String> {
+    let span = info_span!(
+        "command.encryption.change_passphrase",
+        trace_id = tracing::field::Empty,
+        trace_ts = tracing::field::Empty,
+        device_id = %runtime.deps.device_identity.current_device_id(),
+    );
+    record_trace_fields(&span, &_trace);
+
+    let uc = runtime.usecases().change_passphrase();
+    let propagated = uc
+        .execute(
+            uc_core::security::model::Passphrase(old_passphrase),
+            uc_core::security::model::Passphrase(new_passphrase),
+        )
+        .instrument(span)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to change passphrase");
+            e.to_string()
+        })?;
+
+    info!(propagated, "Passphrase changed");
+    Ok(propagated)
+}
+
+/// Get the keyslot update waiting for confirmation
+/// 获取等待确认的密钥槽更新
+///
+/// A trusted peer changed the passphrase or rotated the master key. The update is
+/// applied only after the user confirms it with `confirm_keyslot_update`.
+/// 受信任设备修改了口令或轮换了主密钥，需用户通过 `confirm_keyslot_update` 确认后才会应用。
+#[tauri::command]
+pub async fn get_pending_keyslot_update(
+    runtime: State<'_, Arc<AppRuntime>>,
+    _trace: Option<TraceMetadata>,
+) -> Result<Option<uc_app::usecases::PendingKeySlotUpdate>, String> {
+    let span = info_span!(
+        "command.encryption.get_pending_keyslot_update",
+        trace_id = tracing::field::Empty,
+        trace_ts = tracing::field::Empty,
+    );
+    record_trace_fields(&span, &_trace);
+    Ok(span.in_scope(|| runtime.pending_keyslot_updates().current()))
+}
+
+/// Confirm the pending keyslot update
+/// 确认待应用的密钥槽更新
+///
+/// Unlocks the peer's keyslot with the passphrase entered on this device and
+/// replaces the local keyslot and keyring KEK. Returns the resulting key epoch.
+/// 使用本机输入的口令解开对端密钥槽，并替换本地密钥槽与钥匙串中的 KEK。返回密钥纪元。
+#[tauri::command]
+pub async fn confirm_keyslot_update(
+    runtime: State<'_, Arc<AppRuntime>>,
+    passphrase: String,
+    _trace: Option<TraceMetadata>,
+) -> Result<u32, String> {
+    let span = info_span!(
+        "command.encryption.confirm_keyslot_update",
+        trace_id = tracing::field::Empty,
+        trace_ts = tracing::field::Empty,
+        device_id = %runtime.deps.device_identity.current_device_id(),
+    );
+    record_trace_fields(&span, &_trace);
+
+    let uc = runtime.usecases().confirm_keyslot_update();
+    let epoch = uc
+        .execute(uc_core::security::model::Passphrase(passphrase))
+        .instrument(span)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to confirm keyslot update");
+            e.to_string()
+        })?;
+
+    info!(epoch, "Keyslot update confirmed");
+    Ok(epoch)
+}
+
+/// Dismiss the pending keyslot update
+/// 忽略待应用的密钥槽更新
+#[tauri::command]
+pub async fn dismiss_keyslot_update(
+    runtime: State<'_, Arc<AppRuntime>>,
+    _trace: Option<TraceMetadata>,
+) -> Result<(), String> {
+    let span = info_span!(
+        "command.encryption.dismiss_keyslot_update",
+        trace_id = tracing::field::Empty,
+        trace_ts = tracing::field::Empty,
+    );
+    record_trace_fields(&span, &_trace);
+    span.in_scope(|| {
+        runtime.pending_keyslot_updates().dismiss();
+        info!("Keyslot update dismissed");
+    });
+    Ok(())
+}
+
+/// Rotate the master key
+/// 轮换主密钥
+///
+/// Generates a new master key for the next key epoch and pushes it to connected
+/// trusted peers. Stored history is re-encrypted in the background; progress is
+/// reported on `encryption://event`. Returns the new key epoch.
+/// 为下一个密钥纪元生成新主密钥并推送给受信任设备。历史数据在后台重新加密，进度通过 `encryption://event` 上报。返回新的密钥纪元。
+#[tauri::command]
+pub async fn rotate_master_key(
+    runtime: State<'_, Arc<AppRuntime>>,
+    _trace: Option<TraceMetadata>,
+) -> Result<u32, String> {
+    let span = info_span!(
+        "command.encryption.rotate_master_key",
+        trace_id = tracing::field::Empty,
+        trace_ts = tracing::field::Empty,
+        device_id = %runtime.deps.device_identity.current_device_id(),
+    );
+    record_trace_fields(&span, &_trace);
+
+    let uc = runtime.usecases().rotate_master_key();
+    let epoch = uc.execute().instrument(span).await.map_err(|e| {
+        tracing::error!(error = %e, "Failed to rotate master key");
+        e.to_string()
+    })?;
+
+    info!(epoch, "Master key rotated");
+    Ok(epoch)
+}
+
+/// Get the key rotation status
+/// 获取密钥轮换状态
+#[tauri::command]
+pub async fn get_key_rotation_status(
+    runtime: State<'_, Arc<AppRuntime>>,
+    _trace: Option<TraceMetadata>,
+) -> Result<uc_app::usecases::KeyRotationStatus, String> {
+    let span = info_span!(
+        "command.encryption.get_key_rotation_status",
+        trace_id = tracing::field::Empty,
+        trace_ts = tracing::field::Empty,
+    );
+    record_trace_fields(&span, &_trace);
+
+    runtime
+        .usecases()
+        .reencrypt_history()
+        .status()
+        .instrument(span)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to load key rotation status");
+            e.to_string()
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::{emit_session_ready, unlock_encryption_session_with_runtime};
     use crate::bootstrap::AppRuntime;
     use async_trait::async_trait;
-    use chrono::{DateTime, Utc};
     use serde_json::Value;
     use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Arc;
@@ -192,11 +359,10 @@ mod tests {
         SystemClipboardSnapshot,
     };
     use uc_core::ids::{FormatId, RepresentationId};
-    use uc_core::network::{PairedDevice, PairingState};
     use uc_core::ports::clipboard::{
         ProcessingUpdateOutcome, RepresentationCachePort, SpoolQueuePort, SpoolRequest,
     };
-    use uc_core::ports::errors::{DeviceRepositoryError, PairedDeviceRepositoryError};
+    use uc_core::ports::errors::DeviceRepositoryError;
     use uc_core::ports::security::encryption_state::EncryptionStatePort;
     use uc_core::ports::security::key_scope::KeyScopePort;
     use uc_core::ports::watcher_control::WatcherControlError;
@@ -206,7 +372,8 @@ mod tests {
         KeySlot, KeySlotVersion, MasterKey, Passphrase, WrappedMasterKey,
     };
     use uc_core::security::state::{EncryptionState, EncryptionStateError};
-    use uc_core::{Blob, BlobId, ClipboardChangeOrigin, ContentHash, DeviceId, PeerId};
+    use uc_core::testing::{InMemoryPairedDeviceRepository, TestNetwork};
+    use uc_core::{Blob, BlobId, ClipboardChangeOrigin, ContentHash, DeviceId};
     use uc_infra::clipboard::InMemoryClipboardChangeOrigin;
     #[tokio::test]
     async fn emit_session_ready_emits_event() {
@@ -315,6 +482,58 @@ mod tests {
         async fn delete_entry(&self, _entry_id: &uc_core::ids::EntryId) -> anyhow::Result<()> {
             Ok(())
         }
+
+        async fn list_retention_candidates(
+            &self,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::RetentionCandidate>> {
+            Ok(Vec::new())
+        }
+
+        async fn soft_delete_entries(
+            &self,
+            _entry_ids: &[uc_core::ids::EntryId],
+            _deleted_at_ms: i64,
+        ) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_soft_deleted_entries(
+            &self,
+            _deleted_before_ms: i64,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_entry_source_device(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+        ) -> anyhow::Result<Option<uc_core::ids::DeviceId>> {
+            Ok(None)
+        }
+
+        async fn set_entry_pinned(
+            &self,
+            _entry_id: &uc_core::ids::EntryId,
+            _pinned: bool,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn list_pinned_entries(
+            &self,
+            _limit: usize,
+            _offset: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::ClipboardEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn list_catch_up_candidates(
+            &self,
+            _since_ms: i64,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<uc_core::clipboard::CatchUpCandidate>> {
+            Ok(Vec::new())
+        }
     }
 
     #[async_trait]
@@ -385,6 +604,26 @@ mod tests {
         ) -> anyhow::Result<ProcessingUpdateOutcome> {
             Ok(ProcessingUpdateOutcome::NotFound)
         }
+
+        async fn count_inline_representations(&self) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn list_inline_representation_ids(
+            &self,
+            _after: Option<&uc_core::ids::RepresentationId>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<(uc_core::ids::EventId, uc_core::ids::RepresentationId)>> {
+            Ok(Vec::new())
+        }
+
+        async fn update_inline_data(
+            &self,
+            _representation_id: &uc_core::ids::RepresentationId,
+            _inline_data: &[u8],
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
     }
 
     #[async_trait]
@@ -465,6 +704,7 @@ mod tests {
                 nonce: vec![0u8; 24],
                 ciphertext: vec![0u8; 1],
                 aad_fingerprint: None,
+                key_epoch: 0,
             })
         }
 
@@ -489,6 +729,7 @@ mod tests {
                 nonce: vec![0u8; 24],
                 ciphertext: vec![0u8; 1],
                 aad_fingerprint: None,
+                key_epoch: 0,
             })
         }
 
@@ -519,6 +760,38 @@ mod tests {
         async fn clear(&self) -> Result<(), EncryptionError> {
             Ok(())
         }
+
+        async fn get_current_key(
+            &self,
+        ) -> Result<
+            (
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            ),
+            uc_core::security::model::EncryptionError,
+        > {
+            Ok((0, self.get_master_key().await?))
+        }
+
+        async fn get_master_key_for_epoch(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+        ) -> Result<uc_core::security::model::MasterKey, uc_core::security::model::EncryptionError>
+        {
+            self.get_master_key().await
+        }
+
+        async fn set_epoch_keys(
+            &self,
+            _epoch: uc_core::security::model::KeyEpoch,
+            master_key: uc_core::security::model::MasterKey,
+            _older: Vec<(
+                uc_core::security::model::KeyEpoch,
+                uc_core::security::model::MasterKey,
+            )>,
+        ) -> Result<(), uc_core::security::model::EncryptionError> {
+            self.set_master_key(master_key).await
+        }
     }
 
     #[async_trait]
@@ -567,6 +840,7 @@ mod tests {
                     nonce: vec![0u8; 24],
                     ciphertext: vec![0u8; 1],
                     aad_fingerprint: None,
+                    key_epoch: 0,
                 },
             };
             Ok(KeySlot {
@@ -574,6 +848,10 @@ mod tests {
                 scope: scope.clone(),
                 kdf,
                 salt: vec![0u8; salt_len],
+                epoch: 0,
+                revision: 0,
+                retired_master_key: None,
+                retained_master_keys: Vec::new(),
                 wrapped_master_key: Some(wrapped),
             })
         }
@@ -630,172 +908,157 @@ mod tests {
     }
 
     #[async_trait]
-    impl PairedDeviceRepositoryPort for NoopPort {
-        async fn get_by_peer_id(
-            &self,
-            _peer_id: &PeerId,
-        ) -> Result<Option<PairedDevice>, PairedDeviceRepositoryError> {
-            Ok(None)
+    impl NetworkControlPort for RecordingNetworkControl {
+        async fn start_network(&self) -> anyhow::Result<()> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(())
         }
+    }
 
-        async fn list_all(&self) -> Result<Vec<PairedDevice>, PairedDeviceRepositoryError> {
-            Ok(vec![])
+    #[async_trait]
+    impl uc_core::ports::SetupStatusPort for NoopPort {
+        async fn get_status(&self) -> anyhow::Result<uc_core::setup::SetupStatus> {
+            Ok(uc_core::setup::SetupStatus::default())
         }
 
-        async fn upsert(&self, _device: PairedDevice) -> Result<(), PairedDeviceRepositoryError> {
+        async fn set_status(&self, _status: &uc_core::setup::SetupStatus) -> anyhow::Result<()> {
             Ok(())
         }
+    }
 
-        async fn set_state(
-            &self,
-            _peer_id: &PeerId,
-            _state: PairingState,
-        ) -> Result<(), PairedDeviceRepositoryError> {
-            Ok(())
+    impl SecureStoragePort for NoopPort {
+        fn get(&self, _key: &str) -> Result<Option<Vec<u8>>, uc_core::ports::SecureStorageError> {
+            Ok(None)
         }
 
-        async fn update_last_seen(
-            &self,
-            _peer_id: &PeerId,
-            _last_seen_at: DateTime<Utc>,
-        ) -> Result<(), PairedDeviceRepositoryError> {
+        fn set(&self, _key: &str, _value: &[u8]) -> Result<(), uc_core::ports::SecureStorageError> {
             Ok(())
         }
 
-        async fn delete(&self, _peer_id: &PeerId) -> Result<(), PairedDeviceRepositoryError> {
+        fn delete(&self, _key: &str) -> Result<(), uc_core::ports::SecureStorageError> {
             Ok(())
         }
     }
 
     #[async_trait]
-    impl NetworkPort for NoopPort {
-        async fn send_clipboard(
-            &self,
-            _peer_id: &str,
-            _encrypted_data: Vec<u8>,
-        ) -> anyhow::Result<()> {
-            Ok(())
+    impl BlobStorePort for NoopPort {
+        async fn put(&self, _blob_id: &BlobId, _data: &[u8]) -> anyhow::Result<std::path::PathBuf> {
+            Ok(std::path::PathBuf::from("/tmp/noop"))
         }
 
-        async fn broadcast_clipboard(&self, _encrypted_data: Vec<u8>) -> anyhow::Result<()> {
-            Ok(())
+        async fn get(&self, _blob_id: &BlobId) -> anyhow::Result<Vec<u8>> {
+            Ok(vec![])
         }
 
-        async fn subscribe_clipboard(
+        async fn put_stream(
             &self,
-        ) -> anyhow::Result<mpsc::Receiver<uc_core::network::ClipboardMessage>> {
-            let (_tx, rx) = mpsc::channel(1);
-            Ok(rx)
+            blob_id: &uc_core::BlobId,
+            reader: &mut (dyn tokio::io::AsyncRead + Send + Unpin),
+        ) -> anyhow::Result<std::path::PathBuf> {
+            let mut data = Vec::new();
+            tokio::io::AsyncReadExt::read_to_end(reader, &mut data).await?;
+            self.put(blob_id, &data).await
         }
 
-        async fn get_discovered_peers(
+        async fn open_read(
             &self,
-        ) -> anyhow::Result<Vec<uc_core::network::DiscoveredPeer>> {
-            Ok(vec![])
+            blob_id: &uc_core::BlobId,
+        ) -> anyhow::Result<uc_core::ports::BlobReader> {
+            Ok(Box::new(std::io::Cursor::new(self.get(blob_id).await?)))
         }
 
-        async fn get_connected_peers(
-            &self,
-        ) -> anyhow::Result<Vec<uc_core::network::ConnectedPeer>> {
-            Ok(vec![])
+        async fn delete(&self, _blob_id: &uc_core::BlobId) -> anyhow::Result<u64> {
+            Ok(0)
         }
 
-        fn local_peer_id(&self) -> String {
-            "noop".to_string()
+        async fn list_objects(&self) -> anyhow::Result<Vec<uc_core::ports::StoredBlobObject>> {
+            Ok(Vec::new())
         }
+    }
 
-        async fn announce_device_name(&self, _device_name: String) -> anyhow::Result<()> {
+    #[async_trait]
+    impl BlobRepositoryPort for NoopPort {
+        async fn insert_blob(&self, _blob: &Blob) -> anyhow::Result<()> {
             Ok(())
         }
 
-        async fn open_pairing_session(
-            &self,
-            _peer_id: String,
-            _session_id: String,
-        ) -> anyhow::Result<()> {
-            Ok(())
+        async fn find_by_hash(&self, _content_hash: &ContentHash) -> anyhow::Result<Option<Blob>> {
+            Ok(None)
         }
 
-        async fn send_pairing_on_session(
-            &self,
-            _session_id: String,
-            _message: uc_core::network::PairingMessage,
-        ) -> anyhow::Result<()> {
-            Ok(())
+        async fn count_blobs(&self) -> anyhow::Result<usize> {
+            Ok(0)
         }
 
-        async fn close_pairing_session(
+        async fn list_blob_ids(
             &self,
-            _session_id: String,
-            _reason: Option<String>,
-        ) -> anyhow::Result<()> {
-            Ok(())
+            _after: Option<&uc_core::BlobId>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<uc_core::BlobId>> {
+            Ok(vec![])
         }
 
-        async fn unpair_device(&self, _peer_id: String) -> anyhow::Result<()> {
-            Ok(())
+        async fn list_unreferenced_blobs(
+            &self,
+            _created_before_ms: i64,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<uc_core::Blob>> {
+            Ok(vec![])
         }
 
-        async fn subscribe_events(
+        async fn touch_blob(
             &self,
-        ) -> anyhow::Result<mpsc::Receiver<uc_core::network::NetworkEvent>> {
-            let (_tx, rx) = mpsc::channel(1);
-            Ok(rx)
+            _blob_id: &uc_core::BlobId,
+            _now_ms: i64,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
         }
-    }
 
-    #[async_trait]
-    impl NetworkControlPort for RecordingNetworkControl {
-        async fn start_network(&self) -> anyhow::Result<()> {
-            self.calls.fetch_add(1, Ordering::SeqCst);
-            Ok(())
+        async fn delete_unreferenced_blob(
+            &self,
+            _blob_id: &uc_core::BlobId,
+            _created_before_ms: i64,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
         }
     }
 
     #[async_trait]
-    impl uc_core::ports::SetupStatusPort for NoopPort {
-        async fn get_status(&self) -> anyhow::Result<uc_core::setup::SetupStatus> {
-            Ok(uc_core::setup::SetupStatus::default())
+    impl uc_core::ports::PayloadReencryptionPort for NoopPort {
+        async fn count_inline_payloads(&self) -> anyhow::Result<usize> {
+            Ok(0)
         }
 
-        async fn set_status(&self, _status: &uc_core::setup::SetupStatus) -> anyhow::Result<()> {
-            Ok(())
-        }
-    }
-
-    impl SecureStoragePort for NoopPort {
-        fn get(&self, _key: &str) -> Result<Option<Vec<u8>>, uc_core::ports::SecureStorageError> {
-            Ok(None)
-        }
-
-        fn set(&self, _key: &str, _value: &[u8]) -> Result<(), uc_core::ports::SecureStorageError> {
-            Ok(())
-        }
-
-        fn delete(&self, _key: &str) -> Result<(), uc_core::ports::SecureStorageError> {
-            Ok(())
+        async fn list_inline_payloads(
+            &self,
+            _after: Option<&uc_core::ids::RepresentationId>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<(uc_core::ids::EventId, uc_core::ids::RepresentationId)>> {
+            Ok(vec![])
         }
-    }
 
-    #[async_trait]
-    impl BlobStorePort for NoopPort {
-        async fn put(&self, _blob_id: &BlobId, _data: &[u8]) -> anyhow::Result<std::path::PathBuf> {
-            Ok(std::path::PathBuf::from("/tmp/noop"))
+        async fn count_blob_payloads(&self) -> anyhow::Result<usize> {
+            Ok(0)
         }
 
-        async fn get(&self, _blob_id: &BlobId) -> anyhow::Result<Vec<u8>> {
+        async fn list_blob_payloads(
+            &self,
+            _after: Option<&BlobId>,
+            _limit: usize,
+        ) -> anyhow::Result<Vec<BlobId>> {
             Ok(vec![])
         }
-    }
 
-    #[async_trait]
-    impl BlobRepositoryPort for NoopPort {
-        async fn insert_blob(&self, _blob: &Blob) -> anyhow::Result<()> {
-            Ok(())
+        async fn reencrypt_inline(
+            &self,
+            _event_id: &uc_core::ids::EventId,
+            _representation_id: &uc_core::ids::RepresentationId,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
         }
 
-        async fn find_by_hash(&self, _content_hash: &ContentHash) -> anyhow::Result<Option<Blob>> {
-            Ok(None)
+        async fn reencrypt_blob(&self, _blob_id: &BlobId) -> anyhow::Result<bool> {
+            Ok(false)
         }
     }
 
@@ -810,6 +1073,25 @@ mod tests {
         }
     }
 
+    #[async_trait]
+    impl OutboundSyncQueuePort for NoopPort {
+        async fn enqueue(&self, _message: QueuedClipboardMessage) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn list_pending(&self) -> anyhow::Result<Vec<QueuedClipboardMessage>> {
+            Ok(vec![])
+        }
+
+        async fn remove(&self, _message_ids: &[String]) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+
+        async fn retain_latest(&self, _keep: usize) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+    }
+
     #[async_trait]
     impl ThumbnailRepositoryPort for NoopPort {
         async fn get_by_representation_id(
@@ -822,6 +1104,10 @@ mod tests {
         async fn insert_thumbnail(&self, _metadata: &ThumbnailMetadata) -> anyhow::Result<()> {
             Ok(())
         }
+
+        async fn delete_orphaned_thumbnails(&self) -> anyhow::Result<usize> {
+            Ok(0)
+        }
     }
 
     #[async_trait]
@@ -883,6 +1169,40 @@ mod tests {
         }
     }
 
+    #[async_trait]
+    impl ClipboardPayloadResolverPort for NoopPort {
+        async fn resolve(
+            &self,
+            _representation: &uc_core::clipboard::PersistedClipboardRepresentation,
+        ) -> anyhow::Result<ResolvedClipboardPayload> {
+            Err(anyhow::anyhow!("noop payload resolver"))
+        }
+    }
+
+    #[async_trait]
+    impl RemotePayloadRepositoryPort for NoopPort {
+        async fn insert_remote_payload(
+            &self,
+            _remote: &uc_core::clipboard::RemotePayloadRef,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn get_by_representation_id(
+            &self,
+            _representation_id: &RepresentationId,
+        ) -> anyhow::Result<Option<uc_core::clipboard::RemotePayloadRef>> {
+            Ok(None)
+        }
+
+        async fn delete_remote_payload(
+            &self,
+            _representation_id: &RepresentationId,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
     #[tokio::test]
     async fn unlock_success_triggers_network_start() {
         let start_calls = Arc::new(AtomicUsize::new(0));
@@ -904,22 +1224,29 @@ mod tests {
             representation_normalizer: Arc::new(NoopPort),
             selection_repo: Arc::new(NoopPort),
             representation_policy: Arc::new(NoopPort),
+            sync_filter_policy: Arc::new(uc_core::clipboard::SyncFilterPolicyV1::new()),
             representation_cache: Arc::new(NoopPort),
             spool_queue: Arc::new(NoopPort),
             clipboard_change_origin: origin_port,
+            search_index: Arc::new(uc_infra::clipboard::InMemoryClipboardSearchIndex::new()),
             worker_tx,
+            payload_resolver: Arc::new(NoopPort),
+            remote_payload_repo: Arc::new(NoopPort),
             encryption: Arc::new(MockEncryption),
             encryption_session: Arc::new(MockEncryptionSession),
             encryption_state: Arc::new(MockEncryptionState),
             key_scope: Arc::new(MockKeyScope),
             secure_storage: Arc::new(NoopPort),
             key_material: Arc::new(MockKeyMaterial),
+            payload_reencryption: Arc::new(NoopPort),
             watcher_control: Arc::new(NoopPort),
             device_repo: Arc::new(NoopPort),
             device_identity: Arc::new(MockDeviceIdentity),
-            paired_device_repo: Arc::new(NoopPort),
-            network: Arc::new(NoopPort),
+            paired_device_repo: Arc::new(InMemoryPairedDeviceRepository::new()),
+            active_space: Arc::new(uc_infra::space::InMemoryActiveSpace::default()),
+            network: Arc::new(TestNetwork::new()),
             network_control: Arc::new(RecordingNetworkControl::new(start_calls.clone())),
+            outbound_sync_queue: Arc::new(NoopPort),
             setup_status: Arc::new(NoopPort),
             blob_store: Arc::new(NoopPort),
             blob_repository: Arc::new(NoopPort),
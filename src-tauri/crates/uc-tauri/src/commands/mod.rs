@@ -6,6 +6,7 @@ pub mod lifecycle;
 pub mod pairing;
 pub mod settings;
 pub mod setup;
+pub mod spaces;
 pub mod startup;
 
 use tracing::Span;
@@ -19,6 +20,7 @@ pub use lifecycle::*;
 pub use pairing::*;
 pub use settings::*;
 pub use setup::*;
+pub use spaces::*;
 pub use startup::*;
 
 pub use error::map_err;
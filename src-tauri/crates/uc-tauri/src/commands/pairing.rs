@@ -9,7 +9,10 @@ use std::sync::Arc;
 use tauri::{Emitter, State};
 use tracing::{info_span, Instrument};
 use uc_app::usecases::{LocalDeviceInfo, PairingOrchestrator};
-use uc_core::network::{ConnectedPeer, DiscoveredPeer, PairedDevice, PairingState};
+use uc_core::network::{
+    ConnectedPeer, DeviceSyncPolicy, DiscoveredPeer, LocalNetworkInfo, PairedDevice, PairingState,
+    PeerCapabilities, PeerLiveness, PeerPresence,
+};
 use uc_core::ports::observability::TraceMetadata;
 use uc_core::PeerId;
 
@@ -33,6 +36,16 @@ pub struct PairedPeer {
     pub last_seen: Option<String>,
     pub last_known_addresses: Vec<String>,
     pub connected: bool,
+    /// Heartbeat-based presence: online, idle or unreachable
+    pub presence: PeerPresence,
+    /// Round-trip time of the latest answered heartbeat
+    pub latency_ms: Option<u64>,
+    /// App version reported in the hello handshake while connected
+    pub app_version: Option<String>,
+    /// Business protocol version negotiated with the peer
+    pub protocol_version: Option<String>,
+    /// `Quarantined` after the peer presented a changed identity key
+    pub pairing_state: PairingState,
 }
 
 #[derive(Debug, Deserialize)]
@@ -49,6 +62,15 @@ pub struct P2PPairingResponse {
     pub error: Option<String>,
 }
 
+/// Out-of-band pairing invitation rendered as a QR code
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PairingInvitationInfo {
+    pub uri: String,
+    pub expires_at: String,
+    pub fingerprint: String,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct P2PPinVerifyRequest {
@@ -245,6 +267,36 @@ pub async fn get_paired_peers_with_status(
                 emit_command_error(&runtime, "get_paired_peers_with_status", &message);
                 e.to_string()
             })?;
+        let liveness = runtime
+            .usecases()
+            .list_peer_liveness()
+            .execute()
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "Failed to list peer liveness");
+                let message = format!("list_peer_liveness: {}", e);
+                emit_command_error(&runtime, "get_paired_peers_with_status", &message);
+                e.to_string()
+            })?;
+        let capabilities = runtime
+            .usecases()
+            .list_peer_capabilities()
+            .execute()
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "Failed to list peer capabilities");
+                let message = format!("list_peer_capabilities: {}", e);
+                emit_command_error(&runtime, "get_paired_peers_with_status", &message);
+                e.to_string()
+            })?;
+        let liveness_map: HashMap<String, PeerLiveness> = liveness
+            .into_iter()
+            .map(|entry| (entry.peer_id.clone(), entry))
+            .collect();
+        let capabilities_map: HashMap<String, PeerCapabilities> = capabilities
+            .into_iter()
+            .map(|entry| (entry.peer_id.clone(), entry))
+            .collect();
         let discovered_map = discovered_peer_map(&discovered);
         let connected_map = connected_peer_ids(&connected);
         tracing::info!(
@@ -260,7 +312,13 @@ pub async fn get_paired_peers_with_status(
                 let peer_id = device.peer_id.as_str().to_string();
                 let discovered_peer = discovered_map.get(&peer_id);
                 let connected = connected_map.contains_key(&peer_id);
-                map_paired_device_to_peer(device, discovered_peer, connected)
+                map_paired_device_to_peer(
+                    device,
+                    discovered_peer,
+                    connected,
+                    liveness_map.get(&peer_id),
+                    capabilities_map.get(&peer_id),
+                )
             })
             .collect())
     }
@@ -272,6 +330,8 @@ fn map_paired_device_to_peer(
     device: PairedDevice,
     discovered_peer: Option<&DiscoveredPeer>,
     connected: bool,
+    liveness: Option<&PeerLiveness>,
+    capabilities: Option<&PeerCapabilities>,
 ) -> PairedPeer {
     let peer_id = device.peer_id.as_str().to_string();
 
@@ -288,14 +348,27 @@ fn map_paired_device_to_peer(
         .map(|peer| peer.addresses.clone())
         .unwrap_or_default();
 
+    // The persisted value is throttled; the in-memory heartbeat is fresher.
+    let last_seen = device
+        .last_seen_at
+        .max(liveness.map(|entry| entry.last_heartbeat_at));
+    let presence = PeerPresence::evaluate(connected, last_seen, chrono::Utc::now());
+
     PairedPeer {
         peer_id,
         device_name,
         shared_secret: vec![],
         paired_at: device.paired_at.to_rfc3339(),
-        last_seen: device.last_seen_at.map(|time| time.to_rfc3339()),
+        last_seen: last_seen.map(|time| time.to_rfc3339()),
         last_known_addresses: addresses,
         connected,
+        presence,
+        latency_ms: liveness
+            .filter(|_| presence == PeerPresence::Online)
+            .and_then(|entry| entry.rtt_ms),
+        app_version: capabilities.map(|entry| entry.app_version.clone()),
+        protocol_version: capabilities.map(|entry| entry.protocol_version.clone()),
+        pairing_state: device.pairing_state,
     }
 }
 
@@ -331,6 +404,65 @@ pub async fn set_pairing_state(
     .await
 }
 
+/// Get the sync policy of a paired device
+/// 获取已配对设备的同步策略
+#[tauri::command]
+pub async fn get_device_sync_policy(
+    runtime: State<'_, Arc<AppRuntime>>,
+    peer_id: String,
+    _trace: Option<TraceMetadata>,
+) -> Result<DeviceSyncPolicy, String> {
+    let span = info_span!(
+        "command.pairing.get_sync_policy",
+        trace_id = tracing::field::Empty,
+        trace_ts = tracing::field::Empty,
+        peer_id = %peer_id,
+    );
+    record_trace_fields(&span, &_trace);
+    async {
+        let uc = runtime.usecases().get_device_sync_policy();
+        uc.execute(&PeerId::from(peer_id.as_str()))
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "Failed to get device sync policy");
+                e.to_string()
+            })
+    }
+    .instrument(span)
+    .await
+}
+
+/// Update the sync direction, content types and apply mode of a paired device
+/// 更新已配对设备的同步方向、内容类型与写入方式
+#[tauri::command]
+pub async fn set_device_sync_policy(
+    runtime: State<'_, Arc<AppRuntime>>,
+    peer_id: String,
+    policy: DeviceSyncPolicy,
+    _trace: Option<TraceMetadata>,
+) -> Result<(), String> {
+    let span = info_span!(
+        "command.pairing.set_sync_policy",
+        trace_id = tracing::field::Empty,
+        trace_ts = tracing::field::Empty,
+        peer_id = %peer_id,
+    );
+    record_trace_fields(&span, &_trace);
+    async {
+        let uc = runtime.usecases().set_device_sync_policy();
+        uc.execute(&PeerId::from(peer_id.as_str()), policy)
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "Failed to set device sync policy");
+                let message = e.to_string();
+                emit_command_error(&runtime, "set_device_sync_policy", &message);
+                message
+            })
+    }
+    .instrument(span)
+    .await
+}
+
 #[tauri::command]
 pub async fn initiate_p2p_pairing(
     request: P2PPairingRequest,
@@ -484,6 +616,124 @@ pub async fn unpair_p2p_device(
     .await
 }
 
+/// Add a peer by multiaddr or `host:port`
+/// 通过 multiaddr 或 `host:port` 手动添加对端
+#[tauri::command]
+pub async fn add_manual_peer(
+    address: String,
+    runtime: State<'_, Arc<AppRuntime>>,
+    _trace: Option<TraceMetadata>,
+) -> Result<String, String> {
+    let span = info_span!(
+        "command.pairing.add_manual_peer",
+        trace_id = tracing::field::Empty,
+        trace_ts = tracing::field::Empty,
+    );
+    record_trace_fields(&span, &_trace);
+    async {
+        let uc = runtime.usecases().add_manual_peer();
+        uc.execute(address).await.map_err(|e| {
+            tracing::error!(error = %e, "Failed to add manual peer");
+            let message = e.to_string();
+            emit_command_error(&runtime, "add_manual_peer", &message);
+            message
+        })
+    }
+    .instrument(span)
+    .await
+}
+
+/// Get local interfaces and the addresses this device listens on
+/// 获取本机网卡与监听地址
+#[tauri::command]
+pub async fn get_local_network_info(
+    runtime: State<'_, Arc<AppRuntime>>,
+    _trace: Option<TraceMetadata>,
+) -> Result<LocalNetworkInfo, String> {
+    let span = info_span!(
+        "command.pairing.get_local_network_info",
+        trace_id = tracing::field::Empty,
+        trace_ts = tracing::field::Empty,
+    );
+    record_trace_fields(&span, &_trace);
+    async {
+        let uc = runtime.usecases().get_local_network_info();
+        uc.execute().await.map_err(|e| {
+            tracing::error!(error = %e, "Failed to get local network info");
+            e.to_string()
+        })
+    }
+    .instrument(span)
+    .await
+}
+
+/// Create a one-time pairing invitation for another device to scan
+/// 生成供其他设备扫描的一次性配对邀请
+#[tauri::command]
+pub async fn create_pairing_invitation(
+    runtime: State<'_, Arc<AppRuntime>>,
+    orchestrator: State<'_, Arc<PairingOrchestrator>>,
+    _trace: Option<TraceMetadata>,
+) -> Result<PairingInvitationInfo, String> {
+    let span = info_span!(
+        "command.pairing.create_invitation",
+        trace_id = tracing::field::Empty,
+        trace_ts = tracing::field::Empty,
+    );
+    record_trace_fields(&span, &_trace);
+    async {
+        let uc = runtime
+            .usecases()
+            .create_pairing_invitation(orchestrator.inner().clone());
+        let invitation = uc.execute().await.map_err(|e| {
+            tracing::error!(error = %e, "Failed to create pairing invitation");
+            e.to_string()
+        })?;
+        Ok(PairingInvitationInfo {
+            uri: invitation.to_uri(),
+            expires_at: invitation.expires_at.to_rfc3339(),
+            fingerprint: invitation.fingerprint.to_string(),
+        })
+    }
+    .instrument(span)
+    .await
+}
+
+/// Pair with a device from its scanned or pasted invitation URI
+/// 使用扫描/粘贴的邀请 URI 发起配对
+#[tauri::command]
+pub async fn pair_with_invitation(
+    uri: String,
+    runtime: State<'_, Arc<AppRuntime>>,
+    orchestrator: State<'_, Arc<PairingOrchestrator>>,
+    _trace: Option<TraceMetadata>,
+) -> Result<P2PPairingResponse, String> {
+    let span = info_span!(
+        "command.pairing.pair_with_invitation",
+        trace_id = tracing::field::Empty,
+        trace_ts = tracing::field::Empty,
+    );
+    record_trace_fields(&span, &_trace);
+    async {
+        let uc = runtime
+            .usecases()
+            .pair_with_invitation(orchestrator.inner().clone());
+        let session_id = uc.execute(&uri).await.map_err(|e| {
+            tracing::error!(error = %e, "Failed to pair with invitation");
+            let message = e.to_string();
+            emit_command_error(&runtime, "pair_with_invitation", &message);
+            message
+        })?;
+        Ok(P2PPairingResponse {
+            session_id,
+            success: true,
+            error: None,
+        })
+    }
+    .instrument(span)
+    .await
+}
+
 fn emit_command_error(runtime: &AppRuntime, command: &str, message: &str) {
     if let Some(app) = runtime.app_handle().as_ref() {
         let payload = P2PCommandErrorEvent {
@@ -531,6 +781,7 @@ mod tests {
             paired_at: Utc::now(),
             last_seen_at: None,
             device_name: "Persisted Name".to_string(),
+            device_id: "device-peer".to_string(),
         };
 
         let discovered = DiscoveredPeer {
@@ -543,7 +794,7 @@ mod tests {
             is_paired: true,
         };
 
-        let result = map_paired_device_to_peer(device, Some(&discovered), true);
+        let result = map_paired_device_to_peer(device, Some(&discovered), true, None, None);
 
         assert_eq!(result.device_name, "Persisted Name");
         assert_eq!(result.last_known_addresses, vec!["127.0.0.1:1234"]);
@@ -559,6 +810,7 @@ mod tests {
             paired_at: Utc::now(),
             last_seen_at: None,
             device_name: "".to_string(),
+            device_id: "device-peer".to_string(),
         };
 
         let discovered = DiscoveredPeer {
@@ -571,7 +823,7 @@ mod tests {
             is_paired: true,
         };
 
-        let result = map_paired_device_to_peer(device, Some(&discovered), false);
+        let result = map_paired_device_to_peer(device, Some(&discovered), false, None, None);
 
         assert_eq!(result.device_name, "Discovered Name");
         assert!(!result.connected);
@@ -586,10 +838,65 @@ mod tests {
             paired_at: Utc::now(),
             last_seen_at: None,
             device_name: "".to_string(),
+            device_id: "device-peer".to_string(),
         };
 
-        let result = map_paired_device_to_peer(device, None, false);
+        let result = map_paired_device_to_peer(device, None, false, None, None);
 
         assert_eq!(result.device_name, "Unknown Device");
+        assert_eq!(result.presence, PeerPresence::Unreachable);
+    }
+
+    #[test]
+    fn test_map_paired_device_to_peer_uses_heartbeat_for_presence() {
+        let heartbeat_at = Utc::now();
+        let device = PairedDevice {
+            peer_id: PeerId::from("peer-1"),
+            pairing_state: PairingState::Trusted,
+            identity_fingerprint: "fp".to_string(),
+            paired_at: heartbeat_at - chrono::Duration::days(1),
+            last_seen_at: Some(heartbeat_at - chrono::Duration::seconds(50)),
+            device_name: "Desk".to_string(),
+            device_id: "device-peer".to_string(),
+        };
+        let liveness = PeerLiveness {
+            peer_id: "peer-1".to_string(),
+            last_heartbeat_at: heartbeat_at,
+            rtt_ms: Some(8),
+        };
+
+        let online = map_paired_device_to_peer(device.clone(), None, true, Some(&liveness), None);
+        let offline = map_paired_device_to_peer(device, None, false, Some(&liveness), None);
+
+        assert_eq!(online.presence, PeerPresence::Online);
+        assert_eq!(online.latency_ms, Some(8));
+        assert_eq!(online.last_seen, Some(heartbeat_at.to_rfc3339()));
+        assert_eq!(offline.presence, PeerPresence::Idle);
+        assert_eq!(offline.latency_ms, None);
+    }
+
+    #[test]
+    fn test_map_paired_device_to_peer_reports_negotiated_version() {
+        let device = PairedDevice {
+            peer_id: PeerId::from("peer-1"),
+            pairing_state: PairingState::Trusted,
+            identity_fingerprint: "fp".to_string(),
+            paired_at: Utc::now(),
+            last_seen_at: None,
+            device_name: "Desk".to_string(),
+            device_id: "device-peer".to_string(),
+        };
+        let capabilities = PeerCapabilities {
+            peer_id: "peer-1".to_string(),
+            app_version: "0.2.0".to_string(),
+            protocol_version: "1.0.0".to_string(),
+            features: vec![],
+            negotiated_at: Utc::now(),
+        };
+
+        let result = map_paired_device_to_peer(device, None, true, None, Some(&capabilities));
+
+        assert_eq!(result.app_version.as_deref(), Some("0.2.0"));
+        assert_eq!(result.protocol_version.as_deref(), Some("1.0.0"));
     }
 }
@@ -0,0 +1,110 @@
+//! Space-related Tauri commands
+//! 空间相关的 Tauri 命令
+
+use crate::bootstrap::AppRuntime;
+use crate::commands::record_trace_fields;
+use std::sync::Arc;
+use tauri::State;
+use tracing::{info, info_span, Instrument};
+use uc_app::usecases::SpaceSummary;
+use uc_core::ids::SpaceId;
+use uc_core::ports::observability::TraceMetadata;
+use uc_core::security::model::Passphrase;
+
+/// List known spaces
+/// 列出本机已知的空间
+#[tauri::command]
+pub async fn list_spaces(
+    runtime: State<'_, Arc<AppRuntime>>,
+    _trace: Option<TraceMetadata>,
+) -> Result<Vec<SpaceSummary>, String> {
+    let span = info_span!(
+        "command.spaces.list",
+        trace_id = tracing::field::Empty,
+        trace_ts = tracing::field::Empty,
+    );
+    record_trace_fields(&span, &_trace);
+
+    runtime
+        .usecases()
+        .list_spaces()
+        .execute()
+        .instrument(span)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to list spaces");
+            e.to_string()
+        })
+}
+
+/// Create a space and switch to it
+/// 新建空间并切换到该空间
+///
+/// The space gets its own passphrase, keyslot, paired devices and history.
+/// 新空间拥有独立的口令、密钥槽、配对设备与历史记录。
+#[tauri::command]
+pub async fn create_space(
+    runtime: State<'_, Arc<AppRuntime>>,
+    name: String,
+    passphrase: String,
+    _trace: Option<TraceMetadata>,
+) -> Result<SpaceSummary, String> {
+    let span = info_span!(
+        "command.spaces.create",
+        trace_id = tracing::field::Empty,
+        trace_ts = tracing::field::Empty,
+    );
+    record_trace_fields(&span, &_trace);
+
+    let profile = runtime
+        .usecases()
+        .create_space()
+        .execute(name, Passphrase(passphrase))
+        .instrument(span)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to create space");
+            e.to_string()
+        })?;
+
+    info!(space_id = %profile.id, "Space created");
+    Ok(SpaceSummary {
+        id: profile.id,
+        name: profile.name,
+        created_at_ms: profile.created_at_ms,
+        is_active: true,
+    })
+}
+
+/// Switch the active space
+/// 切换活动空间
+///
+/// Returns whether the new space's session was unlocked automatically; when
+/// `false` the session stays locked until `unlock_encryption_session` runs.
+#[tauri::command]
+pub async fn switch_space(
+    runtime: State<'_, Arc<AppRuntime>>,
+    space_id: String,
+    _trace: Option<TraceMetadata>,
+) -> Result<bool, String> {
+    let span = info_span!(
+        "command.spaces.switch",
+        trace_id = tracing::field::Empty,
+        trace_ts = tracing::field::Empty,
+        space_id = %space_id,
+    );
+    record_trace_fields(&span, &_trace);
+
+    let outcome = runtime
+        .usecases()
+        .switch_space()
+        .execute(SpaceId::from(space_id.as_str()))
+        .instrument(span)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to switch space");
+            e.to_string()
+        })?;
+
+    Ok(outcome.unlocked)
+}
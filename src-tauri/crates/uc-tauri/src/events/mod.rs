@@ -7,7 +7,11 @@ use tauri::{AppHandle, Emitter};
 pub mod p2p_pairing;
 pub mod p2p_peer;
 pub use p2p_pairing::{P2PPairingVerificationEvent, P2PPairingVerificationKind};
-pub use p2p_peer::{P2PPeerConnectionEvent, P2PPeerDiscoveryEvent, P2PPeerNameUpdatedEvent};
+pub use p2p_peer::{
+    P2PPairingRevokedEvent, P2PPeerConnectionEvent, P2PPeerDiscoveryEvent, P2PPeerHeartbeatEvent,
+    P2PPeerIdentityMismatchEvent, P2PPeerNameUpdatedEvent, P2PTransferFailedEvent,
+    P2PTransferProgressEvent,
+};
 
 /// Clipboard events emitted to frontend
 /// 发送到前端的剪贴板事件
@@ -18,6 +22,11 @@ pub enum ClipboardEvent {
     NewContent { entry_id: String, preview: String },
     /// Clipboard content deleted
     Deleted { entry_id: String },
+    /// Clipboard entries removed by the retention policy
+    Expired {
+        count: usize,
+        entry_ids: Vec<String>,
+    },
 }
 
 /// Encryption events emitted to frontend
@@ -31,6 +40,23 @@ pub enum EncryptionEvent {
     SessionReady,
     /// Encryption failed
     Failed { reason: String },
+    /// History re-encryption progress after a key rotation
+    ReencryptionProgress {
+        epoch: u32,
+        processed: usize,
+        total: usize,
+        failed: usize,
+        abandoned: usize,
+    },
+    /// History re-encryption finished; the retired key was dropped, or retained
+    /// when `abandoned` payloads could not be re-encrypted
+    ReencryptionCompleted { epoch: u32, abandoned: usize },
+    /// A peer pushed a new keyslot; it is applied once the user enters the passphrase
+    KeySlotUpdatePending {
+        peer_id: String,
+        epoch: u32,
+        revision: u64,
+    },
 }
 
 /// Forward libp2p startup error to frontend
@@ -61,6 +87,26 @@ mod tests {
             failed,
             serde_json::json!({ "type": "Failed", "reason": "oops" })
         );
+
+        let progress = serde_json::to_value(EncryptionEvent::ReencryptionProgress {
+            epoch: 2,
+            processed: 5,
+            total: 10,
+            failed: 0,
+            abandoned: 1,
+        })
+        .unwrap();
+        assert_eq!(
+            progress,
+            serde_json::json!({
+                "type": "ReencryptionProgress",
+                "epoch": 2,
+                "processed": 5,
+                "total": 10,
+                "failed": 0,
+                "abandoned": 1
+            })
+        );
     }
 
     #[tokio::test]
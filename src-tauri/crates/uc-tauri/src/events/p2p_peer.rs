@@ -6,6 +6,8 @@ pub struct P2PPeerConnectionEvent {
     pub peer_id: String,
     pub device_name: Option<String>,
     pub connected: bool,
+    /// Active transport ("tcp" / "quic") while connected
+    pub transport: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,6 +17,51 @@ pub struct P2PPeerNameUpdatedEvent {
     pub device_name: String,
 }
 
+/// Heartbeat from a peer; `rtt_ms` is set when it answers our probe
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct P2PPeerHeartbeatEvent {
+    pub peer_id: String,
+    pub rtt_ms: Option<u64>,
+}
+
+/// Progress of a blob transfer; `direction` is "inbound" when pulling
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct P2PTransferProgressEvent {
+    pub peer_id: String,
+    pub content_hash: String,
+    pub direction: String,
+    pub transferred_bytes: u64,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct P2PTransferFailedEvent {
+    pub peer_id: String,
+    pub content_hash: String,
+    pub error: String,
+}
+
+/// Pairing with a peer ended; `by_peer` when the other device unpaired us
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct P2PPairingRevokedEvent {
+    pub peer_id: String,
+    pub by_peer: bool,
+}
+
+/// A known device presented a different identity key, on re-pairing or in its
+/// hello; the affected pairing was quarantined
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct P2PPeerIdentityMismatchEvent {
+    pub peer_id: String,
+    pub expected_fingerprint: String,
+    pub presented_fingerprint: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct P2PPeerDiscoveryEvent {
@@ -36,6 +36,8 @@ pub struct ClipboardEntryProjection {
     pub updated_at: i64,
     /// Timestamp of last access/use
     pub active_time: i64,
+    /// Why the entry was not synced to other devices, if it was not
+    pub sync_skip_reason: Option<String>,
 }
 
 /// Clipboard entries response with readiness status
@@ -84,3 +86,58 @@ pub struct ClipboardEntryResource {
     /// Custom protocol URL for resource fetching
     pub url: String,
 }
+
+/// Full-text search request from the frontend
+/// 前端发起的全文检索请求
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClipboardSearchRequest {
+    /// Query text; wrap words in double quotes to match a phrase
+    pub query: String,
+    /// Content kinds to include (`text`, `rich_text`, `link`, `code_snippet`, ...)
+    #[serde(default)]
+    pub content_types: Vec<String>,
+    /// Inclusive lower bound on capture time (Unix ms)
+    pub from_ms: Option<i64>,
+    /// Inclusive upper bound on capture time (Unix ms)
+    pub to_ms: Option<i64>,
+    /// Source device ids to include
+    #[serde(default)]
+    pub source_devices: Vec<String>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+/// Part of a search snippet, highlighted when it matched the query
+/// 检索摘要片段，匹配部分会被高亮
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipboardSearchSnippetSegment {
+    pub text: String,
+    pub highlighted: bool,
+}
+
+/// Single full-text search hit
+/// 单条全文检索结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipboardSearchHit {
+    /// Entry identifier, usable with the detail/restore commands
+    pub id: String,
+    /// Excerpt around the match
+    pub snippet: Vec<ClipboardSearchSnippetSegment>,
+    /// Content kind of the indexed text
+    pub content_type: String,
+    /// Device the entry was captured on
+    pub source_device: String,
+    /// Timestamp when captured (Unix ms)
+    pub captured_at: i64,
+}
+
+/// Search response with readiness status
+/// 带就绪状态的检索响应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ClipboardSearchResponse {
+    /// Session is ready; hits are ordered by relevance
+    Ready { hits: Vec<ClipboardSearchHit> },
+    /// Session not ready yet (e.g., awaiting unlock)
+    NotReady,
+}
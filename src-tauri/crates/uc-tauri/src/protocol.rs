@@ -1,7 +1,12 @@
 use tauri::http::{Request, StatusCode};
+use tokio::io::{AsyncRead, AsyncReadExt};
 use uc_core::ids::RepresentationId;
 use uc_core::BlobId;
 
+/// Largest slice served for one `Range` request; open-ended ranges are clamped to it.
+/// 单次 `Range` 请求返回的最大字节数，开放区间会被截断到此长度。
+pub const MAX_RANGE_LEN: u64 = 1024 * 1024;
+
 /// # Behavior / 行为
 ///
 /// Parsed UC protocol route.
@@ -93,6 +98,121 @@ pub fn parse_uc_request(request: &Request<Vec<u8>>) -> Result<UcRoute, UcRequest
     }
 }
 
+/// Inclusive byte range of a resource, resolved from a `Range` header.
+/// 从 `Range` 请求头解析出的字节区间（闭区间）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl ByteRange {
+    pub fn byte_count(&self) -> u64 {
+        self.end - self.start + 1
+    }
+
+    /// `Content-Range` value for a resource of `total_len` bytes.
+    pub fn content_range(&self, total_len: u64) -> String {
+        format!("bytes {}-{}/{}", self.start, self.end, total_len)
+    }
+}
+
+/// The requested range starts past the end of the resource.
+/// 请求的区间超出资源长度。
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+#[error("Range not satisfiable")]
+pub struct RangeNotSatisfiable;
+
+/// # Behavior / 行为
+///
+/// Resolve a `Range` header against a resource of `total_len` bytes.
+/// 根据资源长度解析 `Range` 请求头。
+///
+/// Only a single `bytes` range is honoured. Other units, multiple ranges and
+/// malformed values return `Ok(None)`, meaning the whole resource is served,
+/// as RFC 9110 allows. The end is clamped to the resource and to
+/// [`MAX_RANGE_LEN`], so a media element paging through a large blob only
+/// ever pulls one bounded slice per request.
+///
+/// 仅支持单个 `bytes` 区间；其他情况返回 `Ok(None)` 表示返回完整资源。区间终点会被截断到
+/// 资源末尾与 [`MAX_RANGE_LEN`]。
+pub fn parse_range_header(
+    value: &str,
+    total_len: u64,
+) -> Result<Option<ByteRange>, RangeNotSatisfiable> {
+    let Some(spec) = value.trim().strip_prefix("bytes=") else {
+        return Ok(None);
+    };
+    if spec.contains(',') {
+        return Ok(None);
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return Ok(None);
+    };
+
+    let (start, end) = match (start.trim(), end.trim()) {
+        ("", "") => return Ok(None),
+        ("", suffix) => {
+            let Ok(suffix) = suffix.parse::<u64>() else {
+                return Ok(None);
+            };
+            if suffix == 0 || total_len == 0 {
+                return Err(RangeNotSatisfiable);
+            }
+            (total_len.saturating_sub(suffix), total_len - 1)
+        }
+        (start, end) => {
+            let Ok(start) = start.parse::<u64>() else {
+                return Ok(None);
+            };
+            let end = if end.is_empty() {
+                u64::MAX
+            } else {
+                match end.parse::<u64>() {
+                    Ok(end) if end >= start => end,
+                    _ => return Ok(None),
+                }
+            };
+            if start >= total_len {
+                return Err(RangeNotSatisfiable);
+            }
+            (start, end.min(total_len - 1))
+        }
+    };
+
+    Ok(Some(ByteRange {
+        start,
+        end: end.min(start.saturating_add(MAX_RANGE_LEN - 1)),
+    }))
+}
+
+/// # Behavior / 行为
+///
+/// Read the bytes of `range` from a plaintext stream.
+/// 从明文流中读取 `range` 对应的字节。
+///
+/// Bytes before the range are discarded as they are read, so memory stays at
+/// the size of the range plus the reader's own frame buffer. A stream shorter
+/// than the range yields the bytes that exist.
+///
+/// 区间之前的字节边读边丢弃，内存占用仅为区间大小加上读取器自身的帧缓冲。
+pub async fn read_range<R>(reader: &mut R, range: ByteRange) -> std::io::Result<Vec<u8>>
+where
+    R: AsyncRead + Unpin + ?Sized,
+{
+    tokio::io::copy(
+        &mut (&mut *reader).take(range.start),
+        &mut tokio::io::sink(),
+    )
+    .await?;
+    let mut bytes = Vec::with_capacity(range.byte_count() as usize);
+    (&mut *reader)
+        .take(range.byte_count())
+        .read_to_end(&mut bytes)
+        .await?;
+    Ok(bytes)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -113,4 +233,56 @@ mod tests {
             } if representation_id == RepresentationId::from("rep-1")
         ));
     }
+
+    #[test]
+    fn test_range_header_resolves_single_byte_ranges() {
+        assert_eq!(
+            parse_range_header("bytes=2-5", 10),
+            Ok(Some(ByteRange { start: 2, end: 5 }))
+        );
+        assert_eq!(
+            parse_range_header("bytes=4-", 10),
+            Ok(Some(ByteRange { start: 4, end: 9 }))
+        );
+        assert_eq!(
+            parse_range_header("bytes=-3", 10),
+            Ok(Some(ByteRange { start: 7, end: 9 }))
+        );
+        assert_eq!(
+            parse_range_header("bytes=8-100", 10),
+            Ok(Some(ByteRange { start: 8, end: 9 }))
+        );
+        assert_eq!(
+            parse_range_header("bytes=0-", 10 * MAX_RANGE_LEN),
+            Ok(Some(ByteRange {
+                start: 0,
+                end: MAX_RANGE_LEN - 1
+            }))
+        );
+    }
+
+    #[test]
+    fn test_range_header_falls_back_or_rejects() {
+        assert_eq!(parse_range_header("items=0-1", 10), Ok(None));
+        assert_eq!(parse_range_header("bytes=0-1,4-5", 10), Ok(None));
+        assert_eq!(parse_range_header("bytes=5-2", 10), Ok(None));
+        assert_eq!(parse_range_header("bytes=x-", 10), Ok(None));
+        assert_eq!(
+            parse_range_header("bytes=10-", 10),
+            Err(RangeNotSatisfiable)
+        );
+        assert_eq!(parse_range_header("bytes=-0", 10), Err(RangeNotSatisfiable));
+    }
+
+    #[tokio::test]
+    async fn test_read_range_skips_leading_bytes() {
+        let data: Vec<u8> = (0..32).collect();
+        let mut reader = data.as_slice();
+
+        let bytes = read_range(&mut reader, ByteRange { start: 10, end: 13 })
+            .await
+            .expect("read range");
+
+        assert_eq!(bytes, vec![10, 11, 12, 13]);
+    }
 }
@@ -4,7 +4,8 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::http::header::{
-    HeaderValue, ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN, CONTENT_TYPE,
+    HeaderValue, ACCEPT_RANGES, ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN,
+    CONTENT_RANGE, CONTENT_TYPE, RANGE,
 };
 use tauri::http::{Request, Response, StatusCode};
 use tauri::webview::PageLoadEvent;
@@ -12,6 +13,7 @@ use tauri::Manager;
 use tauri_plugin_autostart::MacosLauncher;
 use tauri_plugin_single_instance;
 use tauri_plugin_stronghold;
+use tokio::io::AsyncReadExt;
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
@@ -21,7 +23,7 @@ use uc_core::config::AppConfig;
 use uc_core::ports::AppDirsPort;
 use uc_core::ports::ClipboardChangeHandler;
 use uc_core::ports::NetworkPort;
-use uc_infra::fs::key_slot_store::{JsonKeySlotStore, KeySlotStore};
+use uc_infra::fs::key_slot_store::{ActiveSpaceKeySlotStore, JsonKeySlotStore, KeySlotStore};
 use uc_platform::app_dirs::DirsAppDirsAdapter;
 use uc_platform::ipc::PlatformCommand;
 use uc_platform::ports::PlatformCommandExecutorPort;
@@ -32,9 +34,11 @@ use uc_platform::runtime::runtime::PlatformRuntime;
 use uc_tauri::bootstrap::tracing as bootstrap_tracing;
 use uc_tauri::bootstrap::{
     ensure_default_device_name, load_config, resolve_pairing_config, resolve_pairing_device_name,
-    start_background_tasks, wire_dependencies, AppRuntime, SetupRuntimePorts,
+    restore_active_space, start_background_tasks, wire_dependencies, AppRuntime, SetupRuntimePorts,
+};
+use uc_tauri::protocol::{
+    parse_range_header, parse_uc_request, read_range, RangeNotSatisfiable, UcRoute,
 };
-use uc_tauri::protocol::{parse_uc_request, UcRoute};
 
 // Platform-specific command modules
 mod plugins;
@@ -129,6 +133,21 @@ fn build_response(
     response
 }
 
+fn set_header(
+    response: &mut Response<Vec<u8>>,
+    name: tauri::http::header::HeaderName,
+    value: &str,
+) {
+    match HeaderValue::from_str(value) {
+        Ok(value) => {
+            response.headers_mut().insert(name, value);
+        }
+        Err(err) => {
+            error!(error = %err, "Invalid header value for response");
+        }
+    }
+}
+
 fn text_response(status: StatusCode, message: &str, origin: Option<&str>) -> Response<Vec<u8>> {
     build_response(
         status,
@@ -164,16 +183,29 @@ async fn resolve_uc_request(
     };
 
     match route {
-        UcRoute::Blob { blob_id } => resolve_uc_blob_request(app_handle, blob_id, origin).await,
+        UcRoute::Blob { blob_id } => {
+            let range = request
+                .headers()
+                .get(RANGE)
+                .and_then(|value| value.to_str().ok());
+            resolve_uc_blob_request(app_handle, blob_id, range, origin).await
+        }
         UcRoute::Thumbnail { representation_id } => {
             resolve_uc_thumbnail_request(app_handle, representation_id, origin).await
         }
     }
 }
 
+/// Serve a blob from its plaintext stream.
+///
+/// With a `Range` header only the requested slice is read (see
+/// `parse_range_header`), so media elements page through large blobs in
+/// bounded steps. Without one the body is the whole blob, since the webview
+/// protocol API takes a complete body.
 async fn resolve_uc_blob_request(
     app_handle: tauri::AppHandle,
     blob_id: uc_core::BlobId,
+    range: Option<&str>,
     origin: Option<&str>,
 ) -> Response<Vec<u8>> {
     let runtime = match app_handle.try_state::<Arc<AppRuntime>>() {
@@ -189,18 +221,8 @@ async fn resolve_uc_blob_request(
     };
 
     let use_case = runtime.usecases().resolve_blob_resource();
-    match use_case.execute(&blob_id).await {
-        Ok(result) => build_response(
-            StatusCode::OK,
-            Some(
-                result
-                    .mime_type
-                    .as_deref()
-                    .unwrap_or("application/octet-stream"),
-            ),
-            result.bytes,
-            origin,
-        ),
+    let mut result = match use_case.execute(&blob_id).await {
+        Ok(result) => result,
         Err(err) => {
             let err_msg = err.to_string();
             error!(error = %err, blob_id = %blob_id, "Failed to resolve blob resource");
@@ -209,9 +231,69 @@ async fn resolve_uc_blob_request(
             } else {
                 StatusCode::INTERNAL_SERVER_ERROR
             };
-            text_response(status, "Failed to resolve blob resource", origin)
+            return text_response(status, "Failed to resolve blob resource", origin);
         }
+    };
+    let content_type = result
+        .mime_type
+        .clone()
+        .unwrap_or_else(|| "application/octet-stream".to_string());
+    let total_len = result.size_bytes;
+
+    let range = match range.map(|value| parse_range_header(value, total_len)) {
+        Some(Ok(range)) => range,
+        Some(Err(RangeNotSatisfiable)) => {
+            let mut response = text_response(
+                StatusCode::RANGE_NOT_SATISFIABLE,
+                "Range not satisfiable",
+                origin,
+            );
+            set_header(
+                &mut response,
+                CONTENT_RANGE,
+                &format!("bytes */{total_len}"),
+            );
+            return response;
+        }
+        None => None,
+    };
+
+    let (status, body, content_range) = match range {
+        Some(range) => match read_range(&mut result.reader, range).await {
+            Ok(bytes) => (
+                StatusCode::PARTIAL_CONTENT,
+                bytes,
+                Some(range.content_range(total_len)),
+            ),
+            Err(err) => {
+                error!(error = %err, blob_id = %blob_id, "Failed to read blob range");
+                return text_response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to resolve blob resource",
+                    origin,
+                );
+            }
+        },
+        None => {
+            let mut bytes = Vec::with_capacity(total_len as usize);
+            if let Err(err) = result.reader.read_to_end(&mut bytes).await {
+                error!(error = %err, blob_id = %blob_id, "Failed to read blob resource");
+                return text_response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to resolve blob resource",
+                    origin,
+                );
+            }
+            (StatusCode::OK, bytes, None)
+        }
+    };
+
+    let mut response = build_response(status, Some(&content_type), body, origin);
+    set_header(&mut response, ACCEPT_RANGES, "bytes");
+    if let Some(content_range) = content_range {
+        set_header(&mut response, CONTENT_RANGE, &content_range);
     }
+    response
 }
 
 async fn resolve_uc_thumbnail_request(
@@ -414,10 +496,14 @@ fn run_app(config: AppConfig) {
     let pairing_device_repo = deps.paired_device_repo.clone();
     let pairing_device_identity = deps.device_identity.clone();
     let pairing_settings = deps.settings.clone();
+    let active_space = deps.active_space.clone();
     let discovery_network = deps.network.clone();
     let pairing_peer_id = background.libp2p_network.local_peer_id();
     let pairing_identity_pubkey = background.libp2p_network.local_identity_pubkey();
     let (pairing_device_name, pairing_config) = tauri::async_runtime::block_on(async move {
+        if let Err(e) = restore_active_space(pairing_settings.clone(), active_space).await {
+            warn!("Failed to restore active space: {}", e);
+        }
         let device_name = resolve_pairing_device_name(pairing_settings.clone()).await;
         let config = resolve_pairing_config(pairing_settings).await;
         (device_name, config)
@@ -454,7 +540,12 @@ fn run_app(config: AppConfig) {
                 .unwrap_or(&config.vault_key_path)
                 .to_path_buf()
         };
-        Arc::new(JsonKeySlotStore::new(vault_dir.join("keyslot.json")))
+        // Sponsors hand out the keyslot of the active space
+        Arc::new(ActiveSpaceKeySlotStore::new(
+            Arc::new(JsonKeySlotStore::new(vault_dir.join("keyslot.json"))),
+            vault_dir,
+            deps.active_space.clone(),
+        ))
     };
 
     let runtime = AppRuntime::with_setup(
@@ -548,6 +639,7 @@ fn run_app(config: AppConfig) {
                 pairing_action_rx,
                 space_access_orchestrator.clone(),
                 key_slot_store.clone(),
+                runtime_for_handler.pending_keyslot_updates(),
             );
 
             // Clone handles for async blocks
@@ -634,17 +726,31 @@ fn run_app(config: AppConfig) {
         .invoke_handler(tauri::generate_handler![
             // Clipboard commands
             uc_tauri::commands::clipboard::get_clipboard_entries,
+            uc_tauri::commands::clipboard::search_clipboard_entries,
             uc_tauri::commands::clipboard::get_clipboard_entry_detail,
             uc_tauri::commands::clipboard::get_clipboard_entry_resource,
             uc_tauri::commands::clipboard::delete_clipboard_entry,
             uc_tauri::commands::clipboard::restore_clipboard_entry,
+            uc_tauri::commands::clipboard::set_clipboard_entry_pinned,
+            uc_tauri::commands::clipboard::clear_clipboard_items,
+            uc_tauri::commands::clipboard::collect_blob_garbage,
             // Encryption commands
             uc_tauri::commands::encryption::initialize_encryption,
+            uc_tauri::commands::encryption::change_passphrase,
+            uc_tauri::commands::encryption::get_pending_keyslot_update,
+            uc_tauri::commands::encryption::confirm_keyslot_update,
+            uc_tauri::commands::encryption::dismiss_keyslot_update,
+            uc_tauri::commands::encryption::rotate_master_key,
+            uc_tauri::commands::encryption::get_key_rotation_status,
             uc_tauri::commands::encryption::get_encryption_session_status,
             uc_tauri::commands::encryption::unlock_encryption_session,
             // Settings commands
             uc_tauri::commands::settings::get_settings,
             uc_tauri::commands::settings::update_settings,
+            // Space commands
+            uc_tauri::commands::spaces::list_spaces,
+            uc_tauri::commands::spaces::create_space,
+            uc_tauri::commands::spaces::switch_space,
             // Setup commands
             uc_tauri::commands::setup::get_setup_state,
             uc_tauri::commands::setup::start_new_space,
@@ -667,6 +773,12 @@ fn run_app(config: AppConfig) {
             uc_tauri::commands::pairing::unpair_p2p_device,
             uc_tauri::commands::pairing::list_paired_devices,
             uc_tauri::commands::pairing::set_pairing_state,
+            uc_tauri::commands::pairing::get_device_sync_policy,
+            uc_tauri::commands::pairing::set_device_sync_policy,
+            uc_tauri::commands::pairing::add_manual_peer,
+            uc_tauri::commands::pairing::get_local_network_info,
+            uc_tauri::commands::pairing::create_pairing_invitation,
+            uc_tauri::commands::pairing::pair_with_invitation,
             // Lifecycle commands
             uc_tauri::commands::lifecycle::retry_lifecycle,
             uc_tauri::commands::lifecycle::get_lifecycle_status,
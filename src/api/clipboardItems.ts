@@ -1,5 +1,12 @@
 import { invokeWithTrace } from '@/lib/tauri-command'
 
+/** Why an entry was not sent to other devices */
+export type SyncSkipReason =
+  | 'auto_sync_disabled'
+  | 'content_type_disabled'
+  | 'exceeds_max_file_size'
+  | 'sensitive'
+
 // Backend projection type
 interface ClipboardEntryProjection {
   id: string
@@ -13,6 +20,7 @@ interface ClipboardEntryProjection {
   updated_at: number
   active_time: number
   thumbnail_url?: string | null
+  sync_skip_reason?: SyncSkipReason | null
 }
 
 type ClipboardEntriesResponse =
@@ -41,6 +49,43 @@ export interface ClipboardEntryResource {
   url: string
 }
 
+export type ClipboardSearchContentType =
+  | 'text'
+  | 'rich_text'
+  | 'image'
+  | 'link'
+  | 'file'
+  | 'code_snippet'
+  | 'unknown'
+
+export interface ClipboardSearchRequest {
+  /** Query text; wrap words in double quotes to match a phrase */
+  query: string
+  content_types?: ClipboardSearchContentType[]
+  from_ms?: number | null
+  to_ms?: number | null
+  source_devices?: string[]
+  limit?: number
+  offset?: number
+}
+
+export interface ClipboardSearchSnippetSegment {
+  text: string
+  highlighted: boolean
+}
+
+export interface ClipboardSearchHit {
+  id: string
+  snippet: ClipboardSearchSnippetSegment[]
+  content_type: ClipboardSearchContentType
+  source_device: string
+  captured_at: number
+}
+
+export type ClipboardSearchResult =
+  | { status: 'ready'; hits: ClipboardSearchHit[] }
+  | { status: 'not_ready' }
+
 /**
  * 排序选项枚举
  */
@@ -112,6 +157,8 @@ export interface ClipboardItemResponse {
   created_at: number
   updated_at: number
   active_time: number
+  /** Set when the entry was kept local instead of being synced */
+  sync_skip_reason?: SyncSkipReason | null
   item: ClipboardItem
 }
 
@@ -138,24 +185,21 @@ export async function getClipboardStats(): Promise<ClipboardStats> {
  * @param orderBy 排序方式（暂未实现）
  * @param limit 限制返回的条目数
  * @param offset 偏移量，用于分页（暂未实现）
- * @param filter 过滤选项（暂未实现）
+ * @param filter 过滤选项（目前仅支持 Favorited，其余类型在前端过滤）
  * @returns Promise，返回剪贴板条目数组
  */
 export async function getClipboardItems(
   _orderBy?: OrderBy,
   limit?: number,
   offset?: number,
-  _filter?: Filter
+  filter?: Filter
 ): Promise<ClipboardItemsResult> {
   try {
-    // Note: orderBy and filter are not yet implemented in the backend command
-    // Map Filter enum to backend format if needed (for future use)
-    // const mappedFilter = filter === Filter.All ? undefined : filter
-
-    // Use new command name: get_clipboard_entries
+    // Note: orderBy is not yet implemented in the backend command
     const response = await invokeWithTrace<ClipboardEntriesResponse>('get_clipboard_entries', {
       limit: limit ?? 50,
       offset: offset ?? 0,
+      pinnedOnly: filter === Filter.Favorited,
     })
 
     if (response.status === 'not_ready') {
@@ -197,6 +241,7 @@ export async function getClipboardItems(
         created_at: entry.captured_at,
         updated_at: entry.updated_at,
         active_time: entry.active_time,
+        sync_skip_reason: entry.sync_skip_reason ?? null,
         item,
       }
     })
@@ -226,6 +271,23 @@ export async function getClipboardItem(
   }
 }
 
+/**
+ * Full-text search over clipboard history
+ * 剪贴板历史全文检索
+ * @param request Query text and optional filters
+ * @returns Promise with hits ordered by relevance
+ */
+export async function searchClipboardItems(
+  request: ClipboardSearchRequest
+): Promise<ClipboardSearchResult> {
+  try {
+    return await invokeWithTrace<ClipboardSearchResult>('search_clipboard_entries', { request })
+  } catch (error) {
+    console.error('Failed to search clipboard entries:', error)
+    throw error
+  }
+}
+
 /**
  * Get clipboard entry detail (full content)
  * 获取剪切板条目详情（完整内容）
@@ -291,7 +353,7 @@ export async function deleteClipboardItem(id: string): Promise<boolean> {
 }
 
 /**
- * 清空所有剪贴板历史记录
+ * 清空所有剪贴板历史记录（收藏的条目会被保留）
  * @returns Promise，成功返回删除的条目数
  */
 export async function clearClipboardItems(): Promise<number> {
@@ -303,6 +365,29 @@ export async function clearClipboardItems(): Promise<number> {
   }
 }
 
+/**
+ * Blob 垃圾回收结果
+ */
+export interface BlobGcReport {
+  deleted_blobs: number
+  orphan_files: number
+  orphan_thumbnails: number
+  reclaimed_bytes: number
+}
+
+/**
+ * 回收不再被任何剪贴板条目引用的 blob 存储
+ * @returns Promise，返回回收结果（含释放的字节数）
+ */
+export async function collectBlobGarbage(): Promise<BlobGcReport> {
+  try {
+    return await invokeWithTrace('collect_blob_garbage')
+  } catch (error) {
+    console.error('回收 blob 存储失败:', error)
+    throw error
+  }
+}
+
 /**
  * 同步剪贴板内容
  * @returns Promise，成功返回true
@@ -378,7 +463,7 @@ export function isTextType(contentType: string): boolean {
  */
 export async function favoriteClipboardItem(id: string): Promise<boolean> {
   try {
-    return await invokeWithTrace('toggle_favorite_clipboard_item', { id, isFavorited: true })
+    return await invokeWithTrace('set_clipboard_entry_pinned', { entryId: id, pinned: true })
   } catch (error) {
     console.error('收藏剪贴板条目失败:', error)
     throw error
@@ -392,7 +477,7 @@ export async function favoriteClipboardItem(id: string): Promise<boolean> {
  */
 export async function unfavoriteClipboardItem(id: string): Promise<boolean> {
   try {
-    return await invokeWithTrace('toggle_favorite_clipboard_item', { id, isFavorited: false })
+    return await invokeWithTrace('set_clipboard_entry_pinned', { entryId: id, pinned: false })
   } catch (error) {
     console.error('取消收藏剪贴板条目失败:', error)
     throw error
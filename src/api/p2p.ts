@@ -6,6 +6,7 @@
 
 import { listen } from '@tauri-apps/api/event'
 import { invokeWithTrace } from '@/lib/tauri-command'
+import type { ContentTypes } from '@/types/setting'
 
 /**
  * P2P 设备信息
@@ -21,6 +22,8 @@ export interface P2PPeerInfo {
   isPaired: boolean
   /** Connection status */
   connected: boolean
+  /** Active transport while connected */
+  transport?: 'tcp' | 'quic' | 'relay' | null
 }
 
 /**
@@ -36,6 +39,11 @@ export interface LocalDeviceInfo {
 /**
  * 已配对的设备信息
  */
+/**
+ * 已配对设备的信任状态；Quarantined 表示对端身份公钥已变化
+ */
+export type PairedDeviceState = 'Pending' | 'Trusted' | 'Revoked' | 'Quarantined'
+
 export interface PairedPeer {
   /** Peer ID */
   peerId: string
@@ -51,8 +59,23 @@ export interface PairedPeer {
   lastKnownAddresses: string[]
   /** Connection status */
   connected: boolean
+  /** Heartbeat-based presence */
+  presence: PeerPresence
+  /** Round-trip time of the latest heartbeat (ms), only while online */
+  latencyMs: number | null
+  /** App version from the version handshake, only while connected */
+  appVersion?: string | null
+  /** Negotiated business protocol version */
+  protocolVersion?: string | null
+  /** Trust state; Quarantined blocks sync until re-paired or revoked */
+  pairingState?: PairedDeviceState
 }
 
+/**
+ * 基于心跳的在线状态
+ */
+export type PeerPresence = 'online' | 'idle' | 'unreachable'
+
 /**
  * P2P 配对请求
  */
@@ -134,6 +157,62 @@ export interface P2PPeerNameUpdatedEvent {
   deviceName: string
 }
 
+/**
+ * P2P 心跳事件数据
+ */
+export interface P2PPeerHeartbeatEvent {
+  /** Peer ID */
+  peerId: string
+  /** Round-trip time (ms), set when the heartbeat answers our probe */
+  rttMs?: number | null
+}
+
+/**
+ * P2P 分块传输进度事件数据
+ */
+export interface P2PTransferProgressEvent {
+  /** Peer ID */
+  peerId: string
+  /** Content hash of the blob being transferred */
+  contentHash: string
+  /** inbound=pulling from the peer, outbound=serving the peer */
+  direction: 'inbound' | 'outbound'
+  transferredBytes: number
+  totalBytes: number
+}
+
+/**
+ * P2P 分块传输失败事件数据
+ */
+export interface P2PTransferFailedEvent {
+  /** Peer ID */
+  peerId: string
+  contentHash: string
+  error: string
+}
+
+/**
+ * P2P 配对撤销事件数据
+ */
+export interface P2PPairingRevokedEvent {
+  /** Peer ID */
+  peerId: string
+  /** true=the peer unpaired this device, false=our unpair notice reached the peer */
+  byPeer: boolean
+}
+
+/**
+ * 对端身份指纹与配对时记录的不一致（设备已被隔离）
+ */
+export interface P2PPeerIdentityMismatchEvent {
+  /** Peer ID */
+  peerId: string
+  /** Fingerprint pinned at pairing time */
+  expectedFingerprint: string
+  /** Fingerprint presented on the connection */
+  presentedFingerprint: string
+}
+
 /**
  * P2P 设备发现状态变化事件数据
  */
@@ -245,6 +324,135 @@ export async function unpairP2PDevice(peerId: string): Promise<void> {
   }
 }
 
+/**
+ * 同步方向 - 对应 Rust SyncDirection
+ */
+export type SyncDirection = 'bidirectional' | 'send_only' | 'receive_only'
+
+/**
+ * 收到内容后的处理方式 - 对应 Rust ClipboardApplyMode
+ */
+export type ClipboardApplyMode = 'auto_apply' | 'history_only'
+
+/**
+ * 单台已配对设备的同步策略 - 对应 Rust DeviceSyncPolicy
+ */
+export interface DeviceSyncPolicy {
+  direction: SyncDirection
+  /** 双向生效：不允许的类型既不发送也不接收 */
+  content_types: ContentTypes
+  apply_mode: ClipboardApplyMode
+}
+
+/**
+ * 获取设备同步策略，未设置时返回默认策略
+ */
+export async function getDeviceSyncPolicy(peerId: string): Promise<DeviceSyncPolicy> {
+  try {
+    return await invokeWithTrace<DeviceSyncPolicy>('get_device_sync_policy', {
+      peerId,
+    })
+  } catch (error) {
+    console.error('Failed to get device sync policy:', error)
+    throw error
+  }
+}
+
+/**
+ * 保存设备同步策略
+ */
+export async function setDeviceSyncPolicy(peerId: string, policy: DeviceSyncPolicy): Promise<void> {
+  try {
+    await invokeWithTrace('set_device_sync_policy', {
+      peerId,
+      policy,
+    })
+  } catch (error) {
+    console.error('Failed to set device sync policy:', error)
+    throw error
+  }
+}
+
+/**
+ * 手动添加对端地址（multiaddr 或 host:port），返回规范化后的地址
+ */
+export async function addManualPeer(address: string): Promise<string> {
+  try {
+    return await invokeWithTrace<string>('add_manual_peer', {
+      address,
+    })
+  } catch (error) {
+    console.error('Failed to add manual peer:', error)
+    throw error
+  }
+}
+
+/**
+ * 本机网卡地址
+ */
+export interface NetworkInterfaceInfo {
+  name: string
+  address: string
+  /** 是否符合接口允许/拒绝规则 */
+  eligible: boolean
+}
+
+/**
+ * 本机网络信息
+ */
+export interface LocalNetworkInfo {
+  listen_addresses: string[]
+  interfaces: NetworkInterfaceInfo[]
+}
+
+/**
+ * 获取本机网卡与监听地址
+ */
+export async function getLocalNetworkInfo(): Promise<LocalNetworkInfo> {
+  try {
+    return await invokeWithTrace<LocalNetworkInfo>('get_local_network_info')
+  } catch (error) {
+    console.error('Failed to get local network info:', error)
+    throw error
+  }
+}
+
+/**
+ * 带外配对邀请（二维码内容）
+ */
+export interface PairingInvitationInfo {
+  uri: string
+  /** RFC 3339 过期时间 */
+  expiresAt: string
+  fingerprint: string
+}
+
+/**
+ * 生成一次性配对邀请，供另一台设备扫描或粘贴
+ */
+export async function createPairingInvitation(): Promise<PairingInvitationInfo> {
+  try {
+    return await invokeWithTrace<PairingInvitationInfo>('create_pairing_invitation')
+  } catch (error) {
+    console.error('Failed to create pairing invitation:', error)
+    throw error
+  }
+}
+
+/**
+ * 使用配对邀请 URI 发起配对，无需比对短码
+ */
+export async function pairWithInvitation(uri: string): Promise<P2PPairingResponse> {
+  try {
+    return await invokeWithTrace<P2PPairingResponse>('pair_with_invitation', {
+      uri,
+    })
+  } catch (error) {
+    console.error('Failed to pair with invitation:', error)
+    throw error
+  }
+}
+
 /**
  * 接受 P2P 配对请求（接收方）
  */
@@ -345,6 +553,109 @@ export async function onP2PPeerDiscoveryChanged(
   }
 }
 
+/**
+ * 监听 P2P 心跳事件
+ */
+export async function onP2PPeerHeartbeat(
+  callback: (event: P2PPeerHeartbeatEvent) => void
+): Promise<() => void> {
+  try {
+    const unlisten = await listen<P2PPeerHeartbeatEvent>('p2p-peer-heartbeat', event => {
+      callback(event.payload)
+    })
+
+    return () => {
+      unlisten()
+    }
+  } catch (error) {
+    console.error('Failed to setup P2P peer heartbeat listener:', error)
+    return () => {}
+  }
+}
+
+/**
+ * 监听 P2P 分块传输进度事件
+ */
+export async function onP2PTransferProgress(
+  callback: (event: P2PTransferProgressEvent) => void
+): Promise<() => void> {
+  try {
+    const unlisten = await listen<P2PTransferProgressEvent>('p2p-transfer-progress', event => {
+      callback(event.payload)
+    })
+
+    return () => {
+      unlisten()
+    }
+  } catch (error) {
+    console.error('Failed to setup P2P transfer progress listener:', error)
+    return () => {}
+  }
+}
+
+/**
+ * 监听 P2P 分块传输失败事件
+ */
+export async function onP2PTransferFailed(
+  callback: (event: P2PTransferFailedEvent) => void
+): Promise<() => void> {
+  try {
+    const unlisten = await listen<P2PTransferFailedEvent>('p2p-transfer-failed', event => {
+      callback(event.payload)
+    })
+
+    return () => {
+      unlisten()
+    }
+  } catch (error) {
+    console.error('Failed to setup P2P transfer failed listener:', error)
+    return () => {}
+  }
+}
+
+/**
+ * 监听 P2P 配对撤销事件
+ */
+export async function onP2PPairingRevoked(
+  callback: (event: P2PPairingRevokedEvent) => void
+): Promise<() => void> {
+  try {
+    const unlisten = await listen<P2PPairingRevokedEvent>('p2p-pairing-revoked', event => {
+      callback(event.payload)
+    })
+
+    return () => {
+      unlisten()
+    }
+  } catch (error) {
+    console.error('Failed to setup P2P pairing revoked listener:', error)
+    return () => {}
+  }
+}
+
+/**
+ * 监听对端身份变化告警
+ */
+export async function onP2PPeerIdentityMismatch(
+  callback: (event: P2PPeerIdentityMismatchEvent) => void
+): Promise<() => void> {
+  try {
+    const unlisten = await listen<P2PPeerIdentityMismatchEvent>(
+      'p2p-peer-identity-mismatch',
+      event => {
+        callback(event.payload)
+      }
+    )
+
+    return () => {
+      unlisten()
+    }
+  } catch (error) {
+    console.error('Failed to setup P2P peer identity mismatch listener:', error)
+    return () => {}
+  }
+}
+
 /**
  * 监听 Space 访问完成事件（带会话幂等过滤与去重）
  */
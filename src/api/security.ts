@@ -5,6 +5,35 @@ export interface EncryptionSessionStatus {
   session_ready: boolean
 }
 
+export interface KeyRotationStatus {
+  epoch: number
+  in_progress: boolean
+}
+
+/**
+ * 受信任设备推送的、等待本机输入口令确认的密钥槽更新
+ */
+export interface PendingKeySlotUpdate {
+  peer_id: string
+  epoch: number
+  revision: number
+}
+
+/**
+ * 密钥轮换后历史数据重新加密的事件（通过 `encryption://event` 发送）
+ */
+export type ReencryptionEvent =
+  | {
+      type: 'ReencryptionProgress'
+      epoch: number
+      processed: number
+      total: number
+      failed: number
+      abandoned: number
+    }
+  | { type: 'ReencryptionCompleted'; epoch: number; abandoned: number }
+  | { type: 'KeySlotUpdatePending'; peer_id: string; epoch: number; revision: number }
+
 /**
  * 获取加密口令
  * @returns Promise，返回加密口令
@@ -70,3 +99,86 @@ export async function unlockEncryptionSession(): Promise<boolean> {
     throw error
   }
 }
+
+/**
+ * 修改加密口令
+ * @param oldPassphrase 当前口令
+ * @param newPassphrase 新口令
+ * @returns Promise，返回已同步新密钥槽的受信任设备数量
+ */
+export async function changePassphrase(
+  oldPassphrase: string,
+  newPassphrase: string
+): Promise<number> {
+  try {
+    return await invokeWithTrace('change_passphrase', { oldPassphrase, newPassphrase })
+  } catch (error) {
+    console.error('修改加密口令失败:', error)
+    throw error
+  }
+}
+
+/**
+ * 获取等待确认的密钥槽更新
+ * @returns Promise，没有待确认的更新时返回 null
+ */
+export async function getPendingKeySlotUpdate(): Promise<PendingKeySlotUpdate | null> {
+  try {
+    return await invokeWithTrace('get_pending_keyslot_update')
+  } catch (error) {
+    console.error('获取待确认的密钥槽更新失败:', error)
+    throw error
+  }
+}
+
+/**
+ * 输入口令确认受信任设备推送的密钥槽更新
+ * @param passphrase 对端设备设置的口令
+ * @returns Promise，返回应用后的密钥纪元
+ */
+export async function confirmKeySlotUpdate(passphrase: string): Promise<number> {
+  try {
+    return await invokeWithTrace('confirm_keyslot_update', { passphrase })
+  } catch (error) {
+    console.error('确认密钥槽更新失败:', error)
+    throw error
+  }
+}
+
+/**
+ * 忽略待确认的密钥槽更新
+ */
+export async function dismissKeySlotUpdate(): Promise<void> {
+  try {
+    await invokeWithTrace('dismiss_keyslot_update')
+  } catch (error) {
+    console.error('忽略密钥槽更新失败:', error)
+    throw error
+  }
+}
+
+/**
+ * 轮换主密钥，历史数据将在后台重新加密
+ * @returns Promise，返回新的密钥纪元
+ */
+export async function rotateMasterKey(): Promise<number> {
+  try {
+    return await invokeWithTrace('rotate_master_key')
+  } catch (error) {
+    console.error('轮换主密钥失败:', error)
+    throw error
+  }
+}
+
+/**
+ * 获取密钥轮换状态
+ * @returns Promise，返回当前密钥纪元及历史数据是否仍在重新加密
+ */
+export async function getKeyRotationStatus(): Promise<KeyRotationStatus> {
+  try {
+    return await invokeWithTrace('get_key_rotation_status')
+  } catch (error) {
+    console.error('获取密钥轮换状态失败:', error)
+    throw error
+  }
+}
@@ -0,0 +1,33 @@
+import { invokeWithTrace } from '@/lib/tauri-command'
+
+/**
+ * 空间摘要 - 对应 Rust SpaceSummary
+ */
+export interface SpaceSummary {
+  id: string
+  name: string
+  created_at_ms: number
+  is_active: boolean
+}
+
+/**
+ * 列出所有空间
+ */
+export async function listSpaces(): Promise<SpaceSummary[]> {
+  return await invokeWithTrace('list_spaces')
+}
+
+/**
+ * 创建空间并切换过去；新空间使用独立的口令与密钥槽
+ */
+export async function createSpace(name: string, passphrase: string): Promise<SpaceSummary> {
+  return await invokeWithTrace('create_space', { name, passphrase })
+}
+
+/**
+ * 切换活动空间
+ * @returns 新空间是否已自动解锁
+ */
+export async function switchSpace(spaceId: string): Promise<boolean> {
+  return await invokeWithTrace('switch_space', { spaceId })
+}
@@ -6,15 +6,21 @@ import {
   Laptop,
   AlertCircle,
   ShieldCheck,
+  QrCode,
+  Copy,
 } from 'lucide-react'
+import { QRCodeSVG } from 'qrcode.react'
 import React, { useState, useEffect } from 'react'
 import { useTranslation } from 'react-i18next'
 import {
+  createPairingInvitation,
   getP2PPeers,
   initiateP2PPairing,
+  pairWithInvitation,
   verifyP2PPairingPin,
   onP2PPairingVerification,
   type P2PPeerInfo,
+  type PairingInvitationInfo,
 } from '@/api/p2p'
 import { Button } from '@/components/ui/button'
 import {
@@ -24,6 +30,7 @@ import {
   DialogTitle,
   DialogDescription,
 } from '@/components/ui/dialog'
+import { Input } from '@/components/ui/input'
 import { toast } from '@/components/ui/toast'
 import { formatPeerIdForDisplay } from '@/lib/utils'
 // import { getLocalDeviceName } from '@/api/deviceConnection' // Assuming we'll add this or use existing
@@ -31,7 +38,13 @@ import { formatPeerIdForDisplay } from '@/lib/utils'
 // Mock getLocalDeviceName if not available yet, or import if available
 // For now, let's assume we might need to fetch it or just display peers.
 
-type PairingStep = 'discovery' | 'connecting' | 'pin-verify' | 'success' | 'failed'
+type PairingStep =
+  | 'discovery'
+  | 'invitation'
+  | 'connecting'
+  | 'pin-verify'
+  | 'success'
+  | 'failed'
 
 interface PairingDialogProps {
   open: boolean
@@ -49,6 +62,8 @@ export default function PairingDialog({ open, onClose, onPairingSuccess }: Pairi
   const [pinCode, setPinCode] = useState<string>('')
   const [errorMsg, setErrorMsg] = useState<string>('')
   const [isPinVerifying, setIsPinVerifying] = useState(false)
+  const [invitation, setInvitation] = useState<PairingInvitationInfo | null>(null)
+  const [invitationUri, setInvitationUri] = useState('')
 
   // Cleanup refs
   const cleanupRefs = React.useRef<(() => void)[]>([])
@@ -147,6 +162,8 @@ export default function PairingDialog({ open, onClose, onPairingSuccess }: Pairi
       setPinCode('')
       setErrorMsg('')
       setIsPinVerifying(false)
+      setInvitation(null)
+      setInvitationUri('')
 
       // 加载对等设备
       loadPeers()
@@ -184,6 +201,46 @@ export default function PairingDialog({ open, onClose, onPairingSuccess }: Pairi
     }
   }
 
+  // 邀请由对端指纹认证，成功后直接等待 complete/failed 事件，不再比对 PIN
+  const handlePairWithInvitation = async () => {
+    const uri = invitationUri.trim()
+    if (!uri) return
+
+    setSelectedPeer(null)
+    setStep('connecting')
+    setErrorMsg('')
+    try {
+      const response = await pairWithInvitation(uri)
+      setPairingSessionId(response.sessionId)
+    } catch (err) {
+      console.error('Failed to pair with invitation:', err)
+      setErrorMsg(`${t('pairing.failed.errors.invitation')}: ${String(err)}`)
+      setStep('failed')
+    }
+  }
+
+  const handleShowInvitation = async () => {
+    setErrorMsg('')
+    try {
+      setInvitation(await createPairingInvitation())
+      setStep('invitation')
+    } catch (err) {
+      console.error('Failed to create pairing invitation:', err)
+      setErrorMsg(`${t('pairing.failed.errors.createInvitation')}: ${String(err)}`)
+      setStep('failed')
+    }
+  }
+
+  const handleCopyInvitation = async () => {
+    if (!invitation) return
+    try {
+      await navigator.clipboard.writeText(invitation.uri)
+      toast.success(t('pairing.invitation.copied'))
+    } catch (err) {
+      console.error('Failed to copy pairing invitation:', err)
+    }
+  }
+
   const handlePinConfirm = async (matches: boolean) => {
     if (!pairingSessionId) return
     setIsPinVerifying(true)
@@ -220,6 +277,7 @@ export default function PairingDialog({ open, onClose, onPairingSuccess }: Pairi
         <DialogHeader>
           <DialogTitle>
             {step === 'discovery' && t('pairing.steps.discovery')}
+            {step === 'invitation' && t('pairing.steps.invitation')}
             {step === 'connecting' && t('pairing.steps.connecting')}
             {step === 'pin-verify' && t('pairing.steps.pinVerify')}
             {step === 'success' && t('pairing.steps.success')}
@@ -227,6 +285,7 @@ export default function PairingDialog({ open, onClose, onPairingSuccess }: Pairi
           </DialogTitle>
           <DialogDescription>
             {step === 'discovery' && t('pairing.discovery.description')}
+            {step === 'invitation' && t('pairing.invitation.description')}
             {step === 'pin-verify' && t('pairing.pinVerify.description')}
             {step === 'failed' && errorMsg}
           </DialogDescription>
@@ -308,6 +367,59 @@ export default function PairingDialog({ open, onClose, onPairingSuccess }: Pairi
                   </div>
                 </div>
               )}
+
+              {/* Out-of-band pairing */}
+              <div className="border-t pt-4 space-y-2">
+                <div className="flex items-center gap-2">
+                  <Input
+                    type="text"
+                    value={invitationUri}
+                    placeholder={t('pairing.invitation.uriPlaceholder')}
+                    onChange={e => setInvitationUri(e.target.value)}
+                  />
+                  <Button
+                    size="sm"
+                    onClick={handlePairWithInvitation}
+                    disabled={!invitationUri.trim()}
+                  >
+                    {t('pairing.invitation.pair')}
+                  </Button>
+                </div>
+                <Button
+                  variant="outline"
+                  size="sm"
+                  className="w-full gap-2"
+                  onClick={handleShowInvitation}
+                >
+                  <QrCode className="w-4 h-4" />
+                  {t('pairing.invitation.show')}
+                </Button>
+              </div>
+            </div>
+          )}
+
+          {step === 'invitation' && invitation && (
+            <div className="flex flex-col items-center space-y-4">
+              <div className="p-3 bg-white rounded-lg">
+                <QRCodeSVG value={invitation.uri} size={192} />
+              </div>
+              <p className="text-xs text-muted-foreground break-all font-mono w-full">
+                {invitation.uri}
+              </p>
+              <p className="text-xs text-muted-foreground">
+                {t('pairing.invitation.expiresAt', {
+                  time: new Date(invitation.expiresAt).toLocaleTimeString(),
+                })}
+              </p>
+              <div className="flex gap-2 w-full">
+                <Button variant="outline" className="flex-1" onClick={() => setStep('discovery')}>
+                  {t('pairing.invitation.back')}
+                </Button>
+                <Button className="flex-1 gap-2" onClick={handleCopyInvitation}>
+                  <Copy className="w-4 h-4" />
+                  {t('pairing.invitation.copy')}
+                </Button>
+              </div>
             </div>
           )}
 
@@ -321,7 +433,9 @@ export default function PairingDialog({ open, onClose, onPairingSuccess }: Pairi
               </div>
               <div className="text-center space-y-1">
                 <h3 className="font-medium">
-                  {t('pairing.connecting.title', { deviceName: selectedPeer?.deviceName })}
+                  {t('pairing.connecting.title', {
+                    deviceName: selectedPeer?.deviceName ?? t('pairing.discovery.unknownDevice'),
+                  })}
                 </h3>
                 <p className="text-sm text-muted-foreground">
                   {t('pairing.connecting.pleaseWait')}
@@ -4,8 +4,10 @@ import { toast } from 'sonner'
 import {
   acceptP2PPairing,
   onP2PPairingVerification,
+  onP2PPeerIdentityMismatch,
   onSpaceAccessCompleted,
   rejectP2PPairing,
+  unpairP2PDevice,
   type P2PPairingVerificationEvent,
   type SpaceAccessCompletedEvent,
 } from '@/api/p2p'
@@ -55,6 +57,29 @@ export function PairingNotificationProvider() {
     }
   }, [t])
 
+  // 身份变化告警：设备已隔离，提示用户重新配对验证或撤销
+  useEffect(() => {
+    const unlistenPromise = onP2PPeerIdentityMismatch(event => {
+      toast.error(t('devices.identityMismatch.title'), {
+        description: t('devices.identityMismatch.description', {
+          expected: event.expectedFingerprint,
+          presented: event.presentedFingerprint,
+        }),
+        action: {
+          label: t('devices.identityMismatch.revoke'),
+          onClick: () => {
+            unpairP2PDevice(event.peerId).catch(console.error)
+          },
+        },
+        duration: Infinity,
+      })
+    })
+
+    return () => {
+      unlistenPromise.then(unlisten => unlisten())
+    }
+  }, [t])
+
   useEffect(() => {
     const unlistenPromise = onP2PPairingVerification((event: P2PPairingVerificationEvent) => {
       const currentSessionId = activeSessionIdRef.current
@@ -1,45 +1,139 @@
-import React from 'react'
+import React, { useEffect, useState } from 'react'
 import { useTranslation } from 'react-i18next'
+import { getDeviceSyncPolicy, setDeviceSyncPolicy, type DeviceSyncPolicy } from '@/api/p2p'
+import type { ContentTypes } from '@/types/setting'
 
 interface DeviceSettingsPanelProps {
   deviceId: string
   deviceName: string
 }
 
-const DeviceSettingsPanel: React.FC<DeviceSettingsPanelProps> = () => {
+const DEFAULT_POLICY: DeviceSyncPolicy = {
+  direction: 'bidirectional',
+  content_types: {
+    text: true,
+    image: true,
+    link: true,
+    file: true,
+    code_snippet: true,
+    rich_text: true,
+  },
+  apply_mode: 'auto_apply',
+}
+
+const CONTENT_TYPE_RULES: { key: string; field: keyof ContentTypes }[] = [
+  { key: 'syncText', field: 'text' },
+  { key: 'syncRichText', field: 'rich_text' },
+  { key: 'syncImage', field: 'image' },
+  { key: 'syncLink', field: 'link' },
+  { key: 'syncFile', field: 'file' },
+  { key: 'syncCode', field: 'code_snippet' },
+]
+
+interface ToggleProps {
+  checked: boolean
+  disabled: boolean
+  label: string
+  onChange: (checked: boolean) => void
+}
+
+const Toggle: React.FC<ToggleProps> = ({ checked, disabled, label, onChange }) => (
+  <label
+    className={`flex items-center shrink-0 ${
+      disabled ? 'cursor-not-allowed opacity-70' : 'cursor-pointer'
+    }`}
+  >
+    <div className="relative">
+      <input
+        type="checkbox"
+        className="sr-only peer"
+        aria-label={label}
+        checked={checked}
+        disabled={disabled}
+        onChange={e => onChange(e.target.checked)}
+      />
+      <div className="block w-9 h-5 rounded-full transition-colors bg-muted peer-checked:bg-primary"></div>
+      <div className="absolute left-1 top-1 w-3 h-3 rounded-full transition-transform transform peer-checked:translate-x-4 bg-white"></div>
+    </div>
+  </label>
+)
+
+const DeviceSettingsPanel: React.FC<DeviceSettingsPanelProps> = ({ deviceId }) => {
   const { t } = useTranslation()
+  const [policy, setPolicy] = useState<DeviceSyncPolicy | null>(null)
+  const [saving, setSaving] = useState(false)
+  const [error, setError] = useState<string | null>(null)
 
-  const syncRules = [
-    {
-      key: 'autoSync',
-      defaultChecked: true,
-      editable: false,
-    },
+  useEffect(() => {
+    let cancelled = false
+    getDeviceSyncPolicy(deviceId)
+      .then(loaded => {
+        if (!cancelled) setPolicy(loaded)
+      })
+      .catch(err => {
+        if (!cancelled) setError(`${t('devices.settings.errors.load')}: ${String(err)}`)
+      })
+    return () => {
+      cancelled = true
+    }
+  }, [deviceId, t])
+
+  // 先更新界面，保存失败时回滚
+  const savePolicy = async (next: DeviceSyncPolicy) => {
+    const previous = policy
+    setPolicy(next)
+    setSaving(true)
+    try {
+      await setDeviceSyncPolicy(deviceId, next)
+      setError(null)
+    } catch (err) {
+      setPolicy(previous)
+      setError(`${t('devices.settings.errors.save')}: ${String(err)}`)
+    } finally {
+      setSaving(false)
+    }
+  }
+
+  const disabled = !policy || saving
+  const sends = policy?.direction !== 'receive_only'
+  const receives = policy?.direction !== 'send_only'
+
+  // 至少保留一个方向；两个方向都关闭等同于取消配对
+  const setDirection = (send: boolean, receive: boolean) => {
+    if (!policy || (!send && !receive)) return
+    const direction = send && receive ? 'bidirectional' : send ? 'send_only' : 'receive_only'
+    void savePolicy({ ...policy, direction })
+  }
+
+  const setContentType = (field: keyof ContentTypes, checked: boolean) => {
+    if (!policy) return
+    void savePolicy({ ...policy, content_types: { ...policy.content_types, [field]: checked } })
+  }
+
+  const permissionItems = [
     {
-      key: 'syncText',
-      defaultChecked: true,
-      editable: true,
-      mvp: true,
+      key: 'sendToDevice',
+      checked: sends,
+      disabled: disabled || (sends && !receives),
+      onChange: (checked: boolean) => setDirection(checked, receives),
     },
     {
-      key: 'syncImage',
-      defaultChecked: true,
-      editable: false,
+      key: 'receiveFromDevice',
+      checked: receives,
+      disabled: disabled || (receives && !sends),
+      onChange: (checked: boolean) => setDirection(sends, checked),
     },
     {
-      key: 'syncFile',
-      defaultChecked: false,
-      editable: false,
+      key: 'writeClipboard',
+      checked: policy?.apply_mode !== 'history_only',
+      disabled: disabled || !receives,
+      onChange: (checked: boolean) => {
+        if (!policy) return
+        void savePolicy({ ...policy, apply_mode: checked ? 'auto_apply' : 'history_only' })
+      },
     },
   ]
 
-  const permissionItems = [
-    { key: 'readClipboard', checked: true, editable: false },
-    { key: 'writeClipboard', checked: true, editable: false },
-    { key: 'historyAccess', checked: true, editable: false },
-    { key: 'fileTransfer', checked: false, editable: false },
-  ]
-
   return (
     <div className="space-y-6">
       <div>
@@ -49,7 +143,8 @@ const DeviceSettingsPanel: React.FC<DeviceSettingsPanelProps> = () => {
           </h4>
           <button
             type="button"
-            disabled
+            disabled={disabled}
+            onClick={() => void savePolicy(DEFAULT_POLICY)}
             className="text-xs px-2 py-1 rounded-md text-muted-foreground hover:bg-muted hover:text-foreground transition-colors"
           >
             {t('devices.settings.sync.restoreDefaults')}
@@ -57,67 +152,22 @@ const DeviceSettingsPanel: React.FC<DeviceSettingsPanelProps> = () => {
         </div>
 
         <div className="divide-y divide-border/40">
-          {syncRules.map(rule => (
-            <div
-              key={rule.key}
-              className={`flex items-center justify-between py-3 px-1 ${
-                rule.editable ? '' : 'bg-muted/30 text-muted-foreground'
-              }`}
-            >
+          {CONTENT_TYPE_RULES.map(rule => (
+            <div key={rule.key} className="flex items-center justify-between py-3 px-1">
               <div className="pr-4">
-                <div className="flex items-center gap-2">
-                  <h5
-                    className={`text-sm font-medium ${
-                      rule.editable ? 'text-foreground' : 'text-muted-foreground'
-                    }`}
-                  >
-                    {t(`devices.settings.sync.rules.${rule.key}.title`)}
-                  </h5>
-                  {rule.mvp && (
-                    <span className="text-[10px] leading-none rounded px-1.5 py-1 bg-primary/10 text-primary border border-primary/20">
-                      {t('devices.settings.badges.mvp')}
-                    </span>
-                  )}
-                  {!rule.editable && (
-                    <span className="text-[10px] leading-none rounded px-1.5 py-1 bg-muted text-muted-foreground">
-                      {t('devices.settings.readOnly')}
-                    </span>
-                  )}
-                </div>
-                <p
-                  className={`text-xs mt-0.5 ${
-                    rule.editable ? 'text-muted-foreground' : 'text-muted-foreground/80'
-                  }`}
-                >
+                <h5 className="text-sm font-medium text-foreground">
+                  {t(`devices.settings.sync.rules.${rule.key}.title`)}
+                </h5>
+                <p className="text-xs mt-0.5 text-muted-foreground">
                   {t(`devices.settings.sync.rules.${rule.key}.description`)}
                 </p>
               </div>
-              <label
-                className={`flex items-center shrink-0 ${
-                  rule.editable ? 'cursor-pointer' : 'cursor-not-allowed opacity-70'
-                }`}
-              >
-                <div className="relative">
-                  <input
-                    type="checkbox"
-                    className="sr-only peer"
-                    defaultChecked={rule.defaultChecked}
-                    disabled={!rule.editable}
-                  />
-                  <div
-                    className={`block w-9 h-5 rounded-full transition-colors ${
-                      rule.editable
-                        ? 'bg-muted peer-checked:bg-primary'
-                        : 'bg-muted peer-checked:bg-muted-foreground/40'
-                    }`}
-                  ></div>
-                  <div
-                    className={`absolute left-1 top-1 w-3 h-3 rounded-full transition-transform transform peer-checked:translate-x-4 ${
-                      rule.editable ? 'bg-white' : 'bg-muted-foreground/40'
-                    }`}
-                  ></div>
-                </div>
-              </label>
+              <Toggle
+                checked={policy?.content_types[rule.field] ?? false}
+                disabled={disabled}
+                label={t(`devices.settings.sync.rules.${rule.key}.title`)}
+                onChange={checked => setContentType(rule.field, checked)}
+              />
             </div>
           ))}
         </div>
@@ -132,56 +182,27 @@ const DeviceSettingsPanel: React.FC<DeviceSettingsPanelProps> = () => {
 
         <div className="divide-y divide-border/40">
           {permissionItems.map(perm => (
-            <div
-              key={perm.key}
-              className={`flex items-center justify-between py-3 px-1 ${
-                perm.editable ? '' : 'bg-muted/30 text-muted-foreground'
-              }`}
-            >
-              <div className="flex items-center gap-2">
-                <span
-                  className={`text-sm font-medium ${
-                    perm.editable ? 'text-foreground' : 'text-muted-foreground'
-                  }`}
-                >
-                  {t(`devices.settings.permissions.items.${perm.key}`)}
-                </span>
-                {!perm.editable && (
-                  <span className="text-[10px] leading-none rounded px-1.5 py-1 bg-muted text-muted-foreground">
-                    {t('devices.settings.readOnly')}
-                  </span>
-                )}
+            <div key={perm.key} className="flex items-center justify-between py-3 px-1">
+              <div className="pr-4">
+                <h5 className="text-sm font-medium text-foreground">
+                  {t(`devices.settings.permissions.items.${perm.key}.title`)}
+                </h5>
+                <p className="text-xs mt-0.5 text-muted-foreground">
+                  {t(`devices.settings.permissions.items.${perm.key}.description`)}
+                </p>
               </div>
-              <label
-                className={`flex items-center shrink-0 ${
-                  perm.editable ? 'cursor-pointer' : 'cursor-not-allowed opacity-70'
-                }`}
-              >
-                <div className="relative">
-                  <input
-                    type="checkbox"
-                    className="sr-only peer"
-                    defaultChecked={perm.checked}
-                    disabled={!perm.editable}
-                  />
-                  <div
-                    className={`block w-9 h-5 rounded-full transition-colors ${
-                      perm.editable
-                        ? 'bg-muted peer-checked:bg-primary'
-                        : 'bg-muted peer-checked:bg-muted-foreground/40'
-                    }`}
-                  ></div>
-                  <div
-                    className={`absolute left-1 top-1 w-3 h-3 rounded-full transition-transform transform peer-checked:translate-x-4 ${
-                      perm.editable ? 'bg-white' : 'bg-muted-foreground/40'
-                    }`}
-                  ></div>
-                </div>
-              </label>
+              <Toggle
+                checked={perm.checked}
+                disabled={perm.disabled}
+                label={t(`devices.settings.permissions.items.${perm.key}.title`)}
+                onChange={perm.onChange}
+              />
             </div>
           ))}
         </div>
       </div>
+
+      {error && <p className="text-xs text-red-500 px-1">{error}</p>}
     </div>
   )
 }
@@ -8,11 +8,21 @@ import {
   RefreshCw,
   Plus,
   ChevronRight,
+  ShieldAlert,
 } from 'lucide-react'
 import React, { useEffect, useState } from 'react'
 import { useTranslation } from 'react-i18next'
 import DeviceSettingsPanel from './DeviceSettingsPanel'
-import { onP2PPeerConnectionChanged, onP2PPeerNameUpdated, unpairP2PDevice } from '@/api/p2p'
+import {
+  onP2PPairingRevoked,
+  onP2PPeerConnectionChanged,
+  onP2PPeerHeartbeat,
+  onP2PPeerIdentityMismatch,
+  onP2PPeerNameUpdated,
+  unpairP2PDevice,
+  type PairedPeer,
+  type PeerPresence,
+} from '@/api/p2p'
 import { formatPeerIdForDisplay } from '@/lib/utils'
 import { useAppDispatch, useAppSelector } from '@/store/hooks'
 import {
@@ -20,6 +30,7 @@ import {
   clearPairedDevicesError,
   updatePeerConnectionStatus,
   updatePeerDeviceName,
+  updatePeerHeartbeat,
 } from '@/store/slices/devicesSlice'
 
 interface OtherDeviceProps {
@@ -39,6 +50,9 @@ const OtherDevice: React.FC<OtherDeviceProps> = ({ onAddDevice }) => {
 
     let unlistenConnection: (() => void) | undefined
     let unlistenName: (() => void) | undefined
+    let unlistenRevoked: (() => void) | undefined
+    let unlistenHeartbeat: (() => void) | undefined
+    let unlistenIdentity: (() => void) | undefined
 
     const setupConnectionListener = async () => {
       unlistenConnection = await onP2PPeerConnectionChanged(event => {
@@ -63,12 +77,42 @@ const OtherDevice: React.FC<OtherDeviceProps> = ({ onAddDevice }) => {
       })
     }
 
+    const setupRevokedListener = async () => {
+      unlistenRevoked = await onP2PPairingRevoked(() => {
+        dispatch(fetchPairedDevices())
+      })
+    }
+
+    const setupHeartbeatListener = async () => {
+      unlistenHeartbeat = await onP2PPeerHeartbeat(event => {
+        dispatch(
+          updatePeerHeartbeat({
+            peerId: event.peerId,
+            rttMs: event.rttMs,
+            seenAt: new Date().toISOString(),
+          })
+        )
+      })
+    }
+
+    const setupIdentityListener = async () => {
+      unlistenIdentity = await onP2PPeerIdentityMismatch(() => {
+        dispatch(fetchPairedDevices())
+      })
+    }
+
     setupConnectionListener()
     setupNameListener()
+    setupRevokedListener()
+    setupHeartbeatListener()
+    setupIdentityListener()
 
     return () => {
       unlistenConnection?.()
       unlistenName?.()
+      unlistenRevoked?.()
+      unlistenHeartbeat?.()
+      unlistenIdentity?.()
     }
   }, [dispatch])
 
@@ -109,6 +153,21 @@ const OtherDevice: React.FC<OtherDeviceProps> = ({ onAddDevice }) => {
     return Monitor
   }
 
+  const getPresence = (device: PairedPeer): PeerPresence =>
+    device.presence ?? (device.connected ? 'online' : 'unreachable')
+
+  const presenceStyles: Record<PeerPresence, string> = {
+    online: 'bg-green-500/10 text-green-600 border-green-500/20',
+    idle: 'bg-amber-500/10 text-amber-600 border-amber-500/20',
+    unreachable: 'bg-muted text-muted-foreground border-border',
+  }
+
+  const presenceLabels: Record<PeerPresence, string> = {
+    online: t('devices.list.status.online'),
+    idle: t('devices.list.status.idle'),
+    unreachable: t('devices.list.status.offline'),
+  }
+
   const getIconColor = (index: number) => {
     const colors = [
       'text-blue-500 bg-blue-500/10 border-blue-500/20',
@@ -187,6 +246,7 @@ const OtherDevice: React.FC<OtherDeviceProps> = ({ onAddDevice }) => {
           const Icon = getDeviceIcon(device.deviceName)
           const isExpanded = expandedDeviceId === device.peerId
           const iconColor = getIconColor(index)
+          const presence = getPresence(device)
 
           return (
             <div key={device.peerId} className="flex flex-col bg-card/30">
@@ -219,24 +279,34 @@ const OtherDevice: React.FC<OtherDeviceProps> = ({ onAddDevice }) => {
                         {device.connected && (
                           <span className="flex h-2 w-2 rounded-full bg-green-500 animate-pulse" />
                         )}
+                        {device.pairingState === 'Quarantined' && (
+                          <span
+                            className="flex items-center gap-1 text-xs px-2 py-0.5 rounded-full border bg-destructive/10 text-destructive border-destructive/20"
+                            title={t('devices.identityMismatch.hint')}
+                          >
+                            <ShieldAlert className="h-3 w-3" />
+                            {t('devices.list.status.quarantined')}
+                          </span>
+                        )}
                       </div>
                       <span className="text-xs text-muted-foreground font-mono">
                         {formatPeerIdForDisplay(device.peerId)}
+                        {device.appVersion &&
+                          ` · ${t('devices.list.labels.appVersion', { version: device.appVersion })}`}
                       </span>
                     </div>
                   </div>
 
                   <div className="flex items-center gap-3">
+                    {presence === 'online' && device.latencyMs != null && (
+                      <span className="text-xs text-muted-foreground tabular-nums">
+                        {t('devices.list.status.latency', { ms: device.latencyMs })}
+                      </span>
+                    )}
                     <div
-                      className={`text-xs px-2 py-0.5 rounded-full border ${
-                        device.connected
-                          ? 'bg-green-500/10 text-green-600 border-green-500/20'
-                          : 'bg-muted text-muted-foreground border-border'
-                      }`}
+                      className={`text-xs px-2 py-0.5 rounded-full border ${presenceStyles[presence]}`}
                     >
-                      {device.connected
-                        ? t('devices.list.status.online')
-                        : t('devices.list.status.offline')}
+                      {presenceLabels[presence]}
                     </div>
 
                     <ChevronRight
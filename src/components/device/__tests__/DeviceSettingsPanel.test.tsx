@@ -1,6 +1,7 @@
-import { render, screen } from '@testing-library/react'
-import { describe, it, expect, vi } from 'vitest'
+import { render, screen, fireEvent, waitFor } from '@testing-library/react'
+import { describe, it, expect, vi, beforeEach } from 'vitest'
 import DeviceSettingsPanel from '../DeviceSettingsPanel'
+import * as p2pApi from '@/api/p2p'
 
 vi.mock('framer-motion', () => ({
   motion: {
@@ -13,23 +14,66 @@ vi.mock('framer-motion', () => ({
   AnimatePresence: ({ children }: any) => <>{children}</>,
 }))
 
+vi.mock('@/api/p2p', () => ({
+  getDeviceSyncPolicy: vi.fn(),
+  setDeviceSyncPolicy: vi.fn(() => Promise.resolve()),
+}))
+
+const defaultPolicy: p2pApi.DeviceSyncPolicy = {
+  direction: 'bidirectional',
+  content_types: {
+    text: true,
+    image: true,
+    link: true,
+    file: true,
+    code_snippet: true,
+    rich_text: true,
+  },
+  apply_mode: 'auto_apply',
+}
+
 describe('DeviceSettingsPanel', () => {
   const defaultProps = {
     deviceId: 'test-device-id',
     deviceName: 'Test Device',
   }
 
-  it('renders sync rules section', () => {
+  beforeEach(() => {
+    vi.clearAllMocks()
+    vi.mocked(p2pApi.getDeviceSyncPolicy).mockResolvedValue(defaultPolicy)
+  })
+
+  it('renders content types and permissions from the stored policy', async () => {
     render(<DeviceSettingsPanel {...defaultProps} />)
-    expect(screen.getByText('同步设置')).toBeDefined()
-    expect(screen.getByText('自动同步')).toBeDefined()
-    expect(screen.getByText('同步文本')).toBeDefined()
+
+    expect(screen.getByText('Sync Settings')).toBeInTheDocument()
+    expect(screen.getByText('Access Permissions')).toBeInTheDocument()
+    await waitFor(() => expect(screen.getByLabelText('Sync Images')).toBeEnabled())
+    expect(screen.getByLabelText('Sync Images')).toBeChecked()
+    expect(screen.getByLabelText('Write clipboard')).toBeChecked()
+    expect(p2pApi.getDeviceSyncPolicy).toHaveBeenCalledWith('test-device-id')
   })
 
-  it('renders permissions section', () => {
+  it('saves content type and direction changes', async () => {
     render(<DeviceSettingsPanel {...defaultProps} />)
-    expect(screen.getByText('访问权限')).toBeDefined()
-    expect(screen.getByText('读取剪贴板')).toBeDefined()
-    expect(screen.getByText('写入剪贴板')).toBeDefined()
+    await waitFor(() => expect(screen.getByLabelText('Sync Images')).toBeEnabled())
+
+    fireEvent.click(screen.getByLabelText('Sync Images'))
+    await waitFor(() =>
+      expect(p2pApi.setDeviceSyncPolicy).toHaveBeenCalledWith('test-device-id', {
+        ...defaultPolicy,
+        content_types: { ...defaultPolicy.content_types, image: false },
+      })
+    )
+
+    await waitFor(() => expect(screen.getByLabelText('Receive from this device')).toBeEnabled())
+    fireEvent.click(screen.getByLabelText('Receive from this device'))
+    await waitFor(() =>
+      expect(p2pApi.setDeviceSyncPolicy).toHaveBeenLastCalledWith(
+        'test-device-id',
+        expect.objectContaining({ direction: 'send_only' })
+      )
+    )
+    expect(screen.getByLabelText('Send to this device')).toBeDisabled()
   })
 })
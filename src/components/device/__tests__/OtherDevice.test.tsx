@@ -20,6 +20,9 @@ vi.mock('@/store/hooks', () => ({
 vi.mock('@/api/p2p', () => ({
   onP2PPeerConnectionChanged: vi.fn(() => Promise.resolve(() => {})),
   onP2PPeerNameUpdated: vi.fn(() => Promise.resolve(() => {})),
+  onP2PPairingRevoked: vi.fn(() => Promise.resolve(() => {})),
+  onP2PPeerHeartbeat: vi.fn(() => Promise.resolve(() => {})),
+  onP2PPeerIdentityMismatch: vi.fn(() => Promise.resolve(() => {})),
   unpairP2PDevice: vi.fn(),
 }))
 
@@ -28,6 +31,7 @@ vi.mock('@/store/slices/devicesSlice', () => ({
   clearPairedDevicesError: vi.fn(() => ({ type: 'devices/clearPairedDevicesError' })),
   updatePeerConnectionStatus: vi.fn(),
   updatePeerDeviceName: vi.fn(),
+  updatePeerHeartbeat: vi.fn(),
 }))
 
 describe('OtherDevice', () => {
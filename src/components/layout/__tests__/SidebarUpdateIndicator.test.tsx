@@ -72,6 +72,7 @@ describe('Sidebar update indicator', () => {
           updateSyncSetting: vi.fn(),
           updateSecuritySetting: vi.fn(),
           updateRetentionPolicy: vi.fn(),
+          updateNetworkSetting: vi.fn(),
         }}
       >
         <UpdateContext.Provider
@@ -105,6 +106,7 @@ describe('Sidebar update indicator', () => {
           updateSyncSetting: vi.fn(),
           updateSecuritySetting: vi.fn(),
           updateRetentionPolicy: vi.fn(),
+          updateNetworkSetting: vi.fn(),
         }}
       >
         <UpdateContext.Provider
@@ -1,29 +1,371 @@
+import React, { useEffect, useState } from 'react'
 import { useTranslation } from 'react-i18next'
+import { addManualPeer, getLocalNetworkInfo, type LocalNetworkInfo } from '@/api/p2p'
+import { Button, Input, Switch } from '@/components/ui'
 import { Card, CardContent } from '@/components/ui/card'
+import { useSetting } from '@/hooks/useSetting'
+
+// 接口规则以逗号分隔输入，保存为去重后的列表
+const parseRules = (value: string): string[] =>
+  Array.from(
+    new Set(
+      value
+        .split(',')
+        .map(rule => rule.trim())
+        .filter(Boolean)
+    )
+  )
 
-/**
- * Network Section Placeholder
- *
- * 网络设置功能在新架构中尚未实现。
- * 后端 Settings 结构中不存在 network 字段。
- * 此组件作为占位符，待后端实现后替换。
- */
 const NetworkSection: React.FC = () => {
   const { t } = useTranslation()
+  const { setting, error, updateNetworkSetting } = useSetting()
+
+  const [listenPort, setListenPort] = useState('0')
+  const [listenPortError, setListenPortError] = useState<string | null>(null)
+
+  const [peerInput, setPeerInput] = useState('')
+  const [peerError, setPeerError] = useState<string | null>(null)
+  const [adding, setAdding] = useState(false)
+
+  const [allowedInterfaces, setAllowedInterfaces] = useState('')
+  const [deniedInterfaces, setDeniedInterfaces] = useState('')
+
+  const [relayAddress, setRelayAddress] = useState('')
+  const [relayNamespace, setRelayNamespace] = useState('')
+
+  const [networkInfo, setNetworkInfo] = useState<LocalNetworkInfo | null>(null)
+  const listenAddresses = networkInfo?.listen_addresses ?? []
+
+  const bootstrapPeers = setting?.network?.bootstrap_peers ?? []
+
+  useEffect(() => {
+    if (setting) {
+      setListenPort(String(setting.network?.listen_port ?? 0))
+      setAllowedInterfaces((setting.network?.allowed_interfaces ?? []).join(', '))
+      setDeniedInterfaces((setting.network?.denied_interfaces ?? []).join(', '))
+      setRelayAddress(setting.network?.relay?.address ?? '')
+      setRelayNamespace(setting.network?.relay?.namespace ?? '')
+    }
+  }, [setting])
+
+  useEffect(() => {
+    getLocalNetworkInfo()
+      .then(setNetworkInfo)
+      .catch(() => setNetworkInfo(null))
+  }, [])
+
+  const handleInterfaceRulesBlur = () => {
+    const allowed = parseRules(allowedInterfaces)
+    const denied = parseRules(deniedInterfaces)
+    const current = setting?.network
+    if (
+      allowed.join(',') !== (current?.allowed_interfaces ?? []).join(',') ||
+      denied.join(',') !== (current?.denied_interfaces ?? []).join(',')
+    ) {
+      updateNetworkSetting({ allowed_interfaces: allowed, denied_interfaces: denied })
+    }
+  }
+
+  const relay = {
+    enabled: setting?.network?.relay?.enabled ?? false,
+    address: setting?.network?.relay?.address ?? '',
+    namespace: setting?.network?.relay?.namespace ?? '',
+  }
+
+  const handleRelayBlur = () => {
+    const address = relayAddress.trim()
+    const namespace = relayNamespace.trim()
+    if (address !== relay.address || namespace !== relay.namespace) {
+      updateNetworkSetting({ relay: { ...relay, address, namespace } })
+    }
+  }
+
+  // 失焦时校验并保存监听端口（重启后生效）
+  const handleListenPortBlur = () => {
+    const value = listenPort.trim()
+    const port = Number(value)
+    if (!/^\d+$/.test(value) || port > 65535) {
+      setListenPortError(t('settings.sections.network.listenPort.errors.invalid'))
+      return
+    }
+
+    setListenPortError(null)
+    if (port !== (setting?.network?.listen_port ?? 0)) {
+      updateNetworkSetting({ listen_port: port })
+    }
+  }
+
+  // 后端负责规范化、持久化与拨号，这里同步本地上下文避免后续保存覆盖
+  const handleAddPeer = async () => {
+    if (!peerInput.trim()) return
+
+    setAdding(true)
+    try {
+      const address = await addManualPeer(peerInput)
+      if (!bootstrapPeers.includes(address)) {
+        await updateNetworkSetting({ bootstrap_peers: [...bootstrapPeers, address] })
+      }
+      setPeerInput('')
+      setPeerError(null)
+    } catch (err) {
+      setPeerError(`${t('settings.sections.network.manualPeers.errors.add')}: ${String(err)}`)
+    } finally {
+      setAdding(false)
+    }
+  }
+
+  const handleRemovePeer = (address: string) => {
+    updateNetworkSetting({
+      bootstrap_peers: bootstrapPeers.filter(peer => peer !== address),
+    })
+  }
+
+  if (error) {
+    return (
+      <div className="text-destructive py-4">
+        {t('settings.sections.sync.loadError')} {error}
+      </div>
+    )
+  }
 
   return (
-    <Card>
-      <CardContent className="pt-6">
-        <div className="text-center py-8">
+    <>
+      {/* TCP-only switch */}
+      <Card>
+        <div className="flex items-center gap-4 mb-4 px-6 pt-6">
+          <h3 className="text-sm font-medium text-muted-foreground whitespace-nowrap">
+            {t('settings.sections.network.tcpOnly.label')}
+          </h3>
+          <div className="h-px flex-1 bg-border/50"></div>
+        </div>
+        <CardContent className="pt-0">
+          <div className="flex items-center justify-between py-2">
+            <p className="text-sm text-muted-foreground">
+              {t('settings.sections.network.tcpOnly.description')}
+            </p>
+            <Switch
+              id="tcp-only"
+              checked={setting?.network?.tcp_only ?? false}
+              onCheckedChange={checked => updateNetworkSetting({ tcp_only: checked })}
+            />
+          </div>
+        </CardContent>
+      </Card>
+
+      {/* Listen port */}
+      <Card>
+        <div className="flex items-center gap-4 mb-4 px-6 pt-6">
+          <h3 className="text-sm font-medium text-muted-foreground whitespace-nowrap">
+            {t('settings.sections.network.listenPort.label')}
+          </h3>
+          <div className="h-px flex-1 bg-border/50"></div>
+        </div>
+        <CardContent className="pt-0">
+          <div className="flex items-center justify-between gap-4 py-2">
+            <p className="text-sm text-muted-foreground">
+              {t('settings.sections.network.listenPort.description')}
+            </p>
+            <div className="flex flex-col items-end gap-1">
+              <Input
+                type="text"
+                value={listenPort}
+                onChange={e => setListenPort(e.target.value)}
+                onBlur={handleListenPortBlur}
+                className={listenPortError ? 'border-red-500 w-32' : 'w-32'}
+              />
+              {listenPortError && <p className="text-xs text-red-500">{listenPortError}</p>}
+            </div>
+          </div>
+        </CardContent>
+      </Card>
+
+      {/* Interface rules */}
+      <Card>
+        <div className="flex items-center gap-4 mb-4 px-6 pt-6">
+          <h3 className="text-sm font-medium text-muted-foreground whitespace-nowrap">
+            {t('settings.sections.network.interfaces.label')}
+          </h3>
+          <div className="h-px flex-1 bg-border/50"></div>
+        </div>
+        <CardContent className="pt-0 space-y-3">
           <p className="text-sm text-muted-foreground">
-            {t('settings.sections.network.placeholder') || '网络设置功能暂未实现'}
+            {t('settings.sections.network.interfaces.description')}
           </p>
-          <p className="text-xs text-muted-foreground mt-2">
-            Network settings are not yet available in the new architecture.
+          <div className="flex items-center justify-between gap-4">
+            <span className="text-sm whitespace-nowrap">
+              {t('settings.sections.network.interfaces.allowed')}
+            </span>
+            <Input
+              type="text"
+              value={allowedInterfaces}
+              placeholder={t('settings.sections.network.interfaces.placeholder')}
+              onChange={e => setAllowedInterfaces(e.target.value)}
+              onBlur={handleInterfaceRulesBlur}
+              className="w-72"
+            />
+          </div>
+          <div className="flex items-center justify-between gap-4">
+            <span className="text-sm whitespace-nowrap">
+              {t('settings.sections.network.interfaces.denied')}
+            </span>
+            <Input
+              type="text"
+              value={deniedInterfaces}
+              placeholder={t('settings.sections.network.interfaces.placeholder')}
+              onChange={e => setDeniedInterfaces(e.target.value)}
+              onBlur={handleInterfaceRulesBlur}
+              className="w-72"
+            />
+          </div>
+          {networkInfo && networkInfo.interfaces.length > 0 && (
+            <ul className="space-y-1">
+              {networkInfo.interfaces.map(iface => (
+                <li
+                  key={`${iface.name}-${iface.address}`}
+                  className="flex items-center justify-between gap-2 text-xs"
+                >
+                  <code className="break-all">
+                    {iface.name} · {iface.address}
+                  </code>
+                  <span className={iface.eligible ? 'text-green-600' : 'text-muted-foreground'}>
+                    {iface.eligible
+                      ? t('settings.sections.network.interfaces.eligible')
+                      : t('settings.sections.network.interfaces.excluded')}
+                  </span>
+                </li>
+              ))}
+            </ul>
+          )}
+        </CardContent>
+      </Card>
+
+      {/* Relay / rendezvous */}
+      <Card>
+        <div className="flex items-center gap-4 mb-4 px-6 pt-6">
+          <h3 className="text-sm font-medium text-muted-foreground whitespace-nowrap">
+            {t('settings.sections.network.relay.label')}
+          </h3>
+          <div className="h-px flex-1 bg-border/50"></div>
+        </div>
+        <CardContent className="pt-0 space-y-3">
+          <div className="flex items-center justify-between py-2">
+            <p className="text-sm text-muted-foreground">
+              {t('settings.sections.network.relay.description')}
+            </p>
+            <Switch
+              id="relay-enabled"
+              checked={relay.enabled}
+              onCheckedChange={checked =>
+                updateNetworkSetting({ relay: { ...relay, enabled: checked } })
+              }
+            />
+          </div>
+          <div className="flex items-center justify-between gap-4">
+            <span className="text-sm whitespace-nowrap">
+              {t('settings.sections.network.relay.address')}
+            </span>
+            <Input
+              type="text"
+              value={relayAddress}
+              placeholder={t('settings.sections.network.relay.addressPlaceholder')}
+              onChange={e => setRelayAddress(e.target.value)}
+              onBlur={handleRelayBlur}
+              disabled={!relay.enabled}
+              className="w-72"
+            />
+          </div>
+          <div className="flex items-center justify-between gap-4">
+            <span className="text-sm whitespace-nowrap">
+              {t('settings.sections.network.relay.namespace')}
+            </span>
+            <Input
+              type="text"
+              value={relayNamespace}
+              placeholder={t('settings.sections.network.relay.namespacePlaceholder')}
+              onChange={e => setRelayNamespace(e.target.value)}
+              onBlur={handleRelayBlur}
+              disabled={!relay.enabled}
+              className="w-72"
+            />
+          </div>
+        </CardContent>
+      </Card>
+
+      {/* Manual peers */}
+      <Card>
+        <div className="flex items-center gap-4 mb-4 px-6 pt-6">
+          <h3 className="text-sm font-medium text-muted-foreground whitespace-nowrap">
+            {t('settings.sections.network.manualPeers.label')}
+          </h3>
+          <div className="h-px flex-1 bg-border/50"></div>
+        </div>
+        <CardContent className="pt-0 space-y-3">
+          <p className="text-sm text-muted-foreground">
+            {t('settings.sections.network.manualPeers.description')}
           </p>
+          <div className="flex items-center gap-2">
+            <Input
+              type="text"
+              value={peerInput}
+              placeholder={t('settings.sections.network.manualPeers.placeholder')}
+              onChange={e => setPeerInput(e.target.value)}
+              onKeyDown={e => {
+                if (e.key === 'Enter') handleAddPeer()
+              }}
+              className={peerError ? 'border-red-500' : undefined}
+            />
+            <Button onClick={handleAddPeer} disabled={adding || !peerInput.trim()}>
+              {t('settings.sections.network.manualPeers.add')}
+            </Button>
+          </div>
+          {peerError && <p className="text-xs text-red-500">{peerError}</p>}
+          {bootstrapPeers.length === 0 ? (
+            <p className="text-xs text-muted-foreground">
+              {t('settings.sections.network.manualPeers.empty')}
+            </p>
+          ) : (
+            <ul className="space-y-1">
+              {bootstrapPeers.map(address => (
+                <li key={address} className="flex items-center justify-between gap-2 py-1">
+                  <code className="text-xs break-all">{address}</code>
+                  <Button variant="ghost" size="sm" onClick={() => handleRemovePeer(address)}>
+                    {t('settings.sections.network.manualPeers.remove')}
+                  </Button>
+                </li>
+              ))}
+            </ul>
+          )}
+        </CardContent>
+      </Card>
+
+      {/* Local listen addresses */}
+      <Card>
+        <div className="flex items-center gap-4 mb-4 px-6 pt-6">
+          <h3 className="text-sm font-medium text-muted-foreground whitespace-nowrap">
+            {t('settings.sections.network.listenAddresses.label')}
+          </h3>
+          <div className="h-px flex-1 bg-border/50"></div>
         </div>
-      </CardContent>
-    </Card>
+        <CardContent className="pt-0 space-y-2">
+          <p className="text-sm text-muted-foreground">
+            {t('settings.sections.network.listenAddresses.description')}
+          </p>
+          {listenAddresses.length === 0 ? (
+            <p className="text-xs text-muted-foreground">
+              {t('settings.sections.network.listenAddresses.empty')}
+            </p>
+          ) : (
+            <ul className="space-y-1">
+              {listenAddresses.map(address => (
+                <li key={address}>
+                  <code className="text-xs break-all select-all">{address}</code>
+                </li>
+              ))}
+            </ul>
+          )}
+        </CardContent>
+      </Card>
+    </>
   )
 }
 
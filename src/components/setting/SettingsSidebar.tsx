@@ -4,6 +4,7 @@ import {
   RefreshCw,
   Shield,
   Wifi,
+  Layers,
   HardDrive,
   Info,
   ArrowLeft,
@@ -59,6 +60,11 @@ const SettingsSidebar: React.FC<SettingsSidebarProps> = ({ activeCategory, onCat
       label: t('settings.categories.security'),
       icon: Shield,
     },
+    {
+      id: 'spaces',
+      label: t('settings.categories.spaces'),
+      icon: Layers,
+    },
     {
       id: 'network',
       label: t('settings.categories.network'),
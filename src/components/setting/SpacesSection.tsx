@@ -0,0 +1,254 @@
+import React, { useCallback, useEffect, useState } from 'react'
+import { useTranslation } from 'react-i18next'
+import { createSpace, listSpaces, switchSpace, type SpaceSummary } from '@/api/spaces'
+import {
+  Button,
+  Input,
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui'
+import { Card, CardContent } from '@/components/ui/card'
+import { useSetting } from '@/hooks/useSetting'
+import type { SpaceRoutingRule } from '@/types/setting'
+
+const SpacesSection: React.FC = () => {
+  const { t } = useTranslation()
+  const { setting, error, updateSetting } = useSetting()
+
+  const [spaces, setSpaces] = useState<SpaceSummary[]>([])
+  const [actionError, setActionError] = useState<string | null>(null)
+  const [busy, setBusy] = useState(false)
+
+  const [newName, setNewName] = useState('')
+  const [newPassphrase, setNewPassphrase] = useState('')
+
+  const [ruleSpaceId, setRuleSpaceId] = useState('')
+  const [ruleKeyword, setRuleKeyword] = useState('')
+
+  const routingRules = setting?.spaces?.routing_rules ?? []
+
+  const refreshSpaces = useCallback(async () => {
+    try {
+      setSpaces(await listSpaces())
+    } catch (err) {
+      setActionError(String(err))
+    }
+  }, [])
+
+  useEffect(() => {
+    void refreshSpaces()
+  }, [refreshSpaces])
+
+  const handleSwitch = async (spaceId: string) => {
+    setBusy(true)
+    try {
+      const unlocked = await switchSpace(spaceId)
+      setActionError(unlocked ? null : t('settings.sections.spaces.locked'))
+      await refreshSpaces()
+    } catch (err) {
+      setActionError(`${t('settings.sections.spaces.errors.switch')}: ${String(err)}`)
+    } finally {
+      setBusy(false)
+    }
+  }
+
+  // 新空间需要独立口令，创建后立即成为活动空间
+  const handleCreate = async () => {
+    if (!newName.trim() || !newPassphrase) return
+
+    setBusy(true)
+    try {
+      await createSpace(newName.trim(), newPassphrase)
+      setNewName('')
+      setNewPassphrase('')
+      setActionError(null)
+      await refreshSpaces()
+    } catch (err) {
+      setActionError(`${t('settings.sections.spaces.errors.create')}: ${String(err)}`)
+    } finally {
+      setBusy(false)
+    }
+  }
+
+  const saveRoutingRules = async (rules: SpaceRoutingRule[]) => {
+    if (!setting?.spaces) return
+    try {
+      await updateSetting({ ...setting, spaces: { ...setting.spaces, routing_rules: rules } })
+    } catch (err) {
+      setActionError(String(err))
+    }
+  }
+
+  const handleAddRule = () => {
+    const keyword = ruleKeyword.trim()
+    if (!ruleSpaceId || !keyword) return
+
+    void saveRoutingRules([
+      ...routingRules,
+      { space_id: ruleSpaceId, content_kinds: [], text_contains: keyword },
+    ])
+    setRuleKeyword('')
+  }
+
+  const spaceName = (spaceId: string) =>
+    spaces.find(space => space.id === spaceId)?.name ?? spaceId
+
+  if (error) {
+    return (
+      <div className="text-destructive py-4">
+        {t('settings.sections.sync.loadError')} {error}
+      </div>
+    )
+  }
+
+  return (
+    <>
+      {/* Space list */}
+      <Card>
+        <div className="flex items-center gap-4 mb-4 px-6 pt-6">
+          <h3 className="text-sm font-medium text-muted-foreground whitespace-nowrap">
+            {t('settings.sections.spaces.list.label')}
+          </h3>
+          <div className="h-px flex-1 bg-border/50"></div>
+        </div>
+        <CardContent className="pt-0 space-y-3">
+          <p className="text-sm text-muted-foreground">
+            {t('settings.sections.spaces.list.description')}
+          </p>
+          <ul className="space-y-2">
+            {spaces.map(space => (
+              <li key={space.id} className="flex items-center justify-between gap-2">
+                <span className="text-sm">{space.name}</span>
+                {space.is_active ? (
+                  <span className="text-xs text-green-600">
+                    {t('settings.sections.spaces.list.active')}
+                  </span>
+                ) : (
+                  <Button
+                    variant="outline"
+                    size="sm"
+                    disabled={busy}
+                    onClick={() => handleSwitch(space.id)}
+                  >
+                    {t('settings.sections.spaces.list.switch')}
+                  </Button>
+                )}
+              </li>
+            ))}
+          </ul>
+          {actionError && <p className="text-xs text-red-500">{actionError}</p>}
+        </CardContent>
+      </Card>
+
+      {/* Create space */}
+      <Card>
+        <div className="flex items-center gap-4 mb-4 px-6 pt-6">
+          <h3 className="text-sm font-medium text-muted-foreground whitespace-nowrap">
+            {t('settings.sections.spaces.create.label')}
+          </h3>
+          <div className="h-px flex-1 bg-border/50"></div>
+        </div>
+        <CardContent className="pt-0 space-y-3">
+          <p className="text-sm text-muted-foreground">
+            {t('settings.sections.spaces.create.description')}
+          </p>
+          <div className="flex items-center gap-2">
+            <Input
+              type="text"
+              value={newName}
+              placeholder={t('settings.sections.spaces.create.namePlaceholder')}
+              onChange={e => setNewName(e.target.value)}
+            />
+            <Input
+              type="password"
+              value={newPassphrase}
+              placeholder={t('settings.sections.spaces.create.passphrasePlaceholder')}
+              onChange={e => setNewPassphrase(e.target.value)}
+            />
+            <Button
+              onClick={handleCreate}
+              disabled={busy || !newName.trim() || !newPassphrase}
+              size="sm"
+            >
+              {t('settings.sections.spaces.create.button')}
+            </Button>
+          </div>
+        </CardContent>
+      </Card>
+
+      {/* Capture routing rules */}
+      <Card>
+        <div className="flex items-center gap-4 mb-4 px-6 pt-6">
+          <h3 className="text-sm font-medium text-muted-foreground whitespace-nowrap">
+            {t('settings.sections.spaces.routing.label')}
+          </h3>
+          <div className="h-px flex-1 bg-border/50"></div>
+        </div>
+        <CardContent className="pt-0 space-y-3">
+          <p className="text-sm text-muted-foreground">
+            {t('settings.sections.spaces.routing.description')}
+          </p>
+          {routingRules.length > 0 && (
+            <ul className="space-y-1">
+              {routingRules.map((rule, index) => (
+                <li
+                  key={`${rule.space_id}-${index}`}
+                  className="flex items-center justify-between gap-2 text-sm"
+                >
+                  <span className="break-all">
+                    {rule.text_contains ?? rule.content_kinds.join(', ')} →{' '}
+                    {spaceName(rule.space_id)}
+                  </span>
+                  <Button
+                    variant="ghost"
+                    size="sm"
+                    onClick={() =>
+                      void saveRoutingRules(routingRules.filter((_, i) => i !== index))
+                    }
+                  >
+                    {t('settings.sections.spaces.routing.remove')}
+                  </Button>
+                </li>
+              ))}
+            </ul>
+          )}
+          <div className="flex items-center gap-2">
+            <Input
+              type="text"
+              value={ruleKeyword}
+              placeholder={t('settings.sections.spaces.routing.keywordPlaceholder')}
+              onChange={e => setRuleKeyword(e.target.value)}
+            />
+            <Select value={ruleSpaceId} onValueChange={setRuleSpaceId}>
+              <SelectTrigger className="w-40">
+                <SelectValue placeholder={t('settings.sections.spaces.routing.selectSpace')} />
+              </SelectTrigger>
+              <SelectContent>
+                {/* 其他空间未解锁，规则只能指向活动空间 */}
+                {spaces
+                  .filter(space => space.is_active)
+                  .map(space => (
+                    <SelectItem key={space.id} value={space.id}>
+                      {space.name}
+                    </SelectItem>
+                  ))}
+              </SelectContent>
+            </Select>
+            <Button
+              onClick={handleAddRule}
+              disabled={!ruleSpaceId || !ruleKeyword.trim()}
+              size="sm"
+            >
+              {t('settings.sections.spaces.routing.add')}
+            </Button>
+          </div>
+        </CardContent>
+      </Card>
+    </>
+  )
+}
+
+export default SpacesSection
@@ -12,6 +12,30 @@ import {
 import { Card, CardContent } from '@/components/ui/card'
 import { useSetting } from '@/hooks/useSetting'
 
+// Interval options map to `sync_frequency: 'interval'` plus `sync_interval_secs`
+const INTERVAL_SECS_BY_OPTION: Record<string, number> = {
+  '30s': 30,
+  '1m': 60,
+  '5m': 5 * 60,
+  '15m': 15 * 60,
+}
+
+// Catch-up options map to `sync.catch_up_window_secs`; 0 turns catch-up off
+const CATCH_UP_SECS_BY_OPTION: Record<string, number> = {
+  off: 0,
+  '1h': 60 * 60,
+  '24h': 24 * 60 * 60,
+  '7d': 7 * 24 * 60 * 60,
+}
+
+const CATCH_UP_OPTION_BY_SECS: Record<number, string> = Object.fromEntries(
+  Object.entries(CATCH_UP_SECS_BY_OPTION).map(([option, secs]) => [secs, option])
+)
+
+const INTERVAL_OPTION_BY_SECS: Record<number, string> = Object.fromEntries(
+  Object.entries(INTERVAL_SECS_BY_OPTION).map(([option, secs]) => [secs, option])
+)
+
 const SyncSection: React.FC = () => {
   const { t } = useTranslation()
   // Use setting context
@@ -20,6 +44,7 @@ const SyncSection: React.FC = () => {
   // Local state for UI display
   const [autoSync, setAutoSync] = useState(true)
   const [syncFrequency, setSyncFrequency] = useState<string>('realtime')
+  const [catchUpWindow, setCatchUpWindow] = useState<string>('24h')
 
   const [maxFileSize, setMaxFileSize] = useState(10)
   const [maxFileSizeError, setMaxFileSizeError] = useState<string | null>(null)
@@ -33,11 +58,26 @@ const SyncSection: React.FC = () => {
     { value: '15m', label: t('settings.sections.sync.syncFrequency.15m') },
   ]
 
+  // Catch-up window options
+  const catchUpWindowOptions = [
+    { value: 'off', label: t('settings.sections.sync.catchUpWindow.off') },
+    { value: '1h', label: t('settings.sections.sync.catchUpWindow.1h') },
+    { value: '24h', label: t('settings.sections.sync.catchUpWindow.24h') },
+    { value: '7d', label: t('settings.sections.sync.catchUpWindow.7d') },
+  ]
+
   // Update local state when settings are loaded
   useEffect(() => {
     if (setting) {
       setAutoSync(setting.sync.auto_sync)
-      setSyncFrequency(setting.sync.sync_frequency)
+      setSyncFrequency(
+        setting.sync.sync_frequency === 'interval'
+          ? (INTERVAL_OPTION_BY_SECS[setting.sync.sync_interval_secs ?? 60] ?? '1m')
+          : 'realtime'
+      )
+      setCatchUpWindow(
+        CATCH_UP_OPTION_BY_SECS[setting.sync.catch_up_window_secs ?? 24 * 60 * 60] ?? '24h'
+      )
 
       setMaxFileSize(setting.sync.max_file_size_mb)
     }
@@ -52,10 +92,18 @@ const SyncSection: React.FC = () => {
   // Handle sync frequency change
   const handleSyncFrequencyChange = (value: string) => {
     setSyncFrequency(value)
-    // TODO: 后端 SyncFrequency 只支持 'realtime' | 'interval'
-    // UI 选项包括更多值 ('30s', '1m', '5m', '15m')，需要后续扩展后端类型
-    // 暂时使用类型断言让编译通过
-    updateSyncSetting({ sync_frequency: value as 'realtime' | 'interval' })
+    const intervalSecs = INTERVAL_SECS_BY_OPTION[value]
+    if (intervalSecs === undefined) {
+      updateSyncSetting({ sync_frequency: 'realtime' })
+    } else {
+      updateSyncSetting({ sync_frequency: 'interval', sync_interval_secs: intervalSecs })
+    }
+  }
+
+  // Handle catch-up window change
+  const handleCatchUpWindowChange = (value: string) => {
+    setCatchUpWindow(value)
+    updateSyncSetting({ catch_up_window_secs: CATCH_UP_SECS_BY_OPTION[value] ?? 0 })
   }
 
   // Handle max file size change
@@ -148,6 +196,35 @@ const SyncSection: React.FC = () => {
         </CardContent>
       </Card>
 
+      {/* Catch-up window selection */}
+      <Card>
+        <div className="flex items-center gap-4 mb-4 px-6 pt-6">
+          <h3 className="text-sm font-medium text-muted-foreground whitespace-nowrap">
+            {t('settings.sections.sync.catchUpWindow.label')}
+          </h3>
+          <div className="h-px flex-1 bg-border/50"></div>
+        </div>
+        <CardContent className="pt-0">
+          <div className="flex items-center justify-between gap-4 py-2">
+            <p className="text-sm text-muted-foreground">
+              {t('settings.sections.sync.catchUpWindow.description')}
+            </p>
+            <Select value={catchUpWindow} onValueChange={handleCatchUpWindowChange}>
+              <SelectTrigger className="w-52">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                {catchUpWindowOptions.map(option => (
+                  <SelectItem key={option.value} value={option.value}>
+                    {option.label}
+                  </SelectItem>
+                ))}
+              </SelectContent>
+            </Select>
+          </div>
+        </CardContent>
+      </Card>
+
       {/* Max file size input */}
       <Card>
         <div className="flex items-center gap-4 mb-4 px-6 pt-6">
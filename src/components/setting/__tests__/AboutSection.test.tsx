@@ -77,6 +77,7 @@ describe('AboutSection', () => {
           updateSyncSetting: vi.fn(),
           updateSecuritySetting: vi.fn(),
           updateRetentionPolicy: vi.fn(),
+          updateNetworkSetting: vi.fn(),
         }}
       >
         <UpdateContext.Provider
@@ -116,6 +117,7 @@ describe('AboutSection', () => {
           updateSyncSetting: vi.fn(),
           updateSecuritySetting: vi.fn(),
           updateRetentionPolicy: vi.fn(),
+          updateNetworkSetting: vi.fn(),
         }}
       >
         <UpdateContext.Provider
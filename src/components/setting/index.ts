@@ -1,5 +1,6 @@
 export { default as SyncSection } from './SyncSection'
 export { default as SecuritySection } from './SecuritySection'
+export { default as SpacesSection } from './SpacesSection'
 export { default as NetworkSection } from './NetworkSection'
 export { default as StorageSection } from './StorageSection'
 export { default as AboutSection } from './AboutSection'
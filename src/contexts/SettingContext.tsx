@@ -5,7 +5,9 @@ import { DEFAULT_THEME_COLOR } from '@/constants/theme'
 import i18n, { normalizeLanguage, persistLanguage } from '@/i18n'
 import { invokeWithTrace } from '@/lib/tauri-command'
 import type { SettingChangedEvent } from '@/types/events'
-import type { SettingContextType, Settings } from '@/types/setting'
+import type { NetworkSettings, SettingContextType, Settings } from '@/types/setting'
+
+const DEFAULT_NETWORK_SETTINGS: NetworkSettings = { listen_port: 0, bootstrap_peers: [] }
 
 // 设置提供者属性接口
 interface SettingProviderProps {
@@ -107,6 +109,20 @@ export const SettingProvider: React.FC<SettingProviderProps> = ({ children }) =>
     await saveSetting(updatedSetting)
   }
 
+  // 更新网络设置
+  const updateNetworkSetting = async (newNetworkSetting: Partial<NetworkSettings>) => {
+    if (!setting) return
+    const updatedSetting: Settings = {
+      ...setting,
+      network: {
+        ...DEFAULT_NETWORK_SETTINGS,
+        ...setting.network,
+        ...newNetworkSetting,
+      },
+    }
+    await saveSetting(updatedSetting)
+  }
+
   // Load settings immediately on mount
   useEffect(() => {
     void loadSetting()
@@ -201,6 +217,7 @@ export const SettingProvider: React.FC<SettingProviderProps> = ({ children }) =>
     updateSyncSetting,
     updateSecuritySetting,
     updateRetentionPolicy,
+    updateNetworkSetting,
   }
 
   return <SettingContext.Provider value={value}>{children}</SettingContext.Provider>
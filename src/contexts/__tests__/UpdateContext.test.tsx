@@ -92,6 +92,7 @@ describe('UpdateProvider', () => {
           updateSyncSetting: vi.fn(),
           updateSecuritySetting: vi.fn(),
           updateRetentionPolicy: vi.fn(),
+          updateNetworkSetting: vi.fn(),
         }}
       >
         <UpdateProvider>
@@ -117,6 +118,7 @@ describe('UpdateProvider', () => {
           updateSyncSetting: vi.fn(),
           updateSecuritySetting: vi.fn(),
           updateRetentionPolicy: vi.fn(),
+          updateNetworkSetting: vi.fn(),
         }}
       >
         <UpdateProvider>
@@ -150,6 +152,7 @@ describe('UpdateProvider', () => {
           updateSyncSetting: vi.fn(),
           updateSecuritySetting: vi.fn(),
           updateRetentionPolicy: vi.fn(),
+          updateNetworkSetting: vi.fn(),
         }}
       >
         <UpdateProvider>
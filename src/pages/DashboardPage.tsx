@@ -172,6 +172,11 @@ const DashboardPage: React.FC = () => {
 
               // Use debounced function to load data
               debouncedLoadData(currentFilterRef.current)
+            } else if (event.payload.type === 'Expired' && event.payload.count) {
+              toast.info(
+                t('dashboard.notifications.itemsExpired', { count: event.payload.count })
+              )
+              debouncedLoadData(currentFilterRef.current)
             }
           })
 
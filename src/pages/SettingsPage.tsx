@@ -6,6 +6,7 @@ import GeneralSection from '@/components/setting/GeneralSection'
 import NetworkSection from '@/components/setting/NetworkSection'
 import SecuritySection from '@/components/setting/SecuritySection'
 import SettingsSidebar from '@/components/setting/SettingsSidebar'
+import SpacesSection from '@/components/setting/SpacesSection'
 import StorageSection from '@/components/setting/StorageSection'
 import SyncSection from '@/components/setting/SyncSection'
 import { ScrollArea } from '@/components/ui/scroll-area'
@@ -66,6 +67,12 @@ const SettingsPage: React.FC = () => {
             <SecuritySection />
           </SettingContentLayout>
         )
+      case 'spaces':
+        return (
+          <SettingContentLayout>
+            <SpacesSection />
+          </SettingContentLayout>
+        )
       case 'network':
         return (
           <SettingContentLayout>
@@ -190,7 +190,8 @@ const clipboardSlice = createSlice({
 
     // 处理清空剪贴板
     builder.addCase(clearAllItems.fulfilled, state => {
-      state.items = []
+      // Favorited (pinned) entries survive a clear-all
+      state.items = state.items.filter(item => item.is_favorited)
     })
     builder.addCase(clearAllItems.rejected, (state, action) => {
       state.error = action.payload as string
@@ -67,6 +67,10 @@ const devicesSlice = createSlice({
       const peer = state.pairedDevices.find(d => d.peerId === action.payload.peerId)
       if (peer) {
         peer.connected = action.payload.connected
+        if (!action.payload.connected && peer.presence === 'online') {
+          peer.presence = 'idle'
+          peer.latencyMs = null
+        }
         if (action.payload.deviceName) {
           peer.deviceName = action.payload.deviceName
         }
@@ -78,6 +82,19 @@ const devicesSlice = createSlice({
         peer.deviceName = action.payload.deviceName
       }
     },
+    updatePeerHeartbeat: (
+      state,
+      action: { payload: { peerId: string; rttMs?: number | null; seenAt: string } }
+    ) => {
+      const peer = state.pairedDevices.find(d => d.peerId === action.payload.peerId)
+      if (peer) {
+        peer.presence = 'online'
+        peer.lastSeen = action.payload.seenAt
+        if (action.payload.rttMs != null) {
+          peer.latencyMs = action.payload.rttMs
+        }
+      }
+    },
   },
   extraReducers: builder => {
     // Local device info
@@ -117,5 +134,6 @@ export const {
   clearPairedDevicesError,
   updatePeerConnectionStatus,
   updatePeerDeviceName,
+  updatePeerHeartbeat,
 } = devicesSlice.actions
 export default devicesSlice.reducer
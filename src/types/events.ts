@@ -2,9 +2,11 @@
  * Clipboard events from backend
  */
 export interface ClipboardEvent {
-  type: 'NewContent' | 'Deleted'
+  type: 'NewContent' | 'Deleted' | 'Expired'
   entry_id?: string
   preview?: string
+  count?: number
+  entry_ids?: string[]
 }
 
 /**
@@ -38,6 +38,11 @@ export interface ContentTypes {
  */
 export type SyncFrequency = 'realtime' | 'interval'
 
+/**
+ * 间隔同步合并方式 - 对应 Rust IntervalBatchMode enum
+ */
+export type IntervalBatchMode = 'latest_only' | 'all'
+
 /**
  * 同步设置 - 对应 Rust SyncSettings
  */
@@ -46,6 +51,10 @@ export interface SyncSettings {
   sync_frequency: SyncFrequency
   content_types: ContentTypes
   max_file_size_mb: number
+  apply_remote_to_clipboard?: boolean
+  sync_interval_secs?: number
+  interval_batch_mode?: IntervalBatchMode
+  catch_up_window_secs?: number
 }
 
 /**
@@ -70,6 +79,11 @@ export type RetentionRule =
  */
 export type RuleEvaluation = 'any_match' | 'all_match'
 
+/**
+ * 保留策略删除方式 - 对应 Rust RetentionDeleteMode enum
+ */
+export type RetentionDeleteMode = 'soft' | 'hard'
+
 /**
  * 保留策略 - 对应 Rust RetentionPolicy
  */
@@ -78,6 +92,7 @@ export interface RetentionPolicy {
   rules: RetentionRule[]
   skip_pinned: boolean
   evaluation: RuleEvaluation
+  delete_mode?: RetentionDeleteMode
 }
 
 /**
@@ -100,6 +115,76 @@ export interface PairingSettings {
   protocol_version: string
 }
 
+/**
+ * 网络设置 - 对应 Rust NetworkSettings
+ */
+export interface NetworkSettings {
+  /** 固定监听端口，0 表示随机 */
+  listen_port: number
+  /** 手动添加的对端地址（multiaddr） */
+  bootstrap_peers: string[]
+  /** 仅使用 TCP（关闭 QUIC），重启后生效 */
+  tcp_only?: boolean
+  /** 允许监听的接口（接口名或 CIDR），为空表示全部 */
+  allowed_interfaces?: string[]
+  /** 禁止监听的接口（接口名或 CIDR） */
+  denied_interfaces?: string[]
+  /** 中继与 rendezvous，用于跨网络同步，重启后生效 */
+  relay?: RelaySettings
+}
+
+/**
+ * 中继设置 - 对应 Rust RelaySettings
+ */
+export interface RelaySettings {
+  enabled: boolean
+  /** 中继节点 multiaddr，需以 /p2p/<peer id> 结尾 */
+  address: string
+  /** rendezvous 命名空间，为空时使用默认值 */
+  namespace: string
+}
+
+/**
+ * 内容类别 - 对应 Rust ContentKind enum
+ */
+export type ContentKind =
+  | 'text'
+  | 'rich_text'
+  | 'image'
+  | 'link'
+  | 'file'
+  | 'code_snippet'
+  | 'unknown'
+
+/**
+ * 空间 - 对应 Rust SpaceProfile
+ */
+export interface SpaceProfile {
+  id: string
+  name: string
+  created_at_ms: number
+}
+
+/**
+ * 捕获路由规则 - 对应 Rust SpaceRoutingRule
+ * 所有给出的条件都匹配时，捕获内容进入 space_id 指向的空间
+ */
+export interface SpaceRoutingRule {
+  space_id: string
+  content_kinds: ContentKind[]
+  text_contains?: string | null
+}
+
+/**
+ * 空间设置 - 对应 Rust SpaceSettings
+ * 活动空间与空间列表由空间命令维护，保存设置时不会被覆盖
+ */
+export interface SpaceSettings {
+  active_space_id: string
+  spaces: SpaceProfile[]
+  routing_rules: SpaceRoutingRule[]
+}
+
 /**
  * 应用设置 - 对应 Rust Settings
  */
@@ -110,6 +195,8 @@ export interface Settings {
   retention_policy: RetentionPolicy
   security: SecuritySettings
   pairing: PairingSettings
+  network?: NetworkSettings
+  spaces?: SpaceSettings
 }
 
 // ============================================================================
@@ -167,6 +254,7 @@ export interface SettingContextType {
   updateSyncSetting: (newSyncSetting: Partial<SyncSettings>) => Promise<void>
   updateSecuritySetting: (newSecuritySetting: Partial<SecuritySettings>) => Promise<void>
   updateRetentionPolicy: (newPolicy: Partial<RetentionPolicy>) => Promise<void>
+  updateNetworkSetting: (newNetworkSetting: Partial<NetworkSettings>) => Promise<void>
 }
 
 // ============================================================================